@@ -0,0 +1,81 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Generates the protobuf messages of `proto/relationship.proto` for the
+//! `protobuf` feature, and with `grpc` the tonic client and server of
+//! `proto/relationship_service.proto` next to them. Both files share the
+//! `cim.relationship.v1` package, so they are generated into one module.
+//!
+//! `protoc` is run when found on the `PATH` or named by the `PROTOC`
+//! environment variable; without it protox parses the files instead.
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("cargo:rerun-if-changed=proto");
+    println!("cargo:rerun-if-env-changed=PROTOC");
+    #[cfg(feature = "protobuf")]
+    protobuf::generate()?;
+    Ok(())
+}
+
+#[cfg(feature = "protobuf")]
+mod protobuf {
+    use std::error::Error;
+    use std::path::PathBuf;
+    use std::process::Command;
+
+    const INCLUDES: &[&str] = &["proto"];
+
+    #[cfg(not(feature = "grpc"))]
+    const PROTOS: &[&str] = &["proto/relationship.proto"];
+
+    #[cfg(feature = "grpc")]
+    const PROTOS: &[&str] = &["proto/relationship.proto", "proto/relationship_service.proto"];
+
+    pub fn generate() -> Result<(), Box<dyn Error>> {
+        let descriptors = if protoc_available() {
+            None
+        } else {
+            Some(parse_with_protox()?)
+        };
+        compile(descriptors)
+    }
+
+    /// Messages only
+    #[cfg(not(feature = "grpc"))]
+    fn compile(descriptors: Option<PathBuf>) -> Result<(), Box<dyn Error>> {
+        let mut config = prost_build::Config::new();
+        if let Some(path) = descriptors {
+            config.file_descriptor_set_path(path).skip_protoc_run();
+        }
+        config.compile_protos(PROTOS, INCLUDES)?;
+        Ok(())
+    }
+
+    /// Messages, client and server
+    #[cfg(feature = "grpc")]
+    fn compile(descriptors: Option<PathBuf>) -> Result<(), Box<dyn Error>> {
+        let mut builder = tonic_build::configure();
+        if let Some(path) = descriptors {
+            builder = builder.file_descriptor_set_path(path).skip_protoc_run();
+        }
+        builder.compile_protos(PROTOS, INCLUDES)?;
+        Ok(())
+    }
+
+    /// Descriptors as protoc would write them, for generators told to skip it
+    fn parse_with_protox() -> Result<PathBuf, Box<dyn Error>> {
+        let mut compiler = protox::Compiler::new(INCLUDES)?;
+        compiler
+            .include_imports(true)
+            .include_source_info(true)
+            .open_files(PROTOS)?;
+        let path = PathBuf::from(std::env::var("OUT_DIR")?).join("relationship_descriptors.bin");
+        std::fs::write(&path, compiler.encode_file_descriptor_set())?;
+        Ok(path)
+    }
+
+    fn protoc_available() -> bool {
+        std::env::var_os("PROTOC").is_some() || Command::new("protoc").arg("--version").output().is_ok()
+    }
+}
@@ -0,0 +1,186 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Batch Commands
+//!
+//! Imports create thousands of relationships at once. A batch decides its
+//! items in order against a scratch copy of the space, so every item is
+//! checked by the same rules as a single command and against the items
+//! before it:
+//!
+//! ```text
+//! AllOrNothing   item 0 ok, item 1 ok, item 2 refused   ==>  BatchItemRejected { index: 2 }
+//! Partial        item 0 ok, item 1 ok, item 2 refused   ==>  events of 0 and 1
+//!                                                            results [ok, ok, refused]
+//! ```
+//!
+//! `handle_command` returns only the events; `handle_batch` also reports
+//! the outcome of every item.
+
+use super::RelationshipSpace;
+use crate::commands::{AddParticipant, BatchMode, CreateEdge, EdgeCommand, HyperEdgeCommand, RelationshipCommand};
+use crate::events::RelationshipEvent;
+use crate::value_objects::RelationshipId;
+use crate::{RelationshipError, RelationshipResult};
+use serde::{Deserialize, Serialize};
+
+/// Outcome of one batch item
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatchItemResult {
+    /// Position of the item in the batch
+    pub index: usize,
+    /// Relationship the item targets
+    pub relationship_id: RelationshipId,
+    /// Why the item was refused (`None` = applied)
+    pub error: Option<String>,
+}
+
+/// Events and per-item results of a batch
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BatchOutcome {
+    /// Events of the applied items, in item order
+    pub events: Vec<RelationshipEvent>,
+    /// One result per item
+    pub results: Vec<BatchItemResult>,
+}
+
+impl BatchOutcome {
+    /// Items that were applied
+    pub fn applied(&self) -> impl Iterator<Item = &BatchItemResult> {
+        self.results.iter().filter(|r| r.error.is_none())
+    }
+
+    /// Items that were refused
+    pub fn refused(&self) -> impl Iterator<Item = &BatchItemResult> {
+        self.results.iter().filter(|r| r.error.is_some())
+    }
+}
+
+impl RelationshipSpace {
+    /// Decide a batch command, reporting the outcome of every item
+    pub fn handle_batch(&self, cmd: RelationshipCommand) -> RelationshipResult<BatchOutcome> {
+        match cmd {
+            RelationshipCommand::CreateEdgesBatch(c) => {
+                let items = c.edges.into_iter().map(|item| {
+                    let create = EdgeCommand::CreateEdge(CreateEdge {
+                        identity: c.identity,
+                        edge_id: item.edge_id,
+                        source: item.source,
+                        target: item.target,
+                        category: item.category,
+                        name: item.name,
+                        quality: item.quality,
+                        created_by: c.created_by.clone(),
+                    });
+                    (item.edge_id, RelationshipCommand::from(create))
+                });
+                self.decide_items(c.mode, items)
+            }
+            RelationshipCommand::AddParticipantsBatch(c) => {
+                let items = c.participants.into_iter().map(|item| {
+                    let add = HyperEdgeCommand::AddParticipant(AddParticipant {
+                        identity: c.identity,
+                        hyperedge_id: item.hyperedge_id,
+                        participant: item.participant,
+                        role: item.role,
+                        weight: item.weight,
+                        added_by: c.added_by.clone(),
+                    });
+                    (item.hyperedge_id, RelationshipCommand::from(add))
+                });
+                self.decide_items(c.mode, items)
+            }
+            cmd => Err(RelationshipError::InvalidRelationship(format!(
+                "{} is not a batch command",
+                cmd.command_type()
+            ))),
+        }
+    }
+
+    fn decide_items(
+        &self,
+        mode: BatchMode,
+        items: impl Iterator<Item = (RelationshipId, RelationshipCommand)>,
+    ) -> RelationshipResult<BatchOutcome> {
+        let mut scratch = self.clone();
+        let mut outcome = BatchOutcome::default();
+        for (index, (relationship_id, cmd)) in items.enumerate() {
+            let decided = scratch.handle_command(cmd).and_then(|events| {
+                events.iter().try_for_each(|event| scratch.apply_event(event))?;
+                Ok(events)
+            });
+            let error = match decided {
+                Ok(events) => {
+                    outcome.events.extend(events);
+                    None
+                }
+                Err(e) if mode == BatchMode::AllOrNothing => {
+                    return Err(RelationshipError::BatchItemRejected {
+                        index,
+                        reason: e.to_string(),
+                    })
+                }
+                Err(e) => Some(e.to_string()),
+            };
+            outcome.results.push(BatchItemResult {
+                index,
+                relationship_id,
+                error,
+            });
+        }
+        Ok(outcome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{BatchEdge, CreateEdgesBatch};
+    use crate::value_objects::{EntityRef, RelationshipCategory};
+    use cim_domain::MessageIdentity;
+    use cim_domain_spaces::TopologicalSpaceId;
+    use uuid::Uuid;
+
+    fn batch(edges: Vec<BatchEdge>, mode: BatchMode) -> RelationshipCommand {
+        RelationshipCommand::CreateEdgesBatch(CreateEdgesBatch {
+            identity: MessageIdentity::new_root(),
+            edges,
+            mode,
+            created_by: "import".to_string(),
+        })
+    }
+
+    #[test]
+    fn test_create_edges_batch() {
+        let space = RelationshipSpace::new("Import", TopologicalSpaceId::new());
+        let people: Vec<_> = (0..4).map(|_| EntityRef::person(Uuid::now_v7())).collect();
+        let edge = |source: usize, target: usize| BatchEdge {
+            edge_id: RelationshipId::new(),
+            source: people[source].clone(),
+            target: people[target].clone(),
+            category: RelationshipCategory::Friendship,
+            name: "friends".to_string(),
+            quality: None,
+        };
+        let mut edges = vec![edge(0, 1), edge(1, 2), edge(3, 3), edge(2, 3)];
+        // A batch may not create an edge twice
+        edges.push(edges[0].clone());
+
+        let err = space.handle_batch(batch(edges.clone(), BatchMode::AllOrNothing)).unwrap_err();
+        assert!(matches!(err, RelationshipError::BatchItemRejected { index: 2, .. }));
+
+        let outcome = space.handle_batch(batch(edges.clone(), BatchMode::Partial)).unwrap();
+        let refused: Vec<_> = outcome.refused().map(|r| r.index).collect();
+        assert_eq!(refused, vec![2, 4]);
+        assert_eq!(outcome.applied().count(), 3);
+        assert_eq!(outcome.results[4].relationship_id, edges[0].edge_id);
+
+        // Deciding is pure; the events build the edges
+        assert_eq!(space.edges.len(), 0);
+        let mut space = space;
+        let events = space.handle_command(batch(edges, BatchMode::Partial)).unwrap();
+        events.iter().try_for_each(|event| space.apply_event(event)).unwrap();
+        assert_eq!(space.edges.len(), 3);
+    }
+}
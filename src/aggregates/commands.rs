@@ -0,0 +1,803 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Command Handling for a RelationshipSpace
+//!
+//! Edge and hyperedge commands are decided by the aggregate they target.
+//! Restructuring commands replace relationships with differently shaped
+//! ones and so span aggregates; the space decides them (see `restructure`).
+//!
+//! ## Policies
+//!
+//! `EnactPolicy` and `RevokePolicy` change the constraints in force
+//! (`PolicyEnacted` / `PolicyRevoked`). A Policy entity may issue several
+//! constraints; revoking it withdraws them all.
+//!
+//! Deciding is pure: apply the returned events with
+//! `RelationshipSpace::apply_event` to change the space.
+
+use super::{check_name, EdgeConcept, EdgeState, HyperEdgeConcept, HyperEdgeState, RelationshipSpace};
+use crate::commands::{EdgeCommand, EnactPolicy, HyperEdgeCommand, RelationshipCommand, RestructureCommand, RevokePolicy};
+use crate::events::{HyperEdgeEvent, PolicyEnacted, PolicyEvent, PolicyRevoked, RelationshipEvent};
+use crate::value_objects::EntityType;
+use crate::{RelationshipError, RelationshipResult};
+use chrono::Utc;
+use cim_domain::MessageIdentity;
+use uuid::Uuid;
+
+impl RelationshipSpace {
+    /// Decide the events a command produces (pure functional)
+    ///
+    /// Commands for relationships not in this space fail with
+    /// `EntityNotFound`, except the ones creating them. Edges are checked
+    /// against the space's rules as the command would leave them (edges of
+    /// consent categories cannot skip consent, rejected edges are
+    /// re-proposed within the space's policy), against the policies in
+    /// force when they are created, (re)activated or relinked, and
+    /// hyperedges against their role schema when activated, when the
+    /// participants of a live hyperedge change, or when restructuring
+    /// completes. Active hyperedges of restructuring categories refuse
+    /// participant changes.
+    pub fn handle_command(&self, cmd: RelationshipCommand) -> RelationshipResult<Vec<RelationshipEvent>> {
+        match cmd {
+            RelationshipCommand::Edge(cmd) => {
+                let events = match (self.get_edge(&cmd.edge_id()), &cmd) {
+                    (Some(edge), _) => {
+                        let events = edge.handle_command(cmd)?;
+                        let next = events.iter().try_fold(edge.clone(), |e, event| e.apply_event_pure(event))?;
+                        if edge.state == EdgeState::Proposed
+                            && next.state == EdgeState::Active
+                            && self.requires_consent(&edge.category)
+                        {
+                            return Err(RelationshipError::InvalidStateTransition(format!(
+                                "{} edges need the consent of both endpoints",
+                                edge.category.display_name()
+                            )));
+                        }
+                        self.check_edge(&next)?;
+                        let relinked = !next.source.same_entity(&edge.source)
+                            || !next.target.same_entity(&edge.target)
+                            || next.category != edge.category;
+                        let revived = next.state != edge.state
+                            && matches!(
+                                next.state,
+                                EdgeState::Proposed | EdgeState::PendingConsent | EdgeState::Active
+                            );
+                        if relinked || revived {
+                            self.check_policies(&next)?;
+                        }
+                        events
+                    }
+                    (None, EdgeCommand::CreateEdge(c)) => {
+                        let blank =
+                            EdgeConcept::new(c.name.clone(), c.source.clone(), c.target.clone(), c.category.clone());
+                        self.check_edge(&blank)?;
+                        self.check_policies(&blank)?;
+                        blank.handle_command(cmd)?
+                    }
+                    (None, _) => return Err(RelationshipError::EntityNotFound(cmd.edge_id().to_string())),
+                };
+                Ok(events.into_iter().map(RelationshipEvent::from).collect())
+            }
+            RelationshipCommand::HyperEdge(cmd) => {
+                let events = match (self.get_hyperedge(&cmd.hyperedge_id()), &cmd) {
+                    (Some(hyperedge), _) => {
+                        let events = hyperedge.handle_command(cmd)?;
+                        if hyperedge.state == HyperEdgeState::Active
+                            && self.requires_restructuring(&hyperedge.category)
+                            && events.iter().any(changes_participants)
+                        {
+                            return Err(RelationshipError::InvalidStateTransition(format!(
+                                "{} hyperedges change participants only while restructuring",
+                                hyperedge.category.display_name()
+                            )));
+                        }
+                        if events.iter().any(changes_roles) {
+                            let next = events
+                                .iter()
+                                .try_fold(hyperedge.clone(), |h, event| h.apply_event_pure(event))?;
+                            // Constraints may break mid-restructuring
+                            if !matches!(next.state, HyperEdgeState::Forming | HyperEdgeState::Restructuring) {
+                                self.check_roles(&next)?;
+                            }
+                        }
+                        events
+                    }
+                    (None, HyperEdgeCommand::CreateHyperEdge(c)) => {
+                        HyperEdgeConcept::new(c.name.clone(), c.category.clone()).handle_command(cmd)?
+                    }
+                    (None, _) => return Err(RelationshipError::EntityNotFound(cmd.hyperedge_id().to_string())),
+                };
+                Ok(events.into_iter().map(RelationshipEvent::from).collect())
+            }
+            RelationshipCommand::Restructure(RestructureCommand::PromoteEdgeToHyperEdge(c)) => self.promote_edge(c),
+            RelationshipCommand::Restructure(RestructureCommand::MergeEdges(c)) => self.merge_edges(c),
+            RelationshipCommand::Restructure(RestructureCommand::DecomposeHyperEdge(c)) => self.decompose_hyperedge(c),
+            RelationshipCommand::CreateFromTemplate(c) => self.create_from_template(c),
+            cmd @ (RelationshipCommand::CreateEdgesBatch(_) | RelationshipCommand::AddParticipantsBatch(_)) => {
+                Ok(self.handle_batch(cmd)?.events)
+            }
+            RelationshipCommand::ArchiveRelationship(c) => {
+                let cmd = if self.get_edge(&c.relationship_id).is_some() {
+                    RelationshipCommand::from(EdgeCommand::ArchiveEdge(c))
+                } else {
+                    RelationshipCommand::from(HyperEdgeCommand::ArchiveHyperEdge(c))
+                };
+                self.handle_command(cmd)
+            }
+            RelationshipCommand::RestoreRelationship(c) => {
+                let cmd = if self.get_edge(&c.relationship_id).is_some() {
+                    RelationshipCommand::from(EdgeCommand::RestoreEdge(c))
+                } else {
+                    RelationshipCommand::from(HyperEdgeCommand::RestoreHyperEdge(c))
+                };
+                self.handle_command(cmd)
+            }
+            RelationshipCommand::RenameRelationship(c) => {
+                let cmd = if self.get_edge(&c.relationship_id).is_some() {
+                    RelationshipCommand::from(EdgeCommand::RenameEdge(c))
+                } else {
+                    RelationshipCommand::from(HyperEdgeCommand::RenameHyperEdge(c))
+                };
+                self.handle_command(cmd)
+            }
+            RelationshipCommand::UpdateDescription(c) => {
+                let cmd = if self.get_edge(&c.relationship_id).is_some() {
+                    RelationshipCommand::from(EdgeCommand::DescribeEdge(c))
+                } else {
+                    RelationshipCommand::from(HyperEdgeCommand::DescribeHyperEdge(c))
+                };
+                self.handle_command(cmd)
+            }
+            RelationshipCommand::EnactPolicy(c) => self.enact_policy(c),
+            RelationshipCommand::RevokePolicy(c) => self.revoke_policy(c),
+        }
+    }
+
+    fn enact_policy(&self, c: EnactPolicy) -> RelationshipResult<Vec<RelationshipEvent>> {
+        if c.policy.policy.entity_type != EntityType::Policy {
+            return Err(RelationshipError::InvalidRelationship(format!(
+                "{} is not a Policy entity",
+                c.policy.policy
+            )));
+        }
+        let mut policy = c.policy;
+        policy.name = check_name(&policy.name)?;
+        Ok(vec![RelationshipEvent::from(PolicyEvent::PolicyEnacted(PolicyEnacted {
+            event_id: Uuid::now_v7(),
+            identity: MessageIdentity::new_caused_by(&c.identity),
+            policy,
+            enacted_by: c.enacted_by,
+            enacted_at: Utc::now(),
+        }))])
+    }
+
+    fn revoke_policy(&self, c: RevokePolicy) -> RelationshipResult<Vec<RelationshipEvent>> {
+        if !self.policies.policies.iter().any(|p| p.policy.same_entity(&c.policy)) {
+            return Err(RelationshipError::EntityNotFound(c.policy.to_string()));
+        }
+        Ok(vec![RelationshipEvent::from(PolicyEvent::PolicyRevoked(PolicyRevoked {
+            event_id: Uuid::now_v7(),
+            identity: MessageIdentity::new_caused_by(&c.identity),
+            policy: c.policy,
+            reason: c.reason,
+            revoked_by: c.revoked_by,
+            revoked_at: Utc::now(),
+        }))])
+    }
+
+    /// Decide a command and apply its events to this space, collecting them
+    ///
+    /// For operations made of several commands, run against a copy of the
+    /// space so each step sees the ones before it.
+    pub(crate) fn run_command(
+        &mut self,
+        cmd: impl Into<RelationshipCommand>,
+        events: &mut Vec<RelationshipEvent>,
+    ) -> RelationshipResult<()> {
+        for event in self.handle_command(cmd.into())? {
+            self.apply_event(&event)?;
+            events.push(event);
+        }
+        Ok(())
+    }
+}
+
+/// Check if an event can leave a hyperedge's roles unfilled or overfilled
+fn changes_roles(event: &HyperEdgeEvent) -> bool {
+    matches!(
+        event,
+        HyperEdgeEvent::HyperEdgeActivated(_)
+            | HyperEdgeEvent::RestructuringCompleted(_)
+            | HyperEdgeEvent::ParticipantAdded(_)
+            | HyperEdgeEvent::ParticipantRemoved(_)
+            | HyperEdgeEvent::ParticipantRoleChanged(_)
+    )
+}
+
+fn changes_participants(event: &HyperEdgeEvent) -> bool {
+    matches!(
+        event,
+        HyperEdgeEvent::ParticipantAdded(_)
+            | HyperEdgeEvent::ParticipantRemoved(_)
+            | HyperEdgeEvent::ParticipantRoleChanged(_)
+            | HyperEdgeEvent::ParticipantWeightChanged(_)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::DecomposeHyperEdge;
+    use crate::value_objects::{EntityRef, ParticipantRole, RelationshipCategory, RelationshipId};
+    use cim_domain::state_machine::State;
+    use cim_domain_spaces::TopologicalSpaceId;
+
+    #[test]
+    fn test_reverse_dependency() {
+        let (app, lib, db) = (
+            EntityRef::concept(Uuid::now_v7()),
+            EntityRef::concept(Uuid::now_v7()),
+            EntityRef::concept(Uuid::now_v7()),
+        );
+        let mut space = RelationshipSpace::new("Build", TopologicalSpaceId::new());
+        // Recorded the wrong way round
+        let wrong = EdgeConcept::new("Depends", lib.clone(), app.clone(), RelationshipCategory::DependsOn);
+        let wrong_id = wrong.id;
+        space.add_edge(wrong).unwrap();
+        space
+            .add_edge(EdgeConcept::new("Depends", lib.clone(), db.clone(), RelationshipCategory::DependsOn))
+            .unwrap();
+        let app_db = EdgeConcept::new("Depends", app.clone(), db, RelationshipCategory::DependsOn);
+        let app_db_id = app_db.id;
+        space.add_edge(app_db).unwrap();
+
+        let reverse = |edge_id| {
+            RelationshipCommand::from(EdgeCommand::ReverseEdge(crate::commands::ReverseEdge {
+                identity: MessageIdentity::new_root(),
+                edge_id,
+                invert_category: false,
+                reason: "direction".to_string(),
+                reversed_by: "test".to_string(),
+            }))
+        };
+        // The edge itself does not count as a path back
+        for event in space.handle_command(reverse(wrong_id)).unwrap() {
+            space.apply_event(&event).unwrap();
+        }
+        let fixed = space.get_edge(&wrong_id).unwrap();
+        assert_eq!((&fixed.source, &fixed.target), (&app, &lib));
+        assert!(space.dependency_cycles().is_empty());
+
+        // db -> app -> lib -> db
+        assert!(matches!(
+            space.handle_command(reverse(app_db_id)),
+            Err(RelationshipError::CyclicDependency(_))
+        ));
+    }
+
+    #[test]
+    fn test_role_schema_enforced() {
+        use crate::commands::{ActivateHyperEdge, RemoveParticipant};
+        use crate::invariants::{RoleConstraint, RoleSchema};
+
+        let category = RelationshipCategory::Custom("DocumentCollaboration".to_string());
+        let (author, reviewer, approver, editor) = (
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::person(Uuid::now_v7()),
+        );
+        let mut doc = HyperEdgeConcept::new("Spec", category.clone());
+        doc.add_participant(author.clone(), ParticipantRole::Author, 1.0).unwrap();
+        doc.add_participant(editor.clone(), ParticipantRole::Author, 1.0).unwrap();
+        doc.add_participant(reviewer.clone(), ParticipantRole::Reviewer, 1.0).unwrap();
+        let doc_id = doc.id;
+        let mut space = RelationshipSpace::new("Docs", TopologicalSpaceId::new()).with_role_schema(
+            category,
+            RoleSchema::new()
+                .with_constraint(RoleConstraint::at_least(ParticipantRole::Author, 1))
+                .with_constraint(RoleConstraint::at_least(ParticipantRole::Reviewer, 1))
+                .with_constraint(RoleConstraint::exactly(ParticipantRole::Approver, 1)),
+        );
+        space.add_hyperedge(doc);
+
+        let activate = || {
+            RelationshipCommand::from(HyperEdgeCommand::ActivateHyperEdge(ActivateHyperEdge {
+                identity: MessageIdentity::new_root(),
+                hyperedge_id: doc_id,
+                activated_by: "test".to_string(),
+            }))
+        };
+        let remove = |participant: &EntityRef| {
+            RelationshipCommand::from(HyperEdgeCommand::RemoveParticipant(RemoveParticipant {
+                identity: MessageIdentity::new_root(),
+                hyperedge_id: doc_id,
+                participant: participant.clone(),
+                reason: "left".to_string(),
+                removed_by: "test".to_string(),
+            }))
+        };
+
+        // No approver yet
+        match space.handle_command(activate()) {
+            Err(RelationshipError::RoleConstraintViolated(violations)) => {
+                assert_eq!(violations.len(), 1);
+                assert_eq!(violations[0].role, ParticipantRole::Approver);
+                assert_eq!(violations[0].actual, 0);
+            }
+            other => panic!("expected a role violation, got {other:?}"),
+        }
+
+        space
+            .hyperedges
+            .get_mut(&doc_id)
+            .unwrap()
+            .add_participant(approver, ParticipantRole::Approver, 1.0)
+            .unwrap();
+        for event in space.handle_command(activate()).unwrap() {
+            space.apply_event(&event).unwrap();
+        }
+
+        // One of two authors may leave; the only reviewer may not
+        assert!(space.handle_command(remove(&editor)).is_ok());
+        assert!(matches!(
+            space.handle_command(remove(&reviewer)),
+            Err(RelationshipError::RoleConstraintViolated(_))
+        ));
+    }
+
+    #[test]
+    fn test_consent_required() {
+        use crate::commands::{ActivateEdge, RequestConsent};
+
+        let (alice, bob) = (EntityRef::person(Uuid::now_v7()), EntityRef::person(Uuid::now_v7()));
+        let edge = EdgeConcept::new("Friends", alice, bob, RelationshipCategory::Friendship);
+        let edge_id = edge.id;
+        let mut space = RelationshipSpace::new("Social", TopologicalSpaceId::new())
+            .with_consent_category(RelationshipCategory::Friendship);
+        space.add_edge(edge).unwrap();
+
+        let activate = RelationshipCommand::from(EdgeCommand::ActivateEdge(ActivateEdge {
+            identity: MessageIdentity::new_root(),
+            edge_id,
+            activated_by: "test".to_string(),
+        }));
+        assert!(matches!(
+            space.handle_command(activate),
+            Err(RelationshipError::InvalidStateTransition(_))
+        ));
+
+        let request = RelationshipCommand::from(EdgeCommand::RequestConsent(RequestConsent {
+            identity: MessageIdentity::new_root(),
+            edge_id,
+            expires_at: Some(Utc::now()),
+            requested_by: "test".to_string(),
+        }));
+        for event in space.handle_command(request).unwrap() {
+            space.apply_event(&event).unwrap();
+        }
+        assert_eq!(space.expired_consents(Utc::now()), vec![edge_id]);
+    }
+
+    #[test]
+    fn test_archive_and_restore() {
+        use crate::commands::{AddEdgeTag, ArchiveRelationship, RestoreRelationship, TerminateEdge};
+        use crate::value_objects::TagMatch;
+
+        let (alice, bob) = (EntityRef::person(Uuid::now_v7()), EntityRef::person(Uuid::now_v7()));
+        let mut ended = EdgeConcept::new("Friends", alice.clone(), bob.clone(), RelationshipCategory::Friendship)
+            .with_tag("school");
+        ended.activate().unwrap();
+        ended.terminate("moved away").unwrap();
+        let mut rejected = EdgeConcept::new("Friends", bob, alice, RelationshipCategory::Friendship);
+        rejected.reject("bob", None).unwrap();
+        let (ended_id, rejected_id) = (ended.id, rejected.id);
+        let mut space = RelationshipSpace::new("Social", TopologicalSpaceId::new());
+        space.add_edge(ended).unwrap();
+        space.add_edge(rejected).unwrap();
+
+        let archive = |relationship_id| {
+            RelationshipCommand::ArchiveRelationship(ArchiveRelationship {
+                identity: MessageIdentity::new_root(),
+                relationship_id,
+                reason: Some("retention".to_string()),
+                archived_by: "test".to_string(),
+            })
+        };
+        let restore = |relationship_id| {
+            RelationshipCommand::RestoreRelationship(RestoreRelationship {
+                identity: MessageIdentity::new_root(),
+                relationship_id,
+                restored_by: "test".to_string(),
+            })
+        };
+        for id in [ended_id, rejected_id] {
+            for event in space.handle_command(archive(id)).unwrap() {
+                space.apply_event(&event).unwrap();
+            }
+        }
+        assert_eq!(space.archived_edges().len(), 2);
+        assert!(space.edges_tagged(&["school".to_string()], TagMatch::Any).is_empty());
+        assert!(space.handle_command(archive(ended_id)).is_err());
+
+        // Archived is left only by restoring
+        let terminate = RelationshipCommand::from(EdgeCommand::TerminateEdge(TerminateEdge {
+            identity: MessageIdentity::new_root(),
+            edge_id: ended_id,
+            reason: "again".to_string(),
+            terminated_by: "test".to_string(),
+        }));
+        assert!(space.handle_command(terminate).is_err());
+        let tag = RelationshipCommand::from(EdgeCommand::AddEdgeTag(AddEdgeTag {
+            identity: MessageIdentity::new_root(),
+            edge_id: ended_id,
+            tag: "old".to_string(),
+            tagged_by: "test".to_string(),
+        }));
+        assert!(space.handle_command(tag).is_err());
+
+        for id in [ended_id, rejected_id] {
+            for event in space.handle_command(restore(id)).unwrap() {
+                space.apply_event(&event).unwrap();
+            }
+        }
+        assert_eq!(space.get_edge(&ended_id).unwrap().state, EdgeState::Terminated);
+        assert_eq!(space.get_edge(&rejected_id).unwrap().state, EdgeState::Rejected);
+        assert_eq!(space.edges_tagged(&["school".to_string()], TagMatch::Any).len(), 1);
+        assert!(space.handle_command(restore(ended_id)).is_err());
+    }
+
+    #[test]
+    fn test_restructuring() {
+        use crate::commands::{ActivateHyperEdge, BeginRestructuring, ChangeParticipantRole, CompleteRestructuring};
+        use crate::invariants::{RoleConstraint, RoleSchema};
+
+        let category = RelationshipCategory::Custom("Team".to_string());
+        let (lead, dev) = (EntityRef::person(Uuid::now_v7()), EntityRef::person(Uuid::now_v7()));
+        let mut team = HyperEdgeConcept::new("Core", category.clone());
+        team.add_participant(lead.clone(), ParticipantRole::Leader, 1.0).unwrap();
+        team.add_participant(dev.clone(), ParticipantRole::Member, 1.0).unwrap();
+        team.activate().unwrap();
+        let team_id = team.id;
+        let mut space = RelationshipSpace::new("Org", TopologicalSpaceId::new())
+            .with_role_schema(
+                category.clone(),
+                RoleSchema::new().with_constraint(RoleConstraint::exactly(ParticipantRole::Leader, 1)),
+            )
+            .with_restructuring_category(category);
+        space.add_hyperedge(team);
+
+        let change_role = |participant: &EntityRef, new_role| {
+            RelationshipCommand::from(HyperEdgeCommand::ChangeParticipantRole(ChangeParticipantRole {
+                identity: MessageIdentity::new_root(),
+                hyperedge_id: team_id,
+                participant: participant.clone(),
+                new_role,
+                changed_by: "test".to_string(),
+            }))
+        };
+        let complete = || {
+            RelationshipCommand::from(HyperEdgeCommand::CompleteRestructuring(CompleteRestructuring {
+                identity: MessageIdentity::new_root(),
+                hyperedge_id: team_id,
+                completed_by: "test".to_string(),
+            }))
+        };
+        let apply = |space: &mut RelationshipSpace, cmd| {
+            for event in space.handle_command(cmd).unwrap() {
+                space.apply_event(&event).unwrap();
+            }
+        };
+
+        // Active teams change only while restructuring
+        assert!(space.handle_command(change_role(&dev, ParticipantRole::Leader)).is_err());
+        let begin = RelationshipCommand::from(HyperEdgeCommand::BeginRestructuring(BeginRestructuring {
+            identity: MessageIdentity::new_root(),
+            hyperedge_id: team_id,
+            reason: Some("new lead".to_string()),
+            begun_by: "test".to_string(),
+        }));
+        apply(&mut space, begin);
+        assert_eq!(space.get_hyperedge(&team_id).unwrap().state, HyperEdgeState::Restructuring);
+
+        // Leaderless in between; completing is refused until fixed
+        apply(&mut space, change_role(&lead, ParticipantRole::Member));
+        assert!(matches!(
+            space.handle_command(complete()),
+            Err(RelationshipError::RoleConstraintViolated(_))
+        ));
+        let activate = RelationshipCommand::from(HyperEdgeCommand::ActivateHyperEdge(ActivateHyperEdge {
+            identity: MessageIdentity::new_root(),
+            hyperedge_id: team_id,
+            activated_by: "test".to_string(),
+        }));
+        assert!(space.handle_command(activate).is_err());
+
+        apply(&mut space, change_role(&dev, ParticipantRole::Leader));
+        apply(&mut space, complete());
+        let team = space.get_hyperedge(&team_id).unwrap();
+        assert_eq!(team.state, HyperEdgeState::Active);
+        assert_eq!(team.participants.get(&dev).unwrap().role, ParticipantRole::Leader);
+    }
+
+    #[test]
+    fn test_repropose_after_rejection() {
+        use crate::commands::{RejectEdge, ReproposeEdge};
+        use crate::value_objects::ReproposalPolicy;
+
+        let edge = EdgeConcept::new(
+            "Job",
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::organization(Uuid::now_v7()),
+            RelationshipCategory::Employment,
+        );
+        let edge_id = edge.id;
+        let reject = |reason: &str| {
+            RelationshipCommand::from(EdgeCommand::RejectEdge(RejectEdge {
+                identity: MessageIdentity::new_root(),
+                edge_id,
+                reason: Some(reason.to_string()),
+                rejected_by: "hr".to_string(),
+            }))
+        };
+        let repropose = || {
+            RelationshipCommand::from(EdgeCommand::ReproposeEdge(ReproposeEdge {
+                identity: MessageIdentity::new_root(),
+                edge_id,
+                reason: Some("start date fixed".to_string()),
+                reproposed_by: "hr".to_string(),
+            }))
+        };
+        let apply = |space: &mut RelationshipSpace, cmd| {
+            for event in space.handle_command(cmd).unwrap() {
+                space.apply_event(&event).unwrap();
+            }
+        };
+
+        let mut space = RelationshipSpace::new("HR", TopologicalSpaceId::new());
+        space.add_edge(edge.clone()).unwrap();
+        apply(&mut space, reject("wrong start date"));
+        // Default policy: a week of cooling off
+        assert!(space.handle_command(repropose()).is_err());
+
+        let mut space = RelationshipSpace::new("HR", TopologicalSpaceId::new())
+            .with_reproposal_policy(ReproposalPolicy::default().with_max_reproposals(1).with_cooling_off_days(0));
+        space.add_edge(edge).unwrap();
+        apply(&mut space, reject("wrong start date"));
+        apply(&mut space, repropose());
+        let edge = space.get_edge(&edge_id).unwrap();
+        assert_eq!(edge.state, EdgeState::Proposed);
+        assert!(!edge.properties.contains_key("rejection_reason"));
+        assert_eq!(edge.reproposals, 1);
+
+        apply(&mut space, reject("wrong salary"));
+        assert!(space.handle_command(repropose()).is_err());
+        // The edge enforces the policy on its own
+        let rejected = space.get_edge(&edge_id).unwrap();
+        assert!(!rejected.state.is_terminal());
+        assert!(rejected
+            .handle_command(EdgeCommand::ReproposeEdge(ReproposeEdge {
+                identity: MessageIdentity::new_root(),
+                edge_id,
+                reason: None,
+                reproposed_by: "hr".to_string(),
+            }))
+            .is_err());
+        let reasons: Vec<_> = space.get_edge(&edge_id).unwrap().rejections.iter().map(|r| r.reason.clone()).collect();
+        assert_eq!(reasons, vec![Some("wrong start date".to_string()), Some("wrong salary".to_string())]);
+    }
+
+    #[test]
+    fn test_rename_and_describe() {
+        use crate::commands::{RenameRelationship, UpdateDescription};
+
+        let edge = EdgeConcept::new(
+            "Emplyment",
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::organization(Uuid::now_v7()),
+            RelationshipCategory::Employment,
+        );
+        let team = HyperEdgeConcept::new("Core", RelationshipCategory::Membership);
+        let (edge_id, team_id) = (edge.id, team.id);
+        let mut space = RelationshipSpace::new("Org", TopologicalSpaceId::new());
+        space.add_edge(edge).unwrap();
+        space.add_hyperedge(team);
+
+        let rename = |relationship_id, name: &str| {
+            RelationshipCommand::RenameRelationship(RenameRelationship {
+                identity: MessageIdentity::new_root(),
+                relationship_id,
+                name: name.to_string(),
+                renamed_by: "test".to_string(),
+            })
+        };
+        let describe = |relationship_id, description: Option<&str>| {
+            RelationshipCommand::UpdateDescription(UpdateDescription {
+                identity: MessageIdentity::new_root(),
+                relationship_id,
+                description: description.map(str::to_string),
+                updated_by: "test".to_string(),
+            })
+        };
+        let apply = |space: &mut RelationshipSpace, cmd| {
+            for event in space.handle_command(cmd).unwrap() {
+                space.apply_event(&event).unwrap();
+            }
+        };
+
+        assert!(space.handle_command(rename(edge_id, "  ")).is_err());
+        apply(&mut space, rename(edge_id, " Employment "));
+        apply(&mut space, rename(team_id, "Core Team"));
+        assert!(space.handle_command(rename(team_id, "Core Team")).unwrap().is_empty());
+        assert_eq!(space.get_edge(&edge_id).unwrap().name, "Employment");
+        assert_eq!(space.get_hyperedge(&team_id).unwrap().name, "Core Team");
+
+        apply(&mut space, describe(team_id, Some("Platform maintainers")));
+        assert_eq!(space.get_hyperedge(&team_id).unwrap().description.as_deref(), Some("Platform maintainers"));
+        apply(&mut space, describe(team_id, None));
+        assert_eq!(space.get_hyperedge(&team_id).unwrap().description, None);
+        assert!(space.handle_command(rename(RelationshipId::new(), "Ghost")).is_err());
+    }
+
+    #[test]
+    fn test_policy_enforcement() {
+        use crate::commands::{ActivateEdge, CreateEdge, RedirectEdge};
+        use crate::invariants::{EndpointSide, PolicyRule, RelationshipPolicy};
+        use crate::value_objects::EntityType;
+
+        let sanctioned = EntityRef::organization(Uuid::now_v7());
+        let sanctions = EntityRef::new(EntityType::Policy, Uuid::now_v7());
+        let mut space = RelationshipSpace::new("Holdings", TopologicalSpaceId::new()).with_policy(
+            RelationshipPolicy::new(
+                sanctions.clone(),
+                "Sanctions",
+                PolicyRule::Forbid {
+                    category: Some(RelationshipCategory::Ownership),
+                    side: EndpointSide::Target,
+                    entities: vec![sanctioned.clone()],
+                },
+            ),
+        );
+        let holding = EntityRef::organization(Uuid::now_v7());
+        let create = |target: &EntityRef, category| {
+            RelationshipCommand::from(EdgeCommand::CreateEdge(CreateEdge {
+                identity: MessageIdentity::new_root(),
+                edge_id: RelationshipId::new(),
+                source: holding.clone(),
+                target: target.clone(),
+                category,
+                name: "Stake".to_string(),
+                quality: None,
+                created_by: "test".to_string(),
+            }))
+        };
+
+        match space.handle_command(create(&sanctioned, RelationshipCategory::Ownership)) {
+            Err(RelationshipError::PolicyViolated(violations)) => {
+                assert_eq!(violations.len(), 1);
+                assert_eq!(violations[0].policy, sanctions);
+            }
+            other => panic!("expected a policy violation, got {:?}", other),
+        }
+        // Other categories are not constrained
+        assert!(space.handle_command(create(&sanctioned, RelationshipCategory::References)).is_ok());
+
+        // Redirecting an allowed edge onto the sanctioned organization is refused
+        let events = space
+            .handle_command(create(&EntityRef::organization(Uuid::now_v7()), RelationshipCategory::Ownership))
+            .unwrap();
+        events.iter().try_for_each(|e| space.apply_event(e)).unwrap();
+        let edge_id = events[0].relationship_id();
+        let redirect = RelationshipCommand::from(EdgeCommand::RedirectEdge(RedirectEdge {
+            identity: MessageIdentity::new_root(),
+            edge_id,
+            new_source: None,
+            new_target: Some(sanctioned.clone()),
+            reason: "acquisition".to_string(),
+            redirected_by: "test".to_string(),
+        }));
+        assert!(matches!(space.handle_command(redirect), Err(RelationshipError::PolicyViolated(_))));
+        let activate = RelationshipCommand::from(EdgeCommand::ActivateEdge(ActivateEdge {
+            identity: MessageIdentity::new_root(),
+            edge_id,
+            activated_by: "test".to_string(),
+        }));
+        assert!(space.handle_command(activate).is_ok());
+    }
+
+    #[test]
+    fn test_policies_by_command() {
+        use crate::aggregates::RelationshipTemplate;
+        use crate::algebra::MergeStrategy;
+        use crate::commands::{CreateFromTemplate, TemplateEndpoints};
+        use crate::invariants::{EndpointSide, PolicyRule, RelationshipPolicy};
+
+        let sanctioned = EntityRef::organization(Uuid::now_v7());
+        let sanctions = EntityRef::new(EntityType::Policy, Uuid::now_v7());
+        let holding = EntityRef::organization(Uuid::now_v7());
+        let alias = EntityRef::organization(Uuid::now_v7());
+        let mut space = RelationshipSpace::new("Holdings", TopologicalSpaceId::new())
+            .with_template(RelationshipTemplate::edge("stake", RelationshipCategory::Ownership));
+        let mut stake = EdgeConcept::new("Stake", holding.clone(), alias.clone(), RelationshipCategory::Ownership);
+        stake.activate().unwrap();
+        space.add_edge(stake).unwrap();
+        let mut board = HyperEdgeConcept::new("Board", RelationshipCategory::Membership);
+        board.add_participant(holding.clone(), ParticipantRole::Leader, 1.0).unwrap();
+        board.add_participant(sanctioned.clone(), ParticipantRole::Member, 1.0).unwrap();
+        let board_id = board.id;
+        space.add_hyperedge(board);
+
+        let enact = |policy: &EntityRef| {
+            RelationshipCommand::EnactPolicy(EnactPolicy {
+                identity: MessageIdentity::new_root(),
+                policy: RelationshipPolicy::new(
+                    policy.clone(),
+                    "Sanctions",
+                    PolicyRule::Forbid {
+                        category: Some(RelationshipCategory::Ownership),
+                        side: EndpointSide::Target,
+                        entities: vec![sanctioned.clone()],
+                    },
+                ),
+                enacted_by: "test".to_string(),
+            })
+        };
+        assert!(space.handle_command(enact(&holding)).is_err());
+        for event in space.handle_command(enact(&sanctions)).unwrap() {
+            space.apply_event(&event).unwrap();
+        }
+        assert_eq!(space.policies.policies.len(), 1);
+
+        // Every path creating or relinking an edge is checked
+        let before = space.clone();
+        assert!(matches!(
+            space.merge_entities(&alias, &sanctioned, MergeStrategy::MaxQuality),
+            Err(RelationshipError::PolicyViolated(_))
+        ));
+        assert_eq!(space.version, before.version);
+        let decompose = RelationshipCommand::from(RestructureCommand::DecomposeHyperEdge(DecomposeHyperEdge {
+            identity: MessageIdentity::new_root(),
+            hyperedge_id: board_id,
+            from_role: ParticipantRole::Leader,
+            to_role: ParticipantRole::Member,
+            category: RelationshipCategory::Ownership,
+            decomposed_by: "test".to_string(),
+        }));
+        assert!(matches!(space.handle_command(decompose), Err(RelationshipError::PolicyViolated(_))));
+        let from_template = RelationshipCommand::CreateFromTemplate(CreateFromTemplate {
+            identity: MessageIdentity::new_root(),
+            relationship_id: RelationshipId::new(),
+            template: "stake".to_string(),
+            name: "Stake".to_string(),
+            endpoints: TemplateEndpoints::Edge {
+                source: holding.clone(),
+                target: sanctioned.clone(),
+            },
+            properties: Default::default(),
+            valid_until: None,
+            created_by: "test".to_string(),
+        });
+        assert!(matches!(
+            space.handle_command(from_template.clone()),
+            Err(RelationshipError::PolicyViolated(_))
+        ));
+
+        let revoke = || {
+            RelationshipCommand::RevokePolicy(RevokePolicy {
+                identity: MessageIdentity::new_root(),
+                policy: sanctions.clone(),
+                reason: Some("lifted".to_string()),
+                revoked_by: "test".to_string(),
+            })
+        };
+        for event in space.handle_command(revoke()).unwrap() {
+            space.apply_event(&event).unwrap();
+        }
+        assert!(space.policies.policies.is_empty());
+        assert!(matches!(space.handle_command(revoke()), Err(RelationshipError::EntityNotFound(_))));
+        assert!(space.handle_command(from_template).is_ok());
+        assert!(space.merge_entities(&alias, &sanctioned, MergeStrategy::MaxQuality).is_ok());
+    }
+}
@@ -14,16 +14,79 @@
 //! - **Has Entity References**: CID-addressed source and target entities
 //! - **Has State Machine**: Mealy machine for lifecycle transitions
 //! - **Event Sourced**: All changes via immutable events
-
-use crate::events::EdgeEvent;
-use crate::quality::{QualityPoint, RelationshipQuality};
-use crate::value_objects::{EntityRef, RelationshipCategory, RelationshipId, ValidityPeriod};
-use crate::RelationshipResult;
+//!
+//! ## Consent
+//!
+//! Some relationships exist only if both parties agree. A proposed edge
+//! can wait for the consent of both endpoints:
+//!
+//! ```text
+//! Proposed --RequestConsent--> PendingConsent --GrantConsent (both)--> Active
+//!                                    |
+//!                                    +--DeclineConsent / ExpireConsent--> Rejected
+//! ```
+//!
+//! A space can require consent for a category
+//! (`RelationshipSpace::with_consent_category`), refusing to activate its
+//! edges directly from Proposed.
+//!
+//! ## Re-proposal
+//!
+//! A rejected edge can be fixed and proposed again. Every rejection is kept
+//! in `rejections` and every re-proposal counted in `reproposals`; the
+//! edge's `reproposal_policy` limits how often and how soon (a space
+//! installs its own, `RelationshipSpace::with_reproposal_policy`):
+//!
+//! ```text
+//! Rejected --ReproposeEdge--> Proposed
+//! ```
+//!
+//! ## Knowledge
+//!
+//! `ProgressKnowledge` moves an edge one level at a time, each step needing
+//! enough unrevoked evidence and confidence:
+//!
+//! ```text
+//! Unknown --(1 CID)--> Suspected --(3 CIDs, confidence >= 0.7)--> Known
+//! ```
+//!
+//! The confidence checked is the one the command decides on, which the
+//! edge keeps: adding evidence raises confidence to a tenth per CID but
+//! never lowers a decided one. Evidence counts as verified until it is
+//! revoked; the aggregate cannot look behind a CID, so evidence found
+//! wanting is withdrawn with `RevokeEdgeEvidence`, and revoking evidence
+//! below a level's CID count regresses the edge: Known falls to Suspected
+//! under 3 CIDs, and anything to Unknown at 0.
+//!
+//! ## Expiry
+//!
+//! An edge is live only within its validity period. When `ends_at` passes,
+//! `ExpireEdge` (issued by the scheduler) terminates it at `ends_at`:
+//!
+//! ```text
+//! Active / Suspended --ExpireEdge (ends_at <= as_of)--> Terminated
+//! ```
+
+use super::{check_name, check_quality, check_tag, TransitionGuards};
+use crate::algebra::CompositionRegistry;
+use crate::commands::EdgeCommand;
+use crate::events::{
+    EdgeActivated, EdgeArchived, EdgeConsentDeclined, EdgeConsentGranted, EdgeConsentRequested, EdgeCreated, EdgeEndpointsRewritten, EdgeEvent,
+    EdgeEvidenceAdded, EdgeEvidenceRevoked, EdgeKnowledgeProgressed, EdgePropertyRemoved, EdgePropertyUpdated, EdgeQualityUpdated, EdgeRejected, EdgeReproposed, EdgeRestored, EdgeRenamed, EdgeDescriptionUpdated, EdgeTagAdded, EdgeTagRemoved, EdgeReversed, EdgeSuspended, EdgeTerminated,
+};
+use crate::quality::{DurationModel, QualityPoint, RelationshipQuality};
+use crate::value_objects::{
+    normalize_tag, EntityKey, EntityRef, PropertyChange, Redirect, Rejection, RelationshipCategory, RelationshipId,
+    ReproposalPolicy, ValidityPeriod,
+};
+use crate::{RelationshipError, RelationshipResult};
 use chrono::{DateTime, Utc};
 use cim_domain::state_machine::State;
+use cim_domain::MessageIdentity;
 use cim_domain_spaces::{ConceptId, KnowledgeLevel, Point3};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use uuid::Uuid;
 
 // ============================================================================
 // Edge State Machine
@@ -34,6 +97,8 @@ use std::collections::HashMap;
 pub enum EdgeState {
     /// Edge has been created but not yet active
     Proposed,
+    /// Edge is waiting for both endpoints to consent
+    PendingConsent,
     /// Edge is currently active
     Active,
     /// Edge has been suspended (temporarily inactive)
@@ -42,25 +107,35 @@ pub enum EdgeState {
     Terminated,
     /// Edge was rejected (never became active)
     Rejected,
+    /// Edge was terminated or rejected, then archived (hidden until restored)
+    Archived,
 }
 
 impl State for EdgeState {
     fn name(&self) -> &'static str {
         match self {
             EdgeState::Proposed => "Proposed",
+            EdgeState::PendingConsent => "PendingConsent",
             EdgeState::Active => "Active",
             EdgeState::Suspended => "Suspended",
             EdgeState::Terminated => "Terminated",
             EdgeState::Rejected => "Rejected",
+            EdgeState::Archived => "Archived",
         }
     }
 
     fn is_terminal(&self) -> bool {
-        matches!(self, EdgeState::Terminated | EdgeState::Rejected)
+        matches!(self, EdgeState::Terminated | EdgeState::Archived)
     }
 }
 
 impl EdgeState {
+    /// Check if the edge no longer stands: terminal, or rejected (a
+    /// rejected edge may still be fixed and re-proposed)
+    pub fn is_closed(&self) -> bool {
+        self.is_terminal() || *self == EdgeState::Rejected
+    }
+
     /// Check if transition from current state to new state is valid
     pub fn can_transition_to(&self, to: &EdgeState) -> bool {
         use EdgeState::*;
@@ -68,13 +143,22 @@ impl EdgeState {
             (self, to),
             // From Proposed
             (Proposed, Active) |
+            (Proposed, PendingConsent) |
             (Proposed, Rejected) |
+            // From PendingConsent
+            (PendingConsent, Active) |
+            (PendingConsent, Rejected) |
             // From Active
             (Active, Suspended) |
             (Active, Terminated) |
             // From Suspended
             (Suspended, Active) |
-            (Suspended, Terminated)
+            (Suspended, Terminated) |
+            // Re-proposing a rejected edge
+            (Rejected, Proposed) |
+            // Archiving; only RestoreEdge leaves Archived
+            (Terminated, Archived) |
+            (Rejected, Archived)
         )
     }
 
@@ -82,11 +166,13 @@ impl EdgeState {
     pub fn valid_transitions(&self) -> Vec<EdgeState> {
         use EdgeState::*;
         match self {
-            Proposed => vec![Active, Rejected],
+            Proposed => vec![Active, PendingConsent, Rejected],
+            PendingConsent => vec![Active, Rejected],
             Active => vec![Suspended, Terminated],
             Suspended => vec![Active, Terminated],
-            Terminated => vec![],
-            Rejected => vec![],
+            Terminated => vec![Archived],
+            Rejected => vec![Proposed, Archived],
+            Archived => vec![],
         }
     }
 }
@@ -154,16 +240,49 @@ pub struct EdgeConcept {
     pub confidence: f64,
     /// Evidence CIDs supporting this relationship
     pub evidence_cids: Vec<String>,
+    /// Evidence CIDs revoked as invalid
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub revoked_evidence: BTreeSet<String>,
 
     // ---- Lifecycle ----
     /// Current state in the lifecycle
     pub state: EdgeState,
     /// Validity period
     pub validity: ValidityPeriod,
+    /// Endpoints that have consented (while consent is pending)
+    #[serde(default)]
+    pub consents: HashSet<EntityKey>,
+    /// When a pending consent request expires
+    #[serde(default)]
+    pub consent_deadline: Option<DateTime<Utc>>,
+    /// Every rejection of the edge, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rejections: Vec<Rejection>,
+    /// Times the edge has been re-proposed after a rejection
+    #[serde(default)]
+    pub reproposals: u32,
+    /// How often and how soon the edge may be re-proposed
+    #[serde(default)]
+    pub reproposal_policy: ReproposalPolicy,
+    /// Every change of the endpoints, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub redirects: Vec<Redirect>,
+    /// State the edge was archived from, returned to on `RestoreEdge`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archived_from: Option<EdgeState>,
+    /// Preconditions on state transitions (not serialized)
+    #[serde(skip)]
+    pub guards: TransitionGuards,
 
     // ---- Metadata ----
     /// Additional properties
     pub properties: HashMap<String, serde_json::Value>,
+    /// Classification tags, normalized
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub tags: BTreeSet<String>,
+    /// Changes to each property, oldest first
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub property_history: HashMap<String, Vec<PropertyChange>>,
     /// Event version
     pub version: u64,
     /// Creation timestamp
@@ -197,9 +316,20 @@ impl EdgeConcept {
             knowledge_level: KnowledgeLevel::Unknown,
             confidence: 0.0,
             evidence_cids: Vec::new(),
+            revoked_evidence: BTreeSet::new(),
             state: EdgeState::Proposed,
             validity: ValidityPeriod::ongoing_now(),
+            consents: HashSet::new(),
+            consent_deadline: None,
+            rejections: Vec::new(),
+            reproposals: 0,
+            reproposal_policy: ReproposalPolicy::default(),
+            redirects: Vec::new(),
+            archived_from: None,
+            guards: TransitionGuards::new(),
             properties: HashMap::new(),
+            tags: BTreeSet::new(),
+            property_history: HashMap::new(),
             version: 0,
             created_at: now,
             updated_at: now,
@@ -231,11 +361,34 @@ impl EdgeConcept {
         self
     }
 
+    /// Add a tag
+    pub fn with_tag(mut self, tag: &str) -> Self {
+        if let Some(tag) = normalize_tag(tag) {
+            self.tags.insert(tag);
+        }
+        self
+    }
+
+    /// Check if this edge carries a tag
+    pub fn has_tag(&self, tag: &str) -> bool {
+        normalize_tag(tag).is_some_and(|tag| self.tags.contains(&tag))
+    }
+
+    /// Set the guards evaluated on state transitions
+    pub fn with_guards(mut self, guards: TransitionGuards) -> Self {
+        self.guards = guards;
+        self
+    }
+
     // ---- State Machine ----
 
     /// Transition to a new state
+    ///
+    /// The transition must be valid and pass every guard for the edge's
+    /// category.
     pub fn transition_to(&mut self, new_state: EdgeState) -> Result<(), String> {
         if self.state.can_transition_to(&new_state) {
+            self.guards.check_edge(self, &new_state)?;
             self.state = new_state;
             self.updated_at = Utc::now();
             Ok(())
@@ -264,33 +417,123 @@ impl EdgeConcept {
 
     /// Terminate the edge
     pub fn terminate(&mut self, reason: impl Into<String>) -> Result<(), String> {
-        let now = Utc::now();
-        self.validity = self.validity.clone().end(now, reason);
-        self.transition_to(EdgeState::Terminated)
+        let validity = self.validity.clone().end(Utc::now(), reason);
+        self.transition_to(EdgeState::Terminated)?;
+        self.validity = validity;
+        Ok(())
     }
 
-    /// Reject the edge (from Proposed state)
-    pub fn reject(&mut self) -> Result<(), String> {
-        self.transition_to(EdgeState::Rejected)
+    /// Reject the edge (from Proposed state), as `RejectEdge` does
+    pub fn reject(&mut self, rejected_by: impl Into<String>, reason: Option<String>) -> Result<(), String> {
+        self.transition_to(EdgeState::Rejected)?;
+        if let Some(ref reason) = reason {
+            self.properties
+                .insert("rejection_reason".to_string(), serde_json::Value::String(reason.clone()));
+        }
+        self.rejections.push(Rejection {
+            reason,
+            rejected_by: rejected_by.into(),
+            rejected_at: self.updated_at,
+        });
+        Ok(())
     }
 
     // ---- Query Methods ----
 
+    /// Check if the edge is archived
+    pub fn is_archived(&self) -> bool {
+        self.state == EdgeState::Archived
+    }
+
     /// Check if the edge is currently active
     pub fn is_active(&self) -> bool {
         self.state == EdgeState::Active && self.validity.is_active()
     }
 
+    /// Changes to a property, oldest first
+    pub fn property_history(&self, key: &str) -> &[PropertyChange] {
+        self.property_history.get(key).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Number of changes to a property (0 = never set by an event)
+    pub fn property_version(&self, key: &str) -> u64 {
+        self.property_history(key).len() as u64
+    }
+
+    /// Value of a property as of a point in time
+    pub fn property_at(&self, key: &str, at: DateTime<Utc>) -> Option<&serde_json::Value> {
+        self.property_history(key)
+            .iter()
+            .take_while(|change| change.changed_at <= at)
+            .last()
+            .and_then(|change| change.value.as_ref())
+    }
+
+    /// Set or remove a property, recording the change
+    fn record_property(&mut self, key: &str, value: Option<serde_json::Value>, changed_by: Option<String>, changed_at: DateTime<Utc>) {
+        match &value {
+            Some(value) => self.properties.insert(key.to_string(), value.clone()),
+            None => self.properties.remove(key),
+        };
+        let history = self.property_history.entry(key.to_string()).or_default();
+        history.push(PropertyChange {
+            version: history.len() as u64 + 1,
+            value,
+            changed_by,
+            changed_at,
+        });
+    }
+
+    /// Refuse a property change made against a stale version
+    fn check_property_version(&self, key: &str, expected: Option<u64>) -> RelationshipResult<()> {
+        match expected {
+            Some(expected) if expected != self.property_version(key) => Err(RelationshipError::InvalidStateTransition(format!(
+                "property '{}' of edge {} is at version {}, not {}",
+                key,
+                self.id,
+                self.property_version(key),
+                expected
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Check if both endpoints have consented
+    pub fn has_full_consent(&self) -> bool {
+        self.consents.contains(&self.source.key()) && self.consents.contains(&self.target.key())
+    }
+
+    /// Check if a pending consent request has expired
+    pub fn consent_expired(&self, now: DateTime<Utc>) -> bool {
+        self.state == EdgeState::PendingConsent && self.consent_deadline.is_some_and(|deadline| deadline <= now)
+    }
+
+    /// Check if the edge is still live although its validity has ended
+    pub fn validity_expired(&self, now: DateTime<Utc>) -> bool {
+        matches!(self.state, EdgeState::Active | EdgeState::Suspended)
+            && self.validity.ends_at.is_some_and(|end| end <= now)
+    }
+
     /// Check if this is a symmetric (bidirectional) relationship
     pub fn is_symmetric(&self) -> bool {
         self.category.is_symmetric()
     }
 
+    /// Check if this edge relates an entity to itself
+    pub fn is_reflexive(&self) -> bool {
+        self.source.same_entity(&self.target)
+    }
+
     /// Get the quality point in conceptual space
     pub fn quality_point(&self) -> QualityPoint {
         self.quality.to_quality_point()
     }
 
+    /// Get the quality point, normalizing duration with a space's model
+    pub fn quality_point_with(&self, model: &DurationModel) -> QualityPoint {
+        self.quality.to_quality_point_with(model, Some(&self.category))
+    }
+
     /// Calculate similarity to another edge (based on quality space distance)
     pub fn similarity(&self, other: &EdgeConcept) -> f64 {
         let distance = self.quality_point().distance(&other.quality_point());
@@ -299,6 +542,659 @@ impl EdgeConcept {
         1.0 - (distance / 2.236).min(1.0)
     }
 
+    // ---- Composition ----
+
+    /// Compose with an edge starting where this one ends, by the standard
+    /// composition rules
+    pub fn compose(&self, next: &EdgeConcept) -> RelationshipResult<EdgeConcept> {
+        self.compose_with(&CompositionRegistry::standard(), next)
+    }
+
+    /// Compose with an edge starting where this one ends
+    ///
+    /// The composite runs from this edge's source to `next`'s target, as a
+    /// proposed edge that has not been recorded. Its category and quality
+    /// come from the registry's rule for the pair; it is valid while both
+    /// links are.
+    pub fn compose_with(
+        &self,
+        registry: &CompositionRegistry,
+        next: &EdgeConcept,
+    ) -> RelationshipResult<EdgeConcept> {
+        if !self.target.same_entity(&next.source) {
+            return Err(crate::RelationshipError::InvalidRelationship(format!(
+                "cannot compose {} with {}: target does not match source",
+                self.id, next.id
+            )));
+        }
+        let rule = registry.rule(&self.category, &next.category)?;
+
+        let starts_at = self.validity.starts_at.max(next.validity.starts_at);
+        let ends_at = match (self.validity.ends_at, next.validity.ends_at) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        if ends_at.is_some_and(|end| end <= starts_at) {
+            return Err(crate::RelationshipError::InvalidRelationship(format!(
+                "cannot compose {} with {}: validity periods do not overlap",
+                self.id, next.id
+            )));
+        }
+        let validity = ValidityPeriod {
+            starts_at,
+            ends_at,
+            end_reason: None,
+        };
+        let quality = rule.quality.compose(&self.quality, &next.quality, validity.clone());
+
+        let composed_from = serde_json::json!([self.id.to_string(), next.id.to_string()]);
+        Ok(EdgeConcept::new(
+            format!("{} o {}", self.name, next.name),
+            self.source.clone(),
+            next.target.clone(),
+            rule.result.clone(),
+        )
+        .with_quality(quality)
+        .with_validity(validity)
+        .with_property("composed_from", composed_from))
+    }
+
+    // ---- Command Handling ----
+
+    /// Decide the events a command produces (pure functional)
+    ///
+    /// The output half of the Mealy machine: applying the returned events
+    /// with `apply_event_pure` gives the next state. `CreateEdge` is
+    /// decided by a blank edge, e.g. `EdgeConcept::new`, and rejected by
+    /// the edge it would create.
+    /// Evidence already held and redirects to the current endpoints are
+    /// accepted without events.
+    pub fn handle_command(&self, cmd: EdgeCommand) -> RelationshipResult<Vec<EdgeEvent>> {
+        let now = Utc::now();
+        match cmd {
+            EdgeCommand::CreateEdge(c) => {
+                if c.edge_id == self.id {
+                    return Err(RelationshipError::InvalidStateTransition(format!(
+                        "edge {} already exists",
+                        self.id
+                    )));
+                }
+                let mut events = vec![EdgeEvent::EdgeCreated(EdgeCreated {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    edge_id: c.edge_id,
+                    concept_id: ConceptId::new(),
+                    source: c.source,
+                    target: c.target,
+                    category: c.category,
+                    name: c.name,
+                    created_by: c.created_by,
+                    created_at: now,
+                })];
+                if let Some(quality) = c.quality {
+                    check_quality(&quality)?;
+                    events.push(EdgeEvent::QualityUpdated(EdgeQualityUpdated {
+                        event_id: Uuid::now_v7(),
+                        identity: MessageIdentity::new_caused_by(&c.identity),
+                        edge_id: c.edge_id,
+                        old_quality: self.quality.clone(),
+                        new_quality: quality,
+                        reason: "initial quality".to_string(),
+                        updated_at: now,
+                    }));
+                }
+                Ok(events)
+            }
+
+            EdgeCommand::ActivateEdge(c) => {
+                self.check_command(&c.edge_id, Some(EdgeState::Active))?;
+                if self.state == EdgeState::PendingConsent && !self.has_full_consent() {
+                    return Err(RelationshipError::InvalidStateTransition(format!(
+                        "edge {} is waiting for consent",
+                        self.id
+                    )));
+                }
+                Ok(vec![EdgeEvent::EdgeActivated(EdgeActivated {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    edge_id: c.edge_id,
+                    activated_by: c.activated_by,
+                    activated_at: now,
+                })])
+            }
+
+            EdgeCommand::SuspendEdge(c) => {
+                self.check_command(&c.edge_id, Some(EdgeState::Suspended))?;
+                Ok(vec![EdgeEvent::EdgeSuspended(EdgeSuspended {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    edge_id: c.edge_id,
+                    reason: c.reason,
+                    suspended_by: c.suspended_by,
+                    suspended_at: now,
+                })])
+            }
+
+            EdgeCommand::ResumeEdge(c) => {
+                if self.state != EdgeState::Suspended {
+                    return Err(RelationshipError::InvalidStateTransition(format!(
+                        "Cannot resume from {:?}",
+                        self.state
+                    )));
+                }
+                self.check_command(&c.edge_id, Some(EdgeState::Active))?;
+                Ok(vec![EdgeEvent::EdgeActivated(EdgeActivated {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    edge_id: c.edge_id,
+                    activated_by: c.resumed_by,
+                    activated_at: now,
+                })])
+            }
+
+            EdgeCommand::TerminateEdge(c) => {
+                self.check_command(&c.edge_id, Some(EdgeState::Terminated))?;
+                Ok(vec![EdgeEvent::EdgeTerminated(EdgeTerminated {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    edge_id: c.edge_id,
+                    reason: c.reason,
+                    terminated_by: c.terminated_by,
+                    terminated_at: now,
+                })])
+            }
+
+            EdgeCommand::ReproposeEdge(c) => {
+                self.check_command(&c.edge_id, Some(EdgeState::Proposed))?;
+                self.check_reproposal(now)?;
+                Ok(vec![EdgeEvent::Reproposed(EdgeReproposed {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    edge_id: c.edge_id,
+                    reason: c.reason,
+                    reproposed_by: c.reproposed_by,
+                    reproposed_at: now,
+                })])
+            }
+
+            EdgeCommand::ExpireEdge(c) => {
+                // Expiry records that validity ended; category guards govern
+                // decisions to end an edge, not the passing of time
+                self.check_target(&c.edge_id)?;
+                if !self.state.can_transition_to(&EdgeState::Terminated) {
+                    return Err(RelationshipError::InvalidStateTransition(format!(
+                        "Cannot transition from {:?} to {:?}",
+                        self.state,
+                        EdgeState::Terminated
+                    )));
+                }
+                let ends_at = match self.validity.ends_at {
+                    Some(ends_at) if self.validity_expired(c.as_of) => ends_at,
+                    _ => {
+                        return Err(RelationshipError::InvalidStateTransition(format!(
+                            "validity of edge {} has not ended",
+                            self.id
+                        )))
+                    }
+                };
+                Ok(vec![EdgeEvent::EdgeTerminated(EdgeTerminated {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    edge_id: c.edge_id,
+                    reason: "validity period ended".to_string(),
+                    terminated_by: "validity expiry".to_string(),
+                    // Ends at ends_at, so state and validity agree
+                    terminated_at: ends_at,
+                })])
+            }
+
+            EdgeCommand::RejectEdge(c) => {
+                self.check_command(&c.edge_id, Some(EdgeState::Rejected))?;
+                Ok(vec![EdgeEvent::EdgeRejected(EdgeRejected {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    edge_id: c.edge_id,
+                    reason: c.reason,
+                    rejected_by: c.rejected_by,
+                    rejected_at: now,
+                })])
+            }
+
+            EdgeCommand::UpdateEdgeQuality(c) => {
+                self.check_command(&c.edge_id, None)?;
+                check_quality(&c.new_quality)?;
+                Ok(vec![EdgeEvent::QualityUpdated(EdgeQualityUpdated {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    edge_id: c.edge_id,
+                    old_quality: self.quality.clone(),
+                    new_quality: c.new_quality,
+                    reason: c.reason,
+                    updated_at: now,
+                })])
+            }
+
+            EdgeCommand::AddEdgeEvidence(c) => {
+                self.check_command(&c.edge_id, None)?;
+                if self.revoked_evidence.contains(&c.evidence_cid) {
+                    return Err(RelationshipError::InvalidRelationship(format!(
+                        "evidence {} was revoked",
+                        c.evidence_cid
+                    )));
+                }
+                if self.evidence_cids.contains(&c.evidence_cid) {
+                    return Ok(Vec::new());
+                }
+                Ok(vec![EdgeEvent::EvidenceAdded(EdgeEvidenceAdded {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    edge_id: c.edge_id,
+                    evidence_cid: c.evidence_cid,
+                    evidence_type: c.evidence_type,
+                    added_at: now,
+                })])
+            }
+
+            EdgeCommand::RevokeEdgeEvidence(c) => {
+                self.check_command(&c.edge_id, None)?;
+                if !self.evidence_cids.contains(&c.evidence_cid) {
+                    return Ok(Vec::new());
+                }
+                let mut events = vec![EdgeEvent::EvidenceRevoked(EdgeEvidenceRevoked {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    edge_id: c.edge_id,
+                    evidence_cid: c.evidence_cid.clone(),
+                    reason: c.reason.clone(),
+                    revoked_by: c.revoked_by,
+                    revoked_at: now,
+                })];
+                // Knowledge no longer backed by enough evidence regresses
+                let remaining = self.evidence_cids.len() - 1;
+                let supported = supported_knowledge(&self.knowledge_level, remaining);
+                if supported != self.knowledge_level {
+                    events.push(EdgeEvent::KnowledgeProgressed(EdgeKnowledgeProgressed {
+                        event_id: Uuid::now_v7(),
+                        identity: MessageIdentity::new_caused_by(&c.identity),
+                        edge_id: c.edge_id,
+                        from_level: self.knowledge_level,
+                        to_level: supported,
+                        new_confidence: (remaining as f64 / 10.0).min(1.0),
+                        reason: format!("evidence {} revoked: {}", c.evidence_cid, c.reason),
+                        progressed_at: now,
+                    }));
+                }
+                Ok(events)
+            }
+
+            EdgeCommand::ProgressKnowledge(c) => {
+                self.check_command(&c.edge_id, None)?;
+                if !(0.0..=1.0).contains(&c.confidence) {
+                    return Err(RelationshipError::QualityOutOfRange(format!("confidence = {}", c.confidence)));
+                }
+                let (min_evidence, min_confidence) = knowledge_threshold(&self.knowledge_level, &c.to_level)
+                    .ok_or_else(|| {
+                        RelationshipError::InvalidStateTransition(format!(
+                            "knowledge cannot progress from {:?} to {:?}",
+                            self.knowledge_level, c.to_level
+                        ))
+                    })?;
+                if self.evidence_cids.len() < min_evidence || c.confidence < min_confidence {
+                    return Err(RelationshipError::InsufficientEvidence(format!(
+                        "{:?} needs {} unrevoked evidence CIDs and confidence {}, edge {} has {} and asks {}",
+                        c.to_level,
+                        min_evidence,
+                        min_confidence,
+                        self.id,
+                        self.evidence_cids.len(),
+                        c.confidence
+                    )));
+                }
+                Ok(vec![EdgeEvent::KnowledgeProgressed(EdgeKnowledgeProgressed {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    edge_id: c.edge_id,
+                    from_level: self.knowledge_level,
+                    to_level: c.to_level,
+                    new_confidence: c.confidence,
+                    reason: c.reason,
+                    progressed_at: now,
+                })])
+            }
+
+            EdgeCommand::ReverseEdge(c) => {
+                self.check_command(&c.edge_id, None)?;
+                let new_category = if c.invert_category {
+                    self.category.inverse().ok_or_else(|| {
+                        RelationshipError::InvalidRelationship(format!(
+                            "{} has no inverse category",
+                            self.category.display_name()
+                        ))
+                    })?
+                } else {
+                    self.category.clone()
+                };
+                Ok(vec![EdgeEvent::Reversed(EdgeReversed {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    edge_id: c.edge_id,
+                    old_category: self.category.clone(),
+                    new_category,
+                    reason: c.reason,
+                    reversed_by: c.reversed_by,
+                    reversed_at: now,
+                })])
+            }
+
+            EdgeCommand::RedirectEdge(c) => {
+                self.check_command(&c.edge_id, None)?;
+                if c.new_source.is_none() && c.new_target.is_none() {
+                    return Err(RelationshipError::InvalidRelationship(format!(
+                        "redirect of edge {} names no new endpoint",
+                        self.id
+                    )));
+                }
+                let new_source = c.new_source.unwrap_or_else(|| self.source.clone());
+                let new_target = c.new_target.unwrap_or_else(|| self.target.clone());
+                if new_source == self.source && new_target == self.target {
+                    return Ok(Vec::new());
+                }
+                Ok(vec![EdgeEvent::EndpointsRewritten(EdgeEndpointsRewritten {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    edge_id: c.edge_id,
+                    old_source: self.source.clone(),
+                    old_target: self.target.clone(),
+                    new_source,
+                    new_target,
+                    reason: c.reason,
+                    rewritten_by: c.redirected_by,
+                    rewritten_at: now,
+                })])
+            }
+
+            EdgeCommand::RequestConsent(c) => {
+                self.check_command(&c.edge_id, Some(EdgeState::PendingConsent))?;
+                Ok(vec![EdgeEvent::ConsentRequested(EdgeConsentRequested {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    edge_id: c.edge_id,
+                    expires_at: c.expires_at,
+                    requested_by: c.requested_by,
+                    requested_at: now,
+                })])
+            }
+
+            EdgeCommand::GrantConsent(c) => {
+                self.check_consent(&c.edge_id, &c.party)?;
+                if self.consents.contains(&c.party.key()) {
+                    return Ok(Vec::new());
+                }
+                let granted = EdgeEvent::ConsentGranted(EdgeConsentGranted {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    edge_id: c.edge_id,
+                    party: c.party.clone(),
+                    granted_at: now,
+                });
+                let next = self.apply_event_pure(&granted)?;
+                let mut events = vec![granted];
+                if next.has_full_consent() {
+                    self.check_command(&c.edge_id, Some(EdgeState::Active))?;
+                    events.push(EdgeEvent::EdgeActivated(EdgeActivated {
+                        event_id: Uuid::now_v7(),
+                        identity: MessageIdentity::new_caused_by(&c.identity),
+                        edge_id: c.edge_id,
+                        activated_by: c.party.to_string(),
+                        activated_at: now,
+                    }));
+                }
+                Ok(events)
+            }
+
+            EdgeCommand::DeclineConsent(c) => {
+                self.check_consent(&c.edge_id, &c.party)?;
+                self.check_command(&c.edge_id, Some(EdgeState::Rejected))?;
+                Ok(vec![
+                    EdgeEvent::ConsentDeclined(EdgeConsentDeclined {
+                        event_id: Uuid::now_v7(),
+                        identity: MessageIdentity::new_caused_by(&c.identity),
+                        edge_id: c.edge_id,
+                        party: c.party.clone(),
+                        reason: c.reason.clone(),
+                        declined_at: now,
+                    }),
+                    EdgeEvent::EdgeRejected(EdgeRejected {
+                        event_id: Uuid::now_v7(),
+                        identity: MessageIdentity::new_caused_by(&c.identity),
+                        edge_id: c.edge_id,
+                        reason: Some(c.reason.unwrap_or_else(|| format!("consent declined by {}", c.party))),
+                        rejected_by: c.party.to_string(),
+                        rejected_at: now,
+                    }),
+                ])
+            }
+
+            EdgeCommand::ExpireConsent(c) => {
+                self.check_command(&c.edge_id, Some(EdgeState::Rejected))?;
+                if !self.consent_expired(now) {
+                    return Err(RelationshipError::InvalidStateTransition(format!(
+                        "consent for edge {} has not expired",
+                        self.id
+                    )));
+                }
+                Ok(vec![EdgeEvent::EdgeRejected(EdgeRejected {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    edge_id: c.edge_id,
+                    reason: Some("consent expired".to_string()),
+                    rejected_by: "consent timeout".to_string(),
+                    rejected_at: now,
+                })])
+            }
+
+            EdgeCommand::SetEdgeProperty(c) => {
+                self.check_command(&c.edge_id, None)?;
+                self.check_property_version(&c.key, c.expected_version)?;
+                Ok(vec![EdgeEvent::PropertyUpdated(EdgePropertyUpdated {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    edge_id: c.edge_id,
+                    key: c.key,
+                    value: c.value,
+                    updated_by: Some(c.set_by),
+                    updated_at: now,
+                })])
+            }
+
+            EdgeCommand::RemoveEdgeProperty(c) => {
+                self.check_command(&c.edge_id, None)?;
+                self.check_property_version(&c.key, c.expected_version)?;
+                if !self.properties.contains_key(&c.key) {
+                    return Ok(Vec::new());
+                }
+                Ok(vec![EdgeEvent::PropertyRemoved(EdgePropertyRemoved {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    edge_id: c.edge_id,
+                    key: c.key,
+                    removed_by: c.removed_by,
+                    removed_at: now,
+                })])
+            }
+
+            EdgeCommand::AddEdgeTag(c) => {
+                self.check_command(&c.edge_id, None)?;
+                let tag = check_tag(&c.tag)?;
+                if self.tags.contains(&tag) {
+                    return Ok(Vec::new());
+                }
+                Ok(vec![EdgeEvent::TagAdded(EdgeTagAdded {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    edge_id: c.edge_id,
+                    tag,
+                    tagged_by: c.tagged_by,
+                    tagged_at: now,
+                })])
+            }
+
+            EdgeCommand::RemoveEdgeTag(c) => {
+                self.check_command(&c.edge_id, None)?;
+                let tag = check_tag(&c.tag)?;
+                if !self.tags.contains(&tag) {
+                    return Ok(Vec::new());
+                }
+                Ok(vec![EdgeEvent::TagRemoved(EdgeTagRemoved {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    edge_id: c.edge_id,
+                    tag,
+                    removed_by: c.removed_by,
+                    removed_at: now,
+                })])
+            }
+
+            EdgeCommand::ArchiveEdge(c) => {
+                self.check_command(&c.relationship_id, Some(EdgeState::Archived))?;
+                Ok(vec![EdgeEvent::Archived(EdgeArchived {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    edge_id: c.relationship_id,
+                    reason: c.reason,
+                    archived_by: c.archived_by,
+                    archived_at: now,
+                })])
+            }
+
+            EdgeCommand::RestoreEdge(c) => {
+                // Archived is terminal for every other command
+                self.check_target(&c.relationship_id)?;
+                if self.state != EdgeState::Archived {
+                    return Err(RelationshipError::InvalidStateTransition(format!(
+                        "edge {} is not archived",
+                        self.id
+                    )));
+                }
+                let to = self.archived_from.unwrap_or(EdgeState::Terminated);
+                self.guards
+                    .check_edge(self, &to)
+                    .map_err(RelationshipError::InvalidStateTransition)?;
+                Ok(vec![EdgeEvent::Restored(EdgeRestored {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    edge_id: c.relationship_id,
+                    restored_by: c.restored_by,
+                    restored_at: now,
+                })])
+            }
+
+            EdgeCommand::RenameEdge(c) => {
+                self.check_command(&c.relationship_id, None)?;
+                let name = check_name(&c.name)?;
+                if name == self.name {
+                    return Ok(Vec::new());
+                }
+                Ok(vec![EdgeEvent::Renamed(EdgeRenamed {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    edge_id: c.relationship_id,
+                    old_name: self.name.clone(),
+                    new_name: name,
+                    renamed_by: c.renamed_by,
+                    renamed_at: now,
+                })])
+            }
+
+            EdgeCommand::DescribeEdge(c) => {
+                self.check_command(&c.relationship_id, None)?;
+                let description = c.description.map(|d| d.trim().to_string()).filter(|d| !d.is_empty());
+                if description == self.description {
+                    return Ok(Vec::new());
+                }
+                Ok(vec![EdgeEvent::DescriptionUpdated(EdgeDescriptionUpdated {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    edge_id: c.relationship_id,
+                    description,
+                    updated_by: c.updated_by,
+                    updated_at: now,
+                })])
+            }
+        }
+    }
+
+    /// Check that the reproposal policy allows re-proposing at `now`
+    fn check_reproposal(&self, now: DateTime<Utc>) -> RelationshipResult<()> {
+        let policy = &self.reproposal_policy;
+        if self.reproposals >= policy.max_reproposals {
+            return Err(RelationshipError::InvalidStateTransition(format!(
+                "edge {} was already re-proposed {} times",
+                self.id, self.reproposals
+            )));
+        }
+        if let Some(last) = self.rejections.last() {
+            let eligible_at = policy.eligible_at(last.rejected_at);
+            if now < eligible_at {
+                return Err(RelationshipError::InvalidStateTransition(format!(
+                    "edge {} may be re-proposed from {}",
+                    self.id, eligible_at
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that a command targets this edge and, if it changes state,
+    /// that the transition is allowed and passes the guards
+    fn check_command(&self, edge_id: &RelationshipId, to: Option<EdgeState>) -> RelationshipResult<()> {
+        self.check_target(edge_id)?;
+        match to {
+            Some(to) if !self.state.can_transition_to(&to) => Err(RelationshipError::InvalidStateTransition(
+                format!("Cannot transition from {:?} to {:?}", self.state, to),
+            )),
+            Some(to) => self
+                .guards
+                .check_edge(self, &to)
+                .map_err(RelationshipError::InvalidStateTransition),
+            None if self.state.is_terminal() => Err(RelationshipError::InvalidStateTransition(format!(
+                "edge {} is {:?}",
+                self.id, self.state
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Check that a command targets this edge
+    fn check_target(&self, edge_id: &RelationshipId) -> RelationshipResult<()> {
+        if *edge_id != self.id {
+            return Err(RelationshipError::InvalidRelationship(format!(
+                "command for edge {} sent to edge {}",
+                edge_id, self.id
+            )));
+        }
+        Ok(())
+    }
+
+    /// Check that a consent command targets this edge while consent is
+    /// pending, from one of its endpoints
+    fn check_consent(&self, edge_id: &RelationshipId, party: &EntityRef) -> RelationshipResult<()> {
+        self.check_command(edge_id, None)?;
+        if self.state != EdgeState::PendingConsent {
+            return Err(RelationshipError::InvalidStateTransition(format!(
+                "edge {} is not waiting for consent",
+                self.id
+            )));
+        }
+        if !party.same_entity(&self.source) && !party.same_entity(&self.target) {
+            return Err(RelationshipError::InvalidRelationship(format!(
+                "{} is not an endpoint of edge {}",
+                party, self.id
+            )));
+        }
+        Ok(())
+    }
+
     // ---- Event Sourcing ----
 
     /// Apply an event to produce the next state (pure functional)
@@ -346,6 +1242,20 @@ impl EdgeConcept {
                         serde_json::Value::String(reason.clone()),
                     );
                 }
+                next.rejections.push(Rejection {
+                    reason: e.reason.clone(),
+                    rejected_by: e.rejected_by.clone(),
+                    rejected_at: e.rejected_at,
+                });
+            }
+
+            EdgeEvent::Reproposed(_) => {
+                // The rejection stays in `rejections`
+                next.state = EdgeState::Proposed;
+                next.reproposals += 1;
+                next.properties.remove("rejection_reason");
+                next.consents.clear();
+                next.consent_deadline = None;
             }
 
             EdgeEvent::QualityUpdated(e) => {
@@ -357,7 +1267,13 @@ impl EdgeConcept {
                 if !next.evidence_cids.contains(&e.evidence_cid) {
                     next.evidence_cids.push(e.evidence_cid.clone());
                 }
-                // Update confidence based on evidence
+                // Evidence raises confidence; a decided confidence stays
+                next.confidence = next.confidence.max((next.evidence_cids.len() as f64 / 10.0).min(1.0));
+            }
+
+            EdgeEvent::EvidenceRevoked(e) => {
+                next.evidence_cids.retain(|cid| cid != &e.evidence_cid);
+                next.revoked_evidence.insert(e.evidence_cid.clone());
                 next.confidence = (next.evidence_cids.len() as f64 / 10.0).min(1.0);
             }
 
@@ -367,7 +1283,81 @@ impl EdgeConcept {
             }
 
             EdgeEvent::PropertyUpdated(e) => {
-                next.properties.insert(e.key.clone(), e.value.clone());
+                next.record_property(&e.key, Some(e.value.clone()), e.updated_by.clone(), e.updated_at);
+            }
+
+            EdgeEvent::PropertyRemoved(e) => {
+                next.record_property(&e.key, None, Some(e.removed_by.clone()), e.removed_at);
+            }
+
+            EdgeEvent::TagAdded(e) => {
+                next.tags.insert(e.tag.clone());
+            }
+
+            EdgeEvent::TagRemoved(e) => {
+                next.tags.remove(&e.tag);
+            }
+
+            EdgeEvent::Archived(_) => {
+                next.archived_from = Some(next.state);
+                next.state = EdgeState::Archived;
+            }
+
+            EdgeEvent::Restored(_) => {
+                next.state = next.archived_from.take().unwrap_or(EdgeState::Terminated);
+            }
+
+            EdgeEvent::Renamed(e) => {
+                next.name = e.new_name.clone();
+            }
+
+            EdgeEvent::DescriptionUpdated(e) => {
+                next.description = e.description.clone();
+            }
+
+            EdgeEvent::EndpointsRewritten(e) => {
+                next.redirects.push(Redirect {
+                    old_source: e.old_source.clone(),
+                    old_target: e.old_target.clone(),
+                    reason: e.reason.clone(),
+                    redirected_by: e.rewritten_by.clone(),
+                    redirected_at: e.rewritten_at,
+                });
+                next.source = e.new_source.clone();
+                next.target = e.new_target.clone();
+            }
+
+            EdgeEvent::Reversed(e) => {
+                std::mem::swap(&mut next.source, &mut next.target);
+                next.category = e.new_category.clone();
+            }
+
+            EdgeEvent::ConsentRequested(e) => {
+                next.state = EdgeState::PendingConsent;
+                next.consents.clear();
+                next.consent_deadline = e.expires_at;
+            }
+
+            EdgeEvent::ConsentGranted(e) => {
+                next.consents.insert(e.party.key());
+            }
+
+            EdgeEvent::ConsentDeclined(e) => {
+                next.consents.remove(&e.party.key());
+            }
+
+            EdgeEvent::TemplateApplied(e) => {
+                let applied_by = Some(e.applied_by.clone()).filter(|by| !by.is_empty());
+                let mut keys: Vec<&String> = e.properties.keys().collect();
+                keys.sort();
+                for key in keys {
+                    next.record_property(key, Some(e.properties[key].clone()), applied_by.clone(), e.applied_at);
+                }
+                let template = serde_json::Value::String(e.template.clone());
+                next.record_property("template", Some(template), applied_by, e.applied_at);
+                if e.valid_until.is_some() {
+                    next.validity.ends_at = e.valid_until;
+                }
             }
         }
 
@@ -400,9 +1390,20 @@ impl EdgeConcept {
                     knowledge_level: KnowledgeLevel::Unknown,
                     confidence: 0.0,
                     evidence_cids: Vec::new(),
+                    revoked_evidence: BTreeSet::new(),
                     state: EdgeState::Proposed,
                     validity: ValidityPeriod::ongoing(e.created_at),
+                    consents: HashSet::new(),
+                    consent_deadline: None,
+                    rejections: Vec::new(),
+                    reproposals: 0,
+                    reproposal_policy: ReproposalPolicy::default(),
+                    redirects: Vec::new(),
+                    archived_from: None,
+                    guards: TransitionGuards::new(),
                     properties: HashMap::new(),
+                    tags: BTreeSet::new(),
+                    property_history: HashMap::new(),
                     version: 0,
                     created_at: e.created_at,
                     updated_at: e.created_at,
@@ -424,10 +1425,136 @@ impl EdgeConcept {
     }
 }
 
+/// Unrevoked evidence CIDs and confidence a knowledge step needs; `None`
+/// for anything but one step up
+fn knowledge_threshold(from: &KnowledgeLevel, to: &KnowledgeLevel) -> Option<(usize, f64)> {
+    match (from, to) {
+        (KnowledgeLevel::Unknown, KnowledgeLevel::Suspected) => Some((1, 0.0)),
+        (KnowledgeLevel::Suspected, KnowledgeLevel::Known) => Some((3, 0.7)),
+        _ => None,
+    }
+}
+
+/// Highest level up to `level` that `evidence` unrevoked CIDs still support
+fn supported_knowledge(level: &KnowledgeLevel, evidence: usize) -> KnowledgeLevel {
+    match level {
+        _ if evidence == 0 => KnowledgeLevel::Unknown,
+        KnowledgeLevel::Known if evidence < 3 => KnowledgeLevel::Suspected,
+        _ => *level,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use uuid::Uuid;
+    use crate::commands::{
+        ActivateEdge, AddEdgeEvidence, CreateEdge, RedirectEdge, RejectEdge, ResumeEdge, ReverseEdge, SuspendEdge,
+    };
+
+    #[test]
+    fn test_handle_command() {
+        let edge_id = RelationshipId::new();
+        let create = EdgeCommand::CreateEdge(CreateEdge {
+            identity: MessageIdentity::new_root(),
+            edge_id,
+            source: EntityRef::person(Uuid::now_v7()),
+            target: EntityRef::organization(Uuid::now_v7()),
+            category: RelationshipCategory::Employment,
+            name: "Employment".to_string(),
+            quality: Some(RelationshipQuality::default_employment()),
+            created_by: "test".to_string(),
+        });
+        let blank = EdgeConcept::new(
+            "",
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::person(Uuid::now_v7()),
+            RelationshipCategory::Employment,
+        );
+        let events = blank.handle_command(create.clone()).unwrap();
+        assert_eq!(events.len(), 2);
+        let mut edge = EdgeConcept::from_events(&events).unwrap();
+        assert_eq!(edge.id, edge_id);
+        assert_eq!(edge.quality.strength, RelationshipQuality::default_employment().strength);
+        assert!(edge.handle_command(create).is_err());
+
+        let run = |edge: &EdgeConcept, cmd| -> RelationshipResult<EdgeConcept> {
+            edge.handle_command(cmd)?
+                .iter()
+                .try_fold(edge.clone(), |edge, event| edge.apply_event_pure(event))
+        };
+        let resume = EdgeCommand::ResumeEdge(ResumeEdge {
+            identity: MessageIdentity::new_root(),
+            edge_id,
+            resumed_by: "test".to_string(),
+        });
+        assert!(matches!(
+            run(&edge, resume.clone()),
+            Err(RelationshipError::InvalidStateTransition(_))
+        ));
+
+        edge = run(&edge, EdgeCommand::ActivateEdge(ActivateEdge {
+            identity: MessageIdentity::new_root(),
+            edge_id,
+            activated_by: "test".to_string(),
+        }))
+        .unwrap();
+        edge = run(&edge, EdgeCommand::SuspendEdge(SuspendEdge {
+            identity: MessageIdentity::new_root(),
+            edge_id,
+            reason: None,
+            suspended_by: "test".to_string(),
+        }))
+        .unwrap();
+        edge = run(&edge, resume).unwrap();
+        assert_eq!(edge.state, EdgeState::Active);
+
+        let evidence = EdgeCommand::AddEdgeEvidence(AddEdgeEvidence {
+            identity: MessageIdentity::new_root(),
+            edge_id,
+            evidence_cid: "bafy-contract".to_string(),
+            evidence_type: "contract".to_string(),
+        });
+        edge = run(&edge, evidence.clone()).unwrap();
+        assert!(edge.handle_command(evidence).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_edge_composition() {
+        let person = EntityRef::person(Uuid::now_v7());
+        let company = EntityRef::organization(Uuid::now_v7());
+        let association = EntityRef::organization(Uuid::now_v7());
+
+        let employment = EdgeConcept::new("Works at", person.clone(), company.clone(), RelationshipCategory::Employment)
+            .with_quality(RelationshipQuality::default_employment());
+        // The link may be pinned differently on either side
+        let membership = EdgeConcept::new(
+            "Member of",
+            company.with_version(3),
+            association.clone(),
+            RelationshipCategory::Membership,
+        )
+        .with_quality(RelationshipQuality::default_membership());
+
+        let authority = employment.compose(&membership).unwrap();
+        assert_eq!(authority.source, person);
+        assert_eq!(authority.target, association);
+        assert_eq!(authority.category, RelationshipCategory::Custom("authority".to_string()));
+        assert_eq!(authority.state, EdgeState::Proposed);
+        let expected = employment.quality.strength * membership.quality.strength;
+        assert!((authority.quality.strength - expected).abs() < 1e-12);
+        assert_eq!(
+            authority.quality.trust,
+            employment.quality.trust.min(membership.quality.trust)
+        );
+
+        // Endpoints must meet, and the pair must have a rule
+        assert!(membership.compose(&employment).is_err());
+        let other = EdgeConcept::new("Owns", authority.target.clone(), person, RelationshipCategory::Ownership);
+        assert!(matches!(
+            membership.compose(&other),
+            Err(crate::RelationshipError::UnknownComposition(_))
+        ));
+    }
 
     #[test]
     fn test_edge_creation() {
@@ -491,6 +1618,33 @@ mod tests {
         assert!(edge.terminate("Invalid").is_err());
     }
 
+    #[test]
+    fn test_reject() {
+        let mut edge = EdgeConcept::new(
+            "Test",
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::organization(Uuid::now_v7()),
+            RelationshipCategory::Employment,
+        );
+        let mut decided = edge.clone();
+
+        edge.reject("hr", Some("position filled".to_string())).unwrap();
+        let events = decided
+            .handle_command(EdgeCommand::RejectEdge(RejectEdge {
+                identity: MessageIdentity::new_root(),
+                edge_id: decided.id,
+                reason: Some("position filled".to_string()),
+                rejected_by: "hr".to_string(),
+            }))
+            .unwrap();
+        decided = decided.apply_event_pure(&events[0]).unwrap();
+
+        assert_eq!(edge.state, EdgeState::Rejected);
+        assert_eq!(edge.properties, decided.properties);
+        assert_eq!(edge.rejections[0].rejected_by, decided.rejections[0].rejected_by);
+        assert_eq!(edge.rejections[0].reason, decided.rejections[0].reason);
+    }
+
     #[test]
     fn test_similarity() {
         let source1 = EntityRef::person(Uuid::now_v7());
@@ -516,4 +1670,307 @@ mod tests {
         let similarity = edge1.similarity(&edge3);
         assert!(similarity < 0.7);
     }
+
+    #[test]
+    fn test_reverse_edge() {
+        let (wheel, car) = (EntityRef::concept(Uuid::now_v7()), EntityRef::concept(Uuid::now_v7()));
+        let edge = EdgeConcept::new("Wheel", car.clone(), wheel.clone(), RelationshipCategory::PartOf);
+        let reverse = |invert_category| {
+            EdgeCommand::ReverseEdge(ReverseEdge {
+                identity: MessageIdentity::new_root(),
+                edge_id: edge.id,
+                invert_category,
+                reason: "recorded backwards".to_string(),
+                reversed_by: "test".to_string(),
+            })
+        };
+
+        let events = edge.handle_command(reverse(false)).unwrap();
+        let reversed = edge.apply_event_pure(&events[0]).unwrap();
+        assert_eq!((&reversed.source, &reversed.target), (&wheel, &car));
+        assert_eq!(reversed.category, RelationshipCategory::PartOf);
+
+        // Car contains wheel
+        let events = edge.handle_command(reverse(true)).unwrap();
+        let inverted = edge.apply_event_pure(&events[0]).unwrap();
+        assert_eq!((&inverted.source, &inverted.target), (&wheel, &car));
+        assert_eq!(inverted.category, RelationshipCategory::Contains);
+
+        let employment = EdgeConcept::new("Job", car, wheel, RelationshipCategory::Employment);
+        let cmd = EdgeCommand::ReverseEdge(ReverseEdge {
+            identity: MessageIdentity::new_root(),
+            edge_id: employment.id,
+            invert_category: true,
+            reason: String::new(),
+            reversed_by: "test".to_string(),
+        });
+        assert!(employment.handle_command(cmd).is_err());
+    }
+
+    #[test]
+    fn test_redirect_edge() {
+        let (alice, acme, acme_holdings) = (
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::organization(Uuid::now_v7()),
+            EntityRef::organization(Uuid::now_v7()),
+        );
+        let mut edge = EdgeConcept::new("Job", alice.clone(), acme.clone(), RelationshipCategory::Employment)
+            .with_quality(RelationshipQuality::default_employment());
+        edge.evidence_cids.push("bafy-contract".to_string());
+        let redirect = |new_source, new_target| {
+            EdgeCommand::RedirectEdge(RedirectEdge {
+                identity: MessageIdentity::new_root(),
+                edge_id: edge.id,
+                new_source,
+                new_target,
+                reason: "employer restructured".to_string(),
+                redirected_by: "test".to_string(),
+            })
+        };
+
+        assert!(edge.handle_command(redirect(None, None)).is_err());
+        assert!(edge.handle_command(redirect(None, Some(acme.clone()))).unwrap().is_empty());
+
+        let events = edge.handle_command(redirect(None, Some(acme_holdings.clone()))).unwrap();
+        let moved = edge.apply_event_pure(&events[0]).unwrap();
+        assert_eq!((&moved.source, &moved.target), (&alice, &acme_holdings));
+        assert_eq!(moved.evidence_cids, edge.evidence_cids);
+        assert_eq!(moved.quality.strength, edge.quality.strength);
+        assert_eq!(moved.redirects.len(), 1);
+        assert_eq!(moved.redirects[0].old_target, acme);
+        assert_eq!(moved.redirects[0].redirected_by, "test");
+    }
+
+    #[test]
+    fn test_consent_workflow() {
+        use crate::commands::{DeclineConsent, ExpireConsent, GrantConsent, RequestConsent};
+
+        let (alice, bob) = (EntityRef::person(Uuid::now_v7()), EntityRef::person(Uuid::now_v7()));
+        let edge = EdgeConcept::new("Friends", alice.clone(), bob.clone(), RelationshipCategory::Friendship);
+        let request = |expires_at| {
+            EdgeCommand::RequestConsent(RequestConsent {
+                identity: MessageIdentity::new_root(),
+                edge_id: edge.id,
+                expires_at,
+                requested_by: "test".to_string(),
+            })
+        };
+        let grant = |party: &EntityRef| {
+            EdgeCommand::GrantConsent(GrantConsent {
+                identity: MessageIdentity::new_root(),
+                edge_id: edge.id,
+                party: party.clone(),
+            })
+        };
+        let apply = |edge: EdgeConcept, events: Vec<EdgeEvent>| {
+            events.iter().try_fold(edge, |e, event| e.apply_event_pure(event)).unwrap()
+        };
+
+        // Both endpoints consent
+        let pending = apply(edge.clone(), edge.handle_command(request(None)).unwrap());
+        assert_eq!(pending.state, EdgeState::PendingConsent);
+        assert!(pending.handle_command(grant(&EntityRef::person(Uuid::now_v7()))).is_err());
+        let half = apply(pending.clone(), pending.handle_command(grant(&alice)).unwrap());
+        assert_eq!(half.state, EdgeState::PendingConsent);
+        assert!(half.handle_command(grant(&alice)).unwrap().is_empty());
+        let friends = apply(half.clone(), half.handle_command(grant(&bob)).unwrap());
+        assert_eq!(friends.state, EdgeState::Active);
+
+        // One declines
+        let declined = half
+            .handle_command(EdgeCommand::DeclineConsent(DeclineConsent {
+                identity: MessageIdentity::new_root(),
+                edge_id: edge.id,
+                party: bob.clone(),
+                reason: None,
+            }))
+            .unwrap();
+        assert_eq!(apply(half, declined).state, EdgeState::Rejected);
+
+        // Nobody answers in time
+        let expire = EdgeCommand::ExpireConsent(ExpireConsent {
+            identity: MessageIdentity::new_root(),
+            edge_id: edge.id,
+        });
+        let waiting = apply(edge.clone(), edge.handle_command(request(Some(Utc::now() + chrono::Duration::days(7)))).unwrap());
+        assert!(waiting.handle_command(expire.clone()).is_err());
+        let lapsed = apply(edge.clone(), edge.handle_command(request(Some(Utc::now()))).unwrap());
+        let expired = apply(lapsed.clone(), lapsed.handle_command(expire).unwrap());
+        assert_eq!(expired.state, EdgeState::Rejected);
+    }
+
+    #[test]
+    fn test_revoke_evidence() {
+        use crate::commands::{AddEdgeEvidence, RevokeEdgeEvidence};
+
+        let mut edge = EdgeConcept::new(
+            "Job",
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::organization(Uuid::now_v7()),
+            RelationshipCategory::Employment,
+        );
+        edge.evidence_cids = vec!["bafy-badge".to_string(), "bafy-forged".to_string()];
+        edge.confidence = 0.2;
+        edge.knowledge_level = KnowledgeLevel::Known;
+        let revoke = |cid: &str| {
+            EdgeCommand::RevokeEdgeEvidence(RevokeEdgeEvidence {
+                identity: MessageIdentity::new_root(),
+                edge_id: edge.id,
+                evidence_cid: cid.to_string(),
+                reason: "forged".to_string(),
+                revoked_by: "audit".to_string(),
+            })
+        };
+        let apply = |edge: &EdgeConcept, events: Vec<EdgeEvent>| {
+            events.iter().try_fold(edge.clone(), |e, event| e.apply_event_pure(event)).unwrap()
+        };
+
+        let events = edge.handle_command(revoke("bafy-forged")).unwrap();
+        assert!(matches!(events[..], [EdgeEvent::EvidenceRevoked(_), EdgeEvent::KnowledgeProgressed(_)]));
+        let edge = apply(&edge, events);
+        assert_eq!(edge.evidence_cids, vec!["bafy-badge".to_string()]);
+        assert_eq!(edge.confidence, 0.1);
+        assert_eq!(edge.knowledge_level, KnowledgeLevel::Suspected);
+        assert!(edge.handle_command(revoke("bafy-forged")).unwrap().is_empty());
+
+        // Revoked evidence cannot come back
+        let add = EdgeCommand::AddEdgeEvidence(AddEdgeEvidence {
+            identity: MessageIdentity::new_root(),
+            edge_id: edge.id,
+            evidence_cid: "bafy-forged".to_string(),
+            evidence_type: "badge".to_string(),
+        });
+        assert!(edge.handle_command(add).is_err());
+
+        // Revoking the last evidence regresses the knowledge level
+        let events = edge.handle_command(revoke("bafy-badge")).unwrap();
+        assert!(matches!(events[..], [EdgeEvent::EvidenceRevoked(_), EdgeEvent::KnowledgeProgressed(_)]));
+        let edge = apply(&edge, events);
+        assert!(edge.evidence_cids.is_empty());
+        assert_eq!(edge.knowledge_level, KnowledgeLevel::Unknown);
+        assert_eq!(edge.confidence, 0.0);
+    }
+
+    #[test]
+    fn test_progress_knowledge() {
+        use crate::commands::ProgressKnowledge;
+
+        let edge = EdgeConcept::new(
+            "Job",
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::organization(Uuid::now_v7()),
+            RelationshipCategory::Employment,
+        );
+        let progress = |edge: &EdgeConcept, to_level, confidence| {
+            edge.handle_command(EdgeCommand::ProgressKnowledge(ProgressKnowledge {
+                identity: MessageIdentity::new_root(),
+                edge_id: edge.id,
+                to_level,
+                confidence,
+                reason: "review".to_string(),
+            }))
+        };
+        let add = |edge: EdgeConcept, cid: &str| {
+            let events = edge
+                .handle_command(EdgeCommand::AddEdgeEvidence(AddEdgeEvidence {
+                    identity: MessageIdentity::new_root(),
+                    edge_id: edge.id,
+                    evidence_cid: cid.to_string(),
+                    evidence_type: "document".to_string(),
+                }))
+                .unwrap();
+            events.iter().try_fold(edge, |e, event| e.apply_event_pure(event)).unwrap()
+        };
+
+        // Suspected needs a CID
+        assert!(matches!(
+            progress(&edge, KnowledgeLevel::Suspected, 0.5),
+            Err(RelationshipError::InsufficientEvidence(_))
+        ));
+        let edge = add(edge, "bafy-offer");
+        assert_eq!(edge.confidence, 0.1);
+
+        // No skipping levels
+        assert!(matches!(
+            progress(&edge, KnowledgeLevel::Known, 0.9),
+            Err(RelationshipError::InvalidStateTransition(_))
+        ));
+        let events = progress(&edge, KnowledgeLevel::Suspected, 0.5).unwrap();
+        let edge = edge.apply_event_pure(&events[0]).unwrap();
+        assert_eq!((edge.knowledge_level, edge.confidence), (KnowledgeLevel::Suspected, 0.5));
+
+        // Known needs three CIDs and confidence 0.7
+        assert!(matches!(
+            progress(&edge, KnowledgeLevel::Known, 0.9),
+            Err(RelationshipError::InsufficientEvidence(_))
+        ));
+        let edge = add(add(edge, "bafy-contract"), "bafy-payslip");
+        // The decided confidence survives the new evidence
+        assert_eq!(edge.confidence, 0.5);
+        assert!(matches!(
+            progress(&edge, KnowledgeLevel::Known, 0.6),
+            Err(RelationshipError::InsufficientEvidence(_))
+        ));
+        let events = progress(&edge, KnowledgeLevel::Known, 0.8).unwrap();
+        let edge = edge.apply_event_pure(&events[0]).unwrap();
+        assert_eq!((edge.knowledge_level, edge.confidence), (KnowledgeLevel::Known, 0.8));
+        let edge = add(edge, "bafy-reference");
+        assert_eq!((edge.evidence_cids.len(), edge.confidence), (4, 0.8));
+    }
+
+    #[test]
+    fn test_property_history() {
+        use crate::commands::{RemoveEdgeProperty, SetEdgeProperty};
+
+        let edge = EdgeConcept::new(
+            "Job",
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::organization(Uuid::now_v7()),
+            RelationshipCategory::Employment,
+        );
+        let set = |value: serde_json::Value, expected_version, by: &str| {
+            EdgeCommand::SetEdgeProperty(SetEdgeProperty {
+                identity: MessageIdentity::new_root(),
+                edge_id: edge.id,
+                key: "title".to_string(),
+                value,
+                expected_version,
+                set_by: by.to_string(),
+            })
+        };
+        let decide = |edge: &EdgeConcept, cmd| {
+            edge.handle_command(cmd)
+                .and_then(|events| events.iter().try_fold(edge.clone(), |e, event| e.apply_event_pure(event)))
+        };
+
+        let v1 = decide(&edge, set(serde_json::json!("Engineer"), Some(0), "hr")).unwrap();
+        let v2 = decide(&v1, set(serde_json::json!("Lead"), None, "manager")).unwrap();
+        // A writer that read version 1 must not overwrite version 2
+        assert!(decide(&v2, set(serde_json::json!("Intern"), Some(1), "hr")).is_err());
+
+        let removed = decide(
+            &v2,
+            EdgeCommand::RemoveEdgeProperty(RemoveEdgeProperty {
+                identity: MessageIdentity::new_root(),
+                edge_id: edge.id,
+                key: "title".to_string(),
+                expected_version: Some(2),
+                removed_by: "hr".to_string(),
+            }),
+        )
+        .unwrap();
+        assert!(!removed.properties.contains_key("title"));
+
+        let history = removed.property_history("title");
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[1].value, Some(serde_json::json!("Lead")));
+        assert_eq!(history[1].changed_by.as_deref(), Some("manager"));
+        assert_eq!(history[2].value, None);
+        assert_eq!(removed.property_version("title"), 3);
+        assert_eq!(
+            removed.property_at("title", history[1].changed_at),
+            Some(&serde_json::json!("Lead"))
+        );
+        assert!(removed.property_history("grade").is_empty());
+    }
 }
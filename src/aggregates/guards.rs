@@ -0,0 +1,199 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Transition Guards
+//!
+//! The state machines allow a transition whenever its states are adjacent.
+//! Guards add business preconditions, configured per category:
+//!
+//! ```text
+//! Employment   Proposed  --> Active       requires evidence
+//! Ownership    Active    --> Terminated   requires Legal formality
+//! ```
+//!
+//! A guard is a predicate over the aggregate as it is before the
+//! transition; it returns the reason for refusing. Guards are evaluated by
+//! `transition_to` and the other transition methods, and by command
+//! handling, except `ExpireEdge`: an edge whose validity has ended ends
+//! whatever its category requires. Applying events does not evaluate them:
+//! an event records a transition that was already allowed.
+//!
+//! Guards are code, so aggregates do not serialize them. A space installs
+//! its guards (`RelationshipSpace::with_transition_guards`) on every
+//! relationship it holds, including ones rebuilt from events.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! let guards = TransitionGuards::new()
+//!     .with_edge_guard(RelationshipCategory::Employment, EdgeState::Active, "evidence", |edge| {
+//!         if edge.evidence_cids.is_empty() {
+//!             Err("no evidence".to_string())
+//!         } else {
+//!             Ok(())
+//!         }
+//!     });
+//! ```
+
+use super::{EdgeConcept, EdgeState, HyperEdgeConcept, HyperEdgeState};
+use crate::value_objects::RelationshipCategory;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+type Predicate<A> = Arc<dyn Fn(&A) -> Result<(), String> + Send + Sync>;
+
+/// A named precondition on entering a state
+struct Guard<A, S> {
+    name: String,
+    to: S,
+    check: Predicate<A>,
+}
+
+impl<A, S: Clone> Clone for Guard<A, S> {
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            to: self.to.clone(),
+            check: Arc::clone(&self.check),
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+struct GuardTable {
+    edges: HashMap<RelationshipCategory, Vec<Guard<EdgeConcept, EdgeState>>>,
+    hyperedges: HashMap<RelationshipCategory, Vec<Guard<HyperEdgeConcept, HyperEdgeState>>>,
+}
+
+/// Guard predicates on edge and hyperedge transitions, per category
+///
+/// Cheap to clone: aggregates share one table.
+#[derive(Clone, Default)]
+pub struct TransitionGuards {
+    table: Arc<GuardTable>,
+}
+
+impl std::fmt::Debug for TransitionGuards {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TransitionGuards")
+            .field("edges", &names(&self.table.edges))
+            .field("hyperedges", &names(&self.table.hyperedges))
+            .finish()
+    }
+}
+
+fn names<A, S>(guards: &HashMap<RelationshipCategory, Vec<Guard<A, S>>>) -> HashMap<&RelationshipCategory, Vec<&str>> {
+    guards
+        .iter()
+        .map(|(category, guards)| (category, guards.iter().map(|g| g.name.as_str()).collect()))
+        .collect()
+}
+
+impl TransitionGuards {
+    /// Create an empty guard set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Guard edges of a category entering a state
+    pub fn with_edge_guard<F>(mut self, category: RelationshipCategory, to: EdgeState, name: impl Into<String>, check: F) -> Self
+    where
+        F: Fn(&EdgeConcept) -> Result<(), String> + Send + Sync + 'static,
+    {
+        Arc::make_mut(&mut self.table).edges.entry(category).or_default().push(Guard {
+            name: name.into(),
+            to,
+            check: Arc::new(check),
+        });
+        self
+    }
+
+    /// Guard hyperedges of a category entering a state
+    pub fn with_hyperedge_guard<F>(
+        mut self,
+        category: RelationshipCategory,
+        to: HyperEdgeState,
+        name: impl Into<String>,
+        check: F,
+    ) -> Self
+    where
+        F: Fn(&HyperEdgeConcept) -> Result<(), String> + Send + Sync + 'static,
+    {
+        Arc::make_mut(&mut self.table).hyperedges.entry(category).or_default().push(Guard {
+            name: name.into(),
+            to,
+            check: Arc::new(check),
+        });
+        self
+    }
+
+    /// Check if no guard is configured
+    pub fn is_empty(&self) -> bool {
+        self.table.edges.is_empty() && self.table.hyperedges.is_empty()
+    }
+
+    /// Evaluate the guards on an edge entering a state
+    pub fn check_edge(&self, edge: &EdgeConcept, to: &EdgeState) -> Result<(), String> {
+        evaluate(self.table.edges.get(&edge.category), edge, to)
+    }
+
+    /// Evaluate the guards on a hyperedge entering a state
+    pub fn check_hyperedge(&self, hyperedge: &HyperEdgeConcept, to: &HyperEdgeState) -> Result<(), String> {
+        evaluate(self.table.hyperedges.get(&hyperedge.category), hyperedge, to)
+    }
+}
+
+fn evaluate<A, S: PartialEq + std::fmt::Debug>(guards: Option<&Vec<Guard<A, S>>>, aggregate: &A, to: &S) -> Result<(), String> {
+    guards
+        .into_iter()
+        .flatten()
+        .filter(|g| g.to == *to)
+        .try_for_each(|g| (g.check)(aggregate).map_err(|reason| format!("guard '{}' refused {:?}: {}", g.name, to, reason)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value_objects::{EntityRef, Formality};
+    use uuid::Uuid;
+
+    #[test]
+    fn test_edge_guards() {
+        let guards = TransitionGuards::new()
+            .with_edge_guard(RelationshipCategory::Employment, EdgeState::Active, "evidence", |edge| {
+                if edge.evidence_cids.is_empty() {
+                    Err("no evidence".to_string())
+                } else {
+                    Ok(())
+                }
+            })
+            .with_edge_guard(RelationshipCategory::Ownership, EdgeState::Terminated, "legal", |edge| {
+                if edge.quality.formality == Formality::Legal {
+                    Ok(())
+                } else {
+                    Err("ownership ends only by legal act".to_string())
+                }
+            });
+        let (alice, acme) = (EntityRef::person(Uuid::now_v7()), EntityRef::organization(Uuid::now_v7()));
+
+        let mut employment = EdgeConcept::new("Job", alice.clone(), acme.clone(), RelationshipCategory::Employment)
+            .with_guards(guards.clone());
+        let err = employment.activate().unwrap_err();
+        assert!(err.contains("evidence"));
+        assert_eq!(employment.state, EdgeState::Proposed);
+        employment.evidence_cids.push("bafy-contract".to_string());
+        employment.activate().unwrap();
+
+        // Guards bind to their category only
+        let mut friendship =
+            EdgeConcept::new("Pals", alice.clone(), acme.clone(), RelationshipCategory::Friendship).with_guards(guards.clone());
+        friendship.activate().unwrap();
+
+        let mut ownership = EdgeConcept::new("Stake", alice, acme, RelationshipCategory::Ownership).with_guards(guards);
+        ownership.activate().unwrap();
+        assert!(ownership.terminate("sold").is_err());
+        assert_eq!(ownership.state, EdgeState::Active);
+        assert!(ownership.validity.is_active());
+    }
+}
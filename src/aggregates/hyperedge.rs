@@ -17,16 +17,57 @@
 //! - Team membership: [Person1, Person2, Person3] -> Team
 //! - Project assignment: [Team1, Team2] -> [Project1, Project2]
 //! - Document collaboration: [Author1, Author2, Reviewer1] -> Document
+//!
+//! ## Quorum Activation
+//!
+//! A hyperedge created with `ActivationMode::Quorum` becomes Active only
+//! once participants holding the threshold share of the total weight have
+//! acknowledged it, as agreements and multi-party contracts require:
+//!
+//! ```text
+//! Forming --ParticipantAcknowledged--> Forming   (below quorum)
+//!         --ParticipantAcknowledged--> Active    (quorum reached;
+//!                                                 HyperEdgeActivated follows)
+//! ```
+//!
+//! A removed participant's acknowledgement no longer counts.
+//!
+//! ## Restructuring
+//!
+//! Changing several participants at once passes through states that break
+//! the hyperedge's role constraints. Restructuring takes it out of service
+//! meanwhile; the constraints are checked again on completion:
+//!
+//! ```text
+//! Active --BeginRestructuring--> Restructuring --CompleteRestructuring--> Active
+//!                                  (participant changes)
+//! ```
+//!
+//! A space can allow participant changes to active hyperedges of a
+//! category only while restructuring
+//! (`RelationshipSpace::with_restructuring_category`).
 
-use crate::events::HyperEdgeEvent;
-use crate::quality::{QualityPoint, RelationshipQuality};
-use crate::value_objects::{EntityRef, IncidenceMatrix, ParticipantRole, RelationshipCategory, RelationshipId, ValidityPeriod};
-use crate::RelationshipResult;
+use super::{check_name, check_quality, check_tag, TransitionGuards};
+use crate::commands::HyperEdgeCommand;
+use crate::events::{
+    HyperEdgeActivated, HyperEdgeArchived, HyperEdgeCreated, HyperEdgeEvent, HyperEdgeQualityUpdated, HyperEdgeRestored, HyperEdgeRenamed, HyperEdgeDescriptionUpdated, HyperEdgeTagAdded, HyperEdgeTagRemoved, HyperEdgeTerminated, ParticipantAcknowledged,
+    ParticipantAdded, ParticipantAssignedToGroup, ParticipantGroupAdded, ParticipantGroupRemoved, ParticipantRemoved,
+    ParticipantUnassignedFromGroup, ParticipantRoleChanged, ParticipantWeightChanged,
+    RestructuringBegun, RestructuringCompleted,
+};
+use crate::quality::{DurationModel, QualityPoint, RelationshipQuality};
+use crate::value_objects::{
+    normalize_tag, ActivationMode, EntityKey, EntityRef, IncidenceMatrix, ParticipantEntry, ParticipantGroup, ParticipantRole, RelationshipCategory,
+    RelationshipId, ValidityPeriod, WeightChange,
+};
+use crate::{RelationshipError, RelationshipResult};
 use chrono::{DateTime, Utc};
 use cim_domain::state_machine::State;
+use cim_domain::MessageIdentity;
 use cim_domain_spaces::{ConceptId, KnowledgeLevel, Point3};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use uuid::Uuid;
 
 // ============================================================================
 // HyperEdge State Machine
@@ -43,6 +84,8 @@ pub enum HyperEdgeState {
     Restructuring,
     /// HyperEdge has been dissolved
     Dissolved,
+    /// HyperEdge was dissolved, then archived (hidden until restored)
+    Archived,
 }
 
 impl State for HyperEdgeState {
@@ -52,11 +95,12 @@ impl State for HyperEdgeState {
             HyperEdgeState::Active => "Active",
             HyperEdgeState::Restructuring => "Restructuring",
             HyperEdgeState::Dissolved => "Dissolved",
+            HyperEdgeState::Archived => "Archived",
         }
     }
 
     fn is_terminal(&self) -> bool {
-        matches!(self, HyperEdgeState::Dissolved)
+        matches!(self, HyperEdgeState::Dissolved | HyperEdgeState::Archived)
     }
 }
 
@@ -74,7 +118,9 @@ impl HyperEdgeState {
             (Active, Dissolved) |
             // From Restructuring
             (Restructuring, Active) |
-            (Restructuring, Dissolved)
+            (Restructuring, Dissolved) |
+            // Archiving; only RestoreHyperEdge leaves Archived
+            (Dissolved, Archived)
         )
     }
 }
@@ -134,10 +180,26 @@ pub struct HyperEdgeConcept {
     pub state: HyperEdgeState,
     /// Validity period
     pub validity: ValidityPeriod,
+    /// How this hyperedge becomes Active
+    #[serde(default)]
+    pub activation: ActivationMode,
+    /// Participants that have acknowledged this hyperedge
+    #[serde(default)]
+    pub acknowledged: HashSet<EntityKey>,
+    /// Weights each participant has held, oldest first; kept after the
+    /// participant leaves
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub weight_history: HashMap<EntityKey, Vec<WeightChange>>,
+    /// Preconditions on state transitions (not serialized)
+    #[serde(skip)]
+    pub guards: TransitionGuards,
 
     // ---- Metadata ----
     /// Additional properties
     pub properties: HashMap<String, serde_json::Value>,
+    /// Classification tags, normalized
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub tags: BTreeSet<String>,
     /// Event version
     pub version: u64,
     /// Creation timestamp
@@ -170,14 +232,47 @@ impl HyperEdgeConcept {
             evidence_cids: Vec::new(),
             state: HyperEdgeState::Forming,
             validity: ValidityPeriod::ongoing_now(),
+            activation: ActivationMode::Immediate,
+            acknowledged: HashSet::new(),
+            weight_history: HashMap::new(),
+            guards: TransitionGuards::new(),
             properties: HashMap::new(),
+            tags: BTreeSet::new(),
             version: 0,
             created_at: now,
             updated_at: now,
         }
     }
 
+    /// Set how the hyperedge becomes Active
+    pub fn with_activation_mode(mut self, activation: ActivationMode) -> Self {
+        self.activation = activation;
+        self
+    }
+
+    /// Add a tag
+    pub fn with_tag(mut self, tag: &str) -> Self {
+        if let Some(tag) = normalize_tag(tag) {
+            self.tags.insert(tag);
+        }
+        self
+    }
+
+    /// Check if this hyperedge carries a tag
+    pub fn has_tag(&self, tag: &str) -> bool {
+        normalize_tag(tag).is_some_and(|tag| self.tags.contains(&tag))
+    }
+
+    /// Set the guards evaluated on state transitions
+    pub fn with_guards(mut self, guards: TransitionGuards) -> Self {
+        self.guards = guards;
+        self
+    }
+
     /// Add a participant
+    ///
+    /// The weight history records no actor for direct changes; commands
+    /// record theirs.
     pub fn add_participant(
         &mut self,
         entity_ref: EntityRef,
@@ -187,8 +282,10 @@ impl HyperEdgeConcept {
         if self.state.is_terminal() {
             return Err("Cannot modify dissolved hyperedge".to_string());
         }
+        let now = Utc::now();
+        self.record_weight(&entity_ref, weight, "", now);
         self.participants.add_participant(entity_ref, role, weight);
-        self.updated_at = Utc::now();
+        self.updated_at = now;
         Ok(())
     }
 
@@ -205,16 +302,75 @@ impl HyperEdgeConcept {
         Ok(())
     }
 
+    /// Change a participant's weight without resetting its join time
+    pub fn change_participant_weight(
+        &mut self,
+        entity_ref: &EntityRef,
+        weight: f64,
+    ) -> Result<(), String> {
+        if self.state.is_terminal() {
+            return Err("Cannot modify dissolved hyperedge".to_string());
+        }
+        if self.participants.set_weight(entity_ref, weight).is_none() {
+            return Err(format!("{} is not a participant", entity_ref));
+        }
+        let now = Utc::now();
+        self.record_weight(entity_ref, weight, "", now);
+        self.updated_at = now;
+        Ok(())
+    }
+
     /// Get participant count
     pub fn participant_count(&self) -> usize {
         self.participants.participant_count()
     }
 
+    /// Weights a participant has held, oldest first
+    pub fn weight_history(&self, participant: &EntityRef) -> &[WeightChange] {
+        self.weight_history
+            .get(&participant.key())
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Weight a participant held at a point in time
+    ///
+    /// `None` before the participant joined; after it left, the last
+    /// weight it held.
+    pub fn weight_at(&self, participant: &EntityRef, at: DateTime<Utc>) -> Option<f64> {
+        self.weight_history(participant)
+            .iter()
+            .take_while(|change| change.changed_at <= at)
+            .last()
+            .map(|change| change.weight)
+    }
+
+    /// Record a participant's new weight
+    fn record_weight(&mut self, participant: &EntityRef, weight: f64, changed_by: &str, changed_at: DateTime<Utc>) {
+        self.weight_history.entry(participant.key()).or_default().push(WeightChange {
+            weight,
+            changed_by: changed_by.to_string(),
+            changed_at,
+        });
+    }
+
+    /// Check if hyperedge is archived
+    pub fn is_archived(&self) -> bool {
+        self.state == HyperEdgeState::Archived
+    }
+
     /// Check if hyperedge is currently active
     pub fn is_active(&self) -> bool {
         self.state == HyperEdgeState::Active && self.validity.is_active()
     }
 
+    /// Check if the acknowledgements allow activation
+    ///
+    /// Always true unless the hyperedge activates by quorum.
+    pub fn quorum_reached(&self) -> bool {
+        self.activation.is_met(&self.participants, &self.acknowledged)
+    }
+
     /// Activate the hyperedge
     pub fn activate(&mut self) -> Result<(), String> {
         if self.participants.participant_count() < 2 {
@@ -223,6 +379,10 @@ impl HyperEdgeConcept {
         if !self.state.can_transition_to(&HyperEdgeState::Active) {
             return Err(format!("Cannot activate from {:?} state", self.state));
         }
+        if !self.quorum_reached() {
+            return Err("HyperEdge activation quorum not reached".to_string());
+        }
+        self.guards.check_hyperedge(self, &HyperEdgeState::Active)?;
         self.state = HyperEdgeState::Active;
         self.updated_at = Utc::now();
         Ok(())
@@ -233,6 +393,7 @@ impl HyperEdgeConcept {
         if !self.state.can_transition_to(&HyperEdgeState::Dissolved) {
             return Err(format!("Cannot dissolve from {:?} state", self.state));
         }
+        self.guards.check_hyperedge(self, &HyperEdgeState::Dissolved)?;
         let now = Utc::now();
         self.validity = self.validity.clone().end(now, reason);
         self.state = HyperEdgeState::Dissolved;
@@ -245,6 +406,449 @@ impl HyperEdgeConcept {
         self.quality.to_quality_point()
     }
 
+    /// Get the quality point, normalizing duration with a space's model
+    pub fn quality_point_with(&self, model: &DurationModel) -> QualityPoint {
+        self.quality.to_quality_point_with(model, Some(&self.category))
+    }
+
+    /// Decide the events a command produces (pure functional)
+    ///
+    /// The output half of the Mealy machine: applying the returned events
+    /// with `apply_event_pure` gives the next state. `CreateHyperEdge` is
+    /// decided by a blank hyperedge, e.g. `HyperEdgeConcept::new`, and
+    /// rejected by the hyperedge it would create. Role and weight changes
+    /// to the current value are accepted without events.
+    pub fn handle_command(&self, cmd: HyperEdgeCommand) -> RelationshipResult<Vec<HyperEdgeEvent>> {
+        let now = Utc::now();
+        match cmd {
+            HyperEdgeCommand::CreateHyperEdge(c) => {
+                if c.hyperedge_id == self.id {
+                    return Err(RelationshipError::InvalidStateTransition(format!(
+                        "hyperedge {} already exists",
+                        self.id
+                    )));
+                }
+                for entry in c.initial_participants.participants() {
+                    check_weight(entry.weight)?;
+                }
+                Ok(vec![HyperEdgeEvent::HyperEdgeCreated(HyperEdgeCreated {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    hyperedge_id: c.hyperedge_id,
+                    concept_id: ConceptId::new(),
+                    name: c.name,
+                    category: c.category,
+                    initial_participants: c.initial_participants,
+                    activation: c.activation,
+                    created_by: c.created_by,
+                    created_at: now,
+                })])
+            }
+
+            HyperEdgeCommand::ActivateHyperEdge(c) => {
+                self.check_command(&c.hyperedge_id, Some(HyperEdgeState::Active))?;
+                if self.state == HyperEdgeState::Restructuring {
+                    return Err(RelationshipError::InvalidStateTransition(format!(
+                        "hyperedge {} is restructuring; complete the restructuring instead",
+                        self.id
+                    )));
+                }
+                if self.participant_count() < 2 {
+                    return Err(RelationshipError::InsufficientParticipants);
+                }
+                if !self.quorum_reached() {
+                    return Err(RelationshipError::InvalidStateTransition(format!(
+                        "hyperedge {} has not reached its activation quorum",
+                        self.id
+                    )));
+                }
+                Ok(vec![HyperEdgeEvent::HyperEdgeActivated(HyperEdgeActivated {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    hyperedge_id: c.hyperedge_id,
+                    activated_by: c.activated_by,
+                    activated_at: now,
+                })])
+            }
+
+            HyperEdgeCommand::AddParticipant(c) => {
+                self.check_command(&c.hyperedge_id, None)?;
+                check_weight(c.weight)?;
+                if self.participants.get(&c.participant).is_some() {
+                    return Err(RelationshipError::InvalidRelationship(format!(
+                        "{} is already a participant",
+                        c.participant
+                    )));
+                }
+                Ok(vec![HyperEdgeEvent::ParticipantAdded(ParticipantAdded {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    hyperedge_id: c.hyperedge_id,
+                    participant: c.participant,
+                    role: c.role,
+                    weight: c.weight,
+                    added_by: c.added_by,
+                    added_at: now,
+                })])
+            }
+
+            HyperEdgeCommand::RemoveParticipant(c) => {
+                self.check_command(&c.hyperedge_id, None)?;
+                self.participant(&c.participant)?;
+                if self.participant_count() <= 2 {
+                    return Err(RelationshipError::InsufficientParticipants);
+                }
+                Ok(vec![HyperEdgeEvent::ParticipantRemoved(ParticipantRemoved {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    hyperedge_id: c.hyperedge_id,
+                    participant: c.participant,
+                    reason: c.reason,
+                    removed_by: c.removed_by,
+                    removed_at: now,
+                })])
+            }
+
+            HyperEdgeCommand::ChangeParticipantRole(c) => {
+                self.check_command(&c.hyperedge_id, None)?;
+                let entry = self.participant(&c.participant)?;
+                if entry.role == c.new_role {
+                    return Ok(Vec::new());
+                }
+                Ok(vec![HyperEdgeEvent::ParticipantRoleChanged(ParticipantRoleChanged {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    hyperedge_id: c.hyperedge_id,
+                    participant: c.participant,
+                    old_role: entry.role.clone(),
+                    new_role: c.new_role,
+                    changed_by: c.changed_by,
+                    changed_at: now,
+                })])
+            }
+
+            HyperEdgeCommand::ChangeParticipantWeight(c) => {
+                self.check_command(&c.hyperedge_id, None)?;
+                check_weight(c.new_weight)?;
+                let entry = self.participant(&c.participant)?;
+                if entry.weight == c.new_weight {
+                    return Ok(Vec::new());
+                }
+                Ok(vec![HyperEdgeEvent::ParticipantWeightChanged(ParticipantWeightChanged {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    hyperedge_id: c.hyperedge_id,
+                    participant: c.participant,
+                    old_weight: entry.weight,
+                    new_weight: c.new_weight,
+                    changed_by: c.changed_by,
+                    changed_at: now,
+                })])
+            }
+
+            HyperEdgeCommand::AcknowledgeParticipation(c) => {
+                self.check_command(&c.hyperedge_id, None)?;
+                self.participant(&c.participant)?;
+                if self.acknowledged.contains(&c.participant.key()) {
+                    return Ok(Vec::new());
+                }
+                let acknowledged = HyperEdgeEvent::ParticipantAcknowledged(ParticipantAcknowledged {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    hyperedge_id: c.hyperedge_id,
+                    participant: c.participant,
+                    acknowledged_at: now,
+                });
+                let next = self.apply_event_pure(&acknowledged)?;
+                let mut events = vec![acknowledged];
+                // Acknowledgements activate only quorum hyperedges
+                if self.activation != ActivationMode::Immediate
+                    && self.state == HyperEdgeState::Forming
+                    && next.participant_count() >= 2
+                    && next.quorum_reached()
+                    && self.guards.check_hyperedge(&next, &HyperEdgeState::Active).is_ok()
+                {
+                    events.push(HyperEdgeEvent::HyperEdgeActivated(HyperEdgeActivated {
+                        event_id: Uuid::now_v7(),
+                        identity: MessageIdentity::new_caused_by(&c.identity),
+                        hyperedge_id: c.hyperedge_id,
+                        activated_by: "quorum".to_string(),
+                        activated_at: now,
+                    }));
+                }
+                Ok(events)
+            }
+
+            HyperEdgeCommand::TerminateHyperEdge(c) => {
+                self.check_command(&c.hyperedge_id, Some(HyperEdgeState::Dissolved))?;
+                Ok(vec![HyperEdgeEvent::HyperEdgeTerminated(HyperEdgeTerminated {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    hyperedge_id: c.hyperedge_id,
+                    reason: c.reason,
+                    terminated_by: c.terminated_by,
+                    terminated_at: now,
+                })])
+            }
+
+            HyperEdgeCommand::UpdateHyperEdgeQuality(c) => {
+                self.check_command(&c.hyperedge_id, None)?;
+                check_quality(&c.new_quality)?;
+                Ok(vec![HyperEdgeEvent::HyperEdgeQualityUpdated(HyperEdgeQualityUpdated {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    hyperedge_id: c.hyperedge_id,
+                    old_quality: self.quality.clone(),
+                    new_quality: c.new_quality,
+                    reason: c.reason,
+                    updated_at: now,
+                })])
+            }
+
+            HyperEdgeCommand::BeginRestructuring(c) => {
+                self.check_command(&c.hyperedge_id, Some(HyperEdgeState::Restructuring))?;
+                Ok(vec![HyperEdgeEvent::RestructuringBegun(RestructuringBegun {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    hyperedge_id: c.hyperedge_id,
+                    reason: c.reason,
+                    begun_by: c.begun_by,
+                    begun_at: now,
+                })])
+            }
+
+            HyperEdgeCommand::CompleteRestructuring(c) => {
+                self.check_command(&c.hyperedge_id, Some(HyperEdgeState::Active))?;
+                if self.state != HyperEdgeState::Restructuring {
+                    return Err(RelationshipError::InvalidStateTransition(format!(
+                        "hyperedge {} is not restructuring",
+                        self.id
+                    )));
+                }
+                if self.participant_count() < 2 {
+                    return Err(RelationshipError::InsufficientParticipants);
+                }
+                Ok(vec![HyperEdgeEvent::RestructuringCompleted(RestructuringCompleted {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    hyperedge_id: c.hyperedge_id,
+                    completed_by: c.completed_by,
+                    completed_at: now,
+                })])
+            }
+
+            HyperEdgeCommand::AddHyperEdgeTag(c) => {
+                self.check_command(&c.hyperedge_id, None)?;
+                let tag = check_tag(&c.tag)?;
+                if self.tags.contains(&tag) {
+                    return Ok(Vec::new());
+                }
+                Ok(vec![HyperEdgeEvent::TagAdded(HyperEdgeTagAdded {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    hyperedge_id: c.hyperedge_id,
+                    tag,
+                    tagged_by: c.tagged_by,
+                    tagged_at: now,
+                })])
+            }
+
+            HyperEdgeCommand::RemoveHyperEdgeTag(c) => {
+                self.check_command(&c.hyperedge_id, None)?;
+                let tag = check_tag(&c.tag)?;
+                if !self.tags.contains(&tag) {
+                    return Ok(Vec::new());
+                }
+                Ok(vec![HyperEdgeEvent::TagRemoved(HyperEdgeTagRemoved {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    hyperedge_id: c.hyperedge_id,
+                    tag,
+                    removed_by: c.removed_by,
+                    removed_at: now,
+                })])
+            }
+
+            HyperEdgeCommand::AddParticipantGroup(c) => {
+                self.check_command(&c.hyperedge_id, None)?;
+                let name = check_name(&c.name)?;
+                if self.participants.group(&name).is_some() {
+                    return Err(RelationshipError::InvalidRelationship(format!(
+                        "group {name} already exists"
+                    )));
+                }
+                if let Some(parent) = &c.parent {
+                    self.group(parent)?;
+                }
+                Ok(vec![HyperEdgeEvent::GroupAdded(ParticipantGroupAdded {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    hyperedge_id: c.hyperedge_id,
+                    name,
+                    role: c.role,
+                    parent: c.parent,
+                    added_by: c.added_by,
+                    added_at: now,
+                })])
+            }
+
+            HyperEdgeCommand::RemoveParticipantGroup(c) => {
+                self.check_command(&c.hyperedge_id, None)?;
+                self.group(&c.name)?;
+                Ok(vec![HyperEdgeEvent::GroupRemoved(ParticipantGroupRemoved {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    hyperedge_id: c.hyperedge_id,
+                    name: c.name,
+                    removed_by: c.removed_by,
+                    removed_at: now,
+                })])
+            }
+
+            HyperEdgeCommand::AssignToGroup(c) => {
+                self.check_command(&c.hyperedge_id, None)?;
+                self.participant(&c.participant)?;
+                if self.group(&c.group)?.members.contains(&c.participant.key()) {
+                    return Ok(Vec::new());
+                }
+                Ok(vec![HyperEdgeEvent::AssignedToGroup(ParticipantAssignedToGroup {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    hyperedge_id: c.hyperedge_id,
+                    participant: c.participant,
+                    group: c.group,
+                    assigned_by: c.assigned_by,
+                    assigned_at: now,
+                })])
+            }
+
+            HyperEdgeCommand::UnassignFromGroup(c) => {
+                self.check_command(&c.hyperedge_id, None)?;
+                if !self.group(&c.group)?.members.contains(&c.participant.key()) {
+                    return Ok(Vec::new());
+                }
+                Ok(vec![HyperEdgeEvent::UnassignedFromGroup(ParticipantUnassignedFromGroup {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    hyperedge_id: c.hyperedge_id,
+                    participant: c.participant,
+                    group: c.group,
+                    unassigned_by: c.unassigned_by,
+                    unassigned_at: now,
+                })])
+            }
+
+            HyperEdgeCommand::ArchiveHyperEdge(c) => {
+                self.check_command(&c.relationship_id, Some(HyperEdgeState::Archived))?;
+                Ok(vec![HyperEdgeEvent::Archived(HyperEdgeArchived {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    hyperedge_id: c.relationship_id,
+                    reason: c.reason,
+                    archived_by: c.archived_by,
+                    archived_at: now,
+                })])
+            }
+
+            HyperEdgeCommand::RestoreHyperEdge(c) => {
+                // Archived is terminal for every other command
+                self.check_target(&c.relationship_id)?;
+                if self.state != HyperEdgeState::Archived {
+                    return Err(RelationshipError::InvalidStateTransition(format!(
+                        "hyperedge {} is not archived",
+                        self.id
+                    )));
+                }
+                self.guards
+                    .check_hyperedge(self, &HyperEdgeState::Dissolved)
+                    .map_err(RelationshipError::InvalidStateTransition)?;
+                Ok(vec![HyperEdgeEvent::Restored(HyperEdgeRestored {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    hyperedge_id: c.relationship_id,
+                    restored_by: c.restored_by,
+                    restored_at: now,
+                })])
+            }
+
+            HyperEdgeCommand::RenameHyperEdge(c) => {
+                self.check_command(&c.relationship_id, None)?;
+                let name = check_name(&c.name)?;
+                if name == self.name {
+                    return Ok(Vec::new());
+                }
+                Ok(vec![HyperEdgeEvent::Renamed(HyperEdgeRenamed {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    hyperedge_id: c.relationship_id,
+                    old_name: self.name.clone(),
+                    new_name: name,
+                    renamed_by: c.renamed_by,
+                    renamed_at: now,
+                })])
+            }
+
+            HyperEdgeCommand::DescribeHyperEdge(c) => {
+                self.check_command(&c.relationship_id, None)?;
+                let description = c.description.map(|d| d.trim().to_string()).filter(|d| !d.is_empty());
+                if description == self.description {
+                    return Ok(Vec::new());
+                }
+                Ok(vec![HyperEdgeEvent::DescriptionUpdated(HyperEdgeDescriptionUpdated {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    hyperedge_id: c.relationship_id,
+                    description,
+                    updated_by: c.updated_by,
+                    updated_at: now,
+                })])
+            }
+        }
+    }
+
+    /// Check that a command targets this hyperedge and, if it changes
+    /// state, that the transition is allowed and passes the guards
+    fn check_command(&self, hyperedge_id: &RelationshipId, to: Option<HyperEdgeState>) -> RelationshipResult<()> {
+        self.check_target(hyperedge_id)?;
+        match to {
+            Some(to) if !self.state.can_transition_to(&to) => Err(RelationshipError::InvalidStateTransition(
+                format!("Cannot transition from {:?} to {:?}", self.state, to),
+            )),
+            Some(to) => self
+                .guards
+                .check_hyperedge(self, &to)
+                .map_err(RelationshipError::InvalidStateTransition),
+            None if self.state.is_terminal() => Err(RelationshipError::InvalidStateTransition(format!(
+                "hyperedge {} is {:?}",
+                self.id, self.state
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Check that a command targets this hyperedge
+    fn check_target(&self, hyperedge_id: &RelationshipId) -> RelationshipResult<()> {
+        if *hyperedge_id != self.id {
+            return Err(RelationshipError::InvalidRelationship(format!(
+                "command for hyperedge {} sent to hyperedge {}",
+                hyperedge_id, self.id
+            )));
+        }
+        Ok(())
+    }
+
+    fn participant(&self, entity_ref: &EntityRef) -> RelationshipResult<&ParticipantEntry> {
+        self.participants
+            .get(entity_ref)
+            .ok_or_else(|| RelationshipError::EntityNotFound(format!("{} is not a participant", entity_ref)))
+    }
+
+    fn group(&self, name: &str) -> RelationshipResult<&ParticipantGroup> {
+        self.participants
+            .group(name)
+            .ok_or_else(|| RelationshipError::EntityNotFound(format!("group {name} does not exist")))
+    }
+
     /// Apply an event to produce the next state (pure functional)
     pub fn apply_event_pure(&self, event: &HyperEdgeEvent) -> RelationshipResult<Self> {
         let mut next = self.clone();
@@ -258,6 +862,12 @@ impl HyperEdgeConcept {
                 next.name = e.name.clone();
                 next.category = e.category.clone();
                 next.participants = e.initial_participants.clone();
+                next.activation = e.activation;
+                next.acknowledged.clear();
+                next.weight_history.clear();
+                for entry in e.initial_participants.participants() {
+                    next.record_weight(&entry.entity_ref, entry.weight, &e.created_by, e.created_at);
+                }
                 next.state = HyperEdgeState::Forming;
                 next.created_at = e.created_at;
             }
@@ -272,21 +882,25 @@ impl HyperEdgeConcept {
                     e.role.clone(),
                     e.weight,
                 );
+                next.record_weight(&e.participant, e.weight, &e.added_by, e.added_at);
             }
 
             HyperEdgeEvent::ParticipantRemoved(e) => {
                 next.participants.remove_participant(&e.participant);
+                next.acknowledged.remove(&e.participant.key());
             }
 
             HyperEdgeEvent::ParticipantRoleChanged(e) => {
-                // Remove and re-add with new role
-                if let Some(entry) = next.participants.remove_participant(&e.participant) {
-                    next.participants.add_participant(
-                        e.participant.clone(),
-                        e.new_role.clone(),
-                        entry.weight,
-                    );
-                }
+                next.participants.set_role(&e.participant, e.new_role.clone());
+            }
+
+            HyperEdgeEvent::ParticipantWeightChanged(e) => {
+                next.participants.set_weight(&e.participant, e.new_weight);
+                next.record_weight(&e.participant, e.new_weight, &e.changed_by, e.changed_at);
+            }
+
+            HyperEdgeEvent::ParticipantAcknowledged(e) => {
+                next.acknowledged.insert(e.participant.key());
             }
 
             HyperEdgeEvent::HyperEdgeTerminated(e) => {
@@ -294,20 +908,145 @@ impl HyperEdgeConcept {
                 next.validity = next.validity.clone().end(e.terminated_at, &e.reason);
             }
 
+            HyperEdgeEvent::RestructuringBegun(_) => {
+                next.state = HyperEdgeState::Restructuring;
+            }
+
+            HyperEdgeEvent::RestructuringCompleted(_) => {
+                next.state = HyperEdgeState::Active;
+            }
+
             HyperEdgeEvent::HyperEdgeQualityUpdated(e) => {
                 next.quality = e.new_quality.clone();
                 next.position = next.quality.to_quality_point().to_point3();
             }
+
+            HyperEdgeEvent::PromotedFromEdge(e) => {
+                next.knowledge_level = e.knowledge_level;
+                next.confidence = e.confidence;
+                next.evidence_cids = e.evidence_cids.clone();
+                next.properties.extend(e.properties.clone());
+                next.properties.insert(
+                    "promoted_from".to_string(),
+                    serde_json::Value::String(e.edge_id.to_string()),
+                );
+            }
+
+            HyperEdgeEvent::TagAdded(e) => {
+                next.tags.insert(e.tag.clone());
+            }
+
+            HyperEdgeEvent::TagRemoved(e) => {
+                next.tags.remove(&e.tag);
+            }
+
+            HyperEdgeEvent::GroupAdded(e) => {
+                next.participants.add_group(e.name.clone(), e.role.clone(), e.parent.as_deref());
+            }
+
+            HyperEdgeEvent::GroupRemoved(e) => {
+                next.participants.remove_group(&e.name);
+            }
+
+            HyperEdgeEvent::AssignedToGroup(e) => {
+                next.participants.assign_to_group(&e.participant, &e.group);
+            }
+
+            HyperEdgeEvent::UnassignedFromGroup(e) => {
+                next.participants.unassign_from_group(&e.participant, &e.group);
+            }
+
+            HyperEdgeEvent::Archived(_) => {
+                next.state = HyperEdgeState::Archived;
+            }
+
+            HyperEdgeEvent::Restored(_) => {
+                next.state = HyperEdgeState::Dissolved;
+            }
+
+            HyperEdgeEvent::Renamed(e) => {
+                next.name = e.new_name.clone();
+            }
+
+            HyperEdgeEvent::DescriptionUpdated(e) => {
+                next.description = e.description.clone();
+            }
+
+            HyperEdgeEvent::TemplateApplied(e) => {
+                next.properties.extend(e.properties.clone());
+                next.properties
+                    .insert("template".to_string(), serde_json::Value::String(e.template.clone()));
+                if e.valid_until.is_some() {
+                    next.validity.ends_at = e.valid_until;
+                }
+            }
         }
 
         Ok(next)
     }
+
+    /// Rebuild aggregate from event history
+    pub fn from_events(events: &[HyperEdgeEvent]) -> RelationshipResult<Self> {
+        let Some(HyperEdgeEvent::HyperEdgeCreated(e)) = events.first() else {
+            return Err(crate::RelationshipError::InvalidRelationship(
+                "First event must be HyperEdgeCreated".to_string(),
+            ));
+        };
+
+        let quality = RelationshipQuality::default();
+        let mut hyperedge = Self {
+            id: e.hyperedge_id,
+            concept_id: e.concept_id,
+            category: e.category.clone(),
+            name: e.name.clone(),
+            description: None,
+            participants: e.initial_participants.clone(),
+            quality: quality.clone(),
+            position: quality.to_quality_point().to_point3(),
+            knowledge_level: KnowledgeLevel::Unknown,
+            confidence: 0.0,
+            evidence_cids: Vec::new(),
+            state: HyperEdgeState::Forming,
+            validity: ValidityPeriod::ongoing(e.created_at),
+            activation: e.activation,
+            acknowledged: HashSet::new(),
+            weight_history: HashMap::new(),
+            guards: TransitionGuards::new(),
+            properties: HashMap::new(),
+            tags: BTreeSet::new(),
+            version: 0,
+            created_at: e.created_at,
+            updated_at: e.created_at,
+        };
+        for entry in e.initial_participants.participants() {
+            hyperedge.record_weight(&entry.entity_ref, entry.weight, &e.created_by, e.created_at);
+        }
+
+        // Apply remaining events
+        for event in &events[1..] {
+            hyperedge = hyperedge.apply_event_pure(event)?;
+        }
+
+        Ok(hyperedge)
+    }
+}
+
+/// Check a participation weight is within 0.0 - 1.0
+fn check_weight(weight: f64) -> RelationshipResult<()> {
+    if (0.0..=1.0).contains(&weight) {
+        Ok(())
+    } else {
+        Err(RelationshipError::InvalidRelationship(format!(
+            "participant weight {} outside 0.0 - 1.0",
+            weight
+        )))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use uuid::Uuid;
+    use crate::commands::{ActivateHyperEdge, CreateHyperEdge, RemoveParticipant};
 
     #[test]
     fn test_hyperedge_creation() {
@@ -374,4 +1113,295 @@ mod tests {
         // Cannot remove when only 2 participants remain
         assert!(hyperedge.remove_participant(&person1).is_err());
     }
+
+    #[test]
+    fn test_participant_weight_changed_event() {
+        let mut hyperedge = HyperEdgeConcept::new(
+            "Weighted Team",
+            RelationshipCategory::Membership,
+        );
+
+        let person = EntityRef::person(Uuid::now_v7());
+        hyperedge.add_participant(person.clone(), ParticipantRole::Member, 0.4).unwrap();
+        let joined_at = hyperedge.participants.get(&person).unwrap().joined_at;
+
+        let event = HyperEdgeEvent::ParticipantWeightChanged(ParticipantWeightChanged {
+            event_id: Uuid::now_v7(),
+            identity: MessageIdentity::new_root(),
+            hyperedge_id: hyperedge.id,
+            participant: person.clone(),
+            old_weight: 0.4,
+            new_weight: 0.9,
+            changed_by: "test".to_string(),
+            changed_at: Utc::now(),
+        });
+
+        let next = hyperedge.apply_event_pure(&event).unwrap();
+        let entry = next.participants.get(&person).unwrap();
+        assert_eq!(entry.weight, 0.9);
+        assert_eq!(entry.joined_at, joined_at);
+        assert_eq!(entry.role, ParticipantRole::Member);
+    }
+
+    #[test]
+    fn test_weight_history() {
+        use crate::commands::ChangeParticipantWeight;
+
+        let (alice, bob, carol) = (
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::person(Uuid::now_v7()),
+        );
+        let mut participants = IncidenceMatrix::new();
+        participants.add_participant(alice.clone(), ParticipantRole::Leader, 1.0);
+        participants.add_participant(bob.clone(), ParticipantRole::Member, 0.2);
+        participants.add_participant(carol.clone(), ParticipantRole::Member, 0.5);
+        let hyperedge_id = RelationshipId::new();
+        let created = HyperEdgeConcept::new("", RelationshipCategory::Membership)
+            .handle_command(HyperEdgeCommand::CreateHyperEdge(CreateHyperEdge {
+                identity: MessageIdentity::new_root(),
+                hyperedge_id,
+                name: "Project".to_string(),
+                category: RelationshipCategory::Membership,
+                initial_participants: participants,
+                activation: ActivationMode::Immediate,
+                created_by: "pm".to_string(),
+            }))
+            .unwrap();
+        let mut project = HyperEdgeConcept::from_events(&created).unwrap();
+        let joined = Utc::now();
+
+        // Applying the creation event seeds the same history, replacing any
+        let mut stale = HyperEdgeConcept::new("Stale", RelationshipCategory::Membership);
+        stale.add_participant(bob.clone(), ParticipantRole::Member, 0.7).unwrap();
+        assert_eq!(stale.weight_at(&bob, Utc::now()), Some(0.7));
+        let applied = stale.apply_event_pure(&created[0]).unwrap();
+        assert_eq!(applied.weight_history, project.weight_history);
+
+        for new_weight in [0.6, 0.9, 0.9] {
+            let change = HyperEdgeCommand::ChangeParticipantWeight(ChangeParticipantWeight {
+                identity: MessageIdentity::new_root(),
+                hyperedge_id,
+                participant: bob.clone(),
+                new_weight,
+                changed_by: "pm".to_string(),
+            });
+            for event in project.handle_command(change).unwrap() {
+                project = project.apply_event_pure(&event).unwrap();
+            }
+        }
+        let weights: Vec<f64> = project.weight_history(&bob).iter().map(|c| c.weight).collect();
+        assert_eq!(weights, vec![0.2, 0.6, 0.9]);
+        assert_eq!(project.weight_at(&bob, joined), Some(0.2));
+        assert_eq!(project.weight_at(&bob, joined - chrono::Duration::days(1)), None);
+
+        // History outlives membership
+        let remove = HyperEdgeCommand::RemoveParticipant(RemoveParticipant {
+            identity: MessageIdentity::new_root(),
+            hyperedge_id,
+            participant: bob.clone(),
+            reason: "rolled off".to_string(),
+            removed_by: "pm".to_string(),
+        });
+        for event in project.handle_command(remove).unwrap() {
+            project = project.apply_event_pure(&event).unwrap();
+        }
+        assert!(project.participants.get(&bob).is_none());
+        assert_eq!(project.weight_history(&bob).len(), 3);
+        assert_eq!(project.weight_at(&bob, Utc::now()), Some(0.9));
+        assert_eq!(project.weight_history(&carol)[0].changed_by, "pm");
+    }
+
+    #[test]
+    fn test_update_hyperedge_quality() {
+        use crate::commands::UpdateHyperEdgeQuality;
+
+        let team = HyperEdgeConcept::new("Team", RelationshipCategory::Membership);
+        let update = |quality: RelationshipQuality| {
+            HyperEdgeCommand::UpdateHyperEdgeQuality(UpdateHyperEdgeQuality {
+                identity: MessageIdentity::new_root(),
+                hyperedge_id: team.id,
+                new_quality: quality,
+                reason: "quarterly review".to_string(),
+            })
+        };
+        let invalid = RelationshipQuality {
+            trust: 1.5,
+            ..RelationshipQuality::default()
+        };
+        assert!(matches!(
+            team.handle_command(update(invalid)),
+            Err(RelationshipError::QualityOutOfRange(_))
+        ));
+
+        let quality = RelationshipQuality {
+            strength: 0.9,
+            ..RelationshipQuality::default()
+        };
+        let events = team.handle_command(update(quality)).unwrap();
+        let team = team.apply_event_pure(&events[0]).unwrap();
+        assert_eq!(team.quality.strength, 0.9);
+        assert_eq!(team.position, team.quality.to_quality_point().to_point3());
+    }
+
+    #[test]
+    fn test_handle_command() {
+        let (alice, bob) = (EntityRef::person(Uuid::now_v7()), EntityRef::person(Uuid::now_v7()));
+        let mut participants = IncidenceMatrix::new();
+        participants.add_participant(alice.clone(), ParticipantRole::Leader, 1.0);
+        participants.add_participant(bob.clone(), ParticipantRole::Member, 0.5);
+
+        let hyperedge_id = RelationshipId::new();
+        let events = HyperEdgeConcept::new("", RelationshipCategory::Membership)
+            .handle_command(HyperEdgeCommand::CreateHyperEdge(CreateHyperEdge {
+                identity: MessageIdentity::new_root(),
+                hyperedge_id,
+                name: "Team".to_string(),
+                category: RelationshipCategory::Membership,
+                initial_participants: participants,
+                activation: ActivationMode::Immediate,
+                created_by: "test".to_string(),
+            }))
+            .unwrap();
+        let team = HyperEdgeConcept::from_events(&events).unwrap();
+
+        let remove = HyperEdgeCommand::RemoveParticipant(RemoveParticipant {
+            identity: MessageIdentity::new_root(),
+            hyperedge_id,
+            participant: bob,
+            reason: "left".to_string(),
+            removed_by: "test".to_string(),
+        });
+        assert!(matches!(
+            team.handle_command(remove),
+            Err(RelationshipError::InsufficientParticipants)
+        ));
+
+        let activate = |hyperedge_id| {
+            HyperEdgeCommand::ActivateHyperEdge(ActivateHyperEdge {
+                identity: MessageIdentity::new_root(),
+                hyperedge_id,
+                activated_by: "test".to_string(),
+            })
+        };
+        assert!(team.handle_command(activate(RelationshipId::new())).is_err());
+        let events = team.handle_command(activate(hyperedge_id)).unwrap();
+        let team = team.apply_event_pure(&events[0]).unwrap();
+        assert_eq!(team.state, HyperEdgeState::Active);
+        assert!(matches!(
+            team.handle_command(activate(hyperedge_id)),
+            Err(RelationshipError::InvalidStateTransition(_))
+        ));
+    }
+
+    #[test]
+    fn test_quorum_activation() {
+        use crate::commands::AcknowledgeParticipation;
+
+        let (alice, bob, carol) = (
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::organization(Uuid::now_v7()),
+            EntityRef::organization(Uuid::now_v7()),
+        );
+        let mut contract = HyperEdgeConcept::new("Supply Agreement", RelationshipCategory::Custom("contract".to_string()))
+            .with_activation_mode(ActivationMode::quorum(0.6));
+        contract.add_participant(alice.clone(), ParticipantRole::Approver, 0.2).unwrap();
+        contract.add_participant(bob.clone(), ParticipantRole::Primary, 0.4).unwrap();
+        contract.add_participant(carol.clone(), ParticipantRole::Secondary, 0.4).unwrap();
+
+        let acknowledge = |participant: &EntityRef| {
+            HyperEdgeCommand::AcknowledgeParticipation(AcknowledgeParticipation {
+                identity: MessageIdentity::new_root(),
+                hyperedge_id: contract.id,
+                participant: participant.clone(),
+            })
+        };
+        let activate = HyperEdgeCommand::ActivateHyperEdge(ActivateHyperEdge {
+            identity: MessageIdentity::new_root(),
+            hyperedge_id: contract.id,
+            activated_by: "test".to_string(),
+        });
+        assert!(contract.handle_command(activate.clone()).is_err());
+
+        // 0.2 + 0.4 of 1.0 meets the 0.6 threshold on the second acknowledgement
+        let events = contract.handle_command(acknowledge(&alice)).unwrap();
+        assert_eq!(events.len(), 1);
+        let contract = contract.apply_event_pure(&events[0]).unwrap();
+        assert!(contract.handle_command(acknowledge(&alice)).unwrap().is_empty());
+
+        let events = contract.handle_command(acknowledge(&carol)).unwrap();
+        assert!(matches!(events[..], [HyperEdgeEvent::ParticipantAcknowledged(_), HyperEdgeEvent::HyperEdgeActivated(_)]));
+        let contract = events.iter().try_fold(contract, |h, e| h.apply_event_pure(e)).unwrap();
+        assert_eq!(contract.state, HyperEdgeState::Active);
+
+        // A removed participant's acknowledgement stops counting
+        let removed = HyperEdgeEvent::ParticipantRemoved(ParticipantRemoved {
+            event_id: Uuid::now_v7(),
+            identity: MessageIdentity::new_root(),
+            hyperedge_id: contract.id,
+            participant: carol,
+            reason: "withdrew".to_string(),
+            removed_by: "test".to_string(),
+            removed_at: Utc::now(),
+        });
+        let contract = contract.apply_event_pure(&removed).unwrap();
+        assert!(!contract.quorum_reached());
+    }
+
+    #[test]
+    fn test_group_commands() {
+        use crate::commands::{AddParticipantGroup, AssignToGroup, RemoveParticipantGroup};
+
+        let (dev, designer) = (EntityRef::person(Uuid::now_v7()), EntityRef::person(Uuid::now_v7()));
+        let mut team = HyperEdgeConcept::new("Project Team", RelationshipCategory::Membership);
+        team.add_participant(dev.clone(), ParticipantRole::Member, 1.0).unwrap();
+        team.add_participant(designer.clone(), ParticipantRole::Member, 1.0).unwrap();
+        let id = team.id;
+
+        let add_group = |name: &str, parent: Option<&str>| {
+            HyperEdgeCommand::AddParticipantGroup(AddParticipantGroup {
+                identity: MessageIdentity::new_root(),
+                hyperedge_id: id,
+                name: name.to_string(),
+                role: ParticipantRole::Member,
+                parent: parent.map(str::to_string),
+                added_by: "test".to_string(),
+            })
+        };
+        let assign = |participant: &EntityRef, group: &str| {
+            HyperEdgeCommand::AssignToGroup(AssignToGroup {
+                identity: MessageIdentity::new_root(),
+                hyperedge_id: id,
+                participant: participant.clone(),
+                group: group.to_string(),
+                assigned_by: "test".to_string(),
+            })
+        };
+        let run = |team: HyperEdgeConcept, cmd| {
+            let events = team.handle_command(cmd).unwrap();
+            events.iter().try_fold(team, |h, e| h.apply_event_pure(e)).unwrap()
+        };
+
+        assert!(team.handle_command(add_group("frontend", Some("engineering"))).is_err());
+        let team = run(team, add_group("engineering", None));
+        let team = run(team, add_group("frontend", Some("engineering")));
+        assert!(team.handle_command(add_group("frontend", None)).is_err());
+        assert!(team.handle_command(assign(&EntityRef::person(Uuid::now_v7()), "frontend")).is_err());
+
+        let team = run(team, assign(&dev, "frontend"));
+        assert!(team.handle_command(assign(&dev, "frontend")).unwrap().is_empty());
+        assert_eq!(team.participants.group_members("engineering").len(), 1);
+
+        let team = run(
+            team,
+            HyperEdgeCommand::RemoveParticipantGroup(RemoveParticipantGroup {
+                identity: MessageIdentity::new_root(),
+                hyperedge_id: id,
+                name: "engineering".to_string(),
+                removed_by: "test".to_string(),
+            }),
+        );
+        assert_eq!(team.participants.group("frontend").unwrap().parent, None);
+        assert_eq!(team.participants.groups_of(&dev).len(), 1);
+    }
 }
@@ -11,11 +11,52 @@
 //! - **RelationshipSpace**: ConceptualSpace specialized for relationships
 //!
 //! All aggregates follow pure functional event sourcing with Mealy state machines.
+//! Commands are decided by the aggregate they target, or by the space when
+//! they restructure several relationships at once. Transitions can be
+//! further restricted per category with `TransitionGuards`, and common
+//! relationships standardized as `RelationshipTemplate`s.
 
+mod batch;
+mod commands;
 mod edge;
+mod guards;
 mod hyperedge;
+mod restructure;
 mod space;
+mod template;
 
+pub use batch::{BatchItemResult, BatchOutcome};
 pub use edge::{EdgeConcept, EdgeState};
+pub use guards::TransitionGuards;
 pub use hyperedge::{HyperEdgeConcept, HyperEdgeState};
-pub use space::RelationshipSpace;
+pub use space::{RelationshipSpace, TessellationSeeds};
+pub use template::{RelationshipTemplate, TemplateShape, ValidityRule};
+
+/// Check a quality's unit-interval dimensions
+pub(crate) fn check_quality(quality: &crate::quality::RelationshipQuality) -> crate::RelationshipResult<()> {
+    for (name, value) in [
+        ("strength", quality.strength),
+        ("trust", quality.trust),
+        ("reciprocity", quality.reciprocity),
+    ] {
+        if !(0.0..=1.0).contains(&value) {
+            return Err(crate::RelationshipError::QualityOutOfRange(format!("{} = {}", name, value)));
+        }
+    }
+    Ok(())
+}
+
+/// Trim a name given in a command, refusing blank ones
+fn check_name(name: &str) -> crate::RelationshipResult<String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(crate::RelationshipError::InvalidRelationship("name must not be blank".to_string()));
+    }
+    Ok(name.to_string())
+}
+
+/// Normalize a tag given in a command, refusing blank ones
+fn check_tag(tag: &str) -> crate::RelationshipResult<String> {
+    crate::value_objects::normalize_tag(tag)
+        .ok_or_else(|| crate::RelationshipError::InvalidRelationship("tag must not be blank".to_string()))
+}
@@ -0,0 +1,743 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Restructuring a RelationshipSpace
+//!
+//! Restructuring commands replace relationships with differently shaped
+//! ones and so span aggregates; the space decides them.
+//!
+//! ## Promotion
+//!
+//! ```text
+//! A --Membership--> T    ==>   HyperEdgeCreated   {A, T, B, C}
+//!                              HyperEdgeQualityUpdated
+//!                              PromotedFromEdge   (evidence, properties)
+//!                              HyperEdgeActivated (if the edge was active)
+//!                              EdgeTerminated     (EdgeRejected if never activated)
+//! ```
+//!
+//! ## Merging
+//!
+//! ```text
+//! survivor   <-- EvidenceAdded, PropertyUpdated, KnowledgeProgressed
+//!                PropertyUpdated merged_from [duplicate, ...]
+//! duplicate  <-- PropertyUpdated merged_into survivor
+//!                EdgeTerminated (EdgeRejected if never activated)
+//! ```
+//!
+//! ## Decomposition
+//!
+//! ```text
+//! Team {Lead: Leader, Ann: Member, Bo: Member}
+//!      --(Leader -> Member, Management)-->   Lead --Management--> Ann
+//!                                            Lead --Management--> Bo
+//! ```
+//!
+//! Each derived edge has the hyperedge's quality, is active if the
+//! hyperedge is (pending consent, for consent categories), and records it
+//! as `decomposed_from`. Pairs already joined by a live edge of the
+//! category, including one derived earlier in the same decomposition, are
+//! skipped. The edges are created by `CreateEdge`, `SetEdgeProperty` and
+//! `ActivateEdge` / `RequestConsent` commands decided one after another, so
+//! they pass the same checks as edges created one by one.
+//!
+//! Promotion and merging are likewise decided as commands against a copy
+//! of the space, so transition guards apply to every state they change.
+//! Policies are checked on every edge these commands create or relink,
+//! as are the edges created from templates and by merging entities.
+//!
+//! Deciding is pure: apply the returned events with
+//! `RelationshipSpace::apply_event` to change the space.
+
+use super::{EdgeConcept, EdgeState, RelationshipSpace};
+use crate::algebra::RelationshipKey;
+use crate::commands::{
+    ActivateEdge, ActivateHyperEdge, AddEdgeEvidence, CreateEdge, CreateHyperEdge, DecomposeHyperEdge, EdgeCommand,
+    HyperEdgeCommand, MergeEdges, PromoteEdgeToHyperEdge, RejectEdge, RequestConsent, SetEdgeProperty, TerminateEdge,
+    UpdateHyperEdgeQuality,
+};
+use crate::events::{EdgeEvent, EdgeKnowledgeProgressed, HyperEdgeEvent, HyperEdgePromotedFromEdge, RelationshipEvent};
+use crate::value_objects::{ActivationMode, IncidenceMatrix, ParticipantRole, RelationshipId};
+use crate::{RelationshipError, RelationshipResult};
+use chrono::Utc;
+use cim_domain::state_machine::State;
+use cim_domain::MessageIdentity;
+use cim_domain_spaces::KnowledgeLevel;
+use uuid::Uuid;
+
+impl RelationshipSpace {
+    pub(super) fn promote_edge(&self, c: PromoteEdgeToHyperEdge) -> RelationshipResult<Vec<RelationshipEvent>> {
+        let edge = self
+            .get_edge(&c.edge_id)
+            .ok_or_else(|| RelationshipError::EntityNotFound(c.edge_id.to_string()))?;
+        if self.hyperedges.contains_key(&c.hyperedge_id) {
+            return Err(RelationshipError::InvalidStateTransition(format!(
+                "hyperedge {} already exists",
+                c.hyperedge_id
+            )));
+        }
+
+        let mut participants = IncidenceMatrix::new();
+        participants.add_participant(edge.source.clone(), c.source_role, 1.0);
+        participants.add_participant(edge.target.clone(), c.target_role, 1.0);
+        for entry in c.new_participants.participants() {
+            if participants.get(&entry.entity_ref).is_some() {
+                return Err(RelationshipError::InvalidRelationship(format!(
+                    "{} already participates in edge {}",
+                    entry.entity_ref, edge.id
+                )));
+            }
+            participants.add_participant(entry.entity_ref.clone(), entry.role.clone(), entry.weight);
+        }
+        if participants.participant_count() < 2 {
+            return Err(RelationshipError::InsufficientParticipants);
+        }
+
+        let hyperedge_id = c.hyperedge_id;
+        let mut space = self.clone();
+        let mut events = Vec::new();
+        space.run_command(
+            HyperEdgeCommand::CreateHyperEdge(CreateHyperEdge {
+                identity: MessageIdentity::new_caused_by(&c.identity),
+                hyperedge_id,
+                name: c.name,
+                category: c.category.unwrap_or_else(|| edge.category.clone()),
+                initial_participants: participants,
+                activation: ActivationMode::Immediate,
+                created_by: c.promoted_by.clone(),
+            }),
+            &mut events,
+        )?;
+        space.run_command(
+            HyperEdgeCommand::UpdateHyperEdgeQuality(UpdateHyperEdgeQuality {
+                identity: MessageIdentity::new_caused_by(&c.identity),
+                hyperedge_id,
+                new_quality: edge.quality.clone(),
+                reason: format!("promoted from edge {}", edge.id),
+            }),
+            &mut events,
+        )?;
+        // Knowledge, evidence and properties carry over as they are
+        let promoted = RelationshipEvent::from(HyperEdgeEvent::PromotedFromEdge(HyperEdgePromotedFromEdge {
+            event_id: Uuid::now_v7(),
+            identity: MessageIdentity::new_caused_by(&c.identity),
+            hyperedge_id,
+            edge_id: edge.id,
+            knowledge_level: edge.knowledge_level,
+            confidence: edge.confidence,
+            evidence_cids: edge.evidence_cids.clone(),
+            properties: edge.properties.clone(),
+            promoted_by: c.promoted_by.clone(),
+            promoted_at: Utc::now(),
+        }));
+        space.apply_event(&promoted)?;
+        events.push(promoted);
+        if edge.state == EdgeState::Active {
+            space.run_command(
+                HyperEdgeCommand::ActivateHyperEdge(ActivateHyperEdge {
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    hyperedge_id,
+                    activated_by: c.promoted_by.clone(),
+                }),
+                &mut events,
+            )?;
+        }
+        // An edge that never went live is rejected rather than terminated
+        let reason = format!("promoted to hyperedge {}", hyperedge_id);
+        let end = if matches!(edge.state, EdgeState::Proposed | EdgeState::PendingConsent) {
+            EdgeCommand::RejectEdge(RejectEdge {
+                identity: MessageIdentity::new_caused_by(&c.identity),
+                edge_id: edge.id,
+                reason: Some(reason),
+                rejected_by: c.promoted_by,
+            })
+        } else {
+            EdgeCommand::TerminateEdge(TerminateEdge {
+                identity: MessageIdentity::new_caused_by(&c.identity),
+                edge_id: edge.id,
+                reason,
+                terminated_by: c.promoted_by,
+            })
+        };
+        space.run_command(end, &mut events)?;
+        Ok(events)
+    }
+
+    pub(super) fn merge_edges(&self, c: MergeEdges) -> RelationshipResult<Vec<RelationshipEvent>> {
+        let find = |id: &RelationshipId| {
+            self.get_edge(id)
+                .ok_or_else(|| RelationshipError::EntityNotFound(id.to_string()))
+        };
+        let (survivor, duplicate) = (find(&c.survivor_id)?, find(&c.duplicate_id)?);
+        if survivor.id == duplicate.id {
+            return Err(RelationshipError::InvalidRelationship(format!(
+                "cannot merge edge {} with itself",
+                survivor.id
+            )));
+        }
+        if RelationshipKey::of_edge(survivor) != RelationshipKey::of_edge(duplicate) {
+            return Err(RelationshipError::InvalidRelationship(format!(
+                "edges {} and {} differ in endpoints or category",
+                survivor.id, duplicate.id
+            )));
+        }
+        if let Some(edge) = [survivor, duplicate].into_iter().find(|e| e.state.is_closed()) {
+            return Err(RelationshipError::InvalidStateTransition(format!(
+                "edge {} is {:?}",
+                edge.id, edge.state
+            )));
+        }
+
+        let mut space = self.clone();
+        let mut events = Vec::new();
+        let set_property = |edge_id, key: &str, value| {
+            EdgeCommand::SetEdgeProperty(SetEdgeProperty {
+                identity: MessageIdentity::new_caused_by(&c.identity),
+                edge_id,
+                key: key.to_string(),
+                value,
+                expected_version: None,
+                set_by: c.merged_by.clone(),
+            })
+        };
+
+        // Evidence; adding it raises confidence with the evidence count.
+        // Evidence the survivor revoked stays revoked.
+        for cid in &duplicate.evidence_cids {
+            if !survivor.revoked_evidence.contains(cid) {
+                space.run_command(
+                    EdgeCommand::AddEdgeEvidence(AddEdgeEvidence {
+                        identity: MessageIdentity::new_caused_by(&c.identity),
+                        edge_id: survivor.id,
+                        evidence_cid: cid.clone(),
+                        evidence_type: "merged".to_string(),
+                    }),
+                    &mut events,
+                )?;
+            }
+        }
+        // The best known of the two edges and the merged evidence, by level
+        // and then confidence; the duplicate's knowledge carries over rather
+        // than being decided anew
+        let merged = &space.edges[&survivor.id];
+        let (level, confidence) = (merged.knowledge_level, merged.confidence);
+        let (to_level, new_confidence) = [survivor, duplicate]
+            .into_iter()
+            .map(|e| (e.knowledge_level, e.confidence))
+            .fold((level, confidence), |best, next| {
+                if (knowledge_rank(&next.0), next.1) > (knowledge_rank(&best.0), best.1) {
+                    next
+                } else {
+                    best
+                }
+            });
+        if (to_level, new_confidence) != (level, confidence) {
+            let progressed = RelationshipEvent::from(EdgeEvent::KnowledgeProgressed(EdgeKnowledgeProgressed {
+                event_id: Uuid::now_v7(),
+                identity: MessageIdentity::new_caused_by(&c.identity),
+                edge_id: survivor.id,
+                from_level: level,
+                to_level,
+                new_confidence,
+                reason: format!("merged with edge {}", duplicate.id),
+                progressed_at: Utc::now(),
+            }));
+            space.apply_event(&progressed)?;
+            events.push(progressed);
+        }
+
+        // Properties: the survivor's values win
+        let mut keys: Vec<&String> = duplicate
+            .properties
+            .keys()
+            .filter(|k| k.as_str() != "merged_from" && !survivor.properties.contains_key(*k))
+            .collect();
+        keys.sort();
+        for key in keys {
+            space.run_command(set_property(survivor.id, key, duplicate.properties[key].clone()), &mut events)?;
+        }
+        let merged_from: Vec<serde_json::Value> = [survivor, duplicate]
+            .into_iter()
+            .filter_map(|e| e.properties.get("merged_from").and_then(|v| v.as_array()))
+            .flatten()
+            .cloned()
+            .chain([serde_json::Value::String(duplicate.id.to_string())])
+            .collect();
+        space.run_command(
+            set_property(survivor.id, "merged_from", serde_json::Value::Array(merged_from)),
+            &mut events,
+        )?;
+
+        // The duplicate points at the survivor and ends
+        space.run_command(
+            set_property(duplicate.id, "merged_into", serde_json::Value::String(survivor.id.to_string())),
+            &mut events,
+        )?;
+        let reason = format!("merged into edge {}", survivor.id);
+        let end = if matches!(duplicate.state, EdgeState::Proposed | EdgeState::PendingConsent) {
+            EdgeCommand::RejectEdge(RejectEdge {
+                identity: MessageIdentity::new_caused_by(&c.identity),
+                edge_id: duplicate.id,
+                reason: Some(reason),
+                rejected_by: c.merged_by.clone(),
+            })
+        } else {
+            EdgeCommand::TerminateEdge(TerminateEdge {
+                identity: MessageIdentity::new_caused_by(&c.identity),
+                edge_id: duplicate.id,
+                reason,
+                terminated_by: c.merged_by.clone(),
+            })
+        };
+        space.run_command(end, &mut events)?;
+
+        Ok(events)
+    }
+
+    pub(super) fn decompose_hyperedge(&self, c: DecomposeHyperEdge) -> RelationshipResult<Vec<RelationshipEvent>> {
+        let hyperedge = self
+            .get_hyperedge(&c.hyperedge_id)
+            .ok_or_else(|| RelationshipError::EntityNotFound(c.hyperedge_id.to_string()))?;
+        if hyperedge.state.is_terminal() {
+            return Err(RelationshipError::InvalidStateTransition(format!(
+                "hyperedge {} is {:?}",
+                hyperedge.id, hyperedge.state
+            )));
+        }
+
+        let mut participants: Vec<_> = hyperedge.participants.participants().collect();
+        participants.sort_by_cached_key(|p| p.entity_ref.key().to_string());
+        let with_role = |role: &ParticipantRole| -> Vec<_> { participants.iter().filter(|p| p.role == *role).collect() };
+
+        // Each edge is decided against the space as the ones before it left it
+        let mut space = self.clone();
+        let mut events = Vec::new();
+        let mut pairs = 0;
+        let targets = with_role(&c.to_role);
+        for from in with_role(&c.from_role) {
+            for to in targets.iter().filter(|to| !to.entity_ref.same_entity(&from.entity_ref)) {
+                pairs += 1;
+                let key = RelationshipKey::of_edge(&EdgeConcept::new(
+                    "",
+                    from.entity_ref.clone(),
+                    to.entity_ref.clone(),
+                    c.category.clone(),
+                ));
+                if space
+                    .edges
+                    .values()
+                    .any(|e| !e.state.is_closed() && RelationshipKey::of_edge(e) == key)
+                {
+                    continue;
+                }
+
+                let edge_id = RelationshipId::new();
+                space.run_command(
+                    EdgeCommand::CreateEdge(CreateEdge {
+                        identity: MessageIdentity::new_caused_by(&c.identity),
+                        edge_id,
+                        source: from.entity_ref.clone(),
+                        target: to.entity_ref.clone(),
+                        category: c.category.clone(),
+                        name: format!("{} ({})", c.category.display_name(), hyperedge.name),
+                        quality: Some(hyperedge.quality.clone()),
+                        created_by: c.decomposed_by.clone(),
+                    }),
+                    &mut events,
+                )?;
+                space.run_command(
+                    EdgeCommand::SetEdgeProperty(SetEdgeProperty {
+                        identity: MessageIdentity::new_caused_by(&c.identity),
+                        edge_id,
+                        key: "decomposed_from".to_string(),
+                        value: serde_json::Value::String(hyperedge.id.to_string()),
+                        expected_version: None,
+                        set_by: c.decomposed_by.clone(),
+                    }),
+                    &mut events,
+                )?;
+                if hyperedge.is_active() {
+                    // Consent categories wait for both endpoints
+                    let activate = if space.requires_consent(&c.category) {
+                        EdgeCommand::RequestConsent(RequestConsent {
+                            identity: MessageIdentity::new_caused_by(&c.identity),
+                            edge_id,
+                            expires_at: None,
+                            requested_by: c.decomposed_by.clone(),
+                        })
+                    } else {
+                        EdgeCommand::ActivateEdge(ActivateEdge {
+                            identity: MessageIdentity::new_caused_by(&c.identity),
+                            edge_id,
+                            activated_by: c.decomposed_by.clone(),
+                        })
+                    };
+                    space.run_command(activate, &mut events)?;
+                }
+            }
+        }
+        if pairs == 0 {
+            return Err(RelationshipError::InvalidRelationship(format!(
+                "hyperedge {} has no {:?} -> {:?} pairs",
+                hyperedge.id, c.from_role, c.to_role
+            )));
+        }
+
+        Ok(events)
+    }
+}
+
+/// Order of knowledge levels, least known first
+fn knowledge_rank(level: &KnowledgeLevel) -> u8 {
+    match level {
+        KnowledgeLevel::Known => 2,
+        KnowledgeLevel::Suspected => 1,
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregates::{HyperEdgeConcept, HyperEdgeState};
+    use crate::commands::{RelationshipCommand, RestructureCommand};
+    use crate::quality::RelationshipQuality;
+    use crate::value_objects::{EntityRef, RelationshipCategory};
+    use cim_domain_spaces::TopologicalSpaceId;
+
+    #[test]
+    fn test_promote_edge_to_hyperedge() {
+        let (alice, acme, bob) = (
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::organization(Uuid::now_v7()),
+            EntityRef::person(Uuid::now_v7()),
+        );
+        let mut edge = EdgeConcept::new("Employment", alice.clone(), acme.clone(), RelationshipCategory::Employment)
+            .with_quality(RelationshipQuality::default_employment())
+            .with_property("contract", serde_json::json!("c-17"));
+        edge.evidence_cids.push("bafy-contract".to_string());
+        edge.activate().unwrap();
+        let edge_id = edge.id;
+        let mut space = RelationshipSpace::new("Org", TopologicalSpaceId::new());
+        space.add_edge(edge).unwrap();
+
+        let mut new_participants = IncidenceMatrix::new();
+        new_participants.add_participant(bob.clone(), ParticipantRole::Member, 0.5);
+        let promote = |new_participants| {
+            RelationshipCommand::from(RestructureCommand::PromoteEdgeToHyperEdge(PromoteEdgeToHyperEdge {
+                identity: MessageIdentity::new_root(),
+                edge_id,
+                hyperedge_id: RelationshipId::new(),
+                name: "Team".to_string(),
+                category: Some(RelationshipCategory::Membership),
+                source_role: ParticipantRole::Leader,
+                target_role: ParticipantRole::Primary,
+                new_participants,
+                promoted_by: "test".to_string(),
+            }))
+        };
+
+        let mut duplicate = IncidenceMatrix::new();
+        duplicate.add_participant(alice.clone(), ParticipantRole::Member, 0.5);
+        assert!(space.handle_command(promote(duplicate)).is_err());
+
+        for event in space.handle_command(promote(new_participants)).unwrap() {
+            space.apply_event(&event).unwrap();
+        }
+        assert_eq!(space.get_edge(&edge_id).unwrap().state, EdgeState::Terminated);
+        let team = space.hyperedges.values().next().unwrap();
+        assert_eq!(team.state, HyperEdgeState::Active);
+        assert_eq!(team.participant_count(), 3);
+        assert_eq!(team.participants.get(&alice).unwrap().role, ParticipantRole::Leader);
+        assert_eq!(team.quality.strength, RelationshipQuality::default_employment().strength);
+        assert_eq!(team.evidence_cids, vec!["bafy-contract".to_string()]);
+        assert_eq!(team.properties["contract"], serde_json::json!("c-17"));
+        assert_eq!(team.properties["promoted_from"], serde_json::json!(edge_id.to_string()));
+    }
+
+    #[test]
+    fn test_promote_rejects_unactivated_edges() {
+        let (alice, bob, carol) = (
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::person(Uuid::now_v7()),
+        );
+        let mut space = RelationshipSpace::new("Social", TopologicalSpaceId::new())
+            .with_consent_category(RelationshipCategory::Friendship);
+        let proposed = EdgeConcept::new("Friends", alice, bob.clone(), RelationshipCategory::Friendship);
+        let mut pending = EdgeConcept::new("Friends", bob, carol, RelationshipCategory::Friendship);
+        pending.transition_to(EdgeState::PendingConsent).unwrap();
+        let ids = [proposed.id, pending.id];
+        space.add_edge(proposed).unwrap();
+        space.add_edge(pending).unwrap();
+
+        for edge_id in ids {
+            let mut new_participants = IncidenceMatrix::new();
+            new_participants.add_participant(EntityRef::person(Uuid::now_v7()), ParticipantRole::Member, 0.5);
+            let promote = RelationshipCommand::from(RestructureCommand::PromoteEdgeToHyperEdge(PromoteEdgeToHyperEdge {
+                identity: MessageIdentity::new_root(),
+                edge_id,
+                hyperedge_id: RelationshipId::new(),
+                name: "Circle".to_string(),
+                category: None,
+                source_role: ParticipantRole::Member,
+                target_role: ParticipantRole::Member,
+                new_participants,
+                promoted_by: "test".to_string(),
+            }));
+            for event in space.handle_command(promote).unwrap() {
+                space.apply_event(&event).unwrap();
+            }
+            let edge = space.get_edge(&edge_id).unwrap();
+            assert_eq!(edge.state, EdgeState::Rejected);
+            let reason = edge.properties["rejection_reason"].as_str().unwrap();
+            assert!(reason.starts_with("promoted to hyperedge"));
+        }
+        assert_eq!(space.hyperedges.len(), 2);
+    }
+
+    #[test]
+    fn test_merge_edges() {
+        let (alice, bob) = (EntityRef::person(Uuid::now_v7()), EntityRef::person(Uuid::now_v7()));
+        let mut survivor = EdgeConcept::new("Friends", alice.clone(), bob.clone(), RelationshipCategory::Friendship)
+            .with_property("since", serde_json::json!(2019));
+        survivor.evidence_cids.push("bafy-photo".to_string());
+        survivor.confidence = 0.1;
+        survivor.activate().unwrap();
+        // Symmetric: the same friendship with the endpoints swapped
+        let mut duplicate = EdgeConcept::new("Friends", bob.clone(), alice.clone(), RelationshipCategory::Friendship)
+            .with_property("since", serde_json::json!(2020))
+            .with_property("source", serde_json::json!("crm"));
+        duplicate.evidence_cids = vec!["bafy-photo".to_string(), "bafy-chat".to_string()];
+        duplicate.knowledge_level = cim_domain_spaces::KnowledgeLevel::Known;
+        duplicate.confidence = 0.9;
+        let (survivor_id, duplicate_id) = (survivor.id, duplicate.id);
+
+        let mut space = RelationshipSpace::new("Social", TopologicalSpaceId::new());
+        space.add_edge(survivor).unwrap();
+        space.add_edge(duplicate).unwrap();
+        let mut unrelated = EdgeConcept::new("Knows", alice, bob, RelationshipCategory::Custom("knows".to_string()));
+        unrelated.activate().unwrap();
+        let unrelated_id = unrelated.id;
+        space.add_edge(unrelated).unwrap();
+
+        let merge = |survivor_id, duplicate_id| {
+            RelationshipCommand::from(RestructureCommand::MergeEdges(MergeEdges {
+                identity: MessageIdentity::new_root(),
+                survivor_id,
+                duplicate_id,
+                merged_by: "test".to_string(),
+            }))
+        };
+        assert!(space.handle_command(merge(survivor_id, unrelated_id)).is_err());
+
+        for event in space.handle_command(merge(survivor_id, duplicate_id)).unwrap() {
+            space.apply_event(&event).unwrap();
+        }
+        let survivor = space.get_edge(&survivor_id).unwrap();
+        assert_eq!(survivor.evidence_cids.len(), 2);
+        assert_eq!(survivor.confidence, 0.9);
+        assert_eq!(survivor.knowledge_level, cim_domain_spaces::KnowledgeLevel::Known);
+        assert_eq!(survivor.properties["since"], serde_json::json!(2019));
+        assert_eq!(survivor.properties["source"], serde_json::json!("crm"));
+        assert_eq!(survivor.properties["merged_from"], serde_json::json!([duplicate_id.to_string()]));
+
+        // Never activated, so rejected rather than terminated
+        let duplicate = space.get_edge(&duplicate_id).unwrap();
+        assert_eq!(duplicate.state, EdgeState::Rejected);
+        assert_eq!(duplicate.properties["merged_into"], serde_json::json!(survivor_id.to_string()));
+    }
+
+    #[test]
+    fn test_merge_keeps_the_higher_knowledge_level() {
+        let (alice, bob) = (EntityRef::person(Uuid::now_v7()), EntityRef::person(Uuid::now_v7()));
+        let mut survivor = EdgeConcept::new("Friends", alice.clone(), bob.clone(), RelationshipCategory::Friendship);
+        survivor.evidence_cids = vec!["bafy-a".to_string(), "bafy-b".to_string(), "bafy-c".to_string()];
+        survivor.knowledge_level = KnowledgeLevel::Known;
+        survivor.confidence = 0.75;
+        survivor.activate().unwrap();
+        // More confident, but only suspected
+        let mut duplicate = EdgeConcept::new("Friends", alice, bob, RelationshipCategory::Friendship);
+        duplicate.evidence_cids.push("bafy-d".to_string());
+        duplicate.knowledge_level = KnowledgeLevel::Suspected;
+        duplicate.confidence = 0.95;
+        let (survivor_id, duplicate_id) = (survivor.id, duplicate.id);
+        let mut space = RelationshipSpace::new("Social", TopologicalSpaceId::new());
+        space.add_edge(survivor).unwrap();
+        space.add_edge(duplicate).unwrap();
+
+        let merge = RelationshipCommand::from(RestructureCommand::MergeEdges(MergeEdges {
+            identity: MessageIdentity::new_root(),
+            survivor_id,
+            duplicate_id,
+            merged_by: "test".to_string(),
+        }));
+        for event in space.handle_command(merge).unwrap() {
+            space.apply_event(&event).unwrap();
+        }
+        let survivor = space.get_edge(&survivor_id).unwrap();
+        assert_eq!(survivor.evidence_cids.len(), 4);
+        assert_eq!((survivor.knowledge_level, survivor.confidence), (KnowledgeLevel::Known, 0.75));
+    }
+
+    #[test]
+    fn test_merge_rejects_pending_consent() {
+        let (alice, bob) = (EntityRef::person(Uuid::now_v7()), EntityRef::person(Uuid::now_v7()));
+        let mut space = RelationshipSpace::new("Social", TopologicalSpaceId::new())
+            .with_consent_category(RelationshipCategory::Friendship);
+        let mut survivor = EdgeConcept::new("Friends", alice.clone(), bob.clone(), RelationshipCategory::Friendship);
+        survivor.activate().unwrap();
+        let mut duplicate = EdgeConcept::new("Friends", bob, alice, RelationshipCategory::Friendship);
+        duplicate.transition_to(EdgeState::PendingConsent).unwrap();
+        let (survivor_id, duplicate_id) = (survivor.id, duplicate.id);
+        space.add_edge(survivor).unwrap();
+        space.add_edge(duplicate).unwrap();
+
+        let merge = RelationshipCommand::from(RestructureCommand::MergeEdges(MergeEdges {
+            identity: MessageIdentity::new_root(),
+            survivor_id,
+            duplicate_id,
+            merged_by: "test".to_string(),
+        }));
+        for event in space.handle_command(merge).unwrap() {
+            space.apply_event(&event).unwrap();
+        }
+        assert_eq!(space.get_edge(&duplicate_id).unwrap().state, EdgeState::Rejected);
+    }
+
+    #[test]
+    fn test_restructuring_passes_guards() {
+        use crate::aggregates::TransitionGuards;
+
+        let (alice, bob) = (EntityRef::person(Uuid::now_v7()), EntityRef::person(Uuid::now_v7()));
+        let guards = TransitionGuards::new().with_edge_guard(
+            RelationshipCategory::Membership,
+            EdgeState::Terminated,
+            "tenure",
+            |_| Err("members stay".to_string()),
+        );
+        let mut space = RelationshipSpace::new("Club", TopologicalSpaceId::new()).with_transition_guards(guards);
+        let mut ids = Vec::new();
+        for _ in 0..2 {
+            let mut edge = EdgeConcept::new("Member", alice.clone(), bob.clone(), RelationshipCategory::Membership);
+            edge.activate().unwrap();
+            ids.push(edge.id);
+            space.add_edge(edge).unwrap();
+        }
+
+        let merge = RelationshipCommand::from(RestructureCommand::MergeEdges(MergeEdges {
+            identity: MessageIdentity::new_root(),
+            survivor_id: ids[0],
+            duplicate_id: ids[1],
+            merged_by: "test".to_string(),
+        }));
+        assert!(space.handle_command(merge).is_err());
+
+        let promote = RelationshipCommand::from(RestructureCommand::PromoteEdgeToHyperEdge(PromoteEdgeToHyperEdge {
+            identity: MessageIdentity::new_root(),
+            edge_id: ids[0],
+            hyperedge_id: RelationshipId::new(),
+            name: "Club".to_string(),
+            category: None,
+            source_role: ParticipantRole::Member,
+            target_role: ParticipantRole::Primary,
+            new_participants: IncidenceMatrix::new(),
+            promoted_by: "test".to_string(),
+        }));
+        assert!(space.handle_command(promote).is_err());
+    }
+
+    #[test]
+    fn test_decompose_hyperedge() {
+        let (lead, ann, bo) = (
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::person(Uuid::now_v7()),
+        );
+        let mut team = HyperEdgeConcept::new("Platform", RelationshipCategory::Membership);
+        team.add_participant(lead.clone(), ParticipantRole::Leader, 1.0).unwrap();
+        team.add_participant(ann.clone(), ParticipantRole::Member, 1.0).unwrap();
+        team.add_participant(bo.clone(), ParticipantRole::Member, 1.0).unwrap();
+        team.activate().unwrap();
+        let team_id = team.id;
+
+        let mut space = RelationshipSpace::new("Org", TopologicalSpaceId::new());
+        space.add_hyperedge(team);
+        // Already managed
+        let mut existing = EdgeConcept::new("Manages", lead.clone(), ann, RelationshipCategory::Management);
+        existing.activate().unwrap();
+        space.add_edge(existing).unwrap();
+
+        let decompose = |from_role, to_role| {
+            RelationshipCommand::from(RestructureCommand::DecomposeHyperEdge(DecomposeHyperEdge {
+                identity: MessageIdentity::new_root(),
+                hyperedge_id: team_id,
+                from_role,
+                to_role,
+                category: RelationshipCategory::Management,
+                decomposed_by: "test".to_string(),
+            }))
+        };
+        assert!(space
+            .handle_command(decompose(ParticipantRole::Approver, ParticipantRole::Member))
+            .is_err());
+
+        for event in space
+            .handle_command(decompose(ParticipantRole::Leader, ParticipantRole::Member))
+            .unwrap()
+        {
+            space.apply_event(&event).unwrap();
+        }
+        assert_eq!(space.edges.len(), 2);
+        let derived = space.edges.values().find(|e| e.target == bo).unwrap();
+        assert_eq!(derived.source, lead);
+        assert!(derived.is_active());
+        assert_eq!(derived.properties["decomposed_from"], serde_json::json!(team_id.to_string()));
+    }
+
+    #[test]
+    fn test_decompose_sees_its_own_edges() {
+        let (ann, bo, cy) = (
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::person(Uuid::now_v7()),
+        );
+        let mut team = HyperEdgeConcept::new("Crew", RelationshipCategory::Membership);
+        for member in [&ann, &bo, &cy] {
+            team.add_participant(member.clone(), ParticipantRole::Member, 1.0).unwrap();
+        }
+        team.activate().unwrap();
+        let team_id = team.id;
+        let mut space = RelationshipSpace::new("Org", TopologicalSpaceId::new())
+            .with_consent_category(RelationshipCategory::Friendship);
+        space.add_hyperedge(team);
+
+        let decompose = |category| {
+            RelationshipCommand::from(RestructureCommand::DecomposeHyperEdge(DecomposeHyperEdge {
+                identity: MessageIdentity::new_root(),
+                hyperedge_id: team_id,
+                from_role: ParticipantRole::Member,
+                to_role: ParticipantRole::Member,
+                category,
+                decomposed_by: "test".to_string(),
+            }))
+        };
+
+        // Friendship is symmetric: one edge per pair, each awaiting consent
+        for event in space.handle_command(decompose(RelationshipCategory::Friendship)).unwrap() {
+            space.apply_event(&event).unwrap();
+        }
+        assert_eq!(space.edges.len(), 3);
+        assert!(space.edges.values().all(|e| e.state == EdgeState::PendingConsent));
+
+        // Both directions of a dependency would close a cycle
+        let before = space.version;
+        assert!(matches!(
+            space.handle_command(decompose(RelationshipCategory::DependsOn)),
+            Err(RelationshipError::CyclicDependency(_))
+        ));
+        assert_eq!(space.version, before);
+    }
+
+}
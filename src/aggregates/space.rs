@@ -6,14 +6,53 @@
 //!
 //! A conceptual space that contains relationship concepts (edges and hyperedges)
 //! and provides Voronoi tessellation for similarity clustering.
+//!
+//! ## Tessellation
+//!
+//! ```text
+//! compute_tessellation(seeds) --> VoronoiTessellation (cached)
+//!                                      |
+//!        add_edge / add_hyperedge --> version bump --> stale
+//! ```
+//!
+//! The tessellation is computed on demand and cached together with the
+//! space version it was built at. Mutations do not discard it; they leave
+//! it stale until the next `compute_tessellation` or `ensure_tessellation`.
 
-use crate::aggregates::{EdgeConcept, HyperEdgeConcept};
-use crate::quality::QualityPoint;
-use crate::value_objects::RelationshipId;
+use crate::aggregates::{EdgeConcept, HyperEdgeConcept, RelationshipTemplate, TransitionGuards};
+use crate::algebra::CompositionRegistry;
+use crate::events::{EdgeEvent, HyperEdgeEvent, PolicyEvent, RelationshipEvent};
+use crate::graph::{
+    self, Clique, EgoFilter, EgoNetwork, Lineage, ReachabilityIndex, RelationshipGraph,
+    TerminationImpact, TopologicalOrder,
+};
+use crate::invariants::{PolicySet, RelationshipPolicy, RoleSchema, RoleSchemaRegistry};
+use crate::quality::{
+    CategoryConvexity, CategoryDrift, QualityCovariance, CategoryPrototypes, ConvexityValidator, DurationModel,
+    DynamicQualityPoint, PcaProjection, QualityIndex, QualityPoint, QualitySchema, QualityWeightRegistry,
+    QualityWeights,
+};
+use crate::value_objects::{EntityRef, RelationshipCategory, RelationshipId, ReproposalPolicy, TagMatch, TagStats};
+use crate::{RelationshipError, RelationshipResult};
 use chrono::{DateTime, Utc};
-use cim_domain_spaces::{ConceptualSpaceId, TopologicalSpaceId, VoronoiTessellation};
+use cim_domain::state_machine::State;
+use cim_domain_spaces::{
+    ConceptualSpaceId, Point3, TopologicalSpaceId, VoronoiCell, VoronoiTessellation,
+};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use uuid::Uuid;
+
+/// Which points seed the cells of a tessellation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TessellationSeeds {
+    /// One cell per live relationship, generated at its position
+    #[default]
+    Relationships,
+    /// One cell per category, generated at the mean position of its live
+    /// relationships
+    CategoryPrototypes,
+}
 
 /// RelationshipSpace - A conceptual space for relationships
 ///
@@ -21,7 +60,10 @@ use std::collections::HashMap;
 /// - Edges (binary relationships)
 /// - HyperEdges (N-ary relationships)
 /// - Voronoi tessellation for clustering
+///
+/// The similarity index is not serialized; it is rebuilt on load.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(remote = "Self")]
 pub struct RelationshipSpace {
     /// Unique identifier
     pub id: ConceptualSpaceId,
@@ -35,8 +77,74 @@ pub struct RelationshipSpace {
     /// HyperEdge concepts in this space
     pub hyperedges: HashMap<RelationshipId, HyperEdgeConcept>,
 
+    /// Quality dimensions of this space, the standard five plus any
+    /// domain-specific extensions
+    #[serde(default)]
+    pub quality_schema: QualitySchema,
+
+    /// How relationship durations map onto the duration dimension
+    #[serde(default)]
+    pub duration_model: DurationModel,
+
+    /// Quality weights per category, for similarity queries
+    #[serde(default)]
+    pub weight_registry: QualityWeightRegistry,
+
+    /// Rules for composing relationships
+    #[serde(default)]
+    pub composition_rules: CompositionRegistry,
+
+    /// Categories whose edges may relate an entity to itself
+    #[serde(default)]
+    pub reflexive_categories: HashSet<RelationshipCategory>,
+
+    /// Categories whose edges activate only with the consent of both endpoints
+    #[serde(default)]
+    pub consent_categories: HashSet<RelationshipCategory>,
+
+    /// Categories whose active hyperedges change participants only while
+    /// restructuring
+    #[serde(default)]
+    pub restructuring_categories: HashSet<RelationshipCategory>,
+
+    /// How often and how soon rejected edges may be re-proposed
+    #[serde(default)]
+    pub reproposal_policy: ReproposalPolicy,
+
+    /// Role requirements of hyperedges per category
+    #[serde(default)]
+    pub role_schemas: RoleSchemaRegistry,
+
+    /// Constraints issued by Policy entities
+    #[serde(default)]
+    pub policies: PolicySet,
+
+    /// Templates relationships can be created from, by name
+    #[serde(default)]
+    pub templates: HashMap<String, RelationshipTemplate>,
+
+    /// Transition guards installed on every relationship in the space
+    #[serde(skip)]
+    pub guards: TransitionGuards,
+
+    /// KD-tree over edge quality points, for similarity queries
+    #[serde(skip)]
+    pub edge_index: QualityIndex,
+    /// When the index last placed every edge; ongoing edges have since
+    /// drifted along the duration axis
+    #[serde(skip)]
+    edge_index_at: DateTime<Utc>,
+
     /// Voronoi tessellation (computed from relationship positions)
     pub tessellation: Option<VoronoiTessellation>,
+    /// Space version and seeds the tessellation was computed from
+    /// (`None` seeds = generators installed by the caller)
+    #[serde(default)]
+    pub tessellation_basis: Option<(u64, Option<TessellationSeeds>)>,
+    /// Full quality points of installed generators, by cell id; cells are
+    /// located by these rather than by their 3D generator
+    #[serde(default)]
+    pub tessellation_points: HashMap<Uuid, QualityPoint>,
 
     /// Version
     pub version: u64,
@@ -56,29 +164,260 @@ impl RelationshipSpace {
             topology_id,
             edges: HashMap::new(),
             hyperedges: HashMap::new(),
+            quality_schema: QualitySchema::standard(),
+            duration_model: DurationModel::default(),
+            weight_registry: QualityWeightRegistry::standard(),
+            composition_rules: CompositionRegistry::standard(),
+            reflexive_categories: HashSet::new(),
+            consent_categories: HashSet::new(),
+            restructuring_categories: HashSet::new(),
+            reproposal_policy: ReproposalPolicy::default(),
+            role_schemas: RoleSchemaRegistry::new(),
+            policies: PolicySet::new(),
+            templates: HashMap::new(),
+            guards: TransitionGuards::new(),
+            edge_index: QualityIndex::new(),
+            edge_index_at: now,
             tessellation: None,
+            tessellation_basis: None,
+            tessellation_points: HashMap::new(),
             version: 0,
             created_at: now,
             updated_at: now,
         }
     }
 
+    /// Set the quality dimensions of this space
+    pub fn with_quality_schema(mut self, schema: QualitySchema) -> Self {
+        self.quality_schema = schema;
+        self
+    }
+
+    /// Set how durations are normalized in this space
+    pub fn with_duration_model(mut self, model: DurationModel) -> Self {
+        self.duration_model = model;
+        self.rebuild_edge_index();
+        self
+    }
+
+    /// Set the per-category quality weights of this space
+    pub fn with_weight_registry(mut self, registry: QualityWeightRegistry) -> Self {
+        self.weight_registry = registry;
+        self
+    }
+
+    /// Set the composition rules of this space
+    pub fn with_composition_rules(mut self, rules: CompositionRegistry) -> Self {
+        self.composition_rules = rules;
+        self
+    }
+
+    /// Allow edges of a category to relate an entity to itself
+    pub fn with_reflexive_category(mut self, category: RelationshipCategory) -> Self {
+        self.reflexive_categories.insert(category);
+        self
+    }
+
+    /// Check if edges of a category may relate an entity to itself
+    pub fn allows_self_edge(&self, category: &RelationshipCategory) -> bool {
+        self.reflexive_categories.contains(category)
+    }
+
+    /// Require the participants of a hyperedge category to fill roles
+    pub fn with_role_schema(mut self, category: RelationshipCategory, schema: RoleSchema) -> Self {
+        self.role_schemas.schemas.insert(category, schema);
+        self
+    }
+
+    /// Enact a policy constraint
+    pub fn with_policy(mut self, policy: RelationshipPolicy) -> Self {
+        self.policies.policies.push(policy);
+        self
+    }
+
+    /// Check an edge against the policies in force
+    pub fn check_policies(&self, edge: &EdgeConcept) -> RelationshipResult<()> {
+        let violations = self.policies.check(edge);
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(RelationshipError::PolicyViolated(violations))
+        }
+    }
+
+    /// Guard the transitions of every relationship in this space
+    ///
+    /// Guards are not serialized; install them again after loading a space.
+    pub fn with_transition_guards(mut self, guards: TransitionGuards) -> Self {
+        for edge in self.edges.values_mut() {
+            edge.guards = guards.clone();
+        }
+        for hyperedge in self.hyperedges.values_mut() {
+            hyperedge.guards = guards.clone();
+        }
+        self.guards = guards;
+        self
+    }
+
+    /// Check a hyperedge against the role schema of its category, and of
+    /// the template it was created from
+    pub fn check_roles(&self, hyperedge: &HyperEdgeConcept) -> RelationshipResult<()> {
+        let mut violations = self.role_schemas.check(&hyperedge.category, &hyperedge.participants);
+        if let Some(schema) = self.template_role_schema(hyperedge) {
+            violations.extend(schema.check(&hyperedge.category, &hyperedge.participants));
+        }
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(RelationshipError::RoleConstraintViolated(violations))
+        }
+    }
+
+    /// Register a template, replacing any existing one of the same name
+    pub fn with_template(mut self, template: RelationshipTemplate) -> Self {
+        self.templates.insert(template.name.clone(), template);
+        self
+    }
+
+    /// Template by name
+    pub fn template(&self, name: &str) -> Option<&RelationshipTemplate> {
+        self.templates.get(name)
+    }
+
+    /// Require the consent of both endpoints before edges of a category
+    /// become active
+    pub fn with_consent_category(mut self, category: RelationshipCategory) -> Self {
+        self.consent_categories.insert(category);
+        self
+    }
+
+    /// Check if edges of a category need the consent of both endpoints
+    pub fn requires_consent(&self, category: &RelationshipCategory) -> bool {
+        self.consent_categories.contains(category)
+    }
+
+    /// Set how often and how soon rejected edges may be re-proposed
+    pub fn with_reproposal_policy(mut self, policy: ReproposalPolicy) -> Self {
+        for edge in self.edges.values_mut() {
+            edge.reproposal_policy = policy;
+        }
+        self.reproposal_policy = policy;
+        self
+    }
+
+    /// Allow participant changes to active hyperedges of a category only
+    /// between `BeginRestructuring` and `CompleteRestructuring`
+    pub fn with_restructuring_category(mut self, category: RelationshipCategory) -> Self {
+        self.restructuring_categories.insert(category);
+        self
+    }
+
+    /// Check if hyperedges of a category change participants only while
+    /// restructuring
+    pub fn requires_restructuring(&self, category: &RelationshipCategory) -> bool {
+        self.restructuring_categories.contains(category)
+    }
+
+    /// Edges whose consent request has expired, for `ExpireConsent`
+    pub fn expired_consents(&self, now: DateTime<Utc>) -> Vec<RelationshipId> {
+        self.edges.values().filter(|e| e.consent_expired(now)).map(|e| e.id).collect()
+    }
+
+    /// Live edges whose validity has ended, for `ExpireEdge`
+    pub fn expired_edges(&self, now: DateTime<Utc>) -> Vec<RelationshipId> {
+        self.edges.values().filter(|e| e.validity_expired(now)).map(|e| e.id).collect()
+    }
+
+    /// Compose two edges of this space by its composition rules
+    pub fn compose(&self, first: &RelationshipId, second: &RelationshipId) -> RelationshipResult<EdgeConcept> {
+        let edge = |id: &RelationshipId| {
+            self.get_edge(id)
+                .ok_or_else(|| RelationshipError::EntityNotFound(id.to_string()))
+        };
+        edge(first)?.compose_with(&self.composition_rules, edge(second)?)
+    }
+
     /// Add an edge to the space
-    pub fn add_edge(&mut self, edge: EdgeConcept) {
+    ///
+    /// DependsOn edges that would close a dependency cycle are rejected, as
+    /// are self-edges of categories not opted in with
+    /// `with_reflexive_category` (a self-dependency is always a cycle).
+    /// Adding an edge whose id is already present replaces it. The edge
+    /// takes the space's transition guards and reproposal policy.
+    pub fn add_edge(&mut self, edge: EdgeConcept) -> RelationshipResult<()> {
+        self.check_edge(&edge)?;
+        self.insert_edge(edge);
+        Ok(())
+    }
+
+    /// Insert an edge without `check_edge`, for edges already decided on
+    ///
+    /// Stored events are facts: replaying them must not re-run the checks
+    /// their commands passed.
+    fn insert_edge(&mut self, mut edge: EdgeConcept) {
+        edge.guards = self.guards.clone();
+        edge.reproposal_policy = self.reproposal_policy;
+        // Archived edges are out of similarity search until restored
+        if edge.is_archived() {
+            self.edge_index.remove(&edge.id);
+        } else {
+            self.edge_index.insert(edge.id, &edge.quality_point_with(&self.duration_model));
+        }
         self.edges.insert(edge.id, edge);
         self.updated_at = Utc::now();
         self.version += 1;
-        // Invalidate tessellation
-        self.tessellation = None;
+    }
+
+    /// Check that an edge may be added, as `add_edge` does
+    pub fn check_edge(&self, edge: &EdgeConcept) -> RelationshipResult<()> {
+        self.check_dependency_cycle(edge)?;
+        if edge.is_reflexive() && !self.allows_self_edge(&edge.category) {
+            return Err(RelationshipError::InvalidRelationship(format!(
+                "{} edges may not relate {} to itself",
+                edge.category.display_name(),
+                edge.source
+            )));
+        }
+        Ok(())
     }
 
     /// Add a hyperedge to the space
-    pub fn add_hyperedge(&mut self, hyperedge: HyperEdgeConcept) {
+    ///
+    /// The hyperedge takes the space's transition guards.
+    pub fn add_hyperedge(&mut self, mut hyperedge: HyperEdgeConcept) {
+        hyperedge.guards = self.guards.clone();
         self.hyperedges.insert(hyperedge.id, hyperedge);
         self.updated_at = Utc::now();
         self.version += 1;
-        // Invalidate tessellation
-        self.tessellation = None;
+    }
+
+    /// Apply a relationship event to the edge or hyperedge it belongs to
+    ///
+    /// Creation events add the relationship they create; other events for
+    /// relationships not in this space are ignored. Events are applied as
+    /// recorded: the cycle and self-edge checks belong to command handling.
+    pub fn apply_event(&mut self, event: &RelationshipEvent) -> RelationshipResult<()> {
+        match event {
+            RelationshipEvent::Edge(e) => {
+                if let Some(edge) = self.get_edge(&e.edge_id()) {
+                    let next = edge.apply_event_pure(e)?;
+                    self.insert_edge(next);
+                } else if let EdgeEvent::EdgeCreated(_) = e {
+                    self.insert_edge(EdgeConcept::from_events(std::slice::from_ref(e))?);
+                }
+            }
+            RelationshipEvent::HyperEdge(e) => {
+                if let Some(hyperedge) = self.get_hyperedge(&e.hyperedge_id()) {
+                    let next = hyperedge.apply_event_pure(e)?;
+                    self.add_hyperedge(next);
+                } else if let HyperEdgeEvent::HyperEdgeCreated(_) = e {
+                    self.add_hyperedge(HyperEdgeConcept::from_events(std::slice::from_ref(e))?);
+                }
+            }
+            RelationshipEvent::Policy(PolicyEvent::PolicyEnacted(e)) => self.policies.policies.push(e.policy.clone()),
+            RelationshipEvent::Policy(PolicyEvent::PolicyRevoked(e)) => self.policies.revoke(&e.policy),
+        }
+        Ok(())
     }
 
     /// Get an edge by ID
@@ -98,12 +437,144 @@ impl RelationshipSpace {
 
     /// Find similar edges to a given point in quality space
     pub fn find_similar_edges(&self, point: &QualityPoint, max_distance: f64) -> Vec<&EdgeConcept> {
+        self.edge_index
+            .within(point, max_distance + self.edge_index_drift())
+            .iter()
+            .filter_map(|id| self.edges.get(id))
+            .filter(|e| e.quality_point_with(&self.duration_model).distance(point) <= max_distance)
+            .collect()
+    }
+
+    /// Find edges within a weighted distance of a point
+    pub fn find_similar_edges_weighted(
+        &self,
+        point: &QualityPoint,
+        max_distance: f64,
+        weights: &QualityWeights,
+    ) -> Vec<&EdgeConcept> {
+        // A weighted ball fits inside the unweighted ball scaled by the
+        // smallest weight
+        let min_weight = weights.min_weight();
+        let radius = if min_weight > 0.0 {
+            max_distance / min_weight + self.edge_index_drift()
+        } else {
+            f64::INFINITY
+        };
+        self.edge_index
+            .within(point, radius)
+            .iter()
+            .filter_map(|id| self.edges.get(id))
+            .filter(|e| {
+                e.quality_point_with(&self.duration_model)
+                    .weighted_distance(point, weights)
+                    <= max_distance
+            })
+            .collect()
+    }
+
+    /// Covariance of the live relationships in this space
+    pub fn quality_covariance(&self) -> RelationshipResult<QualityCovariance> {
+        let points: Vec<QualityPoint> = self.category_points().into_iter().map(|(_, p)| p).collect();
+        QualityCovariance::estimate(&points)
+    }
+
+    /// Find edges within a Mahalanobis distance of a point
+    ///
+    /// Use with `quality_covariance` so that co-varying dimensions, such as
+    /// strength and trust, are not counted twice.
+    pub fn find_similar_edges_mahalanobis(
+        &self,
+        point: &QualityPoint,
+        max_distance: f64,
+        covariance: &QualityCovariance,
+    ) -> Vec<&EdgeConcept> {
         self.edges
             .values()
-            .filter(|edge| edge.quality_point().distance(point) <= max_distance)
+            .filter(|e| !e.is_archived())
+            .filter(|e| covariance.distance(&e.quality_point_with(&self.duration_model), point) <= max_distance)
             .collect()
     }
 
+    /// Find edges similar to an edge, weighted for the edge's category
+    pub fn find_similar_to(&self, edge_id: &RelationshipId, max_distance: f64) -> RelationshipResult<Vec<&EdgeConcept>> {
+        let edge = self
+            .get_edge(edge_id)
+            .ok_or_else(|| RelationshipError::EntityNotFound(edge_id.to_string()))?;
+        let weights = self.weight_registry.weights_for(&edge.category);
+        let point = edge.quality_point_with(&self.duration_model);
+        Ok(self
+            .find_similar_edges_weighted(&point, max_distance, weights)
+            .into_iter()
+            .filter(|e| e.id != *edge_id)
+            .collect())
+    }
+
+    /// Position of an edge over this space's quality schema
+    ///
+    /// Standard dimensions come from the edge's quality; extension dimensions
+    /// are read from numeric edge properties of the same id. Properties that
+    /// fall outside their dimension's range are ignored.
+    pub fn dynamic_quality_point(&self, edge: &EdgeConcept) -> DynamicQualityPoint {
+        let mut point = DynamicQualityPoint::from(edge.quality_point_with(&self.duration_model));
+        for id in self.quality_schema.ids() {
+            if QualityPoint::DIMENSIONS.contains(&id) {
+                continue;
+            }
+            if let Some(value) = edge.properties.get(id).and_then(|v| v.as_f64()) {
+                let _ = point.set(&self.quality_schema, id, value);
+            }
+        }
+        point
+    }
+
+    /// Find the `k` edges nearest to a point in quality space, closest first
+    pub fn nearest_edges(&self, point: &QualityPoint, k: usize) -> Vec<&EdgeConcept> {
+        // Indexed and current positions differ by at most the drift, so the
+        // current k nearest lie within the k-th indexed distance plus twice it
+        let Some(&(_, kth)) = self.edge_index.nearest(point, k).last() else {
+            return Vec::new();
+        };
+        let mut nearest: Vec<(f64, &EdgeConcept)> = self
+            .edge_index
+            .within(point, kth + 2.0 * self.edge_index_drift())
+            .iter()
+            .filter_map(|id| self.edges.get(id))
+            .map(|e| (e.quality_point_with(&self.duration_model).distance(point), e))
+            .collect();
+        nearest.sort_by(|a, b| a.0.total_cmp(&b.0).then_with(|| a.1.id.as_uuid().cmp(&b.1.id.as_uuid())));
+        nearest.into_iter().take(k).map(|(_, e)| e).collect()
+    }
+
+    /// Rebuild the similarity index from the edges
+    ///
+    /// Needed after mutating `edges` directly rather than through `add_edge`,
+    /// and worth doing periodically: ongoing edges move along the duration
+    /// axis as time passes, and queries widen their search by how far.
+    pub fn rebuild_edge_index(&mut self) {
+        let model = &self.duration_model;
+        let now = Utc::now();
+        self.edge_index = QualityIndex::build(
+            self.edges
+                .values()
+                .filter(|e| !e.is_archived())
+                .map(|e| (e.id, e.quality.to_quality_point_at(model, Some(&e.category), now))),
+        );
+        self.edge_index_at = now;
+    }
+
+    /// Furthest an ongoing edge can have moved along the duration axis since
+    /// it was indexed
+    ///
+    /// Both duration curves are concave from zero, so growth over an
+    /// interval is bounded by the normalized length of the interval.
+    fn edge_index_drift(&self) -> f64 {
+        let days = (Utc::now() - self.edge_index_at).num_seconds().max(0) as f64 / 86_400.0;
+        std::iter::once(&self.duration_model.default)
+            .chain(self.duration_model.categories.values())
+            .map(|scale| scale.normalize(days))
+            .fold(0.0, f64::max)
+    }
+
     /// Get all active edges
     pub fn active_edges(&self) -> Vec<&EdgeConcept> {
         self.edges.values().filter(|e| e.is_active()).collect()
@@ -113,11 +584,502 @@ impl RelationshipSpace {
     pub fn active_hyperedges(&self) -> Vec<&HyperEdgeConcept> {
         self.hyperedges.values().filter(|h| h.is_active()).collect()
     }
+
+    /// Get all archived edges
+    pub fn archived_edges(&self) -> Vec<&EdgeConcept> {
+        self.edges.values().filter(|e| e.is_archived()).collect()
+    }
+
+    /// Get all archived hyperedges
+    pub fn archived_hyperedges(&self) -> Vec<&HyperEdgeConcept> {
+        self.hyperedges.values().filter(|h| h.is_archived()).collect()
+    }
+
+    /// Edges carrying the tags (archived edges excluded)
+    pub fn edges_tagged(&self, tags: &[String], mode: TagMatch) -> Vec<&EdgeConcept> {
+        self.edges
+            .values()
+            .filter(|e| !e.is_archived() && mode.matches(&e.tags, tags))
+            .collect()
+    }
+
+    /// Hyperedges carrying the tags (archived hyperedges excluded)
+    pub fn hyperedges_tagged(&self, tags: &[String], mode: TagMatch) -> Vec<&HyperEdgeConcept> {
+        self.hyperedges
+            .values()
+            .filter(|h| !h.is_archived() && mode.matches(&h.tags, tags))
+            .collect()
+    }
+
+    /// Usage of every tag in the space, most used first (archived
+    /// relationships excluded)
+    pub fn tag_statistics(&self) -> Vec<TagStats> {
+        let mut stats: HashMap<String, TagStats> = HashMap::new();
+        let mut count = |tags: &BTreeSet<String>, hyperedge: bool, active: bool| {
+            for tag in tags {
+                let entry = stats.entry(tag.clone()).or_insert_with(|| TagStats {
+                    tag: tag.clone(),
+                    edges: 0,
+                    hyperedges: 0,
+                    active: 0,
+                });
+                if hyperedge {
+                    entry.hyperedges += 1;
+                } else {
+                    entry.edges += 1;
+                }
+                entry.active += usize::from(active);
+            }
+        };
+        for edge in self.edges.values().filter(|e| !e.is_archived()) {
+            count(&edge.tags, false, edge.is_active());
+        }
+        for hyperedge in self.hyperedges.values().filter(|h| !h.is_archived()) {
+            count(&hyperedge.tags, true, hyperedge.is_active());
+        }
+        let mut stats: Vec<TagStats> = stats.into_values().collect();
+        stats.sort_by(|a, b| (b.edges + b.hyperedges).cmp(&(a.edges + a.hyperedges)).then_with(|| a.tag.cmp(&b.tag)));
+        stats
+    }
+
+    /// Extract the subgraph within `radius` hops of an entity
+    pub fn ego_network(&self, center: &EntityRef, radius: usize, filter: &EgoFilter) -> EgoNetwork {
+        graph::ego_network(self, center, radius, filter)
+    }
+
+    // ---- Tessellation ----
+
+    /// Compute the Voronoi tessellation of the space and cache it
+    ///
+    /// Terminal (terminated, rejected, dissolved) relationships do not seed
+    /// cells. Relationship cells carry the relationship's id and concept id;
+    /// prototype cells carry neither.
+    pub fn compute_tessellation(&mut self, seeds: TessellationSeeds) -> &VoronoiTessellation {
+        let cells = match seeds {
+            TessellationSeeds::Relationships => self.relationship_cells(),
+            TessellationSeeds::CategoryPrototypes => self.prototype_cells(),
+        };
+        self.tessellation_basis = Some((self.version, Some(seeds)));
+        self.tessellation_points.clear();
+        self.tessellation.insert(VoronoiTessellation { cells })
+    }
+
+    /// Tessellate the space around caller-supplied generators (such as
+    /// cluster centroids) and cache the result
+    ///
+    /// The generators keep all five dimensions for `nearest_cell`.
+    pub fn install_tessellation(
+        &mut self,
+        generators: impl IntoIterator<Item = (Uuid, QualityPoint)>,
+    ) -> &VoronoiTessellation {
+        self.tessellation_points = generators.into_iter().collect();
+        let mut cells: Vec<VoronoiCell> = self
+            .tessellation_points
+            .iter()
+            .map(|(id, point)| VoronoiCell {
+                id: *id,
+                generator: point.to_point3(),
+                concept_id: None,
+            })
+            .collect();
+        cells.sort_by_key(|c| c.id);
+        self.tessellation_basis = Some((self.version, None));
+        self.tessellation.insert(VoronoiTessellation { cells })
+    }
+
+    /// Get the cached tessellation, recomputing it if it is stale or was
+    /// built from different seeds
+    pub fn ensure_tessellation(&mut self, seeds: TessellationSeeds) -> &VoronoiTessellation {
+        if self.tessellation_basis != Some((self.version, Some(seeds))) || self.tessellation.is_none() {
+            return self.compute_tessellation(seeds);
+        }
+        self.tessellation.as_ref().expect("checked above")
+    }
+
+    /// Get the cached tessellation if it reflects the current version
+    pub fn tessellation(&self) -> Option<&VoronoiTessellation> {
+        if self.is_tessellation_stale() {
+            None
+        } else {
+            self.tessellation.as_ref()
+        }
+    }
+
+    /// Check if the space changed since the tessellation was computed
+    ///
+    /// A space that was never tessellated is stale.
+    pub fn is_tessellation_stale(&self) -> bool {
+        self.tessellation.is_none()
+            || self.tessellation_basis.is_none_or(|(version, _)| version != self.version)
+    }
+
+    /// Find the cell of the cached tessellation whose generator is nearest
+    /// to a point in quality space
+    ///
+    /// Installed generators are compared in all five dimensions, others by
+    /// their 3D position.
+    pub fn nearest_cell(&self, point: &QualityPoint) -> Option<&VoronoiCell> {
+        let target = point.to_point3();
+        let distance = |cell: &VoronoiCell| match self.tessellation_points.get(&cell.id) {
+            Some(generator) => generator.distance(point).powi(2),
+            None => squared_distance(&cell.generator, &target),
+        };
+        self.tessellation
+            .as_ref()?
+            .cells
+            .iter()
+            .min_by(|a, b| distance(a).total_cmp(&distance(b)))
+    }
+
+    fn relationship_cells(&self) -> Vec<VoronoiCell> {
+        // Self-edges say nothing about how two entities relate and seed no cells
+        let edges = self
+            .edges
+            .values()
+            .filter(|e| !e.state.is_closed() && !e.is_reflexive())
+            .map(|e| (e.id, e.concept_id, e.position));
+        let hyperedges = self
+            .hyperedges
+            .values()
+            .filter(|h| !h.state.is_terminal())
+            .map(|h| (h.id, h.concept_id, h.position));
+
+        let mut cells: Vec<VoronoiCell> = edges
+            .chain(hyperedges)
+            .map(|(id, concept_id, generator)| VoronoiCell {
+                id: id.as_uuid(),
+                generator,
+                concept_id: Some(concept_id),
+            })
+            .collect();
+        cells.sort_by_key(|c| c.id);
+        cells
+    }
+
+    fn prototype_cells(&self) -> Vec<VoronoiCell> {
+        let mut sums: HashMap<&RelationshipCategory, (Point3<f64>, usize)> = HashMap::new();
+        let positions = self
+            .edges
+            .values()
+            .filter(|e| !e.state.is_closed())
+            .map(|e| (&e.category, e.position))
+            .chain(
+                self.hyperedges
+                    .values()
+                    .filter(|h| !h.state.is_terminal())
+                    .map(|h| (&h.category, h.position)),
+            );
+        for (category, position) in positions {
+            let (sum, count) = sums
+                .entry(category)
+                .or_insert((Point3::new(0.0, 0.0, 0.0), 0));
+            sum.x += position.x;
+            sum.y += position.y;
+            sum.z += position.z;
+            *count += 1;
+        }
+
+        let mut prototypes: Vec<_> = sums.into_iter().collect();
+        prototypes.sort_by_key(|(category, _)| format!("{:?}", category));
+        prototypes
+            .into_iter()
+            .map(|(_, (sum, count))| {
+                let n = count as f64;
+                VoronoiCell {
+                    id: Uuid::now_v7(),
+                    generator: Point3::new(sum.x / n, sum.y / n, sum.z / n),
+                    concept_id: None,
+                }
+            })
+            .collect()
+    }
+
+    // ---- Category Prototypes ----
+
+    /// Category and quality point of every live relationship
+    pub fn category_points(&self) -> Vec<(RelationshipCategory, QualityPoint)> {
+        let edges = self
+            .edges
+            .values()
+            .filter(|e| !e.state.is_closed())
+            .map(|e| (e.category.clone(), e.quality_point_with(&self.duration_model)));
+        let hyperedges = self
+            .hyperedges
+            .values()
+            .filter(|h| !h.state.is_terminal())
+            .map(|h| (h.category.clone(), h.quality_point_with(&self.duration_model)));
+        edges.chain(hyperedges).collect()
+    }
+
+    /// Standard prototypes, replaced by the centroids of the categories
+    /// present in this space
+    pub fn category_prototypes(&self) -> CategoryPrototypes {
+        CategoryPrototypes::standard().learn(self.category_points())
+    }
+
+    /// Suggest a category for a point by its nearest prototype
+    pub fn classify(&self, point: &QualityPoint) -> Option<RelationshipCategory> {
+        self.category_prototypes().classify(point)
+    }
+
+    /// Find categories where at least `min_fraction` of relationships sit
+    /// nearer another category's prototype
+    pub fn category_drift(&self, prototypes: &CategoryPrototypes, min_fraction: f64) -> Vec<CategoryDrift> {
+        prototypes.drift(self.category_points(), min_fraction)
+    }
+
+    /// Check that each category's live relationships form a convex region
+    /// of quality space, least convex category first
+    pub fn category_convexity(&self, validator: &ConvexityValidator) -> Vec<CategoryConvexity> {
+        let edges = self
+            .edges
+            .values()
+            .filter(|e| !e.state.is_closed())
+            .map(|e| (e.id, e.category.clone(), e.quality_point_with(&self.duration_model)));
+        let hyperedges = self
+            .hyperedges
+            .values()
+            .filter(|h| !h.state.is_terminal())
+            .map(|h| (h.id, h.category.clone(), h.quality_point_with(&self.duration_model)));
+        validator.validate(&edges.chain(hyperedges).collect::<Vec<_>>())
+    }
+
+    // ---- Betweenness ----
+
+    /// Position of an edge or hyperedge in quality space
+    pub fn relationship_point(&self, id: &RelationshipId) -> RelationshipResult<QualityPoint> {
+        if let Some(edge) = self.edges.get(id) {
+            return Ok(edge.quality_point_with(&self.duration_model));
+        }
+        self.hyperedges
+            .get(id)
+            .map(|h| h.quality_point_with(&self.duration_model))
+            .ok_or_else(|| RelationshipError::EntityNotFound(id.to_string()))
+    }
+
+    /// Check if relationship `b` lies between `a` and `c` in quality space
+    pub fn is_between(
+        &self,
+        a: &RelationshipId,
+        b: &RelationshipId,
+        c: &RelationshipId,
+        tolerance: f64,
+    ) -> RelationshipResult<bool> {
+        let (a, b, c) = (
+            self.relationship_point(a)?,
+            self.relationship_point(b)?,
+            self.relationship_point(c)?,
+        );
+        Ok(b.is_between(&a, &c, tolerance))
+    }
+
+    /// Plan the evolution of a relationship toward another's quality
+    ///
+    /// Returns the intermediate positions, both ends included.
+    pub fn evolution_path(
+        &self,
+        from: &RelationshipId,
+        toward: &RelationshipId,
+        steps: usize,
+    ) -> RelationshipResult<Vec<QualityPoint>> {
+        let (from, toward) = (self.relationship_point(from)?, self.relationship_point(toward)?);
+        Ok(from.interpolate(&toward, steps))
+    }
+
+    // ---- Visualization ----
+
+    /// Fit a 3-D projection to the live relationships of this space
+    pub fn visual_projection(&self) -> RelationshipResult<PcaProjection> {
+        let points: Vec<QualityPoint> = self.category_points().into_iter().map(|(_, p)| p).collect();
+        PcaProjection::fit_quality(&points)
+    }
+
+    /// Reposition every relationship by a fitted projection
+    ///
+    /// Positions revert to `to_point3` when a relationship's quality is next
+    /// updated; call again to refresh them. Leaves the tessellation stale.
+    pub fn apply_projection(&mut self, projection: &PcaProjection) {
+        let model = &self.duration_model;
+        for edge in self.edges.values_mut() {
+            edge.position = projection.project_quality(&edge.quality_point_with(model));
+        }
+        for hyperedge in self.hyperedges.values_mut() {
+            hyperedge.position = projection.project_quality(&hyperedge.quality_point_with(model));
+        }
+        self.updated_at = Utc::now();
+        self.version += 1;
+    }
+
+    // ---- Reachability ----
+
+    /// Build a reachability index over the active edges of the given
+    /// categories (empty = all)
+    ///
+    /// Build once and reuse it for repeated `can_reach` queries.
+    pub fn reachability_index(&self, categories: &[RelationshipCategory]) -> ReachabilityIndex {
+        ReachabilityIndex::build(&RelationshipGraph::from_space_filtered(self, |e| {
+            e.is_active() && (categories.is_empty() || categories.contains(&e.category))
+        }))
+    }
+
+    /// Check if `from` reaches `to` along active edges of the given
+    /// categories (empty = all)
+    pub fn can_reach(&self, from: &EntityRef, to: &EntityRef, categories: &[RelationshipCategory]) -> bool {
+        self.reachability_index(categories).can_reach(from, to)
+    }
+
+    // ---- Cliques ----
+
+    /// Graph of all active edges in symmetric categories
+    pub fn symmetric_graph(&self) -> RelationshipGraph {
+        RelationshipGraph::from_space_filtered(self, |e| e.is_active() && e.is_symmetric())
+    }
+
+    /// Find tightly-knit groups of at least `min_size` entities among
+    /// active symmetric relationships, largest first
+    pub fn cliques(&self, min_size: usize) -> Vec<Clique> {
+        graph::maximal_cliques(&self.symmetric_graph(), min_size)
+    }
+
+    // ---- Lineage ----
+
+    /// Trace the ancestry and descendants of a relationship along
+    /// DerivesFrom edges, following at most `max_depth` steps each way
+    pub fn lineage(&self, id: RelationshipId, max_depth: Option<usize>) -> Lineage {
+        graph::lineage(self, id, max_depth)
+    }
+
+    // ---- Dependency Cycles ----
+
+    /// Graph of all live (non-terminal) DependsOn edges
+    pub fn dependency_graph(&self) -> RelationshipGraph {
+        RelationshipGraph::from_space_filtered(self, |e| {
+            e.category == RelationshipCategory::DependsOn && !e.state.is_closed()
+        })
+    }
+
+    /// Check that adding an edge would not close a dependency cycle
+    ///
+    /// Only live DependsOn edges are checked; all others pass. An earlier
+    /// version of the same edge is disregarded, so a reversed or redirected
+    /// edge is checked as it will be.
+    pub fn check_dependency_cycle(&self, edge: &EdgeConcept) -> RelationshipResult<()> {
+        if edge.category != RelationshipCategory::DependsOn || edge.state.is_closed() {
+            return Ok(());
+        }
+
+        let cycle = |path: Vec<String>| {
+            Err(RelationshipError::CyclicDependency(path.join(" -> ")))
+        };
+
+        if edge.source.same_entity(&edge.target) {
+            return cycle(vec![edge.source.to_string(), edge.target.to_string()]);
+        }
+
+        let graph = RelationshipGraph::from_space_filtered(self, |e| {
+            e.id != edge.id && e.category == RelationshipCategory::DependsOn && !e.state.is_closed()
+        });
+        let (Some(from), Some(to)) = (
+            graph.node_index(&edge.target),
+            graph.node_index(&edge.source),
+        ) else {
+            return Ok(());
+        };
+
+        match graph::find_path(&graph, from, to) {
+            Some(path) => {
+                let mut names = vec![edge.source.to_string()];
+                names.extend(path.into_iter().map(|n| graph.node(n).to_string()));
+                cycle(names)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Find all existing dependency cycles in the space
+    pub fn dependency_cycles(&self) -> Vec<Vec<EntityRef>> {
+        graph::dependency_cycles(&self.dependency_graph())
+    }
+
+    // ---- Termination Impact ----
+
+    /// Graph of all live (non-terminal) DependsOn and PartOf edges
+    pub fn support_graph(&self) -> RelationshipGraph {
+        RelationshipGraph::from_space_filtered(self, |e| {
+            matches!(
+                e.category,
+                RelationshipCategory::DependsOn | RelationshipCategory::PartOf
+            ) && !e.state.is_closed()
+        })
+    }
+
+    /// Report which entities and relationships would lose support if a
+    /// DependsOn or PartOf edge were terminated
+    pub fn terminate_impact(&self, edge_id: &RelationshipId) -> RelationshipResult<TerminationImpact> {
+        let edge = self
+            .get_edge(edge_id)
+            .ok_or_else(|| RelationshipError::EntityNotFound(edge_id.to_string()))?;
+        if !matches!(
+            edge.category,
+            RelationshipCategory::DependsOn | RelationshipCategory::PartOf
+        ) {
+            return Err(RelationshipError::InvalidRelationship(format!(
+                "{} is not a dependency edge",
+                edge_id
+            )));
+        }
+
+        Ok(graph::termination_impact(&self.support_graph(), *edge_id).unwrap_or(TerminationImpact {
+            edge_id: *edge_id,
+            entities: Vec::new(),
+            relationships: Vec::new(),
+        }))
+    }
+
+    // ---- Temporal Ordering ----
+
+    /// Graph of all live (non-terminal) Precedes and Triggers edges
+    pub fn temporal_graph(&self) -> RelationshipGraph {
+        RelationshipGraph::from_space_filtered(self, |e| {
+            matches!(
+                e.category,
+                RelationshipCategory::Precedes | RelationshipCategory::Triggers
+            ) && !e.state.is_closed()
+        })
+    }
+
+    /// Order entities by their Precedes/Triggers edges
+    ///
+    /// Entities caught in or after a cycle are reported rather than ordered.
+    pub fn temporal_order(&self) -> TopologicalOrder {
+        graph::topological_order(&self.temporal_graph())
+    }
+}
+
+impl Serialize for RelationshipSpace {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        RelationshipSpace::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for RelationshipSpace {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut space = RelationshipSpace::deserialize(deserializer)?;
+        space.rebuild_edge_index();
+        Ok(space)
+    }
+}
+
+fn squared_distance(a: &Point3<f64>, b: &Point3<f64>) -> f64 {
+    (a.x - b.x).powi(2) + (a.y - b.y).powi(2) + (a.z - b.z).powi(2)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::aggregates::EdgeState;
+    use crate::commands::{ArchiveRelationship, CreateEdge, EdgeCommand, RelationshipCommand, RestoreRelationship};
+    use cim_domain::MessageIdentity;
     use crate::value_objects::{EntityRef, RelationshipCategory};
     use uuid::Uuid;
 
@@ -142,7 +1104,280 @@ mod tests {
             RelationshipCategory::Employment,
         );
 
-        space.add_edge(edge);
+        space.add_edge(edge).unwrap();
         assert_eq!(space.relationship_count(), 1);
     }
+
+    #[test]
+    fn test_reflexive_edges_opt_in() {
+        let alice = EntityRef::person(Uuid::now_v7());
+        let bob = EntityRef::person(Uuid::now_v7());
+        let alias_of = RelationshipCategory::Custom("alias-of".to_string());
+        let alias = EdgeConcept::new("Alias", alice.clone(), alice.clone(), alias_of.clone());
+
+        let mut space = RelationshipSpace::new("People", TopologicalSpaceId::new());
+        assert!(space.add_edge(alias.clone()).is_err());
+
+        let mut space = space.with_reflexive_category(alias_of);
+        let mut alias = alias;
+        alias.activate().unwrap();
+        space.add_edge(alias.clone()).unwrap();
+        let mut knows = EdgeConcept::new("Knows", alice.clone(), bob.clone(), RelationshipCategory::Friendship);
+        knows.activate().unwrap();
+        space.add_edge(knows.clone()).unwrap();
+
+        // Traversal steps over the self-edge
+        let ego = space.ego_network(&alice, 2, &EgoFilter::default());
+        assert_eq!(ego.nodes.len(), 2);
+        assert!(space.can_reach(&alice, &bob, &[]));
+
+        // Only the edge between distinct entities seeds a cell
+        let cells = &space.compute_tessellation(TessellationSeeds::Relationships).cells;
+        assert_eq!(cells.len(), 1);
+        assert_eq!(cells[0].id, knows.id.as_uuid());
+    }
+
+    #[test]
+    fn test_dependency_cycle_rejected() {
+        let mut space = RelationshipSpace::new("Dependencies", TopologicalSpaceId::new());
+
+        let a = EntityRef::concept(Uuid::now_v7());
+        let b = EntityRef::concept(Uuid::now_v7());
+        let c = EntityRef::concept(Uuid::now_v7());
+
+        let depends = |from: &EntityRef, to: &EntityRef| {
+            EdgeConcept::new("Dependency", from.clone(), to.clone(), RelationshipCategory::DependsOn)
+        };
+
+        space.add_edge(depends(&a, &b)).unwrap();
+        space.add_edge(depends(&b, &c)).unwrap();
+
+        let result = space.add_edge(depends(&c, &a));
+        assert!(matches!(result, Err(RelationshipError::CyclicDependency(_))));
+        assert!(space.add_edge(depends(&a, &a)).is_err());
+        assert!(space.dependency_cycles().is_empty());
+
+        // Other categories may form cycles freely
+        let reference = EdgeConcept::new("Cites", c, a, RelationshipCategory::References);
+        assert!(space.add_edge(reference).is_ok());
+    }
+
+    #[test]
+    fn test_replay_applies_recorded_edges() {
+        let a = EntityRef::concept(Uuid::now_v7());
+        let b = EntityRef::concept(Uuid::now_v7());
+        let create = |source: &EntityRef, target: &EntityRef| {
+            RelationshipCommand::Edge(EdgeCommand::CreateEdge(CreateEdge {
+                identity: MessageIdentity::new_root(),
+                edge_id: RelationshipId::new(),
+                source: source.clone(),
+                target: target.clone(),
+                category: RelationshipCategory::DependsOn,
+                name: "Dependency".to_string(),
+                quality: None,
+                created_by: "test".to_string(),
+            }))
+        };
+
+        // Decided in a space where neither edge closes a cycle
+        let mut space = RelationshipSpace::new("Dependencies", TopologicalSpaceId::new());
+        let forward = space.handle_command(create(&a, &b)).unwrap();
+        let backward = RelationshipSpace::new("Elsewhere", TopologicalSpaceId::new())
+            .handle_command(create(&b, &a))
+            .unwrap();
+        for event in &forward {
+            space.apply_event(event).unwrap();
+        }
+        assert!(space.handle_command(create(&b, &a)).is_err());
+
+        // The recorded fact is applied all the same
+        for event in &backward {
+            space.apply_event(event).unwrap();
+        }
+        assert_eq!(space.edges.len(), 2);
+        assert_eq!(space.dependency_cycles().len(), 1);
+    }
+
+    #[test]
+    fn test_tessellation_staleness() {
+        let mut space = RelationshipSpace::new("Tessellated", TopologicalSpaceId::new());
+        assert!(space.is_tessellation_stale());
+
+        let edge = |category| {
+            EdgeConcept::new(
+                "Edge",
+                EntityRef::person(Uuid::now_v7()),
+                EntityRef::person(Uuid::now_v7()),
+                category,
+            )
+        };
+        space.add_edge(edge(RelationshipCategory::Friendship)).unwrap();
+        space.add_edge(edge(RelationshipCategory::Friendship)).unwrap();
+        space.add_edge(edge(RelationshipCategory::Employment)).unwrap();
+
+        assert_eq!(space.compute_tessellation(TessellationSeeds::Relationships).cells.len(), 3);
+        assert!(!space.is_tessellation_stale());
+        assert!(space.tessellation().is_some());
+
+        // Mutation leaves the cached tessellation in place but stale
+        space.add_edge(edge(RelationshipCategory::Employment)).unwrap();
+        assert!(space.is_tessellation_stale());
+        assert!(space.tessellation().is_none());
+        assert!(space.tessellation.is_some());
+
+        let prototypes = space.ensure_tessellation(TessellationSeeds::CategoryPrototypes);
+        assert_eq!(prototypes.cells.len(), 2);
+        assert!(!space.is_tessellation_stale());
+    }
+
+    #[test]
+    fn test_nearest_cell() {
+        let mut space = RelationshipSpace::new("Tessellated", TopologicalSpaceId::new());
+        let mut friendship = EdgeConcept::new(
+            "Friends",
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::person(Uuid::now_v7()),
+            RelationshipCategory::Friendship,
+        );
+        friendship.position = Point3::new(0.1, 0.1, 0.1);
+        let mut employment = EdgeConcept::new(
+            "Employment",
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::organization(Uuid::now_v7()),
+            RelationshipCategory::Employment,
+        );
+        let point = QualityPoint::new(0.9, 0.9, 0.9, 0.5, 0.5);
+        employment.position = Point3::new(0.8, 0.8, 0.8);
+        let employment_id = employment.id.as_uuid();
+        space.add_edge(friendship).unwrap();
+        space.add_edge(employment).unwrap();
+
+        assert!(space.nearest_cell(&point).is_none());
+        space.compute_tessellation(TessellationSeeds::Relationships);
+        assert_eq!(space.nearest_cell(&point).unwrap().id, employment_id);
+    }
+
+    #[test]
+    fn test_dynamic_quality_point_reads_extensions() {
+        let schema = QualitySchema::standard().with_dimension(
+            crate::quality::RelationshipDimension::new("risk", "Risk", 0.0, 1.0, "Exposure"),
+        );
+        let space = RelationshipSpace::new("Risky", TopologicalSpaceId::new()).with_quality_schema(schema);
+
+        let mut edge = EdgeConcept::new(
+            "Supplier",
+            EntityRef::organization(Uuid::now_v7()),
+            EntityRef::organization(Uuid::now_v7()),
+            RelationshipCategory::DependsOn,
+        );
+        edge.properties.insert("risk".to_string(), serde_json::json!(0.8));
+
+        let point = space.dynamic_quality_point(&edge);
+        assert_eq!(point.get("risk"), Some(0.8));
+        assert_eq!(point.get("trust"), Some(edge.quality.trust));
+    }
+
+    #[test]
+    fn test_similarity_uses_category_weights() {
+        let mut space = RelationshipSpace::new("Weighted", TopologicalSpaceId::new());
+        let friends = |reciprocity: f64| {
+            let quality = crate::quality::RelationshipQuality {
+                reciprocity,
+                ..crate::quality::RelationshipQuality::default_friendship()
+            };
+            EdgeConcept::new(
+                "Friends",
+                EntityRef::person(Uuid::now_v7()),
+                EntityRef::person(Uuid::now_v7()),
+                RelationshipCategory::Friendship,
+            )
+            .with_quality(quality)
+        };
+        let anchor = friends(0.9);
+        let anchor_id = anchor.id;
+        space.add_edge(anchor).unwrap();
+        space.add_edge(friends(0.8)).unwrap();
+
+        // Unweighted the pair is 0.1 apart; social weights double reciprocity
+        let point = space.edges[&anchor_id].quality_point();
+        assert_eq!(space.find_similar_edges(&point, 0.15).len(), 2);
+        assert!(space.find_similar_to(&anchor_id, 0.15).unwrap().is_empty());
+        assert_eq!(space.find_similar_to(&anchor_id, 0.25).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_edge_index_survives_serde_and_time() {
+        let mut space = RelationshipSpace::new("Indexed", TopologicalSpaceId::new());
+        let mut edge = EdgeConcept::new(
+            "Job",
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::organization(Uuid::now_v7()),
+            RelationshipCategory::Employment,
+        );
+        edge.quality.duration = crate::value_objects::ValidityPeriod::ongoing(Utc::now() - chrono::Duration::days(200));
+        let point = edge.quality_point_with(&space.duration_model);
+        space.add_edge(edge.clone()).unwrap();
+
+        // As snapshots store it
+        let mut bytes = Vec::new();
+        ciborium::into_writer(&space, &mut bytes).unwrap();
+        let restored: RelationshipSpace = ciborium::from_reader(bytes.as_slice()).unwrap();
+        assert_eq!(restored.find_similar_edges(&point, 0.01).len(), 1);
+        assert_eq!(restored.nearest_edges(&point, 1)[0].id, edge.id);
+
+        // Indexed 100 days ago, the edge has moved along the duration axis
+        let then = Utc::now() - chrono::Duration::days(100);
+        space.edge_index = QualityIndex::build([(
+            edge.id,
+            edge.quality.to_quality_point_at(&space.duration_model, Some(&edge.category), then),
+        )]);
+        space.edge_index_at = then;
+        assert_eq!(space.find_similar_edges(&point, 0.01).len(), 1);
+        assert_eq!(space.nearest_edges(&point, 1).len(), 1);
+    }
+
+    #[test]
+    fn test_archived_edges_leave_the_index() {
+        let mut space = RelationshipSpace::new("Archive", TopologicalSpaceId::new());
+        let mut edge = EdgeConcept::new(
+            "Job",
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::organization(Uuid::now_v7()),
+            RelationshipCategory::Employment,
+        );
+        edge.state = EdgeState::Terminated;
+        let id = edge.id;
+        let point = edge.quality_point_with(&space.duration_model);
+        space.add_edge(edge).unwrap();
+
+        let mut events = Vec::new();
+        space
+            .run_command(
+                EdgeCommand::ArchiveEdge(ArchiveRelationship {
+                    identity: MessageIdentity::new_root(),
+                    relationship_id: id,
+                    reason: None,
+                    archived_by: "ops".to_string(),
+                }),
+                &mut events,
+            )
+            .unwrap();
+        assert!(space.find_similar_edges(&point, 0.01).is_empty());
+        assert!(space.nearest_edges(&point, 1).is_empty());
+        space.rebuild_edge_index();
+        assert!(space.find_similar_edges(&point, 0.01).is_empty());
+
+        space
+            .run_command(
+                EdgeCommand::RestoreEdge(RestoreRelationship {
+                    identity: MessageIdentity::new_root(),
+                    relationship_id: id,
+                    restored_by: "ops".to_string(),
+                }),
+                &mut events,
+            )
+            .unwrap();
+        assert_eq!(space.find_similar_edges(&point, 0.01).len(), 1);
+        assert_eq!(space.nearest_edges(&point, 1)[0].id, id);
+    }
 }
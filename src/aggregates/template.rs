@@ -0,0 +1,355 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Relationship Templates
+//!
+//! Organizations create the same kinds of relationship over and over: every
+//! employment is a formal edge with a contract reference, every project team
+//! has one lead. A template fixes what such relationships have in common;
+//! `CreateFromTemplate` supplies only the endpoints.
+//!
+//! ```text
+//! Template "employment"              CreateFromTemplate
+//!   shape      Edge                    endpoints  Alice -> Acme
+//!   category   Employment      ==>     properties {grade: 7}
+//!   quality    formal, 0.8
+//!   properties {contract: null}       EdgeCreated, EdgeQualityUpdated
+//!   validity   term 365 days          EdgeTemplateApplied (properties, valid_until)
+//! ```
+//!
+//! Without a template quality, a relationship keeps its aggregate's default.
+//! An edge records the template's properties in its property history.
+//!
+//! The created relationship records its template in the `template`
+//! property. A hyperedge template's role schema then applies to the
+//! hyperedge in addition to its category's schema.
+
+use super::{HyperEdgeConcept, RelationshipSpace};
+use crate::commands::{
+    CreateEdge, CreateFromTemplate, CreateHyperEdge, EdgeCommand, HyperEdgeCommand, TemplateEndpoints, UpdateHyperEdgeQuality,
+};
+use crate::events::{EdgeEvent, EdgeTemplateApplied, HyperEdgeEvent, HyperEdgeTemplateApplied, RelationshipEvent};
+use crate::invariants::RoleSchema;
+use crate::quality::RelationshipQuality;
+use crate::value_objects::{ActivationMode, RelationshipCategory};
+use crate::{RelationshipError, RelationshipResult};
+use chrono::{DateTime, Duration, Utc};
+use cim_domain::MessageIdentity;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Whether a template creates edges or hyperedges
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TemplateShape {
+    Edge,
+    HyperEdge,
+}
+
+/// How long relationships created from a template are valid
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidityRule {
+    /// Validity when the command names no end (`None` = ongoing)
+    #[serde(default)]
+    pub term_days: Option<u32>,
+    /// Longest validity allowed (`None` = unbounded)
+    #[serde(default)]
+    pub max_term_days: Option<u32>,
+}
+
+impl ValidityRule {
+    /// Valid for `days` unless the command names an end
+    pub fn term(days: u32) -> Self {
+        Self {
+            term_days: Some(days),
+            max_term_days: None,
+        }
+    }
+
+    /// Allow at most `days` of validity
+    pub fn with_max_term(mut self, days: u32) -> Self {
+        self.max_term_days = Some(days);
+        self
+    }
+
+    /// End of validity for a relationship created at `now`
+    pub fn resolve(&self, now: DateTime<Utc>, requested: Option<DateTime<Utc>>) -> RelationshipResult<Option<DateTime<Utc>>> {
+        let ends_at = requested.or_else(|| self.term_days.map(|days| now + Duration::days(days.into())));
+        if let Some(ends_at) = ends_at {
+            if ends_at <= now {
+                return Err(RelationshipError::InvalidRelationship(format!(
+                    "validity must end in the future, not at {}",
+                    ends_at
+                )));
+            }
+        }
+        if let Some(max) = self.max_term_days {
+            let limit = now + Duration::days(max.into());
+            if ends_at.is_none_or(|ends_at| ends_at > limit) {
+                return Err(RelationshipError::InvalidRelationship(format!(
+                    "validity may last at most {} days",
+                    max
+                )));
+            }
+        }
+        Ok(ends_at)
+    }
+}
+
+/// What relationships created from a template have in common
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RelationshipTemplate {
+    /// Template name, unique within a space
+    pub name: String,
+    /// Edge or hyperedge
+    pub shape: TemplateShape,
+    /// Category of created relationships
+    pub category: RelationshipCategory,
+    /// Initial quality (`None` = the aggregate default)
+    #[serde(default)]
+    pub quality: Option<RelationshipQuality>,
+    /// Roles created hyperedges must fill
+    #[serde(default)]
+    pub role_schema: Option<RoleSchema>,
+    /// Property defaults
+    #[serde(default)]
+    pub properties: HashMap<String, serde_json::Value>,
+    /// Validity term
+    #[serde(default)]
+    pub validity: ValidityRule,
+}
+
+impl RelationshipTemplate {
+    /// Create an edge template
+    pub fn edge(name: impl Into<String>, category: RelationshipCategory) -> Self {
+        Self::new(name, TemplateShape::Edge, category)
+    }
+
+    /// Create a hyperedge template
+    pub fn hyperedge(name: impl Into<String>, category: RelationshipCategory) -> Self {
+        Self::new(name, TemplateShape::HyperEdge, category)
+    }
+
+    fn new(name: impl Into<String>, shape: TemplateShape, category: RelationshipCategory) -> Self {
+        Self {
+            name: name.into(),
+            shape,
+            category,
+            quality: None,
+            role_schema: None,
+            properties: HashMap::new(),
+            validity: ValidityRule::default(),
+        }
+    }
+
+    /// Set the initial quality
+    pub fn with_quality(mut self, quality: RelationshipQuality) -> Self {
+        self.quality = Some(quality);
+        self
+    }
+
+    /// Set the roles created hyperedges must fill
+    pub fn with_role_schema(mut self, schema: RoleSchema) -> Self {
+        self.role_schema = Some(schema);
+        self
+    }
+
+    /// Set a property default
+    pub fn with_property(mut self, key: impl Into<String>, value: serde_json::Value) -> Self {
+        self.properties.insert(key.into(), value);
+        self
+    }
+
+    /// Set the validity term
+    pub fn with_validity(mut self, validity: ValidityRule) -> Self {
+        self.validity = validity;
+        self
+    }
+}
+
+impl RelationshipSpace {
+    pub(super) fn create_from_template(&self, c: CreateFromTemplate) -> RelationshipResult<Vec<RelationshipEvent>> {
+        let template = self
+            .template(&c.template)
+            .ok_or_else(|| RelationshipError::EntityNotFound(format!("template {}", c.template)))?;
+        let now = Utc::now();
+        let valid_until = template.validity.resolve(now, c.valid_until)?;
+        let mut properties = template.properties.clone();
+        properties.extend(c.properties);
+
+        // Each step is decided against the space as the ones before it left it
+        let mut space = self.clone();
+        let mut events = Vec::new();
+        match (template.shape, c.endpoints) {
+            (TemplateShape::Edge, TemplateEndpoints::Edge { source, target }) => {
+                space.run_command(
+                    EdgeCommand::CreateEdge(CreateEdge {
+                        identity: c.identity,
+                        edge_id: c.relationship_id,
+                        source,
+                        target,
+                        category: template.category.clone(),
+                        name: c.name,
+                        quality: template.quality.clone(),
+                        created_by: c.created_by.clone(),
+                    }),
+                    &mut events,
+                )?;
+                events.push(RelationshipEvent::from(EdgeEvent::TemplateApplied(EdgeTemplateApplied {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    edge_id: c.relationship_id,
+                    template: template.name.clone(),
+                    properties,
+                    valid_until,
+                    applied_by: c.created_by,
+                    applied_at: now,
+                })));
+                Ok(events)
+            }
+            (TemplateShape::HyperEdge, TemplateEndpoints::HyperEdge { participants }) => {
+                space.run_command(
+                    HyperEdgeCommand::CreateHyperEdge(CreateHyperEdge {
+                        identity: c.identity,
+                        hyperedge_id: c.relationship_id,
+                        name: c.name,
+                        category: template.category.clone(),
+                        initial_participants: participants,
+                        activation: ActivationMode::default(),
+                        created_by: c.created_by.clone(),
+                    }),
+                    &mut events,
+                )?;
+                // Without a template quality the hyperedge keeps its default
+                if let Some(quality) = &template.quality {
+                    space.run_command(
+                        HyperEdgeCommand::UpdateHyperEdgeQuality(UpdateHyperEdgeQuality {
+                            identity: MessageIdentity::new_caused_by(&c.identity),
+                            hyperedge_id: c.relationship_id,
+                            new_quality: quality.clone(),
+                            reason: "initial quality".to_string(),
+                        }),
+                        &mut events,
+                    )?;
+                }
+                events.push(RelationshipEvent::from(HyperEdgeEvent::TemplateApplied(HyperEdgeTemplateApplied {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_caused_by(&c.identity),
+                    hyperedge_id: c.relationship_id,
+                    template: template.name.clone(),
+                    properties,
+                    valid_until,
+                    applied_by: c.created_by,
+                    applied_at: now,
+                })));
+                Ok(events)
+            }
+            (shape, _) => Err(RelationshipError::InvalidRelationship(format!(
+                "template {} creates {}s",
+                template.name,
+                match shape {
+                    TemplateShape::Edge => "edge",
+                    TemplateShape::HyperEdge => "hyperedge",
+                }
+            ))),
+        }
+    }
+
+    /// Role schema of the template a hyperedge was created from
+    pub(super) fn template_role_schema(&self, hyperedge: &HyperEdgeConcept) -> Option<&RoleSchema> {
+        let name = hyperedge.properties.get("template")?.as_str()?;
+        self.template(name)?.role_schema.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregates::{EdgeState, HyperEdgeState};
+    use crate::commands::{ActivateHyperEdge, RelationshipCommand};
+    use crate::invariants::RoleConstraint;
+    use crate::value_objects::{EntityRef, Formality, IncidenceMatrix, ParticipantRole, RelationshipId};
+    use cim_domain_spaces::TopologicalSpaceId;
+
+    fn apply(space: &mut RelationshipSpace, cmd: impl Into<RelationshipCommand>) -> RelationshipResult<()> {
+        let events = space.handle_command(cmd.into())?;
+        events.iter().try_for_each(|event| space.apply_event(event))
+    }
+
+    fn from_template(template: &str, endpoints: TemplateEndpoints) -> CreateFromTemplate {
+        CreateFromTemplate {
+            identity: MessageIdentity::new_root(),
+            relationship_id: RelationshipId::new(),
+            template: template.to_string(),
+            name: template.to_string(),
+            endpoints,
+            properties: HashMap::new(),
+            valid_until: None,
+            created_by: "hr".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_create_from_template() {
+        let quality = RelationshipQuality {
+            formality: Formality::Legal,
+            ..Default::default()
+        };
+        let mut space = RelationshipSpace::new("Org", TopologicalSpaceId::new())
+            .with_template(
+                RelationshipTemplate::edge("employment", RelationshipCategory::Employment)
+                    .with_quality(quality.clone())
+                    .with_property("contract", serde_json::Value::Null)
+                    .with_property("grade", serde_json::json!(1))
+                    .with_validity(ValidityRule::term(365).with_max_term(730)),
+            )
+            .with_template(
+                RelationshipTemplate::hyperedge("project", RelationshipCategory::Custom("Project".to_string()))
+                    .with_role_schema(RoleSchema::new().with_constraint(RoleConstraint::exactly(ParticipantRole::Leader, 1))),
+            );
+        let (alice, acme) = (EntityRef::person(Uuid::now_v7()), EntityRef::organization(Uuid::now_v7()));
+
+        let mut cmd = from_template("employment", TemplateEndpoints::Edge { source: alice.clone(), target: acme.clone() });
+        cmd.properties.insert("grade".to_string(), serde_json::json!(7));
+        let edge_id = cmd.relationship_id;
+        apply(&mut space, RelationshipCommand::CreateFromTemplate(cmd)).unwrap();
+        let edge = space.get_edge(&edge_id).unwrap();
+        assert_eq!(edge.state, EdgeState::Proposed);
+        assert_eq!(edge.category, RelationshipCategory::Employment);
+        assert_eq!(edge.quality.formality, Formality::Legal);
+        assert_eq!(edge.properties["grade"], serde_json::json!(7));
+        assert_eq!(edge.properties["template"], serde_json::json!("employment"));
+        assert_eq!(edge.property_history("grade")[0].changed_by.as_deref(), Some("hr"));
+        assert_eq!(edge.property_version("template"), 1);
+        let term = edge.validity.ends_at.unwrap() - edge.validity.starts_at;
+        assert!((term - Duration::days(365)).num_minutes().abs() < 1);
+
+        // The validity rule caps requested terms
+        let mut cmd = from_template("employment", TemplateEndpoints::Edge { source: alice.clone(), target: acme });
+        cmd.valid_until = Some(Utc::now() + Duration::days(1000));
+        assert!(space.handle_command(RelationshipCommand::CreateFromTemplate(cmd)).is_err());
+
+        // Shapes must match, and the template's role schema holds
+        let mut team = IncidenceMatrix::new();
+        team.add_participant(alice.clone(), ParticipantRole::Member, 1.0);
+        team.add_participant(EntityRef::person(Uuid::now_v7()), ParticipantRole::Member, 1.0);
+        assert!(space
+            .handle_command(RelationshipCommand::CreateFromTemplate(from_template(
+                "employment",
+                TemplateEndpoints::HyperEdge { participants: team.clone() }
+            )))
+            .is_err());
+        let cmd = from_template("project", TemplateEndpoints::HyperEdge { participants: team });
+        let hyperedge_id = cmd.relationship_id;
+        apply(&mut space, RelationshipCommand::CreateFromTemplate(cmd)).unwrap();
+        let activate = HyperEdgeCommand::ActivateHyperEdge(ActivateHyperEdge {
+            identity: MessageIdentity::new_root(),
+            hyperedge_id,
+            activated_by: "pm".to_string(),
+        });
+        let err = space.handle_command(activate.into()).unwrap_err();
+        assert!(matches!(err, RelationshipError::RoleConstraintViolated(_)));
+        assert_eq!(space.get_hyperedge(&hyperedge_id).unwrap().state, HyperEdgeState::Forming);
+    }
+}
@@ -0,0 +1,279 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Composition Rules
+//!
+//! A rule maps a pair of categories to the category of their composite and
+//! says how each quality dimension of the two links combines.
+//!
+//! ## Standard Rules
+//!
+//! ```text
+//! Employment  o Membership   authority
+//! PartOf      o PartOf       PartOf
+//! Contains    o Contains     Contains
+//! DependsOn   o DependsOn    DependsOn
+//! Precedes    o Precedes     Precedes
+//! DerivesFrom o DerivesFrom  DerivesFrom
+//! Management  o Management   Management
+//! ```
+//!
+//! All standard rules use the default quality composition: strength is the
+//! product of the links, trust, formality and reciprocity the weakest link.
+
+use crate::quality::RelationshipQuality;
+use crate::value_objects::{Formality, RelationshipCategory, ValidityPeriod};
+use crate::{RelationshipError, RelationshipResult};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// How one dimension of two composed links combines
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Combine {
+    /// Product of both links
+    Product,
+    /// Weaker link
+    Min,
+    /// Stronger link
+    Max,
+    /// Mean of both links
+    Mean,
+    /// First link's value
+    First,
+    /// Second link's value
+    Second,
+}
+
+impl Combine {
+    /// Combine the values of the first and second link
+    pub fn apply(&self, first: f64, second: f64) -> f64 {
+        match self {
+            Combine::Product => first * second,
+            Combine::Min => first.min(second),
+            Combine::Max => first.max(second),
+            Combine::Mean => (first + second) / 2.0,
+            Combine::First => first,
+            Combine::Second => second,
+        }
+    }
+}
+
+/// Quality combination, one combinator per dimension
+///
+/// Duration is not combined: the composite is valid while both links are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QualityComposition {
+    /// Strength (default: product)
+    pub strength: Combine,
+    /// Trust (default: weakest link)
+    pub trust: Combine,
+    /// Formality (default: weakest link)
+    pub formality: Combine,
+    /// Reciprocity (default: weakest link)
+    pub reciprocity: Combine,
+}
+
+impl Default for QualityComposition {
+    fn default() -> Self {
+        Self {
+            strength: Combine::Product,
+            trust: Combine::Min,
+            formality: Combine::Min,
+            reciprocity: Combine::Min,
+        }
+    }
+}
+
+impl QualityComposition {
+    /// Quality of the composite of two links valid over `validity`
+    pub fn compose(
+        &self,
+        first: &RelationshipQuality,
+        second: &RelationshipQuality,
+        validity: ValidityPeriod,
+    ) -> RelationshipQuality {
+        RelationshipQuality {
+            strength: self.strength.apply(first.strength, second.strength),
+            trust: self.trust.apply(first.trust, second.trust),
+            formality: Formality::from_f64(
+                self.formality
+                    .apply(first.formality.as_f64(), second.formality.as_f64()),
+            ),
+            duration: validity,
+            reciprocity: self.reciprocity.apply(first.reciprocity, second.reciprocity),
+        }
+    }
+}
+
+/// Rule for composing two categories
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompositionRule {
+    /// Category of the first link
+    pub first: RelationshipCategory,
+    /// Category of the second link
+    pub second: RelationshipCategory,
+    /// Category of the composite
+    pub result: RelationshipCategory,
+    /// How the links' qualities combine
+    #[serde(default)]
+    pub quality: QualityComposition,
+}
+
+impl CompositionRule {
+    /// Create a rule with the default quality composition
+    pub fn new(
+        first: RelationshipCategory,
+        second: RelationshipCategory,
+        result: RelationshipCategory,
+    ) -> Self {
+        Self {
+            first,
+            second,
+            result,
+            quality: QualityComposition::default(),
+        }
+    }
+
+    /// Set the quality composition
+    pub fn with_quality(mut self, quality: QualityComposition) -> Self {
+        self.quality = quality;
+        self
+    }
+}
+
+/// Composition rules keyed by category pair
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "Vec<CompositionRule>", into = "Vec<CompositionRule>")]
+pub struct CompositionRegistry {
+    rules: HashMap<(RelationshipCategory, RelationshipCategory), CompositionRule>,
+}
+
+impl Default for CompositionRegistry {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+impl From<Vec<CompositionRule>> for CompositionRegistry {
+    fn from(rules: Vec<CompositionRule>) -> Self {
+        rules.into_iter().fold(Self::empty(), Self::with_rule)
+    }
+}
+
+impl From<CompositionRegistry> for Vec<CompositionRule> {
+    fn from(registry: CompositionRegistry) -> Self {
+        registry.rules.into_values().collect()
+    }
+}
+
+impl CompositionRegistry {
+    /// Registry with no rules; nothing composes
+    pub fn empty() -> Self {
+        Self {
+            rules: HashMap::new(),
+        }
+    }
+
+    /// Registry with the standard rules
+    pub fn standard() -> Self {
+        use RelationshipCategory::*;
+        let transitive = [PartOf, Contains, DependsOn, Precedes, DerivesFrom, Management]
+            .map(|c| CompositionRule::new(c.clone(), c.clone(), c));
+        let authority = CompositionRule::new(Employment, Membership, Custom("authority".to_string()));
+
+        std::iter::once(authority)
+            .chain(transitive)
+            .fold(Self::empty(), Self::with_rule)
+    }
+
+    /// Register a rule, replacing any rule for the same pair
+    pub fn with_rule(mut self, rule: CompositionRule) -> Self {
+        self.rules
+            .insert((rule.first.clone(), rule.second.clone()), rule);
+        self
+    }
+
+    /// Rule for composing `first` then `second`
+    pub fn rule(
+        &self,
+        first: &RelationshipCategory,
+        second: &RelationshipCategory,
+    ) -> RelationshipResult<&CompositionRule> {
+        self.rules
+            .get(&(first.clone(), second.clone()))
+            .ok_or_else(|| {
+                RelationshipError::UnknownComposition(format!(
+                    "{} o {}",
+                    first.display_name(),
+                    second.display_name()
+                ))
+            })
+    }
+
+    /// All registered rules
+    pub fn rules(&self) -> impl Iterator<Item = &CompositionRule> {
+        self.rules.values()
+    }
+
+    /// Number of registered rules
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// Check if no rules are registered
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_rules() {
+        let registry = CompositionRegistry::standard();
+        let rule = registry
+            .rule(&RelationshipCategory::PartOf, &RelationshipCategory::PartOf)
+            .unwrap();
+        assert_eq!(rule.result, RelationshipCategory::PartOf);
+
+        let unknown = registry.rule(&RelationshipCategory::Friendship, &RelationshipCategory::Friendship);
+        assert!(matches!(unknown, Err(RelationshipError::UnknownComposition(_))));
+
+        // Friend of a friend: an acquaintance, as trusted as the average link
+        let acquaintance = RelationshipCategory::Custom("acquaintance".to_string());
+        let registry = registry.with_rule(
+            CompositionRule::new(
+                RelationshipCategory::Friendship,
+                RelationshipCategory::Friendship,
+                acquaintance.clone(),
+            )
+            .with_quality(QualityComposition {
+                trust: Combine::Mean,
+                ..QualityComposition::default()
+            }),
+        );
+
+        let json = serde_json::to_string(&registry).unwrap();
+        let restored: CompositionRegistry = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.len(), registry.len());
+        let rule = restored
+            .rule(&RelationshipCategory::Friendship, &RelationshipCategory::Friendship)
+            .unwrap();
+        assert_eq!(rule.result, acquaintance);
+
+        let quality = rule.quality.compose(
+            &RelationshipQuality::default_friendship(),
+            &RelationshipQuality::default_employment(),
+            ValidityPeriod::ongoing_now(),
+        );
+        let expected = (RelationshipQuality::default_friendship().trust
+            + RelationshipQuality::default_employment().trust)
+            / 2.0;
+        assert!((quality.trust - expected).abs() < 1e-12);
+        assert_eq!(quality.formality, Formality::Informal);
+    }
+}
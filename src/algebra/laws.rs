@@ -0,0 +1,325 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Category Laws for Composition Rules
+//!
+//! Relationships are claimed to form a category. A rule set keeps that
+//! claim only if composition is associative and identities are neutral.
+//! `LawChecker` verifies both over a `CompositionRegistry` and reports
+//! counterexamples; consumers can call it from their own tests as their
+//! rule sets grow.
+//!
+//! ## Laws
+//!
+//! ```text
+//! associativity   (a o b) o c  =  a o (b o c)     category and quality
+//!                 one side defined  =>  the other is, with the same result
+//! identity        id o x  =  x  =  x o id           category and quality
+//! ```
+//!
+//! Quality laws are checked numerically over sample qualities. The
+//! identity morphism has the maximal quality (1.0 on every dimension), so
+//! an identity rule must combine with `Product`, `Min` or the non-identity
+//! side.
+
+use super::{CompositionRegistry, CompositionRule};
+use crate::quality::RelationshipQuality;
+use crate::value_objects::{Formality, RelationshipCategory, ValidityPeriod};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A counterexample to a category law
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum LawViolation {
+    /// `(a o b) o c` and `a o (b o c)` differ in category or definedness
+    AssociativityCategory {
+        categories: [RelationshipCategory; 3],
+        left: Option<RelationshipCategory>,
+        right: Option<RelationshipCategory>,
+    },
+    /// `(a o b) o c` and `a o (b o c)` differ in quality
+    AssociativityQuality {
+        categories: [RelationshipCategory; 3],
+        dimension: String,
+        left: f64,
+        right: f64,
+    },
+    /// Composing with the identity is undefined or changes the category
+    IdentityCategory {
+        category: RelationshipCategory,
+        /// True when the identity comes first (`id o x`)
+        identity_first: bool,
+        result: Option<RelationshipCategory>,
+    },
+    /// Composing with the identity changes the quality
+    IdentityQuality {
+        category: RelationshipCategory,
+        identity_first: bool,
+        dimension: String,
+        expected: f64,
+        actual: f64,
+    },
+}
+
+/// Result of checking a rule set
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LawReport {
+    /// Composable category triples checked for associativity
+    pub triples_checked: usize,
+    /// Counterexamples found
+    pub violations: Vec<LawViolation>,
+}
+
+impl LawReport {
+    /// Check if every law holds
+    pub fn is_lawful(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// Panic with the counterexamples unless every law holds
+    ///
+    /// Intended for consumer test suites.
+    pub fn assert_lawful(&self) {
+        assert!(
+            self.is_lawful(),
+            "composition rules violate {} law(s): {:#?}",
+            self.violations.len(),
+            self.violations
+        );
+    }
+}
+
+/// Checks the category laws over a composition rule set
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LawChecker {
+    /// Identity category whose rules are checked, if any
+    pub identity: Option<RelationshipCategory>,
+    /// Qualities the quality laws are checked over
+    pub samples: Vec<RelationshipQuality>,
+    /// Largest difference treated as equal
+    pub tolerance: f64,
+}
+
+impl Default for LawChecker {
+    fn default() -> Self {
+        let sample = |strength, trust, formality, reciprocity| RelationshipQuality {
+            strength,
+            trust,
+            formality,
+            duration: ValidityPeriod::ongoing_now(),
+            reciprocity,
+        };
+        Self {
+            identity: None,
+            samples: vec![
+                sample(0.2, 0.9, Formality::Informal, 0.5),
+                sample(0.6, 0.3, Formality::Contractual, 1.0),
+                sample(0.9, 0.6, Formality::SemiFormal, 0.1),
+            ],
+            tolerance: 1e-9,
+        }
+    }
+}
+
+fn dimensions(quality: &RelationshipQuality) -> [(&'static str, f64); 4] {
+    [
+        ("strength", quality.strength),
+        ("trust", quality.trust),
+        ("formality", quality.formality.as_f64()),
+        ("reciprocity", quality.reciprocity),
+    ]
+}
+
+impl LawChecker {
+    /// Also check the identity laws for a category
+    pub fn with_identity(mut self, category: RelationshipCategory) -> Self {
+        self.identity = Some(category);
+        self
+    }
+
+    /// Check every law over a rule set
+    pub fn check(&self, registry: &CompositionRegistry) -> LawReport {
+        let mut report = LawReport::default();
+        self.check_associativity(registry, &mut report);
+        if let Some(identity) = &self.identity {
+            self.check_identity(registry, identity, &mut report);
+        }
+        report
+    }
+
+    fn compose(&self, rule: &CompositionRule, a: &RelationshipQuality, b: &RelationshipQuality) -> RelationshipQuality {
+        rule.quality.compose(a, b, a.duration.clone())
+    }
+
+    /// First differing dimension of two qualities
+    fn difference(&self, a: &RelationshipQuality, b: &RelationshipQuality) -> Option<(String, f64, f64)> {
+        dimensions(a)
+            .into_iter()
+            .zip(dimensions(b))
+            .find(|((_, x), (_, y))| (x - y).abs() > self.tolerance)
+            .map(|((name, x), (_, y))| (name.to_string(), x, y))
+    }
+
+    fn check_associativity(&self, registry: &CompositionRegistry, report: &mut LawReport) {
+        let mut rules: Vec<&CompositionRule> = registry.rules().collect();
+        rules.sort_by_cached_key(|r| format!("{:?}|{:?}", r.first, r.second));
+
+        for ab in &rules {
+            for bc in rules.iter().filter(|r| r.first == ab.second) {
+                report.triples_checked += 1;
+                let categories = [ab.first.clone(), ab.second.clone(), bc.second.clone()];
+                let left = registry.rule(&ab.result, &bc.second).ok();
+                let right = registry.rule(&ab.first, &bc.result).ok();
+
+                let (Some(left), Some(right)) = (left, right) else {
+                    if left.is_some() || right.is_some() {
+                        report.violations.push(LawViolation::AssociativityCategory {
+                            categories,
+                            left: left.map(|r| r.result.clone()),
+                            right: right.map(|r| r.result.clone()),
+                        });
+                    }
+                    continue;
+                };
+                if left.result != right.result {
+                    report.violations.push(LawViolation::AssociativityCategory {
+                        categories,
+                        left: Some(left.result.clone()),
+                        right: Some(right.result.clone()),
+                    });
+                    continue;
+                }
+
+                'samples: for x in &self.samples {
+                    for y in &self.samples {
+                        for z in &self.samples {
+                            let l = self.compose(left, &self.compose(ab, x, y), z);
+                            let r = self.compose(right, x, &self.compose(bc, y, z));
+                            if let Some((dimension, left, right)) = self.difference(&l, &r) {
+                                report.violations.push(LawViolation::AssociativityQuality {
+                                    categories: categories.clone(),
+                                    dimension,
+                                    left,
+                                    right,
+                                });
+                                break 'samples;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn check_identity(&self, registry: &CompositionRegistry, identity: &RelationshipCategory, report: &mut LawReport) {
+        // Every category named by a rule, other than the identity itself
+        let categories: BTreeMap<String, &RelationshipCategory> = registry
+            .rules()
+            .flat_map(|r| [&r.first, &r.second, &r.result])
+            .filter(|c| *c != identity)
+            .map(|c| (format!("{c:?}"), c))
+            .collect();
+        let unit = RelationshipQuality {
+            strength: 1.0,
+            trust: 1.0,
+            formality: Formality::Legal,
+            duration: ValidityPeriod::ongoing_now(),
+            reciprocity: 1.0,
+        };
+
+        for category in categories.into_values() {
+            for identity_first in [true, false] {
+                let rule = if identity_first {
+                    registry.rule(identity, category)
+                } else {
+                    registry.rule(category, identity)
+                };
+                let rule = match rule {
+                    Ok(rule) if rule.result == *category => rule,
+                    other => {
+                        report.violations.push(LawViolation::IdentityCategory {
+                            category: category.clone(),
+                            identity_first,
+                            result: other.ok().map(|r| r.result.clone()),
+                        });
+                        continue;
+                    }
+                };
+                for x in &self.samples {
+                    let actual = if identity_first {
+                        self.compose(rule, &unit, x)
+                    } else {
+                        self.compose(rule, x, &unit)
+                    };
+                    if let Some((dimension, actual, expected)) = self.difference(&actual, x) {
+                        report.violations.push(LawViolation::IdentityQuality {
+                            category: category.clone(),
+                            identity_first,
+                            dimension,
+                            expected,
+                            actual,
+                        });
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl CompositionRegistry {
+    /// Check the associativity law over these rules
+    pub fn check_laws(&self) -> LawReport {
+        LawChecker::default().check(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algebra::{Combine, QualityComposition};
+
+    #[test]
+    fn test_standard_rules_are_associative() {
+        let report = CompositionRegistry::standard().check_laws();
+        assert!(report.triples_checked > 0);
+        report.assert_lawful();
+    }
+
+    #[test]
+    fn test_counterexamples() {
+        use RelationshipCategory::*;
+        // Mean is not associative
+        let averaging = QualityComposition {
+            trust: Combine::Mean,
+            ..QualityComposition::default()
+        };
+        let registry = CompositionRegistry::empty()
+            .with_rule(CompositionRule::new(References, References, References).with_quality(averaging));
+        let report = registry.check_laws();
+        assert!(matches!(
+            &report.violations[..],
+            [LawViolation::AssociativityQuality { dimension, .. }] if dimension == "trust"
+        ));
+
+        // Identity that is neutral only on the left
+        let identity = Custom("identity".to_string());
+        let registry = CompositionRegistry::empty()
+            .with_rule(CompositionRule::new(identity.clone(), PartOf, PartOf))
+            .with_rule(CompositionRule::new(PartOf, identity.clone(), PartOf).with_quality(QualityComposition {
+                strength: Combine::Max,
+                ..QualityComposition::default()
+            }));
+        let report = LawChecker::default().with_identity(identity).check(&registry);
+        assert!(!report.is_lawful());
+        assert!(report.violations.iter().any(|v| matches!(
+            v,
+            LawViolation::IdentityQuality { identity_first: false, dimension, .. } if dimension == "strength"
+        )));
+        assert!(!report
+            .violations
+            .iter()
+            .any(|v| matches!(v, LawViolation::IdentityQuality { identity_first: true, .. })));
+    }
+}
@@ -0,0 +1,43 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Relationship Algebra
+//!
+//! Operations that build relationships out of other relationships.
+//!
+//! ## Composition
+//!
+//! ```text
+//! A --Employment--> B --Membership--> C
+//!            |
+//!   CompositionRegistry: (Employment, Membership) -> authority
+//!            v
+//! A --------------authority---------------> C
+//! ```
+//!
+//! Which pairs of categories compose, into what, and how their qualities
+//! combine is data held by a `CompositionRegistry`, not code. Pairs
+//! without a rule do not compose. `LawChecker` verifies that a rule set
+//! is associative and respects identities.
+//!
+//! ## Sets
+//!
+//! Union, intersection and difference of the relationships of two spaces,
+//! matching relationships by endpoints and category.
+//!
+//! ## Entity Merges
+//!
+//! `RelationshipSpace::merge_entities` rewrites the relationships of a
+//! merged-away entity onto the survivor and folds resulting duplicates
+//! together, emitting an event for every change.
+
+mod composition;
+mod laws;
+mod pushout;
+mod sets;
+
+pub use composition::{Combine, CompositionRegistry, CompositionRule, QualityComposition};
+pub use laws::{LawChecker, LawReport, LawViolation};
+pub use pushout::ENTITY_MERGE_REASON;
+pub use sets::{MergeStrategy, RelationshipKey};
@@ -0,0 +1,439 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Entity Merge Pushout
+//!
+//! When an upstream domain merges two entities, every relationship of the
+//! absorbed entity is carried over to the survivor: the pushout of the
+//! relationship graph along `absorbed -> survivor`.
+//!
+//! ## Steps
+//!
+//! ```text
+//! 1. rewrite    edges touching `absorbed`            RedirectEdge
+//!               hyperedges with `absorbed`           AddParticipant + RemoveParticipant
+//!                                                    (inside Begin/CompleteRestructuring
+//!                                                    if active)
+//! 2. collapse   edges now joining survivor to itself  TerminateEdge / RejectEdge
+//!               (unless their category allows self-edges)
+//!               hyperedges left with < 2 participants TerminateHyperEdge
+//! 3. merge      parallel duplicates (same RelationshipKey):
+//!               edges into the oldest                 MergeEdges + UpdateEdgeQuality
+//!               hyperedges into the oldest            UpdateHyperEdgeQuality + TerminateHyperEdge
+//! ```
+//!
+//! Every change is a command, decided and applied in turn against a copy of
+//! the space, so the merge passes the same guards, consent rules and
+//! policies as any other command. The space takes the copy only when every
+//! step succeeded; the events are returned for publishing. Terminal
+//! relationships are history and are left untouched.
+//!
+//! Merged qualities combine by the space's composition rule for the
+//! category paired with itself, or by the caller's `MergeStrategy` where
+//! there is none.
+
+use super::{MergeStrategy, RelationshipKey};
+use crate::aggregates::{EdgeState, RelationshipSpace};
+use crate::commands::{
+    AddParticipant, BeginRestructuring, CompleteRestructuring, EdgeCommand, HyperEdgeCommand, MergeEdges, RedirectEdge,
+    RejectEdge, RemoveParticipant, RestructureCommand, TerminateEdge, TerminateHyperEdge,
+    UpdateEdgeQuality, UpdateHyperEdgeQuality,
+};
+use crate::events::RelationshipEvent;
+use crate::quality::RelationshipQuality;
+use crate::value_objects::{EntityRef, RelationshipCategory, RelationshipId};
+use crate::{RelationshipError, RelationshipResult};
+use chrono::{DateTime, Utc};
+use cim_domain::state_machine::State;
+use cim_domain::MessageIdentity;
+use std::collections::HashMap;
+
+/// Reason and actor recorded on entity merge events
+pub const ENTITY_MERGE_REASON: &str = "entity_merge";
+
+/// Ids of relationships sharing a key, oldest first, for keys held by
+/// more than one relationship
+fn parallel_groups(
+    relationships: impl Iterator<Item = (RelationshipKey, DateTime<Utc>, RelationshipId)>,
+) -> Vec<Vec<RelationshipId>> {
+    let mut groups: HashMap<RelationshipKey, Vec<(DateTime<Utc>, RelationshipId)>> = HashMap::new();
+    for (key, created_at, id) in relationships {
+        groups.entry(key).or_default().push((created_at, id));
+    }
+    let mut groups: Vec<Vec<RelationshipId>> = groups
+        .into_values()
+        .filter(|g| g.len() > 1)
+        .map(|mut g| {
+            g.sort_by_key(|(created_at, id)| (*created_at, id.as_uuid()));
+            g.into_iter().map(|(_, id)| id).collect()
+        })
+        .collect();
+    groups.sort_by_key(|g| g[0].as_uuid());
+    groups
+}
+
+impl RelationshipSpace {
+    /// Carry the relationships of `absorbed` over to `survivor`
+    ///
+    /// Parallel relationships that result are merged into the oldest.
+    /// Returns the events applied, in order; if any step is refused the
+    /// space is left as it was.
+    pub fn merge_entities(
+        &mut self,
+        absorbed: &EntityRef,
+        survivor: &EntityRef,
+        strategy: MergeStrategy,
+    ) -> RelationshipResult<Vec<RelationshipEvent>> {
+        if absorbed.same_entity(survivor) {
+            return Err(RelationshipError::InvalidConfiguration(
+                "cannot merge an entity into itself".to_string(),
+            ));
+        }
+
+        let mut merged = self.clone();
+        let mut events = Vec::new();
+        merged.rewrite_edges(absorbed, survivor, &mut events)?;
+        merged.rewrite_hyperedges(absorbed, survivor, &mut events)?;
+        merged.merge_parallel_edges(survivor, strategy, &mut events)?;
+        merged.merge_parallel_hyperedges(survivor, strategy, &mut events)?;
+        *self = merged;
+        Ok(events)
+    }
+
+    /// Quality of two parallel relationships of a category merged into one
+    fn merged_quality(
+        &self,
+        category: &RelationshipCategory,
+        strategy: MergeStrategy,
+        kept: &RelationshipQuality,
+        other: &RelationshipQuality,
+    ) -> RelationshipQuality {
+        match self.composition_rules.rule(category, category) {
+            Ok(rule) => rule.quality.compose(kept, other, kept.duration.clone()),
+            Err(_) => strategy.merge_quality(kept, other),
+        }
+    }
+
+    /// End an edge: rejected if it never activated, terminated otherwise
+    fn end_edge(&mut self, id: RelationshipId, reason: String, events: &mut Vec<RelationshipEvent>) -> RelationshipResult<()> {
+        let cmd = match self.edges[&id].state {
+            EdgeState::Proposed | EdgeState::PendingConsent => EdgeCommand::RejectEdge(RejectEdge {
+                identity: MessageIdentity::new_root(),
+                edge_id: id,
+                reason: Some(reason),
+                rejected_by: ENTITY_MERGE_REASON.to_string(),
+            }),
+            _ => EdgeCommand::TerminateEdge(TerminateEdge {
+                identity: MessageIdentity::new_root(),
+                edge_id: id,
+                reason,
+                terminated_by: ENTITY_MERGE_REASON.to_string(),
+            }),
+        };
+        self.run_command(cmd, events)
+    }
+
+    fn terminate_hyperedge(&mut self, id: RelationshipId, reason: String, events: &mut Vec<RelationshipEvent>) -> RelationshipResult<()> {
+        let cmd = HyperEdgeCommand::TerminateHyperEdge(TerminateHyperEdge {
+            identity: MessageIdentity::new_root(),
+            hyperedge_id: id,
+            reason,
+            terminated_by: ENTITY_MERGE_REASON.to_string(),
+        });
+        self.run_command(cmd, events)
+    }
+
+    fn rewrite_edges(
+        &mut self,
+        absorbed: &EntityRef,
+        survivor: &EntityRef,
+        events: &mut Vec<RelationshipEvent>,
+    ) -> RelationshipResult<()> {
+        let mut touching: Vec<_> = self
+            .edges
+            .values()
+            .filter(|e| !e.state.is_closed())
+            .filter(|e| e.source.same_entity(absorbed) || e.target.same_entity(absorbed))
+            .map(|e| (e.id, e.source.clone(), e.target.clone(), self.allows_self_edge(&e.category)))
+            .collect();
+        touching.sort_by_key(|(id, ..)| id.as_uuid());
+
+        let carry = |entity: &EntityRef| entity.same_entity(absorbed).then(|| survivor.clone());
+        for (id, source, target, reflexive) in touching {
+            let (new_source, new_target) = (carry(&source), carry(&target));
+            let self_loop = new_source.as_ref().unwrap_or(&source).same_entity(new_target.as_ref().unwrap_or(&target));
+            if self_loop && !reflexive {
+                self.end_edge(id, format!("{ENTITY_MERGE_REASON}: collapsed to a self-loop"), events)?;
+                continue;
+            }
+            let cmd = EdgeCommand::RedirectEdge(RedirectEdge {
+                identity: MessageIdentity::new_root(),
+                edge_id: id,
+                new_source,
+                new_target,
+                reason: ENTITY_MERGE_REASON.to_string(),
+                redirected_by: ENTITY_MERGE_REASON.to_string(),
+            });
+            self.run_command(cmd, events)?;
+        }
+        Ok(())
+    }
+
+    fn rewrite_hyperedges(
+        &mut self,
+        absorbed: &EntityRef,
+        survivor: &EntityRef,
+        events: &mut Vec<RelationshipEvent>,
+    ) -> RelationshipResult<()> {
+        let mut touching: Vec<_> = self
+            .hyperedges
+            .values()
+            .filter(|h| !h.state.is_terminal())
+            .filter_map(|h| {
+                let entry = h.participants.get(absorbed)?;
+                Some((h.id, entry.clone(), h.participants.contains(survivor), h.participants.participant_count(), h.is_active()))
+            })
+            .collect();
+        touching.sort_by_key(|(id, ..)| id.as_uuid());
+
+        for (id, entry, has_survivor, count, active) in touching {
+            if has_survivor && count <= 2 {
+                self.terminate_hyperedge(id, format!("{ENTITY_MERGE_REASON}: fewer than two participants remain"), events)?;
+                continue;
+            }
+            // Roles are checked once the swap is complete, not halfway
+            if active {
+                let begin = HyperEdgeCommand::BeginRestructuring(BeginRestructuring {
+                    identity: MessageIdentity::new_root(),
+                    hyperedge_id: id,
+                    reason: Some(format!("{ENTITY_MERGE_REASON}: {} merged into {}", absorbed.key(), survivor.key())),
+                    begun_by: ENTITY_MERGE_REASON.to_string(),
+                });
+                self.run_command(begin, events)?;
+            }
+            if !has_survivor {
+                let add = HyperEdgeCommand::AddParticipant(AddParticipant {
+                    identity: MessageIdentity::new_root(),
+                    hyperedge_id: id,
+                    participant: survivor.clone(),
+                    role: entry.role.clone(),
+                    weight: entry.weight,
+                    added_by: ENTITY_MERGE_REASON.to_string(),
+                });
+                self.run_command(add, events)?;
+            }
+            let remove = HyperEdgeCommand::RemoveParticipant(RemoveParticipant {
+                identity: MessageIdentity::new_root(),
+                hyperedge_id: id,
+                participant: entry.entity_ref.clone(),
+                reason: format!("{ENTITY_MERGE_REASON}: merged into {}", survivor.key()),
+                removed_by: ENTITY_MERGE_REASON.to_string(),
+            });
+            self.run_command(remove, events)?;
+            if active {
+                let complete = HyperEdgeCommand::CompleteRestructuring(CompleteRestructuring {
+                    identity: MessageIdentity::new_root(),
+                    hyperedge_id: id,
+                    completed_by: ENTITY_MERGE_REASON.to_string(),
+                });
+                self.run_command(complete, events)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn merge_parallel_edges(
+        &mut self,
+        survivor: &EntityRef,
+        strategy: MergeStrategy,
+        events: &mut Vec<RelationshipEvent>,
+    ) -> RelationshipResult<()> {
+        let groups = parallel_groups(
+            self.edges
+                .values()
+                .filter(|e| !e.state.is_closed())
+                .filter(|e| e.source.same_entity(survivor) || e.target.same_entity(survivor))
+                .map(|e| (RelationshipKey::of_edge(e), e.created_at, e.id)),
+        );
+        for group in groups {
+            let keep = group[0];
+            let (category, old_quality) = (self.edges[&keep].category.clone(), self.edges[&keep].quality.clone());
+            let new_quality = group[1..].iter().fold(old_quality.clone(), |quality, id| {
+                self.merged_quality(&category, strategy, &quality, &self.edges[id].quality)
+            });
+            // Evidence, confidence and properties merge as MergeEdges does
+            for id in &group[1..] {
+                let merge = RestructureCommand::MergeEdges(MergeEdges {
+                    identity: MessageIdentity::new_root(),
+                    survivor_id: keep,
+                    duplicate_id: *id,
+                    merged_by: ENTITY_MERGE_REASON.to_string(),
+                });
+                self.run_command(merge, events)?;
+            }
+            if new_quality != old_quality {
+                let update = EdgeCommand::UpdateEdgeQuality(UpdateEdgeQuality {
+                    identity: MessageIdentity::new_root(),
+                    edge_id: keep,
+                    new_quality,
+                    reason: ENTITY_MERGE_REASON.to_string(),
+                });
+                self.run_command(update, events)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn merge_parallel_hyperedges(
+        &mut self,
+        survivor: &EntityRef,
+        strategy: MergeStrategy,
+        events: &mut Vec<RelationshipEvent>,
+    ) -> RelationshipResult<()> {
+        let groups = parallel_groups(
+            self.hyperedges
+                .values()
+                .filter(|h| !h.state.is_terminal() && h.participants.contains(survivor))
+                .map(|h| (RelationshipKey::of_hyperedge(h), h.created_at, h.id)),
+        );
+        for group in groups {
+            let keep = group[0];
+            let (category, old_quality) =
+                (self.hyperedges[&keep].category.clone(), self.hyperedges[&keep].quality.clone());
+            let new_quality = group[1..].iter().fold(old_quality.clone(), |quality, id| {
+                self.merged_quality(&category, strategy, &quality, &self.hyperedges[id].quality)
+            });
+            if new_quality != old_quality {
+                let update = HyperEdgeCommand::UpdateHyperEdgeQuality(UpdateHyperEdgeQuality {
+                    identity: MessageIdentity::new_root(),
+                    hyperedge_id: keep,
+                    new_quality,
+                    reason: ENTITY_MERGE_REASON.to_string(),
+                });
+                self.run_command(update, events)?;
+            }
+            for id in &group[1..] {
+                self.terminate_hyperedge(*id, format!("{ENTITY_MERGE_REASON}: merged into {keep}"), events)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregates::{EdgeConcept, HyperEdgeConcept};
+    use uuid::Uuid;
+    use crate::quality::RelationshipQuality;
+    use crate::value_objects::{ParticipantRole, RelationshipCategory};
+    use cim_domain_spaces::TopologicalSpaceId;
+
+    #[test]
+    fn test_merge_entities() {
+        let (alice, alias, acme) = (
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::organization(Uuid::now_v7()),
+        );
+        let employment = |person: &EntityRef, strength| {
+            EdgeConcept::new("Works at", person.clone(), acme.clone(), RelationshipCategory::Employment).with_quality(
+                RelationshipQuality {
+                    strength,
+                    ..RelationshipQuality::default()
+                },
+            )
+        };
+
+        let mut space = RelationshipSpace::new("people", TopologicalSpaceId::new());
+        let mut kept = employment(&alice, 0.4);
+        kept.evidence_cids.push("bafy-payslip".to_string());
+        kept.activate().unwrap();
+        let mut duplicate = employment(&alias, 0.8);
+        duplicate.evidence_cids.push("bafy-contract".to_string());
+        duplicate.activate().unwrap();
+        let self_loop = EdgeConcept::new("Knows", alias.clone(), alice.clone(), RelationshipCategory::Friendship);
+        space.add_edge(kept.clone()).unwrap();
+        space.add_edge(duplicate.clone()).unwrap();
+        space.add_edge(self_loop.clone()).unwrap();
+
+        let mut team = HyperEdgeConcept::new("Team", RelationshipCategory::Membership);
+        team.add_participant(alias.clone(), ParticipantRole::Leader, 1.0).unwrap();
+        team.add_participant(acme.clone(), ParticipantRole::Member, 1.0).unwrap();
+        space.add_hyperedge(team.clone());
+
+        let events = space.merge_entities(&alias, &alice, MergeStrategy::MaxQuality).unwrap();
+
+        // Duplicate rewritten then merged into the older edge, evidence and all
+        let kept = space.get_edge(&kept.id).unwrap();
+        assert!(!kept.state.is_closed());
+        assert_eq!(kept.quality.strength, 0.8);
+        assert!(kept.evidence_cids.contains(&"bafy-contract".to_string()));
+        assert_eq!(space.get_edge(&duplicate.id).unwrap().state, EdgeState::Terminated);
+        assert_eq!(space.get_edge(&duplicate.id).unwrap().source, alice);
+        // Never activated, so rejected rather than terminated
+        assert_eq!(space.get_edge(&self_loop.id).unwrap().state, EdgeState::Rejected);
+
+        let team = space.get_hyperedge(&team.id).unwrap();
+        assert!(team.participants.contains(&alice) && !team.participants.contains(&alias));
+        assert_eq!(team.participants.get(&alice).unwrap().role, ParticipantRole::Leader);
+
+        let types: Vec<&str> = events.iter().map(|e| e.event_type()).collect();
+        assert_eq!(types.iter().filter(|t| **t == "edge_terminated").count(), 1);
+        assert_eq!(types.iter().filter(|t| **t == "edge_rejected").count(), 1);
+        assert!(types.contains(&"edge_endpoints_rewritten"));
+        assert!(types.contains(&"edge_evidence_added"));
+        assert!(types.contains(&"edge_quality_updated"));
+        assert!(types.contains(&"participant_added") && types.contains(&"participant_removed"));
+
+        assert!(space.merge_entities(&alice, &alice, MergeStrategy::PreferLeft).is_err());
+    }
+
+    #[test]
+    fn test_merge_entities_uses_composition_rules() {
+        let (part, alias, whole) = (
+            EntityRef::concept(Uuid::now_v7()),
+            EntityRef::concept(Uuid::now_v7()),
+            EntityRef::concept(Uuid::now_v7()),
+        );
+        let part_of = |part: &EntityRef| {
+            EdgeConcept::new("Part of", part.clone(), whole.clone(), RelationshipCategory::PartOf).with_quality(
+                RelationshipQuality {
+                    strength: 0.5,
+                    ..RelationshipQuality::default()
+                },
+            )
+        };
+        let mut space = RelationshipSpace::new("parts", TopologicalSpaceId::new());
+        let (kept, duplicate) = (part_of(&part), part_of(&alias));
+        space.add_edge(kept.clone()).unwrap();
+        space.add_edge(duplicate).unwrap();
+
+        // PartOf o PartOf multiplies strengths; the strategy would keep 0.5
+        space.merge_entities(&alias, &part, MergeStrategy::MaxQuality).unwrap();
+        assert!((space.get_edge(&kept.id).unwrap().quality.strength - 0.25).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_refused_merge_leaves_space_untouched() {
+        let (a, alias, b) = (
+            EntityRef::organization(Uuid::now_v7()),
+            EntityRef::organization(Uuid::now_v7()),
+            EntityRef::organization(Uuid::now_v7()),
+        );
+        let mut space = RelationshipSpace::new("deps", TopologicalSpaceId::new());
+        let depends = |from: &EntityRef, to: &EntityRef| {
+            EdgeConcept::new("Depends", from.clone(), to.clone(), RelationshipCategory::DependsOn)
+        };
+        let first = depends(&alias, &b);
+        space.add_edge(first.clone()).unwrap();
+        space.add_edge(depends(&b, &a)).unwrap();
+        let version = space.version;
+
+        // alias -> b rewritten to a -> b would close a -> b -> a
+        assert!(space.merge_entities(&alias, &a, MergeStrategy::PreferLeft).is_err());
+        assert_eq!(space.version, version);
+        assert_eq!(space.get_edge(&first.id).unwrap().source, alias);
+    }
+}
@@ -0,0 +1,317 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Relationship Set Algebra
+//!
+//! Union, intersection and difference of the relationships of two spaces,
+//! e.g. two data sources describing the same people.
+//!
+//! ## Identity
+//!
+//! ```text
+//! edge       category + source + target   (endpoints unordered when symmetric)
+//! hyperedge  category + participant set
+//! ```
+//!
+//! Two relationships with the same key are the same relationship, whatever
+//! their ids. Entities are compared by `EntityKey`, so CID or version pins
+//! do not matter.
+//!
+//! ## Merging
+//!
+//! When both sides hold a relationship, a `MergeStrategy` decides the
+//! result. Results are deterministic: relationships are added in key order
+//! and ties go to the left side.
+
+use crate::aggregates::{EdgeConcept, HyperEdgeConcept, RelationshipSpace};
+use crate::quality::RelationshipQuality;
+use crate::value_objects::{EntityKey, Formality, RelationshipCategory};
+use crate::RelationshipResult;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Identity of a relationship for set operations
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RelationshipKey {
+    /// Category
+    pub category: RelationshipCategory,
+    /// Endpoints: source and target of an edge (sorted when symmetric),
+    /// sorted participants of a hyperedge
+    pub endpoints: Vec<EntityKey>,
+    /// Whether the relationship is a hyperedge
+    pub hyperedge: bool,
+}
+
+impl RelationshipKey {
+    /// Key of an edge
+    pub fn of_edge(edge: &EdgeConcept) -> Self {
+        let mut endpoints = vec![edge.source.key(), edge.target.key()];
+        if edge.is_symmetric() {
+            endpoints.sort_by_key(|k| k.to_string());
+        }
+        Self {
+            category: edge.category.clone(),
+            endpoints,
+            hyperedge: false,
+        }
+    }
+
+    /// Key of a hyperedge
+    pub fn of_hyperedge(hyperedge: &HyperEdgeConcept) -> Self {
+        let mut endpoints: Vec<EntityKey> = hyperedge
+            .participants
+            .participants()
+            .map(|p| p.entity_ref.key())
+            .collect();
+        endpoints.sort_by_key(|k| k.to_string());
+        Self {
+            category: hyperedge.category.clone(),
+            endpoints,
+            hyperedge: true,
+        }
+    }
+
+    fn sort_key(&self) -> String {
+        let endpoints: Vec<String> = self.endpoints.iter().map(|k| k.to_string()).collect();
+        format!("{}|{:?}|{}", self.hyperedge, self.category, endpoints.join(","))
+    }
+}
+
+/// How a relationship present on both sides is merged
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum MergeStrategy {
+    /// Keep the left side's relationship
+    #[default]
+    PreferLeft,
+    /// Keep the right side's relationship
+    PreferRight,
+    /// Keep the most recently updated relationship
+    Newest,
+    /// Left side's relationship with the mean of both qualities
+    MeanQuality,
+    /// Left side's relationship with the stronger value of each dimension
+    MaxQuality,
+}
+
+impl MergeStrategy {
+    /// Merge two qualities; duration is kept from the left
+    pub fn merge_quality(&self, left: &RelationshipQuality, right: &RelationshipQuality) -> RelationshipQuality {
+        let combine = |a: f64, b: f64| match self {
+            MergeStrategy::MeanQuality => (a + b) / 2.0,
+            MergeStrategy::MaxQuality => a.max(b),
+            MergeStrategy::PreferRight => b,
+            MergeStrategy::PreferLeft | MergeStrategy::Newest => a,
+        };
+        RelationshipQuality {
+            strength: combine(left.strength, right.strength),
+            trust: combine(left.trust, right.trust),
+            formality: Formality::from_f64(combine(left.formality.as_f64(), right.formality.as_f64())),
+            duration: left.duration.clone(),
+            reciprocity: combine(left.reciprocity, right.reciprocity),
+        }
+    }
+
+    fn merge<C: Concept>(&self, left: &C, right: &C) -> C {
+        match self {
+            MergeStrategy::PreferLeft => left.clone(),
+            MergeStrategy::PreferRight => right.clone(),
+            MergeStrategy::Newest if right.updated_at() > left.updated_at() => right.clone(),
+            MergeStrategy::Newest => left.clone(),
+            MergeStrategy::MeanQuality | MergeStrategy::MaxQuality => {
+                let mut merged = left.clone();
+                merged.set_quality(self.merge_quality(left.quality(), right.quality()));
+                merged
+            }
+        }
+    }
+}
+
+/// The parts of edges and hyperedges set operations need
+trait Concept: Clone {
+    fn key(&self) -> RelationshipKey;
+    fn updated_at(&self) -> DateTime<Utc>;
+    fn quality(&self) -> &RelationshipQuality;
+    fn set_quality(&mut self, quality: RelationshipQuality);
+}
+
+impl Concept for EdgeConcept {
+    fn key(&self) -> RelationshipKey {
+        RelationshipKey::of_edge(self)
+    }
+    fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+    fn quality(&self) -> &RelationshipQuality {
+        &self.quality
+    }
+    fn set_quality(&mut self, quality: RelationshipQuality) {
+        self.position = quality.to_quality_point().to_point3();
+        self.quality = quality;
+    }
+}
+
+impl Concept for HyperEdgeConcept {
+    fn key(&self) -> RelationshipKey {
+        RelationshipKey::of_hyperedge(self)
+    }
+    fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+    fn quality(&self) -> &RelationshipQuality {
+        &self.quality
+    }
+    fn set_quality(&mut self, quality: RelationshipQuality) {
+        self.position = quality.to_quality_point().to_point3();
+        self.quality = quality;
+    }
+}
+
+/// Relationships of one side by key, the most recently updated winning
+/// within the side
+fn keyed<'a, C: Concept + 'a>(concepts: impl Iterator<Item = &'a C>) -> HashMap<RelationshipKey, &'a C> {
+    let mut map: HashMap<RelationshipKey, &C> = HashMap::new();
+    for concept in concepts {
+        let entry = map.entry(concept.key()).or_insert(concept);
+        if concept.updated_at() > entry.updated_at() {
+            *entry = concept;
+        }
+    }
+    map
+}
+
+#[derive(Clone, Copy)]
+enum Operation {
+    Union,
+    Intersection,
+    Difference,
+}
+
+fn combine<'a, C: Concept + 'a>(
+    left: impl Iterator<Item = &'a C>,
+    right: impl Iterator<Item = &'a C>,
+    operation: Operation,
+    strategy: MergeStrategy,
+) -> Vec<C> {
+    let (left, right) = (keyed(left), keyed(right));
+    let mut keys: Vec<&RelationshipKey> = match operation {
+        Operation::Union => left.keys().chain(right.keys().filter(|k| !left.contains_key(*k))).collect(),
+        Operation::Intersection => left.keys().filter(|k| right.contains_key(*k)).collect(),
+        Operation::Difference => left.keys().filter(|k| !right.contains_key(*k)).collect(),
+    };
+    keys.sort_by_cached_key(|k| k.sort_key());
+
+    keys.into_iter()
+        .map(|key| match (left.get(key), right.get(key)) {
+            (Some(l), Some(r)) => strategy.merge(*l, *r),
+            (Some(c), None) | (None, Some(c)) => (*c).clone(),
+            (None, None) => unreachable!("key taken from one side"),
+        })
+        .collect()
+}
+
+fn build(
+    left: &RelationshipSpace,
+    right: &RelationshipSpace,
+    operation: Operation,
+    strategy: MergeStrategy,
+    symbol: &str,
+) -> RelationshipResult<RelationshipSpace> {
+    let mut space = RelationshipSpace::new(format!("{} {symbol} {}", left.name, right.name), left.topology_id)
+        .with_quality_schema(left.quality_schema.clone())
+        .with_duration_model(left.duration_model.clone())
+        .with_weight_registry(left.weight_registry.clone())
+        .with_composition_rules(left.composition_rules.clone());
+    space.reflexive_categories = left.reflexive_categories.clone();
+    space.consent_categories = left.consent_categories.clone();
+    space.restructuring_categories = left.restructuring_categories.clone();
+    space.reproposal_policy = left.reproposal_policy;
+    space.role_schemas = left.role_schemas.clone();
+    space.policies = left.policies.clone();
+    space.templates = left.templates.clone();
+    space.guards = left.guards.clone();
+    for edge in combine(left.edges.values(), right.edges.values(), operation, strategy) {
+        space.add_edge(edge)?;
+    }
+    for hyperedge in combine(left.hyperedges.values(), right.hyperedges.values(), operation, strategy) {
+        space.add_hyperedge(hyperedge);
+    }
+    Ok(space)
+}
+
+impl RelationshipSpace {
+    /// Relationships in either space, as a new space with this space's
+    /// settings
+    ///
+    /// Fails if the combined DependsOn edges would form a cycle.
+    pub fn union(&self, other: &RelationshipSpace, strategy: MergeStrategy) -> RelationshipResult<RelationshipSpace> {
+        build(self, other, Operation::Union, strategy, "|")
+    }
+
+    /// Relationships in both spaces, merged by `strategy`
+    pub fn intersection(
+        &self,
+        other: &RelationshipSpace,
+        strategy: MergeStrategy,
+    ) -> RelationshipResult<RelationshipSpace> {
+        build(self, other, Operation::Intersection, strategy, "&")
+    }
+
+    /// Relationships in this space but not in `other`
+    pub fn difference(&self, other: &RelationshipSpace) -> RelationshipResult<RelationshipSpace> {
+        build(self, other, Operation::Difference, MergeStrategy::PreferLeft, "-")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value_objects::EntityRef;
+    use cim_domain_spaces::TopologicalSpaceId;
+    use uuid::Uuid;
+
+    fn edge(source: &EntityRef, target: &EntityRef, category: RelationshipCategory, strength: f64) -> EdgeConcept {
+        EdgeConcept::new("e", source.clone(), target.clone(), category).with_quality(RelationshipQuality {
+            strength,
+            ..RelationshipQuality::default()
+        })
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let (alice, bob, acme) = (
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::organization(Uuid::now_v7()),
+        );
+        let topology = TopologicalSpaceId::new();
+
+        let mut crm = RelationshipSpace::new("crm", topology);
+        crm.add_edge(edge(&alice, &acme, RelationshipCategory::Employment, 0.4)).unwrap();
+        crm.add_edge(edge(&alice, &bob, RelationshipCategory::Friendship, 0.5)).unwrap();
+
+        let mut hr = RelationshipSpace::new("hr", topology);
+        hr.add_edge(edge(&alice, &acme, RelationshipCategory::Employment, 0.8)).unwrap();
+        // Same friendship, endpoints the other way round
+        hr.add_edge(edge(&bob, &alice, RelationshipCategory::Friendship, 0.7)).unwrap();
+        hr.add_edge(edge(&bob, &acme, RelationshipCategory::Employment, 0.6)).unwrap();
+
+        let union = crm.union(&hr, MergeStrategy::MeanQuality).unwrap();
+        assert_eq!(union.edges.len(), 3);
+        let employment = union
+            .edges
+            .values()
+            .find(|e| e.source == alice && e.category == RelationshipCategory::Employment)
+            .unwrap();
+        assert!((employment.quality.strength - 0.6).abs() < 1e-12);
+
+        let both = crm.intersection(&hr, MergeStrategy::PreferRight).unwrap();
+        assert_eq!(both.edges.len(), 2);
+        assert!(both.edges.values().all(|e| e.quality.strength >= 0.7));
+
+        let only_hr = hr.difference(&crm).unwrap();
+        assert_eq!(only_hr.edges.len(), 1);
+        assert!(only_hr.edges.values().all(|e| e.source == bob && e.target == acme));
+    }
+}
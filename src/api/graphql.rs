@@ -0,0 +1,610 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! GraphQL API (`graphql` feature)
+//!
+//! A read-only GraphQL schema over the `RelationshipReadModel`, for
+//! frontends that do not speak NATS. Writes still go through commands.
+//!
+//! ```graphql
+//! type Query {
+//!   edge(id: UUID!): Edge
+//!   edges(filter: EdgeFilter, limit: Int, offset: Int! = 0): [Edge!]!
+//!   hyperedge(id: UUID!): HyperEdge
+//!   hyperedges(filter: HyperEdgeFilter, limit: Int, offset: Int! = 0): [HyperEdge!]!
+//!   egoNetwork(entity: String!, radius: Int! = 1, filter: EgoFilterInput): EgoNetwork!
+//!   similarEdges(edgeId: UUID!, maxDistance: Float! = 0.5, limit: Int): [SimilarEdge!]!
+//! }
+//! ```
+//!
+//! Entities are addressed by their `EntityKey` (`"person:{uuid}"`) and
+//! carry the display cache's label. Categories and states are matched by
+//! name, case-insensitively; every filter field is optional and the fields
+//! given must all match.
+//!
+//! ```rust,ignore
+//! let schema = graphql::schema(read_model);
+//! let response = schema.execute("{ edges(filter: { categories: [\"employment\"] }) { name } }").await;
+//! ```
+
+use crate::aggregates::{EdgeConcept, HyperEdgeConcept};
+use crate::graph::EgoFilter;
+use crate::projections::{EntityDisplayCache, RelationshipReadModel};
+use crate::quality::RelationshipQuality;
+use crate::value_objects::{EntityKey, EntityRef, RelationshipCategory, RelationshipId};
+use async_graphql::{
+    Context, EmptyMutation, EmptySubscription, InputObject, Object, Result, SimpleObject,
+};
+use chrono::{DateTime, Utc};
+use cim_domain::state_machine::State;
+use std::collections::BTreeSet;
+use uuid::Uuid;
+
+/// The relationship GraphQL schema
+pub type RelationshipSchema = async_graphql::Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Build the schema over a read model
+pub fn schema(read_model: RelationshipReadModel) -> RelationshipSchema {
+    async_graphql::Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(read_model)
+        .finish()
+}
+
+// ============================================================================
+// Output Types
+// ============================================================================
+
+/// A referenced entity
+#[derive(Debug, Clone, SimpleObject)]
+#[graphql(name = "Entity")]
+pub struct EntityObject {
+    /// `"{type}:{uuid}"`
+    pub key: String,
+    pub entity_type: String,
+    pub id: Uuid,
+    /// Display name, or the key when none is known
+    pub label: String,
+}
+
+/// Quality dimensions
+#[derive(Debug, Clone, SimpleObject)]
+#[graphql(name = "Quality")]
+pub struct QualityObject {
+    pub strength: f64,
+    pub trust: f64,
+    pub formality: String,
+    pub reciprocity: f64,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: Option<DateTime<Utc>>,
+}
+
+/// Binary relationship
+#[derive(Debug, Clone, SimpleObject)]
+#[graphql(name = "Edge")]
+pub struct EdgeObject {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub source: EntityObject,
+    pub target: EntityObject,
+    pub category: String,
+    pub state: String,
+    pub quality: QualityObject,
+    pub confidence: f64,
+    pub tags: Vec<String>,
+    pub version: u64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Participant in an n-ary relationship
+#[derive(Debug, Clone, SimpleObject)]
+#[graphql(name = "Participant")]
+pub struct ParticipantObject {
+    pub entity: EntityObject,
+    pub role: String,
+    pub weight: f64,
+}
+
+/// N-ary relationship
+#[derive(Debug, Clone, SimpleObject)]
+#[graphql(name = "HyperEdge")]
+pub struct HyperEdgeObject {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub category: String,
+    pub state: String,
+    pub participants: Vec<ParticipantObject>,
+    pub quality: QualityObject,
+    pub confidence: f64,
+    pub tags: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Entity in an ego network
+#[derive(Debug, Clone, SimpleObject)]
+#[graphql(name = "EgoNode")]
+pub struct EgoNodeObject {
+    pub entity: EntityObject,
+    /// Hops from the center
+    pub distance: usize,
+}
+
+/// Subgraph around a center entity
+#[derive(Debug, Clone, SimpleObject)]
+#[graphql(name = "EgoNetwork")]
+pub struct EgoNetworkObject {
+    pub center: EntityObject,
+    pub radius: usize,
+    pub nodes: Vec<EgoNodeObject>,
+    pub edges: Vec<EdgeObject>,
+    pub hyperedges: Vec<HyperEdgeObject>,
+}
+
+/// Edge near another in quality space
+#[derive(Debug, Clone, SimpleObject)]
+#[graphql(name = "SimilarEdge")]
+pub struct SimilarEdgeObject {
+    pub edge: EdgeObject,
+    /// 1.0 for identical quality, 0.0 for opposite corners of quality space
+    pub similarity: f64,
+}
+
+// ============================================================================
+// Filters
+// ============================================================================
+
+/// Edge field filters
+#[derive(Debug, Clone, Default, InputObject)]
+pub struct EdgeFilter {
+    /// Category names, e.g. "employment"
+    pub categories: Option<Vec<String>>,
+    /// State names, e.g. "Active"
+    pub states: Option<Vec<String>>,
+    /// Entity key at either end
+    pub entity: Option<String>,
+    pub source: Option<String>,
+    pub target: Option<String>,
+    /// Every one of these tags
+    pub tags: Option<Vec<String>>,
+    pub name_contains: Option<String>,
+    pub min_strength: Option<f64>,
+    pub min_trust: Option<f64>,
+    /// Include archived edges
+    #[graphql(default)]
+    pub include_archived: bool,
+}
+
+/// Hyperedge field filters
+#[derive(Debug, Clone, Default, InputObject)]
+pub struct HyperEdgeFilter {
+    pub categories: Option<Vec<String>>,
+    pub states: Option<Vec<String>>,
+    /// Entity key of a participant
+    pub participant: Option<String>,
+    /// Role names held by some participant
+    pub roles: Option<Vec<String>>,
+    pub tags: Option<Vec<String>>,
+    pub name_contains: Option<String>,
+    pub min_strength: Option<f64>,
+    #[graphql(default)]
+    pub include_archived: bool,
+}
+
+/// Ego network traversal filter
+#[derive(Debug, Clone, InputObject)]
+pub struct EgoFilterInput {
+    /// Only traverse these categories
+    pub categories: Option<Vec<String>>,
+    #[graphql(default)]
+    pub include_inactive: bool,
+    pub min_strength: Option<f64>,
+    #[graphql(default = true)]
+    pub include_hyperedges: bool,
+}
+
+impl EdgeFilter {
+    fn matches(&self, edge: &EdgeConcept, keys: &FilterKeys) -> bool {
+        (self.include_archived || !edge.is_archived())
+            && names_match(&self.categories, &edge.category.display_name())
+            && names_match(&self.states, edge.state.name())
+            && keys
+                .entity
+                .as_ref()
+                .is_none_or(|k| *k == edge.source.key() || *k == edge.target.key())
+            && keys.source.as_ref().is_none_or(|k| *k == edge.source.key())
+            && keys.target.as_ref().is_none_or(|k| *k == edge.target.key())
+            && tags_match(&self.tags, &edge.tags)
+            && contains(&self.name_contains, &edge.name)
+            && self
+                .min_strength
+                .is_none_or(|min| edge.quality.strength >= min)
+            && self.min_trust.is_none_or(|min| edge.quality.trust >= min)
+    }
+
+    fn keys(&self) -> Result<FilterKeys> {
+        Ok(FilterKeys {
+            entity: self.entity.as_deref().map(parse_key).transpose()?,
+            source: self.source.as_deref().map(parse_key).transpose()?,
+            target: self.target.as_deref().map(parse_key).transpose()?,
+        })
+    }
+}
+
+impl HyperEdgeFilter {
+    fn matches(&self, hyperedge: &HyperEdgeConcept, participant: Option<&EntityKey>) -> bool {
+        (self.include_archived || !hyperedge.is_archived())
+            && names_match(&self.categories, &hyperedge.category.display_name())
+            && names_match(&self.states, hyperedge.state.name())
+            && participant.is_none_or(|k| {
+                hyperedge
+                    .participants
+                    .participants()
+                    .any(|p| p.entity_ref.key() == *k)
+            })
+            && self.roles.as_ref().is_none_or(|roles| {
+                hyperedge
+                    .participants
+                    .participants()
+                    .any(|p| name_in(roles, &p.role.display_name()))
+            })
+            && tags_match(&self.tags, &hyperedge.tags)
+            && contains(&self.name_contains, &hyperedge.name)
+            && self
+                .min_strength
+                .is_none_or(|min| hyperedge.quality.strength >= min)
+    }
+}
+
+impl EgoFilterInput {
+    fn to_filter(&self) -> EgoFilter {
+        EgoFilter {
+            categories: self
+                .categories
+                .iter()
+                .flatten()
+                .map(|name| RelationshipCategory::from_name(name))
+                .collect(),
+            include_inactive: self.include_inactive,
+            min_strength: self.min_strength,
+            include_hyperedges: self.include_hyperedges,
+        }
+    }
+}
+
+/// Entity keys of an edge filter, parsed once per query
+struct FilterKeys {
+    entity: Option<EntityKey>,
+    source: Option<EntityKey>,
+    target: Option<EntityKey>,
+}
+
+// ============================================================================
+// Query Root
+// ============================================================================
+
+/// Root of every GraphQL query
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// A binary relationship by id
+    async fn edge(&self, ctx: &Context<'_>, id: Uuid) -> Result<Option<EdgeObject>> {
+        let read_model = ctx.data::<RelationshipReadModel>()?;
+        let (space, display) = (
+            read_model.space().read().await,
+            read_model.display().read().await,
+        );
+        Ok(space
+            .get_edge(&RelationshipId::from_uuid(id))
+            .map(|edge| edge_object(edge, &display)))
+    }
+
+    /// Binary relationships matching a filter, oldest first
+    async fn edges(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<EdgeFilter>,
+        limit: Option<usize>,
+        #[graphql(default)] offset: usize,
+    ) -> Result<Vec<EdgeObject>> {
+        let filter = filter.unwrap_or_default();
+        let keys = filter.keys()?;
+        let read_model = ctx.data::<RelationshipReadModel>()?;
+        let (space, display) = (
+            read_model.space().read().await,
+            read_model.display().read().await,
+        );
+        let mut edges: Vec<&EdgeConcept> = space
+            .edges
+            .values()
+            .filter(|e| filter.matches(e, &keys))
+            .collect();
+        edges.sort_by_key(|e| (e.created_at, e.id.as_uuid()));
+        Ok(page(edges, offset, limit)
+            .map(|edge| edge_object(edge, &display))
+            .collect())
+    }
+
+    /// An n-ary relationship by id
+    async fn hyperedge(&self, ctx: &Context<'_>, id: Uuid) -> Result<Option<HyperEdgeObject>> {
+        let read_model = ctx.data::<RelationshipReadModel>()?;
+        let (space, display) = (
+            read_model.space().read().await,
+            read_model.display().read().await,
+        );
+        Ok(space
+            .get_hyperedge(&RelationshipId::from_uuid(id))
+            .map(|hyperedge| hyperedge_object(hyperedge, &display)))
+    }
+
+    /// N-ary relationships matching a filter, oldest first
+    async fn hyperedges(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<HyperEdgeFilter>,
+        limit: Option<usize>,
+        #[graphql(default)] offset: usize,
+    ) -> Result<Vec<HyperEdgeObject>> {
+        let filter = filter.unwrap_or_default();
+        let participant = filter.participant.as_deref().map(parse_key).transpose()?;
+        let read_model = ctx.data::<RelationshipReadModel>()?;
+        let (space, display) = (
+            read_model.space().read().await,
+            read_model.display().read().await,
+        );
+        let mut hyperedges: Vec<&HyperEdgeConcept> = space
+            .hyperedges
+            .values()
+            .filter(|h| filter.matches(h, participant.as_ref()))
+            .collect();
+        hyperedges.sort_by_key(|h| (h.created_at, h.id.as_uuid()));
+        Ok(page(hyperedges, offset, limit)
+            .map(|hyperedge| hyperedge_object(hyperedge, &display))
+            .collect())
+    }
+
+    /// Relationships within `radius` hops of an entity
+    async fn ego_network(
+        &self,
+        ctx: &Context<'_>,
+        entity: String,
+        #[graphql(default = 1)] radius: usize,
+        filter: Option<EgoFilterInput>,
+    ) -> Result<EgoNetworkObject> {
+        let key = parse_key(&entity)?;
+        let center = EntityRef::new(key.entity_type, key.entity_id);
+        let read_model = ctx.data::<RelationshipReadModel>()?;
+        let (space, display) = (
+            read_model.space().read().await,
+            read_model.display().read().await,
+        );
+        let filter = filter.map(|f| f.to_filter()).unwrap_or_default();
+        let network = space.ego_network(&center, radius, &filter);
+        Ok(EgoNetworkObject {
+            center: entity_object(&network.center, &display),
+            radius: network.radius,
+            nodes: network
+                .nodes
+                .iter()
+                .map(|node| EgoNodeObject {
+                    entity: entity_object(&node.entity, &display),
+                    distance: node.distance,
+                })
+                .collect(),
+            edges: network
+                .edges
+                .iter()
+                .map(|e| edge_object(e, &display))
+                .collect(),
+            hyperedges: network
+                .hyperedges
+                .iter()
+                .map(|h| hyperedge_object(h, &display))
+                .collect(),
+        })
+    }
+
+    /// Edges within a category-weighted quality distance of an edge, most
+    /// similar first
+    async fn similar_edges(
+        &self,
+        ctx: &Context<'_>,
+        edge_id: Uuid,
+        #[graphql(default = 0.5)] max_distance: f64,
+        limit: Option<usize>,
+    ) -> Result<Vec<SimilarEdgeObject>> {
+        let read_model = ctx.data::<RelationshipReadModel>()?;
+        let (space, display) = (
+            read_model.space().read().await,
+            read_model.display().read().await,
+        );
+        let edge_id = RelationshipId::from_uuid(edge_id);
+        let Some(edge) = space.get_edge(&edge_id) else {
+            return Ok(Vec::new());
+        };
+        let mut similar: Vec<(&EdgeConcept, f64)> = space
+            .find_similar_to(&edge_id, max_distance)?
+            .into_iter()
+            .map(|other| (other, edge.similarity(other)))
+            .collect();
+        similar.sort_by(|a, b| b.1.total_cmp(&a.1));
+        Ok(page(similar, 0, limit)
+            .map(|(other, similarity)| SimilarEdgeObject {
+                edge: edge_object(other, &display),
+                similarity,
+            })
+            .collect())
+    }
+}
+
+// ============================================================================
+// Conversions
+// ============================================================================
+
+fn entity_object(entity: &EntityRef, display: &EntityDisplayCache) -> EntityObject {
+    let key = entity.key().to_string();
+    EntityObject {
+        entity_type: key
+            .rsplit_once(':')
+            .map(|(t, _)| t.to_string())
+            .unwrap_or_default(),
+        key,
+        id: entity.entity_id,
+        label: display.label(entity),
+    }
+}
+
+fn quality_object(quality: &RelationshipQuality) -> QualityObject {
+    QualityObject {
+        strength: quality.strength,
+        trust: quality.trust,
+        formality: format!("{:?}", quality.formality),
+        reciprocity: quality.reciprocity,
+        starts_at: quality.duration.starts_at,
+        ends_at: quality.duration.ends_at,
+    }
+}
+
+fn edge_object(edge: &EdgeConcept, display: &EntityDisplayCache) -> EdgeObject {
+    EdgeObject {
+        id: edge.id.as_uuid(),
+        name: edge.name.clone(),
+        description: edge.description.clone(),
+        source: entity_object(&edge.source, display),
+        target: entity_object(&edge.target, display),
+        category: edge.category.display_name(),
+        state: edge.state.name().to_string(),
+        quality: quality_object(&edge.quality),
+        confidence: edge.confidence,
+        tags: edge.tags.iter().cloned().collect(),
+        version: edge.version,
+        created_at: edge.created_at,
+        updated_at: edge.updated_at,
+    }
+}
+
+fn hyperedge_object(hyperedge: &HyperEdgeConcept, display: &EntityDisplayCache) -> HyperEdgeObject {
+    HyperEdgeObject {
+        id: hyperedge.id.as_uuid(),
+        name: hyperedge.name.clone(),
+        description: hyperedge.description.clone(),
+        category: hyperedge.category.display_name(),
+        state: hyperedge.state.name().to_string(),
+        participants: hyperedge
+            .participants
+            .participants()
+            .map(|p| ParticipantObject {
+                entity: entity_object(&p.entity_ref, display),
+                role: p.role.display_name(),
+                weight: p.weight,
+            })
+            .collect(),
+        quality: quality_object(&hyperedge.quality),
+        confidence: hyperedge.confidence,
+        tags: hyperedge.tags.iter().cloned().collect(),
+        created_at: hyperedge.created_at,
+        updated_at: hyperedge.updated_at,
+    }
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn parse_key(key: &str) -> Result<EntityKey> {
+    key.parse::<EntityKey>().map_err(async_graphql::Error::new)
+}
+
+fn names_match(names: &Option<Vec<String>>, name: &str) -> bool {
+    names.as_ref().is_none_or(|names| name_in(names, name))
+}
+
+fn name_in(names: &[String], name: &str) -> bool {
+    names.iter().any(|n| n.eq_ignore_ascii_case(name))
+}
+
+fn tags_match(tags: &Option<Vec<String>>, present: &BTreeSet<String>) -> bool {
+    tags.as_ref().is_none_or(|tags| {
+        tags.iter()
+            .all(|tag| present.iter().any(|p| p.eq_ignore_ascii_case(tag.trim())))
+    })
+}
+
+fn contains(needle: &Option<String>, haystack: &str) -> bool {
+    needle
+        .as_ref()
+        .is_none_or(|needle| haystack.to_lowercase().contains(&needle.to_lowercase()))
+}
+
+fn page<T>(items: Vec<T>, offset: usize, limit: Option<usize>) -> impl Iterator<Item = T> {
+    items
+        .into_iter()
+        .skip(offset)
+        .take(limit.unwrap_or(usize::MAX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregates::RelationshipSpace;
+    use cim_domain_spaces::TopologicalSpaceId;
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+
+    #[tokio::test]
+    async fn test_filtered_edges_and_ego_network() {
+        let mut space = RelationshipSpace::new("GraphQL", TopologicalSpaceId::new());
+        let alice = EntityRef::person(Uuid::now_v7());
+        let acme = EntityRef::organization(Uuid::now_v7());
+        space
+            .add_edge(EdgeConcept::new(
+                "Alice at Acme",
+                alice.clone(),
+                acme.clone(),
+                RelationshipCategory::Employment,
+            ))
+            .unwrap();
+        space
+            .add_edge(EdgeConcept::new(
+                "Alice knows Bob",
+                alice.clone(),
+                EntityRef::person(Uuid::now_v7()),
+                RelationshipCategory::Friendship,
+            ))
+            .unwrap();
+        let read_model = RelationshipReadModel::new(Arc::new(RwLock::new(space)));
+        read_model
+            .display()
+            .write()
+            .await
+            .set_name(&acme, "Acme Corp", Utc::now());
+        let schema = schema(read_model);
+
+        let query = format!(
+            r#"{{ edges(filter: {{ categories: ["Employment"], entity: "{}" }}) {{ name target {{ label }} }} }}"#,
+            alice.key()
+        );
+        let response = schema.execute(query).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert_eq!(data["edges"].as_array().unwrap().len(), 1);
+        assert_eq!(data["edges"][0]["target"]["label"], "Acme Corp");
+
+        // Proposed edges are only traversed when inactive ones are included
+        let query = format!(
+            r#"{{ egoNetwork(entity: "{}", filter: {{ includeInactive: true }}) {{ nodes {{ distance }} edges {{ name }} }} }}"#,
+            alice.key()
+        );
+        let data = schema.execute(query).await.data.into_json().unwrap();
+        assert_eq!(data["egoNetwork"]["edges"].as_array().unwrap().len(), 2);
+
+        let response = schema
+            .execute(r#"{ edges(filter: { entity: "nonsense" }) { name } }"#)
+            .await;
+        assert!(!response.errors.is_empty());
+    }
+}
@@ -0,0 +1,492 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! gRPC API (`grpc` feature)
+//!
+//! A tonic service mirroring the NATS API for environments that
+//! standardize on gRPC, defined in `proto/relationship_service.proto`:
+//!
+//! ```text
+//! CreateEdge    --> relationship.commands.create_edge  (RelationshipBus)
+//! GetEdge       --> v1::Query::GetEdge                 (RelationshipReadModel)
+//! ListByEntity  --> v1::Query::RelationshipsOf         (RelationshipReadModel)
+//! StreamEvents  <-- relationship.events.>              (RelationshipBus)
+//! ```
+//!
+//! Messages, client and server are generated from the proto files by
+//! tonic-build (see `build.rs`); this module implements the server side.
+//!
+//! ```rust,ignore
+//! let service = RelationshipGrpc::new(bus, read_model);
+//! tonic::transport::Server::builder()
+//!     .add_service(RelationshipServiceServer::new(service))
+//!     .serve(addr)
+//!     .await?;
+//! ```
+
+use super::v1::{self, EdgeDto, HyperEdgeDto, QualityDto, QueryResponse};
+use crate::commands::{EdgeCommand, RelationshipCommand};
+use crate::events::RelationshipEvent;
+use crate::nats::protobuf::proto;
+use crate::nats::{RelationshipBus, Transport};
+use crate::projections::RelationshipReadModel;
+use crate::value_objects::{EntityKey, EntityRef, Formality, RelationshipCategory};
+use crate::{RelationshipError, RelationshipResult};
+use chrono::{DateTime, SecondsFormat, Utc};
+use futures::{Stream, StreamExt};
+use std::pin::Pin;
+use tonic::{Request, Response, Status};
+use uuid::Uuid;
+
+pub use proto::relationship_service_client::RelationshipServiceClient;
+pub use proto::relationship_service_server::{RelationshipService, RelationshipServiceServer};
+pub use proto::{
+    CreateEdgeRequest, CreateEdgeResponse, Edge, Event, GetEdgeRequest, HyperEdge, ListByEntityRequest,
+    ListByEntityResponse, Participant, Quality, StreamEventsRequest,
+};
+
+/// Fully qualified service name
+pub const SERVICE_NAME: &str = "cim.relationship.v1.RelationshipService";
+
+impl Event {
+    /// The relationship event carried in the message
+    pub fn to_event(&self) -> RelationshipResult<RelationshipEvent> {
+        let event = self
+            .event
+            .clone()
+            .ok_or_else(|| RelationshipError::SerializationError("missing event".to_string()))?;
+        RelationshipEvent::try_from(event)
+    }
+}
+
+impl From<&RelationshipEvent> for Event {
+    fn from(event: &RelationshipEvent) -> Self {
+        Self {
+            event_type: event.event_type().to_string(),
+            relationship_id: event.relationship_id().as_uuid().to_string(),
+            event: Some(event.into()),
+        }
+    }
+}
+
+// ============================================================================
+// Conversions
+// ============================================================================
+
+impl From<&QualityDto> for Quality {
+    fn from(dto: &QualityDto) -> Self {
+        Self {
+            strength: dto.strength,
+            trust: dto.trust,
+            formality: format!("{:?}", dto.formality),
+            reciprocity: dto.reciprocity,
+            starts_at: timestamp(&dto.starts_at),
+            ends_at: dto.ends_at.as_ref().map(timestamp),
+        }
+    }
+}
+
+impl TryFrom<&Quality> for QualityDto {
+    type Error = String;
+
+    fn try_from(quality: &Quality) -> Result<Self, String> {
+        Ok(Self {
+            strength: quality.strength,
+            trust: quality.trust,
+            formality: Formality::from_name(&quality.formality)
+                .ok_or_else(|| format!("unknown formality {:?}", quality.formality))?,
+            reciprocity: quality.reciprocity,
+            starts_at: parse_timestamp(&quality.starts_at)?,
+            ends_at: quality
+                .ends_at
+                .as_deref()
+                .map(parse_timestamp)
+                .transpose()?,
+        })
+    }
+}
+
+impl From<&EdgeDto> for Edge {
+    fn from(dto: &EdgeDto) -> Self {
+        Self {
+            id: dto.id.to_string(),
+            name: dto.name.clone(),
+            source: dto.source.key().to_string(),
+            target: dto.target.key().to_string(),
+            category: dto.category.display_name(),
+            state: dto.state.clone(),
+            quality: Some(Quality::from(&dto.quality)),
+            confidence: dto.confidence,
+            created_at: timestamp(&dto.created_at),
+            updated_at: timestamp(&dto.updated_at),
+        }
+    }
+}
+
+impl From<&HyperEdgeDto> for HyperEdge {
+    fn from(dto: &HyperEdgeDto) -> Self {
+        Self {
+            id: dto.id.to_string(),
+            name: dto.name.clone(),
+            category: dto.category.display_name(),
+            state: dto.state.clone(),
+            participants: dto
+                .participants
+                .iter()
+                .map(|p| Participant {
+                    entity: p.entity.key().to_string(),
+                    role: p.role.display_name(),
+                    weight: p.weight,
+                })
+                .collect(),
+            quality: Some(Quality::from(&dto.quality)),
+            confidence: dto.confidence,
+            created_at: timestamp(&dto.created_at),
+            updated_at: timestamp(&dto.updated_at),
+        }
+    }
+}
+
+// ============================================================================
+// Service
+// ============================================================================
+
+/// Stream of events returned by `StreamEvents`
+pub type EventResultStream = Pin<Box<dyn Stream<Item = Result<Event, Status>> + Send>>;
+
+/// The relationship service over a bus and a read model
+pub struct RelationshipGrpc<T: Transport> {
+    bus: RelationshipBus<T>,
+    read_model: RelationshipReadModel,
+}
+
+impl<T: Transport> RelationshipGrpc<T> {
+    /// Actor recorded when a request names none
+    pub const DEFAULT_ACTOR: &'static str = "grpc";
+
+    /// Serve commands over `bus` and reads from `read_model`
+    pub fn new(bus: RelationshipBus<T>, read_model: RelationshipReadModel) -> Self {
+        Self { bus, read_model }
+    }
+}
+
+#[tonic::async_trait]
+impl<T: Transport + 'static> RelationshipService for RelationshipGrpc<T> {
+    async fn create_edge(
+        &self,
+        request: Request<CreateEdgeRequest>,
+    ) -> Result<Response<CreateEdgeResponse>, Status> {
+        let request = request.into_inner();
+        let command = v1::Command::CreateEdge {
+            source: parse_entity(&request.source).map_err(Status::invalid_argument)?,
+            target: parse_entity(&request.target).map_err(Status::invalid_argument)?,
+            category: RelationshipCategory::from_name(&request.category),
+            name: request.name,
+            quality: request
+                .quality
+                .as_ref()
+                .map(QualityDto::try_from)
+                .transpose()
+                .map_err(Status::invalid_argument)?,
+        };
+        let actor = match request.actor.as_str() {
+            "" => Self::DEFAULT_ACTOR.to_string(),
+            actor => actor.to_string(),
+        };
+        let command = command.into_internal(actor);
+        let RelationshipCommand::Edge(EdgeCommand::CreateEdge(create)) = &command else {
+            return Err(Status::internal(
+                "v1 CreateEdge is not a create_edge command",
+            ));
+        };
+        let edge_id = create.edge_id.as_uuid().to_string();
+
+        let response = self.bus.send_command(&command).await.map_err(status)?;
+        Ok(Response::new(CreateEdgeResponse {
+            accepted: response.accepted,
+            edge_id,
+            error: response.error,
+            events: response.events.iter().map(Event::from).collect(),
+        }))
+    }
+
+    async fn get_edge(&self, request: Request<GetEdgeRequest>) -> Result<Response<Edge>, Status> {
+        let edge_id = parse_uuid(&request.get_ref().edge_id).map_err(Status::invalid_argument)?;
+        let query = v1::Query::GetEdge { edge_id };
+        match query.execute(&*self.read_model.space().read().await) {
+            QueryResponse::Edge { edge: Some(edge) } => Ok(Response::new(Edge::from(&edge))),
+            _ => Err(Status::not_found(format!("edge {}", edge_id))),
+        }
+    }
+
+    async fn list_by_entity(
+        &self,
+        request: Request<ListByEntityRequest>,
+    ) -> Result<Response<ListByEntityResponse>, Status> {
+        let request = request.into_inner();
+        let query = v1::Query::RelationshipsOf {
+            entity: parse_entity(&request.entity).map_err(Status::invalid_argument)?,
+            active_only: request.active_only,
+            include_archived: request.include_archived,
+        };
+        match query.execute(&*self.read_model.space().read().await) {
+            QueryResponse::Relationships { edges, hyperedges } => {
+                Ok(Response::new(ListByEntityResponse {
+                    edges: edges.iter().map(Edge::from).collect(),
+                    hyperedges: hyperedges.iter().map(HyperEdge::from).collect(),
+                }))
+            }
+            _ => Err(Status::internal(
+                "RelationshipsOf answered with another result",
+            )),
+        }
+    }
+
+    type StreamEventsStream = EventResultStream;
+
+    async fn stream_events(
+        &self,
+        request: Request<StreamEventsRequest>,
+    ) -> Result<Response<Self::StreamEventsStream>, Status> {
+        let StreamEventsRequest {
+            event_types,
+            relationship_id,
+        } = request.into_inner();
+        let relationship_id = match relationship_id.as_str() {
+            "" => None,
+            id => Some(parse_uuid(id).map_err(Status::invalid_argument)?),
+        };
+        let events = self.bus.subscribe_events().await.map_err(status)?;
+        let events = events
+            .filter(move |event| {
+                let wanted = (event_types.is_empty()
+                    || event_types.iter().any(|t| t == event.event_type()))
+                    && relationship_id.is_none_or(|id| event.relationship_id().as_uuid() == id);
+                futures::future::ready(wanted)
+            })
+            .map(|event| Event::from(&event))
+            .map(Ok);
+        Ok(Response::new(Box::pin(events)))
+    }
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn parse_entity(key: &str) -> Result<EntityRef, String> {
+    let key: EntityKey = key.parse()?;
+    Ok(EntityRef::new(key.entity_type, key.entity_id))
+}
+
+fn parse_uuid(id: &str) -> Result<Uuid, String> {
+    Uuid::parse_str(id).map_err(|e| format!("{}: {}", id, e))
+}
+
+fn timestamp(at: &DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+}
+
+fn parse_timestamp(at: &str) -> Result<DateTime<Utc>, String> {
+    DateTime::parse_from_rfc3339(at)
+        .map(|t| t.with_timezone(&Utc))
+        .map_err(|e| format!("{}: {}", at, e))
+}
+
+/// gRPC status of a domain error
+fn status(error: RelationshipError) -> Status {
+    match error {
+        RelationshipError::EntityNotFound(_) => Status::not_found(error.to_string()),
+        RelationshipError::TransportError(_) => Status::unavailable(error.to_string()),
+        RelationshipError::SerializationError(_) => Status::internal(error.to_string()),
+        _ => Status::failed_precondition(error.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregates::RelationshipSpace;
+    use crate::events::{EdgeCreated, EdgeEvent};
+    use crate::nats::{decode, encode, CommandResponse, MockTransport, RelationshipSubjects};
+    use cim_domain::MessageIdentity;
+    use cim_domain_spaces::{ConceptId, TopologicalSpaceId};
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+
+    fn read_model() -> RelationshipReadModel {
+        RelationshipReadModel::new(Arc::new(RwLock::new(RelationshipSpace::new(
+            "gRPC",
+            TopologicalSpaceId::new(),
+        ))))
+    }
+
+    #[tokio::test]
+    async fn test_create_get_and_list() {
+        let transport = MockTransport::new();
+        let bus = RelationshipBus::new(transport.clone());
+        let read_model = read_model();
+        let service = RelationshipGrpc::new(bus.clone(), read_model.clone());
+        let mut stream = service
+            .stream_events(Request::new(StreamEventsRequest::default()))
+            .await
+            .unwrap()
+            .into_inner();
+
+        // Stand-in command handler: accept, publish and project EdgeCreated
+        let (handled, mut created) = tokio::sync::mpsc::unbounded_channel();
+        transport.on_request(RelationshipSubjects::all_commands(), move |message| {
+            let RelationshipCommand::Edge(EdgeCommand::CreateEdge(create)) =
+                decode::<RelationshipCommand>(&message.payload)?
+            else {
+                return encode(&CommandResponse::rejected("unsupported"));
+            };
+            let event = RelationshipEvent::Edge(EdgeEvent::EdgeCreated(EdgeCreated {
+                event_id: Uuid::now_v7(),
+                identity: MessageIdentity::new_root(),
+                edge_id: create.edge_id,
+                concept_id: ConceptId::new(),
+                source: create.source,
+                target: create.target,
+                category: create.category,
+                name: create.name,
+                created_by: create.created_by,
+                created_at: Utc::now(),
+            }));
+            let _ = handled.send(event.clone());
+            encode(&CommandResponse::accepted(vec![event]))
+        });
+
+        let alice = EntityRef::person(Uuid::now_v7()).key().to_string();
+        let response = service
+            .create_edge(Request::new(CreateEdgeRequest {
+                source: alice.clone(),
+                target: EntityRef::organization(Uuid::now_v7()).key().to_string(),
+                category: "Employment".to_string(),
+                name: "Alice at Acme".to_string(),
+                quality: None,
+                actor: String::new(),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(response.accepted);
+        assert_eq!(response.events[0].event_type, "edge_created");
+
+        let event = created.recv().await.unwrap();
+        read_model.apply(&event).await.unwrap();
+        bus.publish_event(&event).await.unwrap();
+        let streamed = stream.next().await.unwrap().unwrap();
+        assert_eq!(streamed.relationship_id, response.edge_id);
+        assert_eq!(streamed.to_event().unwrap().event_type(), "edge_created");
+
+        let edge = service
+            .get_edge(Request::new(GetEdgeRequest {
+                edge_id: response.edge_id.clone(),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(edge.category, "employment");
+        assert_eq!(edge.source, alice);
+
+        let listed = service
+            .list_by_entity(Request::new(ListByEntityRequest {
+                entity: alice,
+                active_only: false,
+                include_archived: false,
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(listed.edges.len(), 1);
+
+        let missing = service
+            .get_edge(Request::new(GetEdgeRequest {
+                edge_id: Uuid::now_v7().to_string(),
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(missing.code(), tonic::Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_requests_routed_over_http2() {
+        let transport = MockTransport::new();
+        let bus = RelationshipBus::new(transport.clone());
+        transport.on_request(RelationshipSubjects::all_commands(), |_| {
+            encode(&CommandResponse::rejected("read only"))
+        });
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let incoming = tonic::transport::server::TcpIncoming::from_listener(listener, true, None).unwrap();
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(RelationshipServiceServer::new(RelationshipGrpc::new(bus.clone(), read_model())))
+                .serve_with_incoming(incoming),
+        );
+        let mut client = RelationshipServiceClient::connect(format!("http://{}", addr)).await.unwrap();
+
+        let alice = EntityRef::person(Uuid::now_v7()).key().to_string();
+        let created = client
+            .create_edge(CreateEdgeRequest {
+                source: alice.clone(),
+                target: EntityRef::organization(Uuid::now_v7()).key().to_string(),
+                category: "employment".to_string(),
+                name: "Alice at Acme".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(created.error.as_deref(), Some("read only"));
+        assert_eq!(transport.published_on("relationship.commands.create_edge").len(), 1);
+
+        let missing = client
+            .get_edge(GetEdgeRequest {
+                edge_id: created.edge_id,
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(missing.code(), tonic::Code::NotFound);
+        let malformed = client
+            .get_edge(GetEdgeRequest {
+                edge_id: "edge".to_string(),
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(malformed.code(), tonic::Code::InvalidArgument);
+
+        let listed = client
+            .list_by_entity(ListByEntityRequest {
+                entity: alice,
+                ..Default::default()
+            })
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(listed.edges.is_empty());
+
+        let mut stream = client
+            .stream_events(StreamEventsRequest::default())
+            .await
+            .unwrap()
+            .into_inner();
+        let event = RelationshipEvent::Edge(EdgeEvent::EdgeCreated(EdgeCreated {
+            event_id: Uuid::now_v7(),
+            identity: MessageIdentity::new_root(),
+            edge_id: crate::value_objects::RelationshipId::new(),
+            concept_id: ConceptId::new(),
+            source: EntityRef::person(Uuid::now_v7()),
+            target: EntityRef::person(Uuid::now_v7()),
+            category: RelationshipCategory::Friendship,
+            name: "Knows".to_string(),
+            created_by: "test".to_string(),
+            created_at: Utc::now(),
+        }));
+        bus.publish_event(&event).await.unwrap();
+        let streamed = stream.message().await.unwrap().unwrap();
+        assert_eq!(streamed.to_event().unwrap().event_type(), "edge_created");
+    }
+}
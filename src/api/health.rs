@@ -0,0 +1,83 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Health Endpoints (`health` feature)
+//!
+//! The `HealthMonitor` probes over HTTP, for orchestrators that cannot ask
+//! NATS:
+//!
+//! ```text
+//! GET /livez     Probe::Liveness
+//! GET /readyz    Probe::Readiness
+//! ```
+//!
+//! Both answer the `HealthReport`, with 200 when healthy and 503 otherwise.
+
+use crate::nats::{HealthMonitor, Probe, Transport};
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use std::sync::Arc;
+
+/// Routes answering the probes
+pub fn router<T: Transport + 'static>(monitor: Arc<HealthMonitor<T>>) -> Router {
+    Router::new()
+        .route("/livez", get(live::<T>))
+        .route("/readyz", get(ready::<T>))
+        .with_state(monitor)
+}
+
+async fn live<T: Transport + 'static>(State(monitor): State<Arc<HealthMonitor<T>>>) -> Response {
+    respond(&monitor, Probe::Liveness).await
+}
+
+async fn ready<T: Transport + 'static>(State(monitor): State<Arc<HealthMonitor<T>>>) -> Response {
+    respond(&monitor, Probe::Readiness).await
+}
+
+async fn respond<T: Transport>(monitor: &HealthMonitor<T>, probe: Probe) -> Response {
+    let report = monitor.check(probe).await;
+    let status = if report.healthy {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(report)).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nats::{HealthReport, MockTransport, ProjectionProgress};
+    use axum::body::{to_bytes, Body};
+    use axum::http::Request;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_probes_over_http() {
+        let progress = ProjectionProgress::new();
+        progress.expect("space");
+        let app = router(Arc::new(
+            HealthMonitor::new(MockTransport::new()).with_progress(progress.clone()),
+        ));
+        let get = |path: &str| Request::get(path).body(Body::empty()).unwrap();
+
+        let live = app.clone().oneshot(get("/livez")).await.unwrap();
+        assert_eq!(live.status(), StatusCode::OK);
+
+        let ready = app.clone().oneshot(get("/readyz")).await.unwrap();
+        assert_eq!(ready.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let report: HealthReport =
+            serde_json::from_slice(&to_bytes(ready.into_body(), usize::MAX).await.unwrap())
+                .unwrap();
+        assert_eq!(report.probe, Probe::Readiness);
+        assert!(!report.healthy);
+
+        progress.mark_live("space");
+        let ready = app.oneshot(get("/readyz")).await.unwrap();
+        assert_eq!(ready.status(), StatusCode::OK);
+    }
+}
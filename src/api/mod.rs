@@ -0,0 +1,35 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Versioned Public API
+//!
+//! Stable facades over the relationship domain for downstream consumers.
+//! Each version exposes its own commands, queries, and DTOs, and converts
+//! them to and from the internal aggregate, command, and query types.
+//!
+//! ```text
+//! consumer --> api::v1::Command --> RelationshipCommand --> aggregates
+//! consumer <-- api::v1::EdgeDto <-- EdgeConcept
+//! ```
+//!
+//! Internal types may change freely; a versioned module only changes in
+//! backwards-compatible ways. Breaking changes go in a new version.
+//!
+//! Transport front ends, each behind its feature:
+//!
+//! - **graphql**: reads over the `RelationshipReadModel` as a GraphQL schema
+//! - **grpc**: a tonic service mirroring the NATS API
+//!   (`proto/relationship_service.proto`)
+//! - **rest**: an HTTP/JSON gateway with a generated OpenAPI document
+//! - **health**: liveness and readiness probes over HTTP
+
+#[cfg(feature = "graphql")]
+pub mod graphql;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+#[cfg(feature = "health")]
+pub mod health;
+#[cfg(feature = "rest")]
+pub mod rest;
+pub mod v1;
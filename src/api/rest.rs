@@ -0,0 +1,709 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! REST Gateway (`rest` feature)
+//!
+//! The v1 API over HTTP/JSON, for scripts and curl. Commands are sent over
+//! the `RelationshipBus`; reads are answered from the
+//! `RelationshipReadModel`.
+//!
+//! ```text
+//! POST   /v1/edges                                  CreateEdge
+//! GET    /v1/edges/{id}                             GetEdge
+//! POST   /v1/edges/{id}/activate                    ActivateEdge
+//! POST   /v1/edges/{id}/suspend                     SuspendEdge
+//! POST   /v1/edges/{id}/resume                      ResumeEdge
+//! DELETE /v1/edges/{id}?reason=                     TerminateEdge
+//! POST   /v1/hyperedges                             CreateHyperEdge
+//! GET    /v1/hyperedges/{id}                        GetHyperEdge
+//! POST   /v1/hyperedges/{id}/activate               ActivateHyperEdge
+//! POST   /v1/hyperedges/{id}/participants           AddParticipant
+//! DELETE /v1/hyperedges/{id}/participants/{entity}  RemoveParticipant
+//! DELETE /v1/hyperedges/{id}?reason=                TerminateHyperEdge
+//! GET    /v1/entities/{entity}/relationships        RelationshipsOf
+//! POST   /v1/commands                               any v1::Command
+//! POST   /v1/queries                                any v1::Query
+//! GET    /openapi.json                              OpenAPI 3.0 document
+//! ```
+//!
+//! Entities in paths are EntityKeys (`person:{uuid}`). The acting user is
+//! taken from the `X-Actor` header. Accepted commands answer 200 (201 with
+//! a `Location` for creates, also through `/v1/commands`), rejected ones
+//! 422, both with the `CommandResponse`. Malformed ids, query strings and
+//! bodies answer 400 with an `ErrorBody`.
+
+use super::v1::{self, EdgeDto, HyperEdgeDto, ParticipantDto, QualityDto, QueryResponse};
+use crate::commands::{EdgeCommand, HyperEdgeCommand, RelationshipCommand};
+use crate::nats::{CommandResponse, RelationshipBus, Transport};
+use crate::projections::RelationshipReadModel;
+use crate::value_objects::{EntityKey, EntityRef, RelationshipCategory};
+use crate::RelationshipError;
+use axum::extract::rejection::{JsonRejection, PathRejection, QueryRejection};
+use axum::extract::{Path, Query, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{delete, get, post};
+use axum::{Json, Router};
+use schemars::gen::{SchemaGenerator, SchemaSettings};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Header naming the acting user
+pub const ACTOR_HEADER: &str = "X-Actor";
+
+/// Actor recorded when a request names none
+pub const DEFAULT_ACTOR: &str = "http";
+
+// ============================================================================
+// Request Bodies
+// ============================================================================
+
+/// Body of `POST /v1/edges`
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct NewEdge {
+    pub source: EntityRef,
+    pub target: EntityRef,
+    pub category: RelationshipCategory,
+    pub name: String,
+    #[serde(default)]
+    pub quality: Option<QualityDto>,
+}
+
+/// Body of `POST /v1/hyperedges`
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct NewHyperEdge {
+    pub name: String,
+    pub category: RelationshipCategory,
+    pub participants: Vec<ParticipantDto>,
+}
+
+/// Optional body of `POST /v1/edges/{id}/suspend`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct Suspension {
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// `?reason=` of terminating and removing requests
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Reason {
+    pub reason: String,
+}
+
+/// `?active_only=&include_archived=` of `/v1/entities/{entity}/relationships`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct RelationshipsFilter {
+    #[serde(default)]
+    pub active_only: bool,
+    #[serde(default)]
+    pub include_archived: bool,
+}
+
+/// Error body
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ErrorBody {
+    pub error: String,
+}
+
+struct ApiError(StatusCode, String);
+
+impl ApiError {
+    fn bad_request(error: impl Into<String>) -> Self {
+        Self(StatusCode::BAD_REQUEST, error.into())
+    }
+
+    fn not_found(error: impl Into<String>) -> Self {
+        Self(StatusCode::NOT_FOUND, error.into())
+    }
+}
+
+impl From<RelationshipError> for ApiError {
+    fn from(error: RelationshipError) -> Self {
+        let status = match error {
+            RelationshipError::EntityNotFound(_) => StatusCode::NOT_FOUND,
+            RelationshipError::TransportError(_) => StatusCode::SERVICE_UNAVAILABLE,
+            RelationshipError::SerializationError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            _ => StatusCode::UNPROCESSABLE_ENTITY,
+        };
+        Self(status, error.to_string())
+    }
+}
+
+/// Rejections of malformed requests, which axum would answer in plain text
+macro_rules! rejections {
+    ($($rejection:ty),* $(,)?) => {$(
+        impl From<$rejection> for ApiError {
+            fn from(rejection: $rejection) -> Self {
+                Self::bad_request(rejection.body_text())
+            }
+        }
+    )*};
+}
+
+rejections!(JsonRejection, PathRejection, QueryRejection);
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(ErrorBody { error: self.1 })).into_response()
+    }
+}
+
+// ============================================================================
+// Router
+// ============================================================================
+
+struct Gateway<T: Transport> {
+    bus: RelationshipBus<T>,
+    read_model: RelationshipReadModel,
+}
+
+type Shared<T> = State<Arc<Gateway<T>>>;
+
+/// Routes of the gateway, commands over `bus` and reads from `read_model`
+pub fn router<T: Transport + 'static>(
+    bus: RelationshipBus<T>,
+    read_model: RelationshipReadModel,
+) -> Router {
+    Router::new()
+        .route("/v1/edges", post(create_edge::<T>))
+        .route(
+            "/v1/edges/{id}",
+            get(get_edge::<T>).delete(terminate_edge::<T>),
+        )
+        .route("/v1/edges/{id}/activate", post(activate_edge::<T>))
+        .route("/v1/edges/{id}/suspend", post(suspend_edge::<T>))
+        .route("/v1/edges/{id}/resume", post(resume_edge::<T>))
+        .route("/v1/hyperedges", post(create_hyperedge::<T>))
+        .route(
+            "/v1/hyperedges/{id}",
+            get(get_hyperedge::<T>).delete(terminate_hyperedge::<T>),
+        )
+        .route(
+            "/v1/hyperedges/{id}/activate",
+            post(activate_hyperedge::<T>),
+        )
+        .route(
+            "/v1/hyperedges/{id}/participants",
+            post(add_participant::<T>),
+        )
+        .route(
+            "/v1/hyperedges/{id}/participants/{entity}",
+            delete(remove_participant::<T>),
+        )
+        .route(
+            "/v1/entities/{entity}/relationships",
+            get(relationships_of::<T>),
+        )
+        .route("/v1/commands", post(command::<T>))
+        .route("/v1/queries", post(query::<T>))
+        .route("/openapi.json", get(|| async { Json(openapi()) }))
+        .with_state(Arc::new(Gateway { bus, read_model }))
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+async fn create_edge<T: Transport>(
+    State(gateway): Shared<T>,
+    headers: HeaderMap,
+    body: Result<Json<NewEdge>, JsonRejection>,
+) -> Result<Response, ApiError> {
+    let Json(body) = body?;
+    let command = v1::Command::CreateEdge {
+        source: body.source,
+        target: body.target,
+        category: body.category,
+        name: body.name,
+        quality: body.quality,
+    };
+    gateway.send(command, &headers).await
+}
+
+async fn get_edge<T: Transport>(
+    State(gateway): Shared<T>,
+    path: Result<Path<Uuid>, PathRejection>,
+) -> Result<Json<EdgeDto>, ApiError> {
+    let Path(id) = path?;
+    match gateway.read(v1::Query::GetEdge { edge_id: id }).await {
+        QueryResponse::Edge { edge: Some(edge) } => Ok(Json(edge)),
+        _ => Err(ApiError::not_found(format!("edge {}", id))),
+    }
+}
+
+async fn activate_edge<T: Transport>(
+    State(gateway): Shared<T>,
+    headers: HeaderMap,
+    path: Result<Path<Uuid>, PathRejection>,
+) -> Result<Response, ApiError> {
+    let Path(edge_id) = path?;
+    gateway
+        .send(v1::Command::ActivateEdge { edge_id }, &headers)
+        .await
+}
+
+async fn suspend_edge<T: Transport>(
+    State(gateway): Shared<T>,
+    headers: HeaderMap,
+    path: Result<Path<Uuid>, PathRejection>,
+    body: Result<Option<Json<Suspension>>, JsonRejection>,
+) -> Result<Response, ApiError> {
+    let Path(edge_id) = path?;
+    let reason = body?.and_then(|Json(s)| s.reason);
+    gateway
+        .send(v1::Command::SuspendEdge { edge_id, reason }, &headers)
+        .await
+}
+
+async fn resume_edge<T: Transport>(
+    State(gateway): Shared<T>,
+    headers: HeaderMap,
+    path: Result<Path<Uuid>, PathRejection>,
+) -> Result<Response, ApiError> {
+    let Path(edge_id) = path?;
+    gateway
+        .send(v1::Command::ResumeEdge { edge_id }, &headers)
+        .await
+}
+
+async fn terminate_edge<T: Transport>(
+    State(gateway): Shared<T>,
+    headers: HeaderMap,
+    path: Result<Path<Uuid>, PathRejection>,
+    params: Result<Query<Reason>, QueryRejection>,
+) -> Result<Response, ApiError> {
+    let Path(edge_id) = path?;
+    let Query(Reason { reason }) = params?;
+    gateway
+        .send(v1::Command::TerminateEdge { edge_id, reason }, &headers)
+        .await
+}
+
+async fn create_hyperedge<T: Transport>(
+    State(gateway): Shared<T>,
+    headers: HeaderMap,
+    body: Result<Json<NewHyperEdge>, JsonRejection>,
+) -> Result<Response, ApiError> {
+    let Json(body) = body?;
+    let command = v1::Command::CreateHyperEdge {
+        name: body.name,
+        category: body.category,
+        participants: body.participants,
+    };
+    gateway.send(command, &headers).await
+}
+
+async fn get_hyperedge<T: Transport>(
+    State(gateway): Shared<T>,
+    path: Result<Path<Uuid>, PathRejection>,
+) -> Result<Json<HyperEdgeDto>, ApiError> {
+    let Path(id) = path?;
+    match gateway
+        .read(v1::Query::GetHyperEdge { hyperedge_id: id })
+        .await
+    {
+        QueryResponse::HyperEdge {
+            hyperedge: Some(hyperedge),
+        } => Ok(Json(hyperedge)),
+        _ => Err(ApiError::not_found(format!("hyperedge {}", id))),
+    }
+}
+
+async fn activate_hyperedge<T: Transport>(
+    State(gateway): Shared<T>,
+    headers: HeaderMap,
+    path: Result<Path<Uuid>, PathRejection>,
+) -> Result<Response, ApiError> {
+    let Path(hyperedge_id) = path?;
+    gateway
+        .send(v1::Command::ActivateHyperEdge { hyperedge_id }, &headers)
+        .await
+}
+
+async fn add_participant<T: Transport>(
+    State(gateway): Shared<T>,
+    headers: HeaderMap,
+    path: Result<Path<Uuid>, PathRejection>,
+    participant: Result<Json<ParticipantDto>, JsonRejection>,
+) -> Result<Response, ApiError> {
+    let Path(hyperedge_id) = path?;
+    let Json(participant) = participant?;
+    let command = v1::Command::AddParticipant {
+        hyperedge_id,
+        participant,
+    };
+    gateway.send(command, &headers).await
+}
+
+async fn remove_participant<T: Transport>(
+    State(gateway): Shared<T>,
+    headers: HeaderMap,
+    path: Result<Path<(Uuid, String)>, PathRejection>,
+    params: Result<Query<Reason>, QueryRejection>,
+) -> Result<Response, ApiError> {
+    let Path((hyperedge_id, entity)) = path?;
+    let Query(Reason { reason }) = params?;
+    let command = v1::Command::RemoveParticipant {
+        hyperedge_id,
+        entity: parse_entity(&entity)?,
+        reason,
+    };
+    gateway.send(command, &headers).await
+}
+
+async fn terminate_hyperedge<T: Transport>(
+    State(gateway): Shared<T>,
+    headers: HeaderMap,
+    path: Result<Path<Uuid>, PathRejection>,
+    params: Result<Query<Reason>, QueryRejection>,
+) -> Result<Response, ApiError> {
+    let Path(hyperedge_id) = path?;
+    let Query(Reason { reason }) = params?;
+    let command = v1::Command::TerminateHyperEdge {
+        hyperedge_id,
+        reason,
+    };
+    gateway.send(command, &headers).await
+}
+
+async fn relationships_of<T: Transport>(
+    State(gateway): Shared<T>,
+    path: Result<Path<String>, PathRejection>,
+    params: Result<Query<RelationshipsFilter>, QueryRejection>,
+) -> Result<Json<QueryResponse>, ApiError> {
+    let Path(entity) = path?;
+    let Query(filter) = params?;
+    let query = v1::Query::RelationshipsOf {
+        entity: parse_entity(&entity)?,
+        active_only: filter.active_only,
+        include_archived: filter.include_archived,
+    };
+    Ok(Json(gateway.read(query).await))
+}
+
+async fn command<T: Transport>(
+    State(gateway): Shared<T>,
+    headers: HeaderMap,
+    command: Result<Json<v1::Command>, JsonRejection>,
+) -> Result<Response, ApiError> {
+    let Json(command) = command?;
+    gateway.send(command, &headers).await
+}
+
+async fn query<T: Transport>(
+    State(gateway): Shared<T>,
+    query: Result<Json<v1::Query>, JsonRejection>,
+) -> Result<Json<QueryResponse>, ApiError> {
+    let Json(query) = query?;
+    Ok(Json(gateway.read(query).await))
+}
+
+impl<T: Transport> Gateway<T> {
+    /// Send a v1 command and answer with its response
+    async fn send(&self, command: v1::Command, headers: &HeaderMap) -> Result<Response, ApiError> {
+        let actor = headers
+            .get(ACTOR_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .filter(|v| !v.is_empty())
+            .unwrap_or(DEFAULT_ACTOR);
+        let command = command.into_internal(actor);
+        let location = match &command {
+            RelationshipCommand::Edge(EdgeCommand::CreateEdge(c)) => {
+                Some(format!("/v1/edges/{}", c.edge_id.as_uuid()))
+            }
+            RelationshipCommand::HyperEdge(HyperEdgeCommand::CreateHyperEdge(c)) => {
+                Some(format!("/v1/hyperedges/{}", c.hyperedge_id.as_uuid()))
+            }
+            _ => None,
+        };
+        let response: CommandResponse = self.bus.send_command(&command).await?;
+        Ok(match (response.accepted, location) {
+            (false, _) => (StatusCode::UNPROCESSABLE_ENTITY, Json(response)).into_response(),
+            (true, Some(location)) => (
+                StatusCode::CREATED,
+                [(header::LOCATION, location)],
+                Json(response),
+            )
+                .into_response(),
+            (true, None) => Json(response).into_response(),
+        })
+    }
+
+    /// Answer a v1 query from the read model
+    async fn read(&self, query: v1::Query) -> QueryResponse {
+        query.execute(&*self.read_model.space().read().await)
+    }
+}
+
+fn parse_entity(key: &str) -> Result<EntityRef, ApiError> {
+    let key: EntityKey = key.parse().map_err(ApiError::bad_request)?;
+    Ok(EntityRef::new(key.entity_type, key.entity_id))
+}
+
+// ============================================================================
+// OpenAPI
+// ============================================================================
+
+/// Statuses of accepted commands
+#[derive(Clone, Copy)]
+enum Accepted {
+    Ok,
+    Created,
+    /// `/v1/commands`: 201 for creates, 200 otherwise
+    OkOrCreated,
+}
+
+/// OpenAPI 3.0 description of the gateway, schemas generated from the
+/// request and response types
+pub fn openapi() -> Value {
+    let mut gen = SchemaSettings::openapi3().into_generator();
+    let edge = schema_of::<EdgeDto>(&mut gen);
+    let hyperedge = schema_of::<HyperEdgeDto>(&mut gen);
+    let new_edge = schema_of::<NewEdge>(&mut gen);
+    let new_hyperedge = schema_of::<NewHyperEdge>(&mut gen);
+    let suspension = schema_of::<Suspension>(&mut gen);
+    let participant = schema_of::<ParticipantDto>(&mut gen);
+    let command = schema_of::<v1::Command>(&mut gen);
+    let query = schema_of::<v1::Query>(&mut gen);
+    let response = schema_of::<CommandResponse>(&mut gen);
+    let query_response = schema_of::<QueryResponse>(&mut gen);
+    let error = schema_of::<ErrorBody>(&mut gen);
+
+    let id = path_param(
+        "id",
+        "Relationship id",
+        json!({ "type": "string", "format": "uuid" }),
+    );
+    let entity = path_param(
+        "entity",
+        "EntityKey, e.g. person:{uuid}",
+        json!({ "type": "string" }),
+    );
+    let reason = json!({ "name": "reason", "in": "query", "required": true, "schema": { "type": "string" } });
+    let flag = |name: &str| json!({ "name": name, "in": "query", "required": false, "schema": { "type": "boolean" } });
+
+    let location = json!({
+        "Location": { "description": "Path of the created relationship", "schema": { "type": "string" } },
+    });
+    let command_op = |summary: &str, params: Vec<Value>, body: Option<&Value>, accepted: Accepted| {
+        let mut responses = Map::new();
+        if matches!(accepted, Accepted::Ok | Accepted::OkOrCreated) {
+            responses.insert("200".to_string(), content("Command accepted", &response));
+        }
+        if matches!(accepted, Accepted::Created | Accepted::OkOrCreated) {
+            let mut created = content("Relationship created", &response);
+            created["headers"] = location.clone();
+            responses.insert("201".to_string(), created);
+        }
+        responses.insert("400".to_string(), content("Malformed request", &error));
+        responses.insert("422".to_string(), content("Command rejected", &response));
+        responses.insert(
+            "503".to_string(),
+            content("Message bus unavailable", &error),
+        );
+        operation(summary, params, body, responses)
+    };
+    let read_op =
+        |summary: &str, params: Vec<Value>, body: Option<&Value>, result: &Value, found: bool| {
+            let mut responses = Map::new();
+            responses.insert("200".to_string(), content("Found", result));
+            responses.insert("400".to_string(), content("Malformed request", &error));
+            if found {
+                responses.insert("404".to_string(), content("Not found", &error));
+            }
+            operation(summary, params, body, responses)
+        };
+
+    let paths = json!({
+        "/v1/edges": {
+            "post": command_op("Create an edge", vec![], Some(&new_edge), Accepted::Created),
+        },
+        "/v1/edges/{id}": {
+            "get": read_op("Get an edge", vec![id.clone()], None, &edge, true),
+            "delete": command_op("Terminate an edge", vec![id.clone(), reason.clone()], None, Accepted::Ok),
+        },
+        "/v1/edges/{id}/activate": {
+            "post": command_op("Activate an edge", vec![id.clone()], None, Accepted::Ok),
+        },
+        "/v1/edges/{id}/suspend": {
+            "post": command_op("Suspend an edge", vec![id.clone()], Some(&suspension), Accepted::Ok),
+        },
+        "/v1/edges/{id}/resume": {
+            "post": command_op("Resume an edge", vec![id.clone()], None, Accepted::Ok),
+        },
+        "/v1/hyperedges": {
+            "post": command_op("Create a hyperedge", vec![], Some(&new_hyperedge), Accepted::Created),
+        },
+        "/v1/hyperedges/{id}": {
+            "get": read_op("Get a hyperedge", vec![id.clone()], None, &hyperedge, true),
+            "delete": command_op("Terminate a hyperedge", vec![id.clone(), reason.clone()], None, Accepted::Ok),
+        },
+        "/v1/hyperedges/{id}/activate": {
+            "post": command_op("Activate a hyperedge", vec![id.clone()], None, Accepted::Ok),
+        },
+        "/v1/hyperedges/{id}/participants": {
+            "post": command_op("Add a participant", vec![id.clone()], Some(&participant), Accepted::Ok),
+        },
+        "/v1/hyperedges/{id}/participants/{entity}": {
+            "delete": command_op("Remove a participant", vec![id, entity.clone(), reason], None, Accepted::Ok),
+        },
+        "/v1/entities/{entity}/relationships": {
+            "get": read_op(
+                "Relationships of an entity",
+                vec![entity, flag("active_only"), flag("include_archived")],
+                None,
+                &query_response,
+                false,
+            ),
+        },
+        "/v1/commands": {
+            "post": command_op("Send any v1 command", vec![], Some(&command), Accepted::OkOrCreated),
+        },
+        "/v1/queries": {
+            "post": read_op("Run any v1 query", vec![], Some(&query), &query_response, false),
+        },
+    });
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "Relationship Domain API",
+            "version": v1::VERSION,
+        },
+        "paths": paths,
+        "components": { "schemas": gen.take_definitions() },
+    })
+}
+
+fn schema_of<S: JsonSchema>(gen: &mut SchemaGenerator) -> Value {
+    serde_json::to_value(gen.subschema_for::<S>()).unwrap_or_default()
+}
+
+fn path_param(name: &str, description: &str, schema: Value) -> Value {
+    json!({ "name": name, "in": "path", "required": true, "description": description, "schema": schema })
+}
+
+fn content(description: &str, schema: &Value) -> Value {
+    json!({ "description": description, "content": { "application/json": { "schema": schema } } })
+}
+
+fn operation(
+    summary: &str,
+    parameters: Vec<Value>,
+    body: Option<&Value>,
+    responses: Map<String, Value>,
+) -> Value {
+    let mut operation =
+        json!({ "summary": summary, "parameters": parameters, "responses": responses });
+    if let Some(body) = body {
+        operation["requestBody"] =
+            json!({ "required": true, "content": { "application/json": { "schema": body } } });
+    }
+    operation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregates::RelationshipSpace;
+    use crate::nats::{decode, encode, MockTransport, RelationshipSubjects};
+    use axum::body::Body;
+    use axum::http::Request;
+    use cim_domain_spaces::TopologicalSpaceId;
+    use tokio::sync::RwLock;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_create_and_read_over_http() {
+        let transport = MockTransport::new();
+        let (sent, received) = std::sync::mpsc::channel();
+        transport.on_request(RelationshipSubjects::all_commands(), move |message| {
+            let _ = sent.send(decode::<RelationshipCommand>(&message.payload)?);
+            encode(&CommandResponse::accepted(Vec::new()))
+        });
+        let read_model = RelationshipReadModel::new(Arc::new(RwLock::new(RelationshipSpace::new(
+            "REST",
+            TopologicalSpaceId::new(),
+        ))));
+        let app = router(RelationshipBus::new(transport.clone()), read_model);
+
+        let body = json!({
+            "source": EntityRef::person(Uuid::now_v7()),
+            "target": EntityRef::organization(Uuid::now_v7()),
+            "category": "Employment",
+            "name": "Alice at Acme",
+        });
+        let request = Request::post("/v1/edges")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(ACTOR_HEADER, "curl")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let location = response.headers()[header::LOCATION]
+            .to_str()
+            .unwrap()
+            .to_string();
+        assert!(location.starts_with("/v1/edges/"));
+        let RelationshipCommand::Edge(EdgeCommand::CreateEdge(create)) = received.recv().unwrap()
+        else {
+            panic!("expected CreateEdge");
+        };
+        assert_eq!(create.created_by, "curl");
+        assert_eq!(location, format!("/v1/edges/{}", create.edge_id.as_uuid()));
+
+        // Nothing projected the edge yet
+        let request = Request::get(location).body(Body::empty()).unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let request = Request::get("/openapi.json").body(Body::empty()).unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let document: Value = serde_json::from_slice(&bytes).unwrap();
+        assert!(document["paths"]["/v1/edges/{id}"]["get"].is_object());
+        assert!(document["components"]["schemas"]["EdgeDto"].is_object());
+    }
+
+    #[tokio::test]
+    async fn test_malformed_requests_answer_error_bodies() {
+        let read_model = RelationshipReadModel::new(Arc::new(RwLock::new(RelationshipSpace::new(
+            "REST",
+            TopologicalSpaceId::new(),
+        ))));
+        let app = router(RelationshipBus::new(MockTransport::new()), read_model);
+
+        let requests = [
+            Request::get("/v1/edges/not-a-uuid").body(Body::empty()).unwrap(),
+            Request::delete(format!("/v1/edges/{}", Uuid::now_v7())).body(Body::empty()).unwrap(),
+            Request::post("/v1/commands")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from("{\"CreateEdge\":"))
+                .unwrap(),
+            Request::post("/v1/queries").body(Body::from("{}")).unwrap(),
+        ];
+        for request in requests {
+            let uri = request.uri().clone();
+            let response = app.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{}", uri);
+            let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let error: ErrorBody = serde_json::from_slice(&bytes).unwrap();
+            assert!(!error.error.is_empty());
+        }
+
+        let document = openapi();
+        let commands = &document["paths"]["/v1/commands"]["post"]["responses"];
+        assert!(commands["201"]["headers"]["Location"].is_object());
+        assert!(commands["200"].is_object());
+        assert!(commands["400"].is_object());
+        assert!(document["paths"]["/v1/edges/{id}"]["get"]["responses"]["400"].is_object());
+    }
+}
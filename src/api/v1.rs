@@ -0,0 +1,504 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Relationship API, version 1
+//!
+//! A minimal, serializable surface for creating and reading relationships.
+//! Commands carry no message identity or generated ids; `Command::into_internal`
+//! fills those in. DTOs flatten aggregates to plain data and expose lifecycle
+//! states by name.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use cim_domain_relationship::api::v1;
+//!
+//! let command = v1::Command::CreateEdge {
+//!     source: EntityRef::person(alice_id),
+//!     target: EntityRef::organization(acme_id),
+//!     category: RelationshipCategory::Employment,
+//!     name: "Alice at Acme".to_string(),
+//!     quality: None,
+//! };
+//! let internal = command.into_internal("hr-system");
+//! ```
+
+use crate::aggregates::{EdgeConcept, HyperEdgeConcept, RelationshipSpace};
+use crate::commands::{
+    ActivateEdge, ActivateHyperEdge, AddParticipant, CreateEdge, CreateHyperEdge, EdgeCommand,
+    HyperEdgeCommand, RelationshipCommand, RemoveParticipant, ResumeEdge, SuspendEdge,
+    TerminateEdge, TerminateHyperEdge,
+};
+use crate::quality::RelationshipQuality;
+use crate::value_objects::{ActivationMode, IncidenceMatrix, RelationshipId, ValidityPeriod};
+use chrono::{DateTime, Utc};
+use cim_domain::state_machine::State;
+use cim_domain::MessageIdentity;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub use crate::value_objects::{EntityRef, EntityType, Formality, ParticipantRole, RelationshipCategory};
+
+/// API version identifier
+pub const VERSION: &str = "v1";
+
+// ============================================================================
+// DTOs
+// ============================================================================
+
+/// Relationship quality
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct QualityDto {
+    /// Strength (0.0 - 1.0)
+    pub strength: f64,
+    /// Trust (0.0 - 1.0)
+    pub trust: f64,
+    /// Formality level
+    pub formality: Formality,
+    /// Reciprocity (0.0 - 1.0)
+    pub reciprocity: f64,
+    /// When the relationship started
+    pub starts_at: DateTime<Utc>,
+    /// When the relationship ends (None = ongoing)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ends_at: Option<DateTime<Utc>>,
+}
+
+impl From<&RelationshipQuality> for QualityDto {
+    fn from(quality: &RelationshipQuality) -> Self {
+        Self {
+            strength: quality.strength,
+            trust: quality.trust,
+            formality: quality.formality,
+            reciprocity: quality.reciprocity,
+            starts_at: quality.duration.starts_at,
+            ends_at: quality.duration.ends_at,
+        }
+    }
+}
+
+impl From<QualityDto> for RelationshipQuality {
+    fn from(dto: QualityDto) -> Self {
+        let duration = ValidityPeriod {
+            ends_at: dto.ends_at,
+            ..ValidityPeriod::ongoing(dto.starts_at)
+        };
+        RelationshipQuality::new(dto.strength, dto.trust, dto.formality, duration, dto.reciprocity)
+    }
+}
+
+/// Binary relationship
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EdgeDto {
+    /// Relationship id
+    pub id: Uuid,
+    /// Human-readable name
+    pub name: String,
+    /// Source entity
+    pub source: EntityRef,
+    /// Target entity
+    pub target: EntityRef,
+    /// Relationship category
+    pub category: RelationshipCategory,
+    /// Lifecycle state name
+    pub state: String,
+    /// Quality dimensions
+    pub quality: QualityDto,
+    /// Confidence (0.0 - 1.0)
+    pub confidence: f64,
+    /// Creation timestamp
+    pub created_at: DateTime<Utc>,
+    /// Last update timestamp
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<&EdgeConcept> for EdgeDto {
+    fn from(edge: &EdgeConcept) -> Self {
+        Self {
+            id: edge.id.as_uuid(),
+            name: edge.name.clone(),
+            source: edge.source.clone(),
+            target: edge.target.clone(),
+            category: edge.category.clone(),
+            state: edge.state.name().to_string(),
+            quality: QualityDto::from(&edge.quality),
+            confidence: edge.confidence,
+            created_at: edge.created_at,
+            updated_at: edge.updated_at,
+        }
+    }
+}
+
+/// Participant in an n-ary relationship
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ParticipantDto {
+    /// Participating entity
+    pub entity: EntityRef,
+    /// Role in the relationship
+    pub role: ParticipantRole,
+    /// Participation weight (0.0 - 1.0)
+    pub weight: f64,
+}
+
+/// N-ary relationship
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct HyperEdgeDto {
+    /// Relationship id
+    pub id: Uuid,
+    /// Human-readable name
+    pub name: String,
+    /// Relationship category
+    pub category: RelationshipCategory,
+    /// Lifecycle state name
+    pub state: String,
+    /// Participants
+    pub participants: Vec<ParticipantDto>,
+    /// Quality dimensions
+    pub quality: QualityDto,
+    /// Confidence (0.0 - 1.0)
+    pub confidence: f64,
+    /// Creation timestamp
+    pub created_at: DateTime<Utc>,
+    /// Last update timestamp
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<&HyperEdgeConcept> for HyperEdgeDto {
+    fn from(hyperedge: &HyperEdgeConcept) -> Self {
+        Self {
+            id: hyperedge.id.as_uuid(),
+            name: hyperedge.name.clone(),
+            category: hyperedge.category.clone(),
+            state: hyperedge.state.name().to_string(),
+            participants: hyperedge
+                .participants
+                .participants()
+                .map(|p| ParticipantDto {
+                    entity: p.entity_ref.clone(),
+                    role: p.role.clone(),
+                    weight: p.weight,
+                })
+                .collect(),
+            quality: QualityDto::from(&hyperedge.quality),
+            confidence: hyperedge.confidence,
+            created_at: hyperedge.created_at,
+            updated_at: hyperedge.updated_at,
+        }
+    }
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Commands accepted by the v1 API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum Command {
+    CreateEdge {
+        source: EntityRef,
+        target: EntityRef,
+        category: RelationshipCategory,
+        name: String,
+        #[serde(default)]
+        quality: Option<QualityDto>,
+    },
+    ActivateEdge {
+        edge_id: Uuid,
+    },
+    SuspendEdge {
+        edge_id: Uuid,
+        #[serde(default)]
+        reason: Option<String>,
+    },
+    ResumeEdge {
+        edge_id: Uuid,
+    },
+    TerminateEdge {
+        edge_id: Uuid,
+        reason: String,
+    },
+    CreateHyperEdge {
+        name: String,
+        category: RelationshipCategory,
+        participants: Vec<ParticipantDto>,
+    },
+    ActivateHyperEdge {
+        hyperedge_id: Uuid,
+    },
+    AddParticipant {
+        hyperedge_id: Uuid,
+        participant: ParticipantDto,
+    },
+    RemoveParticipant {
+        hyperedge_id: Uuid,
+        entity: EntityRef,
+        reason: String,
+    },
+    TerminateHyperEdge {
+        hyperedge_id: Uuid,
+        reason: String,
+    },
+}
+
+impl Command {
+    /// Convert to an internal command issued by `actor`
+    ///
+    /// Each call starts a new message identity; create commands are assigned
+    /// a fresh relationship id.
+    pub fn into_internal(self, actor: impl Into<String>) -> RelationshipCommand {
+        let identity = MessageIdentity::new_root();
+        let actor = actor.into();
+        match self {
+            Command::CreateEdge {
+                source,
+                target,
+                category,
+                name,
+                quality,
+            } => RelationshipCommand::Edge(EdgeCommand::CreateEdge(CreateEdge {
+                identity,
+                edge_id: RelationshipId::new(),
+                source,
+                target,
+                category,
+                name,
+                quality: quality.map(RelationshipQuality::from),
+                created_by: actor,
+            })),
+            Command::ActivateEdge { edge_id } => {
+                RelationshipCommand::Edge(EdgeCommand::ActivateEdge(ActivateEdge {
+                    identity,
+                    edge_id: RelationshipId::from_uuid(edge_id),
+                    activated_by: actor,
+                }))
+            }
+            Command::SuspendEdge { edge_id, reason } => {
+                RelationshipCommand::Edge(EdgeCommand::SuspendEdge(SuspendEdge {
+                    identity,
+                    edge_id: RelationshipId::from_uuid(edge_id),
+                    reason,
+                    suspended_by: actor,
+                }))
+            }
+            Command::ResumeEdge { edge_id } => {
+                RelationshipCommand::Edge(EdgeCommand::ResumeEdge(ResumeEdge {
+                    identity,
+                    edge_id: RelationshipId::from_uuid(edge_id),
+                    resumed_by: actor,
+                }))
+            }
+            Command::TerminateEdge { edge_id, reason } => {
+                RelationshipCommand::Edge(EdgeCommand::TerminateEdge(TerminateEdge {
+                    identity,
+                    edge_id: RelationshipId::from_uuid(edge_id),
+                    reason,
+                    terminated_by: actor,
+                }))
+            }
+            Command::CreateHyperEdge {
+                name,
+                category,
+                participants,
+            } => {
+                let mut initial_participants = IncidenceMatrix::new();
+                for p in participants {
+                    initial_participants.add_participant(p.entity, p.role, p.weight);
+                }
+                RelationshipCommand::HyperEdge(HyperEdgeCommand::CreateHyperEdge(CreateHyperEdge {
+                    identity,
+                    hyperedge_id: RelationshipId::new(),
+                    name,
+                    category,
+                    initial_participants,
+                    activation: ActivationMode::Immediate,
+                    created_by: actor,
+                }))
+            }
+            Command::ActivateHyperEdge { hyperedge_id } => {
+                RelationshipCommand::HyperEdge(HyperEdgeCommand::ActivateHyperEdge(ActivateHyperEdge {
+                    identity,
+                    hyperedge_id: RelationshipId::from_uuid(hyperedge_id),
+                    activated_by: actor,
+                }))
+            }
+            Command::AddParticipant {
+                hyperedge_id,
+                participant,
+            } => RelationshipCommand::HyperEdge(HyperEdgeCommand::AddParticipant(AddParticipant {
+                identity,
+                hyperedge_id: RelationshipId::from_uuid(hyperedge_id),
+                participant: participant.entity,
+                role: participant.role,
+                weight: participant.weight,
+                added_by: actor,
+            })),
+            Command::RemoveParticipant {
+                hyperedge_id,
+                entity,
+                reason,
+            } => RelationshipCommand::HyperEdge(HyperEdgeCommand::RemoveParticipant(RemoveParticipant {
+                identity,
+                hyperedge_id: RelationshipId::from_uuid(hyperedge_id),
+                participant: entity,
+                reason,
+                removed_by: actor,
+            })),
+            Command::TerminateHyperEdge {
+                hyperedge_id,
+                reason,
+            } => RelationshipCommand::HyperEdge(HyperEdgeCommand::TerminateHyperEdge(
+                TerminateHyperEdge {
+                    identity,
+                    hyperedge_id: RelationshipId::from_uuid(hyperedge_id),
+                    reason,
+                    terminated_by: actor,
+                },
+            )),
+        }
+    }
+}
+
+// ============================================================================
+// Queries
+// ============================================================================
+
+/// Queries answered by the v1 API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "query", rename_all = "snake_case")]
+pub enum Query {
+    /// Get a binary relationship by id
+    GetEdge { edge_id: Uuid },
+    /// Get an n-ary relationship by id
+    GetHyperEdge { hyperedge_id: Uuid },
+    /// Get every relationship an entity takes part in
+    RelationshipsOf {
+        entity: EntityRef,
+        #[serde(default)]
+        active_only: bool,
+        #[serde(default)]
+        include_archived: bool,
+    },
+}
+
+/// Results of v1 queries
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "result", rename_all = "snake_case")]
+pub enum QueryResponse {
+    Edge { edge: Option<EdgeDto> },
+    HyperEdge { hyperedge: Option<HyperEdgeDto> },
+    Relationships {
+        edges: Vec<EdgeDto>,
+        hyperedges: Vec<HyperEdgeDto>,
+    },
+}
+
+impl Query {
+    /// Answer the query from a relationship space
+    pub fn execute(&self, space: &RelationshipSpace) -> QueryResponse {
+        match self {
+            Query::GetEdge { edge_id } => QueryResponse::Edge {
+                edge: space
+                    .get_edge(&RelationshipId::from_uuid(*edge_id))
+                    .map(EdgeDto::from),
+            },
+            Query::GetHyperEdge { hyperedge_id } => QueryResponse::HyperEdge {
+                hyperedge: space
+                    .get_hyperedge(&RelationshipId::from_uuid(*hyperedge_id))
+                    .map(HyperEdgeDto::from),
+            },
+            Query::RelationshipsOf {
+                entity,
+                active_only,
+                include_archived,
+            } => QueryResponse::Relationships {
+                edges: space
+                    .edges
+                    .values()
+                    .filter(|e| !active_only || e.is_active())
+                    .filter(|e| *include_archived || !e.is_archived())
+                    .filter(|e| e.source.same_entity(entity) || e.target.same_entity(entity))
+                    .map(EdgeDto::from)
+                    .collect(),
+                hyperedges: space
+                    .hyperedges
+                    .values()
+                    .filter(|h| !active_only || h.is_active())
+                    .filter(|h| *include_archived || !h.is_archived())
+                    .filter(|h| {
+                        h.participants
+                            .participants()
+                            .any(|p| p.entity_ref.same_entity(entity))
+                    })
+                    .map(HyperEdgeDto::from)
+                    .collect(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cim_domain_spaces::TopologicalSpaceId;
+
+    #[test]
+    fn test_command_into_internal() {
+        let command = Command::CreateEdge {
+            source: EntityRef::person(Uuid::now_v7()),
+            target: EntityRef::organization(Uuid::now_v7()),
+            category: RelationshipCategory::Employment,
+            name: "Employment".to_string(),
+            quality: Some(QualityDto::from(&RelationshipQuality::default_employment())),
+        };
+
+        // Wire format is stable and tagged
+        let json = serde_json::to_value(&command).unwrap();
+        assert_eq!(json["command"], "create_edge");
+
+        let internal = command.into_internal("api");
+        assert_eq!(internal.command_type(), "create_edge");
+        let RelationshipCommand::Edge(EdgeCommand::CreateEdge(create)) = internal else {
+            panic!("expected CreateEdge");
+        };
+        assert_eq!(create.created_by, "api");
+        assert_eq!(create.quality.unwrap().formality, Formality::Contractual);
+    }
+
+    #[test]
+    fn test_query_returns_dtos() {
+        let mut space = RelationshipSpace::new("Api", TopologicalSpaceId::new());
+        let person = EntityRef::person(Uuid::now_v7());
+        let edge = EdgeConcept::new(
+            "Employment",
+            person.clone(),
+            EntityRef::organization(Uuid::now_v7()),
+            RelationshipCategory::Employment,
+        );
+        let edge_id = edge.id.as_uuid();
+        space.add_edge(edge).unwrap();
+
+        let QueryResponse::Edge { edge: Some(dto) } = Query::GetEdge { edge_id }.execute(&space) else {
+            panic!("expected edge");
+        };
+        assert_eq!(dto.state, "Proposed");
+
+        let query = Query::RelationshipsOf {
+            entity: person,
+            active_only: true,
+            include_archived: false,
+        };
+        let QueryResponse::Relationships { edges, .. } = query.execute(&space) else {
+            panic!("expected relationships");
+        };
+        assert!(edges.is_empty());
+    }
+}
@@ -4,28 +4,315 @@
 
 //! Relationship Service Binary
 //!
-//! NATS-connected service for the relationship domain.
+//! NATS worker for the relationship domain. On startup it waits for the
+//! lease on its events stream, so only one instance decides commands at a
+//! time, checks its JetStream assets and rebuilds the relationship space
+//! from the latest snapshot and the events kept since; it then answers commands and
+//! queries, reacts to upstream domain events and sweeps for expired edges
+//! and consent requests until SIGTERM or Ctrl-C. With `webhooks.enabled`
+//! (feature `webhooks`) it also answers webhook commands and delivers
+//! events to the registered webhooks.
+//!
+//! Health probes are answered from the start, so the service reports not
+//! ready while it rebuilds rather than not alive.
+//!
+//! On shutdown it reports not ready, stops taking new messages, waits up
+//! to `shutdown.drain_timeout_secs` for the ones in hand to be handled and
+//! acked, saves a last snapshot, publishes the outbox, flushes the
+//! connection and releases its lease before exiting.
+//!
+//! Settings are read from the file `RELATIONSHIP_CONFIG` names and
+//! `RELATIONSHIP__{SECTION}__{KEY}` overrides; see `ServiceConfig`.
 
-use std::env;
+use cim_domain_relationship::aggregates::RelationshipSpace;
+use cim_domain_relationship::infrastructure::ServiceConfig;
+#[cfg(feature = "avro")]
+use cim_domain_relationship::interop::AvroEventCodec;
+use cim_domain_relationship::nats::jetstream::{self, Checkpointer, InstanceLease, Snapshot};
+use cim_domain_relationship::nats::{
+    CloudEventEnvelope, HealthMonitor, ProjectionProgress, RelationshipBus, RelationshipWorker,
+    Shutdown,
+};
+use cim_domain_relationship::projections::RelationshipReadModel;
+use cim_domain_relationship::services::EntityVerifier;
+#[cfg(feature = "webhooks")]
+use cim_domain_relationship::services::{HttpWebhookSender, KvWebhookStore, WebhookDispatcher, WebhookManager};
+use cim_domain_relationship::RelationshipResult;
+use cim_domain_spaces::TopologicalSpaceId;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tokio::task::JoinSet;
+
+/// Projection name of the worker's space
+const SPACE_PROJECTION: &str = "space";
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Initialize tracing
     tracing_subscriber::fmt::init();
 
-    let nats_url = env::var("NATS_URL").unwrap_or_else(|_| "nats://localhost:4222".to_string());
+    // Refuse to start on settings we cannot honour
+    let config = match ServiceConfig::from_env() {
+        Ok(config) => config,
+        Err(e) => {
+            tracing::error!("{}", e);
+            std::process::exit(2);
+        }
+    };
 
     tracing::info!("Starting relationship-service");
-    tracing::info!("NATS URL: {}", nats_url);
+    tracing::info!("NATS servers: {}", config.nats.servers.join(", "));
+
+    let transport = config.nats.connect().await?;
+    let mut bus = RelationshipBus::new(transport.clone());
+    if config.features.cloud_events {
+        bus = bus.with_cloud_events(CloudEventEnvelope::default());
+    }
+    #[cfg(feature = "avro")]
+    if config.features.avro_events {
+        bus = bus.with_avro(AvroEventCodec::new()?);
+    }
+    let registry = config.registry();
+    let js = async_nats::jetstream::new(transport.client().clone());
+
+    // Answer probes while rebuilding: alive, but not ready
+    let shutdown = Shutdown::new();
+    let progress = ProjectionProgress::new();
+    progress.expect(SPACE_PROJECTION);
+    if config.snapshots.enabled {
+        progress.expect(Checkpointer::PROJECTION);
+    }
+    let monitor = Arc::new(
+        HealthMonitor::new(transport.clone())
+            .with_jetstream(js.clone(), &config.streams.events)
+            .with_upstream_subjects(registry.subjects())
+            .with_progress(progress.clone())
+            .with_max_lag(config.health.max_lag)
+            .with_shutdown(shutdown.clone()),
+    );
+    // Tasks drained at shutdown, and tasks answering until exit
+    let mut tasks: JoinSet<RelationshipResult<()>> = JoinSet::new();
+    let mut probes: JoinSet<RelationshipResult<()>> = JoinSet::new();
+    probes.spawn({
+        let monitor = monitor.clone();
+        async move { monitor.serve().await }
+    });
+    if let Some(addr) = config.health.addr {
+        #[cfg(feature = "health")]
+        probes.spawn(serve_http(
+            "health endpoints",
+            addr,
+            cim_domain_relationship::api::health::router(monitor.clone()),
+            std::future::pending(),
+        ));
+        #[cfg(not(feature = "health"))]
+        tracing::warn!("health.addr {} ignored: built without the health feature", addr);
+    }
 
-    // TODO: Implement NATS connection and command handler
-    // For now, just a placeholder that demonstrates the library compiles
+    // Stand by while another instance decides commands
+    let lease = Arc::new(
+        InstanceLease::acquire(&js, &config.lease.bucket, &config.streams.events, config.lease.ttl()).await?,
+    );
+    tasks.spawn({
+        let (lease, shutdown) = (lease.clone(), shutdown.clone());
+        async move { lease.keep(&shutdown).await }
+    });
 
-    tracing::info!("Relationship service started (placeholder)");
+    // Refuse to start without the streams and consumers we rely on
+    let assets = config.jetstream_assets(registry.subjects());
+    let mut provisioned = assets.provision(&js).await?;
+    let size = jetstream::stream_size(&mut provisioned.events).await?;
 
-    // Keep running
-    tokio::signal::ctrl_c().await?;
+    let snapshots = if config.snapshots.enabled {
+        Some(Snapshot::bucket(&js, &config.snapshots.bucket).await?)
+    } else {
+        None
+    };
+    let latest = match &snapshots {
+        Some(store) => Snapshot::load(store).await?,
+        None => None,
+    };
+    #[cfg(feature = "webhooks")]
+    let webhooks_base = latest.clone().filter(|_| config.webhooks.enabled);
+    let mut snapshot = latest.unwrap_or_else(|| {
+        Snapshot::new(RelationshipSpace::new("relationship-service", TopologicalSpaceId::new()))
+    });
+    let from = snapshot.sequence;
+    let replayed = snapshot.catch_up(&mut provisioned.events).await?;
+    tracing::info!(
+        "Replayed {} events from {} after sequence {}",
+        replayed,
+        assets.events_stream(),
+        from
+    );
 
+    let read_model = RelationshipReadModel::new(Arc::new(RwLock::new(snapshot.space.clone())));
+    let worker = Arc::new(
+        RelationshipWorker::new(bus.clone(), read_model.clone())
+            .with_registry(registry)
+            .with_verifier(EntityVerifier::new().with_mode(config.verification.mode))
+            .with_shutdown(shutdown.clone()),
+    );
+    worker.metrics().write().await.record_stream(assets.events_stream(), size);
+    progress.mark_live(SPACE_PROJECTION);
+
+    tasks.spawn({
+        let worker = worker.clone();
+        async move { worker.serve().await }
+    });
+    if let Some(period) = config.expiry.sweep_interval() {
+        let worker = worker.clone();
+        tasks.spawn(async move { worker.serve_expiry(period).await });
+    }
+    #[cfg(feature = "webhooks")]
+    if config.webhooks.enabled {
+        let manager = Arc::new(
+            WebhookManager::load(KvWebhookStore::open(&js, &config.webhooks.bucket).await?)
+                .await?
+                .with_shutdown(shutdown.clone()),
+        );
+        let dispatcher = WebhookDispatcher::new(HttpWebhookSender::new(config.webhooks.timeout())?)
+            .with_registry(manager.registry().clone())
+            .with_retry(config.webhooks.retry());
+        jetstream::dead_letter_stream(&js, &config.webhooks.dead_letters).await?;
+        let mut feed = jetstream::WebhookFeed::start(
+            dispatcher,
+            js.clone(),
+            &mut provisioned.events,
+            &config.webhooks.consumer,
+            webhooks_base,
+        )
+        .await?
+        .with_shutdown(shutdown.clone());
+        tracing::info!("Delivering events to {} webhooks", manager.registry().read().await.list().len());
+        tasks.spawn({
+            let transport = transport.clone();
+            async move { manager.serve(&transport).await }
+        });
+        tasks.spawn(async move { feed.run().await });
+    }
+    if let Some(store) = snapshots {
+        let mut checkpointer = Checkpointer::new(snapshot, provisioned.events, store)
+            .with_interval(config.snapshots.interval())
+            .with_every_events(config.snapshots.every_events)
+            .with_progress(progress.clone())
+            .with_shutdown(shutdown.clone());
+        tasks.spawn(async move { checkpointer.run().await });
+    }
+    for upstream in provisioned.upstream {
+        tracing::info!("Consuming {} from stream {}", upstream.subject, upstream.stream);
+        let worker = worker.clone();
+        tasks.spawn(async move { jetstream::follow_upstream(upstream, &worker).await });
+    }
+    #[cfg(feature = "schema")]
+    if config.features.schema_catalog {
+        let transport = transport.clone();
+        probes.spawn(async move {
+            cim_domain_relationship::schema::SchemaCatalog::new()
+                .serve(&transport)
+                .await
+        });
+    }
+    if let Some(addr) = config.http.addr {
+        #[cfg(feature = "rest")]
+        tasks.spawn(serve_http(
+            "REST gateway",
+            addr,
+            cim_domain_relationship::api::rest::router(bus.clone(), read_model.clone()),
+            {
+                let shutdown = shutdown.clone();
+                async move { shutdown.triggered().await }
+            },
+        ));
+        #[cfg(not(feature = "rest"))]
+        tracing::warn!("http.addr {} ignored: built without the rest feature", addr);
+    }
+
+    tracing::info!("Relationship service started");
+
+    tokio::select! {
+        result = terminated() => result?,
+        Some(ended) = tasks.join_next() => report(ended),
+        Some(ended) = probes.join_next() => report(ended),
+    }
+
+    // Stop taking messages and let the ones in hand finish
     tracing::info!("Shutting down relationship-service");
+    shutdown.trigger();
+    let drain = config.shutdown.drain_timeout();
+    let drained = tokio::time::timeout(drain, async {
+        while let Some(ended) = tasks.join_next().await {
+            if !matches!(ended, Ok(Ok(()))) {
+                report(ended);
+            }
+        }
+    })
+    .await;
+    if drained.is_err() {
+        tracing::warn!("{} tasks still busy after {:?}; abandoning them", tasks.len(), drain);
+        tasks.shutdown().await;
+    }
+    probes.shutdown().await;
+
+    match worker.flush_outbox().await {
+        Ok(0) => {}
+        Ok(flushed) => tracing::info!("Published {} events left in the outbox", flushed),
+        Err(e) => tracing::error!("{} events never published: {}", worker.outbox_len().await, e),
+    }
+    // Replies, acks and events are only sent once the client flushes
+    if let Err(e) = transport.client().flush().await {
+        tracing::error!("Failed to flush the NATS connection: {}", e);
+    }
+    if let Err(e) = lease.release().await {
+        tracing::warn!("Failed to release the lease; a standby takes over once it expires: {}", e);
+    }
+    tracing::info!("Relationship service stopped");
     Ok(())
 }
+
+/// Wait for SIGTERM or Ctrl-C
+#[cfg(unix)]
+async fn terminated() -> std::io::Result<()> {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut sigterm = signal(SignalKind::terminate())?;
+    tokio::select! {
+        result = tokio::signal::ctrl_c() => result,
+        _ = sigterm.recv() => Ok(()),
+    }
+}
+
+/// Wait for Ctrl-C
+#[cfg(not(unix))]
+async fn terminated() -> std::io::Result<()> {
+    tokio::signal::ctrl_c().await
+}
+
+/// Log a task ending before shutdown asked it to
+fn report(ended: Result<RelationshipResult<()>, tokio::task::JoinError>) {
+    match ended {
+        Ok(Ok(())) => tracing::error!("A relationship-service task stopped"),
+        Ok(Err(e)) => tracing::error!("A relationship-service task failed: {}", e),
+        Err(e) => tracing::error!("A relationship-service task panicked: {}", e),
+    }
+}
+
+/// Serve HTTP routes until `stop` completes, letting requests in flight
+/// finish, or the listener fails
+#[cfg(any(feature = "rest", feature = "health"))]
+async fn serve_http(
+    what: &'static str,
+    addr: std::net::SocketAddr,
+    router: axum::Router,
+    stop: impl std::future::Future<Output = ()> + Send + 'static,
+) -> RelationshipResult<()> {
+    use cim_domain_relationship::RelationshipError;
+
+    let io_error = |e: std::io::Error| RelationshipError::TransportError(format!("{} on {}: {}", what, addr, e));
+    let listener = tokio::net::TcpListener::bind(addr).await.map_err(io_error)?;
+    tracing::info!("{} listening on {}", what, addr);
+    axum::serve(listener, router)
+        .with_graceful_shutdown(stop)
+        .await
+        .map_err(io_error)
+}
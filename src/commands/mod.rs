@@ -7,10 +7,14 @@
 //! Commands express intent to change the state of relationships.
 //! They are validated before execution and produce events.
 
+use crate::invariants::RelationshipPolicy;
 use crate::quality::RelationshipQuality;
-use crate::value_objects::{EntityRef, IncidenceMatrix, ParticipantRole, RelationshipCategory, RelationshipId};
+use crate::value_objects::{ActivationMode, EntityRef, IncidenceMatrix, ParticipantRole, RelationshipCategory, RelationshipId};
+use chrono::{DateTime, Utc};
 use cim_domain::MessageIdentity;
+use cim_domain_spaces::KnowledgeLevel;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 // ============================================================================
 // Edge Commands
@@ -18,6 +22,7 @@ use serde::{Deserialize, Serialize};
 
 /// Commands for EdgeConcept aggregate
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum EdgeCommand {
     CreateEdge(CreateEdge),
     ActivateEdge(ActivateEdge),
@@ -25,12 +30,98 @@ pub enum EdgeCommand {
     ResumeEdge(ResumeEdge),
     TerminateEdge(TerminateEdge),
     RejectEdge(RejectEdge),
+    ReproposeEdge(ReproposeEdge),
+    ExpireEdge(ExpireEdge),
     UpdateEdgeQuality(UpdateEdgeQuality),
     AddEdgeEvidence(AddEdgeEvidence),
+    RevokeEdgeEvidence(RevokeEdgeEvidence),
+    ProgressKnowledge(ProgressKnowledge),
+    ReverseEdge(ReverseEdge),
+    RedirectEdge(RedirectEdge),
+    RequestConsent(RequestConsent),
+    GrantConsent(GrantConsent),
+    DeclineConsent(DeclineConsent),
+    ExpireConsent(ExpireConsent),
+    SetEdgeProperty(SetEdgeProperty),
+    RemoveEdgeProperty(RemoveEdgeProperty),
+    AddEdgeTag(AddEdgeTag),
+    RemoveEdgeTag(RemoveEdgeTag),
+    ArchiveEdge(ArchiveRelationship),
+    RestoreEdge(RestoreRelationship),
+    RenameEdge(RenameRelationship),
+    DescribeEdge(UpdateDescription),
+}
+
+impl EdgeCommand {
+    /// Get the command type name used in NATS subjects
+    pub fn command_type(&self) -> &'static str {
+        match self {
+            EdgeCommand::CreateEdge(_) => "create_edge",
+            EdgeCommand::ActivateEdge(_) => "activate_edge",
+            EdgeCommand::SuspendEdge(_) => "suspend_edge",
+            EdgeCommand::ResumeEdge(_) => "resume_edge",
+            EdgeCommand::TerminateEdge(_) => "terminate_edge",
+            EdgeCommand::RejectEdge(_) => "reject_edge",
+            EdgeCommand::ReproposeEdge(_) => "repropose_edge",
+            EdgeCommand::ExpireEdge(_) => "expire_edge",
+            EdgeCommand::UpdateEdgeQuality(_) => "update_edge_quality",
+            EdgeCommand::AddEdgeEvidence(_) => "add_edge_evidence",
+            EdgeCommand::RevokeEdgeEvidence(_) => "revoke_edge_evidence",
+            EdgeCommand::ProgressKnowledge(_) => "progress_knowledge",
+            EdgeCommand::ReverseEdge(_) => "reverse_edge",
+            EdgeCommand::RedirectEdge(_) => "redirect_edge",
+            EdgeCommand::RequestConsent(_) => "request_consent",
+            EdgeCommand::GrantConsent(_) => "grant_consent",
+            EdgeCommand::DeclineConsent(_) => "decline_consent",
+            EdgeCommand::ExpireConsent(_) => "expire_consent",
+            EdgeCommand::SetEdgeProperty(_) => "set_edge_property",
+            EdgeCommand::RemoveEdgeProperty(_) => "remove_edge_property",
+            EdgeCommand::AddEdgeTag(_) => "add_edge_tag",
+            EdgeCommand::RemoveEdgeTag(_) => "remove_edge_tag",
+            EdgeCommand::ArchiveEdge(_) => "archive_edge",
+            EdgeCommand::RestoreEdge(_) => "restore_edge",
+            EdgeCommand::RenameEdge(_) => "rename_edge",
+            EdgeCommand::DescribeEdge(_) => "describe_edge",
+        }
+    }
+
+    /// Get the ID of the edge this command targets
+    pub fn edge_id(&self) -> RelationshipId {
+        match self {
+            EdgeCommand::CreateEdge(c) => c.edge_id,
+            EdgeCommand::ActivateEdge(c) => c.edge_id,
+            EdgeCommand::SuspendEdge(c) => c.edge_id,
+            EdgeCommand::ResumeEdge(c) => c.edge_id,
+            EdgeCommand::TerminateEdge(c) => c.edge_id,
+            EdgeCommand::RejectEdge(c) => c.edge_id,
+            EdgeCommand::ReproposeEdge(c) => c.edge_id,
+            EdgeCommand::ExpireEdge(c) => c.edge_id,
+            EdgeCommand::UpdateEdgeQuality(c) => c.edge_id,
+            EdgeCommand::AddEdgeEvidence(c) => c.edge_id,
+            EdgeCommand::RevokeEdgeEvidence(c) => c.edge_id,
+            EdgeCommand::ProgressKnowledge(c) => c.edge_id,
+            EdgeCommand::ReverseEdge(c) => c.edge_id,
+            EdgeCommand::RedirectEdge(c) => c.edge_id,
+            EdgeCommand::RequestConsent(c) => c.edge_id,
+            EdgeCommand::GrantConsent(c) => c.edge_id,
+            EdgeCommand::DeclineConsent(c) => c.edge_id,
+            EdgeCommand::ExpireConsent(c) => c.edge_id,
+            EdgeCommand::SetEdgeProperty(c) => c.edge_id,
+            EdgeCommand::RemoveEdgeProperty(c) => c.edge_id,
+            EdgeCommand::AddEdgeTag(c) => c.edge_id,
+            EdgeCommand::RemoveEdgeTag(c) => c.edge_id,
+            EdgeCommand::ArchiveEdge(c) => c.relationship_id,
+            EdgeCommand::RestoreEdge(c) => c.relationship_id,
+            EdgeCommand::RenameEdge(c) => c.relationship_id,
+            EdgeCommand::DescribeEdge(c) => c.relationship_id,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct CreateEdge {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
     pub identity: MessageIdentity,
     pub edge_id: RelationshipId,
     pub source: EntityRef,
@@ -42,14 +133,18 @@ pub struct CreateEdge {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ActivateEdge {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
     pub identity: MessageIdentity,
     pub edge_id: RelationshipId,
     pub activated_by: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct SuspendEdge {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
     pub identity: MessageIdentity,
     pub edge_id: RelationshipId,
     pub reason: Option<String>,
@@ -57,14 +152,18 @@ pub struct SuspendEdge {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ResumeEdge {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
     pub identity: MessageIdentity,
     pub edge_id: RelationshipId,
     pub resumed_by: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct TerminateEdge {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
     pub identity: MessageIdentity,
     pub edge_id: RelationshipId,
     pub reason: String,
@@ -72,15 +171,41 @@ pub struct TerminateEdge {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct RejectEdge {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
     pub identity: MessageIdentity,
     pub edge_id: RelationshipId,
     pub reason: Option<String>,
     pub rejected_by: String,
 }
 
+/// Propose a rejected edge again, e.g. after fixing a mistake
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ReproposeEdge {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub edge_id: RelationshipId,
+    pub reason: Option<String>,
+    pub reproposed_by: String,
+}
+
+/// Terminate a live edge whose validity period has ended
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ExpireEdge {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub edge_id: RelationshipId,
+    /// Clock the validity is checked against (the scheduler's tick time)
+    pub as_of: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct UpdateEdgeQuality {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
     pub identity: MessageIdentity,
     pub edge_id: RelationshipId,
     pub new_quality: RelationshipQuality,
@@ -88,47 +213,276 @@ pub struct UpdateEdgeQuality {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct AddEdgeEvidence {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
     pub identity: MessageIdentity,
     pub edge_id: RelationshipId,
     pub evidence_cid: String,
     pub evidence_type: String,
 }
 
+/// Withdraw evidence that turned out to be invalid
+///
+/// A revoked CID cannot be added to the edge again.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RevokeEdgeEvidence {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub edge_id: RelationshipId,
+    pub evidence_cid: String,
+    pub reason: String,
+    pub revoked_by: String,
+}
+
+/// Move an edge one knowledge level up, if its evidence supports it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ProgressKnowledge {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub edge_id: RelationshipId,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::knowledge_level"))]
+    pub to_level: KnowledgeLevel,
+    /// Confidence at the new level (0.0 - 1.0)
+    pub confidence: f64,
+    pub reason: String,
+}
+
+/// Set a property of an edge
+///
+/// With `expected_version`, the command is refused if the property changed
+/// since the caller read it (version 0 = not set yet).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SetEdgeProperty {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub edge_id: RelationshipId,
+    pub key: String,
+    pub value: serde_json::Value,
+    #[serde(default)]
+    pub expected_version: Option<u64>,
+    pub set_by: String,
+}
+
+/// Remove a property of an edge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RemoveEdgeProperty {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub edge_id: RelationshipId,
+    pub key: String,
+    #[serde(default)]
+    pub expected_version: Option<u64>,
+    pub removed_by: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct AddEdgeTag {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub edge_id: RelationshipId,
+    pub tag: String,
+    pub tagged_by: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RemoveEdgeTag {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub edge_id: RelationshipId,
+    pub tag: String,
+    pub removed_by: String,
+}
+
+/// Swap an edge's source and target, e.g. to correct a direction mistake
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ReverseEdge {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub edge_id: RelationshipId,
+    /// Also replace the category with its inverse (PartOf <-> Contains)
+    pub invert_category: bool,
+    pub reason: String,
+    pub reversed_by: String,
+}
+
+/// Point an edge at a different source and/or target, e.g. when an entity
+/// was replaced or recorded wrongly, keeping its quality and evidence
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RedirectEdge {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub edge_id: RelationshipId,
+    pub new_source: Option<EntityRef>,
+    pub new_target: Option<EntityRef>,
+    pub reason: String,
+    pub redirected_by: String,
+}
+
+/// Ask both endpoints of a proposed edge to consent before it activates
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RequestConsent {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub edge_id: RelationshipId,
+    /// After this, the edge can be rejected with `ExpireConsent`
+    pub expires_at: Option<DateTime<Utc>>,
+    pub requested_by: String,
+}
+
+/// An endpoint consents to an edge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct GrantConsent {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub edge_id: RelationshipId,
+    pub party: EntityRef,
+}
+
+/// An endpoint refuses an edge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DeclineConsent {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub edge_id: RelationshipId,
+    pub party: EntityRef,
+    pub reason: Option<String>,
+}
+
+/// Reject an edge whose consent deadline has passed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ExpireConsent {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub edge_id: RelationshipId,
+}
+
 // ============================================================================
 // HyperEdge Commands
 // ============================================================================
 
 /// Commands for HyperEdgeConcept aggregate
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum HyperEdgeCommand {
     CreateHyperEdge(CreateHyperEdge),
     ActivateHyperEdge(ActivateHyperEdge),
     AddParticipant(AddParticipant),
     RemoveParticipant(RemoveParticipant),
     ChangeParticipantRole(ChangeParticipantRole),
+    ChangeParticipantWeight(ChangeParticipantWeight),
+    AcknowledgeParticipation(AcknowledgeParticipation),
     TerminateHyperEdge(TerminateHyperEdge),
+    UpdateHyperEdgeQuality(UpdateHyperEdgeQuality),
+    BeginRestructuring(BeginRestructuring),
+    CompleteRestructuring(CompleteRestructuring),
+    AddHyperEdgeTag(AddHyperEdgeTag),
+    RemoveHyperEdgeTag(RemoveHyperEdgeTag),
+    AddParticipantGroup(AddParticipantGroup),
+    RemoveParticipantGroup(RemoveParticipantGroup),
+    AssignToGroup(AssignToGroup),
+    UnassignFromGroup(UnassignFromGroup),
+    ArchiveHyperEdge(ArchiveRelationship),
+    RestoreHyperEdge(RestoreRelationship),
+    RenameHyperEdge(RenameRelationship),
+    DescribeHyperEdge(UpdateDescription),
+}
+
+impl HyperEdgeCommand {
+    /// Get the command type name used in NATS subjects
+    pub fn command_type(&self) -> &'static str {
+        match self {
+            HyperEdgeCommand::CreateHyperEdge(_) => "create_hyperedge",
+            HyperEdgeCommand::ActivateHyperEdge(_) => "activate_hyperedge",
+            HyperEdgeCommand::AddParticipant(_) => "add_participant",
+            HyperEdgeCommand::RemoveParticipant(_) => "remove_participant",
+            HyperEdgeCommand::ChangeParticipantRole(_) => "change_participant_role",
+            HyperEdgeCommand::ChangeParticipantWeight(_) => "change_participant_weight",
+            HyperEdgeCommand::AcknowledgeParticipation(_) => "acknowledge_participation",
+            HyperEdgeCommand::TerminateHyperEdge(_) => "terminate_hyperedge",
+            HyperEdgeCommand::UpdateHyperEdgeQuality(_) => "update_hyperedge_quality",
+            HyperEdgeCommand::BeginRestructuring(_) => "begin_restructuring",
+            HyperEdgeCommand::CompleteRestructuring(_) => "complete_restructuring",
+            HyperEdgeCommand::AddHyperEdgeTag(_) => "add_hyperedge_tag",
+            HyperEdgeCommand::RemoveHyperEdgeTag(_) => "remove_hyperedge_tag",
+            HyperEdgeCommand::AddParticipantGroup(_) => "add_participant_group",
+            HyperEdgeCommand::RemoveParticipantGroup(_) => "remove_participant_group",
+            HyperEdgeCommand::AssignToGroup(_) => "assign_to_group",
+            HyperEdgeCommand::UnassignFromGroup(_) => "unassign_from_group",
+            HyperEdgeCommand::ArchiveHyperEdge(_) => "archive_hyperedge",
+            HyperEdgeCommand::RestoreHyperEdge(_) => "restore_hyperedge",
+            HyperEdgeCommand::RenameHyperEdge(_) => "rename_hyperedge",
+            HyperEdgeCommand::DescribeHyperEdge(_) => "describe_hyperedge",
+        }
+    }
+
+    /// Get the ID of the hyperedge this command targets
+    pub fn hyperedge_id(&self) -> RelationshipId {
+        match self {
+            HyperEdgeCommand::CreateHyperEdge(c) => c.hyperedge_id,
+            HyperEdgeCommand::ActivateHyperEdge(c) => c.hyperedge_id,
+            HyperEdgeCommand::AddParticipant(c) => c.hyperedge_id,
+            HyperEdgeCommand::RemoveParticipant(c) => c.hyperedge_id,
+            HyperEdgeCommand::ChangeParticipantRole(c) => c.hyperedge_id,
+            HyperEdgeCommand::ChangeParticipantWeight(c) => c.hyperedge_id,
+            HyperEdgeCommand::AcknowledgeParticipation(c) => c.hyperedge_id,
+            HyperEdgeCommand::TerminateHyperEdge(c) => c.hyperedge_id,
+            HyperEdgeCommand::UpdateHyperEdgeQuality(c) => c.hyperedge_id,
+            HyperEdgeCommand::BeginRestructuring(c) => c.hyperedge_id,
+            HyperEdgeCommand::CompleteRestructuring(c) => c.hyperedge_id,
+            HyperEdgeCommand::AddHyperEdgeTag(c) => c.hyperedge_id,
+            HyperEdgeCommand::RemoveHyperEdgeTag(c) => c.hyperedge_id,
+            HyperEdgeCommand::AddParticipantGroup(c) => c.hyperedge_id,
+            HyperEdgeCommand::RemoveParticipantGroup(c) => c.hyperedge_id,
+            HyperEdgeCommand::AssignToGroup(c) => c.hyperedge_id,
+            HyperEdgeCommand::UnassignFromGroup(c) => c.hyperedge_id,
+            HyperEdgeCommand::ArchiveHyperEdge(c) => c.relationship_id,
+            HyperEdgeCommand::RestoreHyperEdge(c) => c.relationship_id,
+            HyperEdgeCommand::RenameHyperEdge(c) => c.relationship_id,
+            HyperEdgeCommand::DescribeHyperEdge(c) => c.relationship_id,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct CreateHyperEdge {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
     pub identity: MessageIdentity,
     pub hyperedge_id: RelationshipId,
     pub name: String,
     pub category: RelationshipCategory,
     pub initial_participants: IncidenceMatrix,
+    #[serde(default)]
+    pub activation: ActivationMode,
     pub created_by: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ActivateHyperEdge {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
     pub identity: MessageIdentity,
     pub hyperedge_id: RelationshipId,
     pub activated_by: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct AddParticipant {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
     pub identity: MessageIdentity,
     pub hyperedge_id: RelationshipId,
     pub participant: EntityRef,
@@ -138,7 +492,9 @@ pub struct AddParticipant {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct RemoveParticipant {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
     pub identity: MessageIdentity,
     pub hyperedge_id: RelationshipId,
     pub participant: EntityRef,
@@ -147,7 +503,9 @@ pub struct RemoveParticipant {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ChangeParticipantRole {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
     pub identity: MessageIdentity,
     pub hyperedge_id: RelationshipId,
     pub participant: EntityRef,
@@ -156,22 +514,429 @@ pub struct ChangeParticipantRole {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ChangeParticipantWeight {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub hyperedge_id: RelationshipId,
+    pub participant: EntityRef,
+    pub new_weight: f64,
+    pub changed_by: String,
+}
+
+/// A participant's acknowledgement, counted toward a quorum
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct AcknowledgeParticipation {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub hyperedge_id: RelationshipId,
+    pub participant: EntityRef,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct TerminateHyperEdge {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
     pub identity: MessageIdentity,
     pub hyperedge_id: RelationshipId,
     pub reason: String,
     pub terminated_by: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct UpdateHyperEdgeQuality {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub hyperedge_id: RelationshipId,
+    pub new_quality: RelationshipQuality,
+    pub reason: String,
+}
+
+/// Take an active hyperedge out of service while its participants change
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct BeginRestructuring {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub hyperedge_id: RelationshipId,
+    pub reason: Option<String>,
+    pub begun_by: String,
+}
+
+/// Return a restructured hyperedge to service
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CompleteRestructuring {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub hyperedge_id: RelationshipId,
+    pub completed_by: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct AddHyperEdgeTag {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub hyperedge_id: RelationshipId,
+    pub tag: String,
+    pub tagged_by: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RemoveHyperEdgeTag {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub hyperedge_id: RelationshipId,
+    pub tag: String,
+    pub removed_by: String,
+}
+
+/// Add a participant group, optionally nested in an existing one
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct AddParticipantGroup {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub hyperedge_id: RelationshipId,
+    pub name: String,
+    pub role: ParticipantRole,
+    pub parent: Option<String>,
+    pub added_by: String,
+}
+
+/// Remove a participant group; its subgroups move up to its parent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RemoveParticipantGroup {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub hyperedge_id: RelationshipId,
+    pub name: String,
+    pub removed_by: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct AssignToGroup {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub hyperedge_id: RelationshipId,
+    pub participant: EntityRef,
+    pub group: String,
+    pub assigned_by: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct UnassignFromGroup {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub hyperedge_id: RelationshipId,
+    pub participant: EntityRef,
+    pub group: String,
+    pub unassigned_by: String,
+}
+
+// ============================================================================
+// Restructuring Commands
+// ============================================================================
+
+/// Commands that replace relationships with differently shaped ones,
+/// spanning edge and hyperedge aggregates
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum RestructureCommand {
+    PromoteEdgeToHyperEdge(PromoteEdgeToHyperEdge),
+    MergeEdges(MergeEdges),
+    DecomposeHyperEdge(DecomposeHyperEdge),
+}
+
+impl RestructureCommand {
+    /// Get the command type name used in NATS subjects
+    pub fn command_type(&self) -> &'static str {
+        match self {
+            RestructureCommand::PromoteEdgeToHyperEdge(_) => "promote_edge_to_hyperedge",
+            RestructureCommand::MergeEdges(_) => "merge_edges",
+            RestructureCommand::DecomposeHyperEdge(_) => "decompose_hyperedge",
+        }
+    }
+}
+
+/// Replace an edge with a hyperedge over its endpoints and new participants
+///
+/// The edge is terminated; the hyperedge inherits its quality, evidence
+/// and properties, and records the edge it was promoted from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PromoteEdgeToHyperEdge {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub edge_id: RelationshipId,
+    pub hyperedge_id: RelationshipId,
+    pub name: String,
+    /// Category of the hyperedge; the edge's category if not given
+    pub category: Option<RelationshipCategory>,
+    pub source_role: ParticipantRole,
+    pub target_role: ParticipantRole,
+    pub new_participants: IncidenceMatrix,
+    pub promoted_by: String,
+}
+
+/// Fold a duplicate edge into another with the same endpoints and category
+///
+/// The survivor gains the duplicate's evidence and any properties it lacks
+/// and keeps the higher confidence; the duplicate is terminated (rejected
+/// if never activated) with a reference to the survivor.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct MergeEdges {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub survivor_id: RelationshipId,
+    pub duplicate_id: RelationshipId,
+    pub merged_by: String,
+}
+
+/// Derive edges between participants of a hyperedge by role, e.g.
+/// leader -> member Management edges from a team
+///
+/// One edge is created from each participant in `from_role` to each other
+/// participant in `to_role`; the hyperedge is left as it is.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DecomposeHyperEdge {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub hyperedge_id: RelationshipId,
+    pub from_role: ParticipantRole,
+    pub to_role: ParticipantRole,
+    pub category: RelationshipCategory,
+    pub decomposed_by: String,
+}
+
+// ============================================================================
+// Archive Commands
+// ============================================================================
+
+/// Archive a terminated, rejected or dissolved relationship
+///
+/// Archived relationships are hidden from default queries but kept, with
+/// their history, until restored.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ArchiveRelationship {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub relationship_id: RelationshipId,
+    pub reason: Option<String>,
+    pub archived_by: String,
+}
+
+/// Restore an archived relationship to the state it was archived from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RestoreRelationship {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub relationship_id: RelationshipId,
+    pub restored_by: String,
+}
+
+// ============================================================================
+// Naming Commands
+// ============================================================================
+
+/// Rename a relationship
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RenameRelationship {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub relationship_id: RelationshipId,
+    pub name: String,
+    pub renamed_by: String,
+}
+
+/// Set or clear a relationship's description
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct UpdateDescription {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub relationship_id: RelationshipId,
+    pub description: Option<String>,
+    pub updated_by: String,
+}
+
+// ============================================================================
+// Template Commands
+// ============================================================================
+
+/// Create a relationship from a template registered in the space
+///
+/// The template supplies the category, default quality, property defaults
+/// and validity term; the command supplies the endpoints. `properties`
+/// override the template's defaults.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CreateFromTemplate {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub relationship_id: RelationshipId,
+    pub template: String,
+    pub name: String,
+    pub endpoints: TemplateEndpoints,
+    #[serde(default)]
+    pub properties: HashMap<String, serde_json::Value>,
+    /// End of validity; the template's term from now if not given
+    #[serde(default)]
+    pub valid_until: Option<DateTime<Utc>>,
+    pub created_by: String,
+}
+
+/// Endpoints of a relationship created from a template
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum TemplateEndpoints {
+    Edge { source: EntityRef, target: EntityRef },
+    HyperEdge { participants: IncidenceMatrix },
+}
+
+// ============================================================================
+// Batch Commands
+// ============================================================================
+
+/// How a batch treats items the space refuses
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum BatchMode {
+    /// Any refused item rejects the whole batch
+    #[default]
+    AllOrNothing,
+    /// Refused items are skipped and reported; the rest are applied
+    Partial,
+}
+
+/// Create many edges at once, e.g. for an import
+///
+/// Items are decided in order, each against the space as the earlier items
+/// left it, so a batch may not create an edge twice.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CreateEdgesBatch {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub edges: Vec<BatchEdge>,
+    #[serde(default)]
+    pub mode: BatchMode,
+    pub created_by: String,
+}
+
+/// One edge of a `CreateEdgesBatch`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct BatchEdge {
+    pub edge_id: RelationshipId,
+    pub source: EntityRef,
+    pub target: EntityRef,
+    pub category: RelationshipCategory,
+    pub name: String,
+    pub quality: Option<RelationshipQuality>,
+}
+
+/// Add many participants at once, to one or several hyperedges
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct AddParticipantsBatch {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub participants: Vec<BatchParticipant>,
+    #[serde(default)]
+    pub mode: BatchMode,
+    pub added_by: String,
+}
+
+/// One participant of an `AddParticipantsBatch`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct BatchParticipant {
+    pub hyperedge_id: RelationshipId,
+    pub participant: EntityRef,
+    pub role: ParticipantRole,
+    pub weight: f64,
+}
+
+// ============================================================================
+// Policy Commands
+// ============================================================================
+
+/// Put a constraint issued by a Policy entity in force
+///
+/// Edges already in the space are not re-checked.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EnactPolicy {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub policy: RelationshipPolicy,
+    pub enacted_by: String,
+}
+
+/// Withdraw every constraint issued by a Policy entity
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RevokePolicy {
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub policy: EntityRef,
+    pub reason: Option<String>,
+    pub revoked_by: String,
+}
+
 // ============================================================================
 // Unified Relationship Command
 // ============================================================================
 
 /// Unified command type for the relationship domain
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum RelationshipCommand {
     Edge(EdgeCommand),
     HyperEdge(HyperEdgeCommand),
+    Restructure(RestructureCommand),
+    CreateFromTemplate(CreateFromTemplate),
+    CreateEdgesBatch(CreateEdgesBatch),
+    AddParticipantsBatch(AddParticipantsBatch),
+    ArchiveRelationship(ArchiveRelationship),
+    RestoreRelationship(RestoreRelationship),
+    RenameRelationship(RenameRelationship),
+    UpdateDescription(UpdateDescription),
+    EnactPolicy(EnactPolicy),
+    RevokePolicy(RevokePolicy),
+}
+
+impl RelationshipCommand {
+    /// Get the command type name used in NATS subjects
+    pub fn command_type(&self) -> &'static str {
+        match self {
+            RelationshipCommand::Edge(c) => c.command_type(),
+            RelationshipCommand::HyperEdge(c) => c.command_type(),
+            RelationshipCommand::Restructure(c) => c.command_type(),
+            RelationshipCommand::CreateFromTemplate(_) => "create_from_template",
+            RelationshipCommand::CreateEdgesBatch(_) => "create_edges_batch",
+            RelationshipCommand::AddParticipantsBatch(_) => "add_participants_batch",
+            RelationshipCommand::ArchiveRelationship(_) => "archive_relationship",
+            RelationshipCommand::RestoreRelationship(_) => "restore_relationship",
+            RelationshipCommand::RenameRelationship(_) => "rename_relationship",
+            RelationshipCommand::UpdateDescription(_) => "update_description",
+            RelationshipCommand::EnactPolicy(_) => "enact_policy",
+            RelationshipCommand::RevokePolicy(_) => "revoke_policy",
+        }
+    }
 }
 
 impl From<EdgeCommand> for RelationshipCommand {
@@ -185,3 +950,9 @@ impl From<HyperEdgeCommand> for RelationshipCommand {
         RelationshipCommand::HyperEdge(cmd)
     }
 }
+
+impl From<RestructureCommand> for RelationshipCommand {
+    fn from(cmd: RestructureCommand) -> Self {
+        RelationshipCommand::Restructure(cmd)
+    }
+}
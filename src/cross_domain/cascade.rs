@@ -0,0 +1,203 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Cascade Rules
+//!
+//! When an upstream entity leaves the world - a person is deactivated, an
+//! organization dissolved, a location retired, an agent decommissioned -
+//! its relationships are cascaded according to a rules table keyed by
+//! entity type and relationship category:
+//!
+//! ```text
+//! entity type    category     applies to                 action
+//! Person         *            edges at either end        Suspend
+//! Agent          *            edges at either end        Suspend
+//! Organization   Employment   edges targeting it         Terminate
+//! Organization   Membership   edges targeting it         Terminate
+//! Organization   Ownership    edges targeting it         Terminate
+//! Organization   *            hyperedges it is Primary   Terminate
+//! Location       Contains     edges from it              Terminate
+//! Location       PartOf       edges targeting it         Terminate
+//! ```
+//!
+//! That is `CascadeRules::standard()`. The most recently added matching
+//! rule decides, so a deployment can override single cells:
+//!
+//! ```text
+//! CascadeRules::standard()
+//!     .with_rule(CascadeRule::edges(Person, Some(Employment), Both, FlagForReview))
+//! ```
+//!
+//! Relationships no rule matches are left alone.
+
+use crate::aggregates::{EdgeConcept, HyperEdgeConcept};
+use crate::invariants::EndpointSide;
+use crate::value_objects::{EntityRef, EntityType, ParticipantRole, RelationshipCategory};
+use serde::{Deserialize, Serialize};
+
+/// Tag marking relationships flagged for review by a cascade
+pub const REVIEW_TAG: &str = "needs-review";
+
+/// What a cascade does to a relationship
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CascadeAction {
+    /// Suspend active edges and reject ones not yet active; hyperedges,
+    /// which cannot be suspended, are flagged for review
+    Suspend,
+    /// End the relationship: reject edges not yet active, terminate the rest
+    Terminate,
+    /// Tag the relationship with `REVIEW_TAG` and leave it as it is
+    FlagForReview,
+    /// Leave the relationship alone
+    Ignore,
+}
+
+/// Which relationships of the entity a rule covers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CascadeTarget {
+    /// Edges with the entity at the given end (`Both` = either end)
+    Edges { side: EndpointSide },
+    /// Hyperedges the entity participates in, in the given role
+    /// (`None` = any role)
+    HyperEdges {
+        #[serde(default)]
+        role: Option<ParticipantRole>,
+    },
+}
+
+/// One row of the rules table
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CascadeRule {
+    /// Type of the entity that left
+    pub entity_type: EntityType,
+    /// Relationship category (`None` = any)
+    #[serde(default)]
+    pub category: Option<RelationshipCategory>,
+    /// Relationships covered
+    pub applies_to: CascadeTarget,
+    /// What happens to them
+    pub action: CascadeAction,
+}
+
+impl CascadeRule {
+    /// Rule for edges
+    pub fn edges(
+        entity_type: EntityType,
+        category: Option<RelationshipCategory>,
+        side: EndpointSide,
+        action: CascadeAction,
+    ) -> Self {
+        Self {
+            entity_type,
+            category,
+            applies_to: CascadeTarget::Edges { side },
+            action,
+        }
+    }
+
+    /// Rule for hyperedges
+    pub fn hyperedges(
+        entity_type: EntityType,
+        category: Option<RelationshipCategory>,
+        role: Option<ParticipantRole>,
+        action: CascadeAction,
+    ) -> Self {
+        Self {
+            entity_type,
+            category,
+            applies_to: CascadeTarget::HyperEdges { role },
+            action,
+        }
+    }
+
+    fn covers_category(&self, entity: &EntityRef, category: &RelationshipCategory) -> bool {
+        self.entity_type == entity.entity_type && self.category.as_ref().is_none_or(|c| c == category)
+    }
+
+    fn covers_edge(&self, entity: &EntityRef, edge: &EdgeConcept) -> bool {
+        let CascadeTarget::Edges { side } = &self.applies_to else {
+            return false;
+        };
+        let at_side = match side {
+            EndpointSide::Source => edge.source.same_entity(entity),
+            EndpointSide::Target => edge.target.same_entity(entity),
+            EndpointSide::Both => edge.source.same_entity(entity) || edge.target.same_entity(entity),
+        };
+        at_side && self.covers_category(entity, &edge.category)
+    }
+
+    fn covers_hyperedge(&self, entity: &EntityRef, hyperedge: &HyperEdgeConcept) -> bool {
+        let CascadeTarget::HyperEdges { role } = &self.applies_to else {
+            return false;
+        };
+        self.covers_category(entity, &hyperedge.category)
+            && hyperedge
+                .participants
+                .participants()
+                .any(|p| p.entity_ref.same_entity(entity) && role.as_ref().is_none_or(|r| *r == p.role))
+    }
+}
+
+/// The rules table
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CascadeRules {
+    /// Rules, in the order they were added
+    pub rules: Vec<CascadeRule>,
+}
+
+impl CascadeRules {
+    /// Create a table without rules: nothing cascades
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The built-in cascades (see the module documentation)
+    pub fn standard() -> Self {
+        use CascadeAction::*;
+        use EndpointSide::*;
+        use RelationshipCategory::*;
+
+        Self::new()
+            .with_rule(CascadeRule::edges(EntityType::Person, None, Both, Suspend))
+            .with_rule(CascadeRule::edges(EntityType::Agent, None, Both, Suspend))
+            .with_rule(CascadeRule::edges(EntityType::Organization, Some(Employment), Target, Terminate))
+            .with_rule(CascadeRule::edges(EntityType::Organization, Some(Membership), Target, Terminate))
+            .with_rule(CascadeRule::edges(EntityType::Organization, Some(Ownership), Target, Terminate))
+            .with_rule(CascadeRule::hyperedges(
+                EntityType::Organization,
+                None,
+                Some(ParticipantRole::Primary),
+                Terminate,
+            ))
+            .with_rule(CascadeRule::edges(EntityType::Location, Some(Contains), Source, Terminate))
+            .with_rule(CascadeRule::edges(EntityType::Location, Some(PartOf), Target, Terminate))
+    }
+
+    /// Add a rule, taking precedence over the rules already present
+    pub fn with_rule(mut self, rule: CascadeRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Action for an edge of an entity that left (`None` = no rule matches)
+    pub fn edge_action(&self, entity: &EntityRef, edge: &EdgeConcept) -> Option<CascadeAction> {
+        self.rules
+            .iter()
+            .rev()
+            .find(|r| r.covers_edge(entity, edge))
+            .map(|r| r.action)
+    }
+
+    /// Action for a hyperedge of an entity that left (`None` = no rule
+    /// matches)
+    pub fn hyperedge_action(&self, entity: &EntityRef, hyperedge: &HyperEdgeConcept) -> Option<CascadeAction> {
+        self.rules
+            .iter()
+            .rev()
+            .find(|r| r.covers_hyperedge(entity, hyperedge))
+            .map(|r| r.action)
+    }
+}
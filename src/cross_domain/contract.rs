@@ -0,0 +1,637 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Cross-Domain Contract Testing
+//!
+//! Stub emitters for upstream Person, Organization, Location, Agent and Policy events, in the wire
+//! shape the cross-domain handlers expect, plus a harness that feeds them
+//! through the handler and asserts on the reaction.
+//!
+//! Upstream teams can use the stubs to check their payloads still parse;
+//! downstream teams embedding the engine can check the reactions.
+//!
+//! ```rust,ignore
+//! let mut harness = ContractHarness::new(space);
+//! let reaction = harness.deliver(&StubEmitter::person_deactivated(alice_id))?;
+//!
+//! reaction.assert_suspends(&employment_id);
+//! reaction.assert_only(&["suspend_edge"]);
+//! ```
+
+use super::{
+    AgentDecommissioned, CrossDomainEvent, CrossDomainEventHandler, CrossDomainRegistry, LocationRetired, OrganizationDissolved,
+    OrganizationMerged, OrganizationRenamed, PersonDeactivated, PersonMerged, PolicyEnacted, PolicyRevoked, REVIEW_TAG,
+};
+use crate::invariants::PolicyRule;
+use crate::aggregates::RelationshipSpace;
+use crate::commands::{EdgeCommand, HyperEdgeCommand, RelationshipCommand, RestructureCommand};
+use crate::nats::Transport;
+use crate::value_objects::{EntityRef, RelationshipId};
+use crate::RelationshipResult;
+use bytes::Bytes;
+use chrono::Utc;
+use uuid::Uuid;
+
+/// Upstream message as it appears on the wire
+#[derive(Debug, Clone)]
+pub struct StubMessage {
+    /// Subject published on
+    pub subject: String,
+    /// JSON payload
+    pub payload: Bytes,
+}
+
+impl StubMessage {
+    /// Build the wire message for an upstream event
+    pub fn from_event(event: &CrossDomainEvent) -> RelationshipResult<Self> {
+        Ok(Self {
+            subject: event.subject().to_string(),
+            payload: Bytes::from(event.to_payload()?),
+        })
+    }
+
+    /// Publish on a transport
+    pub async fn emit(&self, transport: &impl Transport) -> RelationshipResult<()> {
+        transport.publish(&self.subject, self.payload.clone()).await
+    }
+}
+
+/// Stub emitters for upstream domain events
+pub struct StubEmitter;
+
+impl StubEmitter {
+    /// `person.events.person_deactivated`
+    pub fn person_deactivated(person_id: Uuid) -> StubMessage {
+        Self::message(CrossDomainEvent::PersonDeactivated(PersonDeactivated {
+            person_id,
+            reason: None,
+            deactivated_at: Utc::now(),
+        }))
+    }
+
+    /// `person.events.person_merged`
+    pub fn person_merged(merged_person_id: Uuid, surviving_person_id: Uuid) -> StubMessage {
+        Self::message(CrossDomainEvent::PersonMerged(PersonMerged {
+            merged_person_id,
+            surviving_person_id,
+            merged_at: Utc::now(),
+        }))
+    }
+
+    /// `organization.events.organization_dissolved`
+    pub fn organization_dissolved(organization_id: Uuid) -> StubMessage {
+        Self::message(CrossDomainEvent::OrganizationDissolved(OrganizationDissolved {
+            organization_id,
+            reason: None,
+            dissolved_at: Utc::now(),
+        }))
+    }
+
+    /// `organization.events.organization_merged`
+    pub fn organization_merged(merged_organization_id: Uuid, surviving_organization_id: Uuid) -> StubMessage {
+        Self::message(CrossDomainEvent::OrganizationMerged(OrganizationMerged {
+            merged_organization_id,
+            surviving_organization_id,
+            merged_at: Utc::now(),
+        }))
+    }
+
+    /// `organization.events.organization_renamed`
+    pub fn organization_renamed(organization_id: Uuid, new_name: &str) -> StubMessage {
+        Self::message(CrossDomainEvent::OrganizationRenamed(OrganizationRenamed {
+            organization_id,
+            old_name: None,
+            new_name: new_name.to_string(),
+            renamed_at: Utc::now(),
+        }))
+    }
+
+    /// `location.events.location_retired`
+    pub fn location_retired(location_id: Uuid) -> StubMessage {
+        Self::message(CrossDomainEvent::LocationRetired(LocationRetired {
+            location_id,
+            reason: None,
+            retired_at: Utc::now(),
+        }))
+    }
+
+    /// `agent.events.agent_decommissioned`
+    pub fn agent_decommissioned(agent_id: Uuid) -> StubMessage {
+        Self::message(CrossDomainEvent::AgentDecommissioned(AgentDecommissioned {
+            agent_id,
+            reason: None,
+            decommissioned_at: Utc::now(),
+        }))
+    }
+
+    /// `policy.events.policy_enacted`
+    pub fn policy_enacted(policy_id: Uuid, name: &str, relationship_rules: Vec<PolicyRule>) -> StubMessage {
+        Self::message(CrossDomainEvent::PolicyEnacted(PolicyEnacted {
+            policy_id,
+            name: name.to_string(),
+            relationship_rules,
+            enacted_at: Utc::now(),
+        }))
+    }
+
+    /// `policy.events.policy_revoked`
+    pub fn policy_revoked(policy_id: Uuid) -> StubMessage {
+        Self::message(CrossDomainEvent::PolicyRevoked(PolicyRevoked {
+            policy_id,
+            reason: None,
+            revoked_at: Utc::now(),
+        }))
+    }
+
+    fn message(event: CrossDomainEvent) -> StubMessage {
+        StubMessage::from_event(&event).expect("stub events always serialize")
+    }
+}
+
+/// Feeds stub messages through the cross-domain handlers
+pub struct ContractHarness {
+    space: RelationshipSpace,
+    registry: CrossDomainRegistry,
+}
+
+impl ContractHarness {
+    /// Create a harness over a space
+    pub fn new(space: RelationshipSpace) -> Self {
+        Self {
+            space,
+            registry: CrossDomainRegistry::standard(),
+        }
+    }
+
+    /// Use a specific handler instead of the standard one
+    pub fn with_handler(mut self, handler: CrossDomainEventHandler) -> Self {
+        self.registry = CrossDomainRegistry::new().with_handler(handler);
+        self
+    }
+
+    /// Dispatch through a registry of handlers
+    pub fn with_registry(mut self, registry: CrossDomainRegistry) -> Self {
+        self.registry = registry;
+        self
+    }
+
+    /// The space reactions are computed against
+    pub fn space(&self) -> &RelationshipSpace {
+        &self.space
+    }
+
+    /// Mutable access to the space, for arranging test fixtures
+    pub fn space_mut(&mut self) -> &mut RelationshipSpace {
+        &mut self.space
+    }
+
+    /// Deliver a message exactly as it would arrive from NATS
+    pub fn deliver(&self, message: &StubMessage) -> RelationshipResult<Reaction> {
+        let commands = self
+            .registry
+            .handle_message(&self.space, &message.subject, &message.payload)?;
+        Ok(Reaction { commands })
+    }
+}
+
+/// Commands the relationship domain issued in reaction to an upstream event
+#[derive(Debug, Clone)]
+pub struct Reaction {
+    /// Issued commands, in order
+    pub commands: Vec<RelationshipCommand>,
+}
+
+impl Reaction {
+    /// Check if nothing was issued
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Command type names, in order
+    pub fn command_types(&self) -> Vec<&'static str> {
+        self.commands.iter().map(|c| c.command_type()).collect()
+    }
+
+    /// Check if an edge was suspended
+    pub fn suspends(&self, edge_id: &RelationshipId) -> bool {
+        self.edge_commands()
+            .any(|c| matches!(c, EdgeCommand::SuspendEdge(s) if s.edge_id == *edge_id))
+    }
+
+    /// Check if an edge was rejected
+    pub fn rejects(&self, edge_id: &RelationshipId) -> bool {
+        self.edge_commands()
+            .any(|c| matches!(c, EdgeCommand::RejectEdge(r) if r.edge_id == *edge_id))
+    }
+
+    /// Check if an edge was terminated
+    pub fn terminates(&self, edge_id: &RelationshipId) -> bool {
+        self.edge_commands()
+            .any(|c| matches!(c, EdgeCommand::TerminateEdge(t) if t.edge_id == *edge_id))
+    }
+
+    /// Check if an edge was flagged for review
+    pub fn flags_for_review(&self, edge_id: &RelationshipId) -> bool {
+        self.edge_commands()
+            .any(|c| matches!(c, EdgeCommand::AddEdgeTag(t) if t.edge_id == *edge_id && t.tag == REVIEW_TAG))
+    }
+
+    /// Check if a hyperedge was dissolved
+    pub fn dissolves(&self, hyperedge_id: &RelationshipId) -> bool {
+        self.hyperedge_commands()
+            .any(|c| matches!(c, HyperEdgeCommand::TerminateHyperEdge(t) if t.hyperedge_id == *hyperedge_id))
+    }
+
+    /// Check if an edge property was set
+    pub fn sets_property(&self, edge_id: &RelationshipId, key: &str) -> bool {
+        self.edge_commands()
+            .any(|c| matches!(c, EdgeCommand::SetEdgeProperty(s) if s.edge_id == *edge_id && s.key == key))
+    }
+
+    /// Check if an edge was created between two entities
+    pub fn creates_edge(&self, source: &EntityRef, target: &EntityRef) -> bool {
+        self.edge_commands().any(|c| {
+            matches!(c, EdgeCommand::CreateEdge(e)
+                if e.source.same_entity(source) && e.target.same_entity(target))
+        })
+    }
+
+    /// Check if an edge was redirected onto an entity
+    pub fn redirects(&self, edge_id: &RelationshipId, entity: &EntityRef) -> bool {
+        self.edge_commands().any(|c| {
+            matches!(c, EdgeCommand::RedirectEdge(r)
+                if r.edge_id == *edge_id
+                    && [&r.new_source, &r.new_target].into_iter().flatten().any(|e| e.same_entity(entity)))
+        })
+    }
+
+    /// Check if an edge was merged into another
+    pub fn merges(&self, survivor_id: &RelationshipId, duplicate_id: &RelationshipId) -> bool {
+        self.commands.iter().any(|c| {
+            matches!(c, RelationshipCommand::Restructure(RestructureCommand::MergeEdges(m))
+                if m.survivor_id == *survivor_id && m.duplicate_id == *duplicate_id)
+        })
+    }
+
+    /// Check if an entity was added to a hyperedge
+    pub fn adds_participant(&self, hyperedge_id: &RelationshipId, entity: &EntityRef) -> bool {
+        self.hyperedge_commands().any(|c| {
+            matches!(c, HyperEdgeCommand::AddParticipant(a)
+                if a.hyperedge_id == *hyperedge_id && a.participant.same_entity(entity))
+        })
+    }
+
+    /// Check if an entity was removed from a hyperedge
+    pub fn removes_participant(&self, hyperedge_id: &RelationshipId, entity: &EntityRef) -> bool {
+        self.hyperedge_commands().any(|c| {
+            matches!(c, HyperEdgeCommand::RemoveParticipant(r)
+                if r.hyperedge_id == *hyperedge_id && r.participant.same_entity(entity))
+        })
+    }
+
+    /// Panic unless an edge was suspended
+    pub fn assert_suspends(&self, edge_id: &RelationshipId) {
+        assert!(self.suspends(edge_id), "expected {} to be suspended, got {:?}", edge_id, self.command_types());
+    }
+
+    /// Panic unless an edge was terminated
+    pub fn assert_terminates(&self, edge_id: &RelationshipId) {
+        assert!(self.terminates(edge_id), "expected {} to be terminated, got {:?}", edge_id, self.command_types());
+    }
+
+    /// Panic unless every issued command is one of the given types
+    pub fn assert_only(&self, command_types: &[&str]) {
+        let unexpected: Vec<_> = self
+            .command_types()
+            .into_iter()
+            .filter(|t| !command_types.contains(t))
+            .collect();
+        assert!(unexpected.is_empty(), "unexpected commands: {:?}", unexpected);
+    }
+
+    fn edge_commands(&self) -> impl Iterator<Item = &EdgeCommand> {
+        self.commands.iter().filter_map(|c| match c {
+            RelationshipCommand::Edge(e) => Some(e),
+            _ => None,
+        })
+    }
+
+    fn hyperedge_commands(&self) -> impl Iterator<Item = &HyperEdgeCommand> {
+        self.commands.iter().filter_map(|c| match c {
+            RelationshipCommand::HyperEdge(h) => Some(h),
+            _ => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregates::{EdgeConcept, EdgeState, HyperEdgeConcept};
+    use crate::nats::MockTransport;
+    use crate::value_objects::{EntityType, ParticipantRole, RelationshipCategory};
+    use cim_domain_spaces::TopologicalSpaceId;
+
+    fn harness() -> ContractHarness {
+        ContractHarness::new(RelationshipSpace::new("Contract", TopologicalSpaceId::new()))
+    }
+
+    fn employment(person: &EntityRef, org: &EntityRef, active: bool) -> EdgeConcept {
+        let mut edge = EdgeConcept::new("Employment", person.clone(), org.clone(), RelationshipCategory::Employment);
+        if active {
+            edge.activate().unwrap();
+        }
+        edge
+    }
+
+    #[test]
+    fn test_person_deactivated_contract() {
+        let mut harness = harness();
+        let person = EntityRef::person(Uuid::now_v7());
+        let active = employment(&person, &EntityRef::organization(Uuid::now_v7()), true);
+        let proposed = employment(&person, &EntityRef::organization(Uuid::now_v7()), false);
+        let (active_id, proposed_id) = (active.id, proposed.id);
+        harness.space_mut().add_edge(active).unwrap();
+        harness.space_mut().add_edge(proposed).unwrap();
+
+        let reaction = harness
+            .deliver(&StubEmitter::person_deactivated(person.entity_id))
+            .unwrap();
+        reaction.assert_suspends(&active_id);
+        assert!(reaction.rejects(&proposed_id));
+        reaction.assert_only(&["suspend_edge", "reject_edge"]);
+
+        // Unrelated people cause no reaction
+        let other = harness.deliver(&StubEmitter::person_deactivated(Uuid::now_v7())).unwrap();
+        assert!(other.is_empty());
+    }
+
+    #[test]
+    fn test_organization_dissolved_contract() {
+        let mut harness = harness();
+        let org = EntityRef::organization(Uuid::now_v7());
+        let edge = employment(&EntityRef::person(Uuid::now_v7()), &org, true);
+        let listing = EdgeConcept::new(
+            "Supplier listing",
+            org.clone(),
+            EntityRef::organization(Uuid::now_v7()),
+            RelationshipCategory::References,
+        );
+        let (edge_id, listing_id) = (edge.id, listing.id);
+        harness.space_mut().add_edge(edge).unwrap();
+        harness.space_mut().add_edge(listing).unwrap();
+
+        let mut board = HyperEdgeConcept::new("Board", RelationshipCategory::Membership);
+        board.add_participant(org.clone(), ParticipantRole::Primary, 1.0).unwrap();
+        let mut consortium = HyperEdgeConcept::new("Consortium", RelationshipCategory::Membership);
+        consortium.add_participant(org.clone(), ParticipantRole::Member, 1.0).unwrap();
+        let (board_id, consortium_id) = (board.id, consortium.id);
+        harness.space_mut().add_hyperedge(board);
+        harness.space_mut().add_hyperedge(consortium);
+
+        let reaction = harness
+            .deliver(&StubEmitter::organization_dissolved(org.entity_id))
+            .unwrap();
+        reaction.assert_terminates(&edge_id);
+        assert!(!reaction.terminates(&listing_id));
+        assert!(reaction.dissolves(&board_id));
+        assert!(!reaction.dissolves(&consortium_id));
+        reaction.assert_only(&["terminate_edge", "terminate_hyperedge"]);
+    }
+
+    #[test]
+    fn test_organization_renamed_contract() {
+        use crate::cross_domain::ORGANIZATION_NAME_PROPERTY;
+
+        let mut harness = harness();
+        let org = EntityRef::organization(Uuid::now_v7());
+        let mut cached = employment(&EntityRef::person(Uuid::now_v7()), &org, true);
+        cached
+            .properties
+            .insert(ORGANIZATION_NAME_PROPERTY.to_string(), serde_json::json!("Acme"));
+        let uncached = employment(&EntityRef::person(Uuid::now_v7()), &org, true);
+        let (cached_id, uncached_id) = (cached.id, uncached.id);
+        harness.space_mut().add_edge(cached).unwrap();
+        harness.space_mut().add_edge(uncached).unwrap();
+
+        let reaction = harness
+            .deliver(&StubEmitter::organization_renamed(org.entity_id, "Acme Holdings"))
+            .unwrap();
+        assert!(reaction.sets_property(&cached_id, ORGANIZATION_NAME_PROPERTY));
+        assert!(!reaction.sets_property(&uncached_id, ORGANIZATION_NAME_PROPERTY));
+        reaction.assert_only(&["set_edge_property"]);
+
+        // The cache already holds the name: nothing to refresh
+        let reaction = harness
+            .deliver(&StubEmitter::organization_renamed(org.entity_id, "Acme"))
+            .unwrap();
+        assert!(reaction.is_empty());
+    }
+
+    #[test]
+    fn test_person_merged_contract() {
+        let mut harness = harness();
+        let merged = EntityRef::person(Uuid::now_v7());
+        let surviving = EntityRef::person(Uuid::now_v7());
+        let org = EntityRef::organization(Uuid::now_v7());
+
+        let edge = employment(&merged, &org, true);
+        let edge_id = edge.id;
+        harness.space_mut().add_edge(edge).unwrap();
+        // Both people were employed by the same organization
+        let other_org = EntityRef::organization(Uuid::now_v7());
+        let kept = employment(&surviving, &other_org, true);
+        let parallel = employment(&merged, &other_org, true);
+        let (kept_id, parallel_id) = (kept.id, parallel.id);
+        harness.space_mut().add_edge(kept).unwrap();
+        harness.space_mut().add_edge(parallel).unwrap();
+
+        let mut team = HyperEdgeConcept::new("Team", RelationshipCategory::Membership);
+        team.add_participant(merged.clone(), ParticipantRole::Leader, 1.0).unwrap();
+        team.add_participant(EntityRef::person(Uuid::now_v7()), ParticipantRole::Member, 1.0).unwrap();
+        let team_id = team.id;
+        harness.space_mut().add_hyperedge(team);
+
+        let reaction = harness
+            .deliver(&StubEmitter::person_merged(merged.entity_id, surviving.entity_id))
+            .unwrap();
+        assert!(reaction.redirects(&edge_id, &surviving));
+        assert!(!reaction.terminates(&edge_id));
+        assert!(reaction.redirects(&parallel_id, &surviving));
+        assert!(reaction.merges(&kept_id, &parallel_id));
+        assert!(reaction.adds_participant(&team_id, &surviving));
+        assert!(reaction.removes_participant(&team_id, &merged));
+
+        // The commands apply in order: redirects first, then merges
+        let space = harness.space_mut();
+        for command in reaction.commands {
+            for event in space.handle_command(command).unwrap() {
+                space.apply_event(&event).unwrap();
+            }
+        }
+        assert!(space.get_edge(&edge_id).unwrap().source.same_entity(&surviving));
+        assert_eq!(space.get_edge(&parallel_id).unwrap().state, EdgeState::Terminated);
+    }
+
+    #[test]
+    fn test_organization_merged_contract() {
+        let mut harness = harness();
+        let merged = EntityRef::organization(Uuid::now_v7());
+        let surviving = EntityRef::organization(Uuid::now_v7());
+        let edge = employment(&EntityRef::person(Uuid::now_v7()), &merged, true);
+        let mut ownership = EdgeConcept::new("Owns", surviving.clone(), merged.clone(), RelationshipCategory::Ownership);
+        ownership.activate().unwrap();
+        let (edge_id, ownership_id) = (edge.id, ownership.id);
+        harness.space_mut().add_edge(edge).unwrap();
+        harness.space_mut().add_edge(ownership).unwrap();
+
+        let reaction = harness
+            .deliver(&StubEmitter::organization_merged(merged.entity_id, surviving.entity_id))
+            .unwrap();
+        assert!(reaction.redirects(&edge_id, &surviving));
+        // The survivor would own itself
+        reaction.assert_terminates(&ownership_id);
+        reaction.assert_only(&["redirect_edge", "terminate_edge"]);
+    }
+
+    #[test]
+    fn test_location_retired_contract() {
+        let mut harness = harness();
+        let building = EntityRef::location(Uuid::now_v7());
+        let site = EntityRef::location(Uuid::now_v7());
+        let mut containment = |source: &EntityRef, target: &EntityRef, category| {
+            let mut edge = EdgeConcept::new("Containment", source.clone(), target.clone(), category);
+            edge.activate().unwrap();
+            let id = edge.id;
+            harness.space_mut().add_edge(edge).unwrap();
+            id
+        };
+        let room = containment(&building, &EntityRef::location(Uuid::now_v7()), RelationshipCategory::Contains);
+        let desk = containment(&EntityRef::agent(Uuid::now_v7()), &building, RelationshipCategory::PartOf);
+        let on_site = containment(&building, &site, RelationshipCategory::PartOf);
+
+        let reaction = harness
+            .deliver(&StubEmitter::location_retired(building.entity_id))
+            .unwrap();
+        reaction.assert_terminates(&room);
+        reaction.assert_terminates(&desk);
+        assert!(!reaction.terminates(&on_site));
+        reaction.assert_only(&["terminate_edge"]);
+    }
+
+    #[test]
+    fn test_agent_decommissioned_contract() {
+        let mut harness = harness();
+        let agent = EntityRef::agent(Uuid::now_v7());
+        let mut implements = EdgeConcept::new(
+            "Enforces",
+            agent.clone(),
+            EntityRef::new(EntityType::Policy, Uuid::now_v7()),
+            RelationshipCategory::Implements,
+        );
+        implements.activate().unwrap();
+        let delegation = EdgeConcept::new(
+            "Delegated by",
+            agent.clone(),
+            EntityRef::person(Uuid::now_v7()),
+            RelationshipCategory::Custom("delegated_by".to_string()),
+        );
+        let (implements_id, delegation_id) = (implements.id, delegation.id);
+        harness.space_mut().add_edge(implements).unwrap();
+        harness.space_mut().add_edge(delegation).unwrap();
+
+        let reaction = harness
+            .deliver(&StubEmitter::agent_decommissioned(agent.entity_id))
+            .unwrap();
+        reaction.assert_suspends(&implements_id);
+        assert!(reaction.rejects(&delegation_id));
+        reaction.assert_only(&["suspend_edge", "reject_edge"]);
+    }
+
+    #[test]
+    fn test_policy_contract() {
+        use crate::invariants::{EndpointSide, RelationshipPolicy};
+
+        let mut harness = harness();
+        let policy_id = Uuid::now_v7();
+        let rule = PolicyRule::Forbid {
+            category: Some(RelationshipCategory::Employment),
+            side: EndpointSide::Target,
+            entities: vec![EntityRef::organization(Uuid::now_v7())],
+        };
+
+        let reaction = harness
+            .deliver(&StubEmitter::policy_enacted(policy_id, "Sanctions", vec![rule.clone()]))
+            .unwrap();
+        reaction.assert_only(&["enact_policy"]);
+
+        // Once in force, re-enactment replaces and revocation withdraws
+        let policy = EntityRef::new(EntityType::Policy, policy_id);
+        harness.space_mut().policies.policies.push(RelationshipPolicy::new(policy, "Sanctions", rule.clone()));
+        harness
+            .deliver(&StubEmitter::policy_enacted(policy_id, "Sanctions", vec![rule]))
+            .unwrap()
+            .assert_only(&["revoke_policy", "enact_policy"]);
+        harness
+            .deliver(&StubEmitter::policy_revoked(policy_id))
+            .unwrap()
+            .assert_only(&["revoke_policy"]);
+        assert!(harness
+            .deliver(&StubEmitter::policy_revoked(Uuid::now_v7()))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_custom_cascade_rules_contract() {
+        use crate::cross_domain::{CascadeAction, CascadeRule, CascadeRules};
+        use crate::invariants::EndpointSide;
+
+        // Deactivated employees are reviewed rather than suspended;
+        // everything else keeps the standard cascade
+        let rules = CascadeRules::standard().with_rule(CascadeRule::edges(
+            EntityType::Person,
+            Some(RelationshipCategory::Employment),
+            EndpointSide::Both,
+            CascadeAction::FlagForReview,
+        ));
+        let mut harness = harness().with_handler(CrossDomainEventHandler::new().with_rules(rules));
+        let person = EntityRef::person(Uuid::now_v7());
+        let job = employment(&person, &EntityRef::organization(Uuid::now_v7()), true);
+        let mut friendship = EdgeConcept::new(
+            "Knows",
+            person.clone(),
+            EntityRef::person(Uuid::now_v7()),
+            RelationshipCategory::Custom("knows".to_string()),
+        );
+        friendship.activate().unwrap();
+        let (job_id, friendship_id) = (job.id, friendship.id);
+        harness.space_mut().add_edge(job).unwrap();
+        harness.space_mut().add_edge(friendship).unwrap();
+
+        let reaction = harness
+            .deliver(&StubEmitter::person_deactivated(person.entity_id))
+            .unwrap();
+        assert!(reaction.flags_for_review(&job_id));
+        reaction.assert_suspends(&friendship_id);
+        reaction.assert_only(&["add_edge_tag", "suspend_edge"]);
+
+        // Without rules nothing cascades
+        let silent = harness.with_handler(CrossDomainEventHandler::new().with_rules(CascadeRules::new()));
+        assert!(silent
+            .deliver(&StubEmitter::person_deactivated(person.entity_id))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_stub_emits_on_upstream_subject() {
+        let transport = MockTransport::new();
+        StubEmitter::person_deactivated(Uuid::now_v7())
+            .emit(&transport)
+            .await
+            .unwrap();
+        assert_eq!(transport.published_on("person.events.>").len(), 1);
+    }
+}
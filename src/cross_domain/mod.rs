@@ -4,19 +4,899 @@
 
 //! Cross-Domain Integration for the Relationship Domain
 //!
-//! Handles events from other domains (Person, Organization) to
-//! maintain relationship consistency.
+//! Handles events from other domains (Person, Organization, Location,
+//! Agent, Policy) to maintain relationship consistency.
 //!
 //! ## Event Subscriptions
 //!
 //! - `person.events.>` - React to Person lifecycle events
 //! - `organization.events.>` - React to Organization lifecycle events
+//! - `location.events.>` - React to Location lifecycle events
+//! - `agent.events.>` - React to Agent lifecycle events
+//! - `policy.events.>` - React to Policy lifecycle events (cim-domain-policy)
 //!
 //! ## Reactions
 //!
 //! - PersonDeactivated -> Suspend related edges
-//! - OrganizationDissolved -> Terminate related edges
-//! - PersonMerged -> Update entity references
+//! - OrganizationDissolved -> Terminate employment, membership and ownership
+//!   edges targeting the organization; dissolve hyperedges it is primary in
+//! - OrganizationRenamed -> Refresh the cached organization name on edges
+//! - LocationRetired -> End the Contains / PartOf edges rooted at the location
+//! - AgentDecommissioned -> Suspend related edges (policies it implements,
+//!   delegations it acts under, ...)
+//! - PersonMerged / OrganizationMerged -> Redirect references to the
+//!   surviving entity, merging edges that become parallel
+//! - PolicyEnacted -> Enact the relationship constraints the policy
+//!   carries, replacing the ones it issued before
+//! - PolicyRevoked -> Revoke the constraints the policy issued
+//!
+//! Deactivation, dissolution, retirement and decommissioning cascade as a
+//! `CascadeRules` table says; the reactions above are
+//! `CascadeRules::standard()`, and `with_rules` replaces them.
+//!
+//! Reactions are expressed as relationship commands; the handler never
+//! mutates the space itself.
+//!
+//! ## Late Events
+//!
+//! Upstream and relationship events are not ordered relative to each
+//! other. An `UpstreamLedger` drops stale or redelivered upstream events and
+//! remembers the rest, so an edge or hyperedge processed after an upstream
+//! event that predates it can be reconciled with `reconcile_edge` /
+//! `reconcile_hyperedge`. Either arrival order yields the same commands.
+//!
+//! ## Registry
+//!
+//! Upstream domains are integrated through the `CrossDomainHandler` trait.
+//! A `CrossDomainRegistry` dispatches messages to the registered handlers
+//! by subject; `CrossDomainRegistry::standard()` holds the built-in handler
+//! for the domains above.
+//!
+//! ## Contract Testing
+//!
+//! The `contract` module emits upstream events in the exact wire shape
+//! these handlers parse, and asserts on the resulting commands.
+
+mod cascade;
+pub mod contract;
+mod registry;
+
+pub use cascade::{CascadeAction, CascadeRule, CascadeRules, CascadeTarget, REVIEW_TAG};
+pub use registry::{CrossDomainHandler, CrossDomainRegistry};
+
+use crate::aggregates::{EdgeConcept, EdgeState, HyperEdgeConcept, RelationshipSpace};
+use crate::algebra::RelationshipKey;
+use crate::commands::{
+    AddEdgeTag, AddHyperEdgeTag, AddParticipant, EdgeCommand, EnactPolicy, HyperEdgeCommand, MergeEdges, RedirectEdge, RejectEdge,
+    RelationshipCommand, RemoveParticipant, RestructureCommand, RevokePolicy, SetEdgeProperty, SuspendEdge, TerminateEdge,
+    TerminateHyperEdge,
+};
+use crate::invariants::{PolicyRule, RelationshipPolicy};
+use crate::value_objects::{EntityKey, EntityRef, EntityType, RelationshipId};
+use crate::{RelationshipError, RelationshipResult};
+use chrono::{DateTime, Utc};
+use cim_domain::state_machine::State;
+use cim_domain::MessageIdentity;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Subjects the relationship domain subscribes to
+pub const SUBSCRIPTIONS: [&str; 5] = [
+    "person.events.>",
+    "organization.events.>",
+    "location.events.>",
+    "agent.events.>",
+    "policy.events.>",
+];
+
+/// Actor recorded on commands issued in reaction to upstream events
+pub const CROSS_DOMAIN_ACTOR: &str = "relationship.cross_domain";
+
+/// Edge property caching the display name of an organization endpoint
+pub const ORGANIZATION_NAME_PROPERTY: &str = "organization_name";
+
+// ============================================================================
+// Upstream Events
+// ============================================================================
+
+/// `person.events.person_deactivated`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersonDeactivated {
+    pub person_id: Uuid,
+    #[serde(default)]
+    pub reason: Option<String>,
+    pub deactivated_at: DateTime<Utc>,
+}
+
+/// `person.events.person_merged`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersonMerged {
+    /// Person that no longer exists
+    pub merged_person_id: Uuid,
+    /// Person it was merged into
+    pub surviving_person_id: Uuid,
+    pub merged_at: DateTime<Utc>,
+}
+
+/// `organization.events.organization_dissolved`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrganizationDissolved {
+    pub organization_id: Uuid,
+    #[serde(default)]
+    pub reason: Option<String>,
+    pub dissolved_at: DateTime<Utc>,
+}
+
+/// `organization.events.organization_merged`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrganizationMerged {
+    /// Organization that no longer exists
+    pub merged_organization_id: Uuid,
+    /// Organization it was merged into
+    pub surviving_organization_id: Uuid,
+    pub merged_at: DateTime<Utc>,
+}
+
+/// `organization.events.organization_renamed`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrganizationRenamed {
+    pub organization_id: Uuid,
+    #[serde(default)]
+    pub old_name: Option<String>,
+    pub new_name: String,
+    pub renamed_at: DateTime<Utc>,
+}
+
+/// `location.events.location_retired`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocationRetired {
+    pub location_id: Uuid,
+    #[serde(default)]
+    pub reason: Option<String>,
+    pub retired_at: DateTime<Utc>,
+}
+
+/// `agent.events.agent_decommissioned`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentDecommissioned {
+    pub agent_id: Uuid,
+    #[serde(default)]
+    pub reason: Option<String>,
+    pub decommissioned_at: DateTime<Utc>,
+}
+
+/// `policy.events.policy_enacted`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyEnacted {
+    pub policy_id: Uuid,
+    pub name: String,
+    /// Constraints the policy puts on relationships
+    #[serde(default)]
+    pub relationship_rules: Vec<PolicyRule>,
+    pub enacted_at: DateTime<Utc>,
+}
+
+/// `policy.events.policy_revoked`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyRevoked {
+    pub policy_id: Uuid,
+    #[serde(default)]
+    pub reason: Option<String>,
+    pub revoked_at: DateTime<Utc>,
+}
+
+/// Upstream events the relationship domain reacts to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum CrossDomainEvent {
+    PersonDeactivated(PersonDeactivated),
+    PersonMerged(PersonMerged),
+    OrganizationDissolved(OrganizationDissolved),
+    OrganizationMerged(OrganizationMerged),
+    OrganizationRenamed(OrganizationRenamed),
+    LocationRetired(LocationRetired),
+    AgentDecommissioned(AgentDecommissioned),
+    PolicyEnacted(PolicyEnacted),
+    PolicyRevoked(PolicyRevoked),
+}
+
+impl CrossDomainEvent {
+    /// Subject this event is published on by its domain
+    pub fn subject(&self) -> &'static str {
+        match self {
+            CrossDomainEvent::PersonDeactivated(_) => "person.events.person_deactivated",
+            CrossDomainEvent::PersonMerged(_) => "person.events.person_merged",
+            CrossDomainEvent::OrganizationDissolved(_) => "organization.events.organization_dissolved",
+            CrossDomainEvent::OrganizationMerged(_) => "organization.events.organization_merged",
+            CrossDomainEvent::OrganizationRenamed(_) => "organization.events.organization_renamed",
+            CrossDomainEvent::LocationRetired(_) => "location.events.location_retired",
+            CrossDomainEvent::AgentDecommissioned(_) => "agent.events.agent_decommissioned",
+            CrossDomainEvent::PolicyEnacted(_) => "policy.events.policy_enacted",
+            CrossDomainEvent::PolicyRevoked(_) => "policy.events.policy_revoked",
+        }
+    }
+
+    /// When the event happened in its domain
+    pub fn occurred_at(&self) -> DateTime<Utc> {
+        match self {
+            CrossDomainEvent::PersonDeactivated(e) => e.deactivated_at,
+            CrossDomainEvent::PersonMerged(e) => e.merged_at,
+            CrossDomainEvent::OrganizationDissolved(e) => e.dissolved_at,
+            CrossDomainEvent::OrganizationMerged(e) => e.merged_at,
+            CrossDomainEvent::OrganizationRenamed(e) => e.renamed_at,
+            CrossDomainEvent::LocationRetired(e) => e.retired_at,
+            CrossDomainEvent::AgentDecommissioned(e) => e.decommissioned_at,
+            CrossDomainEvent::PolicyEnacted(e) => e.enacted_at,
+            CrossDomainEvent::PolicyRevoked(e) => e.revoked_at,
+        }
+    }
+
+    /// The upstream entity the event is about
+    pub fn entity(&self) -> EntityRef {
+        match self {
+            CrossDomainEvent::PersonDeactivated(e) => EntityRef::person(e.person_id),
+            CrossDomainEvent::PersonMerged(e) => EntityRef::person(e.merged_person_id),
+            CrossDomainEvent::OrganizationDissolved(e) => EntityRef::organization(e.organization_id),
+            CrossDomainEvent::OrganizationMerged(e) => EntityRef::organization(e.merged_organization_id),
+            CrossDomainEvent::OrganizationRenamed(e) => EntityRef::organization(e.organization_id),
+            CrossDomainEvent::LocationRetired(e) => EntityRef::location(e.location_id),
+            CrossDomainEvent::AgentDecommissioned(e) => EntityRef::agent(e.agent_id),
+            CrossDomainEvent::PolicyEnacted(e) => EntityRef::new(EntityType::Policy, e.policy_id),
+            CrossDomainEvent::PolicyRevoked(e) => EntityRef::new(EntityType::Policy, e.policy_id),
+        }
+    }
+
+    /// Serialize the event body as its domain publishes it
+    pub fn to_payload(&self) -> RelationshipResult<Vec<u8>> {
+        let body = match self {
+            CrossDomainEvent::PersonDeactivated(e) => serde_json::to_vec(e),
+            CrossDomainEvent::PersonMerged(e) => serde_json::to_vec(e),
+            CrossDomainEvent::OrganizationDissolved(e) => serde_json::to_vec(e),
+            CrossDomainEvent::OrganizationMerged(e) => serde_json::to_vec(e),
+            CrossDomainEvent::OrganizationRenamed(e) => serde_json::to_vec(e),
+            CrossDomainEvent::LocationRetired(e) => serde_json::to_vec(e),
+            CrossDomainEvent::AgentDecommissioned(e) => serde_json::to_vec(e),
+            CrossDomainEvent::PolicyEnacted(e) => serde_json::to_vec(e),
+            CrossDomainEvent::PolicyRevoked(e) => serde_json::to_vec(e),
+        };
+        body.map_err(|e| RelationshipError::CrossDomainEventFailed(e.to_string()))
+    }
+
+    /// Parse an upstream message
+    ///
+    /// Returns `Ok(None)` for events the relationship domain ignores.
+    pub fn from_message(subject: &str, payload: &[u8]) -> RelationshipResult<Option<Self>> {
+        fn parse<T: serde::de::DeserializeOwned>(subject: &str, payload: &[u8]) -> RelationshipResult<T> {
+            serde_json::from_slice(payload)
+                .map_err(|e| RelationshipError::CrossDomainEventFailed(format!("{}: {}", subject, e)))
+        }
+
+        let event = match subject {
+            "person.events.person_deactivated" => {
+                CrossDomainEvent::PersonDeactivated(parse(subject, payload)?)
+            }
+            "person.events.person_merged" => CrossDomainEvent::PersonMerged(parse(subject, payload)?),
+            "organization.events.organization_dissolved" => {
+                CrossDomainEvent::OrganizationDissolved(parse(subject, payload)?)
+            }
+            "organization.events.organization_merged" => {
+                CrossDomainEvent::OrganizationMerged(parse(subject, payload)?)
+            }
+            "organization.events.organization_renamed" => {
+                CrossDomainEvent::OrganizationRenamed(parse(subject, payload)?)
+            }
+            "location.events.location_retired" => CrossDomainEvent::LocationRetired(parse(subject, payload)?),
+            "agent.events.agent_decommissioned" => {
+                CrossDomainEvent::AgentDecommissioned(parse(subject, payload)?)
+            }
+            "policy.events.policy_enacted" => CrossDomainEvent::PolicyEnacted(parse(subject, payload)?),
+            "policy.events.policy_revoked" => CrossDomainEvent::PolicyRevoked(parse(subject, payload)?),
+            _ => return Ok(None),
+        };
+        Ok(Some(event))
+    }
+}
+
+// ============================================================================
+// Handler
+// ============================================================================
+
+/// Decides how the relationship domain reacts to upstream events
+#[derive(Debug, Clone)]
+pub struct CrossDomainEventHandler {
+    actor: String,
+    rules: CascadeRules,
+}
+
+impl Default for CrossDomainEventHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CrossDomainEventHandler {
+    /// Create a handler issuing commands as `CROSS_DOMAIN_ACTOR`, with the
+    /// standard cascade rules
+    pub fn new() -> Self {
+        Self {
+            actor: CROSS_DOMAIN_ACTOR.to_string(),
+            rules: CascadeRules::standard(),
+        }
+    }
+
+    /// Issue commands as a different actor
+    pub fn with_actor(mut self, actor: impl Into<String>) -> Self {
+        self.actor = actor.into();
+        self
+    }
+
+    /// Cascade with a different rules table
+    pub fn with_rules(mut self, rules: CascadeRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// The cascade rules table
+    pub fn rules(&self) -> &CascadeRules {
+        &self.rules
+    }
+
+    /// Commands reacting to an upstream event
+    pub fn react(&self, space: &RelationshipSpace, event: &CrossDomainEvent) -> Vec<RelationshipCommand> {
+        match event {
+            CrossDomainEvent::PolicyEnacted(e) => self.on_policy_enacted(space, e),
+            CrossDomainEvent::PolicyRevoked(e) => self.on_policy_revoked(space, e),
+            _ => self.react_within(&Scope::space(space), event),
+        }
+    }
+
+    /// React to an upstream event unless the ledger has already seen it or
+    /// something newer about the same entity
+    pub fn handle(
+        &self,
+        space: &RelationshipSpace,
+        ledger: &mut UpstreamLedger,
+        event: &CrossDomainEvent,
+    ) -> Vec<RelationshipCommand> {
+        if ledger.record(event) {
+            self.react(space, event)
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Commands correcting an edge processed after upstream events about
+    /// its endpoints
+    ///
+    /// An EdgeCreated can reach us after a PersonDeactivated that predates
+    /// it. Reconciling the new edge against the ledger gives the same result
+    /// as if the events had arrived in order.
+    pub fn reconcile_edge(&self, ledger: &UpstreamLedger, edge: &EdgeConcept) -> Vec<RelationshipCommand> {
+        let scope = Scope {
+            edges: vec![edge],
+            hyperedges: Vec::new(),
+        };
+        ledger
+            .events_about([&edge.source, &edge.target])
+            .into_iter()
+            .flat_map(|event| self.react_within(&scope, event))
+            .collect()
+    }
+
+    /// Commands correcting a hyperedge processed after upstream events about
+    /// its participants
+    pub fn reconcile_hyperedge(
+        &self,
+        ledger: &UpstreamLedger,
+        hyperedge: &HyperEdgeConcept,
+    ) -> Vec<RelationshipCommand> {
+        let scope = Scope {
+            edges: Vec::new(),
+            hyperedges: vec![hyperedge],
+        };
+        ledger
+            .events_about(hyperedge.participants.participants().map(|p| &p.entity_ref))
+            .into_iter()
+            .flat_map(|event| self.react_within(&scope, event))
+            .collect()
+    }
+
+    fn react_within(&self, scope: &Scope<'_>, event: &CrossDomainEvent) -> Vec<RelationshipCommand> {
+        match event {
+            CrossDomainEvent::PersonDeactivated(e) => self.cascade(
+                scope,
+                &EntityRef::person(e.person_id),
+                e.reason.as_deref().unwrap_or("person deactivated"),
+            ),
+            CrossDomainEvent::PersonMerged(e) => self.on_entity_merged(
+                scope,
+                &EntityRef::person(e.merged_person_id),
+                &EntityRef::person(e.surviving_person_id),
+            ),
+            CrossDomainEvent::OrganizationDissolved(e) => self.cascade(
+                scope,
+                &EntityRef::organization(e.organization_id),
+                e.reason.as_deref().unwrap_or("organization dissolved"),
+            ),
+            CrossDomainEvent::OrganizationMerged(e) => self.on_entity_merged(
+                scope,
+                &EntityRef::organization(e.merged_organization_id),
+                &EntityRef::organization(e.surviving_organization_id),
+            ),
+            CrossDomainEvent::OrganizationRenamed(e) => self.on_organization_renamed(scope, e),
+            CrossDomainEvent::LocationRetired(e) => self.cascade(
+                scope,
+                &EntityRef::location(e.location_id),
+                e.reason.as_deref().unwrap_or("location retired"),
+            ),
+            CrossDomainEvent::AgentDecommissioned(e) => self.cascade(
+                scope,
+                &EntityRef::agent(e.agent_id),
+                e.reason.as_deref().unwrap_or("agent decommissioned"),
+            ),
+            // Policies constrain the space, not the relationships in scope
+            CrossDomainEvent::PolicyEnacted(_) | CrossDomainEvent::PolicyRevoked(_) => Vec::new(),
+        }
+    }
+
+    /// Parse an upstream message and react to it
+    pub fn handle_message(
+        &self,
+        space: &RelationshipSpace,
+        subject: &str,
+        payload: &[u8],
+    ) -> RelationshipResult<Vec<RelationshipCommand>> {
+        Ok(CrossDomainEvent::from_message(subject, payload)?
+            .map(|event| self.react(space, &event))
+            .unwrap_or_default())
+    }
+
+    // ---- Reactions ----
+
+    /// Enact a policy's relationship constraints, revoking the ones it
+    /// issued before
+    fn on_policy_enacted(&self, space: &RelationshipSpace, e: &PolicyEnacted) -> Vec<RelationshipCommand> {
+        let policy = EntityRef::new(EntityType::Policy, e.policy_id);
+        let mut commands = self.on_policy_revoked(
+            space,
+            &PolicyRevoked {
+                policy_id: e.policy_id,
+                reason: Some("policy re-enacted".to_string()),
+                revoked_at: e.enacted_at,
+            },
+        );
+        commands.extend(e.relationship_rules.iter().map(|rule| {
+            RelationshipCommand::EnactPolicy(EnactPolicy {
+                identity: MessageIdentity::new_root(),
+                policy: RelationshipPolicy::new(policy.clone(), e.name.clone(), rule.clone()),
+                enacted_by: self.actor.clone(),
+            })
+        }));
+        commands
+    }
+
+    /// Revoke the constraints a policy issued, if any
+    fn on_policy_revoked(&self, space: &RelationshipSpace, e: &PolicyRevoked) -> Vec<RelationshipCommand> {
+        let policy = EntityRef::new(EntityType::Policy, e.policy_id);
+        if !space.policies.policies.iter().any(|p| p.policy.same_entity(&policy)) {
+            return Vec::new();
+        }
+        vec![RelationshipCommand::RevokePolicy(RevokePolicy {
+            identity: MessageIdentity::new_root(),
+            policy,
+            reason: e.reason.clone(),
+            revoked_by: self.actor.clone(),
+        })]
+    }
+
+    /// Relationships of an entity that left, cascaded as the rules table
+    /// says
+    fn cascade(&self, scope: &Scope<'_>, entity: &EntityRef, reason: &str) -> Vec<RelationshipCommand> {
+        let edges = scope.edges.iter().filter(|e| !e.state.is_closed()).filter_map(|e| {
+            let action = self.rules.edge_action(entity, e)?;
+            self.cascade_edge(e, action, reason).map(RelationshipCommand::Edge)
+        });
+        let hyperedges = scope.hyperedges.iter().filter(|h| !h.state.is_terminal()).filter_map(|h| {
+            let action = self.rules.hyperedge_action(entity, h)?;
+            self.cascade_hyperedge(h, action, reason).map(RelationshipCommand::HyperEdge)
+        });
+        edges.chain(hyperedges).collect()
+    }
+
+    fn cascade_edge(&self, edge: &EdgeConcept, action: CascadeAction, reason: &str) -> Option<EdgeCommand> {
+        match (action, &edge.state) {
+            (CascadeAction::Ignore, _) => None,
+            (CascadeAction::Suspend, EdgeState::Active) => Some(EdgeCommand::SuspendEdge(SuspendEdge {
+                identity: MessageIdentity::new_root(),
+                edge_id: edge.id,
+                reason: Some(reason.to_string()),
+                suspended_by: self.actor.clone(),
+            })),
+            (CascadeAction::Suspend, EdgeState::Proposed | EdgeState::PendingConsent) | (CascadeAction::Terminate, _) => {
+                Some(self.end_edge(edge, reason))
+            }
+            (CascadeAction::Suspend, _) => None,
+            (CascadeAction::FlagForReview, _) => (!edge.tags.contains(REVIEW_TAG)).then(|| {
+                EdgeCommand::AddEdgeTag(AddEdgeTag {
+                    identity: MessageIdentity::new_root(),
+                    edge_id: edge.id,
+                    tag: REVIEW_TAG.to_string(),
+                    tagged_by: self.actor.clone(),
+                })
+            }),
+        }
+    }
+
+    fn cascade_hyperedge(
+        &self,
+        hyperedge: &HyperEdgeConcept,
+        action: CascadeAction,
+        reason: &str,
+    ) -> Option<HyperEdgeCommand> {
+        match action {
+            CascadeAction::Ignore => None,
+            CascadeAction::Terminate => Some(HyperEdgeCommand::TerminateHyperEdge(TerminateHyperEdge {
+                identity: MessageIdentity::new_root(),
+                hyperedge_id: hyperedge.id,
+                reason: reason.to_string(),
+                terminated_by: self.actor.clone(),
+            })),
+            CascadeAction::Suspend | CascadeAction::FlagForReview => (!hyperedge.tags.contains(REVIEW_TAG)).then(|| {
+                HyperEdgeCommand::AddHyperEdgeTag(AddHyperEdgeTag {
+                    identity: MessageIdentity::new_root(),
+                    hyperedge_id: hyperedge.id,
+                    tag: REVIEW_TAG.to_string(),
+                    tagged_by: self.actor.clone(),
+                })
+            }),
+        }
+    }
+
+    /// Live edges caching the organization's name get the new one
+    ///
+    /// Only edges that already carry `ORGANIZATION_NAME_PROPERTY` are
+    /// touched; the cache is opt-in.
+    fn on_organization_renamed(&self, scope: &Scope<'_>, event: &OrganizationRenamed) -> Vec<RelationshipCommand> {
+        let organization = EntityRef::organization(event.organization_id);
+        let name = serde_json::Value::String(event.new_name.clone());
+
+        scope
+            .edges
+            .iter()
+            .filter(|e| !e.state.is_closed())
+            .filter(|e| e.source.same_entity(&organization) || e.target.same_entity(&organization))
+            .filter(|e| {
+                e.properties
+                    .get(ORGANIZATION_NAME_PROPERTY)
+                    .is_some_and(|cached| *cached != name)
+            })
+            .map(|e| {
+                RelationshipCommand::Edge(EdgeCommand::SetEdgeProperty(SetEdgeProperty {
+                    identity: MessageIdentity::new_root(),
+                    edge_id: e.id,
+                    key: ORGANIZATION_NAME_PROPERTY.to_string(),
+                    value: name.clone(),
+                    expected_version: None,
+                    set_by: self.actor.clone(),
+                }))
+            })
+            .collect()
+    }
+
+    /// Live edges are redirected to the surviving entity; an edge left
+    /// parallel to another is merged into it, and one left connecting the
+    /// survivor to itself is ended. Hyperedge participation is handed over
+    /// in place.
+    fn on_entity_merged(&self, scope: &Scope<'_>, merged: &EntityRef, surviving: &EntityRef) -> Vec<RelationshipCommand> {
+        let reason = format!("{} merged into {}", merged, surviving);
+        let touches = |e: &EdgeConcept| e.source.same_entity(merged) || e.target.same_entity(merged);
+        let replace = |entity: &EntityRef| entity.same_entity(merged).then(|| surviving.clone());
+
+        let live: Vec<&EdgeConcept> = scope.edges.iter().copied().filter(|e| !e.state.is_closed()).collect();
+        // Edges the merge leaves alone keep their place; redirected edges
+        // join them or are merged into them
+        let mut kept: HashMap<RelationshipKey, RelationshipId> = live
+            .iter()
+            .filter(|e| !touches(e))
+            .map(|e| (RelationshipKey::of_edge(e), e.id))
+            .collect();
+
+        let mut commands = Vec::new();
+        for edge in live.into_iter().filter(|e| touches(e)) {
+            let (new_source, new_target) = (replace(&edge.source), replace(&edge.target));
+            let mut redirected = edge.clone();
+            redirected.source = new_source.clone().unwrap_or_else(|| edge.source.clone());
+            redirected.target = new_target.clone().unwrap_or_else(|| edge.target.clone());
+
+            if redirected.source.same_entity(&redirected.target) {
+                commands.push(RelationshipCommand::Edge(self.end_edge(edge, &reason)));
+                continue;
+            }
+            commands.push(RelationshipCommand::Edge(EdgeCommand::RedirectEdge(RedirectEdge {
+                identity: MessageIdentity::new_root(),
+                edge_id: edge.id,
+                new_source,
+                new_target,
+                reason: reason.clone(),
+                redirected_by: self.actor.clone(),
+            })));
+            match kept.entry(RelationshipKey::of_edge(&redirected)) {
+                Entry::Occupied(survivor) => {
+                    commands.push(RelationshipCommand::Restructure(RestructureCommand::MergeEdges(MergeEdges {
+                        identity: MessageIdentity::new_root(),
+                        survivor_id: *survivor.get(),
+                        duplicate_id: edge.id,
+                        merged_by: self.actor.clone(),
+                    })));
+                }
+                Entry::Vacant(slot) => {
+                    slot.insert(edge.id);
+                }
+            }
+        }
+
+        for hyperedge in scope.hyperedges.iter().filter(|h| !h.state.is_terminal()) {
+            let Some(entry) = hyperedge
+                .participants
+                .participants()
+                .find(|p| p.entity_ref.same_entity(merged))
+            else {
+                continue;
+            };
+            let already_present = hyperedge
+                .participants
+                .participants()
+                .any(|p| p.entity_ref.same_entity(surviving));
+            if !already_present {
+                commands.push(RelationshipCommand::HyperEdge(HyperEdgeCommand::AddParticipant(
+                    AddParticipant {
+                        identity: MessageIdentity::new_root(),
+                        hyperedge_id: hyperedge.id,
+                        participant: surviving.clone(),
+                        role: entry.role.clone(),
+                        weight: entry.weight,
+                        added_by: self.actor.clone(),
+                    },
+                )));
+            }
+            commands.push(RelationshipCommand::HyperEdge(HyperEdgeCommand::RemoveParticipant(
+                RemoveParticipant {
+                    identity: MessageIdentity::new_root(),
+                    hyperedge_id: hyperedge.id,
+                    participant: merged.clone(),
+                    reason: reason.clone(),
+                    removed_by: self.actor.clone(),
+                },
+            )));
+        }
+
+        commands
+    }
+
+    /// Reject an edge that never activated, terminate any other
+    fn end_edge(&self, edge: &EdgeConcept, reason: &str) -> EdgeCommand {
+        match edge.state {
+            EdgeState::Proposed | EdgeState::PendingConsent => EdgeCommand::RejectEdge(RejectEdge {
+                identity: MessageIdentity::new_root(),
+                edge_id: edge.id,
+                reason: Some(reason.to_string()),
+                rejected_by: self.actor.clone(),
+            }),
+            _ => EdgeCommand::TerminateEdge(TerminateEdge {
+                identity: MessageIdentity::new_root(),
+                edge_id: edge.id,
+                reason: reason.to_string(),
+                terminated_by: self.actor.clone(),
+            }),
+        }
+    }
+}
+
+/// Relationships a reaction may touch
+struct Scope<'a> {
+    edges: Vec<&'a EdgeConcept>,
+    hyperedges: Vec<&'a HyperEdgeConcept>,
+}
+
+impl<'a> Scope<'a> {
+    fn space(space: &'a RelationshipSpace) -> Self {
+        Self {
+            edges: space.edges.values().collect(),
+            hyperedges: space.hyperedges.values().collect(),
+        }
+    }
+}
+
+// ============================================================================
+// Upstream Ledger
+// ============================================================================
+
+/// Latest upstream event of each kind seen per entity
+///
+/// Upstream events can be redelivered or arrive out of order relative to
+/// relationship events. The ledger drops events that are not newer than
+/// what it already holds, and remembers accepted ones so relationships
+/// processed later can be reconciled against them.
+///
+/// Serializes as the list of remembered events.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(from = "Vec<CrossDomainEvent>", into = "Vec<CrossDomainEvent>")]
+pub struct UpstreamLedger {
+    events: HashMap<(EntityKey, String), CrossDomainEvent>,
+}
+
+impl UpstreamLedger {
+    /// Create an empty ledger
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an event, returning false if it is stale or a duplicate
+    pub fn record(&mut self, event: &CrossDomainEvent) -> bool {
+        if !self.is_new(event) {
+            return false;
+        }
+        self.events
+            .insert((event.entity().key(), event.subject().to_string()), event.clone());
+        true
+    }
+
+    /// Check if an event is newer than what the ledger holds, without
+    /// recording it
+    pub fn is_new(&self, event: &CrossDomainEvent) -> bool {
+        let key = (event.entity().key(), event.subject().to_string());
+        !matches!(self.events.get(&key), Some(seen) if seen.occurred_at() >= event.occurred_at())
+    }
+
+    /// Recorded events about any of the given entities, oldest first
+    pub fn events_about<'a>(&self, entities: impl IntoIterator<Item = &'a EntityRef>) -> Vec<&CrossDomainEvent> {
+        let keys: Vec<EntityKey> = entities.into_iter().map(EntityRef::key).collect();
+        let mut events: Vec<&CrossDomainEvent> = self
+            .events
+            .iter()
+            .filter(|((entity, _), _)| keys.contains(entity))
+            .map(|(_, event)| event)
+            .collect();
+        events.sort_by_key(|e| e.occurred_at());
+        events
+    }
+}
+
+impl From<Vec<CrossDomainEvent>> for UpstreamLedger {
+    fn from(events: Vec<CrossDomainEvent>) -> Self {
+        let mut ledger = Self::new();
+        for event in &events {
+            ledger.record(event);
+        }
+        ledger
+    }
+}
+
+impl From<UpstreamLedger> for Vec<CrossDomainEvent> {
+    fn from(ledger: UpstreamLedger) -> Self {
+        let mut events: Vec<CrossDomainEvent> = ledger.events.into_values().collect();
+        events.sort_by_key(|e| e.occurred_at());
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value_objects::RelationshipCategory;
+
+    #[test]
+    fn test_parse_upstream_message() {
+        let event = CrossDomainEvent::PersonDeactivated(PersonDeactivated {
+            person_id: Uuid::now_v7(),
+            reason: None,
+            deactivated_at: Utc::now(),
+        });
+        let payload = event.to_payload().unwrap();
+
+        let parsed = CrossDomainEvent::from_message(event.subject(), &payload).unwrap();
+        assert!(matches!(parsed, Some(CrossDomainEvent::PersonDeactivated(_))));
+
+        // Unrelated events are ignored; malformed ones are errors
+        assert!(CrossDomainEvent::from_message("person.events.person_renamed", b"{}")
+            .unwrap()
+            .is_none());
+        assert!(matches!(
+            CrossDomainEvent::from_message(event.subject(), b"{}"),
+            Err(RelationshipError::CrossDomainEventFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_dissolution_rejects_edges_that_never_activated() {
+        use crate::aggregates::EdgeState;
+        use cim_domain_spaces::TopologicalSpaceId;
+
+        let handler = CrossDomainEventHandler::new();
+        let organization = EntityRef::organization(Uuid::now_v7());
+        let employment = |state| {
+            let mut edge = EdgeConcept::new(
+                "Employment",
+                EntityRef::person(Uuid::now_v7()),
+                organization.clone(),
+                RelationshipCategory::Employment,
+            );
+            edge.state = state;
+            edge
+        };
+        let mut space = RelationshipSpace::new("Dissolved", TopologicalSpaceId::new());
+        let proposed = employment(EdgeState::Proposed);
+        let active = employment(EdgeState::Active);
+        space.add_edge(proposed.clone()).unwrap();
+        space.add_edge(active.clone()).unwrap();
+
+        let dissolved = CrossDomainEvent::OrganizationDissolved(OrganizationDissolved {
+            organization_id: organization.entity_id,
+            reason: None,
+            dissolved_at: Utc::now(),
+        });
+        let commands = handler.react(&space, &dissolved);
+        let kind = |id| {
+            commands.iter().find_map(|c| match c {
+                RelationshipCommand::Edge(c) if c.edge_id() == id => Some(c.command_type()),
+                _ => None,
+            })
+        };
+        assert_eq!(kind(proposed.id), Some("reject_edge"));
+        assert_eq!(kind(active.id), Some("terminate_edge"));
+
+        // Every command is accepted by the space
+        for command in commands {
+            for event in space.handle_command(command).unwrap() {
+                space.apply_event(&event).unwrap();
+            }
+        }
+        assert_eq!(space.get_edge(&proposed.id).unwrap().state, EdgeState::Rejected);
+        assert_eq!(space.get_edge(&active.id).unwrap().state, EdgeState::Terminated);
+    }
+
+    #[test]
+    fn test_late_deactivation_and_reconciliation_converge() {
+        use cim_domain_spaces::TopologicalSpaceId;
+
+        let handler = CrossDomainEventHandler::new();
+        let person = EntityRef::person(Uuid::now_v7());
+        let deactivated = CrossDomainEvent::PersonDeactivated(PersonDeactivated {
+            person_id: person.entity_id,
+            reason: None,
+            deactivated_at: Utc::now() - chrono::Duration::hours(1),
+        });
+        let edge = EdgeConcept::new(
+            "Employment",
+            person.clone(),
+            EntityRef::organization(Uuid::now_v7()),
+            RelationshipCategory::Employment,
+        );
+
+        // Deactivation processed first, edge arrives later: reconcile it
+        let mut ledger = UpstreamLedger::new();
+        let empty = RelationshipSpace::new("Empty", TopologicalSpaceId::new());
+        assert!(handler.handle(&empty, &mut ledger, &deactivated).is_empty());
+        let reconciled = handler.reconcile_edge(&ledger, &edge);
+
+        // Edge processed first, deactivation arrives late
+        let mut space = RelationshipSpace::new("Late", TopologicalSpaceId::new());
+        space.add_edge(edge).unwrap();
+        let mut late_ledger = UpstreamLedger::new();
+        let late = handler.handle(&space, &mut late_ledger, &deactivated);
+
+        assert_eq!(reconciled.len(), 1);
+        assert_eq!(late.len(), 1);
+        assert_eq!(reconciled[0].command_type(), late[0].command_type());
+
+        // Redelivery is ignored
+        assert!(handler.handle(&space, &mut late_ledger, &deactivated).is_empty());
 
-// Placeholder for cross-domain integration
-// TODO: Implement PersonEventHandler, OrganizationEventHandler
+        // The ledger survives a JSON round trip
+        let json = serde_json::to_string(&late_ledger).unwrap();
+        let mut restored: UpstreamLedger = serde_json::from_str(&json).unwrap();
+        assert!(!restored.record(&deactivated));
+        assert_eq!(restored.events_about([&person]).len(), 1);
+    }
+}
@@ -0,0 +1,230 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Cross-Domain Handler Registry
+//!
+//! Each upstream domain is integrated by a `CrossDomainHandler`: the
+//! subjects it listens on, how its messages decode, and the commands they
+//! lead to. The registry dispatches every upstream message to the handlers
+//! whose subjects match, so a new domain is added by registering a handler:
+//!
+//! ```text
+//! CrossDomainRegistry
+//!     |
+//!     +-- CrossDomainEventHandler   person.events.>  organization.events.>
+//!     |                             location.events.>  agent.events.>
+//!     +-- (your handler)            inventory.events.>
+//!
+//! handle_message("inventory.events.item_scrapped", payload)
+//!     --> decode --> react --> [RelationshipCommand]
+//! ```
+
+use super::{CrossDomainEvent, CrossDomainEventHandler, SUBSCRIPTIONS};
+use crate::aggregates::RelationshipSpace;
+use crate::commands::RelationshipCommand;
+use crate::nats::RelationshipSubjects;
+use crate::RelationshipResult;
+use std::sync::Arc;
+
+/// Reacts to the events of one upstream domain
+pub trait CrossDomainHandler: Send + Sync {
+    /// Upstream event type
+    type Event;
+
+    /// Subject patterns the handler subscribes to
+    fn subjects(&self) -> Vec<String>;
+
+    /// Decode an upstream message
+    ///
+    /// Returns `Ok(None)` for events the handler ignores.
+    fn decode(&self, subject: &str, payload: &[u8]) -> RelationshipResult<Option<Self::Event>>;
+
+    /// Commands reacting to an upstream event
+    fn react(&self, space: &RelationshipSpace, event: &Self::Event) -> Vec<RelationshipCommand>;
+}
+
+impl CrossDomainHandler for CrossDomainEventHandler {
+    type Event = CrossDomainEvent;
+
+    fn subjects(&self) -> Vec<String> {
+        SUBSCRIPTIONS.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn decode(&self, subject: &str, payload: &[u8]) -> RelationshipResult<Option<CrossDomainEvent>> {
+        CrossDomainEvent::from_message(subject, payload)
+    }
+
+    fn react(&self, space: &RelationshipSpace, event: &CrossDomainEvent) -> Vec<RelationshipCommand> {
+        CrossDomainEventHandler::react(self, space, event)
+    }
+}
+
+/// A handler with its event type erased, so handlers of different domains
+/// can share a registry
+trait RegisteredHandler: Send + Sync {
+    fn subjects(&self) -> Vec<String>;
+    fn handle(&self, space: &RelationshipSpace, subject: &str, payload: &[u8]) -> RelationshipResult<Vec<RelationshipCommand>>;
+}
+
+impl<H: CrossDomainHandler> RegisteredHandler for H {
+    fn subjects(&self) -> Vec<String> {
+        CrossDomainHandler::subjects(self)
+    }
+
+    fn handle(&self, space: &RelationshipSpace, subject: &str, payload: &[u8]) -> RelationshipResult<Vec<RelationshipCommand>> {
+        Ok(self
+            .decode(subject, payload)?
+            .map(|event| self.react(space, &event))
+            .unwrap_or_default())
+    }
+}
+
+/// Handlers of every integrated upstream domain
+#[derive(Clone, Default)]
+pub struct CrossDomainRegistry {
+    handlers: Vec<Arc<dyn RegisteredHandler>>,
+}
+
+impl std::fmt::Debug for CrossDomainRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CrossDomainRegistry")
+            .field("subjects", &self.subjects())
+            .finish()
+    }
+}
+
+impl CrossDomainRegistry {
+    /// Create a registry without handlers
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a registry with the built-in Person, Organization, Location
+    /// and Agent handler
+    pub fn standard() -> Self {
+        Self::new().with_handler(CrossDomainEventHandler::new())
+    }
+
+    /// Register a handler
+    pub fn with_handler<H: CrossDomainHandler + 'static>(mut self, handler: H) -> Self {
+        self.register(handler);
+        self
+    }
+
+    /// Register a handler
+    pub fn register<H: CrossDomainHandler + 'static>(&mut self, handler: H) {
+        self.handlers.push(Arc::new(handler));
+    }
+
+    /// Subject patterns of every registered handler
+    pub fn subjects(&self) -> Vec<String> {
+        let mut subjects: Vec<String> = Vec::new();
+        for subject in self.handlers.iter().flat_map(|h| h.subjects()) {
+            if !subjects.contains(&subject) {
+                subjects.push(subject);
+            }
+        }
+        subjects
+    }
+
+    /// Dispatch an upstream message to every handler subscribed to its
+    /// subject, in registration order
+    pub fn handle_message(
+        &self,
+        space: &RelationshipSpace,
+        subject: &str,
+        payload: &[u8],
+    ) -> RelationshipResult<Vec<RelationshipCommand>> {
+        let mut commands = Vec::new();
+        for handler in &self.handlers {
+            if handler.subjects().iter().any(|pattern| RelationshipSubjects::matches(pattern, subject)) {
+                commands.extend(handler.handle(space, subject, payload)?);
+            }
+        }
+        Ok(commands)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregates::EdgeConcept;
+    use crate::commands::{EdgeCommand, SuspendEdge};
+    use crate::value_objects::{EntityRef, RelationshipCategory};
+    use crate::RelationshipError;
+    use cim_domain::MessageIdentity;
+    use cim_domain_spaces::TopologicalSpaceId;
+    use serde::Deserialize;
+    use uuid::Uuid;
+
+    /// An upstream domain the crate knows nothing about
+    struct InventoryHandler;
+
+    #[derive(Deserialize)]
+    struct ItemScrapped {
+        item_id: Uuid,
+    }
+
+    impl CrossDomainHandler for InventoryHandler {
+        type Event = ItemScrapped;
+
+        fn subjects(&self) -> Vec<String> {
+            vec!["inventory.events.>".to_string()]
+        }
+
+        fn decode(&self, subject: &str, payload: &[u8]) -> RelationshipResult<Option<ItemScrapped>> {
+            if subject != "inventory.events.item_scrapped" {
+                return Ok(None);
+            }
+            serde_json::from_slice(payload)
+                .map(Some)
+                .map_err(|e| RelationshipError::CrossDomainEventFailed(e.to_string()))
+        }
+
+        fn react(&self, space: &RelationshipSpace, event: &ItemScrapped) -> Vec<RelationshipCommand> {
+            let item = EntityRef::agent(event.item_id);
+            space
+                .edges
+                .values()
+                .filter(|e| e.target.same_entity(&item))
+                .map(|e| {
+                    RelationshipCommand::from(EdgeCommand::SuspendEdge(SuspendEdge {
+                        identity: MessageIdentity::new_root(),
+                        edge_id: e.id,
+                        reason: Some("item scrapped".to_string()),
+                        suspended_by: "inventory".to_string(),
+                    }))
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_registered_handler_dispatch() {
+        let item = EntityRef::agent(Uuid::now_v7());
+        let mut edge = EdgeConcept::new(
+            "Owns",
+            EntityRef::person(Uuid::now_v7()),
+            item.clone(),
+            RelationshipCategory::Ownership,
+        );
+        edge.activate().unwrap();
+        let mut space = RelationshipSpace::new("Inventory", TopologicalSpaceId::new());
+        space.add_edge(edge).unwrap();
+
+        let registry = CrossDomainRegistry::standard().with_handler(InventoryHandler);
+        assert!(registry.subjects().contains(&"inventory.events.>".to_string()));
+        assert!(registry.subjects().contains(&"person.events.>".to_string()));
+
+        let payload = format!(r#"{{"item_id":"{}"}}"#, item.entity_id);
+        let commands = registry
+            .handle_message(&space, "inventory.events.item_scrapped", payload.as_bytes())
+            .unwrap();
+        assert_eq!(commands.len(), 1);
+        assert_eq!(commands[0].command_type(), "suspend_edge");
+
+        // Subjects nobody subscribed to are ignored
+        assert!(registry.handle_message(&space, "billing.events.paid", b"{}").unwrap().is_empty());
+    }
+}
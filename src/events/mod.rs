@@ -7,12 +7,14 @@
 //! Immutable facts about what happened in the relationship domain.
 //! All state changes are represented as events for event sourcing.
 
+use crate::invariants::RelationshipPolicy;
 use crate::quality::RelationshipQuality;
-use crate::value_objects::{EntityRef, IncidenceMatrix, ParticipantRole, RelationshipCategory, RelationshipId};
+use crate::value_objects::{ActivationMode, EntityRef, IncidenceMatrix, ParticipantRole, RelationshipCategory, RelationshipId};
 use chrono::{DateTime, Utc};
 use cim_domain::MessageIdentity;
 use cim_domain_spaces::{ConceptId, KnowledgeLevel};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use uuid::Uuid;
 
 // ============================================================================
@@ -21,23 +23,104 @@ use uuid::Uuid;
 
 /// Events for EdgeConcept aggregate
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum EdgeEvent {
     EdgeCreated(EdgeCreated),
     EdgeActivated(EdgeActivated),
     EdgeSuspended(EdgeSuspended),
     EdgeTerminated(EdgeTerminated),
     EdgeRejected(EdgeRejected),
+    Reproposed(EdgeReproposed),
     QualityUpdated(EdgeQualityUpdated),
     EvidenceAdded(EdgeEvidenceAdded),
+    EvidenceRevoked(EdgeEvidenceRevoked),
     KnowledgeProgressed(EdgeKnowledgeProgressed),
     PropertyUpdated(EdgePropertyUpdated),
+    PropertyRemoved(EdgePropertyRemoved),
+    EndpointsRewritten(EdgeEndpointsRewritten),
+    Reversed(EdgeReversed),
+    ConsentRequested(EdgeConsentRequested),
+    ConsentGranted(EdgeConsentGranted),
+    ConsentDeclined(EdgeConsentDeclined),
+    TemplateApplied(EdgeTemplateApplied),
+    TagAdded(EdgeTagAdded),
+    TagRemoved(EdgeTagRemoved),
+    Archived(EdgeArchived),
+    Restored(EdgeRestored),
+    Renamed(EdgeRenamed),
+    DescriptionUpdated(EdgeDescriptionUpdated),
+}
+
+impl EdgeEvent {
+    /// Get the event type name used in NATS subjects
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            EdgeEvent::EdgeCreated(_) => "edge_created",
+            EdgeEvent::EdgeActivated(_) => "edge_activated",
+            EdgeEvent::EdgeSuspended(_) => "edge_suspended",
+            EdgeEvent::EdgeTerminated(_) => "edge_terminated",
+            EdgeEvent::EdgeRejected(_) => "edge_rejected",
+            EdgeEvent::Reproposed(_) => "edge_reproposed",
+            EdgeEvent::QualityUpdated(_) => "edge_quality_updated",
+            EdgeEvent::EvidenceAdded(_) => "edge_evidence_added",
+            EdgeEvent::EvidenceRevoked(_) => "edge_evidence_revoked",
+            EdgeEvent::KnowledgeProgressed(_) => "edge_knowledge_progressed",
+            EdgeEvent::PropertyUpdated(_) => "edge_property_updated",
+            EdgeEvent::PropertyRemoved(_) => "edge_property_removed",
+            EdgeEvent::EndpointsRewritten(_) => "edge_endpoints_rewritten",
+            EdgeEvent::Reversed(_) => "edge_reversed",
+            EdgeEvent::ConsentRequested(_) => "edge_consent_requested",
+            EdgeEvent::ConsentGranted(_) => "edge_consent_granted",
+            EdgeEvent::ConsentDeclined(_) => "edge_consent_declined",
+            EdgeEvent::TemplateApplied(_) => "edge_template_applied",
+            EdgeEvent::TagAdded(_) => "edge_tag_added",
+            EdgeEvent::TagRemoved(_) => "edge_tag_removed",
+            EdgeEvent::Archived(_) => "edge_archived",
+            EdgeEvent::Restored(_) => "edge_restored",
+            EdgeEvent::Renamed(_) => "edge_renamed",
+            EdgeEvent::DescriptionUpdated(_) => "edge_description_updated",
+        }
+    }
+
+    /// Get the ID of the edge this event belongs to
+    pub fn edge_id(&self) -> RelationshipId {
+        match self {
+            EdgeEvent::EdgeCreated(e) => e.edge_id,
+            EdgeEvent::EdgeActivated(e) => e.edge_id,
+            EdgeEvent::EdgeSuspended(e) => e.edge_id,
+            EdgeEvent::EdgeTerminated(e) => e.edge_id,
+            EdgeEvent::EdgeRejected(e) => e.edge_id,
+            EdgeEvent::Reproposed(e) => e.edge_id,
+            EdgeEvent::QualityUpdated(e) => e.edge_id,
+            EdgeEvent::EvidenceAdded(e) => e.edge_id,
+            EdgeEvent::EvidenceRevoked(e) => e.edge_id,
+            EdgeEvent::KnowledgeProgressed(e) => e.edge_id,
+            EdgeEvent::PropertyUpdated(e) => e.edge_id,
+            EdgeEvent::PropertyRemoved(e) => e.edge_id,
+            EdgeEvent::EndpointsRewritten(e) => e.edge_id,
+            EdgeEvent::Reversed(e) => e.edge_id,
+            EdgeEvent::ConsentRequested(e) => e.edge_id,
+            EdgeEvent::ConsentGranted(e) => e.edge_id,
+            EdgeEvent::ConsentDeclined(e) => e.edge_id,
+            EdgeEvent::TemplateApplied(e) => e.edge_id,
+            EdgeEvent::TagAdded(e) => e.edge_id,
+            EdgeEvent::TagRemoved(e) => e.edge_id,
+            EdgeEvent::Archived(e) => e.edge_id,
+            EdgeEvent::Restored(e) => e.edge_id,
+            EdgeEvent::Renamed(e) => e.edge_id,
+            EdgeEvent::DescriptionUpdated(e) => e.edge_id,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct EdgeCreated {
     pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
     pub identity: MessageIdentity,
     pub edge_id: RelationshipId,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::concept_id"))]
     pub concept_id: ConceptId,
     pub source: EntityRef,
     pub target: EntityRef,
@@ -48,8 +131,10 @@ pub struct EdgeCreated {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct EdgeActivated {
     pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
     pub identity: MessageIdentity,
     pub edge_id: RelationshipId,
     pub activated_by: String,
@@ -57,8 +142,10 @@ pub struct EdgeActivated {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct EdgeSuspended {
     pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
     pub identity: MessageIdentity,
     pub edge_id: RelationshipId,
     pub reason: Option<String>,
@@ -67,8 +154,10 @@ pub struct EdgeSuspended {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct EdgeTerminated {
     pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
     pub identity: MessageIdentity,
     pub edge_id: RelationshipId,
     pub reason: String,
@@ -77,8 +166,10 @@ pub struct EdgeTerminated {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct EdgeRejected {
     pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
     pub identity: MessageIdentity,
     pub edge_id: RelationshipId,
     pub reason: Option<String>,
@@ -87,8 +178,22 @@ pub struct EdgeRejected {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EdgeReproposed {
+    pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub edge_id: RelationshipId,
+    pub reason: Option<String>,
+    pub reproposed_by: String,
+    pub reproposed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct EdgeQualityUpdated {
     pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
     pub identity: MessageIdentity,
     pub edge_id: RelationshipId,
     pub old_quality: RelationshipQuality,
@@ -98,8 +203,10 @@ pub struct EdgeQualityUpdated {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct EdgeEvidenceAdded {
     pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
     pub identity: MessageIdentity,
     pub edge_id: RelationshipId,
     pub evidence_cid: String,
@@ -108,11 +215,28 @@ pub struct EdgeEvidenceAdded {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EdgeEvidenceRevoked {
+    pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub edge_id: RelationshipId,
+    pub evidence_cid: String,
+    pub reason: String,
+    pub revoked_by: String,
+    pub revoked_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct EdgeKnowledgeProgressed {
     pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
     pub identity: MessageIdentity,
     pub edge_id: RelationshipId,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::knowledge_level"))]
     pub from_level: KnowledgeLevel,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::knowledge_level"))]
     pub to_level: KnowledgeLevel,
     pub new_confidence: f64,
     pub reason: String,
@@ -120,12 +244,186 @@ pub struct EdgeKnowledgeProgressed {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct EdgePropertyUpdated {
     pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
     pub identity: MessageIdentity,
     pub edge_id: RelationshipId,
     pub key: String,
     pub value: serde_json::Value,
+    #[serde(default)]
+    pub updated_by: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EdgePropertyRemoved {
+    pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub edge_id: RelationshipId,
+    pub key: String,
+    pub removed_by: String,
+    pub removed_at: DateTime<Utc>,
+}
+
+/// An edge's endpoints were replaced, by an entity merge or a redirect
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EdgeEndpointsRewritten {
+    pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub edge_id: RelationshipId,
+    pub old_source: EntityRef,
+    pub old_target: EntityRef,
+    pub new_source: EntityRef,
+    pub new_target: EntityRef,
+    pub reason: String,
+    #[serde(default)]
+    pub rewritten_by: String,
+    pub rewritten_at: DateTime<Utc>,
+}
+
+/// An edge's source and target were swapped
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EdgeReversed {
+    pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub edge_id: RelationshipId,
+    pub old_category: RelationshipCategory,
+    pub new_category: RelationshipCategory,
+    pub reason: String,
+    pub reversed_by: String,
+    pub reversed_at: DateTime<Utc>,
+}
+
+/// A proposed edge now waits for the consent of both endpoints
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EdgeConsentRequested {
+    pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub edge_id: RelationshipId,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub requested_by: String,
+    pub requested_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EdgeConsentGranted {
+    pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub edge_id: RelationshipId,
+    pub party: EntityRef,
+    pub granted_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EdgeConsentDeclined {
+    pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub edge_id: RelationshipId,
+    pub party: EntityRef,
+    pub reason: Option<String>,
+    pub declined_at: DateTime<Utc>,
+}
+
+/// An edge was created from a template, taking its property defaults and
+/// validity term
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EdgeTemplateApplied {
+    pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub edge_id: RelationshipId,
+    pub template: String,
+    pub properties: HashMap<String, serde_json::Value>,
+    pub valid_until: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub applied_by: String,
+    pub applied_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EdgeTagAdded {
+    pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub edge_id: RelationshipId,
+    pub tag: String,
+    pub tagged_by: String,
+    pub tagged_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EdgeTagRemoved {
+    pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub edge_id: RelationshipId,
+    pub tag: String,
+    pub removed_by: String,
+    pub removed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EdgeArchived {
+    pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub edge_id: RelationshipId,
+    pub reason: Option<String>,
+    pub archived_by: String,
+    pub archived_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EdgeRestored {
+    pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub edge_id: RelationshipId,
+    pub restored_by: String,
+    pub restored_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EdgeRenamed {
+    pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub edge_id: RelationshipId,
+    pub old_name: String,
+    pub new_name: String,
+    pub renamed_by: String,
+    pub renamed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EdgeDescriptionUpdated {
+    pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub edge_id: RelationshipId,
+    pub description: Option<String>,
+    pub updated_by: String,
     pub updated_at: DateTime<Utc>,
 }
 
@@ -135,32 +433,116 @@ pub struct EdgePropertyUpdated {
 
 /// Events for HyperEdgeConcept aggregate
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum HyperEdgeEvent {
     HyperEdgeCreated(HyperEdgeCreated),
     HyperEdgeActivated(HyperEdgeActivated),
     ParticipantAdded(ParticipantAdded),
     ParticipantRemoved(ParticipantRemoved),
     ParticipantRoleChanged(ParticipantRoleChanged),
+    ParticipantWeightChanged(ParticipantWeightChanged),
+    ParticipantAcknowledged(ParticipantAcknowledged),
     HyperEdgeTerminated(HyperEdgeTerminated),
+    RestructuringBegun(RestructuringBegun),
+    RestructuringCompleted(RestructuringCompleted),
     HyperEdgeQualityUpdated(HyperEdgeQualityUpdated),
+    PromotedFromEdge(HyperEdgePromotedFromEdge),
+    TemplateApplied(HyperEdgeTemplateApplied),
+    TagAdded(HyperEdgeTagAdded),
+    TagRemoved(HyperEdgeTagRemoved),
+    GroupAdded(ParticipantGroupAdded),
+    GroupRemoved(ParticipantGroupRemoved),
+    AssignedToGroup(ParticipantAssignedToGroup),
+    UnassignedFromGroup(ParticipantUnassignedFromGroup),
+    Archived(HyperEdgeArchived),
+    Restored(HyperEdgeRestored),
+    Renamed(HyperEdgeRenamed),
+    DescriptionUpdated(HyperEdgeDescriptionUpdated),
+}
+
+impl HyperEdgeEvent {
+    /// Get the event type name used in NATS subjects
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            HyperEdgeEvent::HyperEdgeCreated(_) => "hyperedge_created",
+            HyperEdgeEvent::HyperEdgeActivated(_) => "hyperedge_activated",
+            HyperEdgeEvent::ParticipantAdded(_) => "participant_added",
+            HyperEdgeEvent::ParticipantRemoved(_) => "participant_removed",
+            HyperEdgeEvent::ParticipantRoleChanged(_) => "participant_role_changed",
+            HyperEdgeEvent::ParticipantWeightChanged(_) => "participant_weight_changed",
+            HyperEdgeEvent::ParticipantAcknowledged(_) => "participant_acknowledged",
+            HyperEdgeEvent::HyperEdgeTerminated(_) => "hyperedge_terminated",
+            HyperEdgeEvent::RestructuringBegun(_) => "restructuring_begun",
+            HyperEdgeEvent::RestructuringCompleted(_) => "restructuring_completed",
+            HyperEdgeEvent::HyperEdgeQualityUpdated(_) => "hyperedge_quality_updated",
+            HyperEdgeEvent::PromotedFromEdge(_) => "hyperedge_promoted_from_edge",
+            HyperEdgeEvent::TemplateApplied(_) => "hyperedge_template_applied",
+            HyperEdgeEvent::TagAdded(_) => "hyperedge_tag_added",
+            HyperEdgeEvent::TagRemoved(_) => "hyperedge_tag_removed",
+            HyperEdgeEvent::GroupAdded(_) => "participant_group_added",
+            HyperEdgeEvent::GroupRemoved(_) => "participant_group_removed",
+            HyperEdgeEvent::AssignedToGroup(_) => "participant_assigned_to_group",
+            HyperEdgeEvent::UnassignedFromGroup(_) => "participant_unassigned_from_group",
+            HyperEdgeEvent::Archived(_) => "hyperedge_archived",
+            HyperEdgeEvent::Restored(_) => "hyperedge_restored",
+            HyperEdgeEvent::Renamed(_) => "hyperedge_renamed",
+            HyperEdgeEvent::DescriptionUpdated(_) => "hyperedge_description_updated",
+        }
+    }
+
+    /// Get the ID of the hyperedge this event belongs to
+    pub fn hyperedge_id(&self) -> RelationshipId {
+        match self {
+            HyperEdgeEvent::HyperEdgeCreated(e) => e.hyperedge_id,
+            HyperEdgeEvent::HyperEdgeActivated(e) => e.hyperedge_id,
+            HyperEdgeEvent::ParticipantAdded(e) => e.hyperedge_id,
+            HyperEdgeEvent::ParticipantRemoved(e) => e.hyperedge_id,
+            HyperEdgeEvent::ParticipantRoleChanged(e) => e.hyperedge_id,
+            HyperEdgeEvent::ParticipantWeightChanged(e) => e.hyperedge_id,
+            HyperEdgeEvent::ParticipantAcknowledged(e) => e.hyperedge_id,
+            HyperEdgeEvent::HyperEdgeTerminated(e) => e.hyperedge_id,
+            HyperEdgeEvent::RestructuringBegun(e) => e.hyperedge_id,
+            HyperEdgeEvent::RestructuringCompleted(e) => e.hyperedge_id,
+            HyperEdgeEvent::HyperEdgeQualityUpdated(e) => e.hyperedge_id,
+            HyperEdgeEvent::PromotedFromEdge(e) => e.hyperedge_id,
+            HyperEdgeEvent::TemplateApplied(e) => e.hyperedge_id,
+            HyperEdgeEvent::TagAdded(e) => e.hyperedge_id,
+            HyperEdgeEvent::TagRemoved(e) => e.hyperedge_id,
+            HyperEdgeEvent::GroupAdded(e) => e.hyperedge_id,
+            HyperEdgeEvent::GroupRemoved(e) => e.hyperedge_id,
+            HyperEdgeEvent::AssignedToGroup(e) => e.hyperedge_id,
+            HyperEdgeEvent::UnassignedFromGroup(e) => e.hyperedge_id,
+            HyperEdgeEvent::Archived(e) => e.hyperedge_id,
+            HyperEdgeEvent::Restored(e) => e.hyperedge_id,
+            HyperEdgeEvent::Renamed(e) => e.hyperedge_id,
+            HyperEdgeEvent::DescriptionUpdated(e) => e.hyperedge_id,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct HyperEdgeCreated {
     pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
     pub identity: MessageIdentity,
     pub hyperedge_id: RelationshipId,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::concept_id"))]
     pub concept_id: ConceptId,
     pub name: String,
     pub category: RelationshipCategory,
     pub initial_participants: IncidenceMatrix,
+    #[serde(default)]
+    pub activation: ActivationMode,
     pub created_by: String,
     pub created_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct HyperEdgeActivated {
     pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
     pub identity: MessageIdentity,
     pub hyperedge_id: RelationshipId,
     pub activated_by: String,
@@ -168,8 +550,10 @@ pub struct HyperEdgeActivated {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ParticipantAdded {
     pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
     pub identity: MessageIdentity,
     pub hyperedge_id: RelationshipId,
     pub participant: EntityRef,
@@ -180,8 +564,10 @@ pub struct ParticipantAdded {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ParticipantRemoved {
     pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
     pub identity: MessageIdentity,
     pub hyperedge_id: RelationshipId,
     pub participant: EntityRef,
@@ -191,8 +577,10 @@ pub struct ParticipantRemoved {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ParticipantRoleChanged {
     pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
     pub identity: MessageIdentity,
     pub hyperedge_id: RelationshipId,
     pub participant: EntityRef,
@@ -203,8 +591,36 @@ pub struct ParticipantRoleChanged {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ParticipantWeightChanged {
+    pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub hyperedge_id: RelationshipId,
+    pub participant: EntityRef,
+    pub old_weight: f64,
+    pub new_weight: f64,
+    pub changed_by: String,
+    pub changed_at: DateTime<Utc>,
+}
+
+/// A participant acknowledged the hyperedge, toward its activation quorum
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ParticipantAcknowledged {
+    pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub hyperedge_id: RelationshipId,
+    pub participant: EntityRef,
+    pub acknowledged_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct HyperEdgeTerminated {
     pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
     pub identity: MessageIdentity,
     pub hyperedge_id: RelationshipId,
     pub reason: String,
@@ -213,8 +629,33 @@ pub struct HyperEdgeTerminated {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RestructuringBegun {
+    pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub hyperedge_id: RelationshipId,
+    pub reason: Option<String>,
+    pub begun_by: String,
+    pub begun_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RestructuringCompleted {
+    pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub hyperedge_id: RelationshipId,
+    pub completed_by: String,
+    pub completed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct HyperEdgeQualityUpdated {
     pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
     pub identity: MessageIdentity,
     pub hyperedge_id: RelationshipId,
     pub old_quality: RelationshipQuality,
@@ -223,15 +664,254 @@ pub struct HyperEdgeQualityUpdated {
     pub updated_at: DateTime<Utc>,
 }
 
+/// A hyperedge took over the knowledge and properties of the edge it was
+/// promoted from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct HyperEdgePromotedFromEdge {
+    pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub hyperedge_id: RelationshipId,
+    pub edge_id: RelationshipId,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::knowledge_level"))]
+    pub knowledge_level: KnowledgeLevel,
+    pub confidence: f64,
+    pub evidence_cids: Vec<String>,
+    pub properties: HashMap<String, serde_json::Value>,
+    pub promoted_by: String,
+    pub promoted_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct HyperEdgeTagAdded {
+    pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub hyperedge_id: RelationshipId,
+    pub tag: String,
+    pub tagged_by: String,
+    pub tagged_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct HyperEdgeTagRemoved {
+    pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub hyperedge_id: RelationshipId,
+    pub tag: String,
+    pub removed_by: String,
+    pub removed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ParticipantGroupAdded {
+    pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub hyperedge_id: RelationshipId,
+    pub name: String,
+    pub role: ParticipantRole,
+    pub parent: Option<String>,
+    pub added_by: String,
+    pub added_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ParticipantGroupRemoved {
+    pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub hyperedge_id: RelationshipId,
+    pub name: String,
+    pub removed_by: String,
+    pub removed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ParticipantAssignedToGroup {
+    pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub hyperedge_id: RelationshipId,
+    pub participant: EntityRef,
+    pub group: String,
+    pub assigned_by: String,
+    pub assigned_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ParticipantUnassignedFromGroup {
+    pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub hyperedge_id: RelationshipId,
+    pub participant: EntityRef,
+    pub group: String,
+    pub unassigned_by: String,
+    pub unassigned_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct HyperEdgeArchived {
+    pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub hyperedge_id: RelationshipId,
+    pub reason: Option<String>,
+    pub archived_by: String,
+    pub archived_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct HyperEdgeRestored {
+    pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub hyperedge_id: RelationshipId,
+    pub restored_by: String,
+    pub restored_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct HyperEdgeRenamed {
+    pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub hyperedge_id: RelationshipId,
+    pub old_name: String,
+    pub new_name: String,
+    pub renamed_by: String,
+    pub renamed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct HyperEdgeDescriptionUpdated {
+    pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub hyperedge_id: RelationshipId,
+    pub description: Option<String>,
+    pub updated_by: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A hyperedge was created from a template, taking its property defaults
+/// and validity term
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct HyperEdgeTemplateApplied {
+    pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub hyperedge_id: RelationshipId,
+    pub template: String,
+    pub properties: HashMap<String, serde_json::Value>,
+    pub valid_until: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub applied_by: String,
+    pub applied_at: DateTime<Utc>,
+}
+
+// ============================================================================
+// Policy Events
+// ============================================================================
+
+/// Events of the policy constraints in force in a space
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum PolicyEvent {
+    PolicyEnacted(PolicyEnacted),
+    PolicyRevoked(PolicyRevoked),
+}
+
+impl PolicyEvent {
+    /// Get the event type name used in NATS subjects
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            PolicyEvent::PolicyEnacted(_) => "policy_enacted",
+            PolicyEvent::PolicyRevoked(_) => "policy_revoked",
+        }
+    }
+
+    /// The Policy entity the event is about
+    pub fn policy(&self) -> &EntityRef {
+        match self {
+            PolicyEvent::PolicyEnacted(e) => &e.policy.policy,
+            PolicyEvent::PolicyRevoked(e) => &e.policy,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PolicyEnacted {
+    pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    pub policy: RelationshipPolicy,
+    pub enacted_by: String,
+    pub enacted_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PolicyRevoked {
+    pub event_id: Uuid,
+    #[cfg_attr(feature = "schema", schemars(schema_with = "crate::schema::message_identity"))]
+    pub identity: MessageIdentity,
+    /// The Policy entity whose constraints are withdrawn
+    pub policy: EntityRef,
+    pub reason: Option<String>,
+    pub revoked_by: String,
+    pub revoked_at: DateTime<Utc>,
+}
+
 // ============================================================================
 // Unified Relationship Event
 // ============================================================================
 
 /// Unified event type for the relationship domain
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum RelationshipEvent {
     Edge(EdgeEvent),
     HyperEdge(HyperEdgeEvent),
+    Policy(PolicyEvent),
+}
+
+impl RelationshipEvent {
+    /// Get the event type name used in NATS subjects
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            RelationshipEvent::Edge(e) => e.event_type(),
+            RelationshipEvent::HyperEdge(e) => e.event_type(),
+            RelationshipEvent::Policy(e) => e.event_type(),
+        }
+    }
+
+    /// Get the ID of the relationship this event belongs to
+    ///
+    /// Policy events belong to no relationship; they carry the id of their
+    /// Policy entity.
+    pub fn relationship_id(&self) -> RelationshipId {
+        match self {
+            RelationshipEvent::Edge(e) => e.edge_id(),
+            RelationshipEvent::HyperEdge(e) => e.hyperedge_id(),
+            RelationshipEvent::Policy(e) => RelationshipId::from_uuid(e.policy().entity_id),
+        }
+    }
 }
 
 impl From<EdgeEvent> for RelationshipEvent {
@@ -245,3 +925,9 @@ impl From<HyperEdgeEvent> for RelationshipEvent {
         RelationshipEvent::HyperEdge(event)
     }
 }
+
+impl From<PolicyEvent> for RelationshipEvent {
+    fn from(event: PolicyEvent) -> Self {
+        RelationshipEvent::Policy(event)
+    }
+}
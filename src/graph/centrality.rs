@@ -0,0 +1,279 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Centrality Measures
+//!
+//! Identify the most connected and most influential entities in a
+//! relationship graph. All measures are quality-weighted: an edge's weight
+//! is the strength of the relationship it was built from.
+//!
+//! - **Degree**: Sum of incident edge weights, normalized by (n - 1)
+//! - **Betweenness**: Share of weighted shortest paths passing through a node,
+//!   where stronger relationships are shorter (distance = 1 / strength)
+//! - **PageRank**: Stationary distribution of a weighted random walk
+
+use super::RelationshipGraph;
+use crate::value_objects::EntityRef;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+/// Damping factor for PageRank
+pub const PAGERANK_DAMPING: f64 = 0.85;
+
+/// Maximum PageRank power iterations
+const PAGERANK_MAX_ITERATIONS: usize = 100;
+
+/// PageRank convergence tolerance (L1 norm)
+const PAGERANK_TOLERANCE: f64 = 1e-9;
+
+/// Available centrality measures
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CentralityMeasure {
+    /// Weighted degree centrality
+    Degree,
+    /// Weighted betweenness centrality (Brandes)
+    Betweenness,
+    /// Weighted PageRank
+    PageRank,
+}
+
+/// Score assigned to an entity by a graph measure
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntityScore {
+    /// The scored entity
+    pub entity: EntityRef,
+    /// Score value
+    pub score: f64,
+}
+
+/// Compute a centrality measure, returning entities ranked highest first
+pub fn centrality(graph: &RelationshipGraph, measure: CentralityMeasure) -> Vec<EntityScore> {
+    let scores = match measure {
+        CentralityMeasure::Degree => degree_centrality(graph),
+        CentralityMeasure::Betweenness => betweenness_centrality(graph),
+        CentralityMeasure::PageRank => pagerank(graph, PAGERANK_DAMPING),
+    };
+
+    let mut ranked: Vec<EntityScore> = scores
+        .into_iter()
+        .enumerate()
+        .map(|(node, score)| EntityScore {
+            entity: graph.node(node).clone(),
+            score,
+        })
+        .collect();
+    ranked.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(Ordering::Equal));
+    ranked
+}
+
+/// Weighted degree centrality, indexed by node
+pub fn degree_centrality(graph: &RelationshipGraph) -> Vec<f64> {
+    let n = graph.node_count();
+    let mut degree = vec![0.0; n];
+    for edge in graph.edges() {
+        degree[edge.source] += edge.weight;
+        if edge.target != edge.source {
+            degree[edge.target] += edge.weight;
+        }
+    }
+
+    if n > 1 {
+        let norm = (n - 1) as f64;
+        degree.iter_mut().for_each(|d| *d /= norm);
+    }
+    degree
+}
+
+/// Weighted betweenness centrality, indexed by node
+///
+/// Brandes' algorithm with Dijkstra shortest paths. Scores are normalized
+/// by (n - 1)(n - 2), the number of ordered pairs excluding the node.
+pub fn betweenness_centrality(graph: &RelationshipGraph) -> Vec<f64> {
+    let n = graph.node_count();
+    let mut betweenness = vec![0.0; n];
+
+    for source in 0..n {
+        let mut stack = Vec::with_capacity(n);
+        let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); n];
+        let mut sigma = vec![0.0_f64; n];
+        let mut distance = vec![f64::INFINITY; n];
+        let mut settled = vec![false; n];
+        let mut heap = BinaryHeap::new();
+
+        sigma[source] = 1.0;
+        distance[source] = 0.0;
+        heap.push(Candidate { distance: 0.0, node: source });
+
+        while let Some(Candidate { distance: d, node }) = heap.pop() {
+            if settled[node] {
+                continue;
+            }
+            settled[node] = true;
+            stack.push(node);
+
+            for (next, weight) in graph.weighted_successors(node) {
+                if next == node || weight <= 0.0 {
+                    continue;
+                }
+                let candidate = d + 1.0 / weight;
+                if candidate < distance[next] - f64::EPSILON {
+                    distance[next] = candidate;
+                    sigma[next] = sigma[node];
+                    predecessors[next] = vec![node];
+                    heap.push(Candidate { distance: candidate, node: next });
+                } else if (candidate - distance[next]).abs() <= f64::EPSILON {
+                    sigma[next] += sigma[node];
+                    predecessors[next].push(node);
+                }
+            }
+        }
+
+        let mut delta = vec![0.0; n];
+        while let Some(node) = stack.pop() {
+            for &pred in &predecessors[node] {
+                delta[pred] += sigma[pred] / sigma[node] * (1.0 + delta[node]);
+            }
+            if node != source {
+                betweenness[node] += delta[node];
+            }
+        }
+    }
+
+    if n > 2 {
+        let norm = ((n - 1) * (n - 2)) as f64;
+        betweenness.iter_mut().for_each(|b| *b /= norm);
+    }
+    betweenness
+}
+
+/// Weighted PageRank, indexed by node
+///
+/// Rank flows along outgoing edges in proportion to edge weight. Nodes
+/// without outgoing weight distribute their rank uniformly.
+pub fn pagerank(graph: &RelationshipGraph, damping: f64) -> Vec<f64> {
+    let n = graph.node_count();
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let uniform = 1.0 / n as f64;
+    let out_weight: Vec<f64> = (0..n)
+        .map(|node| graph.weighted_successors(node).map(|(_, w)| w.max(0.0)).sum())
+        .collect();
+    let mut rank = vec![uniform; n];
+
+    for _ in 0..PAGERANK_MAX_ITERATIONS {
+        let dangling: f64 = (0..n)
+            .filter(|&node| out_weight[node] <= 0.0)
+            .map(|node| rank[node])
+            .sum();
+        let base = (1.0 - damping) * uniform + damping * dangling * uniform;
+        let mut next = vec![base; n];
+
+        for node in 0..n {
+            if out_weight[node] <= 0.0 {
+                continue;
+            }
+            for (succ, weight) in graph.weighted_successors(node) {
+                next[succ] += damping * rank[node] * weight.max(0.0) / out_weight[node];
+            }
+        }
+
+        let change: f64 = rank.iter().zip(&next).map(|(a, b)| (a - b).abs()).sum();
+        rank = next;
+        if change < PAGERANK_TOLERANCE {
+            break;
+        }
+    }
+
+    rank
+}
+
+/// Priority queue entry for Dijkstra (min-heap on distance)
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Candidate {
+    distance: f64,
+    node: usize,
+}
+
+impl Eq for Candidate {}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .distance
+            .partial_cmp(&self.distance)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| self.node.cmp(&other.node))
+    }
+}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregates::EdgeConcept;
+    use crate::value_objects::RelationshipCategory;
+    use uuid::Uuid;
+
+    /// Star graph: hub manages three spokes
+    fn star() -> (EntityRef, RelationshipGraph) {
+        let hub = EntityRef::person(Uuid::now_v7());
+        let edges: Vec<EdgeConcept> = (0..3)
+            .map(|_| {
+                EdgeConcept::new(
+                    "Manages",
+                    hub.clone(),
+                    EntityRef::person(Uuid::now_v7()),
+                    RelationshipCategory::Management,
+                )
+            })
+            .collect();
+        (hub, RelationshipGraph::from_edges(&edges))
+    }
+
+    #[test]
+    fn test_degree_centrality_ranks_hub_first() {
+        let (hub, graph) = star();
+        let ranked = centrality(&graph, CentralityMeasure::Degree);
+        assert_eq!(ranked[0].entity, hub);
+        assert!((ranked[0].score - 0.5).abs() < 1e-9); // 3 * 0.5 strength / 3
+    }
+
+    #[test]
+    fn test_betweenness_on_path() {
+        let a = EntityRef::person(Uuid::now_v7());
+        let b = EntityRef::person(Uuid::now_v7());
+        let c = EntityRef::person(Uuid::now_v7());
+
+        let edges = [
+            EdgeConcept::new("ab", a.clone(), b.clone(), RelationshipCategory::Friendship),
+            EdgeConcept::new("bc", b.clone(), c.clone(), RelationshipCategory::Friendship),
+        ];
+        let graph = RelationshipGraph::from_edges(&edges);
+
+        let ranked = centrality(&graph, CentralityMeasure::Betweenness);
+        assert_eq!(ranked[0].entity, b);
+        // b lies on a->c and c->a, out of (3-1)(3-2) = 2 ordered pairs
+        assert!((ranked[0].score - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_pagerank_sums_to_one() {
+        let (hub, graph) = star();
+        let ranks = pagerank(&graph, PAGERANK_DAMPING);
+        let total: f64 = ranks.iter().sum();
+        assert!((total - 1.0).abs() < 1e-6);
+
+        // Spokes receive rank from the hub
+        let hub_idx = graph.node_index(&hub).unwrap();
+        assert!(ranks.iter().enumerate().all(|(i, r)| i == hub_idx || *r > ranks[hub_idx]));
+    }
+}
@@ -0,0 +1,186 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Maximal Clique Detection
+//!
+//! A clique is a set of entities that are all directly related to each
+//! other, e.g. a group of mutual friends. Maximal cliques are candidates
+//! for promotion to a HyperEdgeConcept that names the group explicitly.
+//!
+//! Cliques are found with Bron-Kerbosch and pivoting. Edge direction is
+//! ignored, so the graph should normally contain only symmetric
+//! categories (see `RelationshipSpace::symmetric_graph`).
+
+use super::RelationshipGraph;
+use crate::aggregates::HyperEdgeConcept;
+use crate::value_objects::{EntityRef, ParticipantRole, RelationshipCategory};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+
+/// A maximal clique
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Clique {
+    /// Members of the clique
+    pub members: Vec<EntityRef>,
+    /// Mean weight of the edges among the members
+    pub cohesion: f64,
+}
+
+impl Clique {
+    /// Number of members
+    pub fn size(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Materialize the clique as a forming hyperedge with every member
+    /// participating as a Member with full weight
+    pub fn to_hyperedge(&self, name: impl Into<String>, category: RelationshipCategory) -> HyperEdgeConcept {
+        let mut hyperedge = HyperEdgeConcept::new(name, category);
+        for member in &self.members {
+            hyperedge
+                .participants
+                .add_participant(member.clone(), ParticipantRole::Member, 1.0);
+        }
+        hyperedge.quality.strength = self.cohesion.clamp(0.0, 1.0);
+        hyperedge
+    }
+}
+
+/// Find all maximal cliques with at least `min_size` members, largest first
+pub fn maximal_cliques(graph: &RelationshipGraph, min_size: usize) -> Vec<Clique> {
+    let n = graph.node_count();
+
+    // Undirected adjacency with the strongest weight between each pair
+    let mut weights: HashMap<(usize, usize), f64> = HashMap::new();
+    let mut neighbours: Vec<BTreeSet<usize>> = vec![BTreeSet::new(); n];
+    for edge in graph.edges() {
+        if edge.source == edge.target {
+            continue;
+        }
+        neighbours[edge.source].insert(edge.target);
+        neighbours[edge.target].insert(edge.source);
+        let pair = (edge.source.min(edge.target), edge.source.max(edge.target));
+        let weight = weights.entry(pair).or_insert(edge.weight);
+        *weight = weight.max(edge.weight);
+    }
+
+    let mut found = Vec::new();
+    bron_kerbosch(
+        &neighbours,
+        BTreeSet::new(),
+        (0..n).collect(),
+        BTreeSet::new(),
+        &mut found,
+    );
+
+    let mut cliques: Vec<Clique> = found
+        .into_iter()
+        .filter(|members| members.len() >= min_size.max(2))
+        .map(|members| {
+            let mut total = 0.0;
+            let mut pairs = 0usize;
+            for (i, a) in members.iter().enumerate() {
+                for b in &members[i + 1..] {
+                    total += weights[&(*a.min(b), *a.max(b))];
+                    pairs += 1;
+                }
+            }
+            Clique {
+                members: members.iter().map(|&m| graph.node(m).clone()).collect(),
+                cohesion: if pairs == 0 { 0.0 } else { total / pairs as f64 },
+            }
+        })
+        .collect();
+    cliques.sort_by(|a, b| {
+        b.size()
+            .cmp(&a.size())
+            .then_with(|| b.cohesion.total_cmp(&a.cohesion))
+    });
+    cliques
+}
+
+/// Bron-Kerbosch with pivoting on the vertex of most candidates
+fn bron_kerbosch(
+    neighbours: &[BTreeSet<usize>],
+    clique: BTreeSet<usize>,
+    mut candidates: BTreeSet<usize>,
+    mut excluded: BTreeSet<usize>,
+    found: &mut Vec<Vec<usize>>,
+) {
+    if candidates.is_empty() {
+        if excluded.is_empty() {
+            found.push(clique.into_iter().collect());
+        }
+        return;
+    }
+
+    let pivot = candidates
+        .union(&excluded)
+        .max_by_key(|&&v| neighbours[v].intersection(&candidates).count())
+        .copied()
+        .unwrap_or_default();
+    let branches: Vec<usize> = candidates.difference(&neighbours[pivot]).copied().collect();
+
+    for v in branches {
+        let mut next_clique = clique.clone();
+        next_clique.insert(v);
+        bron_kerbosch(
+            neighbours,
+            next_clique,
+            candidates.intersection(&neighbours[v]).copied().collect(),
+            excluded.intersection(&neighbours[v]).copied().collect(),
+            found,
+        );
+        candidates.remove(&v);
+        excluded.insert(v);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregates::EdgeConcept;
+    use uuid::Uuid;
+
+    fn friends(a: &EntityRef, b: &EntityRef) -> EdgeConcept {
+        EdgeConcept::new("Friends", a.clone(), b.clone(), RelationshipCategory::Friendship)
+    }
+
+    #[test]
+    fn test_finds_maximal_cliques() {
+        let people: Vec<EntityRef> = (0..5).map(|_| EntityRef::person(Uuid::now_v7())).collect();
+        // 0-1-2-3 fully connected, 3-4 on the side
+        let mut edges = Vec::new();
+        for i in 0..4 {
+            for j in (i + 1)..4 {
+                edges.push(friends(&people[i], &people[j]));
+            }
+        }
+        edges.push(friends(&people[3], &people[4]));
+        let graph = RelationshipGraph::from_edges(&edges);
+
+        let cliques = maximal_cliques(&graph, 2);
+        assert_eq!(cliques.len(), 2);
+        assert_eq!(cliques[0].size(), 4);
+        assert_eq!(cliques[1].size(), 2);
+
+        // The side pair is filtered out by a higher minimum
+        assert_eq!(maximal_cliques(&graph, 3).len(), 1);
+    }
+
+    #[test]
+    fn test_clique_to_hyperedge() {
+        let people: Vec<EntityRef> = (0..3).map(|_| EntityRef::person(Uuid::now_v7())).collect();
+        let edges = [
+            friends(&people[0], &people[1]),
+            friends(&people[1], &people[2]),
+            friends(&people[2], &people[0]),
+        ];
+        let clique = &maximal_cliques(&RelationshipGraph::from_edges(&edges), 3)[0];
+
+        let hyperedge = clique.to_hyperedge("Friend Group", RelationshipCategory::Friendship);
+        assert_eq!(hyperedge.participant_count(), 3);
+        assert!((hyperedge.quality.strength - clique.cohesion).abs() < 1e-9);
+    }
+}
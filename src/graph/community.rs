@@ -0,0 +1,164 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Community Detection
+//!
+//! Groups entities that are densely connected to each other using weighted
+//! label propagation. Communities describe graph structure; Voronoi cells
+//! describe quality-space structure. `rand_index` measures how well the two
+//! partitions agree.
+
+use super::RelationshipGraph;
+use crate::value_objects::{EntityKey, EntityRef};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Maximum label propagation sweeps
+const MAX_SWEEPS: usize = 100;
+
+/// Community assignment for every entity in a graph
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CommunityAssignment {
+    /// Community index per entity
+    pub communities: HashMap<EntityKey, usize>,
+    /// Number of distinct communities
+    pub count: usize,
+}
+
+impl CommunityAssignment {
+    /// Get the community of an entity
+    pub fn community_of(&self, entity_ref: &EntityRef) -> Option<usize> {
+        self.communities.get(&entity_ref.key()).copied()
+    }
+
+    /// Get the members of a community
+    pub fn members(&self, community: usize) -> Vec<&EntityKey> {
+        self.communities
+            .iter()
+            .filter(|(_, &c)| c == community)
+            .map(|(key, _)| key)
+            .collect()
+    }
+
+    /// Rand index against another partition of the same entities
+    ///
+    /// The fraction of entity pairs on which both partitions agree (both
+    /// together or both apart). Only entities present in both partitions are
+    /// compared. Returns 1.0 when fewer than two entities are shared.
+    pub fn rand_index(&self, other: &HashMap<EntityKey, usize>) -> f64 {
+        let shared: Vec<(usize, usize)> = self
+            .communities
+            .iter()
+            .filter_map(|(key, &a)| other.get(key).map(|&b| (a, b)))
+            .collect();
+
+        let mut agreements = 0usize;
+        let mut pairs = 0usize;
+        for i in 0..shared.len() {
+            for j in (i + 1)..shared.len() {
+                let same_here = shared[i].0 == shared[j].0;
+                let same_there = shared[i].1 == shared[j].1;
+                if same_here == same_there {
+                    agreements += 1;
+                }
+                pairs += 1;
+            }
+        }
+
+        if pairs == 0 {
+            1.0
+        } else {
+            agreements as f64 / pairs as f64
+        }
+    }
+}
+
+/// Detect communities by weighted label propagation
+///
+/// Edges are treated as undirected. Each node repeatedly adopts the label
+/// with the greatest total edge weight among its neighbours, ties going to
+/// the smallest label, until no label changes. Nodes are visited in index
+/// order so the result is deterministic for a given graph.
+pub fn label_propagation(graph: &RelationshipGraph) -> CommunityAssignment {
+    let n = graph.node_count();
+    let mut labels: Vec<usize> = (0..n).collect();
+
+    for _ in 0..MAX_SWEEPS {
+        let mut changed = false;
+        for node in 0..n {
+            let mut totals: HashMap<usize, f64> = HashMap::new();
+            for edge in graph.outgoing(node).iter().chain(graph.incoming(node)) {
+                let edge = &graph.edges()[*edge];
+                let neighbour = edge.opposite(node);
+                if neighbour != node {
+                    *totals.entry(labels[neighbour]).or_insert(0.0) += edge.weight;
+                }
+            }
+
+            let best = totals.into_iter().max_by(|(la, wa), (lb, wb)| {
+                wa.partial_cmp(wb)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then_with(|| lb.cmp(la))
+            });
+            if let Some((label, _)) = best {
+                if label != labels[node] {
+                    labels[node] = label;
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    // Renumber communities densely in order of first appearance
+    let mut renumbered: HashMap<usize, usize> = HashMap::new();
+    let mut communities = HashMap::with_capacity(n);
+    for (node, label) in labels.into_iter().enumerate() {
+        let next = renumbered.len();
+        let community = *renumbered.entry(label).or_insert(next);
+        communities.insert(graph.node(node).key(), community);
+    }
+
+    CommunityAssignment {
+        count: renumbered.len(),
+        communities,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregates::EdgeConcept;
+    use crate::value_objects::RelationshipCategory;
+    use uuid::Uuid;
+
+    fn friends(a: &EntityRef, b: &EntityRef) -> EdgeConcept {
+        EdgeConcept::new("Friends", a.clone(), b.clone(), RelationshipCategory::Friendship)
+    }
+
+    #[test]
+    fn test_two_triangles_form_two_communities() {
+        let people: Vec<EntityRef> = (0..6).map(|_| EntityRef::person(Uuid::now_v7())).collect();
+        let edges = [
+            friends(&people[0], &people[1]),
+            friends(&people[1], &people[2]),
+            friends(&people[2], &people[0]),
+            friends(&people[3], &people[4]),
+            friends(&people[4], &people[5]),
+            friends(&people[5], &people[3]),
+        ];
+        let graph = RelationshipGraph::from_edges(&edges);
+
+        let assignment = label_propagation(&graph);
+        assert_eq!(assignment.count, 2);
+        assert_eq!(assignment.community_of(&people[0]), assignment.community_of(&people[2]));
+        assert_ne!(assignment.community_of(&people[0]), assignment.community_of(&people[3]));
+        assert_eq!(assignment.members(0).len(), 3);
+
+        // Identical partition agrees perfectly
+        assert_eq!(assignment.rand_index(&assignment.communities), 1.0);
+    }
+}
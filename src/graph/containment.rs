@@ -0,0 +1,133 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Containment
+//!
+//! Sites, buildings, rooms and whatever sits in them form a hierarchy of
+//! Contains and PartOf edges. Both read from container to contents once
+//! PartOf is turned around:
+//!
+//! ```text
+//! location:{site}     --Contains-->  location:{building}
+//! location:{room}     --PartOf---->  location:{building}
+//! agent:{printer}     --PartOf---->  location:{room}
+//!
+//! containment graph:  site --> building --> room --> printer
+//! ```
+//!
+//! "Everything at this site" is then a walk down from the site. Only
+//! active edges count: a retired room no longer holds anything.
+
+use super::RelationshipGraph;
+use crate::aggregates::RelationshipSpace;
+use crate::value_objects::{EntityRef, RelationshipCategory, RelationshipId};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Entity found inside a site
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SiteEntry {
+    /// The contained entity
+    pub entity: EntityRef,
+    /// Containment steps from the site (1 = directly inside)
+    pub depth: usize,
+    /// Contains or PartOf edge through which it was first reached
+    pub via: RelationshipId,
+}
+
+/// Graph of active containment edges, directed container -> contents
+pub fn containment_graph(space: &RelationshipSpace) -> RelationshipGraph {
+    let mut graph = RelationshipGraph::new();
+    for edge in space.active_edges() {
+        let (container, contents) = match edge.category {
+            RelationshipCategory::Contains => (&edge.source, &edge.target),
+            RelationshipCategory::PartOf => (&edge.target, &edge.source),
+            _ => continue,
+        };
+        graph.insert_link(
+            container,
+            contents,
+            edge.id,
+            RelationshipCategory::Contains,
+            edge.quality.strength,
+            false,
+        );
+    }
+    graph
+}
+
+/// Everything contained in a site, directly or through sub-locations,
+/// nearest first
+///
+/// `max_depth` limits how many containment steps are followed
+/// (`None` = unlimited).
+pub fn entities_at(space: &RelationshipSpace, site: &EntityRef, max_depth: Option<usize>) -> Vec<SiteEntry> {
+    let graph = containment_graph(space);
+    let Some(start) = graph.node_index(site) else {
+        return Vec::new();
+    };
+
+    let mut entries = Vec::new();
+    let mut visited = HashSet::from([start]);
+    let mut frontier = vec![start];
+    let mut depth = 0;
+
+    while !frontier.is_empty() && max_depth.is_none_or(|max| depth < max) {
+        depth += 1;
+        let mut next = Vec::new();
+        for &node in &frontier {
+            for &e in graph.outgoing(node) {
+                let edge = &graph.edges()[e];
+                if visited.insert(edge.target) {
+                    entries.push(SiteEntry {
+                        entity: graph.node(edge.target).clone(),
+                        depth,
+                        via: edge.relationship_id,
+                    });
+                    next.push(edge.target);
+                }
+            }
+        }
+        frontier = next;
+    }
+
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregates::EdgeConcept;
+    use cim_domain_spaces::TopologicalSpaceId;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_entities_at_site() {
+        let mut space = RelationshipSpace::new("Campus", TopologicalSpaceId::new());
+        let site = EntityRef::location(Uuid::now_v7());
+        let building = EntityRef::location(Uuid::now_v7());
+        let room = EntityRef::location(Uuid::now_v7());
+        let printer = EntityRef::agent(Uuid::now_v7());
+        let mut add = |source: &EntityRef, target: &EntityRef, category, active: bool| {
+            let mut edge = EdgeConcept::new("Containment", source.clone(), target.clone(), category);
+            if active {
+                edge.activate().unwrap();
+            }
+            space.add_edge(edge).unwrap();
+        };
+        add(&site, &building, RelationshipCategory::Contains, true);
+        add(&room, &building, RelationshipCategory::PartOf, true);
+        add(&printer, &room, RelationshipCategory::PartOf, true);
+        // Not yet part of the site
+        add(&EntityRef::location(Uuid::now_v7()), &site, RelationshipCategory::PartOf, false);
+
+        let everything = entities_at(&space, &site, None);
+        let found: Vec<(EntityRef, usize)> = everything.iter().map(|e| (e.entity.clone(), e.depth)).collect();
+        assert_eq!(found, vec![(building.clone(), 1), (room, 2), (printer, 3)]);
+
+        let nearby = entities_at(&space, &site, Some(1));
+        assert_eq!(nearby.len(), 1);
+        assert!(entities_at(&space, &EntityRef::location(Uuid::now_v7()), None).is_empty());
+    }
+}
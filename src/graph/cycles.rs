@@ -0,0 +1,172 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Cycle Detection
+//!
+//! Dependency cycles (A DependsOn B DependsOn ... DependsOn A) are almost
+//! always a modeling bug. These functions find them either incrementally
+//! (before an edge is added) or across a whole graph.
+
+use super::RelationshipGraph;
+use crate::value_objects::EntityRef;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+
+/// Find a directed path between two nodes (breadth-first, shortest in hops)
+///
+/// Returns the node indices along the path, including both endpoints.
+pub fn find_path(graph: &RelationshipGraph, from: usize, to: usize) -> Option<Vec<usize>> {
+    let mut parent: HashMap<usize, usize> = HashMap::new();
+    let mut queue = VecDeque::from([from]);
+    parent.insert(from, from);
+
+    while let Some(node) = queue.pop_front() {
+        if node == to {
+            let mut path = vec![to];
+            let mut current = to;
+            while current != from {
+                current = parent[&current];
+                path.push(current);
+            }
+            path.reverse();
+            return Some(path);
+        }
+        for next in graph.successors(node) {
+            if let Entry::Vacant(slot) = parent.entry(next) {
+                slot.insert(node);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// Find all dependency cycles in a graph
+///
+/// Each cycle is reported as a strongly connected component with more than
+/// one node, or a single node with an edge to itself.
+pub fn dependency_cycles(graph: &RelationshipGraph) -> Vec<Vec<EntityRef>> {
+    strongly_connected_components(graph)
+        .into_iter()
+        .filter(|component| {
+            component.len() > 1
+                || graph.successors(component[0]).any(|next| next == component[0])
+        })
+        .map(|component| component.into_iter().map(|n| graph.node(n).clone()).collect())
+        .collect()
+}
+
+/// Strongly connected components of a graph (Tarjan)
+///
+/// Components are returned in reverse topological order: every component
+/// comes before any component that can reach it.
+pub(super) fn strongly_connected_components(graph: &RelationshipGraph) -> Vec<Vec<usize>> {
+    let mut tarjan = Tarjan::new(graph.node_count());
+    for node in 0..graph.node_count() {
+        if tarjan.index[node].is_none() {
+            tarjan.visit(graph, node);
+        }
+    }
+    tarjan.components
+}
+
+/// Tarjan's strongly connected components
+struct Tarjan {
+    counter: usize,
+    index: Vec<Option<usize>>,
+    lowlink: Vec<usize>,
+    on_stack: Vec<bool>,
+    stack: Vec<usize>,
+    components: Vec<Vec<usize>>,
+}
+
+impl Tarjan {
+    fn new(size: usize) -> Self {
+        Self {
+            counter: 0,
+            index: vec![None; size],
+            lowlink: vec![0; size],
+            on_stack: vec![false; size],
+            stack: Vec::new(),
+            components: Vec::new(),
+        }
+    }
+
+    fn visit(&mut self, graph: &RelationshipGraph, node: usize) {
+        self.index[node] = Some(self.counter);
+        self.lowlink[node] = self.counter;
+        self.counter += 1;
+        self.stack.push(node);
+        self.on_stack[node] = true;
+
+        for next in graph.successors(node) {
+            match self.index[next] {
+                None => {
+                    self.visit(graph, next);
+                    self.lowlink[node] = self.lowlink[node].min(self.lowlink[next]);
+                }
+                Some(next_index) if self.on_stack[next] => {
+                    self.lowlink[node] = self.lowlink[node].min(next_index);
+                }
+                Some(_) => {}
+            }
+        }
+
+        if Some(self.lowlink[node]) == self.index[node] {
+            let mut component = Vec::new();
+            while let Some(member) = self.stack.pop() {
+                self.on_stack[member] = false;
+                component.push(member);
+                if member == node {
+                    break;
+                }
+            }
+            self.components.push(component);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregates::EdgeConcept;
+    use crate::value_objects::RelationshipCategory;
+    use uuid::Uuid;
+
+    fn depends(a: &EntityRef, b: &EntityRef) -> EdgeConcept {
+        EdgeConcept::new("Dependency", a.clone(), b.clone(), RelationshipCategory::DependsOn)
+    }
+
+    #[test]
+    fn test_dependency_cycles() {
+        let a = EntityRef::concept(Uuid::now_v7());
+        let b = EntityRef::concept(Uuid::now_v7());
+        let c = EntityRef::concept(Uuid::now_v7());
+        let d = EntityRef::concept(Uuid::now_v7());
+
+        let edges = [depends(&a, &b), depends(&b, &c), depends(&c, &a), depends(&c, &d)];
+        let graph = RelationshipGraph::from_edges(&edges);
+
+        let cycles = dependency_cycles(&graph);
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].len(), 3);
+        assert!(!cycles[0].contains(&d));
+    }
+
+    #[test]
+    fn test_find_path() {
+        let a = EntityRef::concept(Uuid::now_v7());
+        let b = EntityRef::concept(Uuid::now_v7());
+        let c = EntityRef::concept(Uuid::now_v7());
+
+        let edges = [depends(&a, &b), depends(&b, &c)];
+        let graph = RelationshipGraph::from_edges(&edges);
+
+        let from = graph.node_index(&a).unwrap();
+        let to = graph.node_index(&c).unwrap();
+        assert_eq!(find_path(&graph, from, to).map(|p| p.len()), Some(3));
+        assert!(find_path(&graph, to, from).is_none());
+    }
+}
@@ -0,0 +1,259 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Ego Network Extraction
+//!
+//! The ego network of an entity is every entity within N hops of it, plus
+//! the relationships among them. It is the data behind an "entity
+//! relationship map": the result is serializable and self-contained.
+//!
+//! Hops ignore edge direction. A hyperedge connects all of its participants
+//! to each other in a single hop.
+
+use crate::aggregates::{EdgeConcept, HyperEdgeConcept, RelationshipSpace};
+use crate::value_objects::{EntityKey, EntityRef, RelationshipCategory, RelationshipId};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+
+/// Which relationships an ego network traverses
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EgoFilter {
+    /// Only traverse these categories (empty = all)
+    pub categories: Vec<RelationshipCategory>,
+    /// Traverse relationships that are not currently active
+    pub include_inactive: bool,
+    /// Only traverse relationships at least this strong
+    pub min_strength: Option<f64>,
+    /// Traverse hyperedges as well as edges
+    pub include_hyperedges: bool,
+}
+
+impl Default for EgoFilter {
+    fn default() -> Self {
+        Self {
+            categories: Vec::new(),
+            include_inactive: false,
+            min_strength: None,
+            include_hyperedges: true,
+        }
+    }
+}
+
+impl EgoFilter {
+    fn admits_edge(&self, edge: &EdgeConcept) -> bool {
+        self.admits(&edge.category, edge.is_active(), edge.quality.strength)
+    }
+
+    fn admits_hyperedge(&self, hyperedge: &HyperEdgeConcept) -> bool {
+        self.include_hyperedges
+            && self.admits(&hyperedge.category, hyperedge.is_active(), hyperedge.quality.strength)
+    }
+
+    fn admits(&self, category: &RelationshipCategory, active: bool, strength: f64) -> bool {
+        (self.categories.is_empty() || self.categories.contains(category))
+            && (self.include_inactive || active)
+            && self.min_strength.is_none_or(|min| strength >= min)
+    }
+}
+
+/// Entity in an ego network
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EgoNode {
+    /// The entity
+    pub entity: EntityRef,
+    /// Hops from the center
+    pub distance: usize,
+}
+
+/// Subgraph around a center entity
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EgoNetwork {
+    /// The center entity
+    pub center: EntityRef,
+    /// Maximum hops traversed
+    pub radius: usize,
+    /// Entities within the radius, ordered by distance
+    pub nodes: Vec<EgoNode>,
+    /// Edges between entities in the network
+    pub edges: Vec<EdgeConcept>,
+    /// Hyperedges with at least two participants in the network
+    pub hyperedges: Vec<HyperEdgeConcept>,
+}
+
+impl EgoNetwork {
+    /// Check if an entity is in the network
+    pub fn contains(&self, entity_ref: &EntityRef) -> bool {
+        self.nodes.iter().any(|n| n.entity.same_entity(entity_ref))
+    }
+
+    /// Hops from the center to an entity
+    pub fn distance_to(&self, entity_ref: &EntityRef) -> Option<usize> {
+        self.nodes
+            .iter()
+            .find(|n| n.entity.same_entity(entity_ref))
+            .map(|n| n.distance)
+    }
+}
+
+enum Link {
+    Edge(RelationshipId),
+    HyperEdge(RelationshipId),
+}
+
+/// Extract the ego network of an entity
+pub fn ego_network(
+    space: &RelationshipSpace,
+    center: &EntityRef,
+    radius: usize,
+    filter: &EgoFilter,
+) -> EgoNetwork {
+    // Index admitted relationships by the entities they touch
+    let mut links: HashMap<EntityKey, Vec<Link>> = HashMap::new();
+    for edge in space.edges.values().filter(|e| filter.admits_edge(e)) {
+        links.entry(edge.source.key()).or_default().push(Link::Edge(edge.id));
+        links.entry(edge.target.key()).or_default().push(Link::Edge(edge.id));
+    }
+    for hyperedge in space.hyperedges.values().filter(|h| filter.admits_hyperedge(h)) {
+        for participant in hyperedge.participants.participants() {
+            links
+                .entry(participant.entity_ref.key())
+                .or_default()
+                .push(Link::HyperEdge(hyperedge.id));
+        }
+    }
+
+    // Breadth-first expansion from the center
+    let mut distances: HashMap<EntityKey, usize> = HashMap::from([(center.key(), 0)]);
+    let mut nodes = vec![EgoNode {
+        entity: center.clone(),
+        distance: 0,
+    }];
+    let mut frontier = vec![center.clone()];
+
+    for distance in 1..=radius {
+        let mut next = Vec::new();
+        for entity in &frontier {
+            let neighbours = links.get(&entity.key()).into_iter().flatten().flat_map(|link| {
+                match link {
+                    Link::Edge(id) => {
+                        let edge = &space.edges[id];
+                        vec![edge.source.clone(), edge.target.clone()]
+                    }
+                    Link::HyperEdge(id) => space.hyperedges[id]
+                        .participants
+                        .participants()
+                        .map(|p| p.entity_ref.clone())
+                        .collect(),
+                }
+            });
+            for neighbour in neighbours {
+                if let Entry::Vacant(slot) = distances.entry(neighbour.key()) {
+                    slot.insert(distance);
+                    nodes.push(EgoNode {
+                        entity: neighbour.clone(),
+                        distance,
+                    });
+                    next.push(neighbour);
+                }
+            }
+        }
+        if next.is_empty() {
+            break;
+        }
+        frontier = next;
+    }
+
+    // Induced subgraph over the collected entities
+    let inside: HashSet<&EntityKey> = distances.keys().collect();
+    let edges = space
+        .edges
+        .values()
+        .filter(|e| filter.admits_edge(e))
+        .filter(|e| inside.contains(&e.source.key()) && inside.contains(&e.target.key()))
+        .cloned()
+        .collect();
+    let hyperedges = space
+        .hyperedges
+        .values()
+        .filter(|h| filter.admits_hyperedge(h))
+        .filter(|h| {
+            h.participants
+                .participants()
+                .filter(|p| inside.contains(&p.entity_ref.key()))
+                .count()
+                >= 2
+        })
+        .cloned()
+        .collect();
+
+    EgoNetwork {
+        center: center.clone(),
+        radius,
+        nodes,
+        edges,
+        hyperedges,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value_objects::ParticipantRole;
+    use cim_domain_spaces::TopologicalSpaceId;
+    use uuid::Uuid;
+
+    fn active_edge(a: &EntityRef, b: &EntityRef, category: RelationshipCategory) -> EdgeConcept {
+        let mut edge = EdgeConcept::new("Edge", a.clone(), b.clone(), category);
+        edge.activate().unwrap();
+        edge
+    }
+
+    #[test]
+    fn test_ego_network_radius() {
+        let mut space = RelationshipSpace::new("Ego", TopologicalSpaceId::new());
+        let people: Vec<EntityRef> = (0..4).map(|_| EntityRef::person(Uuid::now_v7())).collect();
+
+        // Chain: 0 - 1 - 2 - 3
+        for pair in people.windows(2) {
+            space
+                .add_edge(active_edge(&pair[0], &pair[1], RelationshipCategory::Friendship))
+                .unwrap();
+        }
+
+        let ego = ego_network(&space, &people[0], 2, &EgoFilter::default());
+        assert_eq!(ego.nodes.len(), 3);
+        assert_eq!(ego.edges.len(), 2);
+        assert_eq!(ego.distance_to(&people[2]), Some(2));
+        assert!(!ego.contains(&people[3]));
+
+        // Filters exclude non-matching categories
+        let filter = EgoFilter {
+            categories: vec![RelationshipCategory::Employment],
+            ..EgoFilter::default()
+        };
+        assert_eq!(ego_network(&space, &people[0], 2, &filter).nodes.len(), 1);
+    }
+
+    #[test]
+    fn test_ego_network_through_hyperedge() {
+        let mut space = RelationshipSpace::new("Ego", TopologicalSpaceId::new());
+        let people: Vec<EntityRef> = (0..3).map(|_| EntityRef::person(Uuid::now_v7())).collect();
+
+        let mut team = HyperEdgeConcept::new("Team", RelationshipCategory::Membership);
+        for person in &people {
+            team.add_participant(person.clone(), ParticipantRole::Member, 1.0).unwrap();
+        }
+        team.activate().unwrap();
+        space.add_hyperedge(team);
+
+        let ego = ego_network(&space, &people[0], 1, &EgoFilter::default());
+        assert_eq!(ego.nodes.len(), 3);
+        assert_eq!(ego.hyperedges.len(), 1);
+
+        // Serializable for visualization
+        assert!(serde_json::to_string(&ego).is_ok());
+    }
+}
@@ -0,0 +1,156 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Hypergraph Projections
+//!
+//! Standard graph algorithms run over pairwise edges, so hyperedges are
+//! projected into a RelationshipGraph before analysis.
+//!
+//! ```text
+//! Bipartite                         Clique expansion
+//!
+//!   A ---+                            A ------- B
+//!        |                             \       /
+//!   B ---+--- [H]                       \     /
+//!        |                               \   /
+//!   C ---+                                 C
+//! ```
+//!
+//! - **Bipartite**: every hyperedge becomes a node (an `EntityType::Relationship`
+//!   reference to the hyperedge); each participant is linked to it with
+//!   weight `strength * participant weight`.
+//! - **Clique**: every pair of participants is linked directly with weight
+//!   `strength * min(participant weights)`.
+//!
+//! Projected edges are undirected and carry the hyperedge's id and category.
+
+use super::RelationshipGraph;
+use crate::aggregates::{HyperEdgeConcept, RelationshipSpace};
+use crate::value_objects::EntityRef;
+use serde::{Deserialize, Serialize};
+
+/// How hyperedges are projected into a pairwise graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum HyperEdgeProjection {
+    /// Participants link to a node standing for the hyperedge
+    #[default]
+    Bipartite,
+    /// Participants link pairwise to each other
+    Clique,
+}
+
+/// Graph node standing for a hyperedge in a bipartite projection
+pub fn hyperedge_node(hyperedge: &HyperEdgeConcept) -> EntityRef {
+    EntityRef::relationship(hyperedge.id.as_uuid())
+}
+
+impl RelationshipGraph {
+    /// Build a graph by projecting hyperedges
+    pub fn from_hyperedges<'a>(
+        hyperedges: impl IntoIterator<Item = &'a HyperEdgeConcept>,
+        projection: HyperEdgeProjection,
+    ) -> Self {
+        let mut graph = Self::new();
+        for hyperedge in hyperedges {
+            graph.insert_hyperedge(hyperedge, projection);
+        }
+        graph
+    }
+
+    /// Build a graph from the active edges and projected active hyperedges
+    /// of a space
+    pub fn from_space_projected(space: &RelationshipSpace, projection: HyperEdgeProjection) -> Self {
+        let mut graph = Self::from_space(space);
+        for hyperedge in space.active_hyperedges() {
+            graph.insert_hyperedge(hyperedge, projection);
+        }
+        graph
+    }
+
+    /// Project a hyperedge into this graph
+    pub fn insert_hyperedge(&mut self, hyperedge: &HyperEdgeConcept, projection: HyperEdgeProjection) {
+        let strength = hyperedge.quality.strength;
+        let participants: Vec<_> = hyperedge.participants.participants().collect();
+
+        match projection {
+            HyperEdgeProjection::Bipartite => {
+                let node = hyperedge_node(hyperedge);
+                self.insert_node(&node);
+                for participant in participants {
+                    self.insert_link(
+                        &participant.entity_ref,
+                        &node,
+                        hyperedge.id,
+                        hyperedge.category.clone(),
+                        strength * participant.weight,
+                        true,
+                    );
+                }
+            }
+            HyperEdgeProjection::Clique => {
+                for (i, a) in participants.iter().enumerate() {
+                    for b in &participants[i + 1..] {
+                        self.insert_link(
+                            &a.entity_ref,
+                            &b.entity_ref,
+                            hyperedge.id,
+                            hyperedge.category.clone(),
+                            strength * a.weight.min(b.weight),
+                            true,
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{centrality, CentralityMeasure};
+    use crate::value_objects::{ParticipantRole, RelationshipCategory};
+    use uuid::Uuid;
+
+    fn team(size: usize) -> (Vec<EntityRef>, HyperEdgeConcept) {
+        let people: Vec<EntityRef> = (0..size).map(|_| EntityRef::person(Uuid::now_v7())).collect();
+        let mut team = HyperEdgeConcept::new("Team", RelationshipCategory::Membership);
+        for person in &people {
+            team.add_participant(person.clone(), ParticipantRole::Member, 1.0).unwrap();
+        }
+        (people, team)
+    }
+
+    #[test]
+    fn test_bipartite_projection() {
+        let (people, team) = team(3);
+        let graph = RelationshipGraph::from_hyperedges([&team], HyperEdgeProjection::Bipartite);
+
+        assert_eq!(graph.node_count(), 4);
+        assert_eq!(graph.edge_count(), 3);
+
+        // The hyperedge node is the hub every participant reaches
+        let hub = graph.node_index(&hyperedge_node(&team)).unwrap();
+        let member = graph.node_index(&people[0]).unwrap();
+        assert_eq!(graph.successors(member).collect::<Vec<_>>(), vec![hub]);
+        assert_eq!(graph.successors(hub).count(), 3);
+        assert_eq!(centrality(&graph, CentralityMeasure::Degree)[0].entity, hyperedge_node(&team));
+    }
+
+    #[test]
+    fn test_clique_expansion() {
+        let (people, mut team) = team(4);
+        team.change_participant_weight(&people[0], 0.5).unwrap();
+        let graph = RelationshipGraph::from_hyperedges([&team], HyperEdgeProjection::Clique);
+
+        // 4 participants -> 6 pairs, no hyperedge node
+        assert_eq!(graph.node_count(), 4);
+        assert_eq!(graph.edge_count(), 6);
+        assert!(graph.node_index(&hyperedge_node(&team)).is_none());
+
+        let strength = team.quality.strength;
+        let weakest = graph.edges().iter().filter(|e| (e.weight - strength * 0.5).abs() < 1e-9);
+        assert_eq!(weakest.count(), 3);
+    }
+}
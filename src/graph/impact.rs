@@ -0,0 +1,163 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Termination Impact Analysis
+//!
+//! DependsOn and PartOf edges point from the supported entity to what
+//! supports it (dependent -> dependency, component -> whole). Terminating
+//! one cuts every support chain running through it.
+//!
+//! ```text
+//! X --DependsOn--> A --DependsOn--> B --DependsOn--> C
+//!                    ^^^^^^^^^^^^^^^
+//!                    terminated
+//!
+//! A loses B and C; X loses B and C through A
+//! ```
+//!
+//! An entity only loses support it cannot still reach by another path, so
+//! redundant dependencies limit the blast radius.
+
+use super::RelationshipGraph;
+use crate::value_objects::{EntityRef, RelationshipId};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashSet, VecDeque};
+
+/// An entity that would lose support
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImpactedEntity {
+    /// The entity losing support
+    pub entity: EntityRef,
+    /// Entities it would no longer reach
+    pub lost: Vec<EntityRef>,
+}
+
+/// What terminating a support edge would cut off
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TerminationImpact {
+    /// The edge under analysis
+    pub edge_id: RelationshipId,
+    /// Entities losing support, nearest to the edge first
+    pub entities: Vec<ImpactedEntity>,
+    /// Support relationships whose chains run through the edge
+    pub relationships: Vec<RelationshipId>,
+}
+
+impl TerminationImpact {
+    /// Check if terminating the edge would cut anything off
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+}
+
+/// Analyze terminating one edge of a support graph
+///
+/// Returns `None` if the edge is not part of the graph.
+pub fn termination_impact(graph: &RelationshipGraph, edge_id: RelationshipId) -> Option<TerminationImpact> {
+    let cut = graph.edges().iter().position(|e| e.relationship_id == edge_id)?;
+    let dependent = graph.edges()[cut].source;
+
+    // Everything upstream of the dependent relies on it, nearest first
+    let mut upstream = vec![dependent];
+    let mut seen = HashSet::from([dependent]);
+    let mut queue = VecDeque::from([dependent]);
+    while let Some(node) = queue.pop_front() {
+        for prev in graph.predecessors(node) {
+            if seen.insert(prev) {
+                upstream.push(prev);
+                queue.push_back(prev);
+            }
+        }
+    }
+
+    let mut entities = Vec::new();
+    let mut impacted = HashSet::new();
+    for &node in &upstream {
+        let before = reachable(graph, node, None);
+        let after = reachable(graph, node, Some(cut));
+        let mut lost: Vec<usize> = before.difference(&after).copied().collect();
+        if lost.is_empty() {
+            continue;
+        }
+        lost.sort_unstable();
+        impacted.insert(node);
+        entities.push(ImpactedEntity {
+            entity: graph.node(node).clone(),
+            lost: lost.into_iter().map(|n| graph.node(n).clone()).collect(),
+        });
+    }
+
+    let relationships = graph
+        .edges()
+        .iter()
+        .enumerate()
+        .filter(|(idx, e)| {
+            *idx == cut || (impacted.contains(&e.source) && impacted.contains(&e.target))
+        })
+        .map(|(_, e)| e.relationship_id)
+        .collect();
+
+    Some(TerminationImpact {
+        edge_id,
+        entities,
+        relationships,
+    })
+}
+
+/// Nodes reachable from a node, optionally ignoring one edge
+fn reachable(graph: &RelationshipGraph, from: usize, skip: Option<usize>) -> HashSet<usize> {
+    let mut seen = HashSet::from([from]);
+    let mut queue = VecDeque::from([from]);
+    while let Some(node) = queue.pop_front() {
+        for &e in graph.outgoing(node) {
+            if Some(e) == skip {
+                continue;
+            }
+            let next = graph.edges()[e].opposite(node);
+            if seen.insert(next) {
+                queue.push_back(next);
+            }
+        }
+    }
+    seen.remove(&from);
+    seen
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregates::EdgeConcept;
+    use crate::value_objects::RelationshipCategory;
+    use uuid::Uuid;
+
+    fn depends(a: &EntityRef, b: &EntityRef) -> EdgeConcept {
+        EdgeConcept::new("Dependency", a.clone(), b.clone(), RelationshipCategory::DependsOn)
+    }
+
+    #[test]
+    fn test_impact_follows_upstream_chain() {
+        let e: Vec<EntityRef> = (0..4).map(|_| EntityRef::concept(Uuid::now_v7())).collect();
+        // x -> a -> b -> c
+        let edges = [depends(&e[0], &e[1]), depends(&e[1], &e[2]), depends(&e[2], &e[3])];
+        let graph = RelationshipGraph::from_edges(&edges);
+
+        let impact = termination_impact(&graph, edges[1].id).unwrap();
+        assert_eq!(impact.entities.len(), 2);
+        assert_eq!(impact.entities[0].entity, e[1]);
+        assert_eq!(impact.entities[0].lost.len(), 2);
+        assert_eq!(impact.entities[1].entity, e[0]);
+        assert_eq!(impact.relationships, vec![edges[0].id, edges[1].id]);
+    }
+
+    #[test]
+    fn test_redundant_path_limits_impact() {
+        let e: Vec<EntityRef> = (0..3).map(|_| EntityRef::concept(Uuid::now_v7())).collect();
+        // a -> c directly and via b
+        let edges = [depends(&e[0], &e[2]), depends(&e[0], &e[1]), depends(&e[1], &e[2])];
+        let graph = RelationshipGraph::from_edges(&edges);
+
+        assert!(termination_impact(&graph, edges[0].id).unwrap().is_empty());
+        assert!(termination_impact(&graph, RelationshipId::new()).is_none());
+    }
+}
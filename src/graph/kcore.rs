@@ -0,0 +1,152 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! k-Core Decomposition
+//!
+//! The k-core of a graph is its largest subgraph in which every entity has
+//! at least k neighbours. An entity's core number is the largest k for which
+//! it belongs to the k-core; the entities with the highest core numbers are
+//! the dense backbone of the network.
+//!
+//! Edge direction and weight are ignored, and parallel edges between the
+//! same pair of entities count once. Computed with the Batagelj-Zaversnik
+//! bucket algorithm in O(n + m).
+
+use super::RelationshipGraph;
+use crate::value_objects::{EntityKey, EntityRef};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+
+/// Core number of every entity in a graph
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CoreDecomposition {
+    /// Core number per entity
+    pub cores: HashMap<EntityKey, usize>,
+    /// Largest core number in the graph
+    pub degeneracy: usize,
+}
+
+impl CoreDecomposition {
+    /// Get the core number of an entity
+    pub fn core_of(&self, entity_ref: &EntityRef) -> Option<usize> {
+        self.cores.get(&entity_ref.key()).copied()
+    }
+
+    /// Entities in the k-core
+    pub fn k_core(&self, k: usize) -> Vec<&EntityKey> {
+        self.cores
+            .iter()
+            .filter(|(_, &core)| core >= k)
+            .map(|(key, _)| key)
+            .collect()
+    }
+
+    /// Entities in the innermost core
+    pub fn backbone(&self) -> Vec<&EntityKey> {
+        self.k_core(self.degeneracy)
+    }
+}
+
+/// Compute core numbers, indexed by node
+pub fn core_numbers(graph: &RelationshipGraph) -> Vec<usize> {
+    let n = graph.node_count();
+    let mut neighbours: Vec<BTreeSet<usize>> = vec![BTreeSet::new(); n];
+    for edge in graph.edges() {
+        if edge.source != edge.target {
+            neighbours[edge.source].insert(edge.target);
+            neighbours[edge.target].insert(edge.source);
+        }
+    }
+
+    let mut degree: Vec<usize> = neighbours.iter().map(BTreeSet::len).collect();
+    let max_degree = degree.iter().copied().max().unwrap_or(0);
+
+    // Bucket sort nodes by degree
+    let mut bucket_start = vec![0usize; max_degree + 2];
+    for &d in &degree {
+        bucket_start[d + 1] += 1;
+    }
+    for d in 1..bucket_start.len() {
+        bucket_start[d] += bucket_start[d - 1];
+    }
+    let mut order = vec![0usize; n];
+    let mut position = vec![0usize; n];
+    let mut next_slot = bucket_start.clone();
+    for node in 0..n {
+        position[node] = next_slot[degree[node]];
+        order[position[node]] = node;
+        next_slot[degree[node]] += 1;
+    }
+
+    // Peel nodes in order of current degree
+    for i in 0..n {
+        let node = order[i];
+        for &other in &neighbours[node] {
+            if degree[other] > degree[node] {
+                // Move `other` to the front of its bucket, then shrink it
+                let d = degree[other];
+                let front = bucket_start[d];
+                let swap = order[front];
+                if swap != other {
+                    order.swap(position[other], front);
+                    position[swap] = position[other];
+                    position[other] = front;
+                }
+                bucket_start[d] += 1;
+                degree[other] -= 1;
+            }
+        }
+    }
+
+    degree
+}
+
+/// Compute the k-core decomposition of a graph
+pub fn k_core_decomposition(graph: &RelationshipGraph) -> CoreDecomposition {
+    let cores = core_numbers(graph);
+    CoreDecomposition {
+        degeneracy: cores.iter().copied().max().unwrap_or(0),
+        cores: cores
+            .into_iter()
+            .enumerate()
+            .map(|(node, core)| (graph.node(node).key(), core))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregates::EdgeConcept;
+    use crate::value_objects::RelationshipCategory;
+    use uuid::Uuid;
+
+    fn contact(a: &EntityRef, b: &EntityRef) -> EdgeConcept {
+        EdgeConcept::new("Contact", a.clone(), b.clone(), RelationshipCategory::ProfessionalContact)
+    }
+
+    #[test]
+    fn test_core_numbers() {
+        let e: Vec<EntityRef> = (0..6).map(|_| EntityRef::person(Uuid::now_v7())).collect();
+        // K4 on 0..4, 4 hangs off 3, 5 hangs off 4
+        let mut edges = Vec::new();
+        for i in 0..4 {
+            for j in (i + 1)..4 {
+                edges.push(contact(&e[i], &e[j]));
+            }
+        }
+        edges.push(contact(&e[3], &e[4]));
+        edges.push(contact(&e[4], &e[5]));
+        // Parallel edge does not raise the core
+        edges.push(contact(&e[5], &e[4]));
+
+        let decomposition = k_core_decomposition(&RelationshipGraph::from_edges(&edges));
+        assert_eq!(decomposition.degeneracy, 3);
+        assert_eq!(decomposition.core_of(&e[0]), Some(3));
+        assert_eq!(decomposition.core_of(&e[4]), Some(1));
+        assert_eq!(decomposition.core_of(&e[5]), Some(1));
+        assert_eq!(decomposition.backbone().len(), 4);
+        assert_eq!(decomposition.k_core(1).len(), 6);
+    }
+}
@@ -0,0 +1,233 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Relationship Lineage
+//!
+//! Composition, splitting, merging, renewal, and inference all produce
+//! successor relationships. Each successor records where it came from with
+//! a DerivesFrom edge between the two relationships:
+//!
+//! ```text
+//! relationship:{derived} --DerivesFrom--> relationship:{source}
+//! ```
+//!
+//! Following those edges forwards yields a relationship's ancestry;
+//! following them backwards yields its descendants. Lineage is history, so
+//! every derivation edge except rejected ones is traversed, whatever the
+//! state of the relationships involved.
+//!
+//! Traversal visits each relationship once, so malformed cyclic lineage
+//! terminates; any cycles reached are reported alongside the result.
+
+use super::{dependency_cycles, RelationshipGraph};
+use crate::aggregates::{EdgeConcept, EdgeState, RelationshipSpace};
+use crate::value_objects::{EntityRef, EntityType, RelationshipCategory, RelationshipId};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Relationship reached while tracing lineage
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LineageEntry {
+    /// The related relationship
+    pub relationship_id: RelationshipId,
+    /// Derivation steps from the root (1 = direct parent or child)
+    pub depth: usize,
+    /// DerivesFrom edge through which it was first reached
+    pub via: RelationshipId,
+}
+
+/// Ancestry and descendants of a relationship
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Lineage {
+    /// The relationship whose lineage was traced
+    pub root: RelationshipId,
+    /// Relationships the root derives from, nearest first
+    pub ancestors: Vec<LineageEntry>,
+    /// Relationships derived from the root, nearest first
+    pub descendants: Vec<LineageEntry>,
+    /// Whether the depth limit stopped the traversal early
+    pub truncated: bool,
+    /// Derivation cycles encountered (a modeling error)
+    pub cycles: Vec<Vec<RelationshipId>>,
+}
+
+impl Lineage {
+    /// Check if a relationship is an ancestor of the root
+    pub fn has_ancestor(&self, id: &RelationshipId) -> bool {
+        self.ancestors.iter().any(|e| e.relationship_id == *id)
+    }
+
+    /// Check if a relationship is a descendant of the root
+    pub fn has_descendant(&self, id: &RelationshipId) -> bool {
+        self.descendants.iter().any(|e| e.relationship_id == *id)
+    }
+}
+
+/// Create a DerivesFrom edge recording that `derived` came from `source`
+pub fn derivation_edge(derived: RelationshipId, source: RelationshipId) -> EdgeConcept {
+    EdgeConcept::new(
+        "Derivation",
+        EntityRef::relationship(derived.as_uuid()),
+        EntityRef::relationship(source.as_uuid()),
+        RelationshipCategory::DerivesFrom,
+    )
+}
+
+/// Graph of all derivation edges between relationships in a space
+pub fn lineage_graph(space: &RelationshipSpace) -> RelationshipGraph {
+    RelationshipGraph::from_space_filtered(space, |e| {
+        e.category == RelationshipCategory::DerivesFrom
+            && e.state != EdgeState::Rejected
+            && e.source.entity_type == EntityType::Relationship
+            && e.target.entity_type == EntityType::Relationship
+    })
+}
+
+/// Trace the lineage of a relationship
+///
+/// `max_depth` limits how many derivation steps are followed in each
+/// direction (`None` = unlimited).
+pub fn lineage(space: &RelationshipSpace, root: RelationshipId, max_depth: Option<usize>) -> Lineage {
+    let graph = lineage_graph(space);
+    let mut result = Lineage {
+        root,
+        ancestors: Vec::new(),
+        descendants: Vec::new(),
+        truncated: false,
+        cycles: Vec::new(),
+    };
+
+    let Some(start) = graph.node_index(&EntityRef::relationship(root.as_uuid())) else {
+        return result;
+    };
+
+    let mut reached = HashSet::from([start]);
+    let (ancestors, truncated_up) = trace(&graph, start, max_depth, true, &mut reached);
+    let (descendants, truncated_down) = trace(&graph, start, max_depth, false, &mut reached);
+    result.ancestors = ancestors;
+    result.descendants = descendants;
+    result.truncated = truncated_up || truncated_down;
+
+    result.cycles = dependency_cycles(&graph)
+        .into_iter()
+        .filter(|cycle| {
+            cycle
+                .iter()
+                .any(|r| graph.node_index(r).is_some_and(|n| reached.contains(&n)))
+        })
+        .map(|cycle| cycle.iter().map(relationship_id).collect())
+        .collect();
+
+    result
+}
+
+/// Breadth-first walk along (or against) derivation edges
+///
+/// Returns the entries reached and whether the depth limit cut the walk short.
+fn trace(
+    graph: &RelationshipGraph,
+    start: usize,
+    max_depth: Option<usize>,
+    upwards: bool,
+    reached: &mut HashSet<usize>,
+) -> (Vec<LineageEntry>, bool) {
+    let edges_of = |node: usize| {
+        if upwards {
+            graph.outgoing(node)
+        } else {
+            graph.incoming(node)
+        }
+    };
+
+    let mut entries = Vec::new();
+    let mut visited = HashSet::from([start]);
+    let mut frontier = vec![start];
+    let mut depth = 0;
+
+    while !frontier.is_empty() {
+        let at_limit = max_depth.is_some_and(|max| depth >= max);
+        depth += 1;
+        let mut next = Vec::new();
+        for &node in &frontier {
+            for &e in edges_of(node) {
+                let edge = &graph.edges()[e];
+                let other = edge.opposite(node);
+                if visited.contains(&other) {
+                    continue;
+                }
+                if at_limit {
+                    return (entries, true);
+                }
+                visited.insert(other);
+                reached.insert(other);
+                entries.push(LineageEntry {
+                    relationship_id: relationship_id(graph.node(other)),
+                    depth,
+                    via: edge.relationship_id,
+                });
+                next.push(other);
+            }
+        }
+        frontier = next;
+    }
+
+    (entries, false)
+}
+
+fn relationship_id(entity_ref: &EntityRef) -> RelationshipId {
+    RelationshipId::from_uuid(entity_ref.entity_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cim_domain_spaces::TopologicalSpaceId;
+
+    /// original -> renewed -> (split_a, split_b)
+    fn renewal_chain() -> (RelationshipSpace, Vec<RelationshipId>) {
+        let mut space = RelationshipSpace::new("Lineage", TopologicalSpaceId::new());
+        let ids: Vec<RelationshipId> = (0..4).map(|_| RelationshipId::new()).collect();
+        space.add_edge(derivation_edge(ids[1], ids[0])).unwrap();
+        space.add_edge(derivation_edge(ids[2], ids[1])).unwrap();
+        space.add_edge(derivation_edge(ids[3], ids[1])).unwrap();
+        (space, ids)
+    }
+
+    #[test]
+    fn test_ancestors_and_descendants() {
+        let (space, ids) = renewal_chain();
+
+        let of_split = lineage(&space, ids[2], None);
+        assert_eq!(of_split.ancestors.len(), 2);
+        assert_eq!(of_split.ancestors[1].relationship_id, ids[0]);
+        assert_eq!(of_split.ancestors[1].depth, 2);
+        assert!(of_split.descendants.is_empty());
+
+        let of_original = lineage(&space, ids[0], None);
+        assert_eq!(of_original.descendants.len(), 3);
+        assert!(of_original.has_descendant(&ids[3]));
+        assert!(!of_original.truncated);
+        assert!(of_original.cycles.is_empty());
+    }
+
+    #[test]
+    fn test_depth_limit_truncates() {
+        let (space, ids) = renewal_chain();
+        let limited = lineage(&space, ids[0], Some(1));
+        assert_eq!(limited.descendants.len(), 1);
+        assert!(limited.truncated);
+    }
+
+    #[test]
+    fn test_cycles_terminate_and_are_reported() {
+        let (mut space, ids) = renewal_chain();
+        space.add_edge(derivation_edge(ids[0], ids[2])).unwrap();
+
+        let traced = lineage(&space, ids[0], None);
+        assert_eq!(traced.ancestors.len(), 2);
+        assert_eq!(traced.descendants.len(), 3);
+        assert_eq!(traced.cycles.len(), 1);
+        assert_eq!(traced.cycles[0].len(), 3);
+    }
+}
@@ -0,0 +1,283 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Graph Analysis for the Relationship Domain
+//!
+//! Read-only graph views over a RelationshipSpace and the algorithms that
+//! run on them.
+//!
+//! ## Graph Model
+//!
+//! ```text
+//! RelationshipGraph
+//!     |
+//!     +-- Nodes: entities, keyed by EntityKey
+//!     +-- Edges: EdgeConcepts, directed source -> target
+//!     +-- Adjacency: outgoing / incoming edge indices per node
+//! ```
+//!
+//! Nodes are keyed by entity identity, not by the full EntityRef, so two
+//! references to the same entity pinned at different CIDs or versions
+//! collapse to a single node. Edges of symmetric categories (Friendship,
+//! ProfessionalContact) are traversable in both directions.
+//!
+//! Hyperedges enter a graph through a `HyperEdgeProjection`: either as
+//! nodes of their own (bipartite) or as pairwise edges between their
+//! participants (clique expansion).
+
+mod centrality;
+mod cliques;
+mod community;
+mod containment;
+mod cycles;
+mod ego;
+mod hypergraph;
+mod impact;
+mod kcore;
+mod lineage;
+mod ordering;
+mod reachability;
+
+pub use centrality::{
+    betweenness_centrality, centrality, degree_centrality, pagerank, CentralityMeasure,
+    EntityScore,
+};
+pub use cliques::{maximal_cliques, Clique};
+pub use community::{label_propagation, CommunityAssignment};
+pub use containment::{containment_graph, entities_at, SiteEntry};
+pub use cycles::{dependency_cycles, find_path};
+pub use ego::{ego_network, EgoFilter, EgoNetwork, EgoNode};
+pub use hypergraph::{hyperedge_node, HyperEdgeProjection};
+pub use impact::{termination_impact, ImpactedEntity, TerminationImpact};
+pub use kcore::{core_numbers, k_core_decomposition, CoreDecomposition};
+pub use lineage::{derivation_edge, lineage, lineage_graph, Lineage, LineageEntry};
+pub use ordering::{topological_order, TopologicalOrder};
+pub use reachability::ReachabilityIndex;
+
+use crate::aggregates::{EdgeConcept, RelationshipSpace};
+use crate::value_objects::{EntityKey, EntityRef, RelationshipCategory, RelationshipId};
+use std::collections::HashMap;
+
+/// Directed edge in a RelationshipGraph
+#[derive(Debug, Clone)]
+pub struct GraphEdge {
+    /// Relationship this graph edge was built from
+    pub relationship_id: RelationshipId,
+    /// Index of the source node
+    pub source: usize,
+    /// Index of the target node
+    pub target: usize,
+    /// Category of the relationship
+    pub category: RelationshipCategory,
+    /// Edge weight (relationship strength by default)
+    pub weight: f64,
+}
+
+impl GraphEdge {
+    /// Get the endpoint opposite to the given node
+    pub fn opposite(&self, node: usize) -> usize {
+        if self.source == node {
+            self.target
+        } else {
+            self.source
+        }
+    }
+}
+
+/// Adjacency view over a set of edges
+///
+/// Built on demand from a RelationshipSpace; it is never persisted and
+/// never mutated after construction.
+#[derive(Debug, Clone, Default)]
+pub struct RelationshipGraph {
+    nodes: Vec<EntityRef>,
+    index: HashMap<EntityKey, usize>,
+    edges: Vec<GraphEdge>,
+    outgoing: Vec<Vec<usize>>,
+    incoming: Vec<Vec<usize>>,
+}
+
+impl RelationshipGraph {
+    /// Create an empty graph
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a graph from an iterator of edges
+    pub fn from_edges<'a>(edges: impl IntoIterator<Item = &'a EdgeConcept>) -> Self {
+        let mut graph = Self::new();
+        for edge in edges {
+            graph.insert_edge(edge);
+        }
+        graph
+    }
+
+    /// Build a graph from the active edges of a space
+    pub fn from_space(space: &RelationshipSpace) -> Self {
+        Self::from_edges(space.active_edges())
+    }
+
+    /// Build a graph from the edges of a space matching a predicate
+    pub fn from_space_filtered<F>(space: &RelationshipSpace, filter: F) -> Self
+    where
+        F: Fn(&EdgeConcept) -> bool,
+    {
+        Self::from_edges(space.edges.values().filter(|e| filter(e)))
+    }
+
+    /// Add an edge, creating its endpoint nodes as needed
+    pub fn insert_edge(&mut self, edge: &EdgeConcept) {
+        self.insert_link(
+            &edge.source,
+            &edge.target,
+            edge.id,
+            edge.category.clone(),
+            edge.quality.strength,
+            edge.is_symmetric(),
+        );
+    }
+
+    /// Add a graph edge between two entities, creating nodes as needed
+    fn insert_link(
+        &mut self,
+        source: &EntityRef,
+        target: &EntityRef,
+        relationship_id: RelationshipId,
+        category: RelationshipCategory,
+        weight: f64,
+        symmetric: bool,
+    ) {
+        let source = self.insert_node(source);
+        let target = self.insert_node(target);
+        let idx = self.edges.len();
+        self.edges.push(GraphEdge {
+            relationship_id,
+            source,
+            target,
+            category,
+            weight,
+        });
+        self.outgoing[source].push(idx);
+        self.incoming[target].push(idx);
+        if symmetric && source != target {
+            self.outgoing[target].push(idx);
+            self.incoming[source].push(idx);
+        }
+    }
+
+    /// Add a node, returning its index
+    pub fn insert_node(&mut self, entity_ref: &EntityRef) -> usize {
+        let key = entity_ref.key();
+        if let Some(&idx) = self.index.get(&key) {
+            return idx;
+        }
+        let idx = self.nodes.len();
+        self.nodes.push(entity_ref.clone());
+        self.index.insert(key, idx);
+        self.outgoing.push(Vec::new());
+        self.incoming.push(Vec::new());
+        idx
+    }
+
+    /// Number of nodes
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Number of edges
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// All nodes, indexed by position
+    pub fn nodes(&self) -> &[EntityRef] {
+        &self.nodes
+    }
+
+    /// All edges, indexed by position
+    pub fn edges(&self) -> &[GraphEdge] {
+        &self.edges
+    }
+
+    /// Get a node by index
+    pub fn node(&self, idx: usize) -> &EntityRef {
+        &self.nodes[idx]
+    }
+
+    /// Find the index of an entity
+    pub fn node_index(&self, entity_ref: &EntityRef) -> Option<usize> {
+        self.index.get(&entity_ref.key()).copied()
+    }
+
+    /// Outgoing edge indices of a node
+    pub fn outgoing(&self, node: usize) -> &[usize] {
+        &self.outgoing[node]
+    }
+
+    /// Incoming edge indices of a node
+    pub fn incoming(&self, node: usize) -> &[usize] {
+        &self.incoming[node]
+    }
+
+    /// Successor node indices of a node
+    pub fn successors(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        self.outgoing[node].iter().map(move |&e| self.edges[e].opposite(node))
+    }
+
+    /// Predecessor node indices of a node
+    pub fn predecessors(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        self.incoming[node].iter().map(move |&e| self.edges[e].opposite(node))
+    }
+
+    /// Successor node indices of a node with the connecting edge weight
+    pub fn weighted_successors(&self, node: usize) -> impl Iterator<Item = (usize, f64)> + '_ {
+        self.outgoing[node].iter().map(move |&e| {
+            let edge = &self.edges[e];
+            (edge.opposite(node), edge.weight)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_pinned_refs_share_node() {
+        let person_id = Uuid::now_v7();
+        let org = EntityRef::organization(Uuid::now_v7());
+
+        let e1 = EdgeConcept::new(
+            "Employment",
+            EntityRef::person(person_id),
+            org.clone(),
+            RelationshipCategory::Employment,
+        );
+        let e2 = EdgeConcept::new(
+            "Membership",
+            EntityRef::person(person_id).with_version(3),
+            org,
+            RelationshipCategory::Membership,
+        );
+
+        let graph = RelationshipGraph::from_edges([&e1, &e2]);
+        assert_eq!(graph.node_count(), 2);
+        assert_eq!(graph.edge_count(), 2);
+    }
+
+    #[test]
+    fn test_symmetric_edges_traverse_both_ways() {
+        let a = EntityRef::person(Uuid::now_v7());
+        let b = EntityRef::person(Uuid::now_v7());
+
+        let friendship = EdgeConcept::new("Friends", a.clone(), b.clone(), RelationshipCategory::Friendship);
+        let graph = RelationshipGraph::from_edges([&friendship]);
+
+        let ia = graph.node_index(&a).unwrap();
+        let ib = graph.node_index(&b).unwrap();
+        assert_eq!(graph.successors(ia).collect::<Vec<_>>(), vec![ib]);
+        assert_eq!(graph.successors(ib).collect::<Vec<_>>(), vec![ia]);
+    }
+}
@@ -0,0 +1,134 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Topological Ordering
+//!
+//! Temporal relationships (Precedes, Triggers) describe an order over
+//! events and process steps. A topological sort recovers that order from
+//! relationship data.
+//!
+//! Kahn's algorithm is used, always taking the earliest-inserted ready
+//! node, so the order is deterministic for a given graph. Nodes on a cycle,
+//! or downstream of one, cannot be ordered; they are listed separately and
+//! the cycles themselves are reported.
+
+use super::{dependency_cycles, RelationshipGraph};
+use crate::value_objects::EntityRef;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Result of topologically sorting a graph
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TopologicalOrder {
+    /// Entities in order: every entity comes after all its predecessors
+    pub order: Vec<EntityRef>,
+    /// Entities on or downstream of a cycle
+    pub unordered: Vec<EntityRef>,
+    /// Cycles preventing a complete order
+    pub cycles: Vec<Vec<EntityRef>>,
+}
+
+impl TopologicalOrder {
+    /// Check if every entity was ordered
+    pub fn is_complete(&self) -> bool {
+        self.unordered.is_empty()
+    }
+
+    /// Position of an entity in the order
+    pub fn position(&self, entity_ref: &EntityRef) -> Option<usize> {
+        self.order.iter().position(|e| e.same_entity(entity_ref))
+    }
+}
+
+/// Topologically sort a graph
+pub fn topological_order(graph: &RelationshipGraph) -> TopologicalOrder {
+    let n = graph.node_count();
+    let mut in_degree = vec![0usize; n];
+    for node in 0..n {
+        for next in graph.successors(node) {
+            in_degree[next] += 1;
+        }
+    }
+
+    let mut ready: BinaryHeap<Reverse<usize>> =
+        (0..n).filter(|&node| in_degree[node] == 0).map(Reverse).collect();
+    let mut ordered = vec![false; n];
+    let mut order = Vec::with_capacity(n);
+
+    while let Some(Reverse(node)) = ready.pop() {
+        ordered[node] = true;
+        order.push(graph.node(node).clone());
+        for next in graph.successors(node) {
+            in_degree[next] -= 1;
+            if in_degree[next] == 0 {
+                ready.push(Reverse(next));
+            }
+        }
+    }
+
+    let unordered: Vec<EntityRef> = (0..n)
+        .filter(|&node| !ordered[node])
+        .map(|node| graph.node(node).clone())
+        .collect();
+    let cycles = if unordered.is_empty() {
+        Vec::new()
+    } else {
+        dependency_cycles(graph)
+    };
+
+    TopologicalOrder {
+        order,
+        unordered,
+        cycles,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregates::EdgeConcept;
+    use crate::value_objects::RelationshipCategory;
+    use uuid::Uuid;
+
+    fn precedes(a: &EntityRef, b: &EntityRef) -> EdgeConcept {
+        EdgeConcept::new("Precedes", a.clone(), b.clone(), RelationshipCategory::Precedes)
+    }
+
+    #[test]
+    fn test_orders_process_steps() {
+        let steps: Vec<EntityRef> = (0..4).map(|_| EntityRef::concept(Uuid::now_v7())).collect();
+        // 0 -> 1 -> 3, 0 -> 2 -> 3
+        let edges = [
+            precedes(&steps[2], &steps[3]),
+            precedes(&steps[0], &steps[1]),
+            precedes(&steps[1], &steps[3]),
+            precedes(&steps[0], &steps[2]),
+        ];
+        let sorted = topological_order(&RelationshipGraph::from_edges(&edges));
+
+        assert!(sorted.is_complete());
+        assert!(sorted.cycles.is_empty());
+        assert_eq!(sorted.position(&steps[0]), Some(0));
+        assert_eq!(sorted.position(&steps[3]), Some(3));
+    }
+
+    #[test]
+    fn test_reports_cycles() {
+        let steps: Vec<EntityRef> = (0..4).map(|_| EntityRef::concept(Uuid::now_v7())).collect();
+        // 0 -> 1 -> 2 -> 1, 2 -> 3
+        let edges = [
+            precedes(&steps[0], &steps[1]),
+            precedes(&steps[1], &steps[2]),
+            precedes(&steps[2], &steps[1]),
+            precedes(&steps[2], &steps[3]),
+        ];
+        let sorted = topological_order(&RelationshipGraph::from_edges(&edges));
+
+        assert_eq!(sorted.order, vec![steps[0].clone()]);
+        assert_eq!(sorted.unordered.len(), 3);
+        assert_eq!(sorted.cycles.len(), 1);
+        assert_eq!(sorted.cycles[0].len(), 2);
+    }
+}
@@ -0,0 +1,161 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Reachability Index
+//!
+//! Answers "can A reach B?" over directed relationships in constant time
+//! after a one-off build, for repeated queries on large graphs.
+//!
+//! ## Construction
+//!
+//! ```text
+//! graph --Tarjan--> condensation DAG --reverse topological sweep--> bitsets
+//! ```
+//!
+//! Entities in the same strongly connected component reach each other, so
+//! the transitive closure is stored per component rather than per entity:
+//! one bitset of reachable components for each component. A query is two
+//! hash lookups and a bit test.
+
+use super::cycles::strongly_connected_components;
+use super::RelationshipGraph;
+use crate::value_objects::{EntityKey, EntityRef};
+use std::collections::HashMap;
+
+/// Precomputed transitive closure of a graph
+#[derive(Debug, Clone, Default)]
+pub struct ReachabilityIndex {
+    nodes: Vec<EntityRef>,
+    component_of: HashMap<EntityKey, usize>,
+    members: Vec<Vec<usize>>,
+    reach: Vec<Vec<u64>>,
+}
+
+impl ReachabilityIndex {
+    /// Build the index for a graph
+    pub fn build(graph: &RelationshipGraph) -> Self {
+        let components = strongly_connected_components(graph);
+        let count = components.len();
+        let words = count.div_ceil(64);
+
+        let mut node_component = vec![0; graph.node_count()];
+        for (c, component) in components.iter().enumerate() {
+            for &node in component {
+                node_component[node] = c;
+            }
+        }
+
+        // Tarjan emits components sinks-first, so every successor component
+        // is complete before the components that reach it
+        let mut reach = vec![vec![0u64; words]; count];
+        for (c, component) in components.iter().enumerate() {
+            let mut bits = vec![0u64; words];
+            bits[c / 64] |= 1 << (c % 64);
+            for &node in component {
+                for next in graph.successors(node) {
+                    let target = node_component[next];
+                    if target != c {
+                        for (word, other) in bits.iter_mut().zip(&reach[target]) {
+                            *word |= other;
+                        }
+                    }
+                }
+            }
+            reach[c] = bits;
+        }
+
+        Self {
+            nodes: graph.nodes().to_vec(),
+            component_of: graph
+                .nodes()
+                .iter()
+                .enumerate()
+                .map(|(node, entity)| (entity.key(), node_component[node]))
+                .collect(),
+            members: components,
+            reach,
+        }
+    }
+
+    /// Check if there is a directed path from one entity to another
+    ///
+    /// Every entity in the graph reaches itself; entities not in the graph
+    /// reach nothing.
+    pub fn can_reach(&self, from: &EntityRef, to: &EntityRef) -> bool {
+        match (self.component_of.get(&from.key()), self.component_of.get(&to.key())) {
+            (Some(&a), Some(&b)) => self.reach[a][b / 64] & (1 << (b % 64)) != 0,
+            _ => false,
+        }
+    }
+
+    /// All entities reachable from an entity, excluding itself
+    pub fn reachable_from(&self, from: &EntityRef) -> Vec<EntityRef> {
+        let Some(&a) = self.component_of.get(&from.key()) else {
+            return Vec::new();
+        };
+        (0..self.members.len())
+            .filter(|&c| self.reach[a][c / 64] & (1 << (c % 64)) != 0)
+            .flat_map(|c| self.members[c].iter())
+            .map(|&node| &self.nodes[node])
+            .filter(|entity| !entity.same_entity(from))
+            .cloned()
+            .collect()
+    }
+
+    /// Number of indexed entities
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Check if the index is empty
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregates::EdgeConcept;
+    use crate::value_objects::RelationshipCategory;
+    use uuid::Uuid;
+
+    fn part_of(a: &EntityRef, b: &EntityRef) -> EdgeConcept {
+        EdgeConcept::new("PartOf", a.clone(), b.clone(), RelationshipCategory::PartOf)
+    }
+
+    #[test]
+    fn test_can_reach() {
+        let e: Vec<EntityRef> = (0..5).map(|_| EntityRef::concept(Uuid::now_v7())).collect();
+        // 0 -> 1 -> 2 <-> 3, 4 isolated via 4 -> 0
+        let edges = [
+            part_of(&e[0], &e[1]),
+            part_of(&e[1], &e[2]),
+            part_of(&e[2], &e[3]),
+            part_of(&e[3], &e[2]),
+            part_of(&e[4], &e[0]),
+        ];
+        let index = ReachabilityIndex::build(&RelationshipGraph::from_edges(&edges));
+
+        assert!(index.can_reach(&e[4], &e[3]));
+        assert!(index.can_reach(&e[3], &e[2]));
+        assert!(!index.can_reach(&e[2], &e[1]));
+        assert!(!index.can_reach(&e[0], &e[4]));
+        assert!(index.can_reach(&e[0], &e[0]));
+        assert_eq!(index.reachable_from(&e[1]).len(), 2);
+        assert!(!index.can_reach(&e[0], &EntityRef::concept(Uuid::now_v7())));
+    }
+
+    #[test]
+    fn test_index_spans_many_words() {
+        // A chain longer than one 64-bit word
+        let chain: Vec<EntityRef> = (0..150).map(|_| EntityRef::concept(Uuid::now_v7())).collect();
+        let edges: Vec<EdgeConcept> = chain.windows(2).map(|w| part_of(&w[0], &w[1])).collect();
+        let index = ReachabilityIndex::build(&RelationshipGraph::from_edges(&edges));
+
+        assert!(index.can_reach(&chain[0], &chain[149]));
+        assert!(!index.can_reach(&chain[149], &chain[0]));
+        assert_eq!(index.reachable_from(&chain[0]).len(), 149);
+    }
+}
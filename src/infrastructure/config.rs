@@ -0,0 +1,818 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Service Configuration
+//!
+//! Settings of the relationship service, read from a TOML or YAML file
+//! (chosen by extension) and overridden by environment variables:
+//!
+//! ```toml
+//! [nats]
+//! servers = ["nats://nats-1:4222", "nats://nats-2:4222"]
+//! name = "relationship-service"
+//!
+//! [streams]
+//! events = "RELATIONSHIP_EVENTS"
+//! consumer_prefix = "relationship-cross-domain"
+//! create_events_stream = true
+//!
+//! [lease]
+//! bucket = "RELATIONSHIP_LEASES"
+//! ttl_secs = 15
+//!
+//! [snapshots]
+//! enabled = true
+//! bucket = "RELATIONSHIP_SNAPSHOTS"
+//! interval_secs = 300
+//! every_events = 1000
+//!
+//! [cascade]
+//! standard = true
+//!
+//! [[cascade.rules]]
+//! entity_type = "Person"
+//! category = "Employment"
+//! applies_to = { kind = "edges", side = "both" }
+//! action = "flag_for_review"
+//!
+//! [features]
+//! cross_domain = true
+//! schema_catalog = true
+//! cloud_events = false
+//! avro_events = false
+//!
+//! [http]
+//! addr = "0.0.0.0:8080"
+//!
+//! [health]
+//! addr = "0.0.0.0:8081"
+//! max_lag = 1000
+//!
+//! [shutdown]
+//! drain_timeout_secs = 30
+//!
+//! [expiry]
+//! sweep_interval_secs = 60
+//!
+//! [verification]
+//! mode = "suspect"
+//!
+//! [webhooks]
+//! enabled = true
+//! bucket = "RELATIONSHIP_WEBHOOKS"
+//! consumer = "relationship-webhooks"
+//! dead_letters = "RELATIONSHIP_WEBHOOK_DEAD_LETTERS"
+//! timeout_secs = 10
+//! max_attempts = 5
+//! initial_backoff_secs = 1
+//! max_backoff_secs = 60
+//! ```
+//!
+//! Every setting has a default, so an empty file, or none, is valid.
+//! `RELATIONSHIP_CONFIG` names the file. `RELATIONSHIP__{SECTION}__{KEY}`
+//! overrides the setting at that path:
+//!
+//! ```text
+//! RELATIONSHIP__NATS__SERVERS=nats://nats-1:4222,nats://nats-2:4222
+//! RELATIONSHIP__SNAPSHOTS__ENABLED=false
+//! RELATIONSHIP__CASCADE__RULES=[{"entity_type": "Person", ...}]
+//! ```
+//!
+//! Override values are read as JSON where they parse as JSON, and as
+//! strings otherwise. Loading fails on unknown settings and reports every
+//! invalid one at once, by path.
+
+use crate::cross_domain::{
+    CascadeRule, CascadeRules, CrossDomainEventHandler, CrossDomainRegistry,
+};
+use crate::nats::jetstream::{
+    JetStreamAssets, CONSUMER_PREFIX, DEAD_LETTER_STREAM, EVENTS_STREAM, LEASE_BUCKET, SNAPSHOT_BUCKET,
+    WEBHOOK_BUCKET, WEBHOOK_CONSUMER,
+};
+use crate::nats::{NatsTransport, DEFAULT_MAX_LAG};
+use crate::services::{RetryPolicy, VerificationMode};
+use crate::{RelationshipError, RelationshipResult};
+use async_nats::ServerAddr;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{Map, Value};
+use std::net::SocketAddr;
+use std::path::Path;
+use std::time::Duration;
+
+/// Environment variable naming the configuration file
+pub const CONFIG_ENV: &str = "RELATIONSHIP_CONFIG";
+
+/// Prefix of environment variables overriding settings
+pub const ENV_PREFIX: &str = "RELATIONSHIP__";
+
+/// Settings of the relationship service
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServiceConfig {
+    pub nats: NatsConfig,
+    pub streams: StreamConfig,
+    pub lease: LeaseConfig,
+    pub snapshots: SnapshotConfig,
+    pub cascade: CascadeConfig,
+    pub features: FeatureToggles,
+    pub http: HttpConfig,
+    pub health: HealthConfig,
+    pub shutdown: ShutdownConfig,
+    pub expiry: ExpiryConfig,
+    pub verification: VerificationConfig,
+    pub webhooks: WebhookConfig,
+}
+
+/// NATS connection
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NatsConfig {
+    /// Servers to connect to, as a list or a comma-separated string
+    #[serde(deserialize_with = "one_or_many")]
+    pub servers: Vec<String>,
+    /// Connection name reported to the server
+    pub name: Option<String>,
+}
+
+/// JetStream streams and consumers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StreamConfig {
+    /// Stream relationship events are kept in
+    pub events: String,
+    /// Prefix of the durable upstream consumers
+    pub consumer_prefix: String,
+    /// Create the events stream when missing
+    pub create_events_stream: bool,
+}
+
+/// The lease one service instance holds while it decides commands
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LeaseConfig {
+    /// Key-value bucket the lease is kept in, keyed by events stream
+    pub bucket: String,
+    /// Seconds a lease lasts unless renewed; applies when the bucket is
+    /// created
+    pub ttl_secs: u64,
+}
+
+/// Snapshots of the relationship space, so startup replays only the
+/// events kept since the latest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SnapshotConfig {
+    pub enabled: bool,
+    /// Object store bucket snapshots are kept in
+    pub bucket: String,
+    /// Seconds between snapshots, taken only when events arrived
+    pub interval_secs: u64,
+    /// Events after which a snapshot is taken before the interval ends
+    pub every_events: u64,
+}
+
+/// Cascade rules of the cross-domain handler
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CascadeConfig {
+    /// Start from `CascadeRules::standard()` rather than an empty table
+    pub standard: bool,
+    /// Rules added on top, later ones taking precedence
+    pub rules: Vec<CascadeRule>,
+}
+
+/// Optional parts of the service
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FeatureToggles {
+    /// React to upstream domain events
+    pub cross_domain: bool,
+    /// Answer schema requests (feature `schema`)
+    pub schema_catalog: bool,
+    /// Publish events wrapped in CloudEvents envelopes
+    pub cloud_events: bool,
+    /// Publish events as Avro records (feature `avro`)
+    pub avro_events: bool,
+}
+
+/// HTTP listener
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HttpConfig {
+    /// Address of the REST gateway (feature `rest`); none = not served
+    pub addr: Option<SocketAddr>,
+}
+
+/// Health probes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HealthConfig {
+    /// Address of the probe endpoints (feature `health`); none = NATS only
+    pub addr: Option<SocketAddr>,
+    /// Events a projection may trail the stream by and still be ready
+    pub max_lag: u64,
+}
+
+/// Graceful shutdown
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ShutdownConfig {
+    /// Seconds to wait for messages in hand before giving up on them
+    pub drain_timeout_secs: u64,
+}
+
+/// Expiry of validity periods and consent requests
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExpiryConfig {
+    /// Seconds between sweeps for expired edges and consent requests;
+    /// 0 = no sweep
+    pub sweep_interval_secs: u64,
+}
+
+/// Verification of edge endpoints with their domains
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct VerificationConfig {
+    /// What happens to new edges whose endpoints are unknown (`reject`,
+    /// `suspect` or `skip`)
+    pub mode: VerificationMode,
+}
+
+/// Delivery of events to webhooks (feature `webhooks`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WebhookConfig {
+    pub enabled: bool,
+    /// Key-value bucket registered webhooks are kept in
+    pub bucket: String,
+    /// Durable consumer of the events stream deliveries follow
+    pub consumer: String,
+    /// Stream deliveries that gave up are kept in
+    pub dead_letters: String,
+    /// Seconds to wait for an endpoint to answer
+    pub timeout_secs: u64,
+    /// Attempts per delivery, the first included
+    pub max_attempts: u32,
+    /// Seconds before the second attempt, doubled for each one after
+    pub initial_backoff_secs: u64,
+    /// Longest wait between attempts, in seconds
+    pub max_backoff_secs: u64,
+}
+
+impl Default for NatsConfig {
+    fn default() -> Self {
+        Self {
+            servers: vec!["nats://localhost:4222".to_string()],
+            name: Some("relationship-service".to_string()),
+        }
+    }
+}
+
+impl Default for StreamConfig {
+    fn default() -> Self {
+        Self {
+            events: EVENTS_STREAM.to_string(),
+            consumer_prefix: CONSUMER_PREFIX.to_string(),
+            create_events_stream: true,
+        }
+    }
+}
+
+impl Default for LeaseConfig {
+    fn default() -> Self {
+        Self {
+            bucket: LEASE_BUCKET.to_string(),
+            ttl_secs: 15,
+        }
+    }
+}
+
+impl Default for SnapshotConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            bucket: SNAPSHOT_BUCKET.to_string(),
+            interval_secs: 300,
+            every_events: 1000,
+        }
+    }
+}
+
+impl Default for CascadeConfig {
+    fn default() -> Self {
+        Self {
+            standard: true,
+            rules: Vec::new(),
+        }
+    }
+}
+
+impl Default for FeatureToggles {
+    fn default() -> Self {
+        Self {
+            cross_domain: true,
+            schema_catalog: true,
+            cloud_events: false,
+            avro_events: false,
+        }
+    }
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        Self {
+            addr: None,
+            max_lag: DEFAULT_MAX_LAG,
+        }
+    }
+}
+
+impl Default for ShutdownConfig {
+    fn default() -> Self {
+        Self {
+            drain_timeout_secs: 30,
+        }
+    }
+}
+
+impl Default for ExpiryConfig {
+    fn default() -> Self {
+        Self {
+            sweep_interval_secs: 60,
+        }
+    }
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bucket: WEBHOOK_BUCKET.to_string(),
+            consumer: WEBHOOK_CONSUMER.to_string(),
+            dead_letters: DEAD_LETTER_STREAM.to_string(),
+            timeout_secs: 10,
+            max_attempts: 5,
+            initial_backoff_secs: 1,
+            max_backoff_secs: 60,
+        }
+    }
+}
+
+impl ServiceConfig {
+    /// Load the file `RELATIONSHIP_CONFIG` names, if any, with the process
+    /// environment's overrides
+    pub fn from_env() -> RelationshipResult<Self> {
+        let path = std::env::var_os(CONFIG_ENV);
+        Self::load(path.as_deref().map(Path::new), std::env::vars())
+    }
+
+    /// Load a file, if any, with overrides from `env`, and validate the
+    /// result
+    pub fn load<I>(path: Option<&Path>, env: I) -> RelationshipResult<Self>
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let mut settings = match path {
+            Some(path) => read_file(path)?,
+            None => Value::Object(Map::new()),
+        };
+        let mut overrides: Vec<_> = env
+            .into_iter()
+            .filter(|(name, _)| name.starts_with(ENV_PREFIX))
+            .collect();
+        overrides.sort();
+        for (name, value) in overrides {
+            apply_override(&mut settings, &name, &value)?;
+        }
+        Self::from_value(settings)
+    }
+
+    /// Parse and validate TOML settings
+    pub fn from_toml(text: &str) -> RelationshipResult<Self> {
+        Self::from_value(toml::from_str(text).map_err(|e| config_error(e.to_string()))?)
+    }
+
+    /// Parse and validate YAML settings
+    pub fn from_yaml(text: &str) -> RelationshipResult<Self> {
+        let settings: Value =
+            serde_yaml::from_str(text).map_err(|e| config_error(e.to_string()))?;
+        Self::from_value(match settings {
+            Value::Null => Value::Object(Map::new()),
+            settings => settings,
+        })
+    }
+
+    fn from_value(settings: Value) -> RelationshipResult<Self> {
+        let config: Self = serde_path_to_error::deserialize(settings).map_err(|e| {
+            match e.path().to_string().as_str() {
+                "." => config_error(e.inner().to_string()),
+                path => config_error(format!("{}: {}", path, e.inner())),
+            }
+        })?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Check every setting, reporting all problems at once
+    pub fn validate(&self) -> RelationshipResult<()> {
+        let mut problems = Vec::new();
+
+        if self.nats.servers.is_empty() {
+            problems.push("nats.servers: at least one server is required".to_string());
+        }
+        for (i, server) in self.nats.servers.iter().enumerate() {
+            if let Err(e) = server.parse::<ServerAddr>() {
+                problems.push(format!(
+                    "nats.servers[{}]: {:?} is not a NATS URL: {}",
+                    i, server, e
+                ));
+            }
+        }
+
+        let mut names = vec![
+            ("streams.events", &self.streams.events),
+            ("streams.consumer_prefix", &self.streams.consumer_prefix),
+        ];
+        if self.webhooks.enabled {
+            names.push(("webhooks.consumer", &self.webhooks.consumer));
+            names.push(("webhooks.dead_letters", &self.webhooks.dead_letters));
+        }
+        for (path, name) in names {
+            if name.is_empty()
+                || name
+                    .chars()
+                    .any(|c| c.is_whitespace() || ".*>/\\".contains(c))
+            {
+                problems.push(format!(
+                    "{}: {:?} must be non-empty, without whitespace or any of . * > / \\",
+                    path, name
+                ));
+            }
+        }
+
+        let mut buckets = vec![("lease.bucket", &self.lease.bucket)];
+        if self.snapshots.enabled {
+            buckets.push(("snapshots.bucket", &self.snapshots.bucket));
+        }
+        if self.webhooks.enabled {
+            buckets.push(("webhooks.bucket", &self.webhooks.bucket));
+        }
+        for (path, bucket) in buckets {
+            if bucket.is_empty()
+                || !bucket
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            {
+                problems.push(format!(
+                    "{}: {:?} must be letters, digits, - and _ only",
+                    path, bucket
+                ));
+            }
+        }
+
+        if self.lease.ttl_secs == 0 {
+            problems.push("lease.ttl_secs: must be at least 1".to_string());
+        }
+
+        if self.snapshots.enabled {
+            if self.snapshots.interval_secs == 0 {
+                problems.push("snapshots.interval_secs: must be at least 1".to_string());
+            }
+            if self.snapshots.every_events == 0 {
+                problems.push("snapshots.every_events: must be at least 1".to_string());
+            }
+        }
+
+        if self.features.avro_events {
+            if !cfg!(feature = "avro") {
+                problems.push("features.avro_events: built without the `avro` feature".to_string());
+            }
+            if self.features.cloud_events {
+                problems.push("features.avro_events: events cannot be both Avro and CloudEvents".to_string());
+            }
+        }
+
+        if self.webhooks.enabled {
+            if !cfg!(feature = "webhooks") {
+                problems.push("webhooks.enabled: built without the `webhooks` feature".to_string());
+            }
+            if self.webhooks.timeout_secs == 0 {
+                problems.push("webhooks.timeout_secs: must be at least 1".to_string());
+            }
+            if self.webhooks.max_attempts == 0 {
+                problems.push("webhooks.max_attempts: must be at least 1".to_string());
+            }
+        }
+
+        if let (Some(health), Some(http)) = (self.health.addr, self.http.addr) {
+            if health == http {
+                problems.push(format!("health.addr: {} is already http.addr", health));
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(config_error(problems.join("; ")))
+        }
+    }
+
+    /// The cascade rules table
+    pub fn cascade_rules(&self) -> CascadeRules {
+        let base = if self.cascade.standard {
+            CascadeRules::standard()
+        } else {
+            CascadeRules::new()
+        };
+        self.cascade
+            .rules
+            .iter()
+            .cloned()
+            .fold(base, CascadeRules::with_rule)
+    }
+
+    /// Cross-domain handlers, none when `features.cross_domain` is off
+    pub fn registry(&self) -> CrossDomainRegistry {
+        if self.features.cross_domain {
+            CrossDomainRegistry::new()
+                .with_handler(CrossDomainEventHandler::new().with_rules(self.cascade_rules()))
+        } else {
+            CrossDomainRegistry::new()
+        }
+    }
+
+    /// JetStream assets for consuming `upstream_subjects`
+    pub fn jetstream_assets(&self, upstream_subjects: Vec<String>) -> JetStreamAssets {
+        let assets = JetStreamAssets::new(upstream_subjects)
+            .with_events_stream(&self.streams.events)
+            .with_consumer_prefix(&self.streams.consumer_prefix);
+        if self.streams.create_events_stream {
+            assets
+        } else {
+            assets.require_events_stream()
+        }
+    }
+}
+
+impl NatsConfig {
+    /// Connect to the configured servers
+    pub async fn connect(&self) -> RelationshipResult<NatsTransport> {
+        let servers = self
+            .servers
+            .iter()
+            .map(|s| s.parse::<ServerAddr>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| config_error(format!("nats.servers: {}", e)))?;
+        let mut options = async_nats::ConnectOptions::new();
+        if let Some(name) = &self.name {
+            options = options.name(name);
+        }
+        let client = options.connect(servers).await.map_err(|e| {
+            RelationshipError::TransportError(format!("{}: {}", self.servers.join(","), e))
+        })?;
+        Ok(NatsTransport::new(client))
+    }
+}
+
+impl LeaseConfig {
+    /// Time a lease lasts unless renewed
+    pub fn ttl(&self) -> Duration {
+        Duration::from_secs(self.ttl_secs)
+    }
+}
+
+impl SnapshotConfig {
+    /// Time between snapshots
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs(self.interval_secs)
+    }
+}
+
+impl WebhookConfig {
+    /// Time to wait for an endpoint
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_secs)
+    }
+
+    /// When failed deliveries are attempted again
+    pub fn retry(&self) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: self.max_attempts,
+            initial_backoff: Duration::from_secs(self.initial_backoff_secs),
+            max_backoff: Duration::from_secs(self.max_backoff_secs),
+        }
+    }
+}
+
+impl ShutdownConfig {
+    /// Time to wait for messages in hand
+    pub fn drain_timeout(&self) -> Duration {
+        Duration::from_secs(self.drain_timeout_secs)
+    }
+}
+
+impl ExpiryConfig {
+    /// Time between sweeps, if any
+    pub fn sweep_interval(&self) -> Option<Duration> {
+        (self.sweep_interval_secs > 0).then(|| Duration::from_secs(self.sweep_interval_secs))
+    }
+}
+
+fn read_file(path: &Path) -> RelationshipResult<Value> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| config_error(format!("{}: {}", path.display(), e)))?;
+    let settings = match path.extension().and_then(|e| e.to_str()) {
+        Some("toml") => toml::from_str(&text).map_err(|e| e.to_string()),
+        Some("yaml" | "yml") => serde_yaml::from_str(&text)
+            .map(|settings| match settings {
+                Value::Null => Value::Object(Map::new()),
+                settings => settings,
+            })
+            .map_err(|e| e.to_string()),
+        _ => Err("expected a .toml, .yaml or .yml file".to_string()),
+    };
+    settings.map_err(|e| config_error(format!("{}: {}", path.display(), e)))
+}
+
+/// Set the setting `RELATIONSHIP__A__B` names (`a.b`) to `value`
+fn apply_override(settings: &mut Value, name: &str, value: &str) -> RelationshipResult<()> {
+    let keys: Vec<String> = name[ENV_PREFIX.len()..]
+        .split("__")
+        .map(str::to_lowercase)
+        .collect();
+    if keys.iter().any(String::is_empty) {
+        return Err(config_error(format!(
+            "{}: expected {}SECTION__KEY",
+            name, ENV_PREFIX
+        )));
+    }
+    let value = serde_json::from_str(value).unwrap_or_else(|_| Value::String(value.to_string()));
+
+    let (last, sections) = keys.split_last().expect("split yields a key");
+    let mut current = settings;
+    for key in sections {
+        current = match current {
+            Value::Object(map) => map
+                .entry(key.clone())
+                .or_insert_with(|| Value::Object(Map::new())),
+            _ => return Err(config_error(format!("{}: {} is not a section", name, key))),
+        };
+    }
+    match current {
+        Value::Object(map) => {
+            map.insert(last.clone(), value);
+            Ok(())
+        }
+        _ => Err(config_error(format!(
+            "{}: {} is not a section",
+            name,
+            sections.join(".")
+        ))),
+    }
+}
+
+fn one_or_many<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(list) => list
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+            .collect(),
+        OneOrMany::Many(servers) => servers,
+    })
+}
+
+fn config_error(message: impl Into<String>) -> RelationshipError {
+    RelationshipError::InvalidConfiguration(format!(
+        "relationship service config: {}",
+        message.into()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cross_domain::CascadeAction;
+
+    #[test]
+    fn test_config_files_and_overrides() {
+        let config = ServiceConfig::from_toml(
+            r#"
+            [nats]
+            servers = "nats://a:4222, nats://b:4222"
+
+            [snapshots]
+            interval_secs = 60
+
+            [shutdown]
+            drain_timeout_secs = 5
+
+            [verification]
+            mode = "reject"
+
+            [[cascade.rules]]
+            entity_type = "Person"
+            category = "Employment"
+            applies_to = { kind = "edges", side = "both" }
+            action = "flag_for_review"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.nats.servers, vec!["nats://a:4222", "nats://b:4222"]);
+        assert_eq!(config.snapshots.interval(), Duration::from_secs(60));
+        assert_eq!(config.shutdown.drain_timeout(), Duration::from_secs(5));
+        assert_eq!(config.verification.mode, VerificationMode::Reject);
+        assert_eq!(config.streams, StreamConfig::default());
+        let rules = config.cascade_rules();
+        assert_eq!(rules.rules.len(), CascadeRules::standard().rules.len() + 1);
+        assert_eq!(
+            rules.rules.last().unwrap().action,
+            CascadeAction::FlagForReview
+        );
+
+        assert_eq!(
+            ServiceConfig::from_yaml("").unwrap(),
+            ServiceConfig::default()
+        );
+        let yaml = ServiceConfig::from_yaml("features:\n  cross_domain: false\n").unwrap();
+        assert!(yaml.registry().subjects().is_empty());
+
+        let env = |pairs: &[(&str, &str)]| -> Vec<(String, String)> {
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect()
+        };
+        let overridden = ServiceConfig::load(
+            None,
+            env(&[
+                ("RELATIONSHIP__STREAMS__EVENTS", "REL_EVENTS"),
+                ("RELATIONSHIP__SNAPSHOTS__ENABLED", "false"),
+                ("RELATIONSHIP__HTTP__ADDR", "127.0.0.1:8080"),
+                ("RELATIONSHIP_SERVICE_PORT", "4222"),
+            ]),
+        )
+        .unwrap();
+        assert_eq!(overridden.streams.events, "REL_EVENTS");
+        assert!(!overridden.snapshots.enabled);
+        assert_eq!(
+            overridden.http.addr,
+            Some("127.0.0.1:8080".parse().unwrap())
+        );
+
+        // Mistakes are reported by path
+        let unknown = ServiceConfig::from_toml("[nats]\nurl = \"nats://a\"").unwrap_err();
+        assert!(
+            unknown.to_string().contains("unknown field `url`"),
+            "{}",
+            unknown
+        );
+        let mistyped = ServiceConfig::load(
+            None,
+            env(&[("RELATIONSHIP__SNAPSHOTS__EVERY_EVENTS", "often")]),
+        )
+        .unwrap_err();
+        assert!(
+            mistyped.to_string().contains("snapshots.every_events"),
+            "{}",
+            mistyped
+        );
+        let invalid = ServiceConfig::load(
+            None,
+            env(&[
+                ("RELATIONSHIP__NATS__SERVERS", "[]"),
+                ("RELATIONSHIP__STREAMS__EVENTS", "relationship.events"),
+                ("RELATIONSHIP__SNAPSHOTS__INTERVAL_SECS", "0"),
+                ("RELATIONSHIP__LEASE__TTL_SECS", "0"),
+                ("RELATIONSHIP__FEATURES__CLOUD_EVENTS", "true"),
+                ("RELATIONSHIP__FEATURES__AVRO_EVENTS", "true"),
+                ("RELATIONSHIP__WEBHOOKS__ENABLED", "true"),
+                ("RELATIONSHIP__WEBHOOKS__BUCKET", "web hooks"),
+                ("RELATIONSHIP__WEBHOOKS__MAX_ATTEMPTS", "0"),
+            ]),
+        )
+        .unwrap_err()
+        .to_string();
+        for path in [
+            "nats.servers",
+            "streams.events",
+            "snapshots.interval_secs",
+            "lease.ttl_secs",
+            "features.avro_events",
+            "webhooks.bucket",
+            "webhooks.max_attempts",
+        ] {
+            assert!(invalid.contains(path), "{}", invalid);
+        }
+    }
+}
@@ -0,0 +1,250 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Domain Metrics for Capacity Planning
+//!
+//! Measurements platform teams need to size JetStream retention and
+//! service instances from real data:
+//!
+//! - **Events per aggregate**: Distribution of aggregate versions (each
+//!   applied event bumps the version by one)
+//! - **Stream sizes**: Message and byte counts per stream, as reported by
+//!   the event store
+//! - **Index memory**: Estimated in-memory footprint of a RelationshipSpace
+//! - **Projection lag**: Bounded history of event-to-projection delays
+//!
+//! Served on `relationship.queries.system.metrics`.
+
+use crate::aggregates::{EdgeConcept, HyperEdgeConcept, RelationshipSpace};
+use crate::value_objects::{EntityKey, ParticipantEntry, RelationshipId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::mem::size_of;
+
+/// Default number of lag samples kept per projection
+pub const DEFAULT_LAG_HISTORY: usize = 1024;
+
+/// Summary statistics of a distribution
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Distribution {
+    pub count: usize,
+    pub min: u64,
+    pub max: u64,
+    pub mean: f64,
+    pub p50: u64,
+    pub p95: u64,
+    pub p99: u64,
+}
+
+impl Distribution {
+    /// Summarize a set of samples
+    pub fn from_samples(mut samples: Vec<u64>) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+        samples.sort_unstable();
+        let count = samples.len();
+        let percentile = |p: f64| samples[((count - 1) as f64 * p).round() as usize];
+        Self {
+            count,
+            min: samples[0],
+            max: samples[count - 1],
+            mean: samples.iter().sum::<u64>() as f64 / count as f64,
+            p50: percentile(0.50),
+            p95: percentile(0.95),
+            p99: percentile(0.99),
+        }
+    }
+}
+
+/// Size of an event stream
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StreamSize {
+    pub messages: u64,
+    pub bytes: u64,
+}
+
+/// Estimated memory held by a RelationshipSpace, in bytes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexMemory {
+    pub edges: usize,
+    pub hyperedges: usize,
+    pub total: usize,
+}
+
+impl IndexMemory {
+    /// Estimate the footprint of a space
+    ///
+    /// Counts map entries and participant entries at their in-memory size.
+    /// Heap data behind strings and properties is not included, so this is
+    /// a lower bound.
+    pub fn estimate(space: &RelationshipSpace) -> Self {
+        let edges = space.edges.len() * (size_of::<RelationshipId>() + size_of::<EdgeConcept>());
+        let hyperedges = space.hyperedges.len()
+            * (size_of::<RelationshipId>() + size_of::<HyperEdgeConcept>())
+            + space
+                .hyperedges
+                .values()
+                .map(|h| h.participant_count() * (size_of::<EntityKey>() + size_of::<ParticipantEntry>()))
+                .sum::<usize>();
+        Self {
+            edges,
+            hyperedges,
+            total: edges + hyperedges,
+        }
+    }
+}
+
+/// One projection lag measurement
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LagSample {
+    /// When the projection processed the event
+    pub processed_at: DateTime<Utc>,
+    /// Delay between the event and its processing, in milliseconds
+    pub lag_ms: i64,
+}
+
+/// Snapshot of domain metrics
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DomainMetrics {
+    pub collected_at: DateTime<Utc>,
+    pub edge_count: usize,
+    pub hyperedge_count: usize,
+    pub events_per_edge: Distribution,
+    pub events_per_hyperedge: Distribution,
+    pub streams: HashMap<String, StreamSize>,
+    pub index_memory: IndexMemory,
+    /// Lag history per projection, oldest first
+    pub projection_lag: HashMap<String, Vec<LagSample>>,
+}
+
+/// Accumulates metrics that cannot be derived from a space alone
+#[derive(Debug, Clone)]
+pub struct MetricsCollector {
+    streams: HashMap<String, StreamSize>,
+    lag: HashMap<String, VecDeque<LagSample>>,
+    lag_history: usize,
+}
+
+impl Default for MetricsCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MetricsCollector {
+    /// Create a collector keeping `DEFAULT_LAG_HISTORY` lag samples
+    pub fn new() -> Self {
+        Self {
+            streams: HashMap::new(),
+            lag: HashMap::new(),
+            lag_history: DEFAULT_LAG_HISTORY,
+        }
+    }
+
+    /// Keep a different number of lag samples per projection
+    pub fn with_lag_history(mut self, samples: usize) -> Self {
+        self.lag_history = samples.max(1);
+        self
+    }
+
+    /// Record the current size of a stream
+    pub fn record_stream(&mut self, stream: impl Into<String>, size: StreamSize) {
+        self.streams.insert(stream.into(), size);
+    }
+
+    /// Record a projection processing an event
+    pub fn record_projection_lag(
+        &mut self,
+        projection: impl Into<String>,
+        event_at: DateTime<Utc>,
+        processed_at: DateTime<Utc>,
+    ) {
+        let history = self.lag.entry(projection.into()).or_default();
+        if history.len() == self.lag_history {
+            history.pop_front();
+        }
+        history.push_back(LagSample {
+            processed_at,
+            lag_ms: (processed_at - event_at).num_milliseconds(),
+        });
+    }
+
+    /// Take a snapshot of all metrics
+    pub fn snapshot(&self, space: &RelationshipSpace, include_lag_history: bool) -> DomainMetrics {
+        DomainMetrics {
+            collected_at: Utc::now(),
+            edge_count: space.edges.len(),
+            hyperedge_count: space.hyperedges.len(),
+            events_per_edge: Distribution::from_samples(space.edges.values().map(|e| e.version).collect()),
+            events_per_hyperedge: Distribution::from_samples(
+                space.hyperedges.values().map(|h| h.version).collect(),
+            ),
+            streams: self.streams.clone(),
+            index_memory: IndexMemory::estimate(space),
+            projection_lag: self
+                .lag
+                .iter()
+                .map(|(name, history)| {
+                    let samples = if include_lag_history {
+                        history.iter().copied().collect()
+                    } else {
+                        history.back().copied().into_iter().collect()
+                    };
+                    (name.clone(), samples)
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value_objects::{EntityRef, RelationshipCategory};
+    use cim_domain_spaces::TopologicalSpaceId;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_distribution() {
+        let d = Distribution::from_samples((1..=100).collect());
+        assert_eq!(d.count, 100);
+        assert_eq!(d.min, 1);
+        assert_eq!(d.max, 100);
+        assert_eq!(d.p50, 51);
+        assert_eq!(d.p99, 99);
+        assert_eq!(Distribution::from_samples(Vec::new()), Distribution::default());
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let mut space = RelationshipSpace::new("Metrics", TopologicalSpaceId::new());
+        let mut edge = EdgeConcept::new(
+            "Edge",
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::person(Uuid::now_v7()),
+            RelationshipCategory::Friendship,
+        );
+        edge.version = 3;
+        space.add_edge(edge).unwrap();
+
+        let mut collector = MetricsCollector::new().with_lag_history(2);
+        collector.record_stream("RELATIONSHIP_EVENTS", StreamSize { messages: 3, bytes: 900 });
+        let now = Utc::now();
+        for ms in [10, 20, 30] {
+            collector.record_projection_lag("health", now - chrono::Duration::milliseconds(ms), now);
+        }
+
+        let metrics = collector.snapshot(&space, true);
+        assert_eq!(metrics.events_per_edge.max, 3);
+        assert!(metrics.index_memory.edges > 0);
+        assert_eq!(metrics.streams["RELATIONSHIP_EVENTS"].bytes, 900);
+        assert_eq!(metrics.projection_lag["health"].len(), 2);
+        assert_eq!(metrics.projection_lag["health"][1].lag_ms, 30);
+
+        // Without history only the latest sample is returned
+        assert_eq!(collector.snapshot(&space, false).projection_lag["health"].len(), 1);
+    }
+}
@@ -6,6 +6,19 @@
 //!
 //! Event store, repositories, and NATS integration.
 
+mod config;
+mod metrics;
+
+pub use config::{
+    CascadeConfig, ExpiryConfig, FeatureToggles, HealthConfig, HttpConfig, LeaseConfig, NatsConfig, ServiceConfig,
+    ShutdownConfig, SnapshotConfig, StreamConfig, VerificationConfig, WebhookConfig, CONFIG_ENV, ENV_PREFIX,
+};
+
+pub use metrics::{
+    DomainMetrics, Distribution, IndexMemory, LagSample, MetricsCollector, StreamSize,
+    DEFAULT_LAG_HISTORY,
+};
+
 // Re-export from cim-domain-spaces infrastructure
 pub use cim_domain_spaces::{
     EventStore, EventStoreError, RepositoryError, StoredEvent, EventMetadata,
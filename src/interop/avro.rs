@@ -0,0 +1,948 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Avro (`avro` feature)
+//!
+//! Encodes relationship events as Avro for pipelines that land them in
+//! Kafka-based lakehouses next to the NATS stream. Each event is one
+//! `cim.relationship.v1.RelationshipEvent` record: the routing fields as
+//! columns, then the event itself as the record of its type, one of a
+//! union of every event type (`avro/relationship_event.avsc`):
+//!
+//! ```text
+//! event_id           string   uuid of the event
+//! event_type         string   as in relationship.events.{event_type}
+//! relationship_id    string   uuid of the edge or hyperedge
+//! relationship_kind  string   "edge", "hyperedge" or "policy"
+//! event              union    EdgeCreated | EdgeActivated | ... | PolicyRevoked
+//! ```
+//!
+//! Event records have the fields of their Rust types. Ids are `uuid`
+//! strings and times `timestamp-micros`, so times keep microseconds only.
+//! Categories, roles and entity types are an enum of the well-known kinds
+//! or a custom name; free-form property values are JSON text, and types
+//! owned by cim-domain (`MessageIdentity`, `ConceptId`, `KnowledgeLevel`)
+//! their serde text form.
+//!
+//! Records use Avro single-object encoding, so every message names its
+//! writer schema:
+//!
+//! ```text
+//! C3 01 | CRC-64-AVRO fingerprint (8 bytes, little endian) | record
+//! ```
+//!
+//! The fingerprint is also exposed as message metadata (`Avro-Schema-*`
+//! headers) for registries that route on it. Readers keep a registry of
+//! writer schemas by fingerprint; a writer schema is only registered when
+//! the codec's schema can read what it writes, so schema changes are
+//! checked before any data is produced with them.
+//!
+//! `RelationshipBus::with_avro` publishes events with a codec, and every
+//! subscriber decodes records written with `AVRO_EVENT_SCHEMA`.
+
+use crate::events::{self, RelationshipEvent};
+use crate::invariants::{self, EndpointSide, PolicyRule};
+use crate::nats::{MessageHeaders, CONTENT_TYPE_HEADER};
+use crate::quality;
+use crate::value_objects::{
+    self, ActivationMode, EntityType, Formality, IncidenceMatrix, ParticipantRole, RelationshipCategory,
+    RelationshipId,
+};
+use crate::{RelationshipError, RelationshipResult};
+use apache_avro::rabin::Rabin;
+use apache_avro::schema_compatibility::SchemaCompatibility;
+use apache_avro::types::Value;
+use apache_avro::Schema;
+use chrono::{DateTime, Utc};
+use cim_domain::MessageIdentity;
+use cim_domain_spaces::{ConceptId, KnowledgeLevel};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::sync::OnceLock;
+use uuid::Uuid;
+
+/// Schema of relationship event records
+pub const AVRO_EVENT_SCHEMA: &str = include_str!("../../avro/relationship_event.avsc");
+
+/// Header carrying the hex CRC-64-AVRO fingerprint of the writer schema
+pub const AVRO_FINGERPRINT_HEADER: &str = "Avro-Schema-Fingerprint";
+
+/// Header carrying the full name of the writer schema
+pub const AVRO_SCHEMA_NAME_HEADER: &str = "Avro-Schema-Name";
+
+/// Content type of single-object encoded records
+pub const AVRO_CONTENT_TYPE: &str = "avro/binary";
+
+/// Marker opening a single-object encoded record
+const SINGLE_OBJECT_MARKER: [u8; 2] = [0xC3, 0x01];
+
+/// CRC-64-AVRO fingerprint of a schema
+pub type AvroFingerprint = [u8; 8];
+
+/// Avro encoder/decoder for relationship events
+#[derive(Debug, Clone)]
+pub struct AvroEventCodec {
+    schema: Schema,
+    fingerprint: AvroFingerprint,
+    /// Record names of the `event` union, by branch
+    events: Vec<String>,
+    writers: HashMap<AvroFingerprint, Schema>,
+}
+
+impl AvroEventCodec {
+    /// Codec writing and reading `AVRO_EVENT_SCHEMA`
+    pub fn new() -> RelationshipResult<Self> {
+        Self::with_schema(AVRO_EVENT_SCHEMA)
+    }
+
+    /// The codec of `AVRO_EVENT_SCHEMA`, shared by subscribers
+    pub(crate) fn standard() -> RelationshipResult<&'static Self> {
+        static STANDARD: OnceLock<Result<AvroEventCodec, String>> = OnceLock::new();
+        STANDARD
+            .get_or_init(|| Self::new().map_err(|e| e.to_string()))
+            .as_ref()
+            .map_err(|e| RelationshipError::InvalidConfiguration(e.clone()))
+    }
+
+    /// Codec reading with another (evolved) event schema
+    ///
+    /// `encode` fills in the fields of `AVRO_EVENT_SCHEMA` only, so a codec
+    /// with added fields is for reading records written by others.
+    pub fn with_schema(schema_json: &str) -> RelationshipResult<Self> {
+        let schema = parse(schema_json)?;
+        let fingerprint = fingerprint(&schema);
+        let events = event_records(&schema)?;
+        let mut writers = HashMap::new();
+        writers.insert(fingerprint, schema.clone());
+        Ok(Self {
+            schema,
+            fingerprint,
+            events,
+            writers,
+        })
+    }
+
+    /// The codec's schema
+    pub fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    /// Fingerprint of the codec's schema
+    pub fn fingerprint(&self) -> AvroFingerprint {
+        self.fingerprint
+    }
+
+    /// Fingerprint as lowercase hex, as sent in metadata
+    pub fn fingerprint_hex(&self) -> String {
+        hex(&self.fingerprint)
+    }
+
+    /// Whether message headers announce an Avro record
+    pub fn is_avro(headers: &MessageHeaders) -> bool {
+        headers.iter().any(|(name, value)| {
+            name.eq_ignore_ascii_case(CONTENT_TYPE_HEADER)
+                && value.split(';').next().unwrap_or_default().trim() == AVRO_CONTENT_TYPE
+        })
+    }
+
+    /// Metadata naming the writer schema of encoded records
+    pub fn metadata(&self) -> MessageHeaders {
+        let name = match &self.schema {
+            Schema::Record(record) => record.name.fullname(None),
+            _ => String::new(),
+        };
+        MessageHeaders::from([
+            (CONTENT_TYPE_HEADER.to_string(), AVRO_CONTENT_TYPE.to_string()),
+            (AVRO_FINGERPRINT_HEADER.to_string(), self.fingerprint_hex()),
+            (AVRO_SCHEMA_NAME_HEADER.to_string(), name),
+        ])
+    }
+
+    /// Check that a reader schema can read records this codec writes
+    ///
+    /// Consumers run this against their own schema before subscribing.
+    pub fn check_compatibility(&self, reader_schema_json: &str) -> RelationshipResult<()> {
+        let reader = parse(reader_schema_json)?;
+        compatible(&self.schema, &reader)
+    }
+
+    /// Accept records written with another schema
+    ///
+    /// Fails, registering nothing, if the codec's schema cannot read
+    /// records written with it. Returns the schema's fingerprint.
+    pub fn register_writer(&mut self, schema_json: &str) -> RelationshipResult<AvroFingerprint> {
+        let writer = parse(schema_json)?;
+        compatible(&writer, &self.schema)?;
+        let fingerprint = fingerprint(&writer);
+        self.writers.insert(fingerprint, writer);
+        Ok(fingerprint)
+    }
+
+    /// Encode an event as a single-object record
+    pub fn encode(&self, event: &RelationshipEvent) -> RelationshipResult<Vec<u8>> {
+        let (name, record) = event_record(event);
+        let branch = self
+            .events
+            .iter()
+            .position(|event| event == name)
+            .ok_or_else(|| avro_error(format!("schema has no {} record", name)))?;
+        let event_id = match &record {
+            Value::Record(fields) => fields.iter().find(|(field, _)| field == "event_id").map(|(_, id)| id),
+            _ => None,
+        };
+        let Some(Value::Uuid(event_id)) = event_id else {
+            return Err(avro_error("event without an event_id"));
+        };
+        let kind = match event {
+            RelationshipEvent::Edge(_) => "edge",
+            RelationshipEvent::HyperEdge(_) => "hyperedge",
+            RelationshipEvent::Policy(_) => "policy",
+        };
+        let record = Value::Record(vec![
+            ("event_id".to_string(), Value::String(event_id.to_string())),
+            ("event_type".to_string(), Value::String(event.event_type().to_string())),
+            (
+                "relationship_id".to_string(),
+                Value::String(event.relationship_id().as_uuid().to_string()),
+            ),
+            ("relationship_kind".to_string(), Value::String(kind.to_string())),
+            ("event".to_string(), Value::Union(branch as u32, Box::new(record))),
+        ]);
+        let datum = apache_avro::to_avro_datum(&self.schema, record).map_err(avro_error)?;
+
+        let mut bytes = Vec::with_capacity(SINGLE_OBJECT_MARKER.len() + 8 + datum.len());
+        bytes.extend_from_slice(&SINGLE_OBJECT_MARKER);
+        bytes.extend_from_slice(&self.fingerprint);
+        bytes.extend_from_slice(&datum);
+        Ok(bytes)
+    }
+
+    /// Decode a single-object record written with any registered schema
+    pub fn decode(&self, bytes: &[u8]) -> RelationshipResult<RelationshipEvent> {
+        let header_len = SINGLE_OBJECT_MARKER.len() + 8;
+        if bytes.len() < header_len || bytes[..2] != SINGLE_OBJECT_MARKER {
+            return Err(avro_error("not an Avro single-object record"));
+        }
+        let mut writer_fingerprint = [0u8; 8];
+        writer_fingerprint.copy_from_slice(&bytes[2..header_len]);
+        let writer = self.writers.get(&writer_fingerprint).ok_or_else(|| {
+            avro_error(format!("unknown writer schema {}", hex(&writer_fingerprint)))
+        })?;
+
+        let mut datum = &bytes[header_len..];
+        let record =
+            apache_avro::from_avro_datum(writer, &mut datum, Some(&self.schema)).map_err(avro_error)?;
+        let Value::Record(fields) = record else {
+            return Err(avro_error("record expected"));
+        };
+        match fields.into_iter().find(|(name, _)| name == "event") {
+            Some((_, Value::Union(branch, record))) => {
+                let name = self
+                    .events
+                    .get(branch as usize)
+                    .ok_or_else(|| avro_error(format!("no event record at branch {}", branch)))?;
+                event_from_record(name, *record)
+            }
+            _ => Err(avro_error("record without an event")),
+        }
+    }
+}
+
+/// Record names of the `event` union of a schema, by branch
+fn event_records(schema: &Schema) -> RelationshipResult<Vec<String>> {
+    let event = match schema {
+        Schema::Record(record) => record.fields.iter().find(|field| field.name == "event"),
+        _ => None,
+    };
+    let Some(Schema::Union(union)) = event.map(|field| &field.schema) else {
+        return Err(RelationshipError::InvalidConfiguration(
+            "avro schema: record with an event union expected".to_string(),
+        ));
+    };
+    union
+        .variants()
+        .iter()
+        .map(|variant| match variant {
+            Schema::Record(record) => Ok(record.name.name.clone()),
+            _ => Err(RelationshipError::InvalidConfiguration(
+                "avro schema: event union of records expected".to_string(),
+            )),
+        })
+        .collect()
+}
+
+// ---- Conversions ----
+
+/// A domain type and its Avro datum
+///
+/// Decoding looks through union branches, so an evolved reader schema
+/// may reorder them.
+trait Avro: Sized {
+    fn to_avro(&self) -> Value;
+
+    fn from_avro(value: Value, name: &'static str) -> RelationshipResult<Self>;
+}
+
+impl Avro for String {
+    fn to_avro(&self) -> Value {
+        Value::String(self.clone())
+    }
+
+    fn from_avro(value: Value, name: &'static str) -> RelationshipResult<Self> {
+        match unwrap(value) {
+            Value::String(s) => Ok(s),
+            other => Err(invalid(name, other)),
+        }
+    }
+}
+
+impl Avro for f64 {
+    fn to_avro(&self) -> Value {
+        Value::Double(*self)
+    }
+
+    fn from_avro(value: Value, name: &'static str) -> RelationshipResult<Self> {
+        match unwrap(value) {
+            Value::Double(d) => Ok(d),
+            Value::Float(f) => Ok(f as f64),
+            Value::Long(l) => Ok(l as f64),
+            Value::Int(i) => Ok(i as f64),
+            other => Err(invalid(name, other)),
+        }
+    }
+}
+
+impl Avro for u64 {
+    fn to_avro(&self) -> Value {
+        Value::Long(*self as i64)
+    }
+
+    fn from_avro(value: Value, name: &'static str) -> RelationshipResult<Self> {
+        match unwrap(value) {
+            Value::Long(l) => Ok(l as u64),
+            Value::Int(i) => Ok(i as u64),
+            other => Err(invalid(name, other)),
+        }
+    }
+}
+
+/// `["null", ...]` unions
+impl<T: Avro> Avro for Option<T> {
+    fn to_avro(&self) -> Value {
+        match self.as_ref().map(T::to_avro) {
+            None => Value::Union(0, Box::new(Value::Null)),
+            // A union inside an optional is flattened behind the null
+            Some(Value::Union(branch, value)) => Value::Union(branch + 1, value),
+            Some(value) => Value::Union(1, Box::new(value)),
+        }
+    }
+
+    fn from_avro(value: Value, name: &'static str) -> RelationshipResult<Self> {
+        match unwrap(value) {
+            Value::Null => Ok(None),
+            value => T::from_avro(value, name).map(Some),
+        }
+    }
+}
+
+impl<T: Avro> Avro for Vec<T> {
+    fn to_avro(&self) -> Value {
+        Value::Array(self.iter().map(T::to_avro).collect())
+    }
+
+    fn from_avro(value: Value, name: &'static str) -> RelationshipResult<Self> {
+        match unwrap(value) {
+            Value::Array(items) => items.into_iter().map(|item| T::from_avro(item, name)).collect(),
+            other => Err(invalid(name, other)),
+        }
+    }
+}
+
+impl<T: Avro + Eq + Hash> Avro for HashSet<T> {
+    fn to_avro(&self) -> Value {
+        Value::Array(self.iter().map(T::to_avro).collect())
+    }
+
+    fn from_avro(value: Value, name: &'static str) -> RelationshipResult<Self> {
+        Vec::from_avro(value, name).map(|items| items.into_iter().collect())
+    }
+}
+
+impl Avro for Uuid {
+    fn to_avro(&self) -> Value {
+        Value::Uuid(*self)
+    }
+
+    fn from_avro(value: Value, name: &'static str) -> RelationshipResult<Self> {
+        match unwrap(value) {
+            Value::Uuid(id) => Ok(id),
+            Value::String(s) => Uuid::parse_str(&s).map_err(|e| invalid(name, e)),
+            other => Err(invalid(name, other)),
+        }
+    }
+}
+
+impl Avro for RelationshipId {
+    fn to_avro(&self) -> Value {
+        Value::Uuid(self.as_uuid())
+    }
+
+    fn from_avro(value: Value, name: &'static str) -> RelationshipResult<Self> {
+        Uuid::from_avro(value, name).map(RelationshipId::from_uuid)
+    }
+}
+
+impl Avro for DateTime<Utc> {
+    fn to_avro(&self) -> Value {
+        Value::TimestampMicros(self.timestamp_micros())
+    }
+
+    fn from_avro(value: Value, name: &'static str) -> RelationshipResult<Self> {
+        match unwrap(value) {
+            Value::TimestampMicros(micros) | Value::Long(micros) => {
+                DateTime::from_timestamp_micros(micros).ok_or_else(|| invalid(name, micros))
+            }
+            other => Err(invalid(name, other)),
+        }
+    }
+}
+
+/// Free-form values, as JSON text
+impl Avro for serde_json::Value {
+    fn to_avro(&self) -> Value {
+        Value::String(self.to_string())
+    }
+
+    fn from_avro(value: Value, name: &'static str) -> RelationshipResult<Self> {
+        serde_json::from_str(&String::from_avro(value, name)?).map_err(|e| invalid(name, e))
+    }
+}
+
+impl Avro for HashMap<String, serde_json::Value> {
+    fn to_avro(&self) -> Value {
+        Value::Map(self.iter().map(|(key, value)| (key.clone(), value.to_avro())).collect())
+    }
+
+    fn from_avro(value: Value, name: &'static str) -> RelationshipResult<Self> {
+        match unwrap(value) {
+            Value::Map(entries) => entries
+                .into_iter()
+                .map(|(key, value)| Ok((key, serde_json::Value::from_avro(value, name)?)))
+                .collect(),
+            other => Err(invalid(name, other)),
+        }
+    }
+}
+
+/// Types owned by cim-domain, in their serde text form
+macro_rules! upstream {
+    ($($name:ty),* $(,)?) => {$(
+        impl Avro for $name {
+            fn to_avro(&self) -> Value {
+                Value::String(serde_text(self))
+            }
+
+            fn from_avro(value: Value, name: &'static str) -> RelationshipResult<Self> {
+                from_serde_text(String::from_avro(value, name)?, name)
+            }
+        }
+    )*};
+}
+
+upstream!(ConceptId, KnowledgeLevel);
+
+impl Avro for MessageIdentity {
+    fn to_avro(&self) -> Value {
+        let identity = serde_json::to_value(self).unwrap_or_default();
+        let id = |name: &str| Value::String(identity.get(name).cloned().map(text).unwrap_or_default());
+        Value::Record(vec![
+            ("message_id".to_string(), id("message_id")),
+            ("correlation_id".to_string(), id("correlation_id")),
+            ("causation_id".to_string(), id("causation_id")),
+        ])
+    }
+
+    fn from_avro(value: Value, name: &'static str) -> RelationshipResult<Self> {
+        let mut fields = record(value, name)?;
+        let mut id = |field: &'static str| field_of::<String>(&mut fields, field).map(untext);
+        let identity = serde_json::json!({
+            "message_id": id("message_id")?,
+            "correlation_id": id("correlation_id")?,
+            "causation_id": id("causation_id")?,
+        });
+        serde_json::from_value(identity).map_err(|e| invalid(name, e))
+    }
+}
+
+/// Structs whose fields each have a record field of the same name
+macro_rules! records {
+    ($($module:ident :: $name:ident { $($field:ident),* $(,)? })*) => {$(
+        impl Avro for $module::$name {
+            fn to_avro(&self) -> Value {
+                Value::Record(vec![
+                    $((stringify!($field).to_string(), self.$field.to_avro()),)*
+                ])
+            }
+
+            fn from_avro(value: Value, name: &'static str) -> RelationshipResult<Self> {
+                let mut fields = record(value, name)?;
+                Ok(Self {
+                    $($field: field_of(&mut fields, stringify!($field))?,)*
+                })
+            }
+        }
+    )*};
+}
+
+records! {
+    value_objects::EntityRef { entity_type, entity_id, cid, version }
+    value_objects::EntityKey { entity_type, entity_id }
+    value_objects::ValidityPeriod { starts_at, ends_at, end_reason }
+    value_objects::ParticipantEntry { entity_ref, role, weight, joined_at }
+    value_objects::ParticipantGroup { name, role, parent, members }
+    quality::RelationshipQuality { strength, trust, formality, duration, reciprocity }
+    invariants::RelationshipPolicy { policy, name, rule }
+    events::EdgeCreated {
+        event_id, identity, edge_id, concept_id, source, target, category, name, created_by, created_at,
+    }
+    events::EdgeActivated { event_id, identity, edge_id, activated_by, activated_at }
+    events::EdgeSuspended { event_id, identity, edge_id, reason, suspended_by, suspended_at }
+    events::EdgeTerminated { event_id, identity, edge_id, reason, terminated_by, terminated_at }
+    events::EdgeRejected { event_id, identity, edge_id, reason, rejected_by, rejected_at }
+    events::EdgeReproposed { event_id, identity, edge_id, reason, reproposed_by, reproposed_at }
+    events::EdgeQualityUpdated { event_id, identity, edge_id, old_quality, new_quality, reason, updated_at }
+    events::EdgeEvidenceAdded { event_id, identity, edge_id, evidence_cid, evidence_type, added_at }
+    events::EdgeEvidenceRevoked { event_id, identity, edge_id, evidence_cid, reason, revoked_by, revoked_at }
+    events::EdgeKnowledgeProgressed {
+        event_id, identity, edge_id, from_level, to_level, new_confidence, reason, progressed_at,
+    }
+    events::EdgePropertyUpdated { event_id, identity, edge_id, key, value, updated_by, updated_at }
+    events::EdgePropertyRemoved { event_id, identity, edge_id, key, removed_by, removed_at }
+    events::EdgeEndpointsRewritten {
+        event_id, identity, edge_id, old_source, old_target, new_source, new_target, reason, rewritten_by,
+        rewritten_at,
+    }
+    events::EdgeReversed { event_id, identity, edge_id, old_category, new_category, reason, reversed_by, reversed_at }
+    events::EdgeConsentRequested { event_id, identity, edge_id, expires_at, requested_by, requested_at }
+    events::EdgeConsentGranted { event_id, identity, edge_id, party, granted_at }
+    events::EdgeConsentDeclined { event_id, identity, edge_id, party, reason, declined_at }
+    events::EdgeTemplateApplied {
+        event_id, identity, edge_id, template, properties, valid_until, applied_by, applied_at,
+    }
+    events::EdgeTagAdded { event_id, identity, edge_id, tag, tagged_by, tagged_at }
+    events::EdgeTagRemoved { event_id, identity, edge_id, tag, removed_by, removed_at }
+    events::EdgeArchived { event_id, identity, edge_id, reason, archived_by, archived_at }
+    events::EdgeRestored { event_id, identity, edge_id, restored_by, restored_at }
+    events::EdgeRenamed { event_id, identity, edge_id, old_name, new_name, renamed_by, renamed_at }
+    events::EdgeDescriptionUpdated { event_id, identity, edge_id, description, updated_by, updated_at }
+    events::HyperEdgeCreated {
+        event_id, identity, hyperedge_id, concept_id, name, category, initial_participants, activation, created_by,
+        created_at,
+    }
+    events::HyperEdgeActivated { event_id, identity, hyperedge_id, activated_by, activated_at }
+    events::ParticipantAdded { event_id, identity, hyperedge_id, participant, role, weight, added_by, added_at }
+    events::ParticipantRemoved { event_id, identity, hyperedge_id, participant, reason, removed_by, removed_at }
+    events::ParticipantRoleChanged {
+        event_id, identity, hyperedge_id, participant, old_role, new_role, changed_by, changed_at,
+    }
+    events::ParticipantWeightChanged {
+        event_id, identity, hyperedge_id, participant, old_weight, new_weight, changed_by, changed_at,
+    }
+    events::ParticipantAcknowledged { event_id, identity, hyperedge_id, participant, acknowledged_at }
+    events::HyperEdgeTerminated { event_id, identity, hyperedge_id, reason, terminated_by, terminated_at }
+    events::RestructuringBegun { event_id, identity, hyperedge_id, reason, begun_by, begun_at }
+    events::RestructuringCompleted { event_id, identity, hyperedge_id, completed_by, completed_at }
+    events::HyperEdgeQualityUpdated { event_id, identity, hyperedge_id, old_quality, new_quality, reason, updated_at }
+    events::HyperEdgePromotedFromEdge {
+        event_id, identity, hyperedge_id, edge_id, knowledge_level, confidence, evidence_cids, properties,
+        promoted_by, promoted_at,
+    }
+    events::HyperEdgeTagAdded { event_id, identity, hyperedge_id, tag, tagged_by, tagged_at }
+    events::HyperEdgeTagRemoved { event_id, identity, hyperedge_id, tag, removed_by, removed_at }
+    events::ParticipantGroupAdded { event_id, identity, hyperedge_id, name, role, parent, added_by, added_at }
+    events::ParticipantGroupRemoved { event_id, identity, hyperedge_id, name, removed_by, removed_at }
+    events::ParticipantAssignedToGroup {
+        event_id, identity, hyperedge_id, participant, group, assigned_by, assigned_at,
+    }
+    events::ParticipantUnassignedFromGroup {
+        event_id, identity, hyperedge_id, participant, group, unassigned_by, unassigned_at,
+    }
+    events::HyperEdgeArchived { event_id, identity, hyperedge_id, reason, archived_by, archived_at }
+    events::HyperEdgeRestored { event_id, identity, hyperedge_id, restored_by, restored_at }
+    events::HyperEdgeRenamed { event_id, identity, hyperedge_id, old_name, new_name, renamed_by, renamed_at }
+    events::HyperEdgeDescriptionUpdated { event_id, identity, hyperedge_id, description, updated_by, updated_at }
+    events::HyperEdgeTemplateApplied {
+        event_id, identity, hyperedge_id, template, properties, valid_until, applied_by, applied_at,
+    }
+    events::PolicyEnacted { event_id, identity, policy, enacted_by, enacted_at }
+    events::PolicyRevoked { event_id, identity, policy, reason, revoked_by, revoked_at }
+}
+
+/// Enums of well-known kinds plus a custom one: `[{Name}Kind, "string"]`
+macro_rules! kinds {
+    ($($name:ident { $($variant:ident),* $(,)? })*) => {$(
+        impl Avro for $name {
+            fn to_avro(&self) -> Value {
+                const SYMBOLS: &[&str] = &[$(stringify!($variant)),*];
+                let symbol = match self {
+                    $($name::$variant => stringify!($variant),)*
+                    $name::Custom(custom) => return Value::Union(1, Box::new(Value::String(custom.clone()))),
+                };
+                let index = SYMBOLS.iter().position(|s| *s == symbol).unwrap_or_default();
+                Value::Union(0, Box::new(Value::Enum(index as u32, symbol.to_string())))
+            }
+
+            fn from_avro(value: Value, name: &'static str) -> RelationshipResult<Self> {
+                match unwrap(value) {
+                    Value::String(custom) => Ok($name::Custom(custom)),
+                    Value::Enum(_, symbol) => match symbol.as_str() {
+                        $(stringify!($variant) => Ok($name::$variant),)*
+                        _ => Err(invalid(name, symbol)),
+                    },
+                    other => Err(invalid(name, other)),
+                }
+            }
+        }
+    )*};
+}
+
+kinds! {
+    EntityType { Person, Organization, Location, Agent, Policy, Concept, Relationship }
+    RelationshipCategory {
+        Employment, Membership, Ownership, Management, Friendship, ProfessionalContact, Mentorship, PartOf,
+        Contains, DependsOn, Implements, Precedes, Triggers, References, DerivesFrom,
+    }
+    ParticipantRole {
+        Primary, Secondary, Observer, Facilitator, Leader, Member, Contributor, Stakeholder, Author, Reviewer,
+        Approver,
+    }
+}
+
+/// Field-less enums
+macro_rules! enums {
+    ($($name:ident { $($variant:ident),* $(,)? })*) => {$(
+        impl Avro for $name {
+            fn to_avro(&self) -> Value {
+                const SYMBOLS: &[&str] = &[$(stringify!($variant)),*];
+                let symbol = match self {
+                    $($name::$variant => stringify!($variant),)*
+                };
+                let index = SYMBOLS.iter().position(|s| *s == symbol).unwrap_or_default();
+                Value::Enum(index as u32, symbol.to_string())
+            }
+
+            fn from_avro(value: Value, name: &'static str) -> RelationshipResult<Self> {
+                match unwrap(value) {
+                    Value::Enum(_, symbol) => match symbol.as_str() {
+                        $(stringify!($variant) => Ok($name::$variant),)*
+                        _ => Err(invalid(name, symbol)),
+                    },
+                    other => Err(invalid(name, other)),
+                }
+            }
+        }
+    )*};
+}
+
+enums! {
+    Formality { Informal, SemiFormal, Formal, Contractual, Legal }
+    EndpointSide { Source, Target, Both }
+}
+
+impl Avro for IncidenceMatrix {
+    fn to_avro(&self) -> Value {
+        Value::Record(vec![
+            (
+                "participants".to_string(),
+                Value::Array(self.participants().map(Avro::to_avro).collect()),
+            ),
+            ("groups".to_string(), Value::Array(self.groups().map(Avro::to_avro).collect())),
+        ])
+    }
+
+    fn from_avro(value: Value, name: &'static str) -> RelationshipResult<Self> {
+        let mut fields = record(value, name)?;
+        let participants: Vec<value_objects::ParticipantEntry> = field_of(&mut fields, "participants")?;
+        let groups: Vec<value_objects::ParticipantGroup> = field_of(&mut fields, "groups")?;
+        Ok(IncidenceMatrix::from_parts(participants, groups))
+    }
+}
+
+impl Avro for ActivationMode {
+    fn to_avro(&self) -> Value {
+        let quorum_threshold = match self {
+            ActivationMode::Immediate => None,
+            ActivationMode::Quorum { threshold } => Some(*threshold),
+        };
+        Value::Record(vec![("quorum_threshold".to_string(), quorum_threshold.to_avro())])
+    }
+
+    fn from_avro(value: Value, name: &'static str) -> RelationshipResult<Self> {
+        let mut fields = record(value, name)?;
+        Ok(match field_of(&mut fields, "quorum_threshold")? {
+            None => ActivationMode::Immediate,
+            Some(threshold) => ActivationMode::Quorum { threshold },
+        })
+    }
+}
+
+/// `[Forbid]`, one record per kind of rule
+impl Avro for PolicyRule {
+    fn to_avro(&self) -> Value {
+        match self {
+            PolicyRule::Forbid { category, side, entities } => Value::Union(
+                0,
+                Box::new(Value::Record(vec![
+                    ("category".to_string(), category.to_avro()),
+                    ("side".to_string(), side.to_avro()),
+                    ("entities".to_string(), entities.to_avro()),
+                ])),
+            ),
+        }
+    }
+
+    fn from_avro(value: Value, name: &'static str) -> RelationshipResult<Self> {
+        let mut fields = record(value, name)?;
+        Ok(PolicyRule::Forbid {
+            category: field_of(&mut fields, "category")?,
+            side: field_of(&mut fields, "side")?,
+            entities: field_of(&mut fields, "entities")?,
+        })
+    }
+}
+
+/// The record of each event type, in the order of the `event` union
+macro_rules! event_union {
+    ($($kind:ident($group:ident::$variant:ident($name:ident))),* $(,)?) => {
+        /// Record name and record of an event
+        fn event_record(event: &RelationshipEvent) -> (&'static str, Value) {
+            match event {
+                $(RelationshipEvent::$kind(events::$group::$variant(e)) => (stringify!($name), e.to_avro()),)*
+            }
+        }
+
+        /// Event of a record of the `event` union
+        fn event_from_record(name: &str, record: Value) -> RelationshipResult<RelationshipEvent> {
+            match name {
+                $(stringify!($name) => Ok(RelationshipEvent::$kind(events::$group::$variant(
+                    events::$name::from_avro(record, "event")?,
+                ))),)*
+                _ => Err(avro_error(format!("unknown event record {}", name))),
+            }
+        }
+    };
+}
+
+event_union! {
+    Edge(EdgeEvent::EdgeCreated(EdgeCreated)),
+    Edge(EdgeEvent::EdgeActivated(EdgeActivated)),
+    Edge(EdgeEvent::EdgeSuspended(EdgeSuspended)),
+    Edge(EdgeEvent::EdgeTerminated(EdgeTerminated)),
+    Edge(EdgeEvent::EdgeRejected(EdgeRejected)),
+    Edge(EdgeEvent::Reproposed(EdgeReproposed)),
+    Edge(EdgeEvent::QualityUpdated(EdgeQualityUpdated)),
+    Edge(EdgeEvent::EvidenceAdded(EdgeEvidenceAdded)),
+    Edge(EdgeEvent::EvidenceRevoked(EdgeEvidenceRevoked)),
+    Edge(EdgeEvent::KnowledgeProgressed(EdgeKnowledgeProgressed)),
+    Edge(EdgeEvent::PropertyUpdated(EdgePropertyUpdated)),
+    Edge(EdgeEvent::PropertyRemoved(EdgePropertyRemoved)),
+    Edge(EdgeEvent::EndpointsRewritten(EdgeEndpointsRewritten)),
+    Edge(EdgeEvent::Reversed(EdgeReversed)),
+    Edge(EdgeEvent::ConsentRequested(EdgeConsentRequested)),
+    Edge(EdgeEvent::ConsentGranted(EdgeConsentGranted)),
+    Edge(EdgeEvent::ConsentDeclined(EdgeConsentDeclined)),
+    Edge(EdgeEvent::TemplateApplied(EdgeTemplateApplied)),
+    Edge(EdgeEvent::TagAdded(EdgeTagAdded)),
+    Edge(EdgeEvent::TagRemoved(EdgeTagRemoved)),
+    Edge(EdgeEvent::Archived(EdgeArchived)),
+    Edge(EdgeEvent::Restored(EdgeRestored)),
+    Edge(EdgeEvent::Renamed(EdgeRenamed)),
+    Edge(EdgeEvent::DescriptionUpdated(EdgeDescriptionUpdated)),
+    HyperEdge(HyperEdgeEvent::HyperEdgeCreated(HyperEdgeCreated)),
+    HyperEdge(HyperEdgeEvent::HyperEdgeActivated(HyperEdgeActivated)),
+    HyperEdge(HyperEdgeEvent::ParticipantAdded(ParticipantAdded)),
+    HyperEdge(HyperEdgeEvent::ParticipantRemoved(ParticipantRemoved)),
+    HyperEdge(HyperEdgeEvent::ParticipantRoleChanged(ParticipantRoleChanged)),
+    HyperEdge(HyperEdgeEvent::ParticipantWeightChanged(ParticipantWeightChanged)),
+    HyperEdge(HyperEdgeEvent::ParticipantAcknowledged(ParticipantAcknowledged)),
+    HyperEdge(HyperEdgeEvent::HyperEdgeTerminated(HyperEdgeTerminated)),
+    HyperEdge(HyperEdgeEvent::RestructuringBegun(RestructuringBegun)),
+    HyperEdge(HyperEdgeEvent::RestructuringCompleted(RestructuringCompleted)),
+    HyperEdge(HyperEdgeEvent::HyperEdgeQualityUpdated(HyperEdgeQualityUpdated)),
+    HyperEdge(HyperEdgeEvent::PromotedFromEdge(HyperEdgePromotedFromEdge)),
+    HyperEdge(HyperEdgeEvent::TemplateApplied(HyperEdgeTemplateApplied)),
+    HyperEdge(HyperEdgeEvent::TagAdded(HyperEdgeTagAdded)),
+    HyperEdge(HyperEdgeEvent::TagRemoved(HyperEdgeTagRemoved)),
+    HyperEdge(HyperEdgeEvent::GroupAdded(ParticipantGroupAdded)),
+    HyperEdge(HyperEdgeEvent::GroupRemoved(ParticipantGroupRemoved)),
+    HyperEdge(HyperEdgeEvent::AssignedToGroup(ParticipantAssignedToGroup)),
+    HyperEdge(HyperEdgeEvent::UnassignedFromGroup(ParticipantUnassignedFromGroup)),
+    HyperEdge(HyperEdgeEvent::Archived(HyperEdgeArchived)),
+    HyperEdge(HyperEdgeEvent::Restored(HyperEdgeRestored)),
+    HyperEdge(HyperEdgeEvent::Renamed(HyperEdgeRenamed)),
+    HyperEdge(HyperEdgeEvent::DescriptionUpdated(HyperEdgeDescriptionUpdated)),
+    Policy(PolicyEvent::PolicyEnacted(PolicyEnacted)),
+    Policy(PolicyEvent::PolicyRevoked(PolicyRevoked)),
+}
+
+fn unwrap(value: Value) -> Value {
+    match value {
+        Value::Union(_, value) => unwrap(*value),
+        value => value,
+    }
+}
+
+fn record(value: Value, name: &'static str) -> RelationshipResult<HashMap<String, Value>> {
+    match unwrap(value) {
+        Value::Record(fields) => Ok(fields.into_iter().collect()),
+        other => Err(invalid(name, other)),
+    }
+}
+
+/// A field of a record; a missing field reads as null
+fn field_of<T: Avro>(fields: &mut HashMap<String, Value>, name: &'static str) -> RelationshipResult<T> {
+    T::from_avro(fields.remove(name).unwrap_or(Value::Null), name)
+}
+
+/// An upstream value in its serde form: strings as they are, anything
+/// else as JSON text
+fn serde_text<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value).map(text).unwrap_or_default()
+}
+
+fn from_serde_text<T: DeserializeOwned>(field: String, name: &'static str) -> RelationshipResult<T> {
+    serde_json::from_value(untext(field)).map_err(|e| invalid(name, e))
+}
+
+fn text(value: serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s,
+        other => other.to_string(),
+    }
+}
+
+fn untext(text: String) -> serde_json::Value {
+    serde_json::from_str(&text).unwrap_or(serde_json::Value::String(text))
+}
+
+fn parse(schema_json: &str) -> RelationshipResult<Schema> {
+    Schema::parse_str(schema_json).map_err(|e| RelationshipError::InvalidConfiguration(format!("avro schema: {}", e)))
+}
+
+fn fingerprint(schema: &Schema) -> AvroFingerprint {
+    let mut fingerprint = [0u8; 8];
+    fingerprint.copy_from_slice(&schema.fingerprint::<Rabin>().bytes);
+    fingerprint
+}
+
+fn compatible(writer: &Schema, reader: &Schema) -> RelationshipResult<()> {
+    SchemaCompatibility::can_read(writer, reader)
+        .map_err(|e| RelationshipError::InvalidConfiguration(format!("incompatible avro schema: {}", e)))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn invalid(name: &str, value: impl std::fmt::Debug) -> RelationshipError {
+    avro_error(format!("invalid {}: {:?}", name, value))
+}
+
+fn avro_error(error: impl std::fmt::Display) -> RelationshipError {
+    RelationshipError::SerializationError(error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{EdgeEvent, EdgeRenamed, HyperEdgeCreated, HyperEdgeEvent};
+    use crate::value_objects::EntityRef;
+    use chrono::SubsecRound;
+
+    fn renamed() -> RelationshipEvent {
+        RelationshipEvent::Edge(EdgeEvent::Renamed(EdgeRenamed {
+            event_id: Uuid::now_v7(),
+            identity: MessageIdentity::new_root(),
+            edge_id: RelationshipId::new(),
+            old_name: "Knows".to_string(),
+            new_name: "Works with".to_string(),
+            renamed_by: "test".to_string(),
+            renamed_at: Utc::now().trunc_subsecs(6),
+        }))
+    }
+
+    fn assert_round_trip(codec: &AvroEventCodec, event: &RelationshipEvent) {
+        let decoded = codec.decode(&codec.encode(event).unwrap()).unwrap();
+        assert_eq!(serde_json::to_value(&decoded).unwrap(), serde_json::to_value(event).unwrap());
+    }
+
+    #[test]
+    fn test_avro_round_trip_with_fingerprint() {
+        let codec = AvroEventCodec::new().unwrap();
+        let event = renamed();
+        let bytes = codec.encode(&event).unwrap();
+        assert_eq!(bytes[..2], SINGLE_OBJECT_MARKER);
+        assert_eq!(bytes[2..10], codec.fingerprint());
+        assert_eq!(codec.metadata()[AVRO_FINGERPRINT_HEADER], codec.fingerprint_hex());
+        assert_eq!(codec.metadata()[AVRO_SCHEMA_NAME_HEADER], "cim.relationship.v1.RelationshipEvent");
+        assert!(AvroEventCodec::is_avro(&codec.metadata()));
+        assert_round_trip(&codec, &event);
+
+        let lead = EntityRef::person(Uuid::now_v7());
+        let mut participants = IncidenceMatrix::new();
+        participants.add_participant(lead.clone(), ParticipantRole::Leader, 1.0);
+        participants.add_participant(EntityRef::agent(Uuid::now_v7()), ParticipantRole::Custom("bot".into()), 0.5);
+        participants.add_group("leads", ParticipantRole::Custom("steering".into()), None);
+        participants.assign_to_group(&lead, "leads");
+        // Avro times keep microseconds
+        let entries = participants.participants().cloned().map(|mut entry| {
+            entry.joined_at = entry.joined_at.trunc_subsecs(6);
+            entry
+        });
+        let participants = IncidenceMatrix::from_parts(entries.collect::<Vec<_>>(), participants.groups().cloned());
+        let created = RelationshipEvent::HyperEdge(HyperEdgeEvent::HyperEdgeCreated(HyperEdgeCreated {
+            event_id: Uuid::now_v7(),
+            identity: MessageIdentity::new_root(),
+            hyperedge_id: RelationshipId::new(),
+            concept_id: ConceptId::new(),
+            name: "Team".to_string(),
+            category: RelationshipCategory::Custom("squad".into()),
+            initial_participants: participants,
+            activation: ActivationMode::Quorum { threshold: 0.5 },
+            created_by: "test".to_string(),
+            created_at: Utc::now().trunc_subsecs(6),
+        }));
+        assert_round_trip(&codec, &created);
+    }
+
+    #[test]
+    fn test_schema_evolution_is_checked() {
+        // v2 adds a defaulted column: old records stay readable
+        let v2 = AVRO_EVENT_SCHEMA.replace(
+            r#"{"name": "relationship_kind", "type": "string"},"#,
+            r#"{"name": "relationship_kind", "type": "string"},
+    {"name": "tenant", "type": "string", "default": ""},"#,
+        );
+        assert_ne!(v2, AVRO_EVENT_SCHEMA);
+        let v1 = AvroEventCodec::new().unwrap();
+        let mut reader = AvroEventCodec::with_schema(&v2).unwrap();
+        assert!(v1.check_compatibility(&v2).is_ok());
+        reader.register_writer(AVRO_EVENT_SCHEMA).unwrap();
+        let event = renamed();
+        let decoded = reader.decode(&v1.encode(&event).unwrap()).unwrap();
+        assert_eq!(serde_json::to_value(&decoded).unwrap(), serde_json::to_value(&event).unwrap());
+
+        // Dropping a column the reader needs is refused
+        let v0 = AVRO_EVENT_SCHEMA.replace(r#"{"name": "relationship_kind", "type": "string"},"#, "");
+        assert!(matches!(
+            reader.register_writer(&v0),
+            Err(RelationshipError::InvalidConfiguration(_))
+        ));
+        let mut unknown = v1.encode(&event).unwrap();
+        unknown[2] ^= 0xff;
+        assert!(v1.decode(&unknown).is_err());
+    }
+}
@@ -0,0 +1,484 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Columnar Export
+//!
+//! Flat, typed tables of a space for analytical engines (DuckDB, Spark,
+//! Polars), so data teams query relationships without replaying the event
+//! store:
+//!
+//! ```text
+//! edges              one row per edge: endpoints, category, state, quality,
+//!                    validity period
+//! participants       one row per hyperedge participant: role, weight
+//! quality_history    one row per quality update event, old and new values
+//! ```
+//!
+//! Tables are built without any columnar dependency. With the `columnar`
+//! feature they convert to Arrow record batches and are written as Parquet
+//! files or Arrow IPC streams:
+//!
+//! ```text
+//! let tables = AnalyticalTables::from_space(&space).with_quality_history(&events);
+//! tables.edges.write_parquet(File::create("edges.parquet")?)?;
+//! ```
+//!
+//! Identifiers are plain UUID strings and entities are `EntityKey` strings
+//! (`person:{uuid}`), matching the other interop formats; timestamps are
+//! UTC microseconds.
+
+use crate::aggregates::{EdgeConcept, HyperEdgeConcept, RelationshipSpace};
+use crate::events::{EdgeEvent, HyperEdgeEvent, RelationshipEvent};
+use crate::quality::RelationshipQuality;
+use crate::value_objects::{EntityRef, EntityType, RelationshipId, ValidityPeriod};
+use chrono::{DateTime, Utc};
+
+/// Values of one column
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnData {
+    /// UTF-8 strings
+    Utf8(Vec<Option<String>>),
+    /// 64-bit floats
+    Float64(Vec<Option<f64>>),
+    /// 64-bit integers
+    Int64(Vec<Option<i64>>),
+    /// UTC timestamps in microseconds
+    Timestamp(Vec<Option<i64>>),
+}
+
+impl ColumnData {
+    /// Number of values
+    pub fn len(&self) -> usize {
+        match self {
+            ColumnData::Utf8(values) => values.len(),
+            ColumnData::Float64(values) => values.len(),
+            ColumnData::Int64(values) | ColumnData::Timestamp(values) => values.len(),
+        }
+    }
+
+    /// Whether the column has no values
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A named, typed column
+#[derive(Debug, Clone, PartialEq)]
+pub struct Column {
+    /// Column name
+    pub name: String,
+    /// Column values
+    pub data: ColumnData,
+}
+
+/// A named table of equal-length columns
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnarTable {
+    /// Table name, used as the file stem by callers writing several tables
+    pub name: String,
+    /// Columns in schema order
+    pub columns: Vec<Column>,
+}
+
+impl ColumnarTable {
+    /// Number of rows
+    pub fn num_rows(&self) -> usize {
+        self.columns.first().map(|c| c.data.len()).unwrap_or(0)
+    }
+
+    /// Column by name
+    pub fn column(&self, name: &str) -> Option<&ColumnData> {
+        self.columns.iter().find(|c| c.name == name).map(|c| &c.data)
+    }
+}
+
+/// The analytical tables of a space
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnalyticalTables {
+    /// One row per edge
+    pub edges: ColumnarTable,
+    /// One row per hyperedge participant
+    pub participants: ColumnarTable,
+    /// One row per quality update
+    pub quality_history: ColumnarTable,
+}
+
+impl AnalyticalTables {
+    /// Tables of a space's current state, with an empty quality history
+    pub fn from_space(space: &RelationshipSpace) -> Self {
+        Self {
+            edges: edges_table(space),
+            participants: participants_table(space),
+            quality_history: quality_history_table(&[]),
+        }
+    }
+
+    /// Fill the quality history from the space's events
+    pub fn with_quality_history(mut self, events: &[RelationshipEvent]) -> Self {
+        self.quality_history = quality_history_table(events);
+        self
+    }
+
+    /// The tables, in a stable order
+    pub fn tables(&self) -> [&ColumnarTable; 3] {
+        [&self.edges, &self.participants, &self.quality_history]
+    }
+}
+
+// ============================================================================
+// Tables
+// ============================================================================
+
+/// One row per edge, ordered by id
+pub fn edges_table(space: &RelationshipSpace) -> ColumnarTable {
+    let mut edges: Vec<&EdgeConcept> = space.edges.values().collect();
+    edges.sort_by_key(|e| e.id.as_uuid());
+
+    let mut table = TableBuilder::new("edges");
+    table.utf8("edge_id", edges.iter().map(|e| Some(uuid(e.id))));
+    table.utf8("name", edges.iter().map(|e| Some(e.name.clone())));
+    table.utf8("category", edges.iter().map(|e| Some(e.category.display_name())));
+    table.utf8("state", edges.iter().map(|e| Some(format!("{:?}", e.state))));
+    table.utf8("source", edges.iter().map(|e| Some(e.source.key().to_string())));
+    table.utf8("source_type", edges.iter().map(|e| Some(entity_type(&e.source))));
+    table.utf8("target", edges.iter().map(|e| Some(e.target.key().to_string())));
+    table.utf8("target_type", edges.iter().map(|e| Some(entity_type(&e.target))));
+    quality_columns(&mut table, "", edges.iter().map(|e| &e.quality));
+    validity_columns(&mut table, "", edges.iter().map(|e| &e.validity));
+    table.int64("evidence_count", edges.iter().map(|e| Some(e.evidence_cids.len() as i64)));
+    table.timestamp("created_at", edges.iter().map(|e| Some(e.created_at)));
+    table.build()
+}
+
+/// One row per hyperedge participant, ordered by hyperedge then entity
+pub fn participants_table(space: &RelationshipSpace) -> ColumnarTable {
+    let mut hyperedges: Vec<&HyperEdgeConcept> = space.hyperedges.values().collect();
+    hyperedges.sort_by_key(|h| h.id.as_uuid());
+    let rows: Vec<_> = hyperedges
+        .into_iter()
+        .flat_map(|h| {
+            let mut participants: Vec<_> = h.participants.participants().collect();
+            participants.sort_by_key(|p| p.entity_ref.key().to_string());
+            participants.into_iter().map(move |p| (h, p))
+        })
+        .collect();
+
+    let mut table = TableBuilder::new("participants");
+    table.utf8("hyperedge_id", rows.iter().map(|(h, _)| Some(uuid(h.id))));
+    table.utf8("hyperedge_name", rows.iter().map(|(h, _)| Some(h.name.clone())));
+    table.utf8("category", rows.iter().map(|(h, _)| Some(h.category.display_name())));
+    table.utf8("state", rows.iter().map(|(h, _)| Some(format!("{:?}", h.state))));
+    table.utf8("entity", rows.iter().map(|(_, p)| Some(p.entity_ref.key().to_string())));
+    table.utf8("entity_type", rows.iter().map(|(_, p)| Some(entity_type(&p.entity_ref))));
+    table.utf8("role", rows.iter().map(|(_, p)| Some(p.role.display_name())));
+    table.float64("weight", rows.iter().map(|(_, p)| Some(p.weight)));
+    table.timestamp("joined_at", rows.iter().map(|(_, p)| Some(p.joined_at)));
+    table.build()
+}
+
+/// One row per quality update event, in event order
+///
+/// Other events are skipped, so the full event log can be passed.
+pub fn quality_history_table(events: &[RelationshipEvent]) -> ColumnarTable {
+    struct Update<'a> {
+        relationship_id: RelationshipId,
+        kind: &'static str,
+        old: &'a RelationshipQuality,
+        new: &'a RelationshipQuality,
+        reason: &'a str,
+        updated_at: DateTime<Utc>,
+    }
+
+    let updates: Vec<Update> = events
+        .iter()
+        .filter_map(|event| match event {
+            RelationshipEvent::Edge(EdgeEvent::QualityUpdated(e)) => Some(Update {
+                relationship_id: e.edge_id,
+                kind: "edge",
+                old: &e.old_quality,
+                new: &e.new_quality,
+                reason: &e.reason,
+                updated_at: e.updated_at,
+            }),
+            RelationshipEvent::HyperEdge(HyperEdgeEvent::HyperEdgeQualityUpdated(e)) => Some(Update {
+                relationship_id: e.hyperedge_id,
+                kind: "hyperedge",
+                old: &e.old_quality,
+                new: &e.new_quality,
+                reason: &e.reason,
+                updated_at: e.updated_at,
+            }),
+            _ => None,
+        })
+        .collect();
+
+    let mut table = TableBuilder::new("quality_history");
+    table.utf8("relationship_id", updates.iter().map(|u| Some(uuid(u.relationship_id))));
+    table.utf8("relationship_kind", updates.iter().map(|u| Some(u.kind.to_string())));
+    table.timestamp("updated_at", updates.iter().map(|u| Some(u.updated_at)));
+    table.utf8("reason", updates.iter().map(|u| Some(u.reason.to_string())));
+    quality_columns(&mut table, "old_", updates.iter().map(|u| u.old));
+    validity_columns(&mut table, "old_", updates.iter().map(|u| &u.old.duration));
+    quality_columns(&mut table, "new_", updates.iter().map(|u| u.new));
+    validity_columns(&mut table, "new_", updates.iter().map(|u| &u.new.duration));
+    table.build()
+}
+
+/// Quality columns, names prefixed with `prefix`
+fn quality_columns<'a>(
+    table: &mut TableBuilder,
+    prefix: &str,
+    qualities: impl Iterator<Item = &'a RelationshipQuality> + Clone,
+) {
+    table.float64(&format!("{}strength", prefix), qualities.clone().map(|q| Some(q.strength)));
+    table.float64(&format!("{}trust", prefix), qualities.clone().map(|q| Some(q.trust)));
+    table.utf8(
+        &format!("{}formality", prefix),
+        qualities.clone().map(|q| Some(format!("{:?}", q.formality))),
+    );
+    table.float64(&format!("{}reciprocity", prefix), qualities.map(|q| Some(q.reciprocity)));
+}
+
+/// Validity columns, names prefixed with `prefix`
+fn validity_columns<'a>(
+    table: &mut TableBuilder,
+    prefix: &str,
+    periods: impl Iterator<Item = &'a ValidityPeriod> + Clone,
+) {
+    table.timestamp(&format!("{}valid_from", prefix), periods.clone().map(|v| Some(v.starts_at)));
+    table.timestamp(&format!("{}valid_until", prefix), periods.map(|v| v.ends_at));
+}
+
+fn uuid(id: RelationshipId) -> String {
+    id.as_uuid().to_string()
+}
+
+fn entity_type(entity: &EntityRef) -> String {
+    match &entity.entity_type {
+        EntityType::Custom(name) => format!("custom/{}", name),
+        other => other.nats_subject_prefix().to_string(),
+    }
+}
+
+struct TableBuilder {
+    name: &'static str,
+    columns: Vec<Column>,
+}
+
+impl TableBuilder {
+    fn new(name: &'static str) -> Self {
+        Self { name, columns: Vec::new() }
+    }
+
+    fn push(&mut self, name: &str, data: ColumnData) {
+        self.columns.push(Column { name: name.to_string(), data });
+    }
+
+    fn utf8(&mut self, name: &str, values: impl Iterator<Item = Option<String>>) {
+        self.push(name, ColumnData::Utf8(values.collect()));
+    }
+
+    fn float64(&mut self, name: &str, values: impl Iterator<Item = Option<f64>>) {
+        self.push(name, ColumnData::Float64(values.collect()));
+    }
+
+    fn int64(&mut self, name: &str, values: impl Iterator<Item = Option<i64>>) {
+        self.push(name, ColumnData::Int64(values.collect()));
+    }
+
+    fn timestamp(&mut self, name: &str, values: impl Iterator<Item = Option<DateTime<Utc>>>) {
+        self.push(
+            name,
+            ColumnData::Timestamp(values.map(|v| v.map(|t| t.timestamp_micros())).collect()),
+        );
+    }
+
+    fn build(self) -> ColumnarTable {
+        ColumnarTable {
+            name: self.name.to_string(),
+            columns: self.columns,
+        }
+    }
+}
+
+// ============================================================================
+// Arrow / Parquet
+// ============================================================================
+
+#[cfg(feature = "columnar")]
+mod arrow_io {
+    use super::{ColumnData, ColumnarTable};
+    use crate::{RelationshipError, RelationshipResult};
+    use arrow::array::{ArrayRef, Float64Array, Int64Array, StringArray, TimestampMicrosecondArray};
+    use arrow::datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit};
+    use arrow::ipc::writer::StreamWriter;
+    use arrow::record_batch::RecordBatch;
+    use parquet::arrow::ArrowWriter;
+    use std::io::Write;
+    use std::sync::Arc;
+
+    fn columnar_error(e: impl std::fmt::Display) -> RelationshipError {
+        RelationshipError::SerializationError(e.to_string())
+    }
+
+    impl ColumnarTable {
+        /// Arrow schema of the table; every column is nullable
+        pub fn arrow_schema(&self) -> SchemaRef {
+            let fields: Vec<Field> = self
+                .columns
+                .iter()
+                .map(|c| {
+                    let data_type = match c.data {
+                        ColumnData::Utf8(_) => DataType::Utf8,
+                        ColumnData::Float64(_) => DataType::Float64,
+                        ColumnData::Int64(_) => DataType::Int64,
+                        ColumnData::Timestamp(_) => DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+                    };
+                    Field::new(c.name.as_str(), data_type, true)
+                })
+                .collect();
+            Arc::new(Schema::new(fields))
+        }
+
+        /// The table as one Arrow record batch
+        pub fn to_record_batch(&self) -> RelationshipResult<RecordBatch> {
+            let arrays: Vec<ArrayRef> = self
+                .columns
+                .iter()
+                .map(|c| -> ArrayRef {
+                    match &c.data {
+                        ColumnData::Utf8(values) => Arc::new(StringArray::from(values.clone())),
+                        ColumnData::Float64(values) => Arc::new(Float64Array::from(values.clone())),
+                        ColumnData::Int64(values) => Arc::new(Int64Array::from(values.clone())),
+                        ColumnData::Timestamp(values) => {
+                            Arc::new(TimestampMicrosecondArray::from(values.clone()).with_timezone("UTC"))
+                        }
+                    }
+                })
+                .collect();
+            RecordBatch::try_new(self.arrow_schema(), arrays).map_err(columnar_error)
+        }
+
+        /// Write the table as a Parquet file
+        pub fn write_parquet<W: Write + Send>(&self, writer: W) -> RelationshipResult<()> {
+            let batch = self.to_record_batch()?;
+            let mut writer = ArrowWriter::try_new(writer, batch.schema(), None).map_err(columnar_error)?;
+            writer.write(&batch).map_err(columnar_error)?;
+            writer.close().map_err(columnar_error)?;
+            Ok(())
+        }
+
+        /// Write the table as an Arrow IPC stream
+        pub fn write_arrow_ipc<W: Write>(&self, writer: W) -> RelationshipResult<()> {
+            let batch = self.to_record_batch()?;
+            let mut writer = StreamWriter::try_new(writer, &batch.schema()).map_err(columnar_error)?;
+            writer.write(&batch).map_err(columnar_error)?;
+            writer.finish().map_err(columnar_error)?;
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{EdgeCommand, RelationshipCommand, UpdateEdgeQuality};
+    use cim_domain::MessageIdentity;
+    use crate::value_objects::{ParticipantRole, RelationshipCategory};
+    use cim_domain_spaces::TopologicalSpaceId;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_analytical_tables() {
+        let mut space = RelationshipSpace::new("Analytics", TopologicalSpaceId::new());
+        let alice = EntityRef::person(Uuid::now_v7());
+        let acme = EntityRef::organization(Uuid::now_v7());
+        let ends_at = Utc::now() + chrono::Duration::days(365);
+        let edge = EdgeConcept::new("Works at", alice.clone(), acme.clone(), RelationshipCategory::Employment)
+            .with_validity(ValidityPeriod::fixed_term(Utc::now(), ends_at));
+        let edge_id = edge.id;
+        space.add_edge(edge).unwrap();
+
+        let mut team = HyperEdgeConcept::new("Squad", RelationshipCategory::Custom("squad".to_string()));
+        team.add_participant(alice.clone(), ParticipantRole::Leader, 1.0).unwrap();
+        team.add_participant(acme.clone(), ParticipantRole::Primary, 0.5).unwrap();
+        space.add_hyperedge(team);
+
+        let mut quality = space.get_edge(&edge_id).unwrap().quality.clone();
+        quality.strength = 0.9;
+        let events = space
+            .handle_command(RelationshipCommand::Edge(EdgeCommand::UpdateEdgeQuality(UpdateEdgeQuality {
+                identity: MessageIdentity::new_root(),
+                edge_id,
+                new_quality: quality,
+                reason: "promotion".to_string(),
+            })))
+            .unwrap();
+
+        let tables = AnalyticalTables::from_space(&space).with_quality_history(&events);
+        assert_eq!(tables.edges.num_rows(), 1);
+        assert!(tables.edges.columns.iter().all(|c| c.data.len() == 1));
+        assert_eq!(
+            tables.edges.column("source"),
+            Some(&ColumnData::Utf8(vec![Some(alice.key().to_string())]))
+        );
+        assert_eq!(tables.edges.column("source_type"), Some(&ColumnData::Utf8(vec![Some("person".to_string())])));
+        assert_eq!(
+            tables.edges.column("valid_until"),
+            Some(&ColumnData::Timestamp(vec![Some(ends_at.timestamp_micros())]))
+        );
+
+        assert_eq!(tables.participants.num_rows(), 2);
+        assert_eq!(tables.participants.column("category"), Some(&ColumnData::Utf8(vec![Some("squad".to_string()); 2])));
+
+        assert_eq!(tables.quality_history.num_rows(), 1);
+        assert_eq!(tables.quality_history.column("new_strength"), Some(&ColumnData::Float64(vec![Some(0.9)])));
+        assert_eq!(
+            tables.quality_history.column("old_valid_until"),
+            Some(&ColumnData::Timestamp(vec![None]))
+        );
+    }
+
+    #[cfg(feature = "columnar")]
+    #[test]
+    fn test_arrow_and_parquet_output() {
+        use arrow::ipc::reader::StreamReader;
+        use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+        let mut space = RelationshipSpace::new("Analytics", TopologicalSpaceId::new());
+        for _ in 0..3 {
+            space
+                .add_edge(EdgeConcept::new(
+                    "Knows",
+                    EntityRef::person(Uuid::now_v7()),
+                    EntityRef::person(Uuid::now_v7()),
+                    RelationshipCategory::Friendship,
+                ))
+                .unwrap();
+        }
+        let edges = AnalyticalTables::from_space(&space).edges;
+
+        let batch = edges.to_record_batch().unwrap();
+        assert_eq!((batch.num_rows(), batch.num_columns()), (3, edges.columns.len()));
+        assert_eq!(batch.schema(), edges.arrow_schema());
+
+        let mut parquet = Vec::new();
+        edges.write_parquet(&mut parquet).unwrap();
+        let read: Vec<_> = ParquetRecordBatchReaderBuilder::try_new(bytes::Bytes::from(parquet))
+            .unwrap()
+            .build()
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(read, vec![batch.clone()]);
+
+        let mut ipc = Vec::new();
+        edges.write_arrow_ipc(&mut ipc).unwrap();
+        let read: Vec<_> = StreamReader::try_new(ipc.as_slice(), None)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(read, vec![batch]);
+    }
+}
@@ -0,0 +1,414 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! CSV Bulk Import
+//!
+//! Reads edges from CSV or TSV, one per row, and creates them through a
+//! `CreateEdgesBatch`, so every row passes the same checks as a single
+//! `CreateEdge`:
+//!
+//! ```text
+//! rows --> parse + validate --> valid rows --> CreateEdgesBatch --> events
+//!               |                                    |
+//!               +-- row errors            refused items (Partial)
+//!                          \                /
+//!                           CsvImportReport: one line per row
+//! ```
+//!
+//! ## Columns
+//!
+//! The first row names the columns (any order, case-insensitive):
+//!
+//! ```text
+//! source_type, source_id   or  source  ("person:{uuid}")     required
+//! target_type, target_id   or  target                         required
+//! category     "Employment", "part of", "part_of", other = custom   required
+//! name         defaults to the category name
+//! strength, trust, reciprocity   0.0 - 1.0
+//! formality    Informal | SemiFormal | Formal | Contractual | Legal
+//! valid_from, valid_until        RFC 3339 or YYYY-MM-DD
+//! ```
+//!
+//! Without quality columns an edge gets the default quality; otherwise the
+//! quality starts from the category's default formality and takes the
+//! columns given. Fields follow RFC 4180: quoted fields may hold the
+//! delimiter, doubled quotes and line breaks.
+//!
+//! `import` applies the events to a copy of the space and takes the copy
+//! only if all of them apply.
+
+use crate::aggregates::RelationshipSpace;
+use crate::commands::{BatchEdge, BatchMode, CreateEdgesBatch, RelationshipCommand};
+use crate::events::RelationshipEvent;
+use crate::quality::RelationshipQuality;
+use crate::value_objects::{EntityKey, EntityRef, Formality, RelationshipCategory, RelationshipId, ValidityPeriod};
+use crate::{RelationshipError, RelationshipResult};
+use chrono::{DateTime, NaiveDate, Utc};
+use cim_domain::MessageIdentity;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Read;
+
+/// Outcome of one data row
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CsvRowResult {
+    /// Line the row starts on (the header is line 1)
+    pub line: usize,
+    /// Edge created from the row (`None` = row invalid)
+    pub edge_id: Option<RelationshipId>,
+    /// Why the row was not imported (`None` = imported)
+    pub error: Option<String>,
+}
+
+/// Per-row outcome of an import, with the events of the imported rows
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CsvImportReport {
+    /// One result per data row, in file order
+    pub rows: Vec<CsvRowResult>,
+    /// Events of the imported rows, already applied to the space
+    pub events: Vec<RelationshipEvent>,
+}
+
+impl CsvImportReport {
+    /// Number of rows imported
+    pub fn imported(&self) -> usize {
+        self.rows.iter().filter(|r| r.error.is_none()).count()
+    }
+
+    /// Rows not imported
+    pub fn failed(&self) -> impl Iterator<Item = &CsvRowResult> {
+        self.rows.iter().filter(|r| r.error.is_some())
+    }
+}
+
+/// Validated rows, ready to become a batch
+#[derive(Debug, Clone, Default)]
+pub struct CsvRows {
+    /// Valid rows with their line numbers
+    pub edges: Vec<(usize, BatchEdge)>,
+    /// Invalid rows with their line numbers and reasons
+    pub errors: Vec<(usize, String)>,
+}
+
+/// Imports edges from delimited text
+#[derive(Debug, Clone)]
+pub struct CsvImporter {
+    delimiter: u8,
+    mode: BatchMode,
+    created_by: String,
+}
+
+impl CsvImporter {
+    /// Importer for comma-separated rows, skipping refused rows
+    pub fn new(created_by: impl Into<String>) -> Self {
+        Self {
+            delimiter: b',',
+            mode: BatchMode::Partial,
+            created_by: created_by.into(),
+        }
+    }
+
+    /// Set the field delimiter (`b'\t'` for TSV)
+    pub fn with_delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Set how the batch treats rows the space refuses
+    ///
+    /// With `AllOrNothing`, an invalid or refused row fails the import and
+    /// nothing is applied.
+    pub fn with_mode(mut self, mode: BatchMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Parse and validate rows without touching a space
+    pub fn parse(&self, mut reader: impl Read) -> RelationshipResult<CsvRows> {
+        let mut text = String::new();
+        reader
+            .read_to_string(&mut text)
+            .map_err(|e| invalid(format!("unreadable CSV: {}", e)))?;
+        let mut records = records(text.trim_start_matches('\u{feff}'), self.delimiter)?.into_iter();
+        let (_, header) = records.next().ok_or_else(|| invalid("CSV has no header row"))?;
+        let columns = Columns::new(&header)?;
+
+        let mut rows = CsvRows::default();
+        for (line, fields) in records {
+            if fields.iter().all(|f| f.trim().is_empty()) {
+                continue;
+            }
+            match columns.edge(&fields) {
+                Ok(edge) => rows.edges.push((line, edge)),
+                Err(reason) => rows.errors.push((line, reason)),
+            }
+        }
+        Ok(rows)
+    }
+
+    /// The batch command creating the valid rows
+    pub fn command(&self, rows: &CsvRows) -> RelationshipCommand {
+        RelationshipCommand::CreateEdgesBatch(CreateEdgesBatch {
+            identity: MessageIdentity::new_root(),
+            edges: rows.edges.iter().map(|(_, edge)| edge.clone()).collect(),
+            mode: self.mode,
+            created_by: self.created_by.clone(),
+        })
+    }
+
+    /// Import rows into a space, reporting the outcome of every row
+    pub fn import(&self, space: &mut RelationshipSpace, reader: impl Read) -> RelationshipResult<CsvImportReport> {
+        let rows = self.parse(reader)?;
+        if self.mode == BatchMode::AllOrNothing {
+            if let Some((line, reason)) = rows.errors.first() {
+                return Err(invalid(format!("line {}: {}", line, reason)));
+            }
+        }
+
+        let outcome = space.handle_batch(self.command(&rows))?;
+        let mut imported = space.clone();
+        for event in &outcome.events {
+            imported.apply_event(event)?;
+        }
+        *space = imported;
+
+        let mut report = CsvImportReport {
+            rows: rows
+                .errors
+                .into_iter()
+                .map(|(line, reason)| CsvRowResult {
+                    line,
+                    edge_id: None,
+                    error: Some(reason),
+                })
+                .collect(),
+            events: outcome.events,
+        };
+        for result in outcome.results {
+            let (line, edge) = &rows.edges[result.index];
+            report.rows.push(CsvRowResult {
+                line: *line,
+                edge_id: Some(edge.edge_id),
+                error: result.error,
+            });
+        }
+        report.rows.sort_by_key(|r| r.line);
+        Ok(report)
+    }
+}
+
+fn invalid(reason: impl Into<String>) -> RelationshipError {
+    RelationshipError::InvalidDocument(reason.into())
+}
+
+// ============================================================================
+// Columns
+// ============================================================================
+
+/// Column positions by name
+struct Columns {
+    index: HashMap<String, usize>,
+}
+
+impl Columns {
+    fn new(header: &[String]) -> RelationshipResult<Self> {
+        let index: HashMap<String, usize> = header
+            .iter()
+            .enumerate()
+            .map(|(i, name)| (name.trim().to_ascii_lowercase(), i))
+            .collect();
+        let columns = Self { index };
+        for side in ["source", "target"] {
+            let by_key = columns.index.contains_key(side);
+            let by_parts = columns.index.contains_key(&format!("{}_type", side))
+                && columns.index.contains_key(&format!("{}_id", side));
+            if !by_key && !by_parts {
+                return Err(invalid(format!("missing column {0} or {0}_type and {0}_id", side)));
+            }
+        }
+        if !columns.index.contains_key("category") {
+            return Err(invalid("missing column category"));
+        }
+        Ok(columns)
+    }
+
+    /// A non-empty field
+    fn get<'a>(&self, fields: &'a [String], column: &str) -> Option<&'a str> {
+        self.index
+            .get(column)
+            .and_then(|&i| fields.get(i))
+            .map(|f| f.trim())
+            .filter(|f| !f.is_empty())
+    }
+
+    fn edge(&self, fields: &[String]) -> Result<BatchEdge, String> {
+        let source = self.entity(fields, "source")?;
+        let target = self.entity(fields, "target")?;
+        let category = RelationshipCategory::from_name(self.get(fields, "category").ok_or("category is empty")?);
+        let name = self
+            .get(fields, "name")
+            .map(str::to_string)
+            .unwrap_or_else(|| category.display_name());
+        let quality = self.quality(fields, &category)?;
+        Ok(BatchEdge {
+            edge_id: RelationshipId::new(),
+            source,
+            target,
+            category,
+            name,
+            quality,
+        })
+    }
+
+    fn entity(&self, fields: &[String], side: &str) -> Result<EntityRef, String> {
+        let key = match self.get(fields, side) {
+            Some(key) => key.to_string(),
+            None => format!(
+                "{}:{}",
+                self.get(fields, &format!("{}_type", side))
+                    .ok_or_else(|| format!("{}_type is empty", side))?,
+                self.get(fields, &format!("{}_id", side))
+                    .ok_or_else(|| format!("{}_id is empty", side))?
+            ),
+        };
+        let key: EntityKey = key.parse().map_err(|e| format!("{}: {}", side, e))?;
+        Ok(EntityRef::new(key.entity_type, key.entity_id))
+    }
+
+    fn quality(
+        &self,
+        fields: &[String],
+        category: &RelationshipCategory,
+    ) -> Result<Option<RelationshipQuality>, String> {
+        const QUALITY_COLUMNS: [&str; 6] =
+            ["strength", "trust", "reciprocity", "formality", "valid_from", "valid_until"];
+        if QUALITY_COLUMNS.iter().all(|c| self.get(fields, c).is_none()) {
+            return Ok(None);
+        }
+
+        let mut quality = RelationshipQuality {
+            formality: category.default_formality(),
+            ..RelationshipQuality::default()
+        };
+        for (column, slot) in [
+            ("strength", &mut quality.strength),
+            ("trust", &mut quality.trust),
+            ("reciprocity", &mut quality.reciprocity),
+        ] {
+            if let Some(value) = self.get(fields, column) {
+                let value: f64 = value.parse().map_err(|_| format!("{} {:?} is not a number", column, value))?;
+                if !(0.0..=1.0).contains(&value) {
+                    return Err(format!("{} {} is outside 0.0 - 1.0", column, value));
+                }
+                *slot = value;
+            }
+        }
+        if let Some(formality) = self.get(fields, "formality") {
+            quality.formality = Formality::from_name(formality)
+                .ok_or_else(|| format!("unknown formality {:?}", formality))?;
+        }
+        let starts_at = self
+            .get(fields, "valid_from")
+            .map(|d| parse_date("valid_from", d))
+            .transpose()?
+            .unwrap_or(quality.duration.starts_at);
+        quality.duration = match self.get(fields, "valid_until").map(|d| parse_date("valid_until", d)).transpose()? {
+            Some(ends_at) if ends_at < starts_at => return Err("valid_until is before valid_from".to_string()),
+            Some(ends_at) => ValidityPeriod::fixed_term(starts_at, ends_at),
+            None => ValidityPeriod::ongoing(starts_at),
+        };
+        Ok(Some(quality))
+    }
+}
+
+fn parse_date(column: &str, value: &str) -> Result<DateTime<Utc>, String> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|t| t.with_timezone(&Utc))
+        .or_else(|_| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d").map(|d| d.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc())
+        })
+        .map_err(|_| format!("{} {:?} is not a date", column, value))
+}
+
+// ============================================================================
+// Records
+// ============================================================================
+
+/// Split text into records of fields, with the line each record starts on
+///
+/// Blank lines are skipped.
+fn records(text: &str, delimiter: u8) -> RelationshipResult<Vec<(usize, Vec<String>)>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(text.as_bytes());
+    reader
+        .records()
+        .map(|record| {
+            let record = record.map_err(|e| invalid(format!("malformed CSV: {}", e)))?;
+            // Positions of records after blank lines point at the blank lines
+            let line = record.position().map_or(1, |p| {
+                let skipped = text[p.byte() as usize..]
+                    .bytes()
+                    .take_while(|b| matches!(b, b'\r' | b'\n'))
+                    .filter(|b| *b == b'\n')
+                    .count();
+                p.line() as usize + skipped
+            });
+            Ok((line, record.iter().map(str::to_string).collect()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cim_domain_spaces::TopologicalSpaceId;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_csv_import_reports_every_row() {
+        let (alice, acme) = (Uuid::now_v7(), Uuid::now_v7());
+        let csv = format!(
+            "Source_Type,Source_ID,target,category,name,strength,formality,valid_from,valid_until\n\
+             person,{alice},organization:{acme},employment,\"Alice, at Acme\",0.9,contractual,2024-01-01,\n\
+             person,{alice},organization:{acme},part_of,,,,,\n\
+             person,not-a-uuid,organization:{acme},employment,,,,,\n\
+             person,{alice},organization:{acme},employment,,1.5,,,\n\
+             \n\
+             person,{alice},person:{alice},depends on,\"Multi\nline\",,,2024-02-01,2024-01-01\n\
+             person,{alice},person:{alice},DependsOn,Self,,,,\n",
+        );
+
+        let mut space = RelationshipSpace::new("Imported", TopologicalSpaceId::new());
+        let report = CsvImporter::new("import").import(&mut space, csv.as_bytes()).unwrap();
+        let lines: Vec<usize> = report.rows.iter().map(|r| r.line).collect();
+        assert_eq!(lines, vec![2, 3, 4, 5, 7, 9]);
+        assert_eq!(report.imported(), 2);
+        assert_eq!(space.edges.len(), 2);
+
+        let job = space.get_edge(&report.rows[0].edge_id.unwrap()).unwrap();
+        assert_eq!(job.name, "Alice, at Acme");
+        assert_eq!(job.quality.strength, 0.9);
+        assert_eq!(job.quality.formality, Formality::Contractual);
+        assert_eq!(space.get_edge(&report.rows[1].edge_id.unwrap()).unwrap().category, RelationshipCategory::PartOf);
+
+        let errors: Vec<&str> = report.failed().map(|r| r.error.as_deref().unwrap()).collect();
+        assert!(errors[0].starts_with("source"));
+        assert!(errors[1].contains("outside"));
+        assert!(errors[2].contains("before valid_from"));
+        // Valid row refused by the space: self-dependency
+        assert!(report.rows[5].edge_id.is_some() && report.rows[5].error.is_some());
+
+        let tsv = format!("source\ttarget\tcategory\nperson:{}\torganization:{}\tFriendship\n", alice, acme);
+        let rows = CsvImporter::new("import").with_delimiter(b'\t').parse(tsv.as_bytes()).unwrap();
+        assert_eq!(rows.edges[0].1.category, RelationshipCategory::Friendship);
+
+        let strict = CsvImporter::new("import").with_mode(BatchMode::AllOrNothing);
+        let mut untouched = RelationshipSpace::new("Strict", TopologicalSpaceId::new());
+        assert!(strict.import(&mut untouched, csv.as_bytes()).is_err());
+        assert!(untouched.edges.is_empty());
+    }
+}
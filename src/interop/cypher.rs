@@ -0,0 +1,292 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Cypher
+//!
+//! Parameterized Cypher that mirrors relationships into a property graph
+//! such as Neo4j:
+//!
+//! ```text
+//! entity       (:Entity:Person {key: "person:{uuid}", entity_type, entity_id})
+//! edge         (source)-[:EMPLOYMENT {id, name, state, strength, ...}]->(target)
+//! hyperedge    (:HyperEdge {id, name, category, state, strength, ...})
+//!              (participant)-[:PARTICIPATES_IN {role, weight}]->(hyperedge)
+//! ```
+//!
+//! Every statement is a MERGE of the relationship's current state, so
+//! running it twice, or out of order with older statements for the same
+//! relationship, leaves the graph as the latest run describes it. Values
+//! travel as parameters; only labels and relationship types, which Cypher
+//! cannot parameterize, are written into the query, restricted to
+//! `[A-Za-z0-9_]`.
+//!
+//! `cypher_for_event` turns a relationship event into the statements for
+//! the relationship it touched, read from the space the event was applied
+//! to.
+
+use crate::aggregates::{EdgeConcept, HyperEdgeConcept, RelationshipSpace};
+use crate::events::RelationshipEvent;
+use crate::quality::RelationshipQuality;
+use crate::value_objects::{EntityRef, EntityType, RelationshipCategory};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+/// Relationship type linking hyperedge participants to their hyperedge
+pub const PARTICIPATES_IN: &str = "PARTICIPATES_IN";
+
+/// A Cypher query with its parameters
+///
+/// Serializes as a statement of the Neo4j HTTP transaction API.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CypherStatement {
+    /// The query
+    pub statement: String,
+    /// Named parameters (`$name` in the query)
+    pub parameters: Map<String, Value>,
+}
+
+impl CypherStatement {
+    fn new(statement: impl Into<String>, parameters: Value) -> Self {
+        let parameters = match parameters {
+            Value::Object(map) => map,
+            _ => Map::new(),
+        };
+        Self {
+            statement: statement.into(),
+            parameters,
+        }
+    }
+}
+
+/// Statements mirroring an edge
+///
+/// A copy of the edge between other endpoints or under another type (after
+/// its endpoints were rewritten or reversed) is deleted first.
+pub fn cypher_for_edge(edge: &EdgeConcept) -> Vec<CypherStatement> {
+    let rel_type = relationship_type(&edge.category);
+    let mut props = quality_properties(&edge.quality);
+    props.insert("id".to_string(), json!(edge.id.as_uuid().to_string()));
+    props.insert("name".to_string(), json!(edge.name));
+    props.insert("category".to_string(), json!(edge.category.display_name()));
+    props.insert("state".to_string(), json!(format!("{:?}", edge.state)));
+    props.insert("tags".to_string(), json!(edge.tags));
+    props.insert("evidence_cids".to_string(), json!(edge.evidence_cids));
+    props.insert("created_at".to_string(), json!(edge.created_at.to_rfc3339()));
+
+    vec![
+        CypherStatement::new(
+            format!(
+                "MATCH (s)-[r {{id: $id}}]->(t) \
+                 WHERE s.key <> $source OR t.key <> $target OR type(r) <> '{}' \
+                 DELETE r",
+                rel_type
+            ),
+            json!({
+                "id": edge.id.as_uuid().to_string(),
+                "source": edge.source.key().to_string(),
+                "target": edge.target.key().to_string(),
+            }),
+        ),
+        CypherStatement::new(
+            format!(
+                "{} {} MERGE (s)-[r:{} {{id: $id}}]->(t) SET r = $props",
+                merge_entity("s", &edge.source, "source"),
+                merge_entity("t", &edge.target, "target"),
+                rel_type
+            ),
+            json!({
+                "id": edge.id.as_uuid().to_string(),
+                "source": entity_parameters(&edge.source),
+                "target": entity_parameters(&edge.target),
+                "props": props,
+            }),
+        ),
+    ]
+}
+
+/// Statements mirroring a hyperedge and its participants
+///
+/// Memberships of entities no longer participating are deleted.
+pub fn cypher_for_hyperedge(hyperedge: &HyperEdgeConcept) -> Vec<CypherStatement> {
+    let id = hyperedge.id.as_uuid().to_string();
+    let mut props = quality_properties(&hyperedge.quality);
+    props.insert("id".to_string(), json!(id));
+    props.insert("name".to_string(), json!(hyperedge.name));
+    props.insert("category".to_string(), json!(hyperedge.category.display_name()));
+    props.insert("state".to_string(), json!(format!("{:?}", hyperedge.state)));
+    props.insert("tags".to_string(), json!(hyperedge.tags));
+    props.insert("evidence_cids".to_string(), json!(hyperedge.evidence_cids));
+    props.insert("created_at".to_string(), json!(hyperedge.created_at.to_rfc3339()));
+
+    let mut participants: Vec<_> = hyperedge.participants.participants().collect();
+    participants.sort_by_key(|p| p.entity_ref.key().to_string());
+    let keys: Vec<String> = participants.iter().map(|p| p.entity_ref.key().to_string()).collect();
+
+    let mut statements = vec![
+        CypherStatement::new("MERGE (h:HyperEdge {id: $id}) SET h = $props", json!({ "id": id, "props": props })),
+        CypherStatement::new(
+            format!(
+                "MATCH (e)-[m:{}]->(:HyperEdge {{id: $id}}) WHERE NOT e.key IN $keys DELETE m",
+                PARTICIPATES_IN
+            ),
+            json!({ "id": id, "keys": keys }),
+        ),
+    ];
+    // One statement per participant: labels differ by entity type
+    for participant in participants {
+        statements.push(CypherStatement::new(
+            format!(
+                "MATCH (h:HyperEdge {{id: $id}}) {} MERGE (e)-[m:{}]->(h) SET m.role = $role, m.weight = $weight",
+                merge_entity("e", &participant.entity_ref, "entity"),
+                PARTICIPATES_IN
+            ),
+            json!({
+                "id": id,
+                "entity": entity_parameters(&participant.entity_ref),
+                "role": participant.role.display_name(),
+                "weight": participant.weight,
+            }),
+        ));
+    }
+    statements
+}
+
+/// Statements mirroring the relationship an event touched
+///
+/// The space must already have the event applied. Events for
+/// relationships not in the space, and policy events, yield no statements.
+pub fn cypher_for_event(space: &RelationshipSpace, event: &RelationshipEvent) -> Vec<CypherStatement> {
+    match event {
+        RelationshipEvent::Edge(e) => space.get_edge(&e.edge_id()).map(cypher_for_edge),
+        RelationshipEvent::HyperEdge(e) => space.get_hyperedge(&e.hyperedge_id()).map(cypher_for_hyperedge),
+        RelationshipEvent::Policy(_) => None,
+    }
+    .unwrap_or_default()
+}
+
+/// Statements mirroring a whole space
+pub fn cypher_for_space(space: &RelationshipSpace) -> Vec<CypherStatement> {
+    let mut edges: Vec<&EdgeConcept> = space.edges.values().collect();
+    edges.sort_by_key(|e| e.id.as_uuid());
+    let mut hyperedges: Vec<&HyperEdgeConcept> = space.hyperedges.values().collect();
+    hyperedges.sort_by_key(|h| h.id.as_uuid());
+    edges
+        .into_iter()
+        .flat_map(cypher_for_edge)
+        .chain(hyperedges.into_iter().flat_map(cypher_for_hyperedge))
+        .collect()
+}
+
+/// `MERGE` of an entity node bound to `var`, from parameter `$param`
+fn merge_entity(var: &str, entity: &EntityRef, param: &str) -> String {
+    format!(
+        "MERGE ({var}:Entity {{key: ${param}.key}}) \
+         ON CREATE SET {var}.entity_type = ${param}.entity_type, {var}.entity_id = ${param}.entity_id \
+         SET {var}:{label}",
+        var = var,
+        param = param,
+        label = entity_label(&entity.entity_type)
+    )
+}
+
+fn entity_parameters(entity: &EntityRef) -> Value {
+    json!({
+        "key": entity.key().to_string(),
+        "entity_type": match &entity.entity_type {
+            EntityType::Custom(name) => format!("custom/{}", name),
+            other => other.nats_subject_prefix().to_string(),
+        },
+        "entity_id": entity.entity_id.to_string(),
+    })
+}
+
+fn quality_properties(quality: &RelationshipQuality) -> Map<String, Value> {
+    let mut props = Map::new();
+    props.insert("strength".to_string(), json!(quality.strength));
+    props.insert("trust".to_string(), json!(quality.trust));
+    props.insert("formality".to_string(), json!(format!("{:?}", quality.formality)));
+    props.insert("reciprocity".to_string(), json!(quality.reciprocity));
+    props.insert("valid_from".to_string(), json!(quality.duration.starts_at.to_rfc3339()));
+    if let Some(ends_at) = quality.duration.ends_at {
+        props.insert("valid_until".to_string(), json!(ends_at.to_rfc3339()));
+    }
+    props
+}
+
+/// Node label of an entity type ("Person", "Ticket" for `custom/ticket`)
+fn entity_label(entity_type: &EntityType) -> String {
+    let name = match entity_type {
+        EntityType::Custom(name) => name.clone(),
+        other => other.nats_subject_prefix().to_string(),
+    };
+    let words = identifier_words(&name);
+    let label: String = words
+        .iter()
+        .map(|w| {
+            let mut chars = w.chars();
+            chars
+                .next()
+                .map(|c| c.to_ascii_uppercase().to_string() + &chars.as_str().to_ascii_lowercase())
+                .unwrap_or_default()
+        })
+        .collect();
+    guard_identifier(label)
+}
+
+/// Relationship type of a category ("PART_OF", "SQUAD" for `Custom("squad")`)
+fn relationship_type(category: &RelationshipCategory) -> String {
+    guard_identifier(identifier_words(&category.display_name()).join("_").to_ascii_uppercase())
+}
+
+fn identifier_words(name: &str) -> Vec<String> {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Identifiers must be non-empty and must not start with a digit
+fn guard_identifier(identifier: String) -> String {
+    match identifier.chars().next() {
+        None => "RELATED".to_string(),
+        Some(c) if c.is_ascii_digit() => format!("_{}", identifier),
+        Some(_) => identifier,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value_objects::ParticipantRole;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_cypher_statements_are_parameterized() {
+        let alice = EntityRef::person(Uuid::now_v7());
+        let wiki = EntityRef::new(EntityType::Custom("wiki page!".to_string()), Uuid::now_v7());
+        let edge = EdgeConcept::new("Part of\") DETACH DELETE n //", alice.clone(), wiki.clone(), RelationshipCategory::PartOf);
+
+        let statements = cypher_for_edge(&edge);
+        assert_eq!(statements.len(), 2);
+        let merge = &statements[1];
+        assert!(merge.statement.contains("MERGE (s)-[r:PART_OF {id: $id}]->(t)"));
+        assert!(merge.statement.contains("SET t:WikiPage"));
+        assert!(!merge.statement.contains("DETACH"));
+        assert_eq!(merge.parameters["props"]["name"], json!(edge.name));
+        assert_eq!(merge.parameters["source"]["key"], json!(alice.key().to_string()));
+        assert!(statements[0].statement.contains("type(r) <> 'PART_OF'"));
+
+        let mut team = HyperEdgeConcept::new("Squad", RelationshipCategory::Custom("9 lives".to_string()));
+        team.add_participant(alice.clone(), ParticipantRole::Leader, 1.0).unwrap();
+        team.add_participant(wiki.clone(), ParticipantRole::Observer, 0.2).unwrap();
+        let statements = cypher_for_hyperedge(&team);
+        assert_eq!(statements.len(), 4);
+        assert_eq!(statements[1].parameters["keys"].as_array().unwrap().len(), 2);
+        assert!(statements[2..].iter().all(|s| s.statement.contains("MERGE (e)-[m:PARTICIPATES_IN]->(h)")));
+        assert_eq!(relationship_type(&team.category), "_9_LIVES");
+
+        let body = serde_json::to_value(&statements[0]).unwrap();
+        assert!(body.get("statement").is_some() && body.get("parameters").is_some());
+    }
+}
@@ -0,0 +1,341 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Portable Relationship Document
+//!
+//! A self-contained, lossless export of a single relationship:
+//!
+//! ```text
+//! RelationshipDocument
+//!     |
+//!     +-- format: "cim.relationship/v1"
+//!     +-- relationship: Edge | HyperEdge
+//!     |   +-- state: aggregate state at export time
+//!     |   +-- history: event history (optional)
+//!     +-- evidence: manifest of evidence CIDs
+//! ```
+//!
+//! Documents are validated on import: the format tag must match, the
+//! evidence manifest must cover the aggregate's evidence CIDs, and when
+//! history is present, replaying it must reproduce the exported state.
+
+use crate::aggregates::{EdgeConcept, HyperEdgeConcept};
+use crate::events::{EdgeEvent, HyperEdgeEvent};
+use crate::value_objects::RelationshipId;
+use crate::{RelationshipError, RelationshipResult};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Format tag of the current document version
+pub const DOCUMENT_FORMAT: &str = "cim.relationship/v1";
+
+/// A relationship and (optionally) the events that produced it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PortableRelationship {
+    Edge {
+        state: EdgeConcept,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        history: Vec<EdgeEvent>,
+    },
+    HyperEdge {
+        state: HyperEdgeConcept,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        history: Vec<HyperEdgeEvent>,
+    },
+}
+
+impl PortableRelationship {
+    /// ID of the relationship
+    pub fn id(&self) -> RelationshipId {
+        match self {
+            PortableRelationship::Edge { state, .. } => state.id,
+            PortableRelationship::HyperEdge { state, .. } => state.id,
+        }
+    }
+
+    /// Evidence CIDs held by the relationship
+    pub fn evidence_cids(&self) -> &[String] {
+        match self {
+            PortableRelationship::Edge { state, .. } => &state.evidence_cids,
+            PortableRelationship::HyperEdge { state, .. } => &state.evidence_cids,
+        }
+    }
+}
+
+/// Entry in the evidence manifest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EvidenceManifestEntry {
+    /// CID of the evidence object
+    pub cid: String,
+    /// Kind of evidence, when known from history
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub evidence_type: Option<String>,
+    /// When the evidence was attached, when known from history
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub added_at: Option<DateTime<Utc>>,
+}
+
+/// Canonical portable document for a single relationship
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelationshipDocument {
+    /// Document format tag
+    pub format: String,
+    /// When the document was produced
+    pub exported_at: DateTime<Utc>,
+    /// The relationship itself
+    pub relationship: PortableRelationship,
+    /// Evidence referenced by the relationship
+    #[serde(default)]
+    pub evidence: Vec<EvidenceManifestEntry>,
+}
+
+impl RelationshipDocument {
+    /// Export an edge, with its event history if available
+    pub fn from_edge(edge: &EdgeConcept, history: &[EdgeEvent]) -> Self {
+        let evidence = edge
+            .evidence_cids
+            .iter()
+            .map(|cid| {
+                let added = history.iter().find_map(|event| match event {
+                    EdgeEvent::EvidenceAdded(e) if &e.evidence_cid == cid => Some(e),
+                    _ => None,
+                });
+                EvidenceManifestEntry {
+                    cid: cid.clone(),
+                    evidence_type: added.map(|e| e.evidence_type.clone()),
+                    added_at: added.map(|e| e.added_at),
+                }
+            })
+            .collect();
+
+        Self {
+            format: DOCUMENT_FORMAT.to_string(),
+            exported_at: Utc::now(),
+            relationship: PortableRelationship::Edge {
+                state: edge.clone(),
+                history: history.to_vec(),
+            },
+            evidence,
+        }
+    }
+
+    /// Export a hyperedge, with its event history if available
+    pub fn from_hyperedge(hyperedge: &HyperEdgeConcept, history: &[HyperEdgeEvent]) -> Self {
+        let evidence = hyperedge
+            .evidence_cids
+            .iter()
+            .map(|cid| EvidenceManifestEntry {
+                cid: cid.clone(),
+                evidence_type: None,
+                added_at: None,
+            })
+            .collect();
+
+        Self {
+            format: DOCUMENT_FORMAT.to_string(),
+            exported_at: Utc::now(),
+            relationship: PortableRelationship::HyperEdge {
+                state: hyperedge.clone(),
+                history: history.to_vec(),
+            },
+            evidence,
+        }
+    }
+
+    /// Check that the document is internally consistent
+    pub fn validate(&self) -> RelationshipResult<()> {
+        if self.format != DOCUMENT_FORMAT {
+            return Err(invalid(format!(
+                "unsupported format '{}', expected '{}'",
+                self.format, DOCUMENT_FORMAT
+            )));
+        }
+
+        let manifest: HashSet<&str> = self.evidence.iter().map(|e| e.cid.as_str()).collect();
+        if let Some(missing) = self
+            .relationship
+            .evidence_cids()
+            .iter()
+            .find(|cid| !manifest.contains(cid.as_str()))
+        {
+            return Err(invalid(format!("evidence {} missing from manifest", missing)));
+        }
+
+        match &self.relationship {
+            PortableRelationship::Edge { state, history } => {
+                check_quality(state.quality.strength, state.quality.trust, state.quality.reciprocity)?;
+                if history.is_empty() {
+                    return Ok(());
+                }
+                let replayed = EdgeConcept::from_events(history)?;
+                if replayed.id != state.id
+                    || replayed.source != state.source
+                    || replayed.target != state.target
+                    || replayed.category != state.category
+                    || replayed.state != state.state
+                    || replayed.evidence_cids != state.evidence_cids
+                {
+                    return Err(invalid("event history does not reproduce edge state".to_string()));
+                }
+            }
+            PortableRelationship::HyperEdge { state, history } => {
+                check_quality(state.quality.strength, state.quality.trust, state.quality.reciprocity)?;
+                if history.is_empty() {
+                    return Ok(());
+                }
+                let replayed = HyperEdgeConcept::from_events(history)?;
+                if replayed.id != state.id
+                    || replayed.category != state.category
+                    || replayed.state != state.state
+                    || replayed.participant_count() != state.participant_count()
+                    || state
+                        .participants
+                        .participants()
+                        .any(|p| !replayed.participants.contains(&p.entity_ref))
+                {
+                    return Err(invalid(
+                        "event history does not reproduce hyperedge state".to_string(),
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Encode as pretty-printed JSON
+    pub fn to_json(&self) -> RelationshipResult<String> {
+        serde_json::to_string_pretty(self).map_err(|e| invalid(e.to_string()))
+    }
+
+    /// Decode and validate a JSON document
+    pub fn from_json(json: &str) -> RelationshipResult<Self> {
+        let document: Self = serde_json::from_str(json).map_err(|e| invalid(e.to_string()))?;
+        document.validate()?;
+        Ok(document)
+    }
+
+    /// Encode as CBOR
+    pub fn to_cbor(&self) -> RelationshipResult<Vec<u8>> {
+        let mut bytes = Vec::new();
+        ciborium::into_writer(self, &mut bytes).map_err(|e| invalid(e.to_string()))?;
+        Ok(bytes)
+    }
+
+    /// Decode and validate a CBOR document
+    pub fn from_cbor(bytes: &[u8]) -> RelationshipResult<Self> {
+        let document: Self = ciborium::from_reader(bytes).map_err(|e| invalid(e.to_string()))?;
+        document.validate()?;
+        Ok(document)
+    }
+}
+
+fn invalid(reason: String) -> RelationshipError {
+    RelationshipError::InvalidDocument(reason)
+}
+
+fn check_quality(strength: f64, trust: f64, reciprocity: f64) -> RelationshipResult<()> {
+    for (name, value) in [("strength", strength), ("trust", trust), ("reciprocity", reciprocity)] {
+        if !(0.0..=1.0).contains(&value) {
+            return Err(RelationshipError::QualityOutOfRange(format!("{} = {}", name, value)));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{EdgeActivated, EdgeCreated, EdgeEvidenceAdded};
+    use crate::value_objects::{EntityRef, RelationshipCategory};
+    use cim_domain::MessageIdentity;
+    use cim_domain_spaces::ConceptId;
+    use uuid::Uuid;
+
+    fn edge_history() -> Vec<EdgeEvent> {
+        let edge_id = RelationshipId::new();
+        let now = Utc::now();
+        vec![
+            EdgeEvent::EdgeCreated(EdgeCreated {
+                event_id: Uuid::now_v7(),
+                identity: MessageIdentity::new_root(),
+                edge_id,
+                concept_id: ConceptId::new(),
+                source: EntityRef::person(Uuid::now_v7()),
+                target: EntityRef::organization(Uuid::now_v7()),
+                category: RelationshipCategory::Employment,
+                name: "Employment".to_string(),
+                created_by: "test".to_string(),
+                created_at: now,
+            }),
+            EdgeEvent::EdgeActivated(EdgeActivated {
+                event_id: Uuid::now_v7(),
+                identity: MessageIdentity::new_root(),
+                edge_id,
+                activated_by: "test".to_string(),
+                activated_at: now,
+            }),
+            EdgeEvent::EvidenceAdded(EdgeEvidenceAdded {
+                event_id: Uuid::now_v7(),
+                identity: MessageIdentity::new_root(),
+                edge_id,
+                evidence_cid: "bafkreicontract".to_string(),
+                evidence_type: "contract".to_string(),
+                added_at: now,
+            }),
+        ]
+    }
+
+    #[test]
+    fn test_json_roundtrip() {
+        let history = edge_history();
+        let edge = EdgeConcept::from_events(&history).unwrap();
+
+        let document = RelationshipDocument::from_edge(&edge, &history);
+        assert_eq!(document.evidence[0].evidence_type.as_deref(), Some("contract"));
+
+        let json = document.to_json().unwrap();
+        let imported = RelationshipDocument::from_json(&json).unwrap();
+        assert_eq!(
+            serde_json::to_value(&imported).unwrap(),
+            serde_json::to_value(&document).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_cbor_roundtrip() {
+        let history = edge_history();
+        let edge = EdgeConcept::from_events(&history).unwrap();
+
+        let document = RelationshipDocument::from_edge(&edge, &history);
+        let imported = RelationshipDocument::from_cbor(&document.to_cbor().unwrap()).unwrap();
+        assert_eq!(imported.relationship.id(), edge.id);
+        assert_eq!(imported.relationship.evidence_cids(), edge.evidence_cids.as_slice());
+    }
+
+    #[test]
+    fn test_import_rejects_tampered_state() {
+        let history = edge_history();
+        let mut edge = EdgeConcept::from_events(&history).unwrap();
+        edge.state = crate::aggregates::EdgeState::Terminated;
+
+        let json = RelationshipDocument::from_edge(&edge, &history).to_json().unwrap();
+        assert!(matches!(
+            RelationshipDocument::from_json(&json),
+            Err(RelationshipError::InvalidDocument(_))
+        ));
+    }
+
+    #[test]
+    fn test_import_rejects_missing_evidence() {
+        let history = edge_history();
+        let edge = EdgeConcept::from_events(&history).unwrap();
+
+        let mut document = RelationshipDocument::from_edge(&edge, &history);
+        document.evidence.clear();
+        assert!(document.validate().is_err());
+    }
+}
@@ -0,0 +1,250 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! DOT (Graphviz)
+//!
+//! Renders a RelationshipSpace, or the ego network of one entity, as a DOT
+//! digraph for documentation and debugging (`dot -Tsvg space.dot`):
+//!
+//! ```text
+//! entity        --> box node labelled with its key
+//! edge          --> arrow source -> target
+//!                     line style / arrowhead  by category family
+//!                     color                   by state
+//!                     penwidth 1 - 5          by strength
+//! hyperedge     --> diamond node, one undirected spoke per participant
+//!                     labelled with the participant's role
+//! ego center    --> filled, bold node
+//! ```
+//!
+//! Category families:
+//!
+//! ```text
+//! organizational  employment, membership, ownership, management  solid
+//! social          friendship, professional contact, mentorship   dashed
+//! structural      part of, contains, depends on, implements      solid, diamond head
+//! temporal        precedes, triggers                             dotted
+//! knowledge       references, derives from                       dashed, empty head
+//! custom          anything else                                  solid, vee head
+//! ```
+//!
+//! Symmetric categories are drawn without arrowheads. Output is sorted, so
+//! the same space always renders the same file.
+
+use crate::aggregates::{EdgeConcept, EdgeState, HyperEdgeConcept, HyperEdgeState, RelationshipSpace};
+use crate::graph::EgoNetwork;
+use crate::value_objects::{EntityRef, RelationshipCategory};
+use std::collections::BTreeSet;
+
+/// Render a whole space as DOT
+pub fn export_dot(space: &RelationshipSpace) -> String {
+    let edges: Vec<&EdgeConcept> = space.edges.values().collect();
+    let hyperedges: Vec<&HyperEdgeConcept> = space.hyperedges.values().collect();
+    render(&space.name, &edges, &hyperedges, None)
+}
+
+/// Render an ego network as DOT, highlighting its center
+pub fn export_ego_dot(ego: &EgoNetwork) -> String {
+    let edges: Vec<&EdgeConcept> = ego.edges.iter().collect();
+    let hyperedges: Vec<&HyperEdgeConcept> = ego.hyperedges.iter().collect();
+    let mut entities: Vec<&EntityRef> = ego.nodes.iter().map(|n| &n.entity).collect();
+    entities.push(&ego.center);
+    render(
+        &format!("ego {} (radius {})", ego.center, ego.radius),
+        &edges,
+        &hyperedges,
+        Some((&ego.center, entities)),
+    )
+}
+
+fn render(
+    name: &str,
+    edges: &[&EdgeConcept],
+    hyperedges: &[&HyperEdgeConcept],
+    ego: Option<(&EntityRef, Vec<&EntityRef>)>,
+) -> String {
+    let mut lines = vec![
+        format!("digraph {} {{", quote(name)),
+        "  node [shape=box, fontname=\"Helvetica\", fontsize=10];".to_string(),
+        "  edge [fontname=\"Helvetica\", fontsize=8];".to_string(),
+    ];
+
+    // Entities: every endpoint and participant, plus isolated ego nodes
+    let mut entities: BTreeSet<String> = BTreeSet::new();
+    for edge in edges {
+        entities.insert(edge.source.key().to_string());
+        entities.insert(edge.target.key().to_string());
+    }
+    for hyperedge in hyperedges {
+        for participant in hyperedge.participants.participants() {
+            entities.insert(participant.entity_ref.key().to_string());
+        }
+    }
+    let center = ego.as_ref().map(|(center, nodes)| {
+        entities.extend(nodes.iter().map(|n| n.key().to_string()));
+        center.key().to_string()
+    });
+    for entity in &entities {
+        if center.as_ref() == Some(entity) {
+            lines.push(format!(
+                "  {} [style=\"filled,bold\", fillcolor=\"lightyellow\", penwidth=2];",
+                quote(entity)
+            ));
+        } else {
+            lines.push(format!("  {};", quote(entity)));
+        }
+    }
+
+    let mut edges = edges.to_vec();
+    edges.sort_by_key(|e| e.id.as_uuid());
+    for edge in edges {
+        let (style, arrowhead) = category_style(&edge.category);
+        lines.push(format!(
+            "  {} -> {} [label={}, style={}, arrowhead={}, dir={}, color=\"{}\", penwidth={:.2}];",
+            quote(&edge.source.key().to_string()),
+            quote(&edge.target.key().to_string()),
+            quote(&edge.name),
+            style,
+            arrowhead,
+            if edge.category.is_symmetric() { "none" } else { "forward" },
+            edge_color(&edge.state),
+            penwidth(edge.quality.strength)
+        ));
+    }
+
+    let mut hyperedges = hyperedges.to_vec();
+    hyperedges.sort_by_key(|h| h.id.as_uuid());
+    for hyperedge in hyperedges {
+        let node = quote(&format!("hyperedge:{}", hyperedge.id.as_uuid()));
+        let (style, _) = category_style(&hyperedge.category);
+        let color = hyperedge_color(&hyperedge.state);
+        lines.push(format!(
+            "  {} [shape=diamond, label={}, color=\"{}\", penwidth={:.2}];",
+            node,
+            quote(&hyperedge.name),
+            color,
+            penwidth(hyperedge.quality.strength)
+        ));
+        let mut participants: Vec<_> = hyperedge.participants.participants().collect();
+        participants.sort_by_key(|p| p.entity_ref.key().to_string());
+        for participant in participants {
+            lines.push(format!(
+                "  {} -> {} [label={}, style={}, dir=none, color=\"{}\"];",
+                node,
+                quote(&participant.entity_ref.key().to_string()),
+                quote(&participant.role.display_name()),
+                style,
+                color
+            ));
+        }
+    }
+
+    lines.push("}".to_string());
+    lines.join("\n") + "\n"
+}
+
+/// Line style and arrowhead of a category's family
+fn category_style(category: &RelationshipCategory) -> (&'static str, &'static str) {
+    use RelationshipCategory::*;
+    match category {
+        Employment | Membership | Ownership | Management => ("solid", "normal"),
+        Friendship | ProfessionalContact | Mentorship => ("dashed", "normal"),
+        PartOf | Contains | DependsOn | Implements => ("solid", "diamond"),
+        Precedes | Triggers => ("dotted", "normal"),
+        References | DerivesFrom => ("dashed", "empty"),
+        Custom(_) => ("solid", "vee"),
+    }
+}
+
+fn edge_color(state: &EdgeState) -> &'static str {
+    match state {
+        EdgeState::Proposed => "gray50",
+        EdgeState::PendingConsent => "steelblue",
+        EdgeState::Active => "forestgreen",
+        EdgeState::Suspended => "orange",
+        EdgeState::Terminated => "red3",
+        EdgeState::Rejected => "firebrick4",
+        EdgeState::Archived => "gray80",
+    }
+}
+
+fn hyperedge_color(state: &HyperEdgeState) -> &'static str {
+    match state {
+        HyperEdgeState::Forming => "gray50",
+        HyperEdgeState::Active => "forestgreen",
+        HyperEdgeState::Restructuring => "steelblue",
+        HyperEdgeState::Dissolved => "red3",
+        HyperEdgeState::Archived => "gray80",
+    }
+}
+
+/// Line width for a strength in [0, 1]
+fn penwidth(strength: f64) -> f64 {
+    1.0 + 4.0 * strength.clamp(0.0, 1.0)
+}
+
+/// DOT double-quoted string
+fn quote(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ego_network, EgoFilter};
+    use crate::quality::RelationshipQuality;
+    use crate::value_objects::ParticipantRole;
+    use cim_domain_spaces::TopologicalSpaceId;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_dot_export_styles_space_and_ego() {
+        let alice = EntityRef::person(Uuid::now_v7());
+        let bob = EntityRef::person(Uuid::now_v7());
+        let acme = EntityRef::organization(Uuid::now_v7());
+        let mut space = RelationshipSpace::new("Team \"A\"", TopologicalSpaceId::new());
+        let mut job = EdgeConcept::new("Works at", alice.clone(), acme.clone(), RelationshipCategory::Employment)
+            .with_quality(RelationshipQuality::default_employment());
+        job.activate().unwrap();
+        space.add_edge(job.clone()).unwrap();
+        space
+            .add_edge(EdgeConcept::new("Friends", alice.clone(), bob.clone(), RelationshipCategory::Friendship))
+            .unwrap();
+        let mut team = HyperEdgeConcept::new("Squad", RelationshipCategory::Custom("squad".to_string()));
+        team.add_participant(alice.clone(), ParticipantRole::Leader, 1.0).unwrap();
+        team.add_participant(bob.clone(), ParticipantRole::Member, 1.0).unwrap();
+        space.add_hyperedge(team);
+
+        let dot = export_dot(&space);
+        assert!(dot.starts_with("digraph \"Team \\\"A\\\"\" {"));
+        assert_eq!(dot, export_dot(&space));
+        let employment = dot.lines().find(|l| l.contains("\"Works at\"")).unwrap();
+        assert!(employment.contains("style=solid") && employment.contains("color=\"forestgreen\""));
+        assert!(employment.contains(&format!("penwidth={:.2}", penwidth(job.quality.strength))));
+        let friends = dot.lines().find(|l| l.contains("\"Friends\"")).unwrap();
+        assert!(friends.contains("style=dashed") && friends.contains("dir=none"));
+        assert!(friends.contains("color=\"gray50\""));
+        assert!(dot.contains("shape=diamond"));
+        assert_eq!(dot.lines().filter(|l| l.contains("label=\"leader\"")).count(), 1);
+
+        // Active relationships only: the proposed friendship and forming
+        // hyperedge drop out, and bob with them
+        let ego = ego_network(&space, &alice, 1, &EgoFilter::default());
+        let dot = export_ego_dot(&ego);
+        assert!(dot.contains(&format!("{} [style=\"filled,bold\"", quote(&alice.key().to_string()))));
+        assert!(dot.contains("\"Works at\""));
+        assert!(!dot.contains(&bob.key().to_string()));
+    }
+}
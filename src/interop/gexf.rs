@@ -0,0 +1,422 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! GEXF
+//!
+//! Exports a RelationshipSpace as a dynamic GEXF 1.3 graph, so Gephi's
+//! timeline can animate how relationships form, change and end:
+//!
+//! ```text
+//! RelationshipSpace                 GEXF
+//!     entity            ------>     <node id="{type}:{uuid}"> (always present)
+//!     EdgeConcept       ------>     directed <edge>, one <spell> for its
+//!                                   validity period
+//!     HyperEdgeConcept  ------>     one undirected <edge> per pair of
+//!                                   participants (clique expansion), the
+//!                                   spell starting when both had joined
+//!       (< 2 participants)  -->     a <node id="hyperedge:{uuid}"> of its
+//!                                   own, joined to its participant if any
+//! quality updates       ------>     dynamic strength / trust / reciprocity
+//!                                   <attvalue>s, one per interval
+//! ```
+//!
+//! Quality over time is rebuilt from the space's events: each
+//! `QualityUpdated` event closes the interval of its old quality and opens
+//! one for the new quality. Without events, the current quality holds for
+//! the whole validity period. Ongoing relationships have open-ended spells.
+//!
+//! Like GraphML, GEXF is an analysis format and is not imported back.
+
+use super::graphml::enum_text;
+use crate::aggregates::{EdgeConcept, HyperEdgeConcept, RelationshipSpace};
+use crate::events::{EdgeEvent, HyperEdgeEvent, RelationshipEvent};
+use crate::quality::RelationshipQuality;
+use crate::value_objects::{RelationshipCategory, RelationshipId, ValidityPeriod};
+use crate::{RelationshipError, RelationshipResult};
+use chrono::{DateTime, Utc};
+use quick_xml::escape::escape;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Write as _;
+
+/// GEXF 1.3 namespace
+pub const GEXF_NAMESPACE: &str = "http://gexf.net/1.3";
+
+/// Static edge attributes: (id, type)
+const STATIC_KEYS: &[(&str, &str)] = &[
+    ("relationship_id", "string"),
+    ("kind", "string"),
+    ("category", "string"),
+    ("state", "string"),
+    ("formality", "string"),
+];
+
+/// Dynamic edge attributes: (id, type)
+const DYNAMIC_KEYS: &[(&str, &str)] = &[("strength", "double"), ("trust", "double"), ("reciprocity", "double")];
+
+/// A quality update: (updated at, old, new)
+type QualityUpdate<'a> = (DateTime<Utc>, &'a RelationshipQuality, &'a RelationshipQuality);
+
+/// A quality holding from `start` until `end` (None = still holding)
+struct QualityInterval<'a> {
+    quality: &'a RelationshipQuality,
+    start: DateTime<Utc>,
+    end: Option<DateTime<Utc>>,
+}
+
+/// Write a space as a dynamic GEXF document
+///
+/// `events` is the space's event log; only quality updates are read, so
+/// the full log can be passed, or an empty slice for a timeline of
+/// validity periods alone.
+pub fn export_gexf(space: &RelationshipSpace, events: &[RelationshipEvent]) -> RelationshipResult<String> {
+    let mut out = String::new();
+    write_gexf(&mut out, space, events).map_err(|e| RelationshipError::SerializationError(e.to_string()))?;
+    Ok(out)
+}
+
+fn write_gexf(out: &mut String, space: &RelationshipSpace, events: &[RelationshipEvent]) -> std::fmt::Result {
+    let updates = quality_updates(events);
+
+    writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(out, r#"<gexf xmlns="{}" version="1.3">"#, GEXF_NAMESPACE)?;
+    writeln!(out, "  <meta>")?;
+    writeln!(out, "    <creator>cim-domain-relationship</creator>")?;
+    writeln!(out, "    <description>{}</description>", escape(&space.name))?;
+    writeln!(out, "  </meta>")?;
+    writeln!(
+        out,
+        r#"  <graph mode="dynamic" defaultedgetype="directed" timeformat="dateTime" timerepresentation="interval">"#
+    )?;
+    for (mode, keys) in [("static", STATIC_KEYS), ("dynamic", DYNAMIC_KEYS)] {
+        writeln!(out, r#"    <attributes class="edge" mode="{}">"#, mode)?;
+        for (id, kind) in keys {
+            writeln!(out, r#"      <attribute id="{id}" title="{id}" type="{kind}"/>"#)?;
+        }
+        writeln!(out, "    </attributes>")?;
+    }
+
+    let mut entities: BTreeSet<String> = BTreeSet::new();
+    for edge in space.edges.values() {
+        entities.insert(edge.source.key().to_string());
+        entities.insert(edge.target.key().to_string());
+    }
+    for hyperedge in space.hyperedges.values() {
+        for participant in hyperedge.participants.participants() {
+            entities.insert(participant.entity_ref.key().to_string());
+        }
+    }
+    let mut hyperedges: Vec<&HyperEdgeConcept> = space.hyperedges.values().collect();
+    hyperedges.sort_by_key(|h| h.id.as_uuid());
+    // Too small for a pair: the hyperedge stands as a node
+    let degenerate: Vec<&HyperEdgeConcept> = hyperedges
+        .iter()
+        .copied()
+        .filter(|h| h.participants.participant_count() < 2)
+        .collect();
+
+    writeln!(out, "    <nodes>")?;
+    for id in &entities {
+        writeln!(out, r#"      <node id="{0}" label="{0}"/>"#, escape(id))?;
+    }
+    for hyperedge in &degenerate {
+        writeln!(
+            out,
+            r#"      <node id="{}" label="{}"/>"#,
+            hyperedge_node(hyperedge),
+            escape(&hyperedge.name)
+        )?;
+    }
+    writeln!(out, "    </nodes>")?;
+
+    writeln!(out, "    <edges>")?;
+    let mut edges: Vec<&EdgeConcept> = space.edges.values().collect();
+    edges.sort_by_key(|e| e.id.as_uuid());
+    for edge in edges {
+        writeln!(
+            out,
+            r#"      <edge id="{}" source="{}" target="{}" type="directed" label="{}" weight="{}">"#,
+            edge.id.as_uuid(),
+            escape(edge.source.key().to_string()),
+            escape(edge.target.key().to_string()),
+            escape(&edge.name),
+            edge.quality.strength
+        )?;
+        let intervals = timeline(&edge.validity, &edge.quality, updates.get(&edge.id));
+        write_relationship(
+            out,
+            edge.id,
+            "edge",
+            &edge.category,
+            &enum_text(&edge.state),
+            &edge.quality,
+            &intervals,
+        )?;
+        write_spell(out, edge.validity.starts_at, edge.validity.ends_at)?;
+        writeln!(out, "      </edge>")?;
+    }
+
+    for hyperedge in hyperedges {
+        let intervals = timeline(&hyperedge.validity, &hyperedge.quality, updates.get(&hyperedge.id));
+        let mut participants: Vec<_> = hyperedge.participants.participants().collect();
+        participants.sort_by_key(|p| p.entity_ref.key().to_string());
+        if let [sole] = participants.as_slice() {
+            writeln!(
+                out,
+                r#"      <edge id="{}/0" source="{}" target="{}" type="undirected" label="{}" weight="{}">"#,
+                hyperedge.id.as_uuid(),
+                hyperedge_node(hyperedge),
+                escape(sole.entity_ref.key().to_string()),
+                escape(&hyperedge.name),
+                hyperedge.quality.strength
+            )?;
+            write_relationship(
+                out,
+                hyperedge.id,
+                "hyperedge",
+                &hyperedge.category,
+                &enum_text(&hyperedge.state),
+                &hyperedge.quality,
+                &intervals,
+            )?;
+            write_spell(out, spell_start(&hyperedge.validity, sole.joined_at), hyperedge.validity.ends_at)?;
+            writeln!(out, "      </edge>")?;
+        }
+        for (i, a) in participants.iter().enumerate() {
+            for (j, b) in participants.iter().enumerate().skip(i + 1) {
+                writeln!(
+                    out,
+                    r#"      <edge id="{}/{}-{}" source="{}" target="{}" type="undirected" label="{}" weight="{}">"#,
+                    hyperedge.id.as_uuid(),
+                    i,
+                    j,
+                    escape(a.entity_ref.key().to_string()),
+                    escape(b.entity_ref.key().to_string()),
+                    escape(&hyperedge.name),
+                    hyperedge.quality.strength
+                )?;
+                write_relationship(
+                    out,
+                    hyperedge.id,
+                    "hyperedge",
+                    &hyperedge.category,
+                    &enum_text(&hyperedge.state),
+                    &hyperedge.quality,
+                    &intervals,
+                )?;
+                let start = spell_start(&hyperedge.validity, a.joined_at.max(b.joined_at));
+                write_spell(out, start, hyperedge.validity.ends_at)?;
+                writeln!(out, "      </edge>")?;
+            }
+        }
+    }
+    writeln!(out, "    </edges>")?;
+
+    writeln!(out, "  </graph>")?;
+    writeln!(out, "</gexf>")
+}
+
+/// Node id of a hyperedge drawn as a node
+fn hyperedge_node(hyperedge: &HyperEdgeConcept) -> String {
+    format!("hyperedge:{}", hyperedge.id.as_uuid())
+}
+
+/// Start of a participant pair's spell: once both joined, within the validity period
+fn spell_start(validity: &ValidityPeriod, joined_at: DateTime<Utc>) -> DateTime<Utc> {
+    let start = validity.starts_at.max(joined_at);
+    validity.ends_at.map_or(start, |end| start.min(end))
+}
+
+fn write_relationship(
+    out: &mut String,
+    id: RelationshipId,
+    kind: &str,
+    category: &RelationshipCategory,
+    state: &str,
+    quality: &RelationshipQuality,
+    intervals: &[QualityInterval],
+) -> std::fmt::Result {
+    writeln!(out, "        <attvalues>")?;
+    write_attvalue(out, "relationship_id", &id.as_uuid().to_string(), None)?;
+    write_attvalue(out, "kind", kind, None)?;
+    write_attvalue(out, "category", &enum_text(category), None)?;
+    write_attvalue(out, "state", state, None)?;
+    write_attvalue(out, "formality", &enum_text(&quality.formality), None)?;
+    for interval in intervals {
+        let span = Some((interval.start, interval.end));
+        write_attvalue(out, "strength", &interval.quality.strength.to_string(), span)?;
+        write_attvalue(out, "trust", &interval.quality.trust.to_string(), span)?;
+        write_attvalue(out, "reciprocity", &interval.quality.reciprocity.to_string(), span)?;
+    }
+    writeln!(out, "        </attvalues>")
+}
+
+fn write_attvalue(
+    out: &mut String,
+    key: &str,
+    value: &str,
+    span: Option<(DateTime<Utc>, Option<DateTime<Utc>>)>,
+) -> std::fmt::Result {
+    write!(out, r#"          <attvalue for="{}" value="{}""#, key, escape(value))?;
+    if let Some((start, end)) = span {
+        write!(out, r#" start="{}""#, start.to_rfc3339())?;
+        if let Some(end) = end {
+            write!(out, r#" end="{}""#, end.to_rfc3339())?;
+        }
+    }
+    writeln!(out, "/>")
+}
+
+fn write_spell(out: &mut String, start: DateTime<Utc>, end: Option<DateTime<Utc>>) -> std::fmt::Result {
+    writeln!(out, "        <spells>")?;
+    write!(out, r#"          <spell start="{}""#, start.to_rfc3339())?;
+    if let Some(end) = end {
+        write!(out, r#" end="{}""#, end.to_rfc3339())?;
+    }
+    writeln!(out, "/>")?;
+    writeln!(out, "        </spells>")
+}
+
+/// Quality updates per relationship, in time order
+fn quality_updates(events: &[RelationshipEvent]) -> HashMap<RelationshipId, Vec<QualityUpdate<'_>>> {
+    let mut updates: HashMap<RelationshipId, Vec<_>> = HashMap::new();
+    for event in events {
+        let (id, update) = match event {
+            RelationshipEvent::Edge(EdgeEvent::QualityUpdated(e)) => {
+                (e.edge_id, (e.updated_at, &e.old_quality, &e.new_quality))
+            }
+            RelationshipEvent::HyperEdge(HyperEdgeEvent::HyperEdgeQualityUpdated(e)) => {
+                (e.hyperedge_id, (e.updated_at, &e.old_quality, &e.new_quality))
+            }
+            _ => continue,
+        };
+        updates.entry(id).or_default().push(update);
+    }
+    for history in updates.values_mut() {
+        history.sort_by_key(|(at, _, _)| *at);
+    }
+    updates
+}
+
+/// Quality intervals covering a validity period
+///
+/// Updates outside the period are clamped to it; with no updates the
+/// current quality covers the whole period.
+fn timeline<'a>(
+    validity: &ValidityPeriod,
+    current: &'a RelationshipQuality,
+    updates: Option<&Vec<QualityUpdate<'a>>>,
+) -> Vec<QualityInterval<'a>> {
+    let clamp = |at: DateTime<Utc>| {
+        let at = at.max(validity.starts_at);
+        validity.ends_at.map_or(at, |end| at.min(end))
+    };
+    let updates = updates.map(Vec::as_slice).unwrap_or_default();
+    let mut intervals = Vec::with_capacity(updates.len() + 1);
+    let mut start = validity.starts_at;
+    let mut quality = updates.first().map_or(current, |(_, old, _)| *old);
+    for (at, _, new) in updates {
+        let at = clamp(*at);
+        if at > start {
+            intervals.push(QualityInterval { quality, start, end: Some(at) });
+            start = at;
+        }
+        quality = *new;
+    }
+    intervals.push(QualityInterval {
+        quality,
+        start,
+        end: validity.ends_at,
+    });
+    intervals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{EdgeCommand, RelationshipCommand, UpdateEdgeQuality};
+    use crate::value_objects::{EntityRef, ParticipantRole};
+    use cim_domain::MessageIdentity;
+    use cim_domain_spaces::TopologicalSpaceId;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_gexf_export_with_quality_timeline() {
+        let mut space = RelationshipSpace::new("Timeline <A&B>", TopologicalSpaceId::new());
+        let alice = EntityRef::person(Uuid::now_v7());
+        let bob = EntityRef::person(Uuid::now_v7());
+        let acme = EntityRef::organization(Uuid::now_v7());
+        let edge = EdgeConcept::new("Works at", alice.clone(), acme.clone(), RelationshipCategory::Employment);
+        let edge_id = edge.id;
+        space.add_edge(edge).unwrap();
+        let mut team = HyperEdgeConcept::new("Squad", RelationshipCategory::Custom("squad".to_string()));
+        team.add_participant(alice.clone(), ParticipantRole::Leader, 1.0).unwrap();
+        team.add_participant(bob.clone(), ParticipantRole::Member, 1.0).unwrap();
+        space.add_hyperedge(team);
+
+        let mut quality = space.get_edge(&edge_id).unwrap().quality.clone();
+        let old_strength = quality.strength;
+        quality.strength = 0.9;
+        let events = space
+            .handle_command(RelationshipCommand::Edge(EdgeCommand::UpdateEdgeQuality(UpdateEdgeQuality {
+                identity: MessageIdentity::new_root(),
+                edge_id,
+                new_quality: quality,
+                reason: "promotion".to_string(),
+            })))
+            .unwrap();
+
+        let gexf = export_gexf(&space, &events).unwrap();
+        assert!(gexf.contains(r#"<graph mode="dynamic""#));
+        assert!(gexf.contains("<description>Timeline &lt;A&amp;B&gt;</description>"));
+        assert_eq!(gexf.matches("<node ").count(), 3);
+        assert_eq!(gexf.matches("<spell ").count(), 2);
+        assert!(gexf.contains(r#"type="undirected" label="Squad""#));
+
+        // The edge's open interval carries the updated strength
+        let edge_block: String = gexf
+            .lines()
+            .skip_while(|l| !l.contains(&format!(r#"<edge id="{}""#, edge_id.as_uuid())))
+            .take_while(|l| !l.contains("</edge>"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let strengths: Vec<&str> = edge_block.lines().filter(|l| l.contains(r#"for="strength""#)).collect();
+        let last = strengths.last().unwrap();
+        assert!(last.contains(r#"value="0.9""#) && last.contains("start=") && !last.contains("end="));
+        assert!(strengths.len() == 1 || strengths[0].contains(&format!(r#"value="{}""#, old_strength)));
+
+        // Without events the current quality spans the validity period
+        let gexf = export_gexf(&space, &[]).unwrap();
+        assert_eq!(gexf.matches(r#"for="strength""#).count(), 2);
+    }
+
+    #[test]
+    fn test_gexf_small_hyperedges_and_late_joiners() {
+        let mut space = RelationshipSpace::new("Small", TopologicalSpaceId::new());
+        let (alice, bob) = (EntityRef::person(Uuid::now_v7()), EntityRef::person(Uuid::now_v7()));
+        let mut solo = HyperEdgeConcept::new("Solo", RelationshipCategory::Membership);
+        solo.add_participant(alice.clone(), ParticipantRole::Leader, 1.0).unwrap();
+        let empty = HyperEdgeConcept::new("Empty", RelationshipCategory::Membership);
+        // Bob joined after the team's validity ended
+        let ended = Utc::now() - chrono::Duration::days(30);
+        let mut team = HyperEdgeConcept::new("Past", RelationshipCategory::Membership);
+        team.add_participant(alice.clone(), ParticipantRole::Member, 1.0).unwrap();
+        team.add_participant(bob, ParticipantRole::Member, 1.0).unwrap();
+        team.validity = ValidityPeriod::fixed_term(ended - chrono::Duration::days(365), ended);
+        for hyperedge in [solo.clone(), empty.clone(), team] {
+            space.add_hyperedge(hyperedge);
+        }
+
+        let gexf = export_gexf(&space, &[]).unwrap();
+        assert!(gexf.contains(&format!(r#"<node id="hyperedge:{}" label="Solo"/>"#, solo.id.as_uuid())));
+        assert!(gexf.contains(&format!(r#"<node id="hyperedge:{}" label="Empty"/>"#, empty.id.as_uuid())));
+        assert!(gexf.contains(&format!(
+            r#"source="hyperedge:{}" target="{}""#,
+            solo.id.as_uuid(),
+            alice.key()
+        )));
+        assert_eq!(gexf.matches("<edge ").count(), 2);
+        let past = format!(r#"<spell start="{0}" end="{0}"/>"#, ended.to_rfc3339());
+        assert!(gexf.contains(&past));
+    }
+}
@@ -0,0 +1,349 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Graph Functor
+//!
+//! Maps a RelationshipSpace to the node/edge model used by graph tooling
+//! and back, without loss.
+//!
+//! ## Mapping
+//!
+//! ```text
+//! RelationshipSpace                 GraphModel
+//!     entity            ------>     node   (id "{type}:{uuid}")
+//!     EdgeConcept       ------>     edge   source node -> target node
+//!     HyperEdgeConcept  ------>     node   (id "hyperedge:{uuid}")
+//!                                   + one "participates" edge per participant
+//!     space settings    ------>     graph attributes
+//! ```
+//!
+//! Relationship edges and hyperedge nodes carry every field of their
+//! concept as attributes (id, category, quality, state, validity, ...),
+//! so `from_graph(to_graph(space))` reproduces the space. Participation
+//! edges are derived and ignored on the way back.
+//!
+//! `to_graph_labelled` additionally gives entity nodes the `name` and
+//! `status` known to an `EntityDisplayCache`; these are for display only
+//! and are ignored on the way back.
+
+use crate::aggregates::{EdgeConcept, HyperEdgeConcept, RelationshipSpace, RelationshipTemplate};
+use crate::algebra::CompositionRegistry;
+use crate::invariants::{PolicySet, RoleSchemaRegistry};
+use crate::projections::EntityDisplayCache;
+use crate::quality::{DurationModel, QualitySchema, QualityWeightRegistry};
+use crate::value_objects::{EntityRef, RelationshipCategory, ReproposalPolicy};
+use crate::{RelationshipError, RelationshipResult};
+use chrono::{DateTime, Utc};
+use cim_domain_spaces::{ConceptualSpaceId, TopologicalSpaceId};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Label of nodes standing for entities
+pub const ENTITY_LABEL: &str = "entity";
+/// Label of nodes standing for hyperedges
+pub const HYPEREDGE_LABEL: &str = "hyperedge";
+/// Label of edges from a participant to its hyperedge node
+pub const PARTICIPATES_LABEL: &str = "participates";
+
+/// Node of a graph model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraphModelNode {
+    /// Node id
+    pub id: String,
+    /// Node label (`entity` or `hyperedge`)
+    pub label: String,
+    /// Attributes
+    pub attributes: Map<String, Value>,
+}
+
+/// Directed edge of a graph model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraphModelEdge {
+    /// Edge id
+    pub id: String,
+    /// Source node id
+    pub source: String,
+    /// Target node id
+    pub target: String,
+    /// Edge label (relationship category, or `participates`)
+    pub label: String,
+    /// Edge weight (relationship strength, or participation weight)
+    pub weight: f64,
+    /// Attributes
+    pub attributes: Map<String, Value>,
+}
+
+/// Node/edge graph with attributes
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct GraphModel {
+    /// Graph-level attributes
+    pub attributes: Map<String, Value>,
+    /// Nodes, ordered by id
+    pub nodes: Vec<GraphModelNode>,
+    /// Edges, ordered by id
+    pub edges: Vec<GraphModelEdge>,
+}
+
+/// Space fields carried as graph attributes
+///
+/// The similarity index and tessellation are derived and not carried.
+#[derive(Serialize, Deserialize)]
+struct SpaceAttributes {
+    id: ConceptualSpaceId,
+    name: String,
+    topology_id: TopologicalSpaceId,
+    quality_schema: QualitySchema,
+    duration_model: DurationModel,
+    weight_registry: QualityWeightRegistry,
+    composition_rules: CompositionRegistry,
+    #[serde(default)]
+    reflexive_categories: HashSet<RelationshipCategory>,
+    #[serde(default)]
+    consent_categories: HashSet<RelationshipCategory>,
+    #[serde(default)]
+    restructuring_categories: HashSet<RelationshipCategory>,
+    #[serde(default)]
+    reproposal_policy: ReproposalPolicy,
+    #[serde(default)]
+    role_schemas: RoleSchemaRegistry,
+    #[serde(default)]
+    policies: PolicySet,
+    #[serde(default)]
+    templates: HashMap<String, RelationshipTemplate>,
+    version: u64,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+fn to_attributes<T: Serialize>(value: &T) -> RelationshipResult<Map<String, Value>> {
+    match serde_json::to_value(value) {
+        Ok(Value::Object(map)) => Ok(map),
+        Ok(_) => Err(RelationshipError::SerializationError(
+            "expected an object".to_string(),
+        )),
+        Err(e) => Err(RelationshipError::SerializationError(e.to_string())),
+    }
+}
+
+fn from_attributes<T: for<'de> Deserialize<'de>>(attributes: &Map<String, Value>) -> RelationshipResult<T> {
+    serde_json::from_value(Value::Object(attributes.clone()))
+        .map_err(|e| RelationshipError::InvalidDocument(e.to_string()))
+}
+
+fn entity_node_id(entity: &EntityRef) -> String {
+    entity.key().to_string()
+}
+
+fn hyperedge_node_id(hyperedge: &HyperEdgeConcept) -> String {
+    format!("{HYPEREDGE_LABEL}:{}", hyperedge.id)
+}
+
+/// Map a space to a graph model
+pub fn to_graph(space: &RelationshipSpace) -> RelationshipResult<GraphModel> {
+    build_graph(space, None)
+}
+
+/// Map a space to a graph model, labelling entity nodes from a display
+/// cache
+pub fn to_graph_labelled(space: &RelationshipSpace, labels: &EntityDisplayCache) -> RelationshipResult<GraphModel> {
+    build_graph(space, Some(labels))
+}
+
+fn build_graph(space: &RelationshipSpace, labels: Option<&EntityDisplayCache>) -> RelationshipResult<GraphModel> {
+    let attributes = to_attributes(&SpaceAttributes {
+        id: space.id,
+        name: space.name.clone(),
+        topology_id: space.topology_id,
+        quality_schema: space.quality_schema.clone(),
+        duration_model: space.duration_model.clone(),
+        weight_registry: space.weight_registry.clone(),
+        composition_rules: space.composition_rules.clone(),
+        reflexive_categories: space.reflexive_categories.clone(),
+        consent_categories: space.consent_categories.clone(),
+        restructuring_categories: space.restructuring_categories.clone(),
+        reproposal_policy: space.reproposal_policy,
+        role_schemas: space.role_schemas.clone(),
+        policies: space.policies.clone(),
+        templates: space.templates.clone(),
+        version: space.version,
+        created_at: space.created_at,
+        updated_at: space.updated_at,
+    })?;
+
+    let mut nodes: BTreeMap<String, GraphModelNode> = BTreeMap::new();
+    let mut entity_node = |entity: &EntityRef| {
+        let id = entity_node_id(entity);
+        nodes.entry(id.clone()).or_insert_with(|| {
+            let mut attributes = Map::new();
+            attributes.insert("entity_type".to_string(), serde_json::to_value(&entity.entity_type).unwrap_or_default());
+            attributes.insert("entity_id".to_string(), Value::String(entity.entity_id.to_string()));
+            if let Some(display) = labels.and_then(|l| l.get(entity)) {
+                if let Some(name) = &display.name {
+                    attributes.insert("name".to_string(), Value::String(name.clone()));
+                }
+                attributes.insert("status".to_string(), Value::String(display.status.display_name()));
+            }
+            GraphModelNode {
+                id: id.clone(),
+                label: ENTITY_LABEL.to_string(),
+                attributes,
+            }
+        });
+        id
+    };
+
+    let mut edges = Vec::new();
+    for edge in space.edges.values() {
+        edges.push(GraphModelEdge {
+            id: edge.id.to_string(),
+            source: entity_node(&edge.source),
+            target: entity_node(&edge.target),
+            label: edge.category.display_name(),
+            weight: edge.quality.strength,
+            attributes: to_attributes(edge)?,
+        });
+    }
+
+    let mut hyperedge_nodes = Vec::new();
+    for hyperedge in space.hyperedges.values() {
+        let node_id = hyperedge_node_id(hyperedge);
+        for participant in hyperedge.participants.participants() {
+            let source = entity_node(&participant.entity_ref);
+            let mut attributes = Map::new();
+            attributes.insert("role".to_string(), Value::String(participant.role.display_name()));
+            edges.push(GraphModelEdge {
+                id: format!("{node_id}/{source}"),
+                source,
+                target: node_id.clone(),
+                label: PARTICIPATES_LABEL.to_string(),
+                weight: participant.weight,
+                attributes,
+            });
+        }
+        hyperedge_nodes.push(GraphModelNode {
+            id: node_id,
+            label: HYPEREDGE_LABEL.to_string(),
+            attributes: to_attributes(hyperedge)?,
+        });
+    }
+
+    let mut nodes: Vec<GraphModelNode> = nodes.into_values().chain(hyperedge_nodes).collect();
+    nodes.sort_by(|a, b| a.id.cmp(&b.id));
+    edges.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(GraphModel {
+        attributes,
+        nodes,
+        edges,
+    })
+}
+
+/// Rebuild a space from a graph model produced by `to_graph`
+///
+/// Relationship edges must run between the nodes of their endpoints.
+pub fn from_graph(graph: &GraphModel) -> RelationshipResult<RelationshipSpace> {
+    let attributes: SpaceAttributes = from_attributes(&graph.attributes)?;
+    let mut space = RelationshipSpace::new(attributes.name, attributes.topology_id)
+        .with_quality_schema(attributes.quality_schema)
+        .with_duration_model(attributes.duration_model)
+        .with_weight_registry(attributes.weight_registry)
+        .with_composition_rules(attributes.composition_rules);
+    space.reflexive_categories = attributes.reflexive_categories;
+    space.consent_categories = attributes.consent_categories;
+    space.restructuring_categories = attributes.restructuring_categories;
+    space.reproposal_policy = attributes.reproposal_policy;
+    space.role_schemas = attributes.role_schemas;
+    space.policies = attributes.policies;
+    space.templates = attributes.templates;
+    space.id = attributes.id;
+    space.version = attributes.version;
+    space.created_at = attributes.created_at;
+    space.updated_at = attributes.updated_at;
+
+    let mut edges = HashMap::new();
+    for record in graph.edges.iter().filter(|e| e.label != PARTICIPATES_LABEL) {
+        let edge: EdgeConcept = from_attributes(&record.attributes)?;
+        if record.source != entity_node_id(&edge.source) || record.target != entity_node_id(&edge.target) {
+            return Err(RelationshipError::InvalidDocument(format!(
+                "edge {} does not run between its endpoints",
+                record.id
+            )));
+        }
+        edges.insert(edge.id, edge);
+    }
+
+    let mut hyperedges = HashMap::new();
+    for node in graph.nodes.iter().filter(|n| n.label == HYPEREDGE_LABEL) {
+        let hyperedge: HyperEdgeConcept = from_attributes(&node.attributes)?;
+        hyperedges.insert(hyperedge.id, hyperedge);
+    }
+
+    space.edges = edges;
+    space.hyperedges = hyperedges;
+    space.rebuild_edge_index();
+    Ok(space)
+}
+
+impl RelationshipSpace {
+    /// Map this space to a graph model
+    pub fn to_graph(&self) -> RelationshipResult<GraphModel> {
+        to_graph(self)
+    }
+
+    /// Rebuild a space from a graph model
+    pub fn from_graph(graph: &GraphModel) -> RelationshipResult<Self> {
+        from_graph(graph)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quality::RelationshipQuality;
+    use crate::value_objects::ParticipantRole;
+    use cim_domain_spaces::TopologicalSpaceId;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_graph_round_trip() {
+        let alice = EntityRef::person(Uuid::now_v7());
+        let acme = EntityRef::organization(Uuid::now_v7());
+        let mut space = RelationshipSpace::new("people", TopologicalSpaceId::new());
+        let edge = EdgeConcept::new("Works at", alice.clone(), acme.clone(), RelationshipCategory::Employment)
+            .with_quality(RelationshipQuality::default_employment());
+        space.add_edge(edge.clone()).unwrap();
+        let mut team = HyperEdgeConcept::new("Team", RelationshipCategory::Membership);
+        team.add_participant(alice.clone(), ParticipantRole::Member, 1.0).unwrap();
+        team.add_participant(acme.clone(), ParticipantRole::Leader, 0.5).unwrap();
+        space.add_hyperedge(team.clone());
+
+        let graph = space.to_graph().unwrap();
+        // Two entities and one hyperedge node; one relationship and two
+        // participation edges
+        assert_eq!(graph.nodes.len(), 3);
+        assert_eq!(graph.edges.len(), 3);
+        let record = graph.edges.iter().find(|e| e.id == edge.id.to_string()).unwrap();
+        assert_eq!(record.source, alice.key().to_string());
+        assert_eq!(record.weight, edge.quality.strength);
+        assert!(record.attributes.contains_key("quality"));
+
+        let restored = RelationshipSpace::from_graph(&graph).unwrap();
+        assert_eq!(restored.id, space.id);
+        assert_eq!(restored.version, space.version);
+        assert_eq!(restored.get_edge(&edge.id).unwrap().quality.trust, edge.quality.trust);
+        assert_eq!(
+            restored.get_hyperedge(&team.id).unwrap().participants.participant_count(),
+            2
+        );
+        let again = restored.to_graph().unwrap();
+        assert_eq!((again.nodes, again.edges), (graph.nodes, graph.edges));
+
+        let mut labels = EntityDisplayCache::new();
+        labels.set_name(&acme, "Acme Corp", Utc::now());
+        let labelled = to_graph_labelled(&space, &labels).unwrap();
+        let node = labelled.nodes.iter().find(|n| n.id == acme.key().to_string()).unwrap();
+        assert_eq!(node.attributes["name"], "Acme Corp");
+        assert!(RelationshipSpace::from_graph(&labelled).is_ok());
+    }
+}
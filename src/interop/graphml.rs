@@ -0,0 +1,713 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! GraphML
+//!
+//! Exports a RelationshipSpace as GraphML for analysis in Gephi, yEd and
+//! similar tools, and reads such files back:
+//!
+//! ```text
+//! RelationshipSpace                 GraphML
+//!     entity            ------>     <node id="{type}:{uuid}">
+//!     EdgeConcept       ------>     <edge> source -> target (directed)
+//!     HyperEdgeConcept  ------>     one undirected <edge> per pair of
+//!                                   participants (clique expansion)
+//!       (< 2 participants)  -->     <hyperedge> with an <endpoint> per
+//!                                   participant
+//! ```
+//!
+//! Every edge carries the relationship's id, kind, name, category, state
+//! and quality dimensions as `<data>` attributes, plus a `weight` (the
+//! strength) that layout and metric tools pick up. Clique edges of one
+//! hyperedge share its `relationship_id` and carry the roles and weights
+//! of both participants, so the importer regroups them into the hyperedge.
+//! Hyperedges too small to have a pair are written as native GraphML
+//! hyperedges, which most tools skip but which import back.
+//!
+//! GraphML is an analysis format: evidence, history, properties and space
+//! settings are not exported. Use `GraphModel` or `RelationshipDocument`
+//! for lossless transfer. Edges drawn by hand in a tool (without a
+//! `relationship_id`) are imported as new proposed edges; imported
+//! qualities are checked like those given in commands.
+
+use crate::aggregates::{check_quality, EdgeConcept, HyperEdgeConcept, HyperEdgeState, RelationshipSpace};
+use crate::quality::RelationshipQuality;
+use crate::value_objects::{
+    EntityKey, EntityRef, EntityType, Formality, ParticipantRole, RelationshipCategory, RelationshipId,
+    ValidityPeriod,
+};
+use crate::{RelationshipError, RelationshipResult};
+use chrono::{DateTime, Utc};
+use cim_domain_spaces::TopologicalSpaceId;
+use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event};
+use quick_xml::{Reader, Writer};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::io::{self, Read};
+use uuid::Uuid;
+
+/// GraphML namespace
+pub const GRAPHML_NAMESPACE: &str = "http://graphml.graphdrawing.org/xmlns";
+
+/// `kind` of edges standing for an EdgeConcept
+const KIND_EDGE: &str = "edge";
+/// `kind` of clique edges standing for a HyperEdgeConcept
+const KIND_HYPEREDGE: &str = "hyperedge";
+
+/// Attribute declarations: (for, name, type)
+const KEYS: &[(&str, &str, &str)] = &[
+    ("graph", "name", "string"),
+    ("node", "entity_type", "string"),
+    ("node", "entity_id", "string"),
+    ("node", "label", "string"),
+    ("edge", "relationship_id", "string"),
+    ("edge", "kind", "string"),
+    ("edge", "name", "string"),
+    ("edge", "category", "string"),
+    ("edge", "state", "string"),
+    ("edge", "weight", "double"),
+    ("edge", "strength", "double"),
+    ("edge", "trust", "double"),
+    ("edge", "formality", "string"),
+    ("edge", "reciprocity", "double"),
+    ("edge", "valid_from", "string"),
+    ("edge", "valid_until", "string"),
+    ("edge", "source_role", "string"),
+    ("edge", "target_role", "string"),
+    ("edge", "source_weight", "double"),
+    ("edge", "target_weight", "double"),
+];
+
+// ============================================================================
+// Export
+// ============================================================================
+
+type XmlWriter = Writer<Vec<u8>>;
+
+/// Write a space as a GraphML document
+pub fn export_graphml(space: &RelationshipSpace) -> RelationshipResult<String> {
+    let mut writer = Writer::new_with_indent(Vec::new(), b' ', 2);
+    write_graphml(&mut writer, space).map_err(|e| RelationshipError::SerializationError(e.to_string()))?;
+    String::from_utf8(writer.into_inner()).map_err(|e| RelationshipError::SerializationError(e.to_string()))
+}
+
+fn write_graphml(w: &mut XmlWriter, space: &RelationshipSpace) -> io::Result<()> {
+    w.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+    w.write_event(Event::Start(BytesStart::new("graphml").with_attributes([("xmlns", GRAPHML_NAMESPACE)])))?;
+    for (target, name, kind) in KEYS {
+        w.create_element("key")
+            .with_attributes([("id", *name), ("for", *target), ("attr.name", *name), ("attr.type", *kind)])
+            .write_empty()?;
+    }
+    let graph_id = space.id.to_string();
+    w.write_event(Event::Start(
+        BytesStart::new("graph").with_attributes([("id", graph_id.as_str()), ("edgedefault", "directed")]),
+    ))?;
+    write_data(w, "name", &space.name)?;
+
+    let mut entities: BTreeMap<String, &EntityRef> = BTreeMap::new();
+    for edge in space.edges.values() {
+        entities.insert(edge.source.key().to_string(), &edge.source);
+        entities.insert(edge.target.key().to_string(), &edge.target);
+    }
+    for hyperedge in space.hyperedges.values() {
+        for participant in hyperedge.participants.participants() {
+            entities.insert(participant.entity_ref.key().to_string(), &participant.entity_ref);
+        }
+    }
+    for (id, entity) in &entities {
+        let entity_type = match &entity.entity_type {
+            EntityType::Custom(name) => format!("custom/{}", name),
+            other => other.nats_subject_prefix().to_string(),
+        };
+        w.create_element("node")
+            .with_attribute(("id", id.as_str()))
+            .write_inner_content(|w| {
+                write_data(w, "entity_type", &entity_type)?;
+                write_data(w, "entity_id", &entity.entity_id.to_string())?;
+                write_data(w, "label", id)
+            })?;
+    }
+
+    let mut edges: Vec<&EdgeConcept> = space.edges.values().collect();
+    edges.sort_by_key(|e| e.id.as_uuid());
+    for edge in edges {
+        let id = edge.id.as_uuid().to_string();
+        let (source, target) = (edge.source.key().to_string(), edge.target.key().to_string());
+        w.create_element("edge")
+            .with_attributes([
+                ("id", id.as_str()),
+                ("source", source.as_str()),
+                ("target", target.as_str()),
+                ("directed", "true"),
+            ])
+            .write_inner_content(|w| {
+                write_relationship(
+                    w,
+                    edge.id,
+                    KIND_EDGE,
+                    &edge.name,
+                    &edge.category,
+                    &enum_text(&edge.state),
+                    &edge.quality,
+                )
+            })?;
+    }
+
+    let mut hyperedges: Vec<&HyperEdgeConcept> = space.hyperedges.values().collect();
+    hyperedges.sort_by_key(|h| h.id.as_uuid());
+    for hyperedge in hyperedges {
+        let mut participants: Vec<_> = hyperedge.participants.participants().collect();
+        participants.sort_by_key(|p| p.entity_ref.key().to_string());
+        let relationship = |w: &mut XmlWriter| {
+            write_relationship(
+                w,
+                hyperedge.id,
+                KIND_HYPEREDGE,
+                &hyperedge.name,
+                &hyperedge.category,
+                &enum_text(&hyperedge.state),
+                &hyperedge.quality,
+            )
+        };
+
+        if participants.len() < 2 {
+            let id = hyperedge.id.as_uuid().to_string();
+            w.create_element("hyperedge")
+                .with_attribute(("id", id.as_str()))
+                .write_inner_content(|w| {
+                    relationship(w)?;
+                    for p in &participants {
+                        write_data(w, "source_role", &enum_text(&p.role))?;
+                        write_data(w, "source_weight", &p.weight.to_string())?;
+                        let node = p.entity_ref.key().to_string();
+                        w.create_element("endpoint")
+                            .with_attribute(("node", node.as_str()))
+                            .write_empty()?;
+                    }
+                    Ok(())
+                })?;
+            continue;
+        }
+
+        for (i, a) in participants.iter().enumerate() {
+            for (j, b) in participants.iter().enumerate().skip(i + 1) {
+                let id = format!("{}/{}-{}", hyperedge.id.as_uuid(), i, j);
+                let (source, target) = (a.entity_ref.key().to_string(), b.entity_ref.key().to_string());
+                w.create_element("edge")
+                    .with_attributes([
+                        ("id", id.as_str()),
+                        ("source", source.as_str()),
+                        ("target", target.as_str()),
+                        ("directed", "false"),
+                    ])
+                    .write_inner_content(|w| {
+                        relationship(w)?;
+                        write_data(w, "source_role", &enum_text(&a.role))?;
+                        write_data(w, "target_role", &enum_text(&b.role))?;
+                        write_data(w, "source_weight", &a.weight.to_string())?;
+                        write_data(w, "target_weight", &b.weight.to_string())
+                    })?;
+            }
+        }
+    }
+
+    w.write_event(Event::End(BytesEnd::new("graph")))?;
+    w.write_event(Event::End(BytesEnd::new("graphml")))
+}
+
+fn write_relationship(
+    w: &mut XmlWriter,
+    id: RelationshipId,
+    kind: &str,
+    name: &str,
+    category: &RelationshipCategory,
+    state: &str,
+    quality: &RelationshipQuality,
+) -> io::Result<()> {
+    write_data(w, "relationship_id", &id.as_uuid().to_string())?;
+    write_data(w, "kind", kind)?;
+    write_data(w, "name", name)?;
+    write_data(w, "category", &enum_text(category))?;
+    write_data(w, "state", state)?;
+    write_data(w, "weight", &quality.strength.to_string())?;
+    write_data(w, "strength", &quality.strength.to_string())?;
+    write_data(w, "trust", &quality.trust.to_string())?;
+    write_data(w, "formality", &enum_text(&quality.formality))?;
+    write_data(w, "reciprocity", &quality.reciprocity.to_string())?;
+    write_data(w, "valid_from", &quality.duration.starts_at.to_rfc3339())?;
+    if let Some(ends_at) = quality.duration.ends_at {
+        write_data(w, "valid_until", &ends_at.to_rfc3339())?;
+    }
+    Ok(())
+}
+
+fn write_data(w: &mut XmlWriter, key: &str, value: &str) -> io::Result<()> {
+    w.create_element("data")
+        .with_attribute(("key", key))
+        .write_text_content(BytesText::new(value))?;
+    Ok(())
+}
+
+/// Variant name of a unit variant, or the name inside `Custom`
+pub(super) fn enum_text<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(name)) => name,
+        Ok(serde_json::Value::Object(map)) => map
+            .get("Custom")
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+            .unwrap_or_default(),
+        _ => String::new(),
+    }
+}
+
+/// Inverse of `enum_text`: unknown names become `custom`
+fn enum_parse<T: DeserializeOwned>(text: &str, custom: impl FnOnce(String) -> T) -> T {
+    serde_json::from_value(serde_json::Value::String(text.to_string())).unwrap_or_else(|_| custom(text.to_string()))
+}
+
+// ============================================================================
+// Import
+// ============================================================================
+
+/// Read a space from a GraphML document
+///
+/// Node ids must be entity keys (`{type}:{uuid}`), as `export_graphml`
+/// writes them. Attributes are matched by their declared `attr.name`, so
+/// files re-saved by other tools (which renumber keys) still import.
+pub fn import_graphml(mut reader: impl Read) -> RelationshipResult<RelationshipSpace> {
+    let mut xml = String::new();
+    reader
+        .read_to_string(&mut xml)
+        .map_err(|e| invalid(format!("unreadable GraphML: {}", e)))?;
+    let document = parse_graphml(&xml)?;
+
+    let name = document
+        .graph
+        .data
+        .get("name")
+        .or(document.graph.id.as_ref())
+        .cloned()
+        .unwrap_or_else(|| "graphml".to_string());
+    let mut space = RelationshipSpace::new(name, TopologicalSpaceId::new());
+
+    let mut cliques: Vec<(RelationshipId, Vec<&GraphElement>)> = Vec::new();
+    for edge in &document.edges {
+        let (source, target) = (endpoint(edge, "source")?, endpoint(edge, "target")?);
+        let id = relationship_id(edge)?;
+
+        if edge.data.get("kind").map(String::as_str) == Some(KIND_HYPEREDGE) {
+            let id = id.ok_or_else(|| invalid("hyperedge clique edge without relationship_id"))?;
+            match cliques.iter_mut().find(|(h, _)| *h == id) {
+                Some((_, group)) => group.push(edge),
+                None => cliques.push((id, vec![edge])),
+            }
+            continue;
+        }
+
+        let category = relationship_category(edge);
+        let name = edge
+            .data
+            .get("name")
+            .cloned()
+            .unwrap_or_else(|| category.display_name());
+        let mut concept = EdgeConcept::new(name, source, target, category);
+        if let Some(id) = id {
+            concept.id = id;
+        }
+        if let Some(state) = edge.data.get("state") {
+            concept.state = parse_state(state)?;
+        }
+        concept.quality = quality(edge, concept.quality.clone())?;
+        concept.position = concept.quality.to_quality_point().to_point3();
+        space.add_edge(concept)?;
+    }
+
+    for (id, group) in cliques {
+        let mut hyperedge = hyperedge(id, group[0]);
+        for edge in &group {
+            for side in ["source", "target"] {
+                add_participant(&mut hyperedge, edge, endpoint(edge, side)?, side)?;
+            }
+        }
+        space.add_hyperedge(finish_hyperedge(hyperedge, group[0])?);
+    }
+
+    // Native hyperedges: the ones too small for a clique, or drawn in a tool
+    for element in &document.hyperedges {
+        let id = relationship_id(element)?.unwrap_or_default();
+        let mut hyperedge = hyperedge(id, element);
+        for (i, node) in element.endpoints.iter().enumerate() {
+            let side = if i == 0 { "source" } else { "target" };
+            add_participant(&mut hyperedge, element, entity(node)?, side)?;
+        }
+        space.add_hyperedge(finish_hyperedge(hyperedge, element)?);
+    }
+
+    Ok(space)
+}
+
+fn invalid(reason: impl Into<String>) -> RelationshipError {
+    RelationshipError::InvalidDocument(reason.into())
+}
+
+fn entity(node: &str) -> RelationshipResult<EntityRef> {
+    let key: EntityKey = node.parse().map_err(invalid)?;
+    Ok(EntityRef::new(key.entity_type, key.entity_id))
+}
+
+fn endpoint(edge: &GraphElement, side: &str) -> RelationshipResult<EntityRef> {
+    entity(
+        edge.attrs
+            .get(side)
+            .ok_or_else(|| invalid(format!("edge without {}", side)))?,
+    )
+}
+
+fn relationship_id(element: &GraphElement) -> RelationshipResult<Option<RelationshipId>> {
+    element
+        .data
+        .get("relationship_id")
+        .map(|id| {
+            Uuid::parse_str(id)
+                .map(RelationshipId::from_uuid)
+                .map_err(|e| invalid(format!("relationship_id {}: {}", id, e)))
+        })
+        .transpose()
+}
+
+fn relationship_category(edge: &GraphElement) -> RelationshipCategory {
+    edge.data
+        .get("category")
+        .map(|c| enum_parse(c, RelationshipCategory::Custom))
+        .unwrap_or_else(|| RelationshipCategory::Custom("related".to_string()))
+}
+
+/// An empty hyperedge named and categorized from an element's attributes
+fn hyperedge(id: RelationshipId, element: &GraphElement) -> HyperEdgeConcept {
+    let category = relationship_category(element);
+    let name = element
+        .data
+        .get("name")
+        .cloned()
+        .unwrap_or_else(|| category.display_name());
+    let mut hyperedge = HyperEdgeConcept::new(name, category);
+    hyperedge.id = id;
+    hyperedge
+}
+
+/// Add the entity at one side of an element, unless already a participant
+fn add_participant(
+    hyperedge: &mut HyperEdgeConcept,
+    element: &GraphElement,
+    entity: EntityRef,
+    side: &str,
+) -> RelationshipResult<()> {
+    if hyperedge
+        .participants
+        .participants()
+        .any(|p| p.entity_ref.same_entity(&entity))
+    {
+        return Ok(());
+    }
+    let role = element
+        .data
+        .get(&format!("{}_role", side))
+        .map(|r| enum_parse(r, ParticipantRole::Custom))
+        .unwrap_or(ParticipantRole::Member);
+    let weight = number(element, &format!("{}_weight", side))?.unwrap_or(1.0);
+    hyperedge.add_participant(entity, role, weight).map_err(invalid)
+}
+
+/// Set a hyperedge's state and quality once its participants are in
+fn finish_hyperedge(mut hyperedge: HyperEdgeConcept, element: &GraphElement) -> RelationshipResult<HyperEdgeConcept> {
+    if let Some(state) = element.data.get("state") {
+        hyperedge.state = parse_state::<HyperEdgeState>(state)?;
+    }
+    hyperedge.quality = quality(element, hyperedge.quality.clone())?;
+    hyperedge.position = hyperedge.quality.to_quality_point().to_point3();
+    Ok(hyperedge)
+}
+
+fn parse_state<S: DeserializeOwned>(state: &str) -> RelationshipResult<S> {
+    serde_json::from_value(serde_json::Value::String(state.to_string()))
+        .map_err(|_| invalid(format!("unknown state {}", state)))
+}
+
+fn number(edge: &GraphElement, key: &str) -> RelationshipResult<Option<f64>> {
+    edge.data
+        .get(key)
+        .map(|v| v.trim().parse::<f64>().map_err(|e| invalid(format!("{} {}: {}", key, v, e))))
+        .transpose()
+}
+
+fn timestamp(edge: &GraphElement, key: &str) -> RelationshipResult<Option<DateTime<Utc>>> {
+    edge.data
+        .get(key)
+        .map(|v| {
+            DateTime::parse_from_rfc3339(v.trim())
+                .map(|t| t.with_timezone(&Utc))
+                .map_err(|e| invalid(format!("{} {}: {}", key, v, e)))
+        })
+        .transpose()
+}
+
+/// Quality from an edge's attributes, keeping `base` for absent ones
+fn quality(edge: &GraphElement, base: RelationshipQuality) -> RelationshipResult<RelationshipQuality> {
+    let mut quality = base;
+    if let Some(strength) = number(edge, "strength")?.or(number(edge, "weight")?) {
+        quality.strength = strength;
+    }
+    if let Some(trust) = number(edge, "trust")? {
+        quality.trust = trust;
+    }
+    if let Some(reciprocity) = number(edge, "reciprocity")? {
+        quality.reciprocity = reciprocity;
+    }
+    if let Some(formality) = edge.data.get("formality") {
+        quality.formality = serde_json::from_value::<Formality>(serde_json::Value::String(formality.clone()))
+            .map_err(|_| invalid(format!("unknown formality {}", formality)))?;
+    }
+    if let Some(starts_at) = timestamp(edge, "valid_from")? {
+        quality.duration = match timestamp(edge, "valid_until")? {
+            Some(ends_at) => ValidityPeriod::fixed_term(starts_at, ends_at),
+            None => ValidityPeriod::ongoing(starts_at),
+        };
+    }
+    check_quality(&quality)?;
+    Ok(quality)
+}
+
+// ============================================================================
+// XML Reading
+// ============================================================================
+
+/// A `<graph>`, `<edge>` or `<hyperedge>` with its attributes and `<data>`
+#[derive(Debug, Default)]
+struct GraphElement {
+    id: Option<String>,
+    attrs: HashMap<String, String>,
+    /// Data values by attribute name
+    data: HashMap<String, String>,
+    /// Nodes of a hyperedge's `<endpoint>`s, in order
+    endpoints: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+struct GraphmlDocument {
+    graph: GraphElement,
+    edges: Vec<GraphElement>,
+    hyperedges: Vec<GraphElement>,
+}
+
+/// Collect the graph, its edges and hyperedges and their data
+///
+/// Only the first `<graph>` is read; nested graphs are flattened into it.
+fn parse_graphml(xml: &str) -> RelationshipResult<GraphmlDocument> {
+    enum Current {
+        Graph,
+        Node,
+        Edge,
+        HyperEdge,
+    }
+
+    let mut reader = Reader::from_str(xml);
+    let mut keys: HashMap<String, String> = HashMap::new();
+    let mut document = GraphmlDocument::default();
+    let mut current = Current::Graph;
+    let mut data: Option<(String, String)> = None;
+    let mut saw_graph = false;
+
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| invalid(format!("malformed GraphML at {}: {}", reader.error_position(), e)))?;
+        match event {
+            Event::Start(ref tag) | Event::Empty(ref tag) => {
+                let empty = matches!(event, Event::Empty(_));
+                let mut attrs = attributes(tag)?;
+                match tag.local_name().as_ref() {
+                    b"key" => {
+                        if let Some(id) = attrs.remove("id") {
+                            let attr_name = attrs.remove("attr.name").unwrap_or_else(|| id.clone());
+                            keys.insert(id, attr_name);
+                        }
+                    }
+                    b"graph" if !saw_graph => {
+                        saw_graph = true;
+                        document.graph.id = attrs.get("id").cloned();
+                        document.graph.attrs = attrs;
+                    }
+                    b"node" if !empty => current = Current::Node,
+                    b"edge" | b"hyperedge" => {
+                        let element = GraphElement {
+                            id: attrs.get("id").cloned(),
+                            attrs,
+                            ..GraphElement::default()
+                        };
+                        if tag.local_name().as_ref() == b"edge" {
+                            document.edges.push(element);
+                            current = if empty { Current::Graph } else { Current::Edge };
+                        } else {
+                            document.hyperedges.push(element);
+                            current = if empty { Current::Graph } else { Current::HyperEdge };
+                        }
+                    }
+                    b"endpoint" => {
+                        if let (Current::HyperEdge, Some(node), Some(hyperedge)) =
+                            (&current, attrs.remove("node"), document.hyperedges.last_mut())
+                        {
+                            hyperedge.endpoints.push(node);
+                        }
+                    }
+                    b"data" => {
+                        let key = attrs.remove("key").ok_or_else(|| invalid("<data> without key"))?;
+                        let attr_name = keys.get(&key).cloned().unwrap_or(key);
+                        if empty {
+                            store(&mut document, &current, attr_name, String::new());
+                        } else {
+                            data = Some((attr_name, String::new()));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Event::Text(text) => {
+                if let Some((_, value)) = &mut data {
+                    value.push_str(&text.unescape().map_err(|e| invalid(e.to_string()))?);
+                }
+            }
+            Event::CData(text) => {
+                if let Some((_, value)) = &mut data {
+                    value.push_str(&String::from_utf8_lossy(&text.into_inner()));
+                }
+            }
+            Event::End(tag) => match tag.local_name().as_ref() {
+                b"data" => {
+                    if let Some((key, value)) = data.take() {
+                        store(&mut document, &current, key, value);
+                    }
+                }
+                b"node" | b"edge" | b"hyperedge" => current = Current::Graph,
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    fn store(document: &mut GraphmlDocument, current: &Current, key: String, value: String) {
+        let element = match current {
+            Current::Graph => Some(&mut document.graph),
+            Current::Edge => document.edges.last_mut(),
+            Current::HyperEdge => document.hyperedges.last_mut(),
+            // Node data is descriptive: entities are identified by node id
+            Current::Node => None,
+        };
+        if let Some(element) = element {
+            element.data.insert(key, value);
+        }
+    }
+
+    if !saw_graph {
+        return Err(invalid("no <graph> element"));
+    }
+    Ok(document)
+}
+
+/// Attributes of a tag by local name, unescaped
+fn attributes(tag: &BytesStart) -> RelationshipResult<HashMap<String, String>> {
+    tag.attributes()
+        .map(|attr| {
+            let attr = attr.map_err(|e| invalid(e.to_string()))?;
+            let value = attr.unescape_value().map_err(|e| invalid(e.to_string()))?;
+            Ok((String::from_utf8_lossy(attr.key.as_ref()).into_owned(), value.into_owned()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregates::EdgeState;
+
+    #[test]
+    fn test_graphml_round_trip() {
+        let alice = EntityRef::person(Uuid::now_v7());
+        let bob = EntityRef::person(Uuid::now_v7());
+        let acme = EntityRef::organization(Uuid::now_v7());
+        let mut space = RelationshipSpace::new("R&D <core>", TopologicalSpaceId::new());
+        let mut job = EdgeConcept::new("Works at", alice.clone(), acme.clone(), RelationshipCategory::Employment)
+            .with_quality(RelationshipQuality::default_employment());
+        job.activate().unwrap();
+        space.add_edge(job.clone()).unwrap();
+        let mut team = HyperEdgeConcept::new("Team", RelationshipCategory::Custom("squad".to_string()));
+        team.add_participant(alice.clone(), ParticipantRole::Leader, 1.0).unwrap();
+        team.add_participant(bob.clone(), ParticipantRole::Member, 0.5).unwrap();
+        team.add_participant(acme.clone(), ParticipantRole::Primary, 0.8).unwrap();
+        space.add_hyperedge(team.clone());
+        // Too small for a clique
+        let mut forming = HyperEdgeConcept::new("Forming", RelationshipCategory::Membership);
+        forming.add_participant(bob.clone(), ParticipantRole::Leader, 0.7).unwrap();
+        space.add_hyperedge(forming.clone());
+        let empty = HyperEdgeConcept::new("Empty", RelationshipCategory::Membership);
+        space.add_hyperedge(empty.clone());
+
+        let xml = export_graphml(&space).unwrap();
+        assert!(xml.contains("R&amp;D &lt;core&gt;"));
+        // One directed edge plus three clique edges for three participants
+        assert_eq!(xml.matches("<edge ").count(), 4);
+        assert_eq!(xml.matches(r#"directed="false""#).count(), 3);
+        assert_eq!(xml.matches("<hyperedge ").count(), 2);
+
+        let imported = import_graphml(xml.as_bytes()).unwrap();
+        assert_eq!(imported.name, "R&D <core>");
+        let restored = imported.get_edge(&job.id).unwrap();
+        assert_eq!(restored.state, EdgeState::Active);
+        assert_eq!(restored.quality.trust, job.quality.trust);
+        assert_eq!(restored.quality.formality, job.quality.formality);
+        let restored = imported.get_hyperedge(&team.id).unwrap();
+        assert_eq!(restored.category, team.category);
+        assert_eq!(restored.participants.participant_count(), 3);
+        let lead = restored
+            .participants
+            .participants()
+            .find(|p| p.entity_ref.same_entity(&alice))
+            .unwrap();
+        assert_eq!(lead.role, ParticipantRole::Leader);
+        let restored = imported.get_hyperedge(&forming.id).unwrap();
+        let sole = restored.participants.participants().next().unwrap();
+        assert!(sole.entity_ref.same_entity(&bob));
+        assert_eq!((sole.role.clone(), sole.weight), (ParticipantRole::Leader, 0.7));
+        assert_eq!(imported.get_hyperedge(&empty.id).unwrap().participants.participant_count(), 0);
+
+        // Files re-saved by tools renumber keys and add edges by hand
+        let edited = r#"<?xml version="1.0"?>
+            <!-- saved by a graph editor -->
+            <graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+              <key id="d0" for="edge" attr.name="weight" attr.type="double"/>
+              <graph id="G" edgedefault="directed">
+                <node id="NODE_A"/>
+                <edge source="SRC" target="DST"><data key="d0">0.9</data></edge>
+              </graph>
+            </graphml>"#
+            .replace("SRC", &alice.key().to_string())
+            .replace("DST", &bob.key().to_string());
+        let imported = import_graphml(edited.as_bytes()).unwrap();
+        let drawn = imported.edges.values().next().unwrap();
+        assert_eq!(drawn.quality.strength, 0.9);
+        assert_eq!(drawn.state, EdgeState::Proposed);
+        assert!(matches!(
+            import_graphml(edited.replace(">0.9<", ">1.5<").as_bytes()),
+            Err(RelationshipError::QualityOutOfRange(_))
+        ));
+
+        assert!(matches!(
+            import_graphml(r#"<graphml><graph><edge source="x" target="y"/></graph></graphml>"#.as_bytes()),
+            Err(RelationshipError::InvalidDocument(_))
+        ));
+    }
+}
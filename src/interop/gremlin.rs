@@ -0,0 +1,345 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Gremlin / TinkerPop
+//!
+//! Two ways into a TinkerPop graph (JanusGraph, Neptune, ...):
+//!
+//! - `export_graphson`: GraphSON 3.0 adjacency lists, one vertex per line,
+//!   as read by `g.io(file).read()` and the JanusGraph bulk loaders
+//! - `gremlin_script`: one idempotent Gremlin traversal per vertex and
+//!   edge, for servers that only take traversals (Neptune)
+//!
+//! ```text
+//! entity      vertex  label "person"     id / key "person:{uuid}"
+//! edge        edge    label "employment" id "{uuid}"   source -> target
+//! hyperedge   vertex  label "hyperedge"  id "{uuid}"
+//!             edge    label "participates_in"  participant -> hyperedge
+//!                     (role, weight)
+//! ```
+//!
+//! Relationship edges and hyperedge vertices carry `relationship_id`,
+//! `name`, `category`, `state` and the quality dimensions as properties.
+//! Vertex and edge ids are strings; graphs assigning their own ids can
+//! match entities by the `key` property instead.
+
+use crate::aggregates::{EdgeConcept, HyperEdgeConcept, RelationshipSpace};
+use crate::quality::RelationshipQuality;
+use crate::value_objects::{EntityRef, EntityType, RelationshipCategory};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+
+/// Label of hyperedge vertices
+pub const HYPEREDGE_VERTEX_LABEL: &str = "hyperedge";
+
+/// Label of edges from participants to their hyperedge vertex
+pub const PARTICIPATES_IN_LABEL: &str = "participates_in";
+
+/// A property value as GraphSON types it
+#[derive(Debug, Clone)]
+enum Property {
+    Text(String),
+    Double(f64),
+}
+
+impl Property {
+    fn graphson(&self) -> Value {
+        match self {
+            Property::Text(text) => json!(text),
+            Property::Double(value) => json!({ "@type": "g:Double", "@value": value }),
+        }
+    }
+
+    fn gremlin(&self) -> String {
+        match self {
+            Property::Text(text) => literal(text),
+            Property::Double(value) => format!("{:?}d", value),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Vertex {
+    label: String,
+    properties: Vec<(String, Property)>,
+}
+
+#[derive(Debug)]
+struct Edge {
+    id: String,
+    label: String,
+    out_v: String,
+    in_v: String,
+    properties: Vec<(String, Property)>,
+}
+
+/// The space as TinkerPop vertices and edges, keyed and ordered by id
+fn property_graph(space: &RelationshipSpace) -> (BTreeMap<String, Vertex>, Vec<Edge>) {
+    let mut vertices: BTreeMap<String, Vertex> = BTreeMap::new();
+    let mut edges = Vec::new();
+
+    let mut sorted: Vec<&EdgeConcept> = space.edges.values().collect();
+    sorted.sort_by_key(|e| e.id.as_uuid());
+    for edge in sorted {
+        let out_v = add_entity(&mut vertices, &edge.source);
+        let in_v = add_entity(&mut vertices, &edge.target);
+        let id = edge.id.as_uuid().to_string();
+        edges.push(Edge {
+            label: edge_label(&edge.category),
+            properties: relationship_properties(
+                &id,
+                &edge.name,
+                &edge.category,
+                &format!("{:?}", edge.state),
+                &edge.quality,
+            ),
+            id,
+            out_v,
+            in_v,
+        });
+    }
+
+    let mut sorted: Vec<&HyperEdgeConcept> = space.hyperedges.values().collect();
+    sorted.sort_by_key(|h| h.id.as_uuid());
+    for hyperedge in sorted {
+        let id = hyperedge.id.as_uuid().to_string();
+        vertices.insert(
+            id.clone(),
+            Vertex {
+                label: HYPEREDGE_VERTEX_LABEL.to_string(),
+                properties: relationship_properties(
+                    &id,
+                    &hyperedge.name,
+                    &hyperedge.category,
+                    &format!("{:?}", hyperedge.state),
+                    &hyperedge.quality,
+                ),
+            },
+        );
+        let mut participants: Vec<_> = hyperedge.participants.participants().collect();
+        participants.sort_by_key(|p| p.entity_ref.key().to_string());
+        for participant in participants {
+            let out_v = add_entity(&mut vertices, &participant.entity_ref);
+            edges.push(Edge {
+                id: format!("{}/{}", id, out_v),
+                label: PARTICIPATES_IN_LABEL.to_string(),
+                out_v,
+                in_v: id.clone(),
+                properties: vec![
+                    ("role".to_string(), Property::Text(participant.role.display_name())),
+                    ("weight".to_string(), Property::Double(participant.weight)),
+                ],
+            });
+        }
+    }
+
+    (vertices, edges)
+}
+
+/// Add an entity's vertex if missing, returning its id
+fn add_entity(vertices: &mut BTreeMap<String, Vertex>, entity: &EntityRef) -> String {
+    let key = entity.key().to_string();
+    vertices.entry(key.clone()).or_insert_with(|| Vertex {
+        label: entity_label(&entity.entity_type),
+        properties: vec![
+            ("key".to_string(), Property::Text(key.clone())),
+            ("entity_id".to_string(), Property::Text(entity.entity_id.to_string())),
+        ],
+    });
+    key
+}
+
+fn relationship_properties(
+    id: &str,
+    name: &str,
+    category: &RelationshipCategory,
+    state: &str,
+    quality: &RelationshipQuality,
+) -> Vec<(String, Property)> {
+    let mut properties = vec![
+        ("relationship_id".to_string(), Property::Text(id.to_string())),
+        ("name".to_string(), Property::Text(name.to_string())),
+        ("category".to_string(), Property::Text(category.display_name())),
+        ("state".to_string(), Property::Text(state.to_string())),
+        ("strength".to_string(), Property::Double(quality.strength)),
+        ("trust".to_string(), Property::Double(quality.trust)),
+        ("formality".to_string(), Property::Text(format!("{:?}", quality.formality))),
+        ("reciprocity".to_string(), Property::Double(quality.reciprocity)),
+        (
+            "valid_from".to_string(),
+            Property::Text(quality.duration.starts_at.to_rfc3339()),
+        ),
+    ];
+    if let Some(ends_at) = quality.duration.ends_at {
+        properties.push(("valid_until".to_string(), Property::Text(ends_at.to_rfc3339())));
+    }
+    properties
+}
+
+// ============================================================================
+// GraphSON
+// ============================================================================
+
+/// Write a space as GraphSON 3.0 adjacency lists, one vertex per line
+pub fn export_graphson(space: &RelationshipSpace) -> String {
+    let (vertices, edges) = property_graph(space);
+    let mut out_e: BTreeMap<&str, BTreeMap<&str, Vec<Value>>> = BTreeMap::new();
+    let mut in_e: BTreeMap<&str, BTreeMap<&str, Vec<Value>>> = BTreeMap::new();
+    for edge in &edges {
+        let properties: Map<String, Value> = edge
+            .properties
+            .iter()
+            .map(|(name, value)| (name.clone(), value.graphson()))
+            .collect();
+        out_e
+            .entry(&edge.out_v)
+            .or_default()
+            .entry(&edge.label)
+            .or_default()
+            .push(json!({ "id": edge.id, "inV": edge.in_v, "properties": properties }));
+        in_e
+            .entry(&edge.in_v)
+            .or_default()
+            .entry(&edge.label)
+            .or_default()
+            .push(json!({ "id": edge.id, "outV": edge.out_v, "properties": properties }));
+    }
+
+    let mut lines = String::new();
+    for (id, vertex) in &vertices {
+        let properties: Map<String, Value> = vertex
+            .properties
+            .iter()
+            .map(|(name, value)| {
+                (
+                    name.clone(),
+                    json!([{ "id": format!("{}/{}", id, name), "value": value.graphson() }]),
+                )
+            })
+            .collect();
+        let mut line = json!({ "id": id, "label": vertex.label, "properties": properties });
+        if let Some(edges) = out_e.remove(id.as_str()) {
+            line["outE"] = json!(edges);
+        }
+        if let Some(edges) = in_e.remove(id.as_str()) {
+            line["inE"] = json!(edges);
+        }
+        lines.push_str(&line.to_string());
+        lines.push('\n');
+    }
+    lines
+}
+
+// ============================================================================
+// Gremlin Script
+// ============================================================================
+
+/// One upserting traversal per vertex, then per edge
+///
+/// Vertices are matched by their `key` property and edges by an `edge_id`
+/// property holding their GraphSON id, so running the script again
+/// updates rather than duplicates.
+pub fn gremlin_script(space: &RelationshipSpace) -> Vec<String> {
+    let (vertices, edges) = property_graph(space);
+    let mut script = Vec::new();
+
+    for (id, vertex) in &vertices {
+        let mut traversal = format!(
+            "g.V().has('key', {id}).fold().coalesce(unfold(), addV({label}).property('key', {id}))",
+            id = literal(id),
+            label = literal(&vertex.label)
+        );
+        for (name, value) in vertex.properties.iter().filter(|(name, _)| name != "key") {
+            traversal.push_str(&format!(".property(single, {}, {})", literal(name), value.gremlin()));
+        }
+        script.push(traversal);
+    }
+
+    for edge in &edges {
+        let mut traversal = format!(
+            "g.E().has('edge_id', {id}).fold().coalesce(unfold(), addE({label})\
+             .from(__.V().has('key', {out_v})).to(__.V().has('key', {in_v})).property('edge_id', {id}))",
+            id = literal(&edge.id),
+            label = literal(&edge.label),
+            out_v = literal(&edge.out_v),
+            in_v = literal(&edge.in_v)
+        );
+        for (name, value) in &edge.properties {
+            traversal.push_str(&format!(".property({}, {})", literal(name), value.gremlin()));
+        }
+        script.push(traversal);
+    }
+    script
+}
+
+/// Single-quoted Gremlin string literal
+fn literal(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('\'');
+    for c in text.chars() {
+        match c {
+            '\'' => quoted.push_str("\\'"),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '$' => quoted.push_str("\\$"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('\'');
+    quoted
+}
+
+fn entity_label(entity_type: &EntityType) -> String {
+    match entity_type {
+        EntityType::Custom(name) => name.clone(),
+        other => other.nats_subject_prefix().to_string(),
+    }
+}
+
+/// "part of" -> "part_of"
+fn edge_label(category: &RelationshipCategory) -> String {
+    category.display_name().split_whitespace().collect::<Vec<_>>().join("_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value_objects::ParticipantRole;
+    use cim_domain_spaces::TopologicalSpaceId;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_graphson_and_script_export() {
+        let alice = EntityRef::person(Uuid::now_v7());
+        let acme = EntityRef::organization(Uuid::now_v7());
+        let mut space = RelationshipSpace::new("Tinker", TopologicalSpaceId::new());
+        let edge = EdgeConcept::new("Alice's job", alice.clone(), acme.clone(), RelationshipCategory::PartOf);
+        space.add_edge(edge.clone()).unwrap();
+        let mut team = HyperEdgeConcept::new("Squad", RelationshipCategory::Custom("squad".to_string()));
+        team.add_participant(alice.clone(), ParticipantRole::Leader, 1.0).unwrap();
+        team.add_participant(acme.clone(), ParticipantRole::Primary, 0.5).unwrap();
+        space.add_hyperedge(team.clone());
+
+        let graphson = export_graphson(&space);
+        let lines: Vec<Value> = graphson.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+        assert_eq!(lines.len(), 3);
+        let person = lines.iter().find(|v| v["id"] == json!(alice.key().to_string())).unwrap();
+        assert_eq!(person["label"], json!("person"));
+        let out = &person["outE"]["part_of"][0];
+        assert_eq!(out["inV"], json!(acme.key().to_string()));
+        assert_eq!(out["properties"]["strength"]["@type"], json!("g:Double"));
+        assert_eq!(person["outE"][PARTICIPATES_IN_LABEL].as_array().unwrap().len(), 1);
+        let organization = lines.iter().find(|v| v["id"] == json!(acme.key().to_string())).unwrap();
+        assert_eq!(organization["inE"]["part_of"][0]["outV"], json!(alice.key().to_string()));
+        let hyperedge = lines.iter().find(|v| v["label"] == json!(HYPEREDGE_VERTEX_LABEL)).unwrap();
+        assert_eq!(hyperedge["inE"][PARTICIPATES_IN_LABEL].as_array().unwrap().len(), 2);
+
+        let script = gremlin_script(&space);
+        // Three vertices, one edge, two participations
+        assert_eq!(script.len(), 6);
+        assert!(script.iter().any(|t| t.contains("'Alice\\'s job'")));
+        assert!(script.iter().any(|t| t.contains("addE('part_of')")));
+        assert!(script.iter().all(|t| t.starts_with("g.")));
+    }
+}
@@ -0,0 +1,246 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! JSON-LD
+//!
+//! Edges, hyperedges and whole spaces as JSON-LD, so web clients read plain
+//! JSON while linked-data systems expand the same document into RDF:
+//!
+//! ```json
+//! {
+//!   "@context": { ... },
+//!   "@id": "urn:cim:relationship:0190...",
+//!   "@type": "Edge",
+//!   "category": "Employment",
+//!   "source": { "@id": "urn:cim:person:0190...", "entityType": "person" },
+//!   "target": { "@id": "urn:cim:organization:0190...", "cid": "ipfs://bafk..." },
+//!   "strength": 0.8,
+//!   "evidence": ["ipfs://bafk..."]
+//! }
+//! ```
+//!
+//! ## Identifiers
+//!
+//! Relationships and entities get the IRIs of the `OntologyMapping` also
+//! used for RDF export, so both exports describe the same nodes. A pinned
+//! entity reference keeps its version-independent `@id` and adds the
+//! pinned content as `cid`; CIDs are written as `ipfs://` IRIs.
+//!
+//! ## Category Vocabulary
+//!
+//! `category` is a vocabulary-typed term: the context defines every
+//! category name as the predicate the mapping binds it to, so
+//! `"category": "Employment"` expands to `http://schema.org/worksFor`
+//! under the standard mapping. Custom categories are written as their
+//! predicate IRI.
+
+use super::rdf::OntologyMapping;
+use crate::aggregates::{EdgeConcept, HyperEdgeConcept, RelationshipSpace};
+use crate::quality::RelationshipQuality;
+use crate::value_objects::{EntityRef, EntityType, RelationshipCategory};
+use serde_json::{json, Map, Value};
+
+/// The `@context` shared by every document
+pub fn jsonld_context(mapping: &OntologyMapping) -> Value {
+    let mut context = Map::new();
+    context.insert("@vocab".to_string(), json!(mapping.vocabulary));
+    context.insert("xsd".to_string(), json!("http://www.w3.org/2001/XMLSchema#"));
+    for (term, kind) in [
+        ("source", "@id"),
+        ("target", "@id"),
+        ("entity", "@id"),
+        ("cid", "@id"),
+        ("evidence", "@id"),
+        ("category", "@vocab"),
+        ("strength", "xsd:double"),
+        ("trust", "xsd:double"),
+        ("reciprocity", "xsd:double"),
+        ("weight", "xsd:double"),
+        ("confidence", "xsd:double"),
+        ("validFrom", "xsd:dateTime"),
+        ("validUntil", "xsd:dateTime"),
+        ("createdAt", "xsd:dateTime"),
+    ] {
+        context.insert(term.to_string(), json!({ "@type": kind }));
+    }
+    context.insert("participants".to_string(), json!({ "@id": "participant", "@container": "@set" }));
+    for category in &RelationshipCategory::STANDARD {
+        context.insert(category_term(category, mapping), json!(mapping.predicate(category)));
+    }
+    Value::Object(context)
+}
+
+/// An edge as a standalone JSON-LD document
+pub fn edge_to_jsonld(edge: &EdgeConcept, mapping: &OntologyMapping) -> Value {
+    with_context(edge_node(edge, mapping), mapping)
+}
+
+/// A hyperedge as a standalone JSON-LD document
+pub fn hyperedge_to_jsonld(hyperedge: &HyperEdgeConcept, mapping: &OntologyMapping) -> Value {
+    with_context(hyperedge_node(hyperedge, mapping), mapping)
+}
+
+/// A space as a JSON-LD document whose `@graph` holds its relationships
+pub fn space_to_jsonld(space: &RelationshipSpace, mapping: &OntologyMapping) -> Value {
+    let mut edges: Vec<&EdgeConcept> = space.edges.values().collect();
+    edges.sort_by_key(|e| e.id.as_uuid());
+    let mut hyperedges: Vec<&HyperEdgeConcept> = space.hyperedges.values().collect();
+    hyperedges.sort_by_key(|h| h.id.as_uuid());
+
+    let graph: Vec<Value> = edges
+        .into_iter()
+        .map(|e| edge_node(e, mapping))
+        .chain(hyperedges.into_iter().map(|h| hyperedge_node(h, mapping)))
+        .collect();
+    json!({
+        "@context": jsonld_context(mapping),
+        "@id": mapping.space_iri(space),
+        "@type": "RelationshipSpace",
+        "name": space.name,
+        "@graph": graph,
+    })
+}
+
+fn with_context(node: Value, mapping: &OntologyMapping) -> Value {
+    let mut document = Map::new();
+    document.insert("@context".to_string(), jsonld_context(mapping));
+    if let Value::Object(node) = node {
+        document.extend(node);
+    }
+    Value::Object(document)
+}
+
+fn edge_node(edge: &EdgeConcept, mapping: &OntologyMapping) -> Value {
+    let mut node = json!({
+        "@id": mapping.relationship_iri(edge.id),
+        "@type": "Edge",
+        "name": edge.name,
+        "category": category_term(&edge.category, mapping),
+        "state": format!("{:?}", edge.state),
+        "source": entity_node(&edge.source, mapping),
+        "target": entity_node(&edge.target, mapping),
+        "createdAt": edge.created_at.to_rfc3339(),
+    });
+    describe(&mut node, &edge.quality, &edge.evidence_cids);
+    node
+}
+
+fn hyperedge_node(hyperedge: &HyperEdgeConcept, mapping: &OntologyMapping) -> Value {
+    let mut participants: Vec<_> = hyperedge.participants.participants().collect();
+    participants.sort_by_key(|p| p.entity_ref.key().to_string());
+    let participants: Vec<Value> = participants
+        .into_iter()
+        .map(|p| {
+            json!({
+                "entity": entity_node(&p.entity_ref, mapping),
+                "role": p.role.display_name(),
+                "weight": p.weight,
+            })
+        })
+        .collect();
+
+    let mut node = json!({
+        "@id": mapping.relationship_iri(hyperedge.id),
+        "@type": "HyperEdge",
+        "name": hyperedge.name,
+        "category": category_term(&hyperedge.category, mapping),
+        "state": format!("{:?}", hyperedge.state),
+        "participants": participants,
+        "confidence": hyperedge.confidence,
+        "createdAt": hyperedge.created_at.to_rfc3339(),
+    });
+    describe(&mut node, &hyperedge.quality, &hyperedge.evidence_cids);
+    node
+}
+
+fn describe(node: &mut Value, quality: &RelationshipQuality, evidence_cids: &[String]) {
+    let Value::Object(node) = node else {
+        return;
+    };
+    node.insert("strength".to_string(), json!(quality.strength));
+    node.insert("trust".to_string(), json!(quality.trust));
+    node.insert("formality".to_string(), json!(format!("{:?}", quality.formality)));
+    node.insert("reciprocity".to_string(), json!(quality.reciprocity));
+    node.insert("validFrom".to_string(), json!(quality.duration.starts_at.to_rfc3339()));
+    if let Some(ends_at) = quality.duration.ends_at {
+        node.insert("validUntil".to_string(), json!(ends_at.to_rfc3339()));
+    }
+    if !evidence_cids.is_empty() {
+        let evidence: Vec<String> = evidence_cids.iter().map(|cid| cid_iri(cid)).collect();
+        node.insert("evidence".to_string(), json!(evidence));
+    }
+}
+
+fn entity_node(entity: &EntityRef, mapping: &OntologyMapping) -> Value {
+    let entity_type = match &entity.entity_type {
+        EntityType::Custom(name) => format!("custom/{}", name),
+        other => other.nats_subject_prefix().to_string(),
+    };
+    let mut node = json!({
+        "@id": mapping.entity_iri(entity),
+        "entityType": entity_type,
+    });
+    if let Some(cid) = &entity.cid {
+        node["cid"] = json!(cid_iri(cid));
+    }
+    if let Some(version) = entity.version {
+        node["version"] = json!(version);
+    }
+    node
+}
+
+/// Context term of a built-in category, predicate IRI of a custom one
+fn category_term(category: &RelationshipCategory, mapping: &OntologyMapping) -> String {
+    match category {
+        RelationshipCategory::Custom(_) => mapping.predicate(category),
+        builtin => format!("{:?}", builtin),
+    }
+}
+
+fn cid_iri(cid: &str) -> String {
+    format!("ipfs://{}", cid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value_objects::ParticipantRole;
+    use cim_domain_spaces::TopologicalSpaceId;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_jsonld_documents() {
+        let mapping = OntologyMapping::standard();
+        let alice = EntityRef::person(Uuid::now_v7());
+        let acme = EntityRef::organization(Uuid::now_v7()).with_cid("bafkacme");
+        let mut edge = EdgeConcept::new("Works at", alice.clone(), acme.clone(), RelationshipCategory::Employment);
+        edge.evidence_cids.push("bafkcontract".to_string());
+
+        let document = edge_to_jsonld(&edge, &mapping);
+        assert_eq!(document["@id"], json!(mapping.relationship_iri(edge.id)));
+        assert_eq!(document["category"], json!("Employment"));
+        assert_eq!(document["@context"]["Employment"], json!("http://schema.org/worksFor"));
+        assert_eq!(document["@context"]["category"]["@type"], json!("@vocab"));
+        assert_eq!(document["source"]["@id"], json!(mapping.entity_iri(&alice)));
+        assert_eq!(document["target"]["@id"], json!(mapping.entity_iri(&acme)));
+        assert_eq!(document["target"]["cid"], json!("ipfs://bafkacme"));
+        assert_eq!(document["evidence"], json!(["ipfs://bafkcontract"]));
+        assert_eq!(document["strength"], json!(edge.quality.strength));
+
+        let mut team = HyperEdgeConcept::new("Squad", RelationshipCategory::Custom("squad".to_string()));
+        team.add_participant(alice.clone(), ParticipantRole::Leader, 1.0).unwrap();
+        team.add_participant(acme.clone(), ParticipantRole::Primary, 0.5).unwrap();
+        let document = hyperedge_to_jsonld(&team, &mapping);
+        assert_eq!(document["@type"], json!("HyperEdge"));
+        assert_eq!(document["category"], json!(format!("{}squad", mapping.vocabulary)));
+        assert_eq!(document["participants"].as_array().unwrap().len(), 2);
+
+        let mut space = RelationshipSpace::new("Linked", TopologicalSpaceId::new());
+        space.add_edge(edge).unwrap();
+        space.add_hyperedge(team);
+        let document = space_to_jsonld(&space, &mapping);
+        assert_eq!(document["@graph"].as_array().unwrap().len(), 2);
+        assert!(document["@graph"][0].get("@context").is_none());
+    }
+}
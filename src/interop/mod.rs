@@ -0,0 +1,78 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Interoperability formats for the Relationship Domain
+//!
+//! Serialized representations used to move relationship data across
+//! deployment and tool boundaries.
+//!
+//! ## Formats
+//!
+//! - **Avro**: Single-object encoded event records, a record type per
+//!   event, with schema fingerprints and compatibility checks, for Kafka
+//!   lakehouses and the bus (`avro` feature)
+//! - **RelationshipDocument**: Canonical portable document for a single
+//!   relationship (JSON or CBOR)
+//! - **GraphModel**: Node/edge view of a whole RelationshipSpace for graph
+//!   tooling, convertible back to the space
+//! - **Columnar**: Edge, participant and quality-history tables for
+//!   analytical engines, written as Parquet or Arrow IPC (`columnar` feature)
+//! - **CSV**: Bulk edge import from CSV/TSV rows through a batch command,
+//!   with a per-row report
+//! - **Cypher**: Parameterized MERGE statements mirroring relationships
+//!   into Neo4j and other property graphs
+//! - **DOT**: Graphviz rendering of a space or ego network, styled by
+//!   category, state and strength
+//! - **GEXF**: Dynamic graph for Gephi's timeline, with validity periods as
+//!   spells and quality updates as time-varying attributes
+//! - **GraphML**: XML export/import of a space for Gephi, yEd and other
+//!   graph analysis tools (hyperedges clique-expanded)
+//! - **Gremlin**: GraphSON 3.0 adjacency lists and upserting Gremlin
+//!   traversals for TinkerPop graphs (JanusGraph, Neptune)
+//! - **JSON-LD**: Linked-data documents for edges, hyperedges and spaces,
+//!   sharing identifiers and vocabulary with the RDF export
+//! - **RDF**: Turtle and N-Quads export for knowledge-graph stores, with a
+//!   configurable category-to-predicate mapping
+
+#[cfg(feature = "avro")]
+mod avro;
+mod columnar;
+mod csv_import;
+mod cypher;
+mod document;
+mod dot;
+mod gexf;
+mod graph;
+mod graphml;
+mod gremlin;
+mod jsonld;
+mod rdf;
+
+#[cfg(feature = "avro")]
+pub use avro::{
+    AvroEventCodec, AvroFingerprint, AVRO_CONTENT_TYPE, AVRO_EVENT_SCHEMA, AVRO_FINGERPRINT_HEADER,
+    AVRO_SCHEMA_NAME_HEADER,
+};
+pub use columnar::{
+    edges_table, participants_table, quality_history_table, AnalyticalTables, Column, ColumnData, ColumnarTable,
+};
+pub use csv_import::{CsvImportReport, CsvImporter, CsvRowResult, CsvRows};
+pub use cypher::{
+    cypher_for_edge, cypher_for_event, cypher_for_hyperedge, cypher_for_space, CypherStatement, PARTICIPATES_IN,
+};
+pub use document::{
+    EvidenceManifestEntry, PortableRelationship, RelationshipDocument, DOCUMENT_FORMAT,
+};
+pub use dot::{export_dot, export_ego_dot};
+pub use gexf::{export_gexf, GEXF_NAMESPACE};
+pub use graph::{
+    from_graph, to_graph, to_graph_labelled, GraphModel, GraphModelEdge, GraphModelNode, ENTITY_LABEL,
+    HYPEREDGE_LABEL, PARTICIPATES_LABEL,
+};
+pub use graphml::{export_graphml, import_graphml, GRAPHML_NAMESPACE};
+pub use gremlin::{export_graphson, gremlin_script, HYPEREDGE_VERTEX_LABEL, PARTICIPATES_IN_LABEL};
+pub use jsonld::{edge_to_jsonld, hyperedge_to_jsonld, jsonld_context, space_to_jsonld};
+pub use rdf::{
+    export_nquads, export_turtle, rdf_triples, OntologyMapping, RdfTerm, RdfTriple, RELATIONSHIP_VOCABULARY,
+};
@@ -0,0 +1,488 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! RDF Export
+//!
+//! Exports a RelationshipSpace as RDF for knowledge-graph stores, as Turtle
+//! or as N-Quads in a named graph per space. An `OntologyMapping` decides
+//! which predicate each category becomes:
+//!
+//! ```text
+//! edge (active)    <source> <predicate(category)> <target> .
+//! every edge       <urn:cim:relationship:{id}> a rdf:Statement ;
+//!                      rdf:subject <source> ; rdf:predicate ... ; rdf:object <target> ;
+//!                      rel:state "Active" ; rel:strength 0.8 ; rel:trust ... .
+//! hyperedge        <urn:cim:relationship:{id}> a rel:HyperEdge ;
+//!                      rel:participant [ rel:entity <e> ; rel:role "leader" ; rel:weight 1.0 ] .
+//! ```
+//!
+//! Only active edges are asserted as direct triples, so a store never holds
+//! a terminated relationship as a fact. The reified statement carries the
+//! state and quality of every edge, active or not.
+//!
+//! ## Standard Mapping
+//!
+//! ```text
+//! Employment    schema:worksFor        PartOf       dcterms:isPartOf
+//! Membership    schema:memberOf        Contains     dcterms:hasPart
+//! Ownership     schema:owns            References   dcterms:references
+//! Friendship    foaf:knows             DerivesFrom  prov:wasDerivedFrom
+//! others        rel:{lowerCamelCase display name}
+//! ```
+
+use crate::aggregates::{EdgeConcept, HyperEdgeConcept, RelationshipSpace};
+use crate::quality::RelationshipQuality;
+use crate::value_objects::{EntityRef, RelationshipCategory, RelationshipId};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Namespace of the relationship vocabulary
+pub const RELATIONSHIP_VOCABULARY: &str = "https://cim.cowboy.ai/ns/relationship#";
+
+const RDF: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#";
+const XSD: &str = "http://www.w3.org/2001/XMLSchema#";
+
+/// How relationships map onto an ontology
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OntologyMapping {
+    /// Namespace of relationship properties and classes (`rel:`)
+    pub vocabulary: String,
+    /// Prefix of entity, relationship and space IRIs
+    pub base: String,
+    /// Predicate IRI per category
+    #[serde(with = "crate::value_objects::category_map")]
+    pub predicates: HashMap<RelationshipCategory, String>,
+    /// Turtle prefixes (name -> namespace)
+    pub prefixes: BTreeMap<String, String>,
+}
+
+impl Default for OntologyMapping {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+impl OntologyMapping {
+    /// Mapping without category bindings: every category becomes a
+    /// property of `vocabulary`
+    pub fn new(vocabulary: impl Into<String>) -> Self {
+        let vocabulary = vocabulary.into();
+        let prefixes = [("rdf", RDF), ("xsd", XSD), ("rel", vocabulary.as_str())]
+            .into_iter()
+            .map(|(name, ns)| (name.to_string(), ns.to_string()))
+            .collect();
+        Self {
+            vocabulary,
+            base: "urn:cim:".to_string(),
+            predicates: HashMap::new(),
+            prefixes,
+        }
+    }
+
+    /// The standard mapping onto schema.org, FOAF, Dublin Core and PROV
+    pub fn standard() -> Self {
+        const SCHEMA: &str = "http://schema.org/";
+        const FOAF: &str = "http://xmlns.com/foaf/0.1/";
+        const DCTERMS: &str = "http://purl.org/dc/terms/";
+        const PROV: &str = "http://www.w3.org/ns/prov#";
+
+        [
+            (RelationshipCategory::Employment, SCHEMA, "worksFor"),
+            (RelationshipCategory::Membership, SCHEMA, "memberOf"),
+            (RelationshipCategory::Ownership, SCHEMA, "owns"),
+            (RelationshipCategory::Friendship, FOAF, "knows"),
+            (RelationshipCategory::PartOf, DCTERMS, "isPartOf"),
+            (RelationshipCategory::Contains, DCTERMS, "hasPart"),
+            (RelationshipCategory::References, DCTERMS, "references"),
+            (RelationshipCategory::DerivesFrom, PROV, "wasDerivedFrom"),
+        ]
+        .into_iter()
+        .fold(Self::new(RELATIONSHIP_VOCABULARY), |mapping, (category, ns, local)| {
+            mapping.with_predicate(category, format!("{}{}", ns, local))
+        })
+        .with_prefix("schema", SCHEMA)
+        .with_prefix("foaf", FOAF)
+        .with_prefix("dcterms", DCTERMS)
+        .with_prefix("prov", PROV)
+    }
+
+    /// Bind a category to a predicate IRI, replacing any existing binding
+    pub fn with_predicate(mut self, category: RelationshipCategory, predicate: impl Into<String>) -> Self {
+        self.predicates.insert(category, predicate.into());
+        self
+    }
+
+    /// Add a Turtle prefix
+    pub fn with_prefix(mut self, name: impl Into<String>, namespace: impl Into<String>) -> Self {
+        self.prefixes.insert(name.into(), namespace.into());
+        self
+    }
+
+    /// Set the prefix of entity, relationship and space IRIs
+    pub fn with_base(mut self, base: impl Into<String>) -> Self {
+        self.base = base.into();
+        self
+    }
+
+    /// Predicate IRI of a category
+    pub fn predicate(&self, category: &RelationshipCategory) -> String {
+        self.predicates
+            .get(category)
+            .cloned()
+            .unwrap_or_else(|| self.term(&lower_camel(&category.display_name())))
+    }
+
+    /// IRI of an entity
+    pub fn entity_iri(&self, entity: &EntityRef) -> String {
+        format!("{}{}", self.base, entity.key())
+    }
+
+    /// IRI of an edge or hyperedge
+    pub fn relationship_iri(&self, id: RelationshipId) -> String {
+        format!("{}relationship:{}", self.base, id.as_uuid())
+    }
+
+    /// IRI of the named graph holding a space
+    pub fn space_iri(&self, space: &RelationshipSpace) -> String {
+        format!("{}space:{}", self.base, space.id)
+    }
+
+    fn term(&self, local: &str) -> String {
+        format!("{}{}", self.vocabulary, local)
+    }
+}
+
+// ============================================================================
+// Triples
+// ============================================================================
+
+/// Subject or object of a triple
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RdfTerm {
+    /// Named node
+    Iri(String),
+    /// Blank node label (without `_:`)
+    Blank(String),
+    /// Literal with optional datatype IRI
+    Literal {
+        /// Lexical value
+        value: String,
+        /// Datatype IRI (`None` = plain string)
+        datatype: Option<String>,
+    },
+}
+
+impl RdfTerm {
+    fn string(value: impl Into<String>) -> Self {
+        RdfTerm::Literal {
+            value: value.into(),
+            datatype: None,
+        }
+    }
+
+    fn typed(value: impl ToString, datatype: &str) -> Self {
+        RdfTerm::Literal {
+            value: value.to_string(),
+            datatype: Some(format!("{}{}", XSD, datatype)),
+        }
+    }
+}
+
+/// One RDF statement
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RdfTriple {
+    /// Subject
+    pub subject: RdfTerm,
+    /// Predicate IRI
+    pub predicate: String,
+    /// Object
+    pub object: RdfTerm,
+}
+
+/// All triples of a space, grouped by subject
+pub fn rdf_triples(space: &RelationshipSpace, mapping: &OntologyMapping) -> Vec<RdfTriple> {
+    let mut triples = Vec::new();
+
+    let mut edges: Vec<&EdgeConcept> = space.edges.values().collect();
+    edges.sort_by_key(|e| e.id.as_uuid());
+    for edge in edges {
+        let source = RdfTerm::Iri(mapping.entity_iri(&edge.source));
+        let target = RdfTerm::Iri(mapping.entity_iri(&edge.target));
+        let predicate = mapping.predicate(&edge.category);
+        if edge.is_active() {
+            triples.push(RdfTriple {
+                subject: source.clone(),
+                predicate: predicate.clone(),
+                object: target.clone(),
+            });
+        }
+        let mut statement = Subject::new(&mut triples, RdfTerm::Iri(mapping.relationship_iri(edge.id)));
+        statement.add(&format!("{}type", RDF), RdfTerm::Iri(format!("{}Statement", RDF)));
+        statement.add(&format!("{}subject", RDF), source);
+        statement.add(&format!("{}predicate", RDF), RdfTerm::Iri(predicate));
+        statement.add(&format!("{}object", RDF), target);
+        describe(
+            &mut statement,
+            mapping,
+            &edge.name,
+            &edge.category,
+            &format!("{:?}", edge.state),
+            &edge.quality,
+        );
+    }
+
+    let mut hyperedges: Vec<&HyperEdgeConcept> = space.hyperedges.values().collect();
+    hyperedges.sort_by_key(|h| h.id.as_uuid());
+    for hyperedge in hyperedges {
+        let mut participants: Vec<_> = hyperedge.participants.participants().collect();
+        participants.sort_by_key(|p| p.entity_ref.key().to_string());
+        let blank = |i: usize| RdfTerm::Blank(format!("p{}x{}", hyperedge.id.as_uuid().simple(), i));
+
+        let mut node = Subject::new(&mut triples, RdfTerm::Iri(mapping.relationship_iri(hyperedge.id)));
+        node.add(&format!("{}type", RDF), RdfTerm::Iri(mapping.term("HyperEdge")));
+        describe(
+            &mut node,
+            mapping,
+            &hyperedge.name,
+            &hyperedge.category,
+            &format!("{:?}", hyperedge.state),
+            &hyperedge.quality,
+        );
+        for i in 0..participants.len() {
+            node.add(&mapping.term("participant"), blank(i));
+        }
+        for (i, participant) in participants.into_iter().enumerate() {
+            let mut entry = Subject::new(&mut triples, blank(i));
+            entry.add(&mapping.term("entity"), RdfTerm::Iri(mapping.entity_iri(&participant.entity_ref)));
+            entry.add(&mapping.term("role"), RdfTerm::string(participant.role.display_name()));
+            entry.add(&mapping.term("weight"), RdfTerm::typed(participant.weight, "double"));
+        }
+    }
+
+    triples
+}
+
+/// Triples sharing a subject
+struct Subject<'a> {
+    triples: &'a mut Vec<RdfTriple>,
+    subject: RdfTerm,
+}
+
+impl<'a> Subject<'a> {
+    fn new(triples: &'a mut Vec<RdfTriple>, subject: RdfTerm) -> Self {
+        Self { triples, subject }
+    }
+
+    fn add(&mut self, predicate: &str, object: RdfTerm) {
+        self.triples.push(RdfTriple {
+            subject: self.subject.clone(),
+            predicate: predicate.to_string(),
+            object,
+        });
+    }
+}
+
+fn describe(
+    subject: &mut Subject<'_>,
+    mapping: &OntologyMapping,
+    name: &str,
+    category: &RelationshipCategory,
+    state: &str,
+    quality: &RelationshipQuality,
+) {
+    subject.add(&mapping.term("name"), RdfTerm::string(name));
+    subject.add(&mapping.term("category"), RdfTerm::string(category.display_name()));
+    subject.add(&mapping.term("state"), RdfTerm::string(state));
+    subject.add(&mapping.term("strength"), RdfTerm::typed(quality.strength, "double"));
+    subject.add(&mapping.term("trust"), RdfTerm::typed(quality.trust, "double"));
+    subject.add(&mapping.term("formality"), RdfTerm::string(format!("{:?}", quality.formality)));
+    subject.add(&mapping.term("reciprocity"), RdfTerm::typed(quality.reciprocity, "double"));
+    subject.add(
+        &mapping.term("validFrom"),
+        RdfTerm::typed(quality.duration.starts_at.to_rfc3339(), "dateTime"),
+    );
+    if let Some(ends_at) = quality.duration.ends_at {
+        subject.add(&mapping.term("validUntil"), RdfTerm::typed(ends_at.to_rfc3339(), "dateTime"));
+    }
+}
+
+// ============================================================================
+// Serialization
+// ============================================================================
+
+/// Write a space as Turtle
+pub fn export_turtle(space: &RelationshipSpace, mapping: &OntologyMapping) -> String {
+    let mut out = String::new();
+    for (name, namespace) in &mapping.prefixes {
+        out.push_str(&format!("@prefix {}: <{}> .\n", name, namespace));
+    }
+    out.push('\n');
+
+    let triples = rdf_triples(space, mapping);
+    let mut current: Option<&RdfTerm> = None;
+    for triple in &triples {
+        let predicate = if triple.predicate == format!("{}type", RDF) {
+            "a".to_string()
+        } else {
+            compact(&triple.predicate, mapping)
+        };
+        let object = turtle_term(&triple.object, mapping);
+        if current == Some(&triple.subject) {
+            out.push_str(&format!(" ;\n    {} {}", predicate, object));
+        } else {
+            if current.is_some() {
+                out.push_str(" .\n");
+            }
+            out.push_str(&format!("{} {} {}", turtle_term(&triple.subject, mapping), predicate, object));
+            current = Some(&triple.subject);
+        }
+    }
+    if current.is_some() {
+        out.push_str(" .\n");
+    }
+    out
+}
+
+/// Write a space as N-Quads, in a named graph for the space
+pub fn export_nquads(space: &RelationshipSpace, mapping: &OntologyMapping) -> String {
+    let graph = format!("<{}>", mapping.space_iri(space));
+    rdf_triples(space, mapping)
+        .iter()
+        .map(|t| {
+            format!(
+                "{} <{}> {} {} .\n",
+                nquads_term(&t.subject),
+                t.predicate,
+                nquads_term(&t.object),
+                graph
+            )
+        })
+        .collect()
+}
+
+fn nquads_term(term: &RdfTerm) -> String {
+    match term {
+        RdfTerm::Iri(iri) => format!("<{}>", iri),
+        RdfTerm::Blank(label) => format!("_:{}", label),
+        RdfTerm::Literal { value, datatype: None } => format!("\"{}\"", escape(value)),
+        RdfTerm::Literal {
+            value,
+            datatype: Some(datatype),
+        } => format!("\"{}\"^^<{}>", escape(value), datatype),
+    }
+}
+
+fn turtle_term(term: &RdfTerm, mapping: &OntologyMapping) -> String {
+    match term {
+        RdfTerm::Iri(iri) => compact(iri, mapping),
+        RdfTerm::Literal {
+            value,
+            datatype: Some(datatype),
+        } => format!("\"{}\"^^{}", escape(value), compact(datatype, mapping)),
+        other => nquads_term(other),
+    }
+}
+
+/// `prefix:local` if a prefix covers the IRI, `<iri>` otherwise
+fn compact(iri: &str, mapping: &OntologyMapping) -> String {
+    mapping
+        .prefixes
+        .iter()
+        .filter_map(|(name, namespace)| Some((name, iri.strip_prefix(namespace.as_str())?)))
+        .find(|(_, local)| {
+            local.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+                && local.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        })
+        .map(|(name, local)| format!("{}:{}", name, local))
+        .unwrap_or_else(|| format!("<{}>", iri))
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// "professional contact" -> "professionalContact"
+fn lower_camel(name: &str) -> String {
+    let mut words = name.split(|c: char| !c.is_ascii_alphanumeric()).filter(|w| !w.is_empty());
+    let mut camel = words.next().map(str::to_lowercase).unwrap_or_default();
+    for word in words {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            camel.push(first.to_ascii_uppercase());
+            camel.push_str(&chars.as_str().to_lowercase());
+        }
+    }
+    camel
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value_objects::ParticipantRole;
+    use cim_domain_spaces::TopologicalSpaceId;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_rdf_export_maps_categories_and_reifies_quality() {
+        let alice = EntityRef::person(Uuid::now_v7());
+        let bob = EntityRef::person(Uuid::now_v7());
+        let acme = EntityRef::organization(Uuid::now_v7());
+        let mut space = RelationshipSpace::new("Knowledge", TopologicalSpaceId::new());
+        let mut job = EdgeConcept::new("Works at", alice.clone(), acme.clone(), RelationshipCategory::Employment);
+        job.activate().unwrap();
+        space.add_edge(job.clone()).unwrap();
+        let mentoring = EdgeConcept::new("Mentors \"Bob\"", alice.clone(), bob.clone(), RelationshipCategory::Mentorship);
+        space.add_edge(mentoring).unwrap();
+        let mut team = HyperEdgeConcept::new("Squad", RelationshipCategory::Custom("squad".to_string()));
+        team.add_participant(alice.clone(), ParticipantRole::Leader, 1.0).unwrap();
+        team.add_participant(bob.clone(), ParticipantRole::Member, 0.5).unwrap();
+        space.add_hyperedge(team);
+
+        let mapping = OntologyMapping::standard()
+            .with_predicate(RelationshipCategory::Mentorship, "http://example.org/mentors");
+        let triples = rdf_triples(&space, &mapping);
+        let asserted: Vec<&RdfTriple> = triples
+            .iter()
+            .filter(|t| t.subject == RdfTerm::Iri(mapping.entity_iri(&alice)))
+            .collect();
+        // The proposed mentorship is only reified, not asserted
+        assert_eq!(asserted.len(), 1);
+        assert_eq!(asserted[0].predicate, "http://schema.org/worksFor");
+        assert!(triples.iter().any(|t| t.predicate == format!("{}predicate", RDF)
+            && t.object == RdfTerm::Iri("http://example.org/mentors".to_string())));
+        assert_eq!(
+            triples.iter().filter(|t| t.predicate == mapping.term("participant")).count(),
+            2
+        );
+
+        let turtle = export_turtle(&space, &mapping);
+        assert!(turtle.contains("@prefix schema: <http://schema.org/> ."));
+        let asserted = format!("<{}> schema:worksFor <{}> .", mapping.entity_iri(&alice), mapping.entity_iri(&acme));
+        assert!(turtle.contains(&asserted));
+        assert!(turtle.contains("a rdf:Statement ;"));
+        assert!(turtle.contains("rel:name \"Mentors \\\"Bob\\\"\""));
+        assert!(turtle.contains("rel:trust \""));
+        assert_eq!(
+            mapping.predicate(&RelationshipCategory::ProfessionalContact),
+            format!("{}professionalContact", RELATIONSHIP_VOCABULARY)
+        );
+
+        let graph = format!("<{}> .", mapping.space_iri(&space));
+        let nquads = export_nquads(&space, &mapping);
+        assert_eq!(nquads.lines().count(), triples.len());
+        assert!(nquads.lines().all(|l| l.ends_with(&graph)));
+    }
+}
@@ -0,0 +1,452 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Declarative Invariants for the Relationship Domain
+//!
+//! Business rules expressed as data rather than code, so each deployment
+//! can load its own rule set at startup without recompiling the crate.
+//!
+//! ## Rule Kinds
+//!
+//! - **Unique**: At most one live edge of a category per source, target, or
+//!   endpoint pair
+//! - **Endpoints**: Allowed entity types at each end of a category
+//! - **Cardinality**: Maximum live edges of a category per source or target
+//! - **QualityBounds**: Allowed range of a quality dimension
+//!
+//! ## Rule File Format
+//!
+//! ```json
+//! {
+//!   "invariants": [
+//!     { "rule": "endpoints", "category": "Employment",
+//!       "source_types": ["Person"], "target_types": ["Organization"] },
+//!     { "rule": "cardinality", "category": "Management",
+//!       "side": "target", "max": 1 },
+//!     { "rule": "quality_bounds", "category": "Ownership",
+//!       "dimension": "formality", "min": 0.75 }
+//!   ]
+//! }
+//! ```
+//!
+//! A rule without a `category` applies to every category.
+//!
+//! Role requirements of hyperedge categories are expressed separately, as
+//! role schemas (see `RoleSchemaRegistry`), and constraints issued by
+//! Policy entities as a `PolicySet`.
+
+use crate::aggregates::{EdgeConcept, HyperEdgeConcept, RelationshipSpace};
+use crate::quality::QualityPoint;
+use crate::value_objects::{EntityType, RelationshipCategory};
+use crate::{RelationshipError, RelationshipResult};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+mod policy;
+mod roles;
+
+pub use policy::{PolicyRule, PolicySet, PolicyViolation, RelationshipPolicy};
+pub use roles::{RoleConstraint, RoleSchema, RoleSchemaRegistry, RoleViolation};
+
+/// Which end of an edge a rule constrains
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum EndpointSide {
+    /// The source entity
+    Source,
+    /// The target entity
+    Target,
+    /// The (source, target) pair
+    Both,
+}
+
+/// A single declarative invariant
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "rule", rename_all = "snake_case")]
+pub enum Invariant {
+    /// At most one live edge of the category per endpoint (or pair)
+    Unique {
+        #[serde(default)]
+        category: Option<RelationshipCategory>,
+        scope: EndpointSide,
+    },
+    /// Allowed entity types at each end (empty = any)
+    Endpoints {
+        #[serde(default)]
+        category: Option<RelationshipCategory>,
+        #[serde(default)]
+        source_types: Vec<EntityType>,
+        #[serde(default)]
+        target_types: Vec<EntityType>,
+    },
+    /// Maximum live edges of the category per endpoint
+    Cardinality {
+        #[serde(default)]
+        category: Option<RelationshipCategory>,
+        side: EndpointSide,
+        max: usize,
+    },
+    /// Allowed range of a quality dimension
+    QualityBounds {
+        #[serde(default)]
+        category: Option<RelationshipCategory>,
+        dimension: String,
+        #[serde(default)]
+        min: Option<f64>,
+        #[serde(default)]
+        max: Option<f64>,
+    },
+}
+
+impl Invariant {
+    /// Category the rule is restricted to, if any
+    pub fn category(&self) -> Option<&RelationshipCategory> {
+        match self {
+            Invariant::Unique { category, .. }
+            | Invariant::Endpoints { category, .. }
+            | Invariant::Cardinality { category, .. }
+            | Invariant::QualityBounds { category, .. } => category.as_ref(),
+        }
+    }
+
+    /// Check if the rule applies to a category
+    pub fn applies_to(&self, category: &RelationshipCategory) -> bool {
+        self.category().is_none_or(|c| c == category)
+    }
+
+    /// Short description used in violation messages
+    pub fn describe(&self) -> String {
+        let scope = self
+            .category()
+            .map_or("all categories".to_string(), |c| c.display_name());
+        match self {
+            Invariant::Unique { scope: side, .. } => format!("unique {:?} ({})", side, scope),
+            Invariant::Endpoints { .. } => format!("endpoint types ({})", scope),
+            Invariant::Cardinality { side, max, .. } => {
+                format!("at most {} per {:?} ({})", max, side, scope)
+            }
+            Invariant::QualityBounds { dimension, min, max, .. } => {
+                format!("{} within [{:?}, {:?}] ({})", dimension, min, max, scope)
+            }
+        }
+    }
+
+    /// Check the rule definition itself
+    fn validate(&self) -> RelationshipResult<()> {
+        match self {
+            Invariant::Cardinality { side: EndpointSide::Both, .. } => Err(
+                RelationshipError::InvalidConfiguration(
+                    "cardinality rules constrain 'source' or 'target', not 'both'".to_string(),
+                ),
+            ),
+            Invariant::QualityBounds { dimension, min, max, .. } => {
+                if !QualityPoint::DIMENSIONS.contains(&dimension.as_str()) {
+                    return Err(RelationshipError::InvalidConfiguration(format!(
+                        "unknown quality dimension '{}'",
+                        dimension
+                    )));
+                }
+                if let (Some(min), Some(max)) = (min, max) {
+                    if min > max {
+                        return Err(RelationshipError::InvalidConfiguration(format!(
+                            "quality bounds for '{}' have min {} > max {}",
+                            dimension, min, max
+                        )));
+                    }
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A broken invariant
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InvariantViolation {
+    /// Position of the rule in its set
+    pub rule_index: usize,
+    /// Description of the rule
+    pub rule: String,
+    /// What was wrong
+    pub message: String,
+}
+
+impl std::fmt::Display for InvariantViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "rule #{} [{}]: {}", self.rule_index, self.rule, self.message)
+    }
+}
+
+/// A rule set loaded from configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InvariantSet {
+    /// The rules, evaluated in order
+    #[serde(default)]
+    pub invariants: Vec<Invariant>,
+}
+
+impl InvariantSet {
+    /// Create an empty rule set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a rule
+    pub fn with_invariant(mut self, invariant: Invariant) -> Self {
+        self.invariants.push(invariant);
+        self
+    }
+
+    /// Parse and validate a JSON rule set
+    pub fn from_json(json: &str) -> RelationshipResult<Self> {
+        let set: Self = serde_json::from_str(json)
+            .map_err(|e| RelationshipError::InvalidConfiguration(e.to_string()))?;
+        set.validate()?;
+        Ok(set)
+    }
+
+    /// Load and validate a JSON rule set from a file
+    pub fn from_file(path: impl AsRef<Path>) -> RelationshipResult<Self> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path).map_err(|e| {
+            RelationshipError::InvalidConfiguration(format!("{}: {}", path.display(), e))
+        })?;
+        Self::from_json(&json)
+    }
+
+    /// Check every rule definition
+    pub fn validate(&self) -> RelationshipResult<()> {
+        self.invariants.iter().try_for_each(Invariant::validate)
+    }
+}
+
+/// Evaluates an InvariantSet against relationships
+#[derive(Debug, Clone, Default)]
+pub struct InvariantEngine {
+    rules: InvariantSet,
+}
+
+impl InvariantEngine {
+    /// Create an engine for a validated rule set
+    pub fn new(rules: InvariantSet) -> RelationshipResult<Self> {
+        rules.validate()?;
+        Ok(Self { rules })
+    }
+
+    /// The loaded rules
+    pub fn rules(&self) -> &InvariantSet {
+        &self.rules
+    }
+
+    /// Check an edge against every applicable rule
+    ///
+    /// Uniqueness and cardinality count the live (non-terminal) edges
+    /// already in the space, excluding the edge itself.
+    pub fn check_edge(&self, edge: &EdgeConcept, space: &RelationshipSpace) -> Vec<InvariantViolation> {
+        let point = edge.quality_point();
+        let mut violations = Vec::new();
+
+        for (rule_index, rule) in self.rules.invariants.iter().enumerate() {
+            if !rule.applies_to(&edge.category) {
+                continue;
+            }
+            let mut violate = |message: String| {
+                violations.push(InvariantViolation {
+                    rule_index,
+                    rule: rule.describe(),
+                    message,
+                })
+            };
+
+            match rule {
+                Invariant::Unique { scope, .. } => {
+                    if count_live(space, edge, *scope) > 0 {
+                        violate(format!(
+                            "a live {} edge already exists for {}",
+                            edge.category.display_name(),
+                            scope_label(edge, *scope)
+                        ));
+                    }
+                }
+                Invariant::Endpoints { source_types, target_types, .. } => {
+                    if !source_types.is_empty() && !source_types.contains(&edge.source.entity_type) {
+                        violate(format!("source type {:?} not allowed", edge.source.entity_type));
+                    }
+                    if !target_types.is_empty() && !target_types.contains(&edge.target.entity_type) {
+                        violate(format!("target type {:?} not allowed", edge.target.entity_type));
+                    }
+                }
+                Invariant::Cardinality { side, max, .. } => {
+                    let existing = count_live(space, edge, *side);
+                    if existing + 1 > *max {
+                        violate(format!(
+                            "{} would have {} live {} edges",
+                            scope_label(edge, *side),
+                            existing + 1,
+                            edge.category.display_name()
+                        ));
+                    }
+                }
+                Invariant::QualityBounds { dimension, min, max, .. } => {
+                    if let Some(message) = check_bounds(&point, dimension, *min, *max) {
+                        violate(message);
+                    }
+                }
+            }
+        }
+
+        violations
+    }
+
+    /// Check a hyperedge against the applicable quality bound rules
+    pub fn check_hyperedge(&self, hyperedge: &HyperEdgeConcept) -> Vec<InvariantViolation> {
+        let point = hyperedge.quality_point();
+        self.rules
+            .invariants
+            .iter()
+            .enumerate()
+            .filter(|(_, rule)| rule.applies_to(&hyperedge.category))
+            .filter_map(|(rule_index, rule)| match rule {
+                Invariant::QualityBounds { dimension, min, max, .. } => {
+                    check_bounds(&point, dimension, *min, *max).map(|message| InvariantViolation {
+                        rule_index,
+                        rule: rule.describe(),
+                        message,
+                    })
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Reject an edge that breaks any rule
+    pub fn enforce_edge(&self, edge: &EdgeConcept, space: &RelationshipSpace) -> RelationshipResult<()> {
+        into_result(self.check_edge(edge, space))
+    }
+
+    /// Reject a hyperedge that breaks any rule
+    pub fn enforce_hyperedge(&self, hyperedge: &HyperEdgeConcept) -> RelationshipResult<()> {
+        into_result(self.check_hyperedge(hyperedge))
+    }
+}
+
+fn into_result(violations: Vec<InvariantViolation>) -> RelationshipResult<()> {
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        let messages: Vec<String> = violations.iter().map(|v| v.to_string()).collect();
+        Err(RelationshipError::InvariantViolated(messages.join("; ")))
+    }
+}
+
+/// Count live edges of the same category sharing the given endpoint(s)
+fn count_live(space: &RelationshipSpace, edge: &EdgeConcept, side: EndpointSide) -> usize {
+    space
+        .edges
+        .values()
+        .filter(|other| {
+            other.id != edge.id && other.category == edge.category && !other.state.is_closed()
+        })
+        .filter(|other| match side {
+            EndpointSide::Source => other.source.same_entity(&edge.source),
+            EndpointSide::Target => other.target.same_entity(&edge.target),
+            EndpointSide::Both => {
+                other.source.same_entity(&edge.source) && other.target.same_entity(&edge.target)
+            }
+        })
+        .count()
+}
+
+fn scope_label(edge: &EdgeConcept, side: EndpointSide) -> String {
+    match side {
+        EndpointSide::Source => format!("source {}", edge.source),
+        EndpointSide::Target => format!("target {}", edge.target),
+        EndpointSide::Both => format!("{} -> {}", edge.source, edge.target),
+    }
+}
+
+fn check_bounds(point: &QualityPoint, dimension: &str, min: Option<f64>, max: Option<f64>) -> Option<String> {
+    let value = point.get(dimension)?;
+    if min.is_some_and(|min| value < min) || max.is_some_and(|max| value > max) {
+        Some(format!("{} = {:.3} out of bounds", dimension, value))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quality::RelationshipQuality;
+    use crate::value_objects::EntityRef;
+    use cim_domain_spaces::TopologicalSpaceId;
+    use uuid::Uuid;
+
+    const RULES: &str = r#"{
+        "invariants": [
+            { "rule": "endpoints", "category": "Employment",
+              "source_types": ["Person"], "target_types": ["Organization"] },
+            { "rule": "cardinality", "category": "Management", "side": "target", "max": 1 },
+            { "rule": "quality_bounds", "category": "Ownership", "dimension": "formality", "min": 0.75 }
+        ]
+    }"#;
+
+    #[test]
+    fn test_load_rules() {
+        let set = InvariantSet::from_json(RULES).unwrap();
+        assert_eq!(set.invariants.len(), 3);
+
+        let bad = r#"{ "invariants": [ { "rule": "quality_bounds", "dimension": "risk" } ] }"#;
+        assert!(matches!(
+            InvariantSet::from_json(bad),
+            Err(RelationshipError::InvalidConfiguration(_))
+        ));
+    }
+
+    #[test]
+    fn test_endpoint_and_quality_rules() {
+        let engine = InvariantEngine::new(InvariantSet::from_json(RULES).unwrap()).unwrap();
+        let space = RelationshipSpace::new("Rules", TopologicalSpaceId::new());
+
+        let wrong_way = EdgeConcept::new(
+            "Backwards",
+            EntityRef::organization(Uuid::now_v7()),
+            EntityRef::person(Uuid::now_v7()),
+            RelationshipCategory::Employment,
+        );
+        assert_eq!(engine.check_edge(&wrong_way, &space).len(), 2);
+
+        let informal_ownership = EdgeConcept::new(
+            "Handshake",
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::concept(Uuid::now_v7()),
+            RelationshipCategory::Ownership,
+        )
+        .with_quality(RelationshipQuality::default_friendship());
+        assert!(matches!(
+            engine.enforce_edge(&informal_ownership, &space),
+            Err(RelationshipError::InvariantViolated(_))
+        ));
+    }
+
+    #[test]
+    fn test_cardinality_rule() {
+        let engine = InvariantEngine::new(InvariantSet::from_json(RULES).unwrap()).unwrap();
+        let mut space = RelationshipSpace::new("Rules", TopologicalSpaceId::new());
+        let report = EntityRef::person(Uuid::now_v7());
+
+        let manages = |manager: EntityRef| {
+            EdgeConcept::new("Manages", manager, report.clone(), RelationshipCategory::Management)
+        };
+
+        let first = manages(EntityRef::person(Uuid::now_v7()));
+        assert!(engine.enforce_edge(&first, &space).is_ok());
+        space.add_edge(first).unwrap();
+
+        let second = manages(EntityRef::person(Uuid::now_v7()));
+        assert!(engine.enforce_edge(&second, &space).is_err());
+    }
+}
@@ -0,0 +1,152 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Policy Constraints
+//!
+//! Policy entities (cim-domain-policy) can constrain which relationships
+//! may exist. Each `RelationshipPolicy` carries a reference to the Policy
+//! entity that issued it, so a refused command names the policy behind
+//! the refusal:
+//!
+//! ```text
+//! policy:{sanctions}  "no ownership of sanctioned organizations"
+//!     forbid Ownership, target in [organization:{a}, organization:{b}]
+//!
+//! CreateEdge(holding -Ownership-> organization:{a})
+//!     ==> PolicyViolated [policy:{sanctions} ...]
+//! ```
+//!
+//! Policies are evaluated when a command decides an edge: when it is
+//! created, activated, re-proposed, or its endpoints or category change.
+//! Edges already in a space are not re-checked when a policy is added, and
+//! ending a forbidden edge is always allowed.
+//!
+//! Policies are enacted and revoked by command (`EnactPolicy`,
+//! `RevokePolicy`), so they are part of the event history; the
+//! cross-domain handler issues both in reaction to cim-domain-policy's
+//! `policy.events.policy_enacted` / `policy_revoked`.
+
+use super::EndpointSide;
+use crate::aggregates::EdgeConcept;
+use crate::value_objects::{EntityRef, RelationshipCategory};
+use serde::{Deserialize, Serialize};
+
+/// What a policy forbids
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "rule", rename_all = "snake_case")]
+pub enum PolicyRule {
+    /// No edges of the category with any of the entities at the given end
+    /// (`Both` = either end)
+    Forbid {
+        #[serde(default)]
+        category: Option<RelationshipCategory>,
+        side: EndpointSide,
+        entities: Vec<EntityRef>,
+    },
+}
+
+impl PolicyRule {
+    /// Why an edge breaks this rule, if it does
+    fn violated_by(&self, edge: &EdgeConcept) -> Option<String> {
+        match self {
+            PolicyRule::Forbid {
+                category,
+                side,
+                entities,
+            } => {
+                if category.as_ref().is_some_and(|c| *c != edge.category) {
+                    return None;
+                }
+                let ends: &[&EntityRef] = match side {
+                    EndpointSide::Source => &[&edge.source],
+                    EndpointSide::Target => &[&edge.target],
+                    EndpointSide::Both => &[&edge.source, &edge.target],
+                };
+                ends.iter()
+                    .find(|end| entities.iter().any(|e| e.same_entity(end)))
+                    .map(|end| format!("{} edges with {} are forbidden", edge.category.display_name(), end))
+            }
+        }
+    }
+}
+
+/// A constraint issued by a Policy entity
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RelationshipPolicy {
+    /// The Policy entity the constraint comes from
+    pub policy: EntityRef,
+    /// Human-readable name
+    pub name: String,
+    /// What the policy forbids
+    pub rule: PolicyRule,
+}
+
+impl RelationshipPolicy {
+    /// Create a policy constraint
+    pub fn new(policy: EntityRef, name: impl Into<String>, rule: PolicyRule) -> Self {
+        Self {
+            policy,
+            name: name.into(),
+            rule,
+        }
+    }
+}
+
+/// A policy an edge does not comply with
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PolicyViolation {
+    /// The Policy entity
+    pub policy: EntityRef,
+    /// Name of the policy
+    pub name: String,
+    /// What the edge does that the policy forbids
+    pub reason: String,
+}
+
+impl std::fmt::Display for PolicyViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({}): {}", self.name, self.policy, self.reason)
+    }
+}
+
+/// Policy constraints in force in a space
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PolicySet {
+    /// Constraints, in the order they were enacted
+    pub policies: Vec<RelationshipPolicy>,
+}
+
+impl PolicySet {
+    /// Create an empty set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enact a policy
+    pub fn with_policy(mut self, policy: RelationshipPolicy) -> Self {
+        self.policies.push(policy);
+        self
+    }
+
+    /// Withdraw every constraint issued by a Policy entity
+    pub fn revoke(&mut self, policy: &EntityRef) {
+        self.policies.retain(|p| !p.policy.same_entity(policy));
+    }
+
+    /// Check an edge against every policy
+    pub fn check(&self, edge: &EdgeConcept) -> Vec<PolicyViolation> {
+        self.policies
+            .iter()
+            .filter_map(|p| {
+                p.rule.violated_by(edge).map(|reason| PolicyViolation {
+                    policy: p.policy.clone(),
+                    name: p.name.clone(),
+                    reason,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,210 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Role Schemas for Hyperedge Categories
+//!
+//! A hyperedge category often implies which roles must be filled: a
+//! document collaboration without an approver cannot complete. A
+//! `RoleSchema` bounds how many participants may hold each role, and a
+//! `RoleSchemaRegistry` binds schemas to categories.
+//!
+//! ## Example
+//!
+//! ```text
+//! DocumentCollaboration   Author    1..
+//!                         Reviewer  1..
+//!                         Approver  1..=1
+//! ```
+//!
+//! Schemas are checked when a hyperedge is activated and whenever the
+//! participants of a live hyperedge change. A forming hyperedge may be
+//! incomplete.
+
+use crate::value_objects::{IncidenceMatrix, ParticipantRole, RelationshipCategory};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Bounds on how many participants hold a role
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoleConstraint {
+    /// The constrained role
+    pub role: ParticipantRole,
+    /// Fewest holders allowed
+    #[serde(default)]
+    pub min: usize,
+    /// Most holders allowed (`None` = unbounded)
+    #[serde(default)]
+    pub max: Option<usize>,
+}
+
+impl RoleConstraint {
+    /// At least `min` holders
+    pub fn at_least(role: ParticipantRole, min: usize) -> Self {
+        Self { role, min, max: None }
+    }
+
+    /// Exactly `count` holders
+    pub fn exactly(role: ParticipantRole, count: usize) -> Self {
+        Self {
+            role,
+            min: count,
+            max: Some(count),
+        }
+    }
+
+    /// Between `min` and `max` holders, inclusive
+    pub fn between(role: ParticipantRole, min: usize, max: usize) -> Self {
+        Self {
+            role,
+            min,
+            max: Some(max),
+        }
+    }
+
+    fn allows(&self, count: usize) -> bool {
+        count >= self.min && self.max.is_none_or(|max| count <= max)
+    }
+}
+
+/// A role constraint a hyperedge does not meet
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoleViolation {
+    /// Category of the hyperedge
+    pub category: RelationshipCategory,
+    /// The constrained role
+    pub role: ParticipantRole,
+    /// Fewest holders allowed
+    pub min: usize,
+    /// Most holders allowed
+    pub max: Option<usize>,
+    /// Holders present
+    pub actual: usize,
+}
+
+impl std::fmt::Display for RoleViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let bounds = match self.max {
+            Some(max) if max == self.min => format!("exactly {}", max),
+            Some(max) => format!("{}..={}", self.min, max),
+            None => format!("at least {}", self.min),
+        };
+        write!(
+            f,
+            "{:?} requires {} {}, found {}",
+            self.category,
+            bounds,
+            self.role.display_name(),
+            self.actual
+        )
+    }
+}
+
+/// Role constraints of one hyperedge category
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RoleSchema {
+    /// Constraints, one per role
+    pub constraints: Vec<RoleConstraint>,
+}
+
+impl RoleSchema {
+    /// Create a schema without constraints
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a constraint, replacing any existing one for its role
+    pub fn with_constraint(mut self, constraint: RoleConstraint) -> Self {
+        self.constraints.retain(|c| c.role != constraint.role);
+        self.constraints.push(constraint);
+        self
+    }
+
+    /// Check participants against every constraint
+    pub fn check(&self, category: &RelationshipCategory, participants: &IncidenceMatrix) -> Vec<RoleViolation> {
+        self.constraints
+            .iter()
+            .filter_map(|c| {
+                let actual = participants.participants_with_role(&c.role).len();
+                (!c.allows(actual)).then(|| RoleViolation {
+                    category: category.clone(),
+                    role: c.role.clone(),
+                    min: c.min,
+                    max: c.max,
+                    actual,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Role schemas bound to hyperedge categories
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RoleSchemaRegistry {
+    /// Per-category schemas; unbound categories are unconstrained
+    #[serde(with = "crate::value_objects::category_map")]
+    pub schemas: HashMap<RelationshipCategory, RoleSchema>,
+}
+
+impl RoleSchemaRegistry {
+    /// Create a registry without schemas
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bind a schema to a category, replacing any existing binding
+    pub fn with_schema(mut self, category: RelationshipCategory, schema: RoleSchema) -> Self {
+        self.schemas.insert(category, schema);
+        self
+    }
+
+    /// Schema for a category
+    pub fn schema(&self, category: &RelationshipCategory) -> Option<&RoleSchema> {
+        self.schemas.get(category)
+    }
+
+    /// Check participants against the schema of their category
+    pub fn check(&self, category: &RelationshipCategory, participants: &IncidenceMatrix) -> Vec<RoleViolation> {
+        self.schema(category)
+            .map(|schema| schema.check(category, participants))
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value_objects::EntityRef;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_role_schema_check() {
+        let category = RelationshipCategory::Custom("DocumentCollaboration".to_string());
+        let registry = RoleSchemaRegistry::new().with_schema(
+            category.clone(),
+            RoleSchema::new()
+                .with_constraint(RoleConstraint::at_least(ParticipantRole::Author, 1))
+                .with_constraint(RoleConstraint::at_least(ParticipantRole::Reviewer, 1))
+                .with_constraint(RoleConstraint::exactly(ParticipantRole::Approver, 1)),
+        );
+
+        let mut participants = IncidenceMatrix::new();
+        participants.add_participant(EntityRef::person(Uuid::now_v7()), ParticipantRole::Author, 1.0);
+        participants.add_participant(EntityRef::person(Uuid::now_v7()), ParticipantRole::Approver, 1.0);
+        participants.add_participant(EntityRef::person(Uuid::now_v7()), ParticipantRole::Approver, 1.0);
+
+        let violations = registry.check(&category, &participants);
+        assert_eq!(violations.len(), 2);
+        assert!(violations.iter().any(|v| v.role == ParticipantRole::Reviewer && v.actual == 0));
+        assert!(violations.iter().any(|v| v.role == ParticipantRole::Approver && v.actual == 2));
+        assert!(violations[1].to_string().contains("exactly 1 approver"));
+
+        // Unbound categories are unconstrained
+        assert!(registry.check(&RelationshipCategory::Membership, &participants).is_empty());
+
+        // Schemas round-trip through configuration
+        let json = serde_json::to_string(&registry).unwrap();
+        let restored: RoleSchemaRegistry = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.schema(&category), registry.schema(&category));
+    }
+}
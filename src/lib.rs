@@ -73,14 +73,22 @@
 //! ```
 
 pub mod aggregates;
+pub mod algebra;
 pub mod value_objects;
 pub mod events;
 pub mod commands;
+pub mod queries;
 pub mod infrastructure;
 pub mod projections;
 pub mod services;
 pub mod nats;
 pub mod cross_domain;
+pub mod graph;
+pub mod interop;
+pub mod invariants;
+pub mod api;
+#[cfg(feature = "schema")]
+pub mod schema;
 
 // Quality dimension module for Gärdenfors conceptual spaces
 pub mod quality;
@@ -100,13 +108,14 @@ pub use cim_domain_spaces::{
 };
 
 // Re-export main types
-pub use aggregates::{EdgeConcept, HyperEdgeConcept, RelationshipSpace};
+pub use aggregates::{EdgeConcept, HyperEdgeConcept, RelationshipSpace, RelationshipTemplate, TessellationSeeds};
 pub use value_objects::{
-    EntityRef, EntityType, RelationshipId, RelationshipCategory,
-    ValidityPeriod, IncidenceMatrix, ParticipantRole, Formality,
+    EntityRef, EntityKey, EntityType, RelationshipId, RelationshipCategory,
+    ValidityPeriod, IncidenceMatrix, ParticipantGroup, ParticipantRole, ActivationMode, Formality, PropertyChange, Redirect, Rejection, ReproposalPolicy, TagMatch, TagStats, WeightChange,
 };
 pub use events::RelationshipEvent;
 pub use commands::RelationshipCommand;
+pub use queries::RelationshipQuery;
 pub use quality::{RelationshipQuality, QualityPoint};
 
 // Domain-specific error types
@@ -126,12 +135,45 @@ pub enum RelationshipError {
     #[error("Invalid state transition: {0}")]
     InvalidStateTransition(String),
 
+    #[error("Cyclic dependency: {0}")]
+    CyclicDependency(String),
+
+    #[error("Invariant violated: {0}")]
+    InvariantViolated(String),
+
+    #[error("Invalid configuration: {0}")]
+    InvalidConfiguration(String),
+
+    #[error("No composition rule for {0}")]
+    UnknownComposition(String),
+
+    #[error("Role constraints violated: {}", .0.iter().map(ToString::to_string).collect::<Vec<_>>().join("; "))]
+    RoleConstraintViolated(Vec<invariants::RoleViolation>),
+
+    #[error("Policy violated: {}", .0.iter().map(ToString::to_string).collect::<Vec<_>>().join("; "))]
+    PolicyViolated(Vec<invariants::PolicyViolation>),
+
+    #[error("Batch item {index} rejected: {reason}")]
+    BatchItemRejected { index: usize, reason: String },
+
+    #[error("Insufficient evidence: {0}")]
+    InsufficientEvidence(String),
+
     #[error("Hyperedge requires at least 2 participants")]
     InsufficientParticipants,
 
     #[error("CID resolution failed: {0}")]
     CidResolutionFailed(String),
 
+    #[error("Invalid relationship document: {0}")]
+    InvalidDocument(String),
+
+    #[error("Transport error: {0}")]
+    TransportError(String),
+
+    #[error("Serialization error: {0}")]
+    SerializationError(String),
+
     #[error("Cross-domain event failed: {0}")]
     CrossDomainEventFailed(String),
 
@@ -0,0 +1,469 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Relationship Message Bus
+//!
+//! Typed command/query/event plumbing over any `Transport`. Commands and
+//! queries use request/reply; events are published to the events stream.
+//!
+//! ```text
+//! send_command  --> relationship.commands.{command_type}  --> CommandResponse
+//! query         --> relationship.queries.{query_type}     --> QueryResult
+//! system_query  --> relationship.queries.system.{name}    --> SystemResult
+//! publish_event --> relationship.events.{event_type}      --> stored by the events stream
+//! ```
+//!
+//! Events and commands are JSON unless the bus is given another
+//! `WireFormat`; the format is announced in the `Content-Type` header and
+//! incoming events are decoded by theirs. Queries are always JSON. Events
+//! can instead go out as CloudEvents envelopes or Avro records (feature
+//! `avro`), which subscribers decode the same way.
+//!
+//! Events are published with `Transport::publish_persisted`: over NATS a
+//! publish returns once the events stream has stored the event, and fails
+//! if no stream captures it.
+
+use super::cloudevents::{CloudEvent, CloudEventEnvelope};
+use super::subjects::RelationshipSubjects;
+use super::transport::{Transport, TransportMessage};
+use super::wire::{MessageHeaders, WireFormat};
+use crate::commands::RelationshipCommand;
+use crate::events::RelationshipEvent;
+#[cfg(feature = "avro")]
+use crate::interop::AvroEventCodec;
+use crate::queries::{QueryResult, RelationshipQuery, SystemQuery, SystemResult};
+use crate::{RelationshipError, RelationshipResult};
+use bytes::Bytes;
+use futures::{Stream, StreamExt};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::pin::Pin;
+
+/// Reply to a command sent over the bus
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CommandResponse {
+    /// Whether the command was accepted
+    pub accepted: bool,
+    /// Events produced by the command
+    pub events: Vec<RelationshipEvent>,
+    /// Rejection reason
+    pub error: Option<String>,
+}
+
+impl CommandResponse {
+    /// Accepted command with the events it produced
+    pub fn accepted(events: Vec<RelationshipEvent>) -> Self {
+        Self {
+            accepted: true,
+            events,
+            error: None,
+        }
+    }
+
+    /// Rejected command
+    pub fn rejected(error: impl Into<String>) -> Self {
+        Self {
+            accepted: false,
+            events: Vec::new(),
+            error: Some(error.into()),
+        }
+    }
+}
+
+/// Stream of decoded relationship events
+pub type EventStream = Pin<Box<dyn Stream<Item = RelationshipEvent> + Send>>;
+
+/// Typed relationship messaging over a transport
+#[derive(Debug, Clone)]
+pub struct RelationshipBus<T: Transport> {
+    transport: T,
+    format: WireFormat,
+    envelope: Option<CloudEventEnvelope>,
+    #[cfg(feature = "avro")]
+    avro: Option<AvroEventCodec>,
+}
+
+impl<T: Transport> RelationshipBus<T> {
+    /// Create a bus over a transport
+    pub fn new(transport: T) -> Self {
+        Self {
+            transport,
+            format: WireFormat::default(),
+            envelope: None,
+            #[cfg(feature = "avro")]
+            avro: None,
+        }
+    }
+
+    /// Send events and commands in another format
+    ///
+    /// Responders must answer requests in the format they arrive in.
+    pub fn with_format(mut self, format: WireFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Publish events wrapped in CloudEvents envelopes
+    ///
+    /// Commands and queries keep the bus's `WireFormat`.
+    pub fn with_cloud_events(mut self, envelope: CloudEventEnvelope) -> Self {
+        self.envelope = Some(envelope);
+        self
+    }
+
+    /// Publish events as single-object Avro records
+    ///
+    /// Commands and queries keep the bus's `WireFormat`; a CloudEvents
+    /// envelope takes precedence.
+    #[cfg(feature = "avro")]
+    pub fn with_avro(mut self, codec: AvroEventCodec) -> Self {
+        self.avro = Some(codec);
+        self
+    }
+
+    /// Format of outgoing payloads
+    pub fn format(&self) -> WireFormat {
+        self.format
+    }
+
+    /// The underlying transport
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    /// Publish a domain event
+    pub async fn publish_event(&self, event: &RelationshipEvent) -> RelationshipResult<()> {
+        let (headers, payload) = self.encode_event(event)?;
+        self.transport
+            .publish_persisted(&RelationshipSubjects::event(event), headers, payload)
+            .await
+    }
+
+    /// Publish an event already wrapped in a CloudEvent, e.g. one carrying
+    /// trace context
+    pub async fn publish_cloud_event(&self, cloud_event: &CloudEvent) -> RelationshipResult<()> {
+        let event = cloud_event.to_event()?;
+        self.transport
+            .publish_persisted(
+                &RelationshipSubjects::event(&event),
+                CloudEvent::headers(),
+                cloud_event.encode()?,
+            )
+            .await
+    }
+
+    /// Publish many events, e.g. those of a batch command
+    ///
+    /// All events are encoded before any is sent, then published one after
+    /// another in order. Publishing stops at the first failure; the events
+    /// before it have gone out.
+    pub async fn publish_events(&self, events: &[RelationshipEvent]) -> RelationshipResult<()> {
+        let messages = events
+            .iter()
+            .map(|event| Ok((RelationshipSubjects::event(event), self.encode_event(event)?)))
+            .collect::<RelationshipResult<Vec<_>>>()?;
+        for (subject, (headers, payload)) in messages {
+            self.transport.publish_persisted(&subject, headers, payload).await?;
+        }
+        Ok(())
+    }
+
+    /// Send a command and wait for its response
+    pub async fn send_command(
+        &self,
+        command: &RelationshipCommand,
+    ) -> RelationshipResult<CommandResponse> {
+        let reply = self
+            .transport
+            .request_with_headers(
+                &RelationshipSubjects::command(command),
+                self.format.headers(),
+                self.format.encode(command)?,
+            )
+            .await?;
+        self.format.decode(&reply)
+    }
+
+    /// Run a query and wait for its result
+    pub async fn query(&self, query: &RelationshipQuery) -> RelationshipResult<QueryResult> {
+        let reply = self
+            .transport
+            .request_with_headers(
+                &RelationshipSubjects::query(query),
+                WireFormat::Json.headers(),
+                encode(query)?,
+            )
+            .await?;
+        decode(&reply)
+    }
+
+    /// Run a system query and wait for its result
+    pub async fn system_query(&self, query: &SystemQuery) -> RelationshipResult<SystemResult> {
+        let reply = self
+            .transport
+            .request_with_headers(
+                &RelationshipSubjects::system_query(query),
+                WireFormat::Json.headers(),
+                encode(query)?,
+            )
+            .await?;
+        decode(&reply)
+    }
+
+    /// Subscribe to every relationship event
+    ///
+    /// Each message is decoded in the format its headers name, CloudEvents
+    /// envelopes unwrapped; messages that do not decode as relationship
+    /// events are logged and skipped.
+    pub async fn subscribe_events(&self) -> RelationshipResult<EventStream> {
+        let messages = self
+            .transport
+            .subscribe(&RelationshipSubjects::all_events())
+            .await?;
+        Ok(Box::pin(messages.filter_map(|message| async move {
+            decode_event(&message)
+                .map_err(|e| tracing::warn!("undecodable event on {} skipped: {}", message.subject, e))
+                .ok()
+        })))
+    }
+
+    fn encode_event(
+        &self,
+        event: &RelationshipEvent,
+    ) -> RelationshipResult<(MessageHeaders, Bytes)> {
+        if let Some(envelope) = &self.envelope {
+            return Ok((CloudEvent::headers(), envelope.wrap(event)?.encode()?));
+        }
+        #[cfg(feature = "avro")]
+        if let Some(codec) = &self.avro {
+            return Ok((codec.metadata(), Bytes::from(codec.encode(event)?)));
+        }
+        Ok((self.format.headers(), self.format.encode(event)?))
+    }
+}
+
+/// Decode a received event in the format its headers name
+pub(crate) fn decode_event(message: &TransportMessage) -> RelationshipResult<RelationshipEvent> {
+    if CloudEvent::is_cloudevent(&message.headers) {
+        return CloudEvent::decode(&message.payload)?.to_event();
+    }
+    #[cfg(feature = "avro")]
+    if AvroEventCodec::is_avro(&message.headers) {
+        return AvroEventCodec::standard()?.decode(&message.payload);
+    }
+    WireFormat::from_headers(&message.headers)?.decode(&message.payload)
+}
+
+/// Encode a message payload as JSON
+pub fn encode<M: Serialize>(message: &M) -> RelationshipResult<Bytes> {
+    serde_json::to_vec(message)
+        .map(Bytes::from)
+        .map_err(|e| RelationshipError::SerializationError(e.to_string()))
+}
+
+/// Decode a JSON message payload
+pub fn decode<M: DeserializeOwned>(payload: &[u8]) -> RelationshipResult<M> {
+    serde_json::from_slice(payload).map_err(|e| RelationshipError::SerializationError(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregates::RelationshipSpace;
+    use crate::commands::{CreateEdge, EdgeCommand};
+    use crate::events::{EdgeCreated, EdgeEvent};
+    use crate::nats::MockTransport;
+    use crate::queries::{AnalyticsQuery, CommunityQuery};
+    use crate::value_objects::{EntityRef, RelationshipCategory, RelationshipId};
+    use cim_domain::MessageIdentity;
+    use cim_domain_spaces::{ConceptId, TopologicalSpaceId};
+    use uuid::Uuid;
+
+    fn create_edge() -> RelationshipCommand {
+        RelationshipCommand::Edge(EdgeCommand::CreateEdge(CreateEdge {
+            identity: MessageIdentity::new_root(),
+            edge_id: RelationshipId::new(),
+            source: EntityRef::person(Uuid::now_v7()),
+            target: EntityRef::person(Uuid::now_v7()),
+            category: RelationshipCategory::Friendship,
+            name: "Knows".to_string(),
+            quality: None,
+            created_by: "test".to_string(),
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_command_round_trip_and_events() {
+        let transport = MockTransport::new();
+        let bus = RelationshipBus::new(transport.clone());
+        let mut events = bus.subscribe_events().await.unwrap();
+
+        // Stand-in command handler: accept and echo an EdgeCreated event
+        transport.on_request(RelationshipSubjects::all_commands(), |message| {
+            let command: RelationshipCommand = decode(&message.payload)?;
+            let RelationshipCommand::Edge(EdgeCommand::CreateEdge(create)) = command else {
+                return encode(&CommandResponse::rejected("unsupported"));
+            };
+            encode(&CommandResponse::accepted(vec![RelationshipEvent::Edge(
+                EdgeEvent::EdgeCreated(EdgeCreated {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_root(),
+                    edge_id: create.edge_id,
+                    concept_id: ConceptId::new(),
+                    source: create.source,
+                    target: create.target,
+                    category: create.category,
+                    name: create.name,
+                    created_by: create.created_by,
+                    created_at: chrono::Utc::now(),
+                }),
+            )]))
+        });
+
+        let response = bus.send_command(&create_edge()).await.unwrap();
+        assert!(response.accepted);
+        assert_eq!(response.events.len(), 1);
+
+        bus.publish_event(&response.events[0]).await.unwrap();
+        let observed = events.next().await.unwrap();
+        assert_eq!(observed.event_type(), "edge_created");
+        assert_eq!(transport.events().len(), 1);
+        assert_eq!(
+            transport.published_on("relationship.commands.create_edge").len(),
+            1
+        );
+    }
+
+    #[cfg(feature = "protobuf")]
+    #[tokio::test]
+    async fn test_protobuf_negotiated_by_header() {
+        let transport = MockTransport::new();
+        let bus = RelationshipBus::new(transport.clone()).with_format(WireFormat::Protobuf);
+        let mut events = RelationshipBus::new(transport.clone()).subscribe_events().await.unwrap();
+
+        // Responder answers in the format the request arrived in
+        transport.on_request(RelationshipSubjects::all_commands(), |message| {
+            let format = WireFormat::from_headers(&message.headers)?;
+            assert_eq!(format, WireFormat::Protobuf);
+            let _: RelationshipCommand = format.decode(&message.payload)?;
+            format.encode(&CommandResponse::rejected("read only"))
+        });
+        let response = bus.send_command(&create_edge()).await.unwrap();
+        assert_eq!(response.error.as_deref(), Some("read only"));
+
+        let event = RelationshipEvent::Edge(EdgeEvent::EdgeCreated(EdgeCreated {
+            event_id: Uuid::now_v7(),
+            identity: MessageIdentity::new_root(),
+            edge_id: RelationshipId::new(),
+            concept_id: ConceptId::new(),
+            source: EntityRef::person(Uuid::now_v7()),
+            target: EntityRef::person(Uuid::now_v7()),
+            category: RelationshipCategory::Friendship,
+            name: "Knows".to_string(),
+            created_by: "test".to_string(),
+            created_at: chrono::Utc::now(),
+        }));
+        bus.publish_event(&event).await.unwrap();
+        // A JSON subscriber still decodes it, and so does the recorder
+        assert_eq!(events.next().await.unwrap().event_type(), "edge_created");
+        assert_eq!(transport.events().len(), 1);
+        assert!(decode::<RelationshipEvent>(&transport.published_on("relationship.events.>")[0].payload).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cloud_events_unwrapped_by_subscribers() {
+        use crate::nats::CloudEventEnvelope;
+
+        let transport = MockTransport::new();
+        let bus = RelationshipBus::new(transport.clone()).with_cloud_events(CloudEventEnvelope::default());
+        let mut events = RelationshipBus::new(transport.clone()).subscribe_events().await.unwrap();
+
+        let event = RelationshipEvent::Edge(EdgeEvent::EdgeCreated(EdgeCreated {
+            event_id: Uuid::now_v7(),
+            identity: MessageIdentity::new_root(),
+            edge_id: RelationshipId::new(),
+            concept_id: ConceptId::new(),
+            source: EntityRef::person(Uuid::now_v7()),
+            target: EntityRef::person(Uuid::now_v7()),
+            category: RelationshipCategory::Friendship,
+            name: "Knows".to_string(),
+            created_by: "test".to_string(),
+            created_at: chrono::Utc::now(),
+        }));
+        bus.publish_event(&event).await.unwrap();
+        assert_eq!(events.next().await.unwrap().event_type(), "edge_created");
+
+        let published = &transport.published_on("relationship.events.edge_created")[0];
+        let cloud_event = CloudEvent::decode(&published.payload).unwrap();
+        assert_eq!(cloud_event.event_type, "cim.relationship.edge_created");
+        assert_eq!(transport.events().len(), 1);
+    }
+
+    #[cfg(feature = "avro")]
+    #[tokio::test]
+    async fn test_avro_events_decoded_by_subscribers() {
+        let transport = MockTransport::new();
+        let bus = RelationshipBus::new(transport.clone()).with_avro(AvroEventCodec::new().unwrap());
+        let mut events = RelationshipBus::new(transport.clone()).subscribe_events().await.unwrap();
+
+        let event = RelationshipEvent::Edge(EdgeEvent::EdgeCreated(EdgeCreated {
+            event_id: Uuid::now_v7(),
+            identity: MessageIdentity::new_root(),
+            edge_id: RelationshipId::new(),
+            concept_id: ConceptId::new(),
+            source: EntityRef::person(Uuid::now_v7()),
+            target: EntityRef::person(Uuid::now_v7()),
+            category: RelationshipCategory::Friendship,
+            name: "Knows".to_string(),
+            created_by: "test".to_string(),
+            created_at: chrono::Utc::now(),
+        }));
+        bus.publish_event(&event).await.unwrap();
+        assert_eq!(events.next().await.unwrap().event_type(), "edge_created");
+
+        let published = &transport.published_on("relationship.events.edge_created")[0];
+        assert!(AvroEventCodec::is_avro(&published.headers));
+        assert_eq!(transport.events().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_query_round_trip() {
+        let transport = MockTransport::new();
+        let bus = RelationshipBus::new(transport.clone());
+
+        let space = RelationshipSpace::new("Bus", TopologicalSpaceId::new());
+        transport.on_request(RelationshipSubjects::all_queries(), move |message| {
+            let query: RelationshipQuery = decode(&message.payload)?;
+            encode(&query.execute(&space))
+        });
+
+        let query = RelationshipQuery::from(AnalyticsQuery::Communities(CommunityQuery::default()));
+        let result = bus.query(&query).await.unwrap();
+        assert!(matches!(result, QueryResult::Analytics(_)));
+    }
+
+    #[tokio::test]
+    async fn test_system_metrics_round_trip() {
+        use crate::infrastructure::MetricsCollector;
+        use crate::queries::MetricsQuery;
+
+        let transport = MockTransport::new();
+        let bus = RelationshipBus::new(transport.clone());
+
+        let space = RelationshipSpace::new("Bus", TopologicalSpaceId::new());
+        let collector = MetricsCollector::new();
+        transport.on_request(RelationshipSubjects::all_system_queries(), move |message| {
+            let query: SystemQuery = decode(&message.payload)?;
+            encode(&query.execute(&space, &collector))
+        });
+
+        let query = SystemQuery::Metrics(MetricsQuery::default());
+        let SystemResult::Metrics(metrics) = bus.system_query(&query).await.unwrap();
+        assert_eq!(metrics.edge_count, 0);
+        assert_eq!(
+            transport.published_on("relationship.queries.system.metrics").len(),
+            1
+        );
+    }
+}
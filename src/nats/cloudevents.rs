@@ -0,0 +1,327 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! CloudEvents Envelopes
+//!
+//! Relationship events wrapped as CloudEvents 1.0 (structured JSON mode)
+//! for event meshes outside CIM. The envelope carries routing and tracing
+//! context; `data` is the RelationshipEvent exactly as sent on its subject:
+//!
+//! ```text
+//! Content-Type: application/cloudevents+json
+//!
+//! {
+//!   "specversion": "1.0",
+//!   "id":          event_id
+//!   "source":      e.g. "/cim/relationship"
+//!   "type":        "cim.relationship.{event_type}"
+//!   "subject":     relationship id
+//!   "time":        from the event_id (UUIDv7) timestamp
+//!   "traceparent": W3C trace context: trace id from the correlation id,
+//!                  parent id from the message id
+//!   "data":        {"Edge": {"EdgeCreated": {...}}}
+//! }
+//! ```
+//!
+//! Subscribers decode envelopes transparently: `subscribe_events` unwraps
+//! any message sent with the CloudEvents content type.
+
+use super::wire::{MessageHeaders, CONTENT_TYPE_HEADER};
+use crate::events::RelationshipEvent;
+use crate::{RelationshipError, RelationshipResult};
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+/// Content type of structured-mode CloudEvents
+pub const CLOUDEVENTS_CONTENT_TYPE: &str = "application/cloudevents+json";
+
+/// Supported CloudEvents spec version
+pub const CLOUDEVENTS_SPEC_VERSION: &str = "1.0";
+
+/// Prefix of relationship CloudEvent types
+pub const CLOUDEVENT_TYPE_PREFIX: &str = "cim.relationship.";
+
+/// Default `source` of envelopes
+pub const DEFAULT_CLOUDEVENT_SOURCE: &str = "/cim/relationship";
+
+/// A CloudEvents 1.0 event in structured JSON mode
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CloudEvent {
+    pub specversion: String,
+    pub id: String,
+    pub source: String,
+    #[serde(rename = "type")]
+    pub event_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub datacontenttype: Option<String>,
+    /// Distributed tracing extension: W3C `traceparent`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub traceparent: Option<String>,
+    /// Distributed tracing extension: W3C `tracestate`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tracestate: Option<String>,
+    #[serde(default)]
+    pub data: Value,
+    /// Other extension attributes, kept as received
+    #[serde(flatten)]
+    pub extensions: BTreeMap<String, Value>,
+}
+
+impl CloudEvent {
+    /// Attach W3C trace context, e.g. that of the caller's span, in place
+    /// of the one derived from the event's identity
+    ///
+    /// `traceparent` must be `{version}-{trace-id}-{parent-id}-{flags}`
+    /// with a non-zero trace and parent id.
+    pub fn with_trace(
+        mut self,
+        traceparent: impl Into<String>,
+        tracestate: Option<String>,
+    ) -> RelationshipResult<Self> {
+        let traceparent = traceparent.into();
+        if !is_valid_traceparent(&traceparent) {
+            return Err(RelationshipError::InvalidConfiguration(format!(
+                "invalid traceparent {}",
+                traceparent
+            )));
+        }
+        self.traceparent = Some(traceparent);
+        self.tracestate = tracestate;
+        Ok(self)
+    }
+
+    /// The relationship event carried in `data`
+    ///
+    /// Fails for other spec versions, foreign types, and data that does
+    /// not match the type.
+    pub fn to_event(&self) -> RelationshipResult<RelationshipEvent> {
+        if self.specversion != CLOUDEVENTS_SPEC_VERSION {
+            return Err(envelope_error(format!(
+                "unsupported specversion {}",
+                self.specversion
+            )));
+        }
+        let event_type = self
+            .event_type
+            .strip_prefix(CLOUDEVENT_TYPE_PREFIX)
+            .ok_or_else(|| {
+                envelope_error(format!("not a relationship event: {}", self.event_type))
+            })?;
+        if let Some(content_type) = &self.datacontenttype {
+            if content_type.split(';').next().unwrap_or_default().trim() != "application/json" {
+                return Err(envelope_error(format!(
+                    "unsupported datacontenttype {}",
+                    content_type
+                )));
+            }
+        }
+        let event: RelationshipEvent =
+            serde_json::from_value(self.data.clone()).map_err(envelope_error)?;
+        if event.event_type() != event_type {
+            return Err(envelope_error(format!(
+                "type {} carries a {} event",
+                self.event_type,
+                event.event_type()
+            )));
+        }
+        Ok(event)
+    }
+
+    /// Serialize in structured mode
+    pub fn encode(&self) -> RelationshipResult<Bytes> {
+        super::bus::encode(self)
+    }
+
+    /// Parse a structured-mode payload
+    pub fn decode(payload: &[u8]) -> RelationshipResult<Self> {
+        super::bus::decode(payload)
+    }
+
+    /// Headers announcing structured mode
+    pub fn headers() -> MessageHeaders {
+        MessageHeaders::from([(
+            CONTENT_TYPE_HEADER.to_string(),
+            CLOUDEVENTS_CONTENT_TYPE.to_string(),
+        )])
+    }
+
+    /// Whether headers announce a structured-mode CloudEvent
+    pub fn is_cloudevent(headers: &MessageHeaders) -> bool {
+        headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(CONTENT_TYPE_HEADER))
+            .is_some_and(|(_, value)| {
+                value.split(';').next().unwrap_or_default().trim() == CLOUDEVENTS_CONTENT_TYPE
+            })
+    }
+}
+
+/// Wraps relationship events in CloudEvents from one source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CloudEventEnvelope {
+    source: String,
+}
+
+impl Default for CloudEventEnvelope {
+    fn default() -> Self {
+        Self::new(DEFAULT_CLOUDEVENT_SOURCE)
+    }
+}
+
+impl CloudEventEnvelope {
+    /// Envelope naming `source` (a URI-reference) as the producer
+    pub fn new(source: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+        }
+    }
+
+    /// The producer's `source`
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Wrap an event
+    ///
+    /// The event's `MessageIdentity` becomes its trace context, so every
+    /// event of one correlation shares a trace.
+    pub fn wrap(&self, event: &RelationshipEvent) -> RelationshipResult<CloudEvent> {
+        let data = serde_json::to_value(event).map_err(envelope_error)?;
+        let payload = data
+            .as_object()
+            .and_then(|wrapper| wrapper.values().next())
+            .and_then(|variant| variant.as_object())
+            .and_then(|variant| variant.values().next());
+        let id = payload
+            .and_then(|payload| payload.get("event_id"))
+            .and_then(|id| id.as_str())
+            .ok_or_else(|| envelope_error("event without an event_id"))?
+            .to_string();
+        let traceparent = payload
+            .and_then(|payload| payload.get("identity"))
+            .and_then(identity_traceparent);
+        Ok(CloudEvent {
+            specversion: CLOUDEVENTS_SPEC_VERSION.to_string(),
+            time: uuid_time(&id),
+            id,
+            source: self.source.clone(),
+            event_type: format!("{}{}", CLOUDEVENT_TYPE_PREFIX, event.event_type()),
+            subject: Some(event.relationship_id().as_uuid().to_string()),
+            datacontenttype: Some("application/json".to_string()),
+            traceparent,
+            tracestate: None,
+            data,
+            extensions: BTreeMap::new(),
+        })
+    }
+}
+
+/// Whether a string is a well-formed W3C `traceparent`
+pub fn is_valid_traceparent(traceparent: &str) -> bool {
+    let parts: Vec<&str> = traceparent.split('-').collect();
+    let hex = |s: &str, len: usize| {
+        s.len() == len
+            && s.bytes()
+                .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+    };
+    let nonzero = |s: &str| s.bytes().any(|b| b != b'0');
+    matches!(
+        parts.as_slice(),
+        [version, trace_id, parent_id, flags]
+            if hex(version, 2) && *version != "ff"
+                && hex(trace_id, 32) && nonzero(trace_id)
+                && hex(parent_id, 16) && nonzero(parent_id)
+                && hex(flags, 2)
+    )
+}
+
+/// `traceparent` of a serialized `MessageIdentity`: the correlation id as
+/// trace id, the low half of the message id as parent id, sampled
+fn identity_traceparent(identity: &Value) -> Option<String> {
+    let trace_id = first_uuid(identity.get("correlation_id")?)?;
+    let message_id = first_uuid(identity.get("message_id")?)?.simple().to_string();
+    let traceparent = format!("00-{}-{}-01", trace_id.simple(), &message_id[16..]);
+    is_valid_traceparent(&traceparent).then_some(traceparent)
+}
+
+/// First UUID in a serialized id, however upstream wraps it
+fn first_uuid(value: &Value) -> Option<Uuid> {
+    match value {
+        Value::String(s) => Uuid::parse_str(s).ok(),
+        Value::Array(items) => items.iter().find_map(first_uuid),
+        Value::Object(fields) => fields.values().find_map(first_uuid),
+        _ => None,
+    }
+}
+
+/// Creation time encoded in a UUIDv7 event id
+fn uuid_time(id: &str) -> Option<DateTime<Utc>> {
+    let (seconds, nanos) = Uuid::parse_str(id).ok()?.get_timestamp()?.to_unix();
+    DateTime::from_timestamp(seconds as i64, nanos)
+}
+
+fn envelope_error(error: impl std::fmt::Display) -> RelationshipError {
+    RelationshipError::SerializationError(format!("cloudevent: {}", error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{EdgeEvent, EdgeRenamed};
+    use crate::value_objects::RelationshipId;
+    use cim_domain::MessageIdentity;
+
+    #[test]
+    fn test_wrap_and_unwrap() {
+        let identity = MessageIdentity::new_root();
+        let event = RelationshipEvent::Edge(EdgeEvent::Renamed(EdgeRenamed {
+            event_id: Uuid::now_v7(),
+            identity: MessageIdentity::new_caused_by(&identity),
+            edge_id: RelationshipId::new(),
+            old_name: "Knows".to_string(),
+            new_name: "Works with".to_string(),
+            renamed_by: "test".to_string(),
+            renamed_at: Utc::now(),
+        }));
+        // Traced by the event's correlation id
+        let wrapped = CloudEventEnvelope::new("/tenants/acme/relationship").wrap(&event).unwrap();
+        let derived = wrapped.traceparent.clone().unwrap();
+        assert!(is_valid_traceparent(&derived), "{}", derived);
+        let correlation = first_uuid(&serde_json::to_value(identity).unwrap()["correlation_id"]).unwrap();
+        assert!(derived.contains(&correlation.simple().to_string()), "{}", derived);
+
+        let traceparent = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let cloud_event = wrapped.with_trace(traceparent, None).unwrap();
+        assert_eq!(cloud_event.event_type, "cim.relationship.edge_renamed");
+        assert_eq!(
+            cloud_event.subject,
+            Some(event.relationship_id().as_uuid().to_string())
+        );
+        assert!(cloud_event.time.is_some());
+
+        let json: Value = serde_json::from_slice(&cloud_event.encode().unwrap()).unwrap();
+        assert_eq!(json["specversion"], "1.0");
+        assert_eq!(json["traceparent"], traceparent);
+
+        // Extensions from other producers survive a round trip
+        let mut inbound = json.clone();
+        inbound["partitionkey"] = "acme".into();
+        let decoded = CloudEvent::decode(&serde_json::to_vec(&inbound).unwrap()).unwrap();
+        assert_eq!(decoded.extensions["partitionkey"], "acme");
+        assert_eq!(decoded.to_event().unwrap().event_type(), "edge_renamed");
+
+        let mut mislabeled = decoded.clone();
+        mislabeled.event_type = "cim.relationship.edge_created".to_string();
+        assert!(mislabeled.to_event().is_err());
+        assert!(decoded.clone().with_trace("00-0-0-01", None).is_err());
+    }
+}
@@ -0,0 +1,413 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Health Probes
+//!
+//! Liveness and readiness of the relationship service, so orchestrators
+//! can gate traffic on it. Both are answered over NATS, and over HTTP by
+//! `api::health` (feature `health`):
+//!
+//! ```text
+//! relationship.health.live    GET /livez    the service answers
+//! relationship.health.ready   GET /readyz   not shutting down
+//!                                           NATS connected
+//!                                           streams present
+//!                                           projections caught up
+//! ```
+//!
+//! Each probe answers a `HealthReport` naming every check that failed.
+//!
+//! Projections report their progress through `ProjectionProgress`. One
+//! following the events stream records the sequence it has reached, and is
+//! caught up within `max_lag` events of the stream's last one. One kept
+//! current by the worker itself is marked live once rebuilt.
+
+use super::shutdown::Shutdown;
+use super::subjects::RelationshipSubjects;
+use super::transport::Transport;
+use crate::{RelationshipError, RelationshipResult};
+use async_nats::jetstream::{self, stream};
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+
+/// Events a projection may trail the stream by and still be caught up
+pub const DEFAULT_MAX_LAG: u64 = 1000;
+
+/// What a probe asks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Probe {
+    /// The service answers; failing it means restart
+    Liveness,
+    /// The service can take traffic
+    Readiness,
+}
+
+impl Probe {
+    /// Subject token: `live` or `ready`
+    pub fn token(&self) -> &'static str {
+        match self {
+            Probe::Liveness => "live",
+            Probe::Readiness => "ready",
+        }
+    }
+}
+
+/// Outcome of one check
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HealthCheck {
+    pub name: String,
+    pub healthy: bool,
+    /// Why the check failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl HealthCheck {
+    fn new(name: &str, problems: Vec<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            healthy: problems.is_empty(),
+            detail: (!problems.is_empty()).then(|| problems.join("; ")),
+        }
+    }
+}
+
+/// Answer to a probe
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HealthReport {
+    pub probe: Probe,
+    /// Whether every check passed
+    pub healthy: bool,
+    pub checks: Vec<HealthCheck>,
+    pub checked_at: DateTime<Utc>,
+}
+
+impl HealthReport {
+    fn new(probe: Probe, checks: Vec<HealthCheck>) -> Self {
+        Self {
+            probe,
+            healthy: checks.iter().all(|c| c.healthy),
+            checks,
+            checked_at: Utc::now(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Position {
+    Pending,
+    At(u64),
+    Live,
+}
+
+/// How far each projection has got, shared between the projections and
+/// the `HealthMonitor`
+#[derive(Debug, Clone, Default)]
+pub struct ProjectionProgress {
+    positions: Arc<RwLock<BTreeMap<String, Position>>>,
+}
+
+impl ProjectionProgress {
+    /// Create progress expecting no projections
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Expect a projection: the service is not ready until it reports
+    pub fn expect(&self, projection: impl Into<String>) {
+        self.write().insert(projection.into(), Position::Pending);
+    }
+
+    /// Record the events stream sequence a projection has applied
+    pub fn record(&self, projection: impl Into<String>, sequence: u64) {
+        self.write()
+            .insert(projection.into(), Position::At(sequence));
+    }
+
+    /// Mark a projection rebuilt and kept current from now on
+    pub fn mark_live(&self, projection: impl Into<String>) {
+        self.write().insert(projection.into(), Position::Live);
+    }
+
+    /// Problems of projections not caught up with the stream's `last`
+    /// sequence, if known
+    fn problems(&self, last: Option<u64>, max_lag: u64) -> Vec<String> {
+        let positions = self.positions.read().unwrap_or_else(|p| p.into_inner());
+        positions
+            .iter()
+            .filter_map(|(name, position)| match (position, last) {
+                (Position::Pending, _) => Some(format!("{} not rebuilt yet", name)),
+                (Position::At(sequence), Some(last))
+                    if last.saturating_sub(*sequence) > max_lag =>
+                {
+                    Some(format!("{} is {} events behind", name, last - sequence))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, BTreeMap<String, Position>> {
+        self.positions.write().unwrap_or_else(|p| p.into_inner())
+    }
+}
+
+/// Answers liveness and readiness probes
+pub struct HealthMonitor<T: Transport> {
+    transport: T,
+    jetstream: Option<(jetstream::Context, String)>,
+    upstream_subjects: Vec<String>,
+    progress: ProjectionProgress,
+    max_lag: u64,
+    shutdown: Shutdown,
+}
+
+impl<T: Transport> std::fmt::Debug for HealthMonitor<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HealthMonitor")
+            .field(
+                "events_stream",
+                &self.jetstream.as_ref().map(|(_, name)| name),
+            )
+            .field("upstream_subjects", &self.upstream_subjects)
+            .field("progress", &self.progress)
+            .field("max_lag", &self.max_lag)
+            .finish()
+    }
+}
+
+impl<T: Transport> HealthMonitor<T> {
+    /// Monitor checking the transport's connection and the projections
+    pub fn new(transport: T) -> Self {
+        Self {
+            transport,
+            jetstream: None,
+            upstream_subjects: Vec::new(),
+            progress: ProjectionProgress::new(),
+            max_lag: DEFAULT_MAX_LAG,
+            shutdown: Shutdown::new(),
+        }
+    }
+
+    /// Also check the events stream, measuring projection lag against it
+    pub fn with_jetstream(
+        mut self,
+        js: jetstream::Context,
+        events_stream: impl Into<String>,
+    ) -> Self {
+        self.jetstream = Some((js, events_stream.into()));
+        self
+    }
+
+    /// Also check a stream captures each upstream subject
+    pub fn with_upstream_subjects(mut self, subjects: Vec<String>) -> Self {
+        self.upstream_subjects = subjects;
+        self
+    }
+
+    /// Share progress with the projections
+    pub fn with_progress(mut self, progress: ProjectionProgress) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Events a projection may trail the stream by
+    pub fn with_max_lag(mut self, max_lag: u64) -> Self {
+        self.max_lag = max_lag;
+        self
+    }
+
+    /// Report not ready once `shutdown` is triggered, so traffic moves
+    /// away while the service drains
+    pub fn with_shutdown(mut self, shutdown: Shutdown) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+
+    /// Progress the projections report to
+    pub fn progress(&self) -> &ProjectionProgress {
+        &self.progress
+    }
+
+    /// Answer a probe
+    pub async fn check(&self, probe: Probe) -> HealthReport {
+        match probe {
+            Probe::Liveness => {
+                HealthReport::new(probe, vec![HealthCheck::new("service", Vec::new())])
+            }
+            Probe::Readiness => self.readiness().await,
+        }
+    }
+
+    async fn readiness(&self) -> HealthReport {
+        let mut checks = Vec::new();
+        if self.shutdown.is_triggered() {
+            checks.push(HealthCheck::new("shutdown", vec!["draining".to_string()]));
+        }
+        let mut nats = Vec::new();
+        if !self.transport.is_connected() {
+            nats.push("not connected".to_string());
+        }
+        checks.push(HealthCheck::new("nats", nats));
+
+        let mut last = None;
+        if let Some((js, events_stream)) = &self.jetstream {
+            let (problems, sequence) = self.streams(js, events_stream).await;
+            last = sequence;
+            checks.push(HealthCheck::new("streams", problems));
+        }
+        checks.push(HealthCheck::new(
+            "projections",
+            self.progress.problems(last, self.max_lag),
+        ));
+        HealthReport::new(Probe::Readiness, checks)
+    }
+
+    /// Problems with the streams, and the events stream's last sequence
+    async fn streams(
+        &self,
+        js: &jetstream::Context,
+        events_stream: &str,
+    ) -> (Vec<String>, Option<u64>) {
+        let mut problems = Vec::new();
+        let last = match js.get_stream(events_stream).await {
+            Ok(stream) => Some(stream.cached_info().state.last_sequence),
+            Err(e) => {
+                problems.push(format!("stream {}: {}", events_stream, e));
+                None
+            }
+        };
+        if self.upstream_subjects.is_empty() {
+            return (problems, last);
+        }
+
+        let mut captured: Vec<String> = Vec::new();
+        let mut listing = js.streams();
+        while let Some(info) = listing.next().await {
+            let info: stream::Info = match info {
+                Ok(info) => info,
+                Err(e) => {
+                    problems.push(format!("listing streams: {}", e));
+                    return (problems, last);
+                }
+            };
+            captured.extend(info.config.subjects);
+        }
+        for subject in &self.upstream_subjects {
+            if !captured
+                .iter()
+                .any(|s| RelationshipSubjects::matches(s, subject))
+            {
+                problems.push(format!("no stream captures {}", subject));
+            }
+        }
+        (problems, last)
+    }
+
+    /// Answer probes on `relationship.health.>` until the subscription ends
+    pub async fn serve(&self) -> RelationshipResult<()> {
+        let mut requests = self
+            .transport
+            .subscribe(&RelationshipSubjects::all_health())
+            .await?;
+        while let Some(request) = requests.next().await {
+            let Some(reply) = request.reply else {
+                continue;
+            };
+            let probe = match request.subject.rsplit('.').next() {
+                Some("live") => Probe::Liveness,
+                Some("ready") => Probe::Readiness,
+                _ => {
+                    tracing::debug!("health request on {} unanswered", request.subject);
+                    continue;
+                }
+            };
+            let report = serde_json::to_vec(&self.check(probe).await)
+                .map_err(|e| RelationshipError::SerializationError(e.to_string()))?;
+            self.transport.publish(&reply, report.into()).await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nats::MockTransport;
+
+    #[tokio::test]
+    async fn test_readiness_over_nats() {
+        let transport = MockTransport::new();
+        let progress = ProjectionProgress::new();
+        progress.expect("space");
+        let monitor =
+            Arc::new(HealthMonitor::new(transport.clone()).with_progress(progress.clone()));
+        let serving = tokio::spawn({
+            let monitor = monitor.clone();
+            async move { monitor.serve().await }
+        });
+        for _ in 0..10 {
+            tokio::task::yield_now().await;
+        }
+
+        let ask = |probe: Probe| {
+            let transport = transport.clone();
+            async move {
+                let reply = transport
+                    .request(
+                        &RelationshipSubjects::health(probe.token()),
+                        bytes::Bytes::new(),
+                    )
+                    .await
+                    .unwrap();
+                serde_json::from_slice::<HealthReport>(&reply).unwrap()
+            }
+        };
+
+        assert!(ask(Probe::Liveness).await.healthy);
+        let report = ask(Probe::Readiness).await;
+        assert!(!report.healthy);
+        let projections = report
+            .checks
+            .iter()
+            .find(|c| c.name == "projections")
+            .unwrap();
+        assert_eq!(projections.detail.as_deref(), Some("space not rebuilt yet"));
+
+        progress.mark_live("space");
+        assert!(ask(Probe::Readiness).await.healthy);
+
+        transport.set_connected(false);
+        let report = ask(Probe::Readiness).await;
+        assert!(!report.healthy);
+        assert!(report.checks.iter().any(|c| c.name == "nats" && !c.healthy));
+        assert!(ask(Probe::Liveness).await.healthy);
+
+        // Lag is measured against the stream's last sequence
+        progress.record("snapshots", 10);
+        assert_eq!(
+            progress.problems(Some(5000), 1000),
+            vec!["snapshots is 4990 events behind"]
+        );
+        assert!(progress.problems(Some(500), 1000).is_empty());
+
+        // Draining services are alive but not ready
+        let shutdown = Shutdown::new();
+        let draining = HealthMonitor::new(MockTransport::new()).with_shutdown(shutdown.clone());
+        assert!(draining.check(Probe::Readiness).await.healthy);
+        shutdown.trigger();
+        let report = draining.check(Probe::Readiness).await;
+        assert!(!report.healthy);
+        assert!(report
+            .checks
+            .iter()
+            .any(|c| c.name == "shutdown" && !c.healthy));
+        assert!(draining.check(Probe::Liveness).await.healthy);
+        serving.abort();
+    }
+}
@@ -0,0 +1,982 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! JetStream Assets
+//!
+//! The durable side of the relationship service: the stream relationship
+//! events are kept in, snapshots of the space built from it, and the
+//! durable consumers through which upstream domain events reach the
+//! cross-domain dispatcher and relationship events reach webhooks.
+//!
+//! ```text
+//! RELATIONSHIP_EVENTS    relationship.events.>     created if missing
+//!     +-- replayed on startup, after the latest snapshot, to rebuild the space
+//!     +-- followed by the Checkpointer
+//!     +-- relationship-webhooks                    durable pull consumer of the WebhookFeed
+//!
+//! RELATIONSHIP_SNAPSHOTS (object store)            created if missing
+//!     +-- space          Snapshot: the space as of a stream sequence
+//!
+//! RELATIONSHIP_WEBHOOKS (key-value)                created if missing
+//!     +-- {webhook_id}   registered webhooks, kept by the WebhookManager
+//!
+//! RELATIONSHIP_LEASES (key-value)                  created if missing
+//!     +-- {events stream}  InstanceLease: the one service deciding commands
+//!
+//! RELATIONSHIP_WEBHOOK_DEAD_LETTERS  relationship.webhooks.dead_letters.*  created if missing
+//!
+//! (upstream stream)      person.events.>           owned by the person domain
+//!     +-- relationship-cross-domain-person         durable pull consumer
+//! ```
+//!
+//! `JetStreamAssets::provision` checks every asset before the service takes
+//! traffic. Upstream streams belong to their domains and are never created
+//! here: a subject no stream captures is a startup error, not a consumer
+//! that silently receives nothing.
+
+use super::bus::{decode_event, encode};
+use super::health::ProjectionProgress;
+use super::shutdown::Shutdown;
+use super::subjects::RelationshipSubjects;
+use super::transport::{from_header_map, TransportMessage};
+use super::worker::RelationshipWorker;
+use super::Transport;
+use crate::aggregates::RelationshipSpace;
+use crate::infrastructure::StreamSize;
+use crate::services::{WebhookDispatcher, WebhookSender};
+use crate::{RelationshipError, RelationshipResult};
+use async_nats::jetstream::consumer::{self, PullConsumer};
+use async_nats::jetstream::kv;
+use async_nats::jetstream::object_store::{self, ObjectStore};
+use async_nats::jetstream::{self, stream, AckKind};
+use cim_domain_spaces::TopologicalSpaceId;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::io::AsyncReadExt;
+use uuid::Uuid;
+
+/// Stream relationship events are kept in
+pub const EVENTS_STREAM: &str = "RELATIONSHIP_EVENTS";
+
+/// Prefix of the durable upstream consumers
+pub const CONSUMER_PREFIX: &str = "relationship-cross-domain";
+
+/// Object store bucket snapshots are kept in
+pub const SNAPSHOT_BUCKET: &str = "RELATIONSHIP_SNAPSHOTS";
+
+/// Object holding the latest snapshot
+const SNAPSHOT_OBJECT: &str = "space";
+
+/// Key-value bucket instance leases are kept in
+pub const LEASE_BUCKET: &str = "RELATIONSHIP_LEASES";
+
+/// Durable consumer webhook deliveries follow
+pub const WEBHOOK_CONSUMER: &str = "relationship-webhooks";
+
+/// Key-value bucket registered webhooks are kept in
+pub const WEBHOOK_BUCKET: &str = "RELATIONSHIP_WEBHOOKS";
+
+/// Stream webhook deliveries that gave up are kept in
+pub const DEAD_LETTER_STREAM: &str = "RELATIONSHIP_WEBHOOK_DEAD_LETTERS";
+
+/// Streams and consumers the relationship service needs
+#[derive(Debug, Clone)]
+pub struct JetStreamAssets {
+    events_stream: String,
+    consumer_prefix: String,
+    upstream_subjects: Vec<String>,
+    create_events_stream: bool,
+}
+
+/// A durable consumer of one upstream subject
+#[derive(Debug)]
+pub struct UpstreamConsumer {
+    /// Subject pattern consumed
+    pub subject: String,
+    /// Upstream stream capturing the subject
+    pub stream: String,
+    pub consumer: PullConsumer,
+}
+
+/// Assets checked, and created where allowed
+#[derive(Debug)]
+pub struct ProvisionedAssets {
+    pub events: stream::Stream,
+    pub upstream: Vec<UpstreamConsumer>,
+}
+
+impl JetStreamAssets {
+    /// Assets for consuming upstream subjects, e.g.
+    /// `RelationshipWorker::upstream_subjects`
+    pub fn new(upstream_subjects: Vec<String>) -> Self {
+        Self {
+            events_stream: EVENTS_STREAM.to_string(),
+            consumer_prefix: CONSUMER_PREFIX.to_string(),
+            upstream_subjects,
+            create_events_stream: true,
+        }
+    }
+
+    /// Keep events in another stream
+    pub fn with_events_stream(mut self, name: impl Into<String>) -> Self {
+        self.events_stream = name.into();
+        self
+    }
+
+    /// Name consumers with another prefix
+    pub fn with_consumer_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.consumer_prefix = prefix.into();
+        self
+    }
+
+    /// Fail instead of creating a missing events stream, for deployments
+    /// whose streams are managed elsewhere
+    pub fn require_events_stream(mut self) -> Self {
+        self.create_events_stream = false;
+        self
+    }
+
+    pub fn events_stream(&self) -> &str {
+        &self.events_stream
+    }
+
+    /// Durable name of the consumer of an upstream subject:
+    /// `{prefix}-{domain}`, e.g. `relationship-cross-domain-person`
+    pub fn consumer_name(&self, subject: &str) -> String {
+        let domain: String = subject
+            .split('.')
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        format!("{}-{}", self.consumer_prefix, domain)
+    }
+
+    /// Check every stream and create the consumers, reporting all problems
+    /// at once
+    pub async fn provision(
+        &self,
+        js: &jetstream::Context,
+    ) -> RelationshipResult<ProvisionedAssets> {
+        let mut problems = Vec::new();
+
+        let all_events = RelationshipSubjects::all_events();
+        let events = match js.get_stream(&self.events_stream).await {
+            Ok(mut events) => {
+                let info = events.info().await.map_err(jetstream_error)?;
+                if !info
+                    .config
+                    .subjects
+                    .iter()
+                    .any(|s| RelationshipSubjects::matches(s, &all_events))
+                {
+                    problems.push(format!(
+                        "stream {} does not capture {} (subjects: {})",
+                        self.events_stream,
+                        all_events,
+                        info.config.subjects.join(", ")
+                    ));
+                }
+                Some(events)
+            }
+            Err(_) if self.create_events_stream => {
+                let config = stream::Config {
+                    name: self.events_stream.clone(),
+                    subjects: vec![all_events.clone()],
+                    ..Default::default()
+                };
+                tracing::info!("creating stream {}", self.events_stream);
+                Some(js.create_stream(config).await.map_err(jetstream_error)?)
+            }
+            Err(e) => {
+                problems.push(format!("stream {}: {}", self.events_stream, e));
+                None
+            }
+        };
+
+        let mut streams = Vec::new();
+        let mut listing = js.streams();
+        while let Some(info) = listing.next().await {
+            let info: stream::Info = info.map_err(jetstream_error)?;
+            streams.push(info.config);
+        }
+
+        let mut upstream = Vec::new();
+        for subject in &self.upstream_subjects {
+            let Some(config) = streams.iter().find(|c| {
+                c.subjects
+                    .iter()
+                    .any(|s| RelationshipSubjects::matches(s, subject))
+            }) else {
+                problems.push(format!("no stream captures {}", subject));
+                continue;
+            };
+            let name = self.consumer_name(subject);
+            let consumer = js
+                .get_stream(&config.name)
+                .await
+                .map_err(jetstream_error)?
+                .get_or_create_consumer(
+                    &name,
+                    consumer::pull::Config {
+                        durable_name: Some(name.clone()),
+                        filter_subject: subject.clone(),
+                        ack_policy: consumer::AckPolicy::Explicit,
+                        ..Default::default()
+                    },
+                )
+                .await
+                .map_err(jetstream_error)?;
+            upstream.push(UpstreamConsumer {
+                subject: subject.clone(),
+                stream: config.name.clone(),
+                consumer,
+            });
+        }
+
+        match events {
+            Some(events) if problems.is_empty() => Ok(ProvisionedAssets { events, upstream }),
+            _ => Err(RelationshipError::InvalidConfiguration(format!(
+                "JetStream assets missing: {}",
+                problems.join("; ")
+            ))),
+        }
+    }
+}
+
+/// Current size of a stream, for `MetricsCollector::record_stream`
+pub async fn stream_size(stream: &mut stream::Stream) -> RelationshipResult<StreamSize> {
+    let info = stream.info().await.map_err(jetstream_error)?;
+    Ok(StreamSize {
+        messages: info.state.messages,
+        bytes: info.state.bytes,
+    })
+}
+
+/// Apply every event kept in a stream to a space, oldest first, returning
+/// how many applied
+///
+/// Events that do not decode or apply are logged and skipped.
+pub async fn replay(
+    events: &mut stream::Stream,
+    space: &mut RelationshipSpace,
+) -> RelationshipResult<u64> {
+    Ok(replay_after(events, space, 0).await?.0)
+}
+
+/// Apply the events kept after `sequence`, returning how many applied and
+/// the sequence of the last
+async fn replay_after(
+    events: &mut stream::Stream,
+    space: &mut RelationshipSpace,
+    sequence: u64,
+) -> RelationshipResult<(u64, u64)> {
+    replay_between(events, space, sequence, u64::MAX).await
+}
+
+/// Apply the events kept after `sequence` up to `through`, returning how
+/// many applied and the sequence of the last
+async fn replay_between(
+    events: &mut stream::Stream,
+    space: &mut RelationshipSpace,
+    sequence: u64,
+    through: u64,
+) -> RelationshipResult<(u64, u64)> {
+    let state = &events.info().await.map_err(jetstream_error)?.state;
+    let (kept, first, last) = (state.messages, state.first_sequence, state.last_sequence);
+    let Some(range) = replay_range(kept, first, last, sequence, through) else {
+        return Ok((0, sequence));
+    };
+    if first > sequence + 1 {
+        tracing::warn!(
+            "events {} to {} are no longer kept; the space misses them",
+            sequence + 1,
+            first - 1
+        );
+    }
+    let consumer = ordered_after(events, sequence).await?;
+    let mut messages = consumer.messages().await.map_err(jetstream_error)?;
+
+    let (mut applied, mut last) = (0, sequence);
+    while let Some(message) = messages.next().await {
+        let message = message.map_err(jetstream_error)?;
+        let info = message.info().map_err(jetstream_error)?;
+        let pending = info.pending;
+        if info.stream_sequence > *range.end() {
+            break;
+        }
+        last = info.stream_sequence;
+        if apply_message(space, &message) {
+            applied += 1;
+        }
+        if pending == 0 || last >= *range.end() {
+            break;
+        }
+    }
+    Ok((applied, last))
+}
+
+/// Sequences to replay after `sequence` up to `through` of a stream keeping
+/// `kept` events from `first` to `last`; `None` when there are none
+fn replay_range(kept: u64, first: u64, last: u64, sequence: u64, through: u64) -> Option<RangeInclusive<u64>> {
+    let (from, to) = (first.max(sequence.saturating_add(1)), last.min(through));
+    (kept > 0 && from <= to).then_some(from..=to)
+}
+
+async fn ordered_after(
+    events: &stream::Stream,
+    sequence: u64,
+) -> RelationshipResult<consumer::Consumer<consumer::pull::OrderedConfig>> {
+    let deliver_policy = match sequence {
+        0 => consumer::DeliverPolicy::All,
+        sequence => consumer::DeliverPolicy::ByStartSequence {
+            start_sequence: sequence + 1,
+        },
+    };
+    events
+        .create_consumer(consumer::pull::OrderedConfig {
+            deliver_policy,
+            ..Default::default()
+        })
+        .await
+        .map_err(jetstream_error)
+}
+
+fn apply_message(space: &mut RelationshipSpace, message: &jetstream::Message) -> bool {
+    match decode_event(&transport_message(message)).and_then(|event| space.apply_event(&event)) {
+        Ok(()) => true,
+        Err(e) => {
+            tracing::warn!("replay skipped {}: {}", message.subject, e);
+            false
+        }
+    }
+}
+
+// ---- Snapshots ----
+
+/// The space as of an events stream sequence
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// Sequence of the last event applied (0 = none)
+    pub sequence: u64,
+    pub space: RelationshipSpace,
+}
+
+impl Snapshot {
+    /// A space no event has been applied to
+    pub fn new(space: RelationshipSpace) -> Self {
+        Self { sequence: 0, space }
+    }
+
+    /// Open a snapshot bucket, creating it if missing
+    pub async fn bucket(js: &jetstream::Context, bucket: &str) -> RelationshipResult<ObjectStore> {
+        match js.get_object_store(bucket).await {
+            Ok(store) => Ok(store),
+            Err(_) => {
+                tracing::info!("creating object store {}", bucket);
+                js.create_object_store(object_store::Config {
+                    bucket: bucket.to_string(),
+                    ..Default::default()
+                })
+                .await
+                .map_err(jetstream_error)
+            }
+        }
+    }
+
+    /// The latest snapshot saved in a bucket, if any
+    pub async fn load(store: &ObjectStore) -> RelationshipResult<Option<Self>> {
+        let mut object = match store.get(SNAPSHOT_OBJECT).await {
+            Ok(object) => object,
+            Err(e) if e.kind() == object_store::GetErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(jetstream_error(e)),
+        };
+        let mut bytes = Vec::new();
+        object
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(jetstream_error)?;
+        ciborium::from_reader(bytes.as_slice())
+            .map(Some)
+            .map_err(|e| RelationshipError::SerializationError(format!("snapshot: {}", e)))
+    }
+
+    /// Save as the latest snapshot in a bucket
+    pub async fn save(&self, store: &ObjectStore) -> RelationshipResult<()> {
+        let mut bytes = Vec::new();
+        ciborium::into_writer(self, &mut bytes)
+            .map_err(|e| RelationshipError::SerializationError(format!("snapshot: {}", e)))?;
+        store
+            .put(SNAPSHOT_OBJECT, &mut bytes.as_slice())
+            .await
+            .map_err(jetstream_error)?;
+        Ok(())
+    }
+
+    /// Apply the events kept since the snapshot, returning how many applied
+    pub async fn catch_up(&mut self, events: &mut stream::Stream) -> RelationshipResult<u64> {
+        let (applied, sequence) = replay_after(events, &mut self.space, self.sequence).await?;
+        self.sequence = sequence;
+        Ok(applied)
+    }
+}
+
+/// Keeps the latest snapshot current
+///
+/// Follows the events stream with a space of its own, so every snapshot it
+/// saves is the space as of a stream sequence, whatever the worker is
+/// doing. A snapshot is saved once `every_events` events arrived since the
+/// last one, or at the end of each interval in which any did.
+pub struct Checkpointer {
+    snapshot: Snapshot,
+    events: stream::Stream,
+    store: ObjectStore,
+    interval: Duration,
+    every_events: u64,
+    unsaved: u64,
+    progress: Option<ProjectionProgress>,
+    shutdown: Shutdown,
+}
+
+impl std::fmt::Debug for Checkpointer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Checkpointer")
+            .field("sequence", &self.snapshot.sequence)
+            .field("interval", &self.interval)
+            .field("every_events", &self.every_events)
+            .field("unsaved", &self.unsaved)
+            .finish()
+    }
+}
+
+impl Checkpointer {
+    /// Projection name the checkpointer reports its progress under
+    pub const PROJECTION: &'static str = "snapshots";
+
+    /// Checkpointer continuing from `snapshot`, saving every five minutes
+    /// or 1000 events
+    pub fn new(snapshot: Snapshot, events: stream::Stream, store: ObjectStore) -> Self {
+        Self {
+            snapshot,
+            events,
+            store,
+            interval: Duration::from_secs(300),
+            every_events: 1000,
+            unsaved: 0,
+            progress: None,
+            shutdown: Shutdown::new(),
+        }
+    }
+
+    /// Report the sequence reached, for readiness
+    pub fn with_progress(mut self, progress: ProjectionProgress) -> Self {
+        progress.record(Self::PROJECTION, self.snapshot.sequence);
+        self.progress = Some(progress);
+        self
+    }
+
+    /// Save at most this often, unless `every_events` is reached first
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Save once this many events arrived
+    pub fn with_every_events(mut self, every_events: u64) -> Self {
+        self.every_events = every_events.max(1);
+        self
+    }
+
+    /// Stop following, saving what arrived, when `shutdown` is triggered
+    pub fn with_shutdown(mut self, shutdown: Shutdown) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+
+    /// The snapshot as it stands
+    pub fn snapshot(&self) -> &Snapshot {
+        &self.snapshot
+    }
+
+    /// Save the snapshot if events arrived since the last save, returning
+    /// whether it was saved
+    pub async fn checkpoint(&mut self) -> RelationshipResult<bool> {
+        if self.unsaved == 0 {
+            return Ok(false);
+        }
+        self.snapshot.save(&self.store).await?;
+        tracing::debug!("snapshot saved at sequence {}", self.snapshot.sequence);
+        self.unsaved = 0;
+        Ok(true)
+    }
+
+    /// Follow the events stream, saving snapshots, until it ends or
+    /// shutdown, then save what arrived since the last save
+    pub async fn run(&mut self) -> RelationshipResult<()> {
+        let consumer = ordered_after(&self.events, self.snapshot.sequence).await?;
+        let mut messages = consumer.messages().await.map_err(jetstream_error)?;
+        let mut ticks =
+            tokio::time::interval_at(tokio::time::Instant::now() + self.interval, self.interval);
+        ticks.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                biased;
+                _ = self.shutdown.triggered() => break,
+                message = messages.next() => {
+                    let Some(message) = message else { break };
+                    let message = message.map_err(jetstream_error)?;
+                    self.snapshot.sequence = message.info().map_err(jetstream_error)?.stream_sequence;
+                    apply_message(&mut self.snapshot.space, &message);
+                    self.unsaved += 1;
+                    if let Some(progress) = &self.progress {
+                        progress.record(Self::PROJECTION, self.snapshot.sequence);
+                    }
+                    if self.unsaved >= self.every_events {
+                        self.checkpoint().await?;
+                    }
+                }
+                _ = ticks.tick() => {
+                    self.checkpoint().await?;
+                }
+            }
+        }
+        self.checkpoint().await?;
+        Ok(())
+    }
+}
+
+/// Deliver an upstream consumer's messages to a worker until the consumer
+/// ends or the worker shuts down
+///
+/// Handled messages are acked. Messages whose reactions could not be
+/// stored for a transport failure are nacked, for redelivery; messages that
+/// do not decode are terminated so they are not. Messages fetched but not
+/// yet handled at shutdown are left unacked, for redelivery.
+pub async fn follow_upstream<T: Transport>(
+    upstream: UpstreamConsumer,
+    worker: &RelationshipWorker<T>,
+) -> RelationshipResult<()> {
+    let mut messages = upstream
+        .consumer
+        .messages()
+        .await
+        .map_err(jetstream_error)?;
+    while let Some(message) = worker.shutdown().next(&mut messages).await {
+        let message = message.map_err(jetstream_error)?;
+        let handled = worker
+            .handle_upstream(message.subject.as_str(), &message.payload)
+            .await;
+        let ack = upstream_ack(&handled);
+        if let Err(e) = handled {
+            match ack {
+                AckKind::Nak(_) => tracing::warn!("upstream message on {} to be redelivered: {}", message.subject, e),
+                _ => tracing::warn!("upstream message on {} dropped: {}", message.subject, e),
+            }
+        }
+        message.ack_with(ack).await.map_err(jetstream_error)?;
+    }
+    Ok(())
+}
+
+/// Acknowledgement of an upstream message handled with `handled`
+fn upstream_ack(handled: &RelationshipResult<usize>) -> AckKind {
+    match handled {
+        Ok(_) => AckKind::Ack,
+        Err(RelationshipError::TransportError(_)) => AckKind::Nak(None),
+        Err(_) => AckKind::Term,
+    }
+}
+
+// ---- Instance Lease ----
+
+/// Held by the one relationship service deciding commands for an events
+/// stream
+///
+/// Commands are decided against the worker's own space, so a second worker
+/// would decide them without the first's events. The lease is a key in a
+/// bucket whose entries expire after its time to live: it is taken only
+/// while the key is empty, kept by rewriting it at the revision last
+/// written, and given up when the holder stops or fails to renew it in
+/// time, to a standby waiting in `acquire`.
+#[derive(Debug)]
+pub struct InstanceLease {
+    bucket: kv::Store,
+    key: String,
+    holder: String,
+    ttl: Duration,
+    revision: AtomicU64,
+}
+
+impl InstanceLease {
+    /// Wait until the lease on `key` is free, then take it
+    ///
+    /// The bucket is created with `ttl` as its entries' time to live when
+    /// missing; an existing bucket keeps its own.
+    pub async fn acquire(
+        js: &jetstream::Context,
+        bucket: &str,
+        key: &str,
+        ttl: Duration,
+    ) -> RelationshipResult<Self> {
+        let store = match js.get_key_value(bucket).await {
+            Ok(store) => store,
+            Err(_) => {
+                tracing::info!("creating key-value bucket {}", bucket);
+                js.create_key_value(kv::Config {
+                    bucket: bucket.to_string(),
+                    history: 1,
+                    max_age: ttl,
+                    ..Default::default()
+                })
+                .await
+                .map_err(jetstream_error)?
+            }
+        };
+        let ttl = store.status().await.map_err(jetstream_error)?.max_age();
+        if ttl.is_zero() {
+            return Err(RelationshipError::InvalidConfiguration(format!(
+                "bucket {} keeps entries forever; a lease in it would never expire",
+                bucket
+            )));
+        }
+        let holder = Uuid::now_v7().to_string();
+
+        let mut standing_by = false;
+        loop {
+            let entry = store.entry(key).await.map_err(jetstream_error)?;
+            if let Some(revision) = vacant_at(entry.map(|e| (e.operation, e.revision))) {
+                // Fails when another instance took it in between
+                if let Ok(revision) = store.update(key, holder.clone().into(), revision).await {
+                    tracing::info!("lease {} taken as {}", key, holder);
+                    return Ok(Self {
+                        bucket: store,
+                        key: key.to_string(),
+                        holder,
+                        ttl,
+                        revision: AtomicU64::new(revision),
+                    });
+                }
+            }
+            if !standing_by {
+                tracing::info!("lease {} held by another instance; standing by", key);
+                standing_by = true;
+            }
+            tokio::time::sleep(ttl / 3).await;
+        }
+    }
+
+    /// Renew the lease every third of its time to live until shutdown
+    ///
+    /// Gives up once two thirds of its time to live pass without a renewal,
+    /// a third before a standby may take it over: it triggers `shutdown`,
+    /// so the service stops taking commands, and fails.
+    pub async fn keep(&self, shutdown: &Shutdown) -> RelationshipResult<()> {
+        let period = self.ttl / 3;
+        let mut ticks = tokio::time::interval(period);
+        ticks.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        let mut renewed = tokio::time::Instant::now();
+        loop {
+            tokio::select! {
+                biased;
+                _ = shutdown.triggered() => return Ok(()),
+                at = ticks.tick() => {
+                    let revision = self.revision.load(Ordering::SeqCst);
+                    let update = self.bucket.update(&self.key, self.holder.clone().into(), revision);
+                    let renewal = match tokio::time::timeout(period, update).await {
+                        Ok(renewal) => renewal.map_err(jetstream_error),
+                        Err(_) => Err(RelationshipError::TransportError(format!("no answer within {:?}", period))),
+                    };
+                    match renewal {
+                        Ok(revision) => {
+                            self.revision.store(revision, Ordering::SeqCst);
+                            renewed = at;
+                        }
+                        Err(e) if !lease_lapsing(at.saturating_duration_since(renewed), period) => {
+                            tracing::warn!("failed to renew lease {}: {}", self.key, e);
+                        }
+                        Err(e) => {
+                            shutdown.trigger();
+                            return Err(RelationshipError::TransportError(format!(
+                                "lease {} given up, not renewed for {:?}: {}",
+                                self.key,
+                                at.saturating_duration_since(renewed),
+                                e
+                            )));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Give the lease up, if still held, for a standby to take at once
+    pub async fn release(&self) -> RelationshipResult<()> {
+        let held = self
+            .bucket
+            .entry(self.key.as_str())
+            .await
+            .map_err(jetstream_error)?
+            .is_some_and(|entry| entry.operation == kv::Operation::Put && entry.value == self.holder.as_bytes());
+        if held {
+            self.bucket.delete(&self.key).await.map_err(jetstream_error)?;
+        }
+        Ok(())
+    }
+}
+
+/// Revision a lease key is vacant at, given its latest entry's operation
+/// and revision: free when never written, released or expired
+fn vacant_at(entry: Option<(kv::Operation, u64)>) -> Option<u64> {
+    match entry {
+        None => Some(0),
+        Some((kv::Operation::Put, _)) => None,
+        Some((_, revision)) => Some(revision),
+    }
+}
+
+/// Check if a lease renewed every `period`, last `since_renewal` ago, is
+/// to be given up: a standby may take it a period after the next renewal
+/// misses
+fn lease_lapsing(since_renewal: Duration, period: Duration) -> bool {
+    since_renewal >= period * 2
+}
+
+// ---- Webhooks ----
+
+/// Open the stream dead letters are kept in, creating it if missing
+pub async fn dead_letter_stream(js: &jetstream::Context, name: &str) -> RelationshipResult<stream::Stream> {
+    js.get_or_create_stream(stream::Config {
+        name: name.to_string(),
+        subjects: vec![RelationshipSubjects::all_webhook_dead_letters()],
+        ..Default::default()
+    })
+    .await
+    .map_err(jetstream_error)
+}
+
+/// Delivers relationship events to webhooks, following the events stream
+/// through a durable consumer
+///
+/// The feed applies each event to a space of its own before matching
+/// webhooks against it, so filters see the relationship as of that event.
+/// The consumer hands out one event at a time, acked once every delivery
+/// succeeded or was dead-lettered; after a restart the space is rebuilt up
+/// to the consumer's ack floor and delivery resumes after it. A consumer
+/// feeds one feed: a second would receive only part of the stream.
+pub struct WebhookFeed<S: WebhookSender> {
+    dispatcher: WebhookDispatcher<S>,
+    snapshot: Snapshot,
+    consumer: PullConsumer,
+    js: jetstream::Context,
+    shutdown: Shutdown,
+}
+
+impl<S: WebhookSender> std::fmt::Debug for WebhookFeed<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebhookFeed")
+            .field("sequence", &self.snapshot.sequence)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: WebhookSender> WebhookFeed<S> {
+    /// Feed through the durable consumer `name` of `events`, created if
+    /// missing to deliver only events published from now on
+    ///
+    /// The space is rebuilt from `base` when it is no newer than the
+    /// consumer's ack floor, and from the start of the stream otherwise.
+    pub async fn start(
+        dispatcher: WebhookDispatcher<S>,
+        js: jetstream::Context,
+        events: &mut stream::Stream,
+        name: &str,
+        base: Option<Snapshot>,
+    ) -> RelationshipResult<Self> {
+        let mut consumer: PullConsumer = events
+            .get_or_create_consumer(
+                name,
+                consumer::pull::Config {
+                    durable_name: Some(name.to_string()),
+                    filter_subject: RelationshipSubjects::all_events(),
+                    deliver_policy: consumer::DeliverPolicy::New,
+                    ack_policy: consumer::AckPolicy::Explicit,
+                    // One event in hand at a time, so they apply in order
+                    max_ack_pending: 1,
+                    ..Default::default()
+                },
+            )
+            .await
+            .map_err(jetstream_error)?;
+        let floor = consumer.info().await.map_err(jetstream_error)?.ack_floor.stream_sequence;
+        let mut snapshot = base
+            .filter(|snapshot| snapshot.sequence <= floor)
+            .unwrap_or_else(|| Snapshot::new(RelationshipSpace::new("webhooks", TopologicalSpaceId::new())));
+        let from = snapshot.sequence;
+        let (replayed, _) = replay_between(events, &mut snapshot.space, from, floor).await?;
+        snapshot.sequence = floor;
+        tracing::info!(
+            "webhook feed {} replayed {} events after sequence {}, resuming after {}",
+            name,
+            replayed,
+            from,
+            floor
+        );
+        Ok(Self {
+            dispatcher,
+            snapshot,
+            consumer,
+            js,
+            shutdown: Shutdown::new(),
+        })
+    }
+
+    /// Stop following, finishing the event in hand, when `shutdown` is
+    /// triggered
+    pub fn with_shutdown(mut self, shutdown: Shutdown) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+
+    pub fn dispatcher(&self) -> &WebhookDispatcher<S> {
+        &self.dispatcher
+    }
+
+    /// Deliver events until the consumer ends or shutdown
+    ///
+    /// Deliveries that gave up are published to their dead-letter subject
+    /// before the event is acked. A dead letter JetStream does not confirm
+    /// stops the feed, leaving the event unacked for after the restart.
+    pub async fn run(&mut self) -> RelationshipResult<()> {
+        let mut messages = self
+            .consumer
+            .messages()
+            .await
+            .map_err(jetstream_error)?;
+        while let Some(message) = self.shutdown.next(&mut messages).await {
+            let message = message.map_err(jetstream_error)?;
+            let sequence = message.info().map_err(jetstream_error)?.stream_sequence;
+            // Redelivered when its ack wait ran out while it was in hand
+            if sequence <= self.snapshot.sequence {
+                message.ack().await.map_err(jetstream_error)?;
+                continue;
+            }
+            self.snapshot.sequence = sequence;
+            let event = match decode_event(&transport_message(&message)) {
+                Ok(event) => event,
+                Err(e) => {
+                    tracing::warn!("webhook feed skipped {}: {}", message.subject, e);
+                    message.ack_with(AckKind::Term).await.map_err(jetstream_error)?;
+                    continue;
+                }
+            };
+            if let Err(e) = self.snapshot.space.apply_event(&event) {
+                tracing::warn!("webhook feed could not apply {}: {}", message.subject, e);
+            }
+            match self.dispatcher.dispatch(&self.snapshot.space, &event).await {
+                Ok(dead_letters) => {
+                    for dead_letter in dead_letters {
+                        self.js
+                            .publish(dead_letter.subject(), encode(&dead_letter)?)
+                            .await
+                            .map_err(jetstream_error)?
+                            .await
+                            .map_err(jetstream_error)?;
+                    }
+                }
+                Err(e) => tracing::warn!("failed to deliver {} to webhooks: {}", message.subject, e),
+            }
+            message.ack().await.map_err(jetstream_error)?;
+        }
+        Ok(())
+    }
+}
+
+fn transport_message(message: &jetstream::Message) -> TransportMessage {
+    TransportMessage {
+        subject: message.subject.to_string(),
+        payload: message.payload.clone(),
+        reply: None,
+        headers: message
+            .headers
+            .as_ref()
+            .map(from_header_map)
+            .unwrap_or_default(),
+    }
+}
+
+fn jetstream_error(error: impl std::fmt::Display) -> RelationshipError {
+    RelationshipError::TransportError(error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upstream_ack() {
+        assert!(matches!(upstream_ack(&Ok(2)), AckKind::Ack));
+        let unstored = Err(RelationshipError::TransportError("publish refused".to_string()));
+        assert!(matches!(upstream_ack(&unstored), AckKind::Nak(None)));
+        let undecodable = Err(RelationshipError::SerializationError("not JSON".to_string()));
+        assert!(matches!(upstream_ack(&undecodable), AckKind::Term));
+    }
+
+    #[test]
+    fn test_replay_range() {
+        // Nothing kept, or nothing after the snapshot
+        assert_eq!(replay_range(0, 0, 0, 0, u64::MAX), None);
+        assert_eq!(replay_range(10, 1, 10, 10, u64::MAX), None);
+        // Everything after the snapshot, or up to `through`
+        assert_eq!(replay_range(10, 1, 10, 4, u64::MAX), Some(5..=10));
+        assert_eq!(replay_range(10, 1, 10, 4, 7), Some(5..=7));
+        assert_eq!(replay_range(10, 1, 10, 4, 4), None);
+        assert_eq!(replay_range(10, 1, 10, 8, 7), None);
+        // Events no longer kept are skipped
+        assert_eq!(replay_range(5, 6, 10, 2, u64::MAX), Some(6..=10));
+        assert_eq!(replay_range(5, 6, 10, 2, 4), None);
+    }
+
+    #[test]
+    fn test_lease_handover() {
+        // Taken while vacant, at the revision it became vacant at
+        assert_eq!(vacant_at(None), Some(0));
+        assert_eq!(vacant_at(Some((kv::Operation::Put, 7))), None);
+        assert_eq!(vacant_at(Some((kv::Operation::Delete, 8))), Some(8));
+        assert_eq!(vacant_at(Some((kv::Operation::Purge, 9))), Some(9));
+
+        // With renewals every 10s of a 30s lease, one missed renewal is
+        // tolerated; the holder gives up at 20s, before a standby takes over
+        let (ttl, period) = (Duration::from_secs(30), Duration::from_secs(10));
+        assert!(!lease_lapsing(Duration::ZERO, period));
+        assert!(!lease_lapsing(period, period));
+        assert!(lease_lapsing(period * 2, period));
+        assert!(period * 2 < ttl);
+    }
+
+    #[test]
+    fn test_consumer_names() {
+        let assets = JetStreamAssets::new(vec!["person.events.>".to_string()]);
+        assert_eq!(
+            assets.consumer_name("person.events.>"),
+            "relationship-cross-domain-person"
+        );
+        assert_eq!(
+            assets
+                .with_consumer_prefix("rel")
+                .consumer_name("inventory.v2.events.>"),
+            "rel-inventory"
+        );
+    }
+}
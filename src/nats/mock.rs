@@ -0,0 +1,307 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! In-Process Mock Transport
+//!
+//! A `Transport` that routes messages between subscribers in the same
+//! process and records everything published, so downstream crates can
+//! integration-test against the relationship domain without a NATS server.
+//!
+//! ```rust,ignore
+//! let transport = MockTransport::new();
+//! let bus = RelationshipBus::new(transport.clone());
+//!
+//! bus.publish_event(&event).await?;
+//! assert_eq!(transport.events().len(), 1);
+//! ```
+//!
+//! Requests are answered by a responder registered with `on_request`, or
+//! otherwise delivered to matching subscribers with a reply subject, just
+//! as NATS does.
+
+use super::subjects::RelationshipSubjects;
+use super::transport::{MessageStream, Transport, TransportMessage};
+use super::wire::MessageHeaders;
+use crate::events::RelationshipEvent;
+use crate::{RelationshipError, RelationshipResult};
+use async_trait::async_trait;
+use bytes::Bytes;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use uuid::Uuid;
+
+/// Default time to wait for a reply to a request
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+type Responder = Arc<dyn Fn(TransportMessage) -> RelationshipResult<Bytes> + Send + Sync>;
+
+#[derive(Default)]
+struct MockState {
+    published: Vec<TransportMessage>,
+    subscribers: Vec<(String, mpsc::UnboundedSender<TransportMessage>)>,
+    responders: Vec<(String, Responder)>,
+    disconnected: bool,
+    publish_budget: Option<usize>,
+}
+
+/// In-process transport for tests
+#[derive(Clone)]
+pub struct MockTransport {
+    state: Arc<Mutex<MockState>>,
+    request_timeout: Duration,
+}
+
+impl Default for MockTransport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for MockTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MockTransport")
+            .field("published", &self.published().len())
+            .finish()
+    }
+}
+
+impl MockTransport {
+    /// Create an empty transport
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(MockState::default())),
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+        }
+    }
+
+    /// Set how long requests wait for a subscriber's reply
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
+    /// Answer requests on a subject pattern with a function
+    pub fn on_request<F>(&self, pattern: impl Into<String>, responder: F)
+    where
+        F: Fn(TransportMessage) -> RelationshipResult<Bytes> + Send + Sync + 'static,
+    {
+        self.lock().responders.push((pattern.into(), Arc::new(responder)));
+    }
+
+    /// Every message published so far, in order
+    pub fn published(&self) -> Vec<TransportMessage> {
+        self.lock().published.clone()
+    }
+
+    /// Messages published on subjects matching a pattern
+    pub fn published_on(&self, pattern: &str) -> Vec<TransportMessage> {
+        self.lock()
+            .published
+            .iter()
+            .filter(|m| RelationshipSubjects::matches(pattern, &m.subject))
+            .cloned()
+            .collect()
+    }
+
+    /// Relationship events published so far, decoded in the format their
+    /// headers name
+    pub fn events(&self) -> Vec<RelationshipEvent> {
+        self.published_on(&RelationshipSubjects::all_events())
+            .iter()
+            .filter_map(|m| super::bus::decode_event(m).ok())
+            .collect()
+    }
+
+    /// Forget all recorded messages
+    pub fn clear(&self) {
+        self.lock().published.clear();
+    }
+
+    /// Report the bus unreachable, or reachable again, through
+    /// `Transport::is_connected`; messages are still routed
+    pub fn set_connected(&self, connected: bool) {
+        self.lock().disconnected = !connected;
+    }
+
+    /// Refuse publishes once `count` more have gone out, or never again
+    /// with `None`
+    pub fn fail_publishing_after(&self, count: Option<usize>) {
+        self.lock().publish_budget = count;
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, MockState> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Deliver a message to matching subscribers, returning how many got it
+    fn deliver(&self, message: &TransportMessage) -> usize {
+        let mut state = self.lock();
+        state.subscribers.retain(|(_, tx)| !tx.is_closed());
+        state
+            .subscribers
+            .iter()
+            .filter(|(pattern, _)| RelationshipSubjects::matches(pattern, &message.subject))
+            .filter(|(_, tx)| tx.send(message.clone()).is_ok())
+            .count()
+    }
+}
+
+#[async_trait]
+impl Transport for MockTransport {
+    async fn publish(&self, subject: &str, payload: Bytes) -> RelationshipResult<()> {
+        self.publish_with_headers(subject, MessageHeaders::new(), payload).await
+    }
+
+    async fn request(&self, subject: &str, payload: Bytes) -> RelationshipResult<Bytes> {
+        self.request_with_headers(subject, MessageHeaders::new(), payload).await
+    }
+
+    async fn subscribe(&self, subject: &str) -> RelationshipResult<MessageStream> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.lock().subscribers.push((subject.to_string(), tx));
+        Ok(Box::pin(UnboundedReceiverStream::new(rx)))
+    }
+
+    async fn publish_with_headers(
+        &self,
+        subject: &str,
+        headers: MessageHeaders,
+        payload: Bytes,
+    ) -> RelationshipResult<()> {
+        let message = TransportMessage {
+            subject: subject.to_string(),
+            payload,
+            reply: None,
+            headers,
+        };
+        {
+            let mut state = self.lock();
+            match &mut state.publish_budget {
+                Some(0) => return Err(RelationshipError::TransportError(format!("publish on {} refused", subject))),
+                Some(budget) => *budget -= 1,
+                None => {}
+            }
+            state.published.push(message.clone());
+        }
+        self.deliver(&message);
+        Ok(())
+    }
+
+    async fn request_with_headers(
+        &self,
+        subject: &str,
+        headers: MessageHeaders,
+        payload: Bytes,
+    ) -> RelationshipResult<Bytes> {
+        let responder = self
+            .lock()
+            .responders
+            .iter()
+            .find(|(pattern, _)| RelationshipSubjects::matches(pattern, subject))
+            .map(|(_, responder)| responder.clone());
+
+        let inbox = format!("_INBOX.mock.{}", Uuid::now_v7().simple());
+        let message = TransportMessage {
+            subject: subject.to_string(),
+            payload,
+            reply: Some(inbox.clone()),
+            headers,
+        };
+        self.lock().published.push(message.clone());
+
+        if let Some(responder) = responder {
+            return responder(message);
+        }
+
+        let mut replies = self.subscribe(&inbox).await?;
+        if self.deliver(&message) == 0 {
+            return Err(RelationshipError::TransportError(format!(
+                "no responders for {}",
+                subject
+            )));
+        }
+
+        use futures::StreamExt;
+        match tokio::time::timeout(self.request_timeout, replies.next()).await {
+            Ok(Some(reply)) => Ok(reply.payload),
+            Ok(None) => Err(RelationshipError::TransportError("reply channel closed".to_string())),
+            Err(_) => Err(RelationshipError::TransportError(format!(
+                "request to {} timed out",
+                subject
+            ))),
+        }
+    }
+
+    fn is_connected(&self) -> bool {
+        !self.lock().disconnected
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+
+    #[tokio::test]
+    async fn test_publish_and_subscribe() {
+        let transport = MockTransport::new();
+        let mut events = transport.subscribe("relationship.events.>").await.unwrap();
+
+        transport
+            .publish("relationship.events.edge_created", Bytes::from_static(b"{}"))
+            .await
+            .unwrap();
+        transport
+            .publish("person.events.person_created", Bytes::from_static(b"{}"))
+            .await
+            .unwrap();
+
+        let received = events.next().await.unwrap();
+        assert_eq!(received.subject, "relationship.events.edge_created");
+        assert_eq!(transport.published().len(), 2);
+        assert_eq!(transport.published_on("person.>").len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_request_with_responder() {
+        let transport = MockTransport::new();
+        transport.on_request("relationship.queries.>", |_| Ok(Bytes::from_static(b"pong")));
+
+        let reply = transport
+            .request("relationship.queries.ping", Bytes::new())
+            .await
+            .unwrap();
+        assert_eq!(reply, Bytes::from_static(b"pong"));
+    }
+
+    #[tokio::test]
+    async fn test_request_answered_by_subscriber() {
+        let transport = MockTransport::new();
+        let mut requests = transport.subscribe("relationship.commands.>").await.unwrap();
+
+        let server = transport.clone();
+        tokio::spawn(async move {
+            while let Some(message) = requests.next().await {
+                if let Some(reply) = message.reply {
+                    server.publish(&reply, message.payload).await.unwrap();
+                }
+            }
+        });
+
+        let reply = transport
+            .request("relationship.commands.echo", Bytes::from_static(b"hello"))
+            .await
+            .unwrap();
+        assert_eq!(reply, Bytes::from_static(b"hello"));
+    }
+
+    #[tokio::test]
+    async fn test_request_without_responders_fails() {
+        let transport = MockTransport::new();
+        let result = transport.request("nobody.home", Bytes::new()).await;
+        assert!(matches!(result, Err(RelationshipError::TransportError(_))));
+    }
+}
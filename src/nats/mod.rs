@@ -4,7 +4,8 @@
 
 //! NATS integration for the Relationship Domain
 //!
-//! Subject patterns, message handlers, and cross-domain events.
+//! Subject patterns, message transports, and typed command/query/event
+//! plumbing.
 //!
 //! ## Subject Patterns
 //!
@@ -14,7 +15,62 @@
 //! relationship.events.{event_type}
 //! relationship.commands.{command_type}
 //! relationship.queries.{query_type}
+//! relationship.schema.{events|commands}.{type}
+//! relationship.health.{live|ready}
+//! relationship.webhooks.commands.{command_type}
+//! relationship.webhooks.dead_letters.{webhook_id}
 //! ```
 
-// Placeholder for NATS integration
-// TODO: Implement RelationshipSubjects, RelationshipCommandHandler, CrossDomainEventHandler
+//!
+//! ## Transports
+//!
+//! ```text
+//! RelationshipBus<T: Transport>
+//!     |
+//!     +-- NatsTransport: async-nats client
+//!     +-- MockTransport: in-process, records every message (tests)
+//! ```
+//!
+//! ## Wire Formats
+//!
+//! JSON by default; protobuf (`proto/relationship.proto`) with the
+//! `protobuf` feature, negotiated per message through `Content-Type`.
+//! Events can also travel as CloudEvents 1.0 envelopes
+//! (`application/cloudevents+json`) for non-CIM event meshes.
+//!
+//! ## Service Side
+//!
+//! `RelationshipWorker` answers what the bus sends: it decides commands,
+//! publishes their events, answers queries, and reacts to upstream domain
+//! events. `JetStreamAssets` checks the streams and durable consumers the
+//! worker relies on before it starts. `HealthMonitor` answers liveness and
+//! readiness probes. Triggering a `Shutdown` stops them all taking new
+//! messages once the ones in hand are handled.
+
+mod bus;
+mod cloudevents;
+mod health;
+pub mod jetstream;
+mod mock;
+#[cfg(feature = "protobuf")]
+pub mod protobuf;
+mod shutdown;
+mod subjects;
+mod transport;
+mod wire;
+mod worker;
+
+pub use bus::{decode, encode, CommandResponse, EventStream, RelationshipBus};
+pub use cloudevents::{
+    is_valid_traceparent, CloudEvent, CloudEventEnvelope, CLOUDEVENTS_CONTENT_TYPE, CLOUDEVENTS_SPEC_VERSION,
+    CLOUDEVENT_TYPE_PREFIX, DEFAULT_CLOUDEVENT_SOURCE,
+};
+pub use health::{
+    HealthCheck, HealthMonitor, HealthReport, Probe, ProjectionProgress, DEFAULT_MAX_LAG,
+};
+pub use mock::MockTransport;
+pub use shutdown::Shutdown;
+pub use subjects::RelationshipSubjects;
+pub use transport::{MessageStream, NatsTransport, Transport, TransportMessage};
+pub use wire::{MessageHeaders, WireFormat, CONTENT_TYPE_HEADER};
+pub use worker::RelationshipWorker;
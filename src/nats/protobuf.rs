@@ -0,0 +1,920 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Protobuf Payloads (`protobuf` feature)
+//!
+//! The messages of `proto/relationship.proto`, generated by prost-build,
+//! and their conversions to and from the domain types. Every event and
+//! command has a message of its own; a payload is one of the envelopes:
+//!
+//! ```text
+//! RelationshipEvent   <--> proto::RelationshipEvent   --prost--> bytes
+//! RelationshipCommand <--> proto::RelationshipCommand --prost--> bytes
+//! CommandResponse     <--> proto::CommandResponse     --prost--> bytes
+//! ```
+//!
+//! Decoding fails with a `SerializationError` naming the field when a
+//! required message is missing, an enum value is unknown or an id is
+//! malformed. Types owned by cim-domain (`MessageIdentity`, `ConceptId`,
+//! `KnowledgeLevel`) travel in their serde text form.
+
+use super::bus::CommandResponse;
+use crate::commands::{self, BatchMode, TemplateEndpoints};
+use crate::events;
+use crate::invariants::{self, EndpointSide, PolicyRule};
+use crate::quality;
+use crate::value_objects::{
+    self, ActivationMode, EntityType, Formality, IncidenceMatrix, ParticipantRole, RelationshipCategory,
+    RelationshipId,
+};
+use crate::{RelationshipError, RelationshipResult};
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use cim_domain::MessageIdentity;
+use cim_domain_spaces::{ConceptId, KnowledgeLevel};
+use prost::Message as _;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use uuid::Uuid;
+
+/// Messages generated from `proto/relationship.proto`, and with the `grpc`
+/// feature the service of `proto/relationship_service.proto`
+#[allow(missing_docs, clippy::all)]
+pub mod proto {
+    include!(concat!(env!("OUT_DIR"), "/cim.relationship.v1.rs"));
+}
+
+/// An event, command or command reply with a protobuf message
+pub trait ProtobufMessage: Sized {
+    /// Encode as protobuf
+    fn encode_protobuf(&self) -> Bytes;
+
+    /// Decode from protobuf
+    fn decode_protobuf(payload: &[u8]) -> RelationshipResult<Self>;
+}
+
+/// Encode a message payload as protobuf
+pub fn encode<M: ProtobufMessage>(message: &M) -> RelationshipResult<Bytes> {
+    Ok(message.encode_protobuf())
+}
+
+/// Decode a protobuf message payload
+pub fn decode<M: ProtobufMessage>(payload: &[u8]) -> RelationshipResult<M> {
+    M::decode_protobuf(payload)
+}
+
+macro_rules! envelopes {
+    ($($name:ty => $message:ident),* $(,)?) => {$(
+        impl ProtobufMessage for $name {
+            fn encode_protobuf(&self) -> Bytes {
+                Bytes::from(self.to_proto().encode_to_vec())
+            }
+
+            fn decode_protobuf(payload: &[u8]) -> RelationshipResult<Self> {
+                Self::from_proto(proto::$message::decode(payload).map_err(protobuf_error)?)
+            }
+        }
+
+        impl From<&$name> for proto::$message {
+            fn from(message: &$name) -> Self {
+                message.to_proto()
+            }
+        }
+
+        impl TryFrom<proto::$message> for $name {
+            type Error = RelationshipError;
+
+            fn try_from(message: proto::$message) -> RelationshipResult<Self> {
+                <$name>::from_proto(message)
+            }
+        }
+    )*};
+}
+
+envelopes! {
+    events::RelationshipEvent => RelationshipEvent,
+    commands::RelationshipCommand => RelationshipCommand,
+    CommandResponse => CommandResponse,
+}
+
+// ---- Conversions ----
+
+/// A domain type with a message of its own
+trait Proto: Sized {
+    type Message;
+
+    fn to_proto(&self) -> Self::Message;
+
+    fn from_proto(message: Self::Message) -> RelationshipResult<Self>;
+}
+
+/// A struct field and the message field it travels in
+///
+/// prost makes every message field an `Option`, so a required message is
+/// checked for on decoding.
+trait ProtoField: Sized {
+    type Field;
+
+    fn to_field(&self) -> Self::Field;
+
+    fn from_field(field: Self::Field, name: &'static str) -> RelationshipResult<Self>;
+}
+
+impl<T: Proto> ProtoField for T {
+    type Field = Option<T::Message>;
+
+    fn to_field(&self) -> Self::Field {
+        Some(self.to_proto())
+    }
+
+    fn from_field(field: Self::Field, name: &'static str) -> RelationshipResult<Self> {
+        T::from_proto(field.ok_or_else(|| protobuf_error(format!("missing {}", name)))?)
+    }
+}
+
+impl<T: Proto> ProtoField for Option<T> {
+    type Field = Option<T::Message>;
+
+    fn to_field(&self) -> Self::Field {
+        self.as_ref().map(T::to_proto)
+    }
+
+    fn from_field(field: Self::Field, _name: &'static str) -> RelationshipResult<Self> {
+        field.map(T::from_proto).transpose()
+    }
+}
+
+impl<T: Proto> ProtoField for Vec<T> {
+    type Field = Vec<T::Message>;
+
+    fn to_field(&self) -> Self::Field {
+        self.iter().map(T::to_proto).collect()
+    }
+
+    fn from_field(field: Self::Field, _name: &'static str) -> RelationshipResult<Self> {
+        field.into_iter().map(T::from_proto).collect()
+    }
+}
+
+impl<T: Proto + Eq + Hash> ProtoField for HashSet<T> {
+    type Field = Vec<T::Message>;
+
+    fn to_field(&self) -> Self::Field {
+        self.iter().map(T::to_proto).collect()
+    }
+
+    fn from_field(field: Self::Field, _name: &'static str) -> RelationshipResult<Self> {
+        field.into_iter().map(T::from_proto).collect()
+    }
+}
+
+macro_rules! scalars {
+    ($($name:ty),* $(,)?) => {$(
+        impl ProtoField for $name {
+            type Field = $name;
+
+            fn to_field(&self) -> Self::Field {
+                self.clone()
+            }
+
+            fn from_field(field: Self::Field, _name: &'static str) -> RelationshipResult<Self> {
+                Ok(field)
+            }
+        }
+    )*};
+}
+
+scalars!(String, f64, bool, Option<String>, Option<u64>, Vec<String>);
+
+impl ProtoField for Uuid {
+    type Field = String;
+
+    fn to_field(&self) -> Self::Field {
+        self.to_string()
+    }
+
+    fn from_field(field: Self::Field, name: &'static str) -> RelationshipResult<Self> {
+        Uuid::parse_str(&field).map_err(|e| invalid(name, e))
+    }
+}
+
+impl ProtoField for RelationshipId {
+    type Field = String;
+
+    fn to_field(&self) -> Self::Field {
+        self.as_uuid().to_string()
+    }
+
+    fn from_field(field: Self::Field, name: &'static str) -> RelationshipResult<Self> {
+        Uuid::from_field(field, name).map(RelationshipId::from_uuid)
+    }
+}
+
+impl ProtoField for ConceptId {
+    type Field = String;
+
+    fn to_field(&self) -> Self::Field {
+        serde_text(self)
+    }
+
+    fn from_field(field: Self::Field, name: &'static str) -> RelationshipResult<Self> {
+        from_serde_text(field, name)
+    }
+}
+
+impl ProtoField for KnowledgeLevel {
+    type Field = String;
+
+    fn to_field(&self) -> Self::Field {
+        serde_text(self)
+    }
+
+    fn from_field(field: Self::Field, name: &'static str) -> RelationshipResult<Self> {
+        from_serde_text(field, name)
+    }
+}
+
+/// Free-form values; a missing value is null
+impl ProtoField for serde_json::Value {
+    type Field = Option<proto::Value>;
+
+    fn to_field(&self) -> Self::Field {
+        Some(proto::Value::from(self))
+    }
+
+    fn from_field(field: Self::Field, _name: &'static str) -> RelationshipResult<Self> {
+        Ok(field.map(serde_json::Value::from).unwrap_or_default())
+    }
+}
+
+impl ProtoField for HashMap<String, serde_json::Value> {
+    type Field = HashMap<String, proto::Value>;
+
+    fn to_field(&self) -> Self::Field {
+        self.iter().map(|(key, value)| (key.clone(), proto::Value::from(value))).collect()
+    }
+
+    fn from_field(field: Self::Field, _name: &'static str) -> RelationshipResult<Self> {
+        Ok(field.into_iter().map(|(key, value)| (key, value.into())).collect())
+    }
+}
+
+impl Proto for DateTime<Utc> {
+    type Message = prost_types::Timestamp;
+
+    fn to_proto(&self) -> Self::Message {
+        prost_types::Timestamp {
+            seconds: self.timestamp(),
+            nanos: self.timestamp_subsec_nanos() as i32,
+        }
+    }
+
+    fn from_proto(message: Self::Message) -> RelationshipResult<Self> {
+        u32::try_from(message.nanos)
+            .ok()
+            .and_then(|nanos| DateTime::from_timestamp(message.seconds, nanos))
+            .ok_or_else(|| invalid("timestamp", message))
+    }
+}
+
+impl Proto for MessageIdentity {
+    type Message = proto::MessageIdentity;
+
+    fn to_proto(&self) -> Self::Message {
+        let identity = serde_json::to_value(self).unwrap_or_default();
+        let id = |name: &str| identity.get(name).cloned().map(text).unwrap_or_default();
+        proto::MessageIdentity {
+            message_id: id("message_id"),
+            correlation_id: id("correlation_id"),
+            causation_id: id("causation_id"),
+        }
+    }
+
+    fn from_proto(message: Self::Message) -> RelationshipResult<Self> {
+        let identity = serde_json::json!({
+            "message_id": untext(message.message_id),
+            "correlation_id": untext(message.correlation_id),
+            "causation_id": untext(message.causation_id),
+        });
+        serde_json::from_value(identity).map_err(|e| invalid("identity", e))
+    }
+}
+
+/// Structs whose fields each have a message field of the same name
+macro_rules! messages {
+    ($($module:ident :: $name:ident { $($field:ident),* $(,)? })*) => {$(
+        impl Proto for $module::$name {
+            type Message = proto::$name;
+
+            fn to_proto(&self) -> Self::Message {
+                proto::$name {
+                    $($field: self.$field.to_field(),)*
+                }
+            }
+
+            fn from_proto(message: Self::Message) -> RelationshipResult<Self> {
+                Ok(Self {
+                    $($field: ProtoField::from_field(message.$field, stringify!($field))?,)*
+                })
+            }
+        }
+    )*};
+}
+
+messages! {
+    value_objects::EntityRef { entity_type, entity_id, cid, version }
+    value_objects::EntityKey { entity_type, entity_id }
+    value_objects::ValidityPeriod { starts_at, ends_at, end_reason }
+    value_objects::ParticipantEntry { entity_ref, role, weight, joined_at }
+    value_objects::ParticipantGroup { name, role, parent, members }
+    quality::RelationshipQuality { strength, trust, formality, duration, reciprocity }
+    invariants::RelationshipPolicy { policy, name, rule }
+    events::EdgeCreated {
+        event_id, identity, edge_id, concept_id, source, target, category, name, created_by, created_at,
+    }
+    events::EdgeActivated { event_id, identity, edge_id, activated_by, activated_at }
+    events::EdgeSuspended { event_id, identity, edge_id, reason, suspended_by, suspended_at }
+    events::EdgeTerminated { event_id, identity, edge_id, reason, terminated_by, terminated_at }
+    events::EdgeRejected { event_id, identity, edge_id, reason, rejected_by, rejected_at }
+    events::EdgeReproposed { event_id, identity, edge_id, reason, reproposed_by, reproposed_at }
+    events::EdgeQualityUpdated { event_id, identity, edge_id, old_quality, new_quality, reason, updated_at }
+    events::EdgeEvidenceAdded { event_id, identity, edge_id, evidence_cid, evidence_type, added_at }
+    events::EdgeEvidenceRevoked { event_id, identity, edge_id, evidence_cid, reason, revoked_by, revoked_at }
+    events::EdgeKnowledgeProgressed {
+        event_id, identity, edge_id, from_level, to_level, new_confidence, reason, progressed_at,
+    }
+    events::EdgePropertyUpdated { event_id, identity, edge_id, key, value, updated_by, updated_at }
+    events::EdgePropertyRemoved { event_id, identity, edge_id, key, removed_by, removed_at }
+    events::EdgeEndpointsRewritten {
+        event_id, identity, edge_id, old_source, old_target, new_source, new_target, reason, rewritten_by,
+        rewritten_at,
+    }
+    events::EdgeReversed { event_id, identity, edge_id, old_category, new_category, reason, reversed_by, reversed_at }
+    events::EdgeConsentRequested { event_id, identity, edge_id, expires_at, requested_by, requested_at }
+    events::EdgeConsentGranted { event_id, identity, edge_id, party, granted_at }
+    events::EdgeConsentDeclined { event_id, identity, edge_id, party, reason, declined_at }
+    events::EdgeTemplateApplied {
+        event_id, identity, edge_id, template, properties, valid_until, applied_by, applied_at,
+    }
+    events::EdgeTagAdded { event_id, identity, edge_id, tag, tagged_by, tagged_at }
+    events::EdgeTagRemoved { event_id, identity, edge_id, tag, removed_by, removed_at }
+    events::EdgeArchived { event_id, identity, edge_id, reason, archived_by, archived_at }
+    events::EdgeRestored { event_id, identity, edge_id, restored_by, restored_at }
+    events::EdgeRenamed { event_id, identity, edge_id, old_name, new_name, renamed_by, renamed_at }
+    events::EdgeDescriptionUpdated { event_id, identity, edge_id, description, updated_by, updated_at }
+    events::HyperEdgeCreated {
+        event_id, identity, hyperedge_id, concept_id, name, category, initial_participants, activation, created_by,
+        created_at,
+    }
+    events::HyperEdgeActivated { event_id, identity, hyperedge_id, activated_by, activated_at }
+    events::ParticipantAdded { event_id, identity, hyperedge_id, participant, role, weight, added_by, added_at }
+    events::ParticipantRemoved { event_id, identity, hyperedge_id, participant, reason, removed_by, removed_at }
+    events::ParticipantRoleChanged {
+        event_id, identity, hyperedge_id, participant, old_role, new_role, changed_by, changed_at,
+    }
+    events::ParticipantWeightChanged {
+        event_id, identity, hyperedge_id, participant, old_weight, new_weight, changed_by, changed_at,
+    }
+    events::ParticipantAcknowledged { event_id, identity, hyperedge_id, participant, acknowledged_at }
+    events::HyperEdgeTerminated { event_id, identity, hyperedge_id, reason, terminated_by, terminated_at }
+    events::RestructuringBegun { event_id, identity, hyperedge_id, reason, begun_by, begun_at }
+    events::RestructuringCompleted { event_id, identity, hyperedge_id, completed_by, completed_at }
+    events::HyperEdgeQualityUpdated { event_id, identity, hyperedge_id, old_quality, new_quality, reason, updated_at }
+    events::HyperEdgePromotedFromEdge {
+        event_id, identity, hyperedge_id, edge_id, knowledge_level, confidence, evidence_cids, properties,
+        promoted_by, promoted_at,
+    }
+    events::HyperEdgeTagAdded { event_id, identity, hyperedge_id, tag, tagged_by, tagged_at }
+    events::HyperEdgeTagRemoved { event_id, identity, hyperedge_id, tag, removed_by, removed_at }
+    events::ParticipantGroupAdded { event_id, identity, hyperedge_id, name, role, parent, added_by, added_at }
+    events::ParticipantGroupRemoved { event_id, identity, hyperedge_id, name, removed_by, removed_at }
+    events::ParticipantAssignedToGroup {
+        event_id, identity, hyperedge_id, participant, group, assigned_by, assigned_at,
+    }
+    events::ParticipantUnassignedFromGroup {
+        event_id, identity, hyperedge_id, participant, group, unassigned_by, unassigned_at,
+    }
+    events::HyperEdgeArchived { event_id, identity, hyperedge_id, reason, archived_by, archived_at }
+    events::HyperEdgeRestored { event_id, identity, hyperedge_id, restored_by, restored_at }
+    events::HyperEdgeRenamed { event_id, identity, hyperedge_id, old_name, new_name, renamed_by, renamed_at }
+    events::HyperEdgeDescriptionUpdated { event_id, identity, hyperedge_id, description, updated_by, updated_at }
+    events::HyperEdgeTemplateApplied {
+        event_id, identity, hyperedge_id, template, properties, valid_until, applied_by, applied_at,
+    }
+    events::PolicyEnacted { event_id, identity, policy, enacted_by, enacted_at }
+    events::PolicyRevoked { event_id, identity, policy, reason, revoked_by, revoked_at }
+    commands::CreateEdge { identity, edge_id, source, target, category, name, quality, created_by }
+    commands::ActivateEdge { identity, edge_id, activated_by }
+    commands::SuspendEdge { identity, edge_id, reason, suspended_by }
+    commands::ResumeEdge { identity, edge_id, resumed_by }
+    commands::TerminateEdge { identity, edge_id, reason, terminated_by }
+    commands::RejectEdge { identity, edge_id, reason, rejected_by }
+    commands::ReproposeEdge { identity, edge_id, reason, reproposed_by }
+    commands::ExpireEdge { identity, edge_id, as_of }
+    commands::UpdateEdgeQuality { identity, edge_id, new_quality, reason }
+    commands::AddEdgeEvidence { identity, edge_id, evidence_cid, evidence_type }
+    commands::RevokeEdgeEvidence { identity, edge_id, evidence_cid, reason, revoked_by }
+    commands::ProgressKnowledge { identity, edge_id, to_level, confidence, reason }
+    commands::SetEdgeProperty { identity, edge_id, key, value, expected_version, set_by }
+    commands::RemoveEdgeProperty { identity, edge_id, key, expected_version, removed_by }
+    commands::AddEdgeTag { identity, edge_id, tag, tagged_by }
+    commands::RemoveEdgeTag { identity, edge_id, tag, removed_by }
+    commands::ReverseEdge { identity, edge_id, invert_category, reason, reversed_by }
+    commands::RedirectEdge { identity, edge_id, new_source, new_target, reason, redirected_by }
+    commands::RequestConsent { identity, edge_id, expires_at, requested_by }
+    commands::GrantConsent { identity, edge_id, party }
+    commands::DeclineConsent { identity, edge_id, party, reason }
+    commands::ExpireConsent { identity, edge_id }
+    commands::CreateHyperEdge { identity, hyperedge_id, name, category, initial_participants, activation, created_by }
+    commands::ActivateHyperEdge { identity, hyperedge_id, activated_by }
+    commands::AddParticipant { identity, hyperedge_id, participant, role, weight, added_by }
+    commands::RemoveParticipant { identity, hyperedge_id, participant, reason, removed_by }
+    commands::ChangeParticipantRole { identity, hyperedge_id, participant, new_role, changed_by }
+    commands::ChangeParticipantWeight { identity, hyperedge_id, participant, new_weight, changed_by }
+    commands::AcknowledgeParticipation { identity, hyperedge_id, participant }
+    commands::TerminateHyperEdge { identity, hyperedge_id, reason, terminated_by }
+    commands::UpdateHyperEdgeQuality { identity, hyperedge_id, new_quality, reason }
+    commands::BeginRestructuring { identity, hyperedge_id, reason, begun_by }
+    commands::CompleteRestructuring { identity, hyperedge_id, completed_by }
+    commands::AddHyperEdgeTag { identity, hyperedge_id, tag, tagged_by }
+    commands::RemoveHyperEdgeTag { identity, hyperedge_id, tag, removed_by }
+    commands::AddParticipantGroup { identity, hyperedge_id, name, role, parent, added_by }
+    commands::RemoveParticipantGroup { identity, hyperedge_id, name, removed_by }
+    commands::AssignToGroup { identity, hyperedge_id, participant, group, assigned_by }
+    commands::UnassignFromGroup { identity, hyperedge_id, participant, group, unassigned_by }
+    commands::PromoteEdgeToHyperEdge {
+        identity, edge_id, hyperedge_id, name, category, source_role, target_role, new_participants, promoted_by,
+    }
+    commands::MergeEdges { identity, survivor_id, duplicate_id, merged_by }
+    commands::DecomposeHyperEdge { identity, hyperedge_id, from_role, to_role, category, decomposed_by }
+    commands::ArchiveRelationship { identity, relationship_id, reason, archived_by }
+    commands::RestoreRelationship { identity, relationship_id, restored_by }
+    commands::RenameRelationship { identity, relationship_id, name, renamed_by }
+    commands::UpdateDescription { identity, relationship_id, description, updated_by }
+    commands::CreateFromTemplate {
+        identity, relationship_id, template, name, endpoints, properties, valid_until, created_by,
+    }
+    commands::CreateEdgesBatch { identity, edges, mode, created_by }
+    commands::BatchEdge { edge_id, source, target, category, name, quality }
+    commands::AddParticipantsBatch { identity, participants, mode, added_by }
+    commands::BatchParticipant { hyperedge_id, participant, role, weight }
+    commands::EnactPolicy { identity, policy, enacted_by }
+    commands::RevokePolicy { identity, policy, reason, revoked_by }
+}
+
+/// Enums whose variants each have a oneof case of the same name
+macro_rules! oneofs {
+    ($($module:ident :: $name:ident => $oneof_module:ident :: $oneof:ident . $field:ident {
+        $($variant:ident),* $(,)?
+    })*) => {$(
+        impl Proto for $module::$name {
+            type Message = proto::$name;
+
+            fn to_proto(&self) -> Self::Message {
+                use proto::$oneof_module::$oneof;
+                let $field = match self {
+                    $(Self::$variant(inner) => $oneof::$variant(inner.to_proto()),)*
+                };
+                proto::$name { $field: Some($field) }
+            }
+
+            fn from_proto(message: Self::Message) -> RelationshipResult<Self> {
+                use proto::$oneof_module::$oneof;
+                match message.$field {
+                    $(Some($oneof::$variant(inner)) => Ok(Self::$variant(Proto::from_proto(inner)?)),)*
+                    None => Err(protobuf_error(format!("missing {}", stringify!($name)))),
+                }
+            }
+        }
+    )*};
+}
+
+oneofs! {
+    events::EdgeEvent => edge_event::Event.event {
+        EdgeCreated, EdgeActivated, EdgeSuspended, EdgeTerminated, EdgeRejected, Reproposed, QualityUpdated,
+        EvidenceAdded, EvidenceRevoked, KnowledgeProgressed, PropertyUpdated, PropertyRemoved, EndpointsRewritten,
+        Reversed, ConsentRequested, ConsentGranted, ConsentDeclined, TemplateApplied, TagAdded, TagRemoved, Archived,
+        Restored, Renamed, DescriptionUpdated,
+    }
+    events::HyperEdgeEvent => hyper_edge_event::Event.event {
+        HyperEdgeCreated, HyperEdgeActivated, ParticipantAdded, ParticipantRemoved, ParticipantRoleChanged,
+        ParticipantWeightChanged, ParticipantAcknowledged, HyperEdgeTerminated, RestructuringBegun,
+        RestructuringCompleted, HyperEdgeQualityUpdated, PromotedFromEdge, TemplateApplied, TagAdded, TagRemoved,
+        GroupAdded, GroupRemoved, AssignedToGroup, UnassignedFromGroup, Archived, Restored, Renamed,
+        DescriptionUpdated,
+    }
+    events::PolicyEvent => policy_event::Event.event { PolicyEnacted, PolicyRevoked }
+    events::RelationshipEvent => relationship_event::Event.event { Edge, HyperEdge, Policy }
+    commands::EdgeCommand => edge_command::Command.command {
+        CreateEdge, ActivateEdge, SuspendEdge, ResumeEdge, TerminateEdge, RejectEdge, ReproposeEdge, ExpireEdge,
+        UpdateEdgeQuality, AddEdgeEvidence, RevokeEdgeEvidence, ProgressKnowledge, ReverseEdge, RedirectEdge,
+        RequestConsent, GrantConsent, DeclineConsent, ExpireConsent, SetEdgeProperty, RemoveEdgeProperty, AddEdgeTag,
+        RemoveEdgeTag, ArchiveEdge, RestoreEdge, RenameEdge, DescribeEdge,
+    }
+    commands::HyperEdgeCommand => hyper_edge_command::Command.command {
+        CreateHyperEdge, ActivateHyperEdge, AddParticipant, RemoveParticipant, ChangeParticipantRole,
+        ChangeParticipantWeight, AcknowledgeParticipation, TerminateHyperEdge, UpdateHyperEdgeQuality,
+        BeginRestructuring, CompleteRestructuring, AddHyperEdgeTag, RemoveHyperEdgeTag, AddParticipantGroup,
+        RemoveParticipantGroup, AssignToGroup, UnassignFromGroup, ArchiveHyperEdge, RestoreHyperEdge, RenameHyperEdge,
+        DescribeHyperEdge,
+    }
+    commands::RestructureCommand => restructure_command::Command.command {
+        PromoteEdgeToHyperEdge, MergeEdges, DecomposeHyperEdge,
+    }
+    commands::RelationshipCommand => relationship_command::Command.command {
+        Edge, HyperEdge, Restructure, CreateFromTemplate, CreateEdgesBatch, AddParticipantsBatch, ArchiveRelationship,
+        RestoreRelationship, RenameRelationship, UpdateDescription, EnactPolicy, RevokePolicy,
+    }
+}
+
+/// Enums of well-known kinds plus a custom one
+macro_rules! kinds {
+    ($($name:ident => $module:ident { $($variant:ident),* $(,)? })*) => {$(
+        impl Proto for $name {
+            type Message = proto::$name;
+
+            fn to_proto(&self) -> Self::Message {
+                use proto::$module::{Kind, Value};
+                let value = match self {
+                    $($name::$variant => Value::Known(Kind::$variant as i32),)*
+                    $name::Custom(custom) => Value::Custom(custom.clone()),
+                };
+                proto::$name { value: Some(value) }
+            }
+
+            fn from_proto(message: Self::Message) -> RelationshipResult<Self> {
+                use proto::$module::{Kind, Value};
+                match message.value {
+                    Some(Value::Custom(custom)) => Ok($name::Custom(custom)),
+                    Some(Value::Known(kind)) => match Kind::try_from(kind) {
+                        $(Ok(Kind::$variant) => Ok($name::$variant),)*
+                        _ => Err(invalid(stringify!($name), kind)),
+                    },
+                    None => Err(protobuf_error(format!("missing {}", stringify!($name)))),
+                }
+            }
+        }
+    )*};
+}
+
+kinds! {
+    EntityType => entity_type { Person, Organization, Location, Agent, Policy, Concept, Relationship }
+    RelationshipCategory => relationship_category {
+        Employment, Membership, Ownership, Management, Friendship, ProfessionalContact, Mentorship, PartOf,
+        Contains, DependsOn, Implements, Precedes, Triggers, References, DerivesFrom,
+    }
+    ParticipantRole => participant_role {
+        Primary, Secondary, Observer, Facilitator, Leader, Member, Contributor, Stakeholder, Author, Reviewer,
+        Approver,
+    }
+}
+
+/// Field-less enums with a protobuf enum of their own
+macro_rules! enums {
+    ($($name:ident { $($variant:ident),* $(,)? })*) => {$(
+        impl ProtoField for $name {
+            type Field = i32;
+
+            fn to_field(&self) -> Self::Field {
+                match self {
+                    $($name::$variant => proto::$name::$variant as i32,)*
+                }
+            }
+
+            fn from_field(field: Self::Field, name: &'static str) -> RelationshipResult<Self> {
+                match proto::$name::try_from(field) {
+                    $(Ok(proto::$name::$variant) => Ok($name::$variant),)*
+                    _ => Err(invalid(name, field)),
+                }
+            }
+        }
+    )*};
+}
+
+enums! {
+    Formality { Informal, SemiFormal, Formal, Contractual, Legal }
+    EndpointSide { Source, Target, Both }
+    BatchMode { AllOrNothing, Partial }
+}
+
+impl Proto for IncidenceMatrix {
+    type Message = proto::IncidenceMatrix;
+
+    fn to_proto(&self) -> Self::Message {
+        proto::IncidenceMatrix {
+            participants: self.participants().map(Proto::to_proto).collect(),
+            groups: self.groups().map(Proto::to_proto).collect(),
+        }
+    }
+
+    fn from_proto(message: Self::Message) -> RelationshipResult<Self> {
+        Ok(IncidenceMatrix::from_parts(
+            Vec::from_field(message.participants, "participants")?,
+            Vec::from_field(message.groups, "groups")?,
+        ))
+    }
+}
+
+impl Proto for ActivationMode {
+    type Message = proto::ActivationMode;
+
+    fn to_proto(&self) -> Self::Message {
+        let quorum_threshold = match self {
+            ActivationMode::Immediate => None,
+            ActivationMode::Quorum { threshold } => Some(*threshold),
+        };
+        proto::ActivationMode { quorum_threshold }
+    }
+
+    fn from_proto(message: Self::Message) -> RelationshipResult<Self> {
+        Ok(match message.quorum_threshold {
+            None => ActivationMode::Immediate,
+            Some(threshold) => ActivationMode::Quorum { threshold },
+        })
+    }
+}
+
+impl Proto for PolicyRule {
+    type Message = proto::PolicyRule;
+
+    fn to_proto(&self) -> Self::Message {
+        use proto::policy_rule::{Forbid, Rule};
+        let rule = match self {
+            PolicyRule::Forbid { category, side, entities } => Rule::Forbid(Forbid {
+                category: category.to_field(),
+                side: side.to_field(),
+                entities: entities.to_field(),
+            }),
+        };
+        proto::PolicyRule { rule: Some(rule) }
+    }
+
+    fn from_proto(message: Self::Message) -> RelationshipResult<Self> {
+        use proto::policy_rule::Rule;
+        match message.rule {
+            Some(Rule::Forbid(forbid)) => Ok(PolicyRule::Forbid {
+                category: ProtoField::from_field(forbid.category, "category")?,
+                side: ProtoField::from_field(forbid.side, "side")?,
+                entities: ProtoField::from_field(forbid.entities, "entities")?,
+            }),
+            None => Err(protobuf_error("missing rule")),
+        }
+    }
+}
+
+impl Proto for TemplateEndpoints {
+    type Message = proto::TemplateEndpoints;
+
+    fn to_proto(&self) -> Self::Message {
+        use proto::template_endpoints::{Edge, Endpoints, HyperEdge};
+        let endpoints = match self {
+            TemplateEndpoints::Edge { source, target } => Endpoints::Edge(Edge {
+                source: source.to_field(),
+                target: target.to_field(),
+            }),
+            TemplateEndpoints::HyperEdge { participants } => Endpoints::HyperEdge(HyperEdge {
+                participants: participants.to_field(),
+            }),
+        };
+        proto::TemplateEndpoints { endpoints: Some(endpoints) }
+    }
+
+    fn from_proto(message: Self::Message) -> RelationshipResult<Self> {
+        use proto::template_endpoints::Endpoints;
+        match message.endpoints {
+            Some(Endpoints::Edge(edge)) => Ok(TemplateEndpoints::Edge {
+                source: ProtoField::from_field(edge.source, "source")?,
+                target: ProtoField::from_field(edge.target, "target")?,
+            }),
+            Some(Endpoints::HyperEdge(hyperedge)) => Ok(TemplateEndpoints::HyperEdge {
+                participants: ProtoField::from_field(hyperedge.participants, "participants")?,
+            }),
+            None => Err(protobuf_error("missing endpoints")),
+        }
+    }
+}
+
+impl Proto for CommandResponse {
+    type Message = proto::CommandResponse;
+
+    fn to_proto(&self) -> Self::Message {
+        proto::CommandResponse {
+            accepted: self.accepted,
+            events: self.events.to_field(),
+            error: self.error.clone(),
+        }
+    }
+
+    fn from_proto(message: Self::Message) -> RelationshipResult<Self> {
+        Ok(CommandResponse {
+            accepted: message.accepted,
+            events: ProtoField::from_field(message.events, "events")?,
+            error: message.error,
+        })
+    }
+}
+
+impl From<&serde_json::Value> for proto::Value {
+    fn from(value: &serde_json::Value) -> Self {
+        use proto::value::Kind;
+        let kind = match value {
+            serde_json::Value::Null => None,
+            serde_json::Value::Bool(b) => Some(Kind::BoolValue(*b)),
+            serde_json::Value::Number(n) => match (n.as_i64(), n.as_u64()) {
+                (Some(i), _) => Some(Kind::IntValue(i)),
+                (None, Some(u)) => Some(Kind::UintValue(u)),
+                _ => n.as_f64().map(Kind::DoubleValue),
+            },
+            serde_json::Value::String(s) => Some(Kind::StringValue(s.clone())),
+            serde_json::Value::Array(values) => Some(Kind::ListValue(proto::ListValue {
+                values: values.iter().map(proto::Value::from).collect(),
+            })),
+            serde_json::Value::Object(fields) => Some(Kind::StructValue(proto::Struct {
+                fields: fields.iter().map(|(k, v)| (k.clone(), proto::Value::from(v))).collect(),
+            })),
+        };
+        Self { kind }
+    }
+}
+
+impl From<proto::Value> for serde_json::Value {
+    fn from(value: proto::Value) -> Self {
+        use proto::value::Kind;
+        match value.kind {
+            None => serde_json::Value::Null,
+            Some(Kind::BoolValue(b)) => serde_json::Value::Bool(b),
+            Some(Kind::IntValue(i)) => i.into(),
+            Some(Kind::UintValue(u)) => u.into(),
+            Some(Kind::DoubleValue(f)) => serde_json::Number::from_f64(f)
+                .map(serde_json::Value::Number)
+                .unwrap_or(serde_json::Value::Null),
+            Some(Kind::StringValue(s)) => serde_json::Value::String(s),
+            Some(Kind::ListValue(list)) => {
+                serde_json::Value::Array(list.values.into_iter().map(Into::into).collect())
+            }
+            Some(Kind::StructValue(fields)) => serde_json::Value::Object(
+                fields.fields.into_iter().map(|(k, v)| (k, v.into())).collect(),
+            ),
+        }
+    }
+}
+
+/// An upstream value in its serde form: strings as they are, anything
+/// else as JSON text
+fn serde_text<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value).map(text).unwrap_or_default()
+}
+
+fn from_serde_text<T: DeserializeOwned>(field: String, name: &'static str) -> RelationshipResult<T> {
+    serde_json::from_value(untext(field)).map_err(|e| invalid(name, e))
+}
+
+fn text(value: serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s,
+        other => other.to_string(),
+    }
+}
+
+fn untext(text: String) -> serde_json::Value {
+    serde_json::from_str(&text).unwrap_or(serde_json::Value::String(text))
+}
+
+fn invalid(name: &str, value: impl std::fmt::Debug) -> RelationshipError {
+    protobuf_error(format!("invalid {}: {:?}", name, value))
+}
+
+fn protobuf_error(error: impl std::fmt::Display) -> RelationshipError {
+    RelationshipError::SerializationError(error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{EdgeCommand, EnactPolicy, RelationshipCommand, SetEdgeProperty};
+    use crate::events::{EdgeEvent, EdgeQualityUpdated, HyperEdgeCreated, HyperEdgeEvent, RelationshipEvent};
+    use crate::invariants::RelationshipPolicy;
+    use crate::quality::RelationshipQuality;
+    use crate::value_objects::{EntityRef, ValidityPeriod};
+
+    fn assert_round_trip<M: ProtobufMessage + Serialize>(message: &M) {
+        let decoded: M = decode(&encode(message).unwrap()).unwrap();
+        assert_eq!(serde_json::to_value(&decoded).unwrap(), serde_json::to_value(message).unwrap());
+    }
+
+    #[test]
+    fn test_event_and_command_round_trip() {
+        let mut new_quality = RelationshipQuality::default_employment();
+        new_quality.strength = 1.0;
+        new_quality.duration = ValidityPeriod::ongoing_now().end(Utc::now(), "contract ended");
+        let quality_updated = RelationshipEvent::Edge(EdgeEvent::QualityUpdated(EdgeQualityUpdated {
+            event_id: Uuid::now_v7(),
+            identity: MessageIdentity::new_root(),
+            edge_id: RelationshipId::new(),
+            old_quality: RelationshipQuality::default_employment(),
+            new_quality,
+            reason: "promotion".to_string(),
+            updated_at: Utc::now(),
+        }));
+        assert_round_trip(&quality_updated);
+
+        let lead = EntityRef::person(Uuid::now_v7());
+        let mut participants = IncidenceMatrix::new();
+        participants.add_participant(lead.clone(), ParticipantRole::Leader, 1.0);
+        participants.add_participant(EntityRef::agent(Uuid::now_v7()), ParticipantRole::Custom("bot".into()), 0.5);
+        participants.add_group("core", ParticipantRole::Contributor, None);
+        participants.add_group("leads", ParticipantRole::Custom("steering".into()), Some("core"));
+        participants.assign_to_group(&lead, "leads");
+        let created = RelationshipEvent::HyperEdge(HyperEdgeEvent::HyperEdgeCreated(HyperEdgeCreated {
+            event_id: Uuid::now_v7(),
+            identity: MessageIdentity::new_root(),
+            hyperedge_id: RelationshipId::new(),
+            concept_id: ConceptId::new(),
+            name: "Team".to_string(),
+            category: RelationshipCategory::Custom("squad".into()),
+            initial_participants: participants,
+            activation: ActivationMode::Quorum { threshold: 0.5 },
+            created_by: "test".to_string(),
+            created_at: Utc::now(),
+        }));
+        assert_round_trip(&created);
+        assert_round_trip(&CommandResponse::accepted(vec![quality_updated, created]));
+        assert_round_trip(&CommandResponse::rejected("read only"));
+
+        let set_property: RelationshipCommand = EdgeCommand::SetEdgeProperty(SetEdgeProperty {
+            identity: MessageIdentity::new_root(),
+            edge_id: RelationshipId::new(),
+            key: "weights".to_string(),
+            value: serde_json::json!({ "big": u64::MAX, "negative": -3, "ratio": 0.25, "none": null, "list": [true, "x"] }),
+            expected_version: Some(2),
+            set_by: "test".to_string(),
+        })
+        .into();
+        assert_round_trip(&set_property);
+
+        let enact = RelationshipCommand::EnactPolicy(EnactPolicy {
+            identity: MessageIdentity::new_root(),
+            policy: RelationshipPolicy::new(
+                EntityRef::new(EntityType::Policy, Uuid::now_v7()),
+                "No dealings with rivals",
+                PolicyRule::Forbid {
+                    category: None,
+                    side: EndpointSide::Target,
+                    entities: vec![EntityRef::organization(Uuid::now_v7())],
+                },
+            ),
+            enacted_by: "compliance".to_string(),
+        });
+        assert_round_trip(&enact);
+    }
+
+    #[test]
+    fn test_malformed_messages_rejected() {
+        assert!(matches!(
+            decode::<RelationshipEvent>(b"\xff\xff"),
+            Err(RelationshipError::SerializationError(_))
+        ));
+
+        // No event at all
+        let empty = proto::RelationshipEvent::default().encode_to_vec();
+        let error = decode::<RelationshipEvent>(&empty).unwrap_err();
+        assert!(error.to_string().contains("missing"));
+
+        // A bad id and an unknown enum value name the field
+        let command = RelationshipCommand::EnactPolicy(EnactPolicy {
+            identity: MessageIdentity::new_root(),
+            policy: RelationshipPolicy::new(
+                EntityRef::new(EntityType::Policy, Uuid::now_v7()),
+                "Quiet",
+                PolicyRule::Forbid {
+                    category: Some(RelationshipCategory::Friendship),
+                    side: EndpointSide::Both,
+                    entities: vec![],
+                },
+            ),
+            enacted_by: "compliance".to_string(),
+        });
+        let mut message = proto::RelationshipCommand::from(&command);
+        let Some(proto::relationship_command::Command::EnactPolicy(enact)) = message.command.as_mut() else {
+            unreachable!()
+        };
+        enact.policy.as_mut().unwrap().policy.as_mut().unwrap().entity_id = "nope".to_string();
+        let error = RelationshipCommand::try_from(message.clone()).unwrap_err();
+        assert!(error.to_string().contains("entity_id"));
+
+        let Some(proto::relationship_command::Command::EnactPolicy(enact)) = message.command.as_mut() else {
+            unreachable!()
+        };
+        let policy = enact.policy.as_mut().unwrap();
+        policy.policy = Some(EntityRef::new(EntityType::Policy, Uuid::now_v7()).to_proto());
+        let Some(proto::policy_rule::Rule::Forbid(forbid)) = policy.rule.as_mut().and_then(|r| r.rule.as_mut()) else {
+            unreachable!()
+        };
+        forbid.side = 42;
+        let error = RelationshipCommand::try_from(message).unwrap_err();
+        assert!(error.to_string().contains("side"));
+    }
+}
@@ -0,0 +1,84 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Shutdown Signal
+//!
+//! Tells serving loops to stop taking new messages. A loop reading through
+//! `Shutdown::next` finishes the message in hand, so its reply or ack goes
+//! out, and then ends:
+//!
+//! ```rust,ignore
+//! while let Some(request) = shutdown.next(&mut requests).await {
+//!     handle(request).await;
+//! }
+//! ```
+
+use futures::{Stream, StreamExt};
+use std::sync::Arc;
+use tokio::sync::watch;
+
+/// Signal shared by everything that stops at shutdown
+#[derive(Debug, Clone)]
+pub struct Shutdown {
+    sender: Arc<watch::Sender<bool>>,
+}
+
+impl Default for Shutdown {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Shutdown {
+    /// Create a signal not yet triggered
+    pub fn new() -> Self {
+        Self {
+            sender: Arc::new(watch::channel(false).0),
+        }
+    }
+
+    /// Tell every holder to stop
+    pub fn trigger(&self) {
+        self.sender.send_replace(true);
+    }
+
+    pub fn is_triggered(&self) -> bool {
+        *self.sender.borrow()
+    }
+
+    /// Wait until triggered
+    pub async fn triggered(&self) {
+        let _ = self
+            .sender
+            .subscribe()
+            .wait_for(|triggered| *triggered)
+            .await;
+    }
+
+    /// The next item of a stream, or `None` once triggered
+    pub async fn next<S: Stream + Unpin>(&self, stream: &mut S) -> Option<S::Item> {
+        tokio::select! {
+            biased;
+            _ = self.triggered() => None,
+            item = stream.next() => item,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_next_stops_once_triggered() {
+        let shutdown = Shutdown::new();
+        let mut items = futures::stream::iter([1, 2]).chain(futures::stream::pending());
+
+        assert_eq!(shutdown.next(&mut items).await, Some(1));
+        shutdown.clone().trigger();
+        assert!(shutdown.is_triggered());
+        assert_eq!(shutdown.next(&mut items).await, None);
+        shutdown.triggered().await;
+    }
+}
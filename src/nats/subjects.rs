@@ -0,0 +1,171 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! NATS Subject Patterns
+//!
+//! Builders for every subject the relationship domain publishes or listens
+//! on, plus NATS wildcard matching (`*` = one token, `>` = the rest).
+
+use crate::commands::RelationshipCommand;
+use crate::events::RelationshipEvent;
+use crate::queries::{RelationshipQuery, SystemQuery};
+use crate::value_objects::{EntityType, RelationshipCategory};
+use uuid::Uuid;
+
+/// Subject builders for the relationship domain
+pub struct RelationshipSubjects;
+
+impl RelationshipSubjects {
+    /// Root token of every relationship subject
+    pub const DOMAIN: &'static str = "relationship";
+
+    /// `relationship.events.{event_type}`
+    pub fn event(event: &RelationshipEvent) -> String {
+        format!("{}.events.{}", Self::DOMAIN, event.event_type())
+    }
+
+    /// `relationship.commands.{command_type}`
+    pub fn command(command: &RelationshipCommand) -> String {
+        format!("{}.commands.{}", Self::DOMAIN, command.command_type())
+    }
+
+    /// `relationship.queries.{query_type}`
+    pub fn query(query: &RelationshipQuery) -> String {
+        format!("{}.queries.{}", Self::DOMAIN, query.query_type())
+    }
+
+    /// `relationship.queries.system.{name}`
+    pub fn system_query(query: &SystemQuery) -> String {
+        format!("{}.queries.{}", Self::DOMAIN, query.query_type())
+    }
+
+    /// `relationship.edge.{source_type}.{target_type}.{action}`
+    pub fn edge(source: &EntityType, target: &EntityType, action: &str) -> String {
+        format!(
+            "{}.edge.{}.{}.{}",
+            Self::DOMAIN,
+            source.nats_subject_prefix(),
+            target.nats_subject_prefix(),
+            action
+        )
+    }
+
+    /// `relationship.hyperedge.{category}.{action}`
+    pub fn hyperedge(category: &RelationshipCategory, action: &str) -> String {
+        format!("{}.hyperedge.{}.{}", Self::DOMAIN, token(&category.display_name()), action)
+    }
+
+    /// Every relationship event
+    pub fn all_events() -> String {
+        format!("{}.events.>", Self::DOMAIN)
+    }
+
+    /// Every relationship command
+    pub fn all_commands() -> String {
+        format!("{}.commands.>", Self::DOMAIN)
+    }
+
+    /// Every relationship query
+    pub fn all_queries() -> String {
+        format!("{}.queries.>", Self::DOMAIN)
+    }
+
+    /// Every system query
+    pub fn all_system_queries() -> String {
+        format!("{}.queries.system.>", Self::DOMAIN)
+    }
+
+    /// `relationship.schema.{kind}.{message_type}`, kind being `events` or
+    /// `commands`
+    pub fn schema(kind: &str, message_type: &str) -> String {
+        format!("{}.schema.{}.{}", Self::DOMAIN, kind, message_type)
+    }
+
+    /// `relationship.schema.catalog`: every schema in one bundle
+    pub fn schema_catalog() -> String {
+        format!("{}.schema.catalog", Self::DOMAIN)
+    }
+
+    /// Every schema request
+    pub fn all_schemas() -> String {
+        format!("{}.schema.>", Self::DOMAIN)
+    }
+
+    /// `relationship.health.{live|ready}`
+    pub fn health(probe: &str) -> String {
+        format!("{}.health.{}", Self::DOMAIN, probe)
+    }
+
+    /// Every health probe
+    pub fn all_health() -> String {
+        format!("{}.health.>", Self::DOMAIN)
+    }
+
+    /// `relationship.webhooks.commands.{command_type}`
+    pub fn webhook_command(command_type: &str) -> String {
+        format!("{}.webhooks.commands.{}", Self::DOMAIN, command_type)
+    }
+
+    /// Every webhook command
+    pub fn all_webhook_commands() -> String {
+        format!("{}.webhooks.commands.*", Self::DOMAIN)
+    }
+
+    /// `relationship.webhooks.dead_letters.{webhook_id}`
+    pub fn webhook_dead_letter(webhook_id: &Uuid) -> String {
+        format!("{}.webhooks.dead_letters.{}", Self::DOMAIN, webhook_id)
+    }
+
+    /// Every webhook dead letter
+    pub fn all_webhook_dead_letters() -> String {
+        format!("{}.webhooks.dead_letters.*", Self::DOMAIN)
+    }
+
+    /// Check if a subject matches a NATS subscription pattern
+    pub fn matches(pattern: &str, subject: &str) -> bool {
+        let mut pattern_tokens = pattern.split('.');
+        let mut subject_tokens = subject.split('.');
+        loop {
+            match (pattern_tokens.next(), subject_tokens.next()) {
+                (Some(">"), Some(_)) => return true,
+                (Some("*"), Some(_)) => continue,
+                (Some(p), Some(s)) if p == s => continue,
+                (None, None) => return true,
+                _ => return false,
+            }
+        }
+    }
+}
+
+/// Make a single subject token from a display name
+fn token(name: &str) -> String {
+    name.to_lowercase().replace([' ', '.', '*', '>'], "_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subject_patterns() {
+        assert_eq!(
+            RelationshipSubjects::edge(&EntityType::Person, &EntityType::Organization, "created"),
+            "relationship.edge.person.organization.created"
+        );
+        assert_eq!(
+            RelationshipSubjects::hyperedge(&RelationshipCategory::ProfessionalContact, "created"),
+            "relationship.hyperedge.professional_contact.created"
+        );
+    }
+
+    #[test]
+    fn test_wildcard_matching() {
+        assert!(RelationshipSubjects::matches("relationship.events.>", "relationship.events.edge_created"));
+        assert!(RelationshipSubjects::matches("relationship.*.edge_created", "relationship.events.edge_created"));
+        assert!(!RelationshipSubjects::matches("relationship.events.>", "relationship.events"));
+        assert!(!RelationshipSubjects::matches("relationship.events.*", "relationship.events.a.b"));
+        assert!(RelationshipSubjects::matches("a.b", "a.b"));
+        assert!(!RelationshipSubjects::matches("a.b", "a.b.c"));
+    }
+}
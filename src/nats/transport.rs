@@ -0,0 +1,214 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Message Transport Abstraction
+//!
+//! The relationship domain talks to the message bus only through the
+//! `Transport` trait: publish, request/reply, subscribe, and publish to a
+//! stream, confirmed once stored. `NatsTransport`
+//! implements it over an async-nats client; `MockTransport` implements it
+//! in-process for tests.
+
+use super::wire::MessageHeaders;
+use crate::{RelationshipError, RelationshipResult};
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::{Stream, StreamExt};
+use std::pin::Pin;
+
+/// A message received from a transport
+#[derive(Debug, Clone)]
+pub struct TransportMessage {
+    /// Subject the message was published on
+    pub subject: String,
+    /// Message body
+    pub payload: Bytes,
+    /// Reply subject for request/reply
+    pub reply: Option<String>,
+    /// Message headers; empty when none were sent
+    pub headers: MessageHeaders,
+}
+
+/// Stream of messages from a subscription
+pub type MessageStream = Pin<Box<dyn Stream<Item = TransportMessage> + Send>>;
+
+/// Publish/request/subscribe operations used by the relationship domain
+#[async_trait]
+pub trait Transport: Send + Sync {
+    /// Publish a message
+    async fn publish(&self, subject: &str, payload: Bytes) -> RelationshipResult<()>;
+
+    /// Send a request and wait for a single reply
+    async fn request(&self, subject: &str, payload: Bytes) -> RelationshipResult<Bytes>;
+
+    /// Subscribe to a subject pattern
+    async fn subscribe(&self, subject: &str) -> RelationshipResult<MessageStream>;
+
+    /// Publish a message with headers
+    ///
+    /// Transports without header support drop the headers.
+    async fn publish_with_headers(
+        &self,
+        subject: &str,
+        headers: MessageHeaders,
+        payload: Bytes,
+    ) -> RelationshipResult<()> {
+        let _ = headers;
+        self.publish(subject, payload).await
+    }
+
+    /// Send a request with headers and wait for a single reply
+    ///
+    /// Transports without header support drop the headers.
+    async fn request_with_headers(
+        &self,
+        subject: &str,
+        headers: MessageHeaders,
+        payload: Bytes,
+    ) -> RelationshipResult<Bytes> {
+        let _ = headers;
+        self.request(subject, payload).await
+    }
+
+    /// Publish a message to the stream capturing its subject, returning
+    /// once the stream has stored it
+    ///
+    /// Transports without streams publish it as `publish_with_headers`
+    /// does.
+    async fn publish_persisted(
+        &self,
+        subject: &str,
+        headers: MessageHeaders,
+        payload: Bytes,
+    ) -> RelationshipResult<()> {
+        self.publish_with_headers(subject, headers, payload).await
+    }
+
+    /// Whether the bus can currently be reached
+    ///
+    /// Transports that cannot tell report `true`.
+    fn is_connected(&self) -> bool {
+        true
+    }
+}
+
+/// Transport over a NATS connection
+#[derive(Debug, Clone)]
+pub struct NatsTransport {
+    client: async_nats::Client,
+}
+
+impl NatsTransport {
+    /// Wrap an existing NATS client
+    pub fn new(client: async_nats::Client) -> Self {
+        Self { client }
+    }
+
+    /// Connect to a NATS server
+    pub async fn connect(url: &str) -> RelationshipResult<Self> {
+        let client = async_nats::connect(url).await.map_err(transport_error)?;
+        Ok(Self::new(client))
+    }
+
+    /// The underlying NATS client
+    pub fn client(&self) -> &async_nats::Client {
+        &self.client
+    }
+}
+
+#[async_trait]
+impl Transport for NatsTransport {
+    async fn publish(&self, subject: &str, payload: Bytes) -> RelationshipResult<()> {
+        self.client
+            .publish(subject.to_string(), payload)
+            .await
+            .map_err(transport_error)
+    }
+
+    async fn request(&self, subject: &str, payload: Bytes) -> RelationshipResult<Bytes> {
+        self.client
+            .request(subject.to_string(), payload)
+            .await
+            .map(|message| message.payload)
+            .map_err(transport_error)
+    }
+
+    async fn subscribe(&self, subject: &str) -> RelationshipResult<MessageStream> {
+        let subscriber = self
+            .client
+            .subscribe(subject.to_string())
+            .await
+            .map_err(transport_error)?;
+        Ok(Box::pin(subscriber.map(|message| TransportMessage {
+            subject: message.subject.to_string(),
+            payload: message.payload,
+            reply: message.reply.map(|r| r.to_string()),
+            headers: message.headers.as_ref().map(from_header_map).unwrap_or_default(),
+        })))
+    }
+
+    async fn publish_with_headers(
+        &self,
+        subject: &str,
+        headers: MessageHeaders,
+        payload: Bytes,
+    ) -> RelationshipResult<()> {
+        self.client
+            .publish_with_headers(subject.to_string(), to_header_map(&headers), payload)
+            .await
+            .map_err(transport_error)
+    }
+
+    async fn request_with_headers(
+        &self,
+        subject: &str,
+        headers: MessageHeaders,
+        payload: Bytes,
+    ) -> RelationshipResult<Bytes> {
+        self.client
+            .request_with_headers(subject.to_string(), to_header_map(&headers), payload)
+            .await
+            .map(|message| message.payload)
+            .map_err(transport_error)
+    }
+
+    /// Published through JetStream, waiting for its PubAck
+    async fn publish_persisted(
+        &self,
+        subject: &str,
+        headers: MessageHeaders,
+        payload: Bytes,
+    ) -> RelationshipResult<()> {
+        async_nats::jetstream::new(self.client.clone())
+            .publish_with_headers(subject.to_string(), to_header_map(&headers), payload)
+            .await
+            .map_err(transport_error)?
+            .await
+            .map_err(transport_error)?;
+        Ok(())
+    }
+
+    fn is_connected(&self) -> bool {
+        self.client.connection_state() == async_nats::connection::State::Connected
+    }
+}
+
+fn to_header_map(headers: &MessageHeaders) -> async_nats::HeaderMap {
+    let mut map = async_nats::HeaderMap::new();
+    for (name, value) in headers {
+        map.insert(name.as_str(), value.as_str());
+    }
+    map
+}
+
+/// First value of each header
+pub(super) fn from_header_map(map: &async_nats::HeaderMap) -> MessageHeaders {
+    map.iter()
+        .filter_map(|(name, values)| Some((name.to_string(), values.first()?.as_str().to_string())))
+        .collect()
+}
+
+fn transport_error(error: impl std::fmt::Display) -> RelationshipError {
+    RelationshipError::TransportError(error.to_string())
+}
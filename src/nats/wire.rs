@@ -0,0 +1,139 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Wire Formats
+//!
+//! Payloads are JSON unless the sender says otherwise. The format travels
+//! in the `Content-Type` header, so receivers decode each message as it
+//! was sent and mixed deployments keep working:
+//!
+//! ```text
+//! Content-Type: application/json          JSON (also when the header is absent)
+//! Content-Type: application/x-protobuf    proto/relationship.proto (`protobuf` feature)
+//! ```
+//!
+//! Events, commands and command responses travel in either format; queries
+//! and their results are always JSON. Replies to requests use the format of
+//! the request.
+
+use crate::{RelationshipError, RelationshipResult};
+use bytes::Bytes;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Header naming the payload format
+pub const CONTENT_TYPE_HEADER: &str = "Content-Type";
+
+/// Message headers, by name
+pub type MessageHeaders = BTreeMap<String, String>;
+
+/// A message every wire format carries: an event, command or command
+/// response
+#[cfg(feature = "protobuf")]
+pub trait WireMessage: Serialize + DeserializeOwned + super::protobuf::ProtobufMessage {}
+
+#[cfg(feature = "protobuf")]
+impl<M: Serialize + DeserializeOwned + super::protobuf::ProtobufMessage> WireMessage for M {}
+
+/// A message every wire format carries: an event, command or command
+/// response
+#[cfg(not(feature = "protobuf"))]
+pub trait WireMessage: Serialize + DeserializeOwned {}
+
+#[cfg(not(feature = "protobuf"))]
+impl<M: Serialize + DeserializeOwned> WireMessage for M {}
+
+/// Payload encoding
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum WireFormat {
+    /// `application/json`
+    #[default]
+    Json,
+    /// `application/x-protobuf`
+    #[cfg(feature = "protobuf")]
+    Protobuf,
+}
+
+impl WireFormat {
+    /// MIME type sent in `Content-Type`
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            WireFormat::Json => "application/json",
+            #[cfg(feature = "protobuf")]
+            WireFormat::Protobuf => "application/x-protobuf",
+        }
+    }
+
+    /// Format of a MIME type, ignoring parameters such as `charset`
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        match content_type.split(';').next().unwrap_or_default().trim() {
+            "application/json" => Some(WireFormat::Json),
+            #[cfg(feature = "protobuf")]
+            "application/x-protobuf" | "application/protobuf" => Some(WireFormat::Protobuf),
+            _ => None,
+        }
+    }
+
+    /// Format of a received message; JSON without a `Content-Type`
+    pub fn from_headers(headers: &MessageHeaders) -> RelationshipResult<Self> {
+        let content_type = headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(CONTENT_TYPE_HEADER))
+            .map(|(_, value)| value.as_str());
+        match content_type {
+            None => Ok(WireFormat::Json),
+            Some(content_type) => Self::from_content_type(content_type).ok_or_else(|| {
+                RelationshipError::SerializationError(format!("unsupported content type {}", content_type))
+            }),
+        }
+    }
+
+    /// Headers announcing the format
+    pub fn headers(&self) -> MessageHeaders {
+        MessageHeaders::from([(CONTENT_TYPE_HEADER.to_string(), self.content_type().to_string())])
+    }
+
+    /// Encode a message payload
+    pub fn encode<M: WireMessage>(&self, message: &M) -> RelationshipResult<Bytes> {
+        match self {
+            WireFormat::Json => super::bus::encode(message),
+            #[cfg(feature = "protobuf")]
+            WireFormat::Protobuf => super::protobuf::encode(message),
+        }
+    }
+
+    /// Decode a message payload
+    pub fn decode<M: WireMessage>(&self, payload: &[u8]) -> RelationshipResult<M> {
+        match self {
+            WireFormat::Json => super::bus::decode(payload),
+            #[cfg(feature = "protobuf")]
+            WireFormat::Protobuf => super::protobuf::decode(payload),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_negotiation() {
+        assert_eq!(WireFormat::from_headers(&MessageHeaders::new()).unwrap(), WireFormat::Json);
+        let headers = MessageHeaders::from([("content-type".to_string(), "application/json; charset=utf-8".to_string())]);
+        assert_eq!(WireFormat::from_headers(&headers).unwrap(), WireFormat::Json);
+        let headers = MessageHeaders::from([(CONTENT_TYPE_HEADER.to_string(), "text/plain".to_string())]);
+        assert!(WireFormat::from_headers(&headers).is_err());
+
+        #[cfg(feature = "protobuf")]
+        {
+            use crate::nats::bus::CommandResponse;
+            let format = WireFormat::from_headers(&WireFormat::Protobuf.headers()).unwrap();
+            assert_eq!(format, WireFormat::Protobuf);
+            let payload = format.encode(&CommandResponse::rejected("read only")).unwrap();
+            let response: CommandResponse = format.decode(&payload).unwrap();
+            assert_eq!(response.error.as_deref(), Some("read only"));
+        }
+    }
+}
@@ -0,0 +1,676 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Relationship Worker
+//!
+//! The service side of the NATS API: answers the commands and queries
+//! `RelationshipBus` sends, and turns upstream domain events into commands.
+//!
+//! ```text
+//! relationship.commands.>        --> EntityVerifier::decide --> apply --> outbox --> relationship.events.*
+//! relationship.queries.>         --> RelationshipQuery::execute
+//! relationship.queries.system.>  --> SystemQuery::execute (MetricsCollector)
+//! upstream events                --> UpstreamLedger --> CrossDomainRegistry --> commands, as above
+//!                                --> UpstreamLedger, display cache (once the commands are stored)
+//! expiry sweep                   --> ExpireEdge / ExpireConsent commands, as above
+//! ```
+//!
+//! Commands are decided through an `EntityVerifier`, which by default skips
+//! verification; `with_verifier` has `CreateEdge` endpoints checked with
+//! their domains over the bus's transport first.
+//!
+//! Upstream events the `UpstreamLedger` has already seen, or holds
+//! something newer about, are dropped, so redeliveries are not reacted to
+//! twice. An event whose commands could not be stored is not recorded, so
+//! its redelivery is reacted to.
+//!
+//! The worker owns the write side of a `RelationshipReadModel`: commands
+//! are decided one at a time against its space and their events applied to
+//! a copy of it, which replaces it once the events stream has stored the
+//! first event, so readers of the read model see them without following
+//! the stream. Events of a stored command that fail to publish stay in the
+//! outbox and go out before the next command is decided, or with
+//! `flush_outbox`.
+//!
+//! Commands are decided against the worker's own space, so one worker
+//! answers them per events stream; the service holds a
+//! `jetstream::InstanceLease` while it serves.
+//!
+//! Replies use the wire format the request arrived in; queries are
+//! answered in JSON only.
+//!
+//! Once its `Shutdown` is triggered the worker stops taking new messages:
+//! each serving loop finishes the message in hand and returns.
+
+use super::bus::{decode, encode, CommandResponse, RelationshipBus};
+use super::shutdown::Shutdown;
+use super::subjects::RelationshipSubjects;
+use super::transport::{Transport, TransportMessage};
+use super::wire::WireFormat;
+use crate::commands::{EdgeCommand, ExpireConsent, ExpireEdge, RelationshipCommand};
+use crate::cross_domain::{CrossDomainEvent, CrossDomainRegistry, UpstreamLedger};
+use crate::events::RelationshipEvent;
+use crate::infrastructure::MetricsCollector;
+use crate::projections::RelationshipReadModel;
+use crate::queries::{RelationshipQuery, SystemQuery};
+use crate::services::EntityVerifier;
+use crate::{RelationshipError, RelationshipResult};
+use bytes::Bytes;
+use chrono::Utc;
+use cim_domain::MessageIdentity;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{Mutex, RwLock};
+
+/// Answers relationship commands and queries, and reacts to upstream events
+#[derive(Debug)]
+pub struct RelationshipWorker<T: Transport> {
+    bus: RelationshipBus<T>,
+    read_model: RelationshipReadModel,
+    registry: CrossDomainRegistry,
+    verifier: EntityVerifier,
+    metrics: Arc<RwLock<MetricsCollector>>,
+    outbox: Mutex<Vec<RelationshipEvent>>,
+    ledger: Mutex<UpstreamLedger>,
+    shutdown: Shutdown,
+}
+
+impl<T: Transport> RelationshipWorker<T> {
+    /// Worker publishing over `bus`, writing to `read_model`, with the
+    /// standard cross-domain handlers
+    pub fn new(bus: RelationshipBus<T>, read_model: RelationshipReadModel) -> Self {
+        Self {
+            bus,
+            read_model,
+            registry: CrossDomainRegistry::standard(),
+            verifier: EntityVerifier::new(),
+            metrics: Arc::new(RwLock::new(MetricsCollector::new())),
+            outbox: Mutex::new(Vec::new()),
+            ledger: Mutex::new(UpstreamLedger::new()),
+            shutdown: Shutdown::new(),
+        }
+    }
+
+    /// React to upstream events with another registry
+    pub fn with_registry(mut self, registry: CrossDomainRegistry) -> Self {
+        self.registry = registry;
+        self
+    }
+
+    /// Verify the endpoints of new edges with `verifier`
+    pub fn with_verifier(mut self, verifier: EntityVerifier) -> Self {
+        self.verifier = verifier;
+        self
+    }
+
+    /// Share an existing metrics collector
+    pub fn with_metrics(mut self, metrics: Arc<RwLock<MetricsCollector>>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    /// Stop serving when `shutdown` is triggered
+    pub fn with_shutdown(mut self, shutdown: Shutdown) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+
+    /// The signal that stops the worker
+    pub fn shutdown(&self) -> &Shutdown {
+        &self.shutdown
+    }
+
+    /// The read model the worker writes
+    pub fn read_model(&self) -> &RelationshipReadModel {
+        &self.read_model
+    }
+
+    /// Upstream subject patterns the worker reacts to
+    pub fn upstream_subjects(&self) -> Vec<String> {
+        self.registry.subjects()
+    }
+
+    /// The metrics collector answering system queries
+    pub fn metrics(&self) -> &Arc<RwLock<MetricsCollector>> {
+        &self.metrics
+    }
+
+    // ---- Commands ----
+
+    /// Decide a command, apply its events and publish them
+    ///
+    /// The events are applied to a copy of the space, which replaces it
+    /// once the events stream has stored the first of them; the command is
+    /// accepted from then on. Refused commands, and commands none of whose
+    /// events could be stored, leave the space untouched and fail. Events
+    /// of an earlier command left in the outbox are published first, so
+    /// while they cannot be every command fails; events after the first
+    /// that fail to publish are kept, in order, for `flush_outbox`.
+    pub async fn execute(
+        &self,
+        command: RelationshipCommand,
+    ) -> RelationshipResult<Vec<RelationshipEvent>> {
+        let mut outbox = self.outbox.lock().await;
+        let (events, decided) = {
+            let space = self.read_model.space().read().await;
+            let events = self.verifier.decide(self.bus.transport(), &space, command).await?;
+            let mut decided = space.clone();
+            for event in &events {
+                decided.apply_event(event)?;
+            }
+            (events, decided)
+        };
+        outbox.extend(events.iter().cloned());
+        let published = self.publish(&mut outbox).await;
+        let unpublished = outbox.len();
+        if let Err(e) = published {
+            if unpublished >= events.len() && !events.is_empty() {
+                outbox.truncate(unpublished - events.len());
+                return Err(e);
+            }
+            tracing::warn!("{} events left in the outbox: {}", unpublished, e);
+        }
+        *self.read_model.space().write().await = decided;
+        Ok(events)
+    }
+
+    /// Publish events left over by earlier publishing failures, returning
+    /// how many went out
+    pub async fn flush_outbox(&self) -> RelationshipResult<usize> {
+        self.publish(&mut *self.outbox.lock().await).await
+    }
+
+    /// Events waiting to be published
+    pub async fn outbox_len(&self) -> usize {
+        self.outbox.lock().await.len()
+    }
+
+    /// Publish the outbox in order, removing each event once it is out
+    async fn publish(&self, outbox: &mut Vec<RelationshipEvent>) -> RelationshipResult<usize> {
+        let mut published = 0;
+        let mut result = Ok(());
+        for event in outbox.iter() {
+            result = self.bus.publish_event(event).await;
+            if result.is_err() {
+                break;
+            }
+            published += 1;
+        }
+        outbox.drain(..published);
+        result.map(|()| published)
+    }
+
+    /// Reply to a command message with its `CommandResponse`
+    pub async fn handle_command(&self, message: &TransportMessage) -> RelationshipResult<Bytes> {
+        let format = WireFormat::from_headers(&message.headers)?;
+        let response = match format.decode::<RelationshipCommand>(&message.payload) {
+            Ok(command) => match self.execute(command).await {
+                Ok(events) => CommandResponse::accepted(events),
+                Err(e) => CommandResponse::rejected(e.to_string()),
+            },
+            Err(e) => CommandResponse::rejected(e.to_string()),
+        };
+        format.encode(&response)
+    }
+
+    // ---- Queries ----
+
+    /// Reply to a query message, system queries included
+    ///
+    /// Queries are JSON; others go unanswered.
+    pub async fn handle_query(&self, message: &TransportMessage) -> RelationshipResult<Bytes> {
+        let format = WireFormat::from_headers(&message.headers)?;
+        if format != WireFormat::Json {
+            return Err(RelationshipError::SerializationError(format!(
+                "queries are JSON, not {}",
+                format.content_type()
+            )));
+        }
+        let space = self.read_model.space().read().await;
+        if RelationshipSubjects::matches(
+            &RelationshipSubjects::all_system_queries(),
+            &message.subject,
+        ) {
+            let query: SystemQuery = decode(&message.payload)?;
+            encode(&query.execute(&space, &*self.metrics.read().await))
+        } else {
+            let query: RelationshipQuery = decode(&message.payload)?;
+            encode(&query.execute(&space))
+        }
+    }
+
+    // ---- Upstream Events ----
+
+    /// React to an upstream message, returning how many commands it led to
+    /// were accepted
+    ///
+    /// Stale and redelivered events lead to nothing. The event is recorded
+    /// in the ledger and the display cache only once every command it led
+    /// to has been stored or refused: refusals are logged, while a
+    /// transport failure fails the message unrecorded, for redelivery,
+    /// which decides its commands again. Messages that do not decode are
+    /// an error too.
+    pub async fn handle_upstream(
+        &self,
+        subject: &str,
+        payload: &[u8],
+    ) -> RelationshipResult<usize> {
+        let event = CrossDomainEvent::from_message(subject, payload).ok().flatten();
+        // Held throughout, so a redelivery waits for the reactions in hand
+        let mut ledger = self.ledger.lock().await;
+        if let Some(event) = &event {
+            if !ledger.is_new(event) {
+                tracing::debug!("{} from {} already seen", subject, event.entity());
+                return Ok(0);
+            }
+        }
+        let commands = {
+            let space = self.read_model.space().read().await;
+            self.registry.handle_message(&space, subject, payload)?
+        };
+
+        let mut accepted = 0;
+        for command in commands {
+            let command_type = command.command_type();
+            match self.execute(command).await {
+                Ok(_) => accepted += 1,
+                Err(e @ RelationshipError::TransportError(_)) => return Err(e),
+                Err(e) => {
+                    tracing::warn!("{} in reaction to {} refused: {}", command_type, subject, e)
+                }
+            }
+        }
+        if let Some(event) = &event {
+            ledger.record(event);
+            self.read_model.apply_cross_domain(event).await;
+        }
+        Ok(accepted)
+    }
+
+    // ---- Expiry ----
+
+    /// End live edges whose validity has ended and reject edges whose
+    /// consent request has expired, returning how many commands were
+    /// accepted
+    ///
+    /// Each goes through `execute` like any other command; refusals are
+    /// logged.
+    pub async fn sweep_expired(&self) -> usize {
+        let now = Utc::now();
+        let commands: Vec<RelationshipCommand> = {
+            let space = self.read_model.space().read().await;
+            let expired = space.expired_edges(now).into_iter().map(|edge_id| {
+                EdgeCommand::ExpireEdge(ExpireEdge {
+                    identity: MessageIdentity::new_root(),
+                    edge_id,
+                    as_of: now,
+                })
+            });
+            let unconsented = space.expired_consents(now).into_iter().map(|edge_id| {
+                EdgeCommand::ExpireConsent(ExpireConsent {
+                    identity: MessageIdentity::new_root(),
+                    edge_id,
+                })
+            });
+            expired.chain(unconsented).map(RelationshipCommand::from).collect()
+        };
+        let mut accepted = 0;
+        for command in commands {
+            let command_type = command.command_type();
+            match self.execute(command).await {
+                Ok(_) => accepted += 1,
+                Err(e) => tracing::warn!("{} in expiry sweep refused: {}", command_type, e),
+            }
+        }
+        accepted
+    }
+
+    // ---- Serving ----
+
+    /// Answer commands and queries until shutdown, the subscriptions end,
+    /// or one fails
+    pub async fn serve(&self) -> RelationshipResult<()> {
+        tokio::try_join!(self.serve_commands(), self.serve_queries())?;
+        Ok(())
+    }
+
+    /// Answer commands on `relationship.commands.>`, one at a time
+    ///
+    /// Run one worker per events stream: a second would decide commands
+    /// without the events of the first.
+    pub async fn serve_commands(&self) -> RelationshipResult<()> {
+        let mut requests = self
+            .bus
+            .transport()
+            .subscribe(&RelationshipSubjects::all_commands())
+            .await?;
+        while let Some(request) = self.shutdown.next(&mut requests).await {
+            let reply = self.handle_command(&request).await;
+            self.reply(&request, reply).await;
+        }
+        Ok(())
+    }
+
+    /// Run `sweep_expired` every `period` until shutdown
+    pub async fn serve_expiry(&self, period: Duration) -> RelationshipResult<()> {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            tokio::select! {
+                biased;
+                _ = self.shutdown.triggered() => return Ok(()),
+                _ = interval.tick() => {
+                    self.sweep_expired().await;
+                }
+            }
+        }
+    }
+
+    /// Answer queries on `relationship.queries.>`
+    pub async fn serve_queries(&self) -> RelationshipResult<()> {
+        let mut requests = self
+            .bus
+            .transport()
+            .subscribe(&RelationshipSubjects::all_queries())
+            .await?;
+        while let Some(request) = self.shutdown.next(&mut requests).await {
+            let reply = self.handle_query(&request).await;
+            self.reply(&request, reply).await;
+        }
+        Ok(())
+    }
+
+    /// React to upstream events over plain subscriptions
+    ///
+    /// Messages published while the worker is down are missed; durable
+    /// deployments consume through `jetstream::follow_upstream` instead.
+    pub async fn serve_upstream(&self) -> RelationshipResult<()> {
+        let mut subscriptions = Vec::new();
+        for subject in self.upstream_subjects() {
+            subscriptions.push(self.bus.transport().subscribe(&subject).await?);
+        }
+        let mut messages = futures::stream::select_all(subscriptions);
+        while let Some(message) = self.shutdown.next(&mut messages).await {
+            if let Err(e) = self
+                .handle_upstream(&message.subject, &message.payload)
+                .await
+            {
+                tracing::warn!("upstream message on {} skipped: {}", message.subject, e);
+            }
+        }
+        Ok(())
+    }
+
+    async fn reply(&self, request: &TransportMessage, reply: RelationshipResult<Bytes>) {
+        let Some(inbox) = &request.reply else {
+            return;
+        };
+        let sent = match (reply, WireFormat::from_headers(&request.headers)) {
+            (Ok(payload), Ok(format)) => {
+                self.bus
+                    .transport()
+                    .publish_with_headers(inbox, format.headers(), payload)
+                    .await
+            }
+            (Ok(_), Err(e)) | (Err(e), _) => {
+                tracing::debug!("request on {} unanswered: {}", request.subject, e);
+                return;
+            }
+        };
+        if let Err(e) = sent {
+            tracing::warn!("failed to reply on {}: {}", request.subject, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregates::RelationshipSpace;
+    use crate::commands::{CreateEdge, RequestConsent};
+    use crate::cross_domain::contract::StubEmitter;
+    use crate::nats::MockTransport;
+    use crate::queries::{MetricsQuery, SystemResult};
+    use crate::value_objects::{EntityRef, RelationshipCategory, RelationshipId};
+    use cim_domain::MessageIdentity;
+    use cim_domain_spaces::TopologicalSpaceId;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_worker_answers_bus() {
+        let transport = MockTransport::new();
+        let bus = RelationshipBus::new(transport.clone());
+        let read_model = RelationshipReadModel::new(Arc::new(RwLock::new(RelationshipSpace::new(
+            "Worker",
+            TopologicalSpaceId::new(),
+        ))));
+        let worker = Arc::new(RelationshipWorker::new(bus.clone(), read_model.clone()));
+        let serving = tokio::spawn({
+            let worker = worker.clone();
+            async move { tokio::try_join!(worker.serve(), worker.serve_upstream()) }
+        });
+        for _ in 0..10 {
+            tokio::task::yield_now().await;
+        }
+
+        let person = Uuid::now_v7();
+        let edge_id = RelationshipId::new();
+        let create = RelationshipCommand::Edge(EdgeCommand::CreateEdge(CreateEdge {
+            identity: MessageIdentity::new_root(),
+            edge_id,
+            source: EntityRef::person(person),
+            target: EntityRef::organization(Uuid::now_v7()),
+            category: RelationshipCategory::Employment,
+            name: "Employment".to_string(),
+            quality: None,
+            created_by: "test".to_string(),
+        }));
+        let response = bus.send_command(&create).await.unwrap();
+        assert!(response.accepted, "{:?}", response.error);
+        assert!(read_model.space().read().await.get_edge(&edge_id).is_some());
+        assert_eq!(transport.events().len(), response.events.len());
+        assert!(!bus.send_command(&create).await.unwrap().accepted);
+
+        let metrics = bus
+            .system_query(&SystemQuery::Metrics(MetricsQuery::default()))
+            .await
+            .unwrap();
+        let SystemResult::Metrics(metrics) = metrics;
+        assert_eq!(metrics.edge_count, 1);
+
+        // An upstream deactivation ends the proposed edge through a command
+        let published = transport.events().len();
+        let deactivated = StubEmitter::person_deactivated(person);
+        deactivated.emit(&transport).await.unwrap();
+        for _ in 0..100 {
+            if transport.events().len() > published {
+                break;
+            }
+            tokio::task::yield_now().await;
+        }
+        assert!(transport.events().len() > published);
+        assert_eq!(worker.outbox_len().await, 0);
+
+        // Redelivered upstream events are not reacted to again
+        let redelivered = worker
+            .handle_upstream(&deactivated.subject, &deactivated.payload)
+            .await;
+        assert_eq!(redelivered.unwrap(), 0);
+
+        // The sweep rejects consent requests past their deadline
+        let friends = RelationshipId::new();
+        for command in [
+            RelationshipCommand::from(EdgeCommand::CreateEdge(CreateEdge {
+                identity: MessageIdentity::new_root(),
+                edge_id: friends,
+                source: EntityRef::person(Uuid::now_v7()),
+                target: EntityRef::person(Uuid::now_v7()),
+                category: RelationshipCategory::Friendship,
+                name: "Friends".to_string(),
+                quality: None,
+                created_by: "test".to_string(),
+            })),
+            RelationshipCommand::from(EdgeCommand::RequestConsent(RequestConsent {
+                identity: MessageIdentity::new_root(),
+                edge_id: friends,
+                expires_at: Some(chrono::Utc::now()),
+                requested_by: "test".to_string(),
+            })),
+        ] {
+            worker.execute(command).await.unwrap();
+        }
+        assert_eq!(worker.sweep_expired().await, 1);
+        let state = read_model.space().read().await.get_edge(&friends).unwrap().state;
+        assert_eq!(state, crate::aggregates::EdgeState::Rejected);
+
+        // Shutdown ends every serving loop
+        worker.shutdown().trigger();
+        serving.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_commands_commit_once_an_event_is_stored() {
+        use crate::services::VerificationMode;
+
+        let transport = MockTransport::new();
+        let read_model = RelationshipReadModel::new(Arc::new(RwLock::new(RelationshipSpace::new(
+            "Worker",
+            TopologicalSpaceId::new(),
+        ))));
+        // Edges to unknown organizations are created suspected, in three events
+        let worker = RelationshipWorker::new(RelationshipBus::new(transport.clone()), read_model.clone())
+            .with_verifier(EntityVerifier::new().with_mode(VerificationMode::Suspect));
+        let create = || {
+            let person = EntityRef::person(Uuid::now_v7());
+            let organization = EntityRef::organization(Uuid::now_v7());
+            transport.on_request(person.to_nats_subject(), |_| Ok(Bytes::from_static(br#"{"name":"Alice"}"#)));
+            transport.on_request(organization.to_nats_subject(), |_| Ok(Bytes::from_static(b"null")));
+            let edge_id = RelationshipId::new();
+            let command = RelationshipCommand::from(EdgeCommand::CreateEdge(CreateEdge {
+                identity: MessageIdentity::new_root(),
+                edge_id,
+                source: person,
+                target: organization,
+                category: RelationshipCategory::Employment,
+                name: "Employment".to_string(),
+                quality: None,
+                created_by: "test".to_string(),
+            }));
+            (edge_id, command)
+        };
+        let exists = |edge_id| {
+            let read_model = read_model.clone();
+            async move { read_model.space().read().await.get_edge(&edge_id).is_some() }
+        };
+
+        // Nothing stored: the command fails and the space is untouched
+        transport.fail_publishing_after(Some(0));
+        let (unstored, command) = create();
+        assert!(worker.execute(command).await.is_err());
+        assert!(!exists(unstored).await);
+        assert_eq!(worker.outbox_len().await, 0);
+
+        // The first event stored commits the command; the rest wait
+        transport.fail_publishing_after(Some(1));
+        let (stored, command) = create();
+        let events = worker.execute(command).await.unwrap();
+        assert_eq!(events.len(), 3);
+        assert!(exists(stored).await);
+        assert_eq!(worker.outbox_len().await, 2);
+
+        // Later commands fail while the earlier events cannot go out
+        transport.fail_publishing_after(Some(0));
+        let (blocked, command) = create();
+        assert!(worker.execute(command).await.is_err());
+        assert!(!exists(blocked).await);
+        assert_eq!(worker.outbox_len().await, 2);
+
+        transport.fail_publishing_after(None);
+        assert_eq!(worker.flush_outbox().await.unwrap(), 2);
+        let types: Vec<_> = transport.events().iter().map(|e| e.event_type()).collect();
+        let expected: Vec<_> = events.iter().map(|e| e.event_type()).collect();
+        assert_eq!(types, expected);
+        assert!(transport.events().iter().all(|e| e.relationship_id() == stored));
+    }
+
+    #[tokio::test]
+    async fn test_upstream_event_recorded_once_reactions_are_stored() {
+        let transport = MockTransport::new();
+        let read_model = RelationshipReadModel::new(Arc::new(RwLock::new(RelationshipSpace::new(
+            "Worker",
+            TopologicalSpaceId::new(),
+        ))));
+        let worker = RelationshipWorker::new(RelationshipBus::new(transport.clone()), read_model.clone());
+        let person = Uuid::now_v7();
+        let edge_id = RelationshipId::new();
+        worker
+            .execute(RelationshipCommand::from(EdgeCommand::CreateEdge(CreateEdge {
+                identity: MessageIdentity::new_root(),
+                edge_id,
+                source: EntityRef::person(person),
+                target: EntityRef::organization(Uuid::now_v7()),
+                category: RelationshipCategory::Employment,
+                name: "Employment".to_string(),
+                quality: None,
+                created_by: "test".to_string(),
+            })))
+            .await
+            .unwrap();
+        let deactivated = StubEmitter::person_deactivated(person);
+        let state = || {
+            let read_model = read_model.clone();
+            async move { read_model.space().read().await.get_edge(&edge_id).unwrap().state }
+        };
+
+        // Nothing stored: the message fails, for redelivery
+        transport.fail_publishing_after(Some(0));
+        let failed = worker.handle_upstream(&deactivated.subject, &deactivated.payload).await;
+        assert!(matches!(failed, Err(RelationshipError::TransportError(_))));
+        assert_eq!(state().await, crate::aggregates::EdgeState::Proposed);
+
+        // The redelivery is reacted to, and only it
+        transport.fail_publishing_after(None);
+        let handled = worker.handle_upstream(&deactivated.subject, &deactivated.payload).await;
+        assert_eq!(handled.unwrap(), 1);
+        assert_ne!(state().await, crate::aggregates::EdgeState::Proposed);
+        let redelivered = worker.handle_upstream(&deactivated.subject, &deactivated.payload).await;
+        assert_eq!(redelivered.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_worker_verifies_new_edges() {
+        use crate::services::VerificationMode;
+        use cim_domain_spaces::KnowledgeLevel;
+
+        let transport = MockTransport::new();
+        let alice = EntityRef::person(Uuid::now_v7());
+        let acme = EntityRef::organization(Uuid::now_v7());
+        transport.on_request(alice.to_nats_subject(), |_| Ok(Bytes::from_static(br#"{"name":"Alice"}"#)));
+        transport.on_request(acme.to_nats_subject(), |_| Ok(Bytes::from_static(b"null")));
+        let read_model = RelationshipReadModel::new(Arc::new(RwLock::new(RelationshipSpace::new(
+            "Worker",
+            TopologicalSpaceId::new(),
+        ))));
+        let worker = RelationshipWorker::new(RelationshipBus::new(transport.clone()), read_model.clone())
+            .with_verifier(EntityVerifier::new().with_mode(VerificationMode::Suspect));
+
+        let edge_id = RelationshipId::new();
+        let events = worker
+            .execute(RelationshipCommand::from(EdgeCommand::CreateEdge(CreateEdge {
+                identity: MessageIdentity::new_root(),
+                edge_id,
+                source: alice,
+                target: acme,
+                category: RelationshipCategory::Employment,
+                name: "Employment".to_string(),
+                quality: None,
+                created_by: "test".to_string(),
+            })))
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 3);
+        assert_eq!(transport.events().len(), 3);
+        let space = read_model.space().read().await;
+        assert_eq!(space.get_edge(&edge_id).unwrap().knowledge_level, KnowledgeLevel::Suspected);
+    }
+}
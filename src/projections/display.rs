@@ -0,0 +1,226 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Entity Display Cache
+//!
+//! Edges only hold references to entities owned by other domains. To show
+//! "Acme Corp" instead of `organization:0190...`, the cache keeps a little
+//! display metadata per referenced entity, refreshed from the upstream
+//! events the crate already subscribes to:
+//!
+//! ```text
+//! organization.events.organization_renamed    --> name
+//! person.events.person_deactivated            --> status Deactivated
+//! organization.events.organization_merged     --> status Merged(into)
+//! ...
+//!
+//! label(organization:{acme})  --> "Acme Corp"
+//! label(person:{unknown})     --> "person:{unknown}"
+//! ```
+//!
+//! Names and statuses are applied by their own timestamps, so a late event
+//! never overwrites newer metadata. Names the upstream events do not carry
+//! can be seeded from a query with `set_name`.
+
+use crate::cross_domain::CrossDomainEvent;
+use crate::value_objects::{EntityKey, EntityRef, EntityType};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Lifecycle status of a referenced entity, as last reported upstream
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EntityStatus {
+    /// No lifecycle event seen
+    #[default]
+    Active,
+    /// Person deactivated
+    Deactivated,
+    /// Organization dissolved
+    Dissolved,
+    /// Location retired
+    Retired,
+    /// Agent decommissioned
+    Decommissioned,
+    /// Policy revoked
+    Revoked,
+    /// Merged into another entity
+    Merged(EntityRef),
+}
+
+impl EntityStatus {
+    /// Human-readable name
+    pub fn display_name(&self) -> String {
+        match self {
+            EntityStatus::Active => "Active".to_string(),
+            EntityStatus::Deactivated => "Deactivated".to_string(),
+            EntityStatus::Dissolved => "Dissolved".to_string(),
+            EntityStatus::Retired => "Retired".to_string(),
+            EntityStatus::Decommissioned => "Decommissioned".to_string(),
+            EntityStatus::Revoked => "Revoked".to_string(),
+            EntityStatus::Merged(into) => format!("Merged into {}", into),
+        }
+    }
+}
+
+/// Display metadata of one entity
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntityDisplay {
+    /// Type of entity
+    pub entity_type: EntityType,
+    /// Last known name
+    pub name: Option<String>,
+    /// Last known status
+    pub status: EntityStatus,
+    name_as_of: Option<DateTime<Utc>>,
+    status_as_of: Option<DateTime<Utc>>,
+}
+
+impl EntityDisplay {
+    fn new(entity_type: EntityType) -> Self {
+        Self {
+            entity_type,
+            name: None,
+            status: EntityStatus::Active,
+            name_as_of: None,
+            status_as_of: None,
+        }
+    }
+
+    fn set_name(&mut self, name: &str, at: DateTime<Utc>) {
+        if self.name_as_of.is_none_or(|as_of| as_of <= at) {
+            self.name = Some(name.to_string());
+            self.name_as_of = Some(at);
+        }
+    }
+
+    fn set_status(&mut self, status: EntityStatus, at: DateTime<Utc>) {
+        if self.status_as_of.is_none_or(|as_of| as_of <= at) {
+            self.status = status;
+            self.status_as_of = Some(at);
+        }
+    }
+}
+
+/// Name and status of every entity upstream events mentioned
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EntityDisplayCache {
+    entities: HashMap<EntityKey, EntityDisplay>,
+}
+
+impl EntityDisplayCache {
+    /// Create an empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply an upstream event
+    pub fn apply(&mut self, event: &CrossDomainEvent) {
+        let at = event.occurred_at();
+        let status = match event {
+            CrossDomainEvent::OrganizationRenamed(e) => {
+                self.entry(&event.entity()).set_name(&e.new_name, at);
+                return;
+            }
+            CrossDomainEvent::PersonDeactivated(_) => EntityStatus::Deactivated,
+            CrossDomainEvent::OrganizationDissolved(_) => EntityStatus::Dissolved,
+            CrossDomainEvent::LocationRetired(_) => EntityStatus::Retired,
+            CrossDomainEvent::AgentDecommissioned(_) => EntityStatus::Decommissioned,
+            CrossDomainEvent::PolicyEnacted(e) => {
+                self.entry(&event.entity()).set_name(&e.name, at);
+                EntityStatus::Active
+            }
+            CrossDomainEvent::PolicyRevoked(_) => EntityStatus::Revoked,
+            CrossDomainEvent::PersonMerged(e) => EntityStatus::Merged(EntityRef::person(e.surviving_person_id)),
+            CrossDomainEvent::OrganizationMerged(e) => {
+                EntityStatus::Merged(EntityRef::organization(e.surviving_organization_id))
+            }
+        };
+        self.entry(&event.entity()).set_status(status, at);
+    }
+
+    /// Apply a sequence of upstream events
+    pub fn apply_all<'a>(&mut self, events: impl IntoIterator<Item = &'a CrossDomainEvent>) {
+        for event in events {
+            self.apply(event);
+        }
+    }
+
+    /// Record a name learned outside the event stream (e.g. from a query)
+    pub fn set_name(&mut self, entity: &EntityRef, name: impl Into<String>, as_of: DateTime<Utc>) {
+        self.entry(entity).set_name(&name.into(), as_of);
+    }
+
+    /// Metadata of an entity, if any was seen
+    pub fn get(&self, entity: &EntityRef) -> Option<&EntityDisplay> {
+        self.entities.get(&entity.key())
+    }
+
+    /// Name of an entity, falling back to its reference
+    pub fn label(&self, entity: &EntityRef) -> String {
+        self.get(entity)
+            .and_then(|d| d.name.clone())
+            .unwrap_or_else(|| entity.key().to_string())
+    }
+
+    /// Number of entities with metadata
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Check if the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    fn entry(&mut self, entity: &EntityRef) -> &mut EntityDisplay {
+        self.entities
+            .entry(entity.key())
+            .or_insert_with(|| EntityDisplay::new(entity.entity_type.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cross_domain::{OrganizationMerged, OrganizationRenamed};
+    use chrono::Duration;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_display_cache_applies_by_timestamp() {
+        let acme = EntityRef::organization(Uuid::now_v7());
+        let holding = EntityRef::organization(Uuid::now_v7());
+        let t0 = Utc::now();
+        let renamed = |name: &str, at| {
+            CrossDomainEvent::OrganizationRenamed(OrganizationRenamed {
+                organization_id: acme.entity_id,
+                old_name: None,
+                new_name: name.to_string(),
+                renamed_at: at,
+            })
+        };
+
+        let mut cache = EntityDisplayCache::new();
+        assert_eq!(cache.label(&acme), acme.key().to_string());
+
+        cache.apply(&renamed("Acme Corp", t0 + Duration::days(2)));
+        // A late rename does not overwrite the newer name
+        cache.apply(&renamed("Acme Ltd", t0 + Duration::days(1)));
+        cache.apply(&CrossDomainEvent::OrganizationMerged(OrganizationMerged {
+            merged_organization_id: acme.entity_id,
+            surviving_organization_id: holding.entity_id,
+            merged_at: t0 + Duration::days(3),
+        }));
+
+        assert_eq!(cache.label(&acme), "Acme Corp");
+        let display = cache.get(&acme).unwrap();
+        assert_eq!(display.entity_type, EntityType::Organization);
+        assert_eq!(display.status, EntityStatus::Merged(holding.clone()));
+
+        cache.set_name(&holding, "Holding AG", t0);
+        assert_eq!(cache.label(&holding), "Holding AG");
+        assert_eq!(cache.len(), 2);
+    }
+}
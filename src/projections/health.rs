@@ -0,0 +1,471 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Hyperedge Health Projection
+//!
+//! A "collective health" score per hyperedge, built from its event stream,
+//! for "teams at risk" dashboards.
+//!
+//! ## Components
+//!
+//! Each component is normalized to 0.0 (unhealthy) - 1.0 (healthy):
+//!
+//! - **Stability**: 1 - churn rate, where churn is participants added or
+//!   removed within the window per current participant
+//! - **Balance**: Shannon entropy of participant weights divided by its
+//!   maximum, ln(n); 1.0 when everyone carries equal weight
+//! - **Trend**: Change in strength across the window, mapped so that
+//!   no change is 0.5
+//! - **Activity**: Halves for every `activity_half_life_days` since the
+//!   last event
+//!
+//! The score is the weighted mean of the components.
+//!
+//! Events are applied by their own timestamps, so the projection reaches
+//! the same state whatever order they arrive in.
+
+use crate::events::HyperEdgeEvent;
+use crate::value_objects::{EntityKey, RelationshipId};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Tuning for the health score
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HealthConfig {
+    /// Days of history considered for churn and trend
+    pub window_days: i64,
+    /// Days of inactivity after which activity health halves
+    pub activity_half_life_days: f64,
+    /// Scores below this are at risk
+    pub at_risk_threshold: f64,
+    /// Weight of the stability component
+    pub stability_weight: f64,
+    /// Weight of the balance component
+    pub balance_weight: f64,
+    /// Weight of the trend component
+    pub trend_weight: f64,
+    /// Weight of the activity component
+    pub activity_weight: f64,
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        Self {
+            window_days: 90,
+            activity_half_life_days: 30.0,
+            at_risk_threshold: 0.5,
+            stability_weight: 1.0,
+            balance_weight: 1.0,
+            trend_weight: 1.0,
+            activity_weight: 1.0,
+        }
+    }
+}
+
+/// Health of a single hyperedge at a point in time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HyperEdgeHealth {
+    /// The hyperedge
+    pub hyperedge_id: RelationshipId,
+    /// Hyperedge name
+    pub name: String,
+    /// Current participant count
+    pub participant_count: usize,
+    /// Stability component (1 - churn rate)
+    pub stability: f64,
+    /// Balance component (normalized weight entropy)
+    pub balance: f64,
+    /// Trend component (0.5 = flat)
+    pub trend: f64,
+    /// Activity component
+    pub activity: f64,
+    /// Combined score (0.0 - 1.0)
+    pub score: f64,
+    /// Whether the score is below the at-risk threshold
+    pub at_risk: bool,
+}
+
+/// Latest known membership of one participant
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Membership {
+    weight: f64,
+    present: bool,
+    as_of: DateTime<Utc>,
+}
+
+/// Everything the projection remembers about one hyperedge
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct HyperEdgeActivity {
+    name: String,
+    /// When the name was set
+    #[serde(default)]
+    name_as_of: Option<DateTime<Utc>>,
+    members: HashMap<EntityKey, Membership>,
+    membership_changes: Vec<DateTime<Utc>>,
+    /// (updated_at, old strength, new strength), ordered by time
+    strength_history: Vec<(DateTime<Utc>, f64, f64)>,
+    last_activity: Option<DateTime<Utc>>,
+    terminated: bool,
+}
+
+impl HyperEdgeActivity {
+    /// Apply a name unless a newer one is already known
+    fn set_name(&mut self, name: &str, at: DateTime<Utc>) {
+        if self.name_as_of.is_none_or(|as_of| as_of <= at) {
+            self.name = name.to_string();
+            self.name_as_of = Some(at);
+        }
+    }
+
+    /// Apply a membership fact unless a newer one is already known
+    fn set_member(&mut self, key: EntityKey, weight: Option<f64>, present: bool, at: DateTime<Utc>) {
+        match self.members.get_mut(&key) {
+            Some(member) if member.as_of > at => {}
+            Some(member) => {
+                member.weight = weight.unwrap_or(member.weight);
+                member.present = present;
+                member.as_of = at;
+            }
+            None => {
+                self.members.insert(
+                    key,
+                    Membership {
+                        weight: weight.unwrap_or(0.0),
+                        present,
+                        as_of: at,
+                    },
+                );
+            }
+        }
+    }
+
+    fn weights(&self) -> impl Iterator<Item = f64> + '_ {
+        self.members.values().filter(|m| m.present).map(|m| m.weight)
+    }
+}
+
+/// Projection of hyperedge events into health scores
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HyperEdgeHealthProjection {
+    /// Scoring configuration
+    pub config: HealthConfig,
+    hyperedges: HashMap<RelationshipId, HyperEdgeActivity>,
+}
+
+impl HyperEdgeHealthProjection {
+    /// Create an empty projection
+    pub fn new(config: HealthConfig) -> Self {
+        Self {
+            config,
+            hyperedges: HashMap::new(),
+        }
+    }
+
+    /// Apply a hyperedge event
+    ///
+    /// Events may arrive in any order: every fact is applied by its own
+    /// timestamp, so a late event corrects the read model instead of
+    /// overwriting newer state.
+    pub fn apply(&mut self, event: &HyperEdgeEvent) {
+        let activity = self.hyperedges.entry(event.hyperedge_id()).or_default();
+        let at = match event {
+            HyperEdgeEvent::HyperEdgeCreated(e) => {
+                activity.set_name(&e.name, e.created_at);
+                for p in e.initial_participants.participants() {
+                    activity.set_member(p.entity_ref.key(), Some(p.weight), true, e.created_at);
+                }
+                e.created_at
+            }
+            HyperEdgeEvent::HyperEdgeActivated(e) => e.activated_at,
+            HyperEdgeEvent::ParticipantAdded(e) => {
+                activity.set_member(e.participant.key(), Some(e.weight), true, e.added_at);
+                activity.membership_changes.push(e.added_at);
+                e.added_at
+            }
+            HyperEdgeEvent::ParticipantRemoved(e) => {
+                activity.set_member(e.participant.key(), None, false, e.removed_at);
+                activity.membership_changes.push(e.removed_at);
+                e.removed_at
+            }
+            HyperEdgeEvent::ParticipantRoleChanged(e) => e.changed_at,
+            HyperEdgeEvent::ParticipantWeightChanged(e) => {
+                let present = activity
+                    .members
+                    .get(&e.participant.key())
+                    .is_none_or(|m| m.present || m.as_of <= e.changed_at);
+                activity.set_member(e.participant.key(), Some(e.new_weight), present, e.changed_at);
+                e.changed_at
+            }
+            HyperEdgeEvent::HyperEdgeTerminated(e) => {
+                activity.terminated = true;
+                e.terminated_at
+            }
+            HyperEdgeEvent::HyperEdgeQualityUpdated(e) => {
+                let entry = (e.updated_at, e.old_quality.strength, e.new_quality.strength);
+                let idx = activity.strength_history.partition_point(|(t, _, _)| *t <= e.updated_at);
+                activity.strength_history.insert(idx, entry);
+                e.updated_at
+            }
+            HyperEdgeEvent::PromotedFromEdge(e) => e.promoted_at,
+            HyperEdgeEvent::TagAdded(e) => e.tagged_at,
+            HyperEdgeEvent::TagRemoved(e) => e.removed_at,
+            HyperEdgeEvent::GroupAdded(e) => e.added_at,
+            HyperEdgeEvent::GroupRemoved(e) => e.removed_at,
+            HyperEdgeEvent::AssignedToGroup(e) => e.assigned_at,
+            HyperEdgeEvent::UnassignedFromGroup(e) => e.unassigned_at,
+            HyperEdgeEvent::Archived(e) => e.archived_at,
+            HyperEdgeEvent::RestructuringBegun(e) => e.begun_at,
+            HyperEdgeEvent::RestructuringCompleted(e) => e.completed_at,
+            HyperEdgeEvent::Restored(e) => e.restored_at,
+            HyperEdgeEvent::Renamed(e) => {
+                activity.set_name(&e.new_name, e.renamed_at);
+                e.renamed_at
+            }
+            HyperEdgeEvent::DescriptionUpdated(e) => e.updated_at,
+            HyperEdgeEvent::ParticipantAcknowledged(e) => e.acknowledged_at,
+            HyperEdgeEvent::TemplateApplied(e) => e.applied_at,
+        };
+        activity.last_activity = Some(activity.last_activity.map_or(at, |last| last.max(at)));
+    }
+
+    /// Apply a sequence of hyperedge events
+    pub fn apply_all<'a>(&mut self, events: impl IntoIterator<Item = &'a HyperEdgeEvent>) {
+        for event in events {
+            self.apply(event);
+        }
+    }
+
+    /// Health of one hyperedge as of `now`
+    pub fn health(&self, id: &RelationshipId, now: DateTime<Utc>) -> Option<HyperEdgeHealth> {
+        self.hyperedges.get(id).map(|a| self.score(*id, a, now))
+    }
+
+    /// Health of every live hyperedge, least healthy first
+    pub fn all(&self, now: DateTime<Utc>) -> Vec<HyperEdgeHealth> {
+        let mut scores: Vec<HyperEdgeHealth> = self
+            .hyperedges
+            .iter()
+            .filter(|(_, a)| !a.terminated)
+            .map(|(id, a)| self.score(*id, a, now))
+            .collect();
+        scores.sort_by(|a, b| a.score.total_cmp(&b.score));
+        scores
+    }
+
+    /// Live hyperedges whose score is below the at-risk threshold, least
+    /// healthy first
+    pub fn at_risk(&self, now: DateTime<Utc>) -> Vec<HyperEdgeHealth> {
+        self.all(now).into_iter().filter(|h| h.at_risk).collect()
+    }
+
+    fn score(&self, id: RelationshipId, activity: &HyperEdgeActivity, now: DateTime<Utc>) -> HyperEdgeHealth {
+        let config = &self.config;
+        let since = now - Duration::days(config.window_days);
+        let participant_count = activity.weights().count();
+
+        let churn = activity.membership_changes.iter().filter(|t| **t >= since).count();
+        let stability = 1.0 - (churn as f64 / participant_count.max(1) as f64).min(1.0);
+
+        let balance = normalized_entropy(activity.weights());
+
+        let recent: Vec<_> = activity
+            .strength_history
+            .iter()
+            .filter(|(t, _, _)| *t >= since)
+            .collect();
+        let trend = match (recent.first(), recent.last()) {
+            (Some((_, before, _)), Some((_, _, after))) => (0.5 + (after - before) / 2.0).clamp(0.0, 1.0),
+            _ => 0.5,
+        };
+
+        let idle_days = activity
+            .last_activity
+            .map_or(0.0, |t| (now - t).num_seconds().max(0) as f64 / 86_400.0);
+        let activity_score = 0.5_f64.powf(idle_days / config.activity_half_life_days);
+
+        let total_weight = config.stability_weight
+            + config.balance_weight
+            + config.trend_weight
+            + config.activity_weight;
+        let score = if total_weight > 0.0 {
+            (stability * config.stability_weight
+                + balance * config.balance_weight
+                + trend * config.trend_weight
+                + activity_score * config.activity_weight)
+                / total_weight
+        } else {
+            0.0
+        };
+
+        HyperEdgeHealth {
+            hyperedge_id: id,
+            name: activity.name.clone(),
+            participant_count,
+            stability,
+            balance,
+            trend,
+            activity: activity_score,
+            score,
+            at_risk: score < config.at_risk_threshold,
+        }
+    }
+}
+
+/// Shannon entropy of a weight distribution divided by ln(n)
+///
+/// 1.0 for a single participant or perfectly equal weights.
+fn normalized_entropy(weights: impl Iterator<Item = f64>) -> f64 {
+    let weights: Vec<f64> = weights.filter(|w| *w > 0.0).collect();
+    let total: f64 = weights.iter().sum();
+    if weights.len() < 2 || total <= 0.0 {
+        return 1.0;
+    }
+    let entropy: f64 = weights
+        .iter()
+        .map(|w| {
+            let p = w / total;
+            -p * p.ln()
+        })
+        .sum();
+    entropy / (weights.len() as f64).ln()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{HyperEdgeCreated, HyperEdgeQualityUpdated, ParticipantAdded, ParticipantRemoved};
+    use crate::quality::RelationshipQuality;
+    use crate::value_objects::{EntityRef, IncidenceMatrix, ParticipantRole, RelationshipCategory};
+    use cim_domain::MessageIdentity;
+    use cim_domain_spaces::ConceptId;
+    use uuid::Uuid;
+
+    fn created(id: RelationshipId, weights: &[f64], at: DateTime<Utc>) -> HyperEdgeEvent {
+        let mut participants = IncidenceMatrix::new();
+        for w in weights {
+            participants.add_participant(EntityRef::person(Uuid::now_v7()), ParticipantRole::Member, *w);
+        }
+        HyperEdgeEvent::HyperEdgeCreated(HyperEdgeCreated {
+            event_id: Uuid::now_v7(),
+            identity: MessageIdentity::new_root(),
+            hyperedge_id: id,
+            concept_id: ConceptId::new(),
+            name: "Team".to_string(),
+            category: RelationshipCategory::Membership,
+            initial_participants: participants,
+            activation: Default::default(),
+            created_by: "test".to_string(),
+            created_at: at,
+        })
+    }
+
+    #[test]
+    fn test_balanced_active_team_is_healthy() {
+        let now = Utc::now();
+        let id = RelationshipId::new();
+        let mut projection = HyperEdgeHealthProjection::default();
+        projection.apply(&created(id, &[1.0, 1.0, 1.0], now));
+
+        let health = projection.health(&id, now).unwrap();
+        assert_eq!(health.participant_count, 3);
+        assert!((health.balance - 1.0).abs() < 1e-9);
+        assert!((health.stability - 1.0).abs() < 1e-9);
+        assert!(!health.at_risk);
+        assert!(projection.at_risk(now).is_empty());
+    }
+
+    #[test]
+    fn test_churning_declining_team_is_at_risk() {
+        let start = Utc::now() - Duration::days(60);
+        let id = RelationshipId::new();
+        let mut projection = HyperEdgeHealthProjection::default();
+        projection.apply(&created(id, &[1.0, 0.1], start));
+
+        // Members come and go
+        for day in 1..=3 {
+            let person = EntityRef::person(Uuid::now_v7());
+            let at = start + Duration::days(day);
+            projection.apply(&HyperEdgeEvent::ParticipantAdded(ParticipantAdded {
+                event_id: Uuid::now_v7(),
+                identity: MessageIdentity::new_root(),
+                hyperedge_id: id,
+                participant: person.clone(),
+                role: ParticipantRole::Member,
+                weight: 0.5,
+                added_by: "test".to_string(),
+                added_at: at,
+            }));
+            projection.apply(&HyperEdgeEvent::ParticipantRemoved(ParticipantRemoved {
+                event_id: Uuid::now_v7(),
+                identity: MessageIdentity::new_root(),
+                hyperedge_id: id,
+                participant: person,
+                reason: "left".to_string(),
+                removed_by: "test".to_string(),
+                removed_at: at,
+            }));
+        }
+
+        // Strength falls
+        let mut weaker = RelationshipQuality::default_membership();
+        weaker.strength = 0.1;
+        projection.apply(&HyperEdgeEvent::HyperEdgeQualityUpdated(HyperEdgeQualityUpdated {
+            event_id: Uuid::now_v7(),
+            identity: MessageIdentity::new_root(),
+            hyperedge_id: id,
+            old_quality: RelationshipQuality::default_membership(),
+            new_quality: weaker,
+            reason: "drift".to_string(),
+            updated_at: start + Duration::days(5),
+        }));
+
+        let health = projection.health(&id, Utc::now()).unwrap();
+        assert_eq!(health.stability, 0.0);
+        assert!(health.trend < 0.5);
+        assert!(health.balance < 0.5);
+        assert!(health.at_risk);
+        assert_eq!(projection.at_risk(Utc::now()).len(), 1);
+    }
+
+    #[test]
+    fn test_late_events_do_not_corrupt_membership() {
+        let now = Utc::now();
+        let id = RelationshipId::new();
+        let person = EntityRef::person(Uuid::now_v7());
+        let added = HyperEdgeEvent::ParticipantAdded(ParticipantAdded {
+            event_id: Uuid::now_v7(),
+            identity: MessageIdentity::new_root(),
+            hyperedge_id: id,
+            participant: person.clone(),
+            role: ParticipantRole::Member,
+            weight: 1.0,
+            added_by: "test".to_string(),
+            added_at: now - Duration::days(2),
+        });
+        let removed = HyperEdgeEvent::ParticipantRemoved(ParticipantRemoved {
+            event_id: Uuid::now_v7(),
+            identity: MessageIdentity::new_root(),
+            hyperedge_id: id,
+            participant: person,
+            reason: "left".to_string(),
+            removed_by: "test".to_string(),
+            removed_at: now - Duration::days(1),
+        });
+        let creation = created(id, &[1.0, 1.0], now - Duration::days(3));
+
+        let mut in_order = HyperEdgeHealthProjection::default();
+        in_order.apply_all([&creation, &added, &removed]);
+        let mut out_of_order = HyperEdgeHealthProjection::default();
+        out_of_order.apply_all([&removed, &added, &creation]);
+
+        let a = in_order.health(&id, now).unwrap();
+        let b = out_of_order.health(&id, now).unwrap();
+        assert_eq!(a.participant_count, 2);
+        assert_eq!(b.participant_count, 2);
+        assert_eq!(a.name, b.name);
+        assert!((a.score - b.score).abs() < 1e-9);
+    }
+}
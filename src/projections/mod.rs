@@ -5,6 +5,20 @@
 //! Projections for the Relationship Domain
 //!
 //! Read models and query-optimized views.
+//!
+//! `RelationshipReadModel` is the shared space and display cache the API
+//! layers answer from.
+
+mod display;
+mod health;
+mod read_model;
+mod trajectory;
+
+pub use display::{EntityDisplay, EntityDisplayCache, EntityStatus};
+pub use health::{HealthConfig, HyperEdgeHealth, HyperEdgeHealthProjection};
+pub use read_model::RelationshipReadModel;
+pub use trajectory::{
+    QualityTrajectoryProjection, QualityVelocity, Trajectory, TrajectorySample, Trend, TrendReversal,
+};
 
-// Placeholder for relationship projections
 // TODO: Implement RelationshipSummaryProjection, EntityRelationshipsProjection
@@ -0,0 +1,131 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Relationship Read Model
+//!
+//! The shared, query-side view of the domain that API layers read from: a
+//! RelationshipSpace kept current from the event stream, and the display
+//! cache that names the entities it references.
+//!
+//! ```text
+//! relationship.events.>  --> space.apply_event    --+
+//! upstream domain events --> display.apply          +--> GraphQL / REST / gRPC
+//! ```
+//!
+//! Readers take the locks only for the duration of one query.
+
+use super::display::EntityDisplayCache;
+use crate::aggregates::RelationshipSpace;
+use crate::cross_domain::CrossDomainEvent;
+use crate::events::RelationshipEvent;
+use crate::nats::{RelationshipBus, Transport};
+use crate::RelationshipResult;
+use futures::StreamExt;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Shared relationship space and entity display cache
+#[derive(Debug, Clone)]
+pub struct RelationshipReadModel {
+    space: Arc<RwLock<RelationshipSpace>>,
+    display: Arc<RwLock<EntityDisplayCache>>,
+}
+
+impl RelationshipReadModel {
+    /// Read model over a shared space, with an empty display cache
+    pub fn new(space: Arc<RwLock<RelationshipSpace>>) -> Self {
+        Self {
+            space,
+            display: Arc::new(RwLock::new(EntityDisplayCache::new())),
+        }
+    }
+
+    /// Share an existing display cache
+    pub fn with_display(mut self, display: Arc<RwLock<EntityDisplayCache>>) -> Self {
+        self.display = display;
+        self
+    }
+
+    /// The relationship space
+    pub fn space(&self) -> &Arc<RwLock<RelationshipSpace>> {
+        &self.space
+    }
+
+    /// The entity display cache
+    pub fn display(&self) -> &Arc<RwLock<EntityDisplayCache>> {
+        &self.display
+    }
+
+    /// Apply a relationship event
+    pub async fn apply(&self, event: &RelationshipEvent) -> RelationshipResult<()> {
+        self.space.write().await.apply_event(event)
+    }
+
+    /// Apply an upstream event to the display cache
+    pub async fn apply_cross_domain(&self, event: &CrossDomainEvent) {
+        self.display.write().await.apply(event);
+    }
+
+    /// Follow the relationship event stream
+    ///
+    /// Runs until the subscription ends. Events that do not apply are
+    /// logged and skipped.
+    pub async fn run<T: Transport>(self, bus: RelationshipBus<T>) -> RelationshipResult<()> {
+        let mut events = bus.subscribe_events().await?;
+        while let Some(event) = events.next().await {
+            if let Err(e) = self.apply(&event).await {
+                tracing::warn!("read model skipped {}: {}", event.event_type(), e);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{EdgeCreated, EdgeEvent};
+    use crate::nats::MockTransport;
+    use crate::value_objects::{EntityRef, RelationshipCategory, RelationshipId};
+    use cim_domain::MessageIdentity;
+    use cim_domain_spaces::{ConceptId, TopologicalSpaceId};
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_follows_event_stream() {
+        let transport = MockTransport::new();
+        let bus = RelationshipBus::new(transport.clone());
+        let space = Arc::new(RwLock::new(RelationshipSpace::new(
+            "Read",
+            TopologicalSpaceId::new(),
+        )));
+        let read_model = RelationshipReadModel::new(space.clone());
+        let follower = tokio::spawn(read_model.run(bus.clone()));
+        tokio::task::yield_now().await;
+
+        let edge_id = RelationshipId::new();
+        let event = RelationshipEvent::Edge(EdgeEvent::EdgeCreated(EdgeCreated {
+            event_id: Uuid::now_v7(),
+            identity: MessageIdentity::new_root(),
+            edge_id,
+            concept_id: ConceptId::new(),
+            source: EntityRef::person(Uuid::now_v7()),
+            target: EntityRef::organization(Uuid::now_v7()),
+            category: RelationshipCategory::Employment,
+            name: "Employment".to_string(),
+            created_by: "test".to_string(),
+            created_at: chrono::Utc::now(),
+        }));
+        bus.publish_event(&event).await.unwrap();
+
+        for _ in 0..100 {
+            if space.read().await.get_edge(&edge_id).is_some() {
+                break;
+            }
+            tokio::task::yield_now().await;
+        }
+        assert!(space.read().await.get_edge(&edge_id).is_some());
+        follower.abort();
+    }
+}
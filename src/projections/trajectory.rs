@@ -0,0 +1,313 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Quality Trajectory Projection
+//!
+//! Records the successive QualityPoints of each relationship, taken from
+//! its quality-updated events, so a relationship's movement through quality
+//! space can be inspected.
+//!
+//! ## Trajectory
+//!
+//! ```text
+//! origin --> sample(t1) --> sample(t2) --> ... --> sample(tn)
+//!   ^                                                   |
+//!   old quality of the earliest update        velocity = last segment
+//! ```
+//!
+//! Samples are kept in timestamp order, so late events slot into place.
+//! A trend reversal is a dimension changing direction between successive
+//! moves, e.g. trust that was rising starting to fall.
+
+use crate::events::{EdgeEvent, HyperEdgeEvent, RelationshipEvent};
+use crate::quality::{DurationModel, QualityPoint, RelationshipQuality};
+use crate::value_objects::RelationshipId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Direction of movement along one dimension
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Trend {
+    /// Value increasing
+    Rising,
+    /// Value decreasing
+    Falling,
+}
+
+/// Point on a trajectory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrajectorySample {
+    /// When the relationship moved here
+    pub at: DateTime<Utc>,
+    /// Position in quality space
+    pub point: QualityPoint,
+    /// Reason recorded on the update
+    pub reason: String,
+}
+
+/// Rate of movement through quality space
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QualityVelocity {
+    /// Change per day along each dimension, in `QualityPoint::DIMENSIONS` order
+    pub per_day: [f64; 5],
+    /// Length of `per_day`
+    pub speed: f64,
+    /// Unit vector of `per_day` (zero when not moving)
+    pub direction: [f64; 5],
+}
+
+/// A dimension changing direction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrendReversal {
+    /// Dimension name
+    pub dimension: String,
+    /// Direction before the reversal
+    pub from: Trend,
+    /// Direction after the reversal
+    pub to: Trend,
+    /// Time of the sample the new direction led to
+    pub at: DateTime<Utc>,
+}
+
+/// Movement of one relationship through quality space
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Trajectory {
+    /// The relationship
+    pub relationship_id: RelationshipId,
+    /// Position before the earliest recorded update
+    pub origin: Option<QualityPoint>,
+    /// Positions after each update, oldest first
+    pub samples: Vec<TrajectorySample>,
+}
+
+impl Trajectory {
+    fn new(relationship_id: RelationshipId) -> Self {
+        Self {
+            relationship_id,
+            origin: None,
+            samples: Vec::new(),
+        }
+    }
+
+    fn record(&mut self, at: DateTime<Utc>, old: QualityPoint, new: QualityPoint, reason: &str) {
+        if self.samples.first().is_none_or(|first| at < first.at) {
+            self.origin = Some(old);
+        }
+        let idx = self.samples.partition_point(|s| s.at <= at);
+        self.samples.insert(
+            idx,
+            TrajectorySample {
+                at,
+                point: new,
+                reason: reason.to_string(),
+            },
+        );
+    }
+
+    /// Every position, origin first
+    pub fn path(&self) -> Vec<QualityPoint> {
+        self.origin
+            .into_iter()
+            .chain(self.samples.iter().map(|s| s.point))
+            .collect()
+    }
+
+    /// Velocity over the most recent move between two samples
+    pub fn velocity(&self) -> Option<QualityVelocity> {
+        let [.., from, to] = self.samples.as_slice() else {
+            return None;
+        };
+        let days = (to.at - from.at).num_seconds() as f64 / 86_400.0;
+        if days <= 0.0 {
+            return None;
+        }
+
+        let (a, b) = (from.point.to_array(), to.point.to_array());
+        let per_day: [f64; 5] = std::array::from_fn(|i| (b[i] - a[i]) / days);
+        let speed = per_day.iter().map(|v| v * v).sum::<f64>().sqrt();
+        let direction = if speed > 0.0 {
+            per_day.map(|v| v / speed)
+        } else {
+            [0.0; 5]
+        };
+        Some(QualityVelocity {
+            per_day,
+            speed,
+            direction,
+        })
+    }
+
+    /// Successive directions of one dimension, ignoring moves smaller than
+    /// `epsilon`, each with the time of the sample it led to
+    fn moves(&self, dimension: usize, epsilon: f64) -> Vec<(Trend, DateTime<Utc>)> {
+        // The path is the origin followed by the samples, so window k
+        // leads to sample k
+        self.path()
+            .windows(2)
+            .zip(self.samples.iter().map(|s| s.at))
+            .filter_map(|(pair, at)| {
+                let delta = pair[1].to_array()[dimension] - pair[0].to_array()[dimension];
+                if delta > epsilon {
+                    Some((Trend::Rising, at))
+                } else if delta < -epsilon {
+                    Some((Trend::Falling, at))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Most recent direction of a dimension, if it has moved
+    pub fn trend(&self, dimension: &str, epsilon: f64) -> Option<Trend> {
+        let idx = QualityPoint::DIMENSIONS.iter().position(|d| *d == dimension)?;
+        self.moves(idx, epsilon).last().map(|(trend, _)| *trend)
+    }
+
+    /// Every change of direction across all dimensions, oldest first
+    pub fn reversals(&self, epsilon: f64) -> Vec<TrendReversal> {
+        let mut reversals: Vec<TrendReversal> = QualityPoint::DIMENSIONS
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, name)| {
+                self.moves(idx, epsilon)
+                    .windows(2)
+                    .filter(|w| w[0].0 != w[1].0)
+                    .map(|w| TrendReversal {
+                        dimension: name.to_string(),
+                        from: w[0].0,
+                        to: w[1].0,
+                        at: w[1].1,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        reversals.sort_by_key(|r| r.at);
+        reversals
+    }
+}
+
+/// Read model of relationship quality trajectories
+#[derive(Debug, Clone, Default)]
+pub struct QualityTrajectoryProjection {
+    duration_model: DurationModel,
+    trajectories: HashMap<RelationshipId, Trajectory>,
+}
+
+impl QualityTrajectoryProjection {
+    /// Create an empty projection
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Normalize durations with a space's model
+    pub fn with_duration_model(mut self, model: DurationModel) -> Self {
+        self.duration_model = model;
+        self
+    }
+
+    /// Apply a relationship event; only quality updates are recorded
+    pub fn apply(&mut self, event: &RelationshipEvent) {
+        let (id, old, new, reason, at) = match event {
+            RelationshipEvent::Edge(EdgeEvent::QualityUpdated(e)) => {
+                (e.edge_id, &e.old_quality, &e.new_quality, &e.reason, e.updated_at)
+            }
+            RelationshipEvent::HyperEdge(HyperEdgeEvent::HyperEdgeQualityUpdated(e)) => {
+                (e.hyperedge_id, &e.old_quality, &e.new_quality, &e.reason, e.updated_at)
+            }
+            _ => return,
+        };
+        let point = |q: &RelationshipQuality| q.to_quality_point_at(&self.duration_model, None, at);
+        let (old, new) = (point(old), point(new));
+        self.trajectories
+            .entry(id)
+            .or_insert_with(|| Trajectory::new(id))
+            .record(at, old, new, reason);
+    }
+
+    /// Apply a sequence of relationship events
+    pub fn apply_all<'a>(&mut self, events: impl IntoIterator<Item = &'a RelationshipEvent>) {
+        for event in events {
+            self.apply(event);
+        }
+    }
+
+    /// Trajectory of a relationship
+    pub fn trajectory(&self, id: &RelationshipId) -> Option<&Trajectory> {
+        self.trajectories.get(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::EdgeQualityUpdated;
+    use chrono::Duration;
+    use cim_domain::MessageIdentity;
+    use uuid::Uuid;
+
+    fn update(id: RelationshipId, at: DateTime<Utc>, old: (f64, f64), new: (f64, f64)) -> RelationshipEvent {
+        let quality = |(strength, trust)| RelationshipQuality {
+            strength,
+            trust,
+            ..RelationshipQuality::default()
+        };
+        RelationshipEvent::Edge(EdgeEvent::QualityUpdated(EdgeQualityUpdated {
+            event_id: Uuid::now_v7(),
+            identity: MessageIdentity::new_root(),
+            edge_id: id,
+            old_quality: quality(old),
+            new_quality: quality(new),
+            reason: "review".to_string(),
+            updated_at: at,
+        }))
+    }
+
+    #[test]
+    fn test_trajectory_out_of_order() {
+        let id = RelationshipId::new();
+        let t0 = Utc::now();
+        let events = [
+            update(id, t0 + Duration::days(20), (0.6, 0.6), (0.8, 0.4)),
+            update(id, t0 + Duration::days(10), (0.5, 0.5), (0.6, 0.6)),
+        ];
+
+        let mut projection = QualityTrajectoryProjection::new();
+        projection.apply_all(&events);
+        let trajectory = projection.trajectory(&id).unwrap();
+
+        let strengths: Vec<f64> = trajectory.path().iter().map(|p| p.strength).collect();
+        assert_eq!(strengths, vec![0.5, 0.6, 0.8]);
+
+        // 0.2 strength over 10 days
+        let velocity = trajectory.velocity().unwrap();
+        assert!((velocity.per_day[0] - 0.02).abs() < 1e-9);
+        assert!((velocity.direction.iter().map(|v| v * v).sum::<f64>() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_trend_reversal() {
+        let id = RelationshipId::new();
+        let t0 = Utc::now();
+        let events = [
+            update(id, t0 + Duration::days(1), (0.5, 0.5), (0.5, 0.6)),
+            update(id, t0 + Duration::days(2), (0.5, 0.6), (0.6, 0.7)),
+            update(id, t0 + Duration::days(3), (0.6, 0.7), (0.7, 0.5)),
+        ];
+
+        let mut projection = QualityTrajectoryProjection::new();
+        projection.apply_all(&events);
+        let trajectory = projection.trajectory(&id).unwrap();
+
+        assert_eq!(trajectory.trend("trust", 1e-6), Some(Trend::Falling));
+        assert_eq!(trajectory.trend("strength", 1e-6), Some(Trend::Rising));
+
+        let reversals = trajectory.reversals(1e-6);
+        assert_eq!(reversals.len(), 1);
+        assert_eq!(reversals[0].dimension, "trust");
+        assert_eq!((reversals[0].from, reversals[0].to), (Trend::Rising, Trend::Falling));
+        assert_eq!(reversals[0].at, t0 + Duration::days(3));
+    }
+}
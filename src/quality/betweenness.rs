@@ -0,0 +1,93 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Betweenness and Interpolation
+//!
+//! Gärdenfors' betweenness: B lies between A and C when going from A to C
+//! by way of B is no longer than going directly.
+//!
+//! ```text
+//! d(A, B) + d(B, C) - d(A, C) <= tolerance
+//!
+//! A ---- B ---- C     between (excess 0)
+//! A ----------- C
+//!        B            not between (excess > 0)
+//! ```
+//!
+//! Interpolation paths are the evenly spaced points from A to C; every
+//! point on one lies between its ends. They give the intermediate
+//! qualities a relationship passes through when planned to evolve from
+//! one position to another.
+
+use super::QualityPoint;
+
+impl QualityPoint {
+    /// How much longer the route from `a` to `c` through this point is
+    /// than the direct route (zero when exactly between)
+    pub fn betweenness_excess(&self, a: &Self, c: &Self) -> f64 {
+        (a.distance(self) + self.distance(c) - a.distance(c)).max(0.0)
+    }
+
+    /// Check if this point lies between `a` and `c`, within `tolerance`
+    pub fn is_between(&self, a: &Self, c: &Self, tolerance: f64) -> bool {
+        self.betweenness_excess(a, c) <= tolerance
+    }
+
+    /// Evenly spaced points from this point to `to`, both ends included
+    ///
+    /// `steps` is the number of moves; zero is treated as one.
+    pub fn interpolate(&self, to: &Self, steps: usize) -> Vec<Self> {
+        let steps = steps.max(1);
+        (0..=steps)
+            .map(|i| self.lerp(to, i as f64 / steps as f64))
+            .collect()
+    }
+
+    /// Path through a sequence of waypoints, `steps` moves per leg
+    pub fn interpolate_through(waypoints: &[Self], steps: usize) -> Vec<Self> {
+        let mut path: Vec<Self> = waypoints.first().copied().into_iter().collect();
+        for leg in waypoints.windows(2) {
+            path.extend(leg[0].interpolate(&leg[1], steps).into_iter().skip(1));
+        }
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_betweenness() {
+        let a = QualityPoint::new(0.0, 0.0, 0.5, 0.5, 0.5);
+        let c = QualityPoint::new(1.0, 1.0, 0.5, 0.5, 0.5);
+        let b = QualityPoint::new(0.3, 0.3, 0.5, 0.5, 0.5);
+        let off = QualityPoint::new(0.3, 0.7, 0.5, 0.5, 0.5);
+
+        assert!(b.is_between(&a, &c, 1e-9));
+        assert!(!off.is_between(&a, &c, 1e-9));
+        assert!(off.betweenness_excess(&a, &c) > 0.1);
+        // Ends are between themselves
+        assert!(a.is_between(&a, &c, 1e-9));
+    }
+
+    #[test]
+    fn test_interpolation_paths() {
+        let a = QualityPoint::origin();
+        let c = QualityPoint::new(1.0, 0.5, 0.0, 0.0, 0.0);
+        let path = a.interpolate(&c, 4);
+        assert_eq!(path.len(), 5);
+        assert_eq!(path[0], a);
+        assert_eq!(path[4], c);
+        assert!((path[1].strength - 0.25).abs() < 1e-12);
+        assert!(path.iter().all(|p| p.is_between(&a, &c, 1e-9)));
+
+        let b = QualityPoint::new(1.0, 1.0, 1.0, 1.0, 1.0);
+        let through = QualityPoint::interpolate_through(&[a, c, b], 2);
+        assert_eq!(through.len(), 5);
+        assert_eq!(through[2], c);
+        assert_eq!(through[4], b);
+        assert!(QualityPoint::interpolate_through(&[], 3).is_empty());
+    }
+}
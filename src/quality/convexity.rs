@@ -0,0 +1,211 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Convexity of Category Regions
+//!
+//! Gärdenfors' criterion for natural concepts: a category occupies a convex
+//! region, so anything between two members is itself a member. In quality
+//! space, "between" means on the segment joining two points.
+//!
+//! ## Check
+//!
+//! ```text
+//! for each category C:
+//!     for each relationship x not in C:
+//!         x within `tolerance` of the segment between two members of C
+//!             --> violation (x intrudes on C)
+//! ```
+//!
+//! Members that bound many intrusions are outliers: they stretch the
+//! category's region over ground that belongs to other categories.
+//! The check is quadratic in the size of each category.
+
+use super::QualityPoint;
+use crate::value_objects::{RelationshipCategory, RelationshipId};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A relationship of another category lying between two members
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConvexityViolation {
+    /// The intruding relationship
+    pub intruder: RelationshipId,
+    /// Its category
+    pub intruder_category: RelationshipCategory,
+    /// The pair of members it lies between (the closest such pair)
+    pub between: (RelationshipId, RelationshipId),
+    /// Distance from the intruder to the segment joining the pair
+    pub distance: f64,
+}
+
+/// Member of a category bounding one or more intrusions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConvexityOutlier {
+    /// The member
+    pub relationship_id: RelationshipId,
+    /// Number of (intruder, pair) combinations it takes part in
+    pub violations: usize,
+}
+
+/// Convexity report for one category
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryConvexity {
+    /// The category
+    pub category: RelationshipCategory,
+    /// Number of members checked
+    pub members: usize,
+    /// Intruders, closest first
+    pub violations: Vec<ConvexityViolation>,
+    /// Members bounding intrusions, worst first
+    pub outliers: Vec<ConvexityOutlier>,
+}
+
+impl CategoryConvexity {
+    /// Check if the category's region is convex
+    pub fn is_convex(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+/// Checks that categories occupy convex regions of quality space
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ConvexityValidator {
+    /// How close to a segment a point must be to count as between
+    pub tolerance: f64,
+}
+
+impl Default for ConvexityValidator {
+    fn default() -> Self {
+        Self { tolerance: 0.05 }
+    }
+}
+
+impl ConvexityValidator {
+    /// Create a validator with the given tolerance
+    pub fn new(tolerance: f64) -> Self {
+        Self { tolerance }
+    }
+
+    /// Check every category among the points, least convex first
+    pub fn validate(&self, points: &[(RelationshipId, RelationshipCategory, QualityPoint)]) -> Vec<CategoryConvexity> {
+        let mut categories: Vec<&RelationshipCategory> = Vec::new();
+        for (_, category, _) in points {
+            if !categories.contains(&category) {
+                categories.push(category);
+            }
+        }
+
+        let mut reports: Vec<CategoryConvexity> = categories
+            .into_iter()
+            .map(|category| self.check(category, points))
+            .collect();
+        reports.sort_by(|a, b| {
+            b.violations
+                .len()
+                .cmp(&a.violations.len())
+                .then_with(|| a.category.display_name().cmp(&b.category.display_name()))
+        });
+        reports
+    }
+
+    /// Check one category against every other point
+    pub fn check(
+        &self,
+        category: &RelationshipCategory,
+        points: &[(RelationshipId, RelationshipCategory, QualityPoint)],
+    ) -> CategoryConvexity {
+        let (members, others): (Vec<_>, Vec<_>) = points.iter().partition(|(_, c, _)| c == category);
+
+        let mut violations = Vec::new();
+        let mut counts: HashMap<RelationshipId, usize> = HashMap::new();
+        for (intruder, intruder_category, x) in &others {
+            let mut closest: Option<ConvexityViolation> = None;
+            for (i, (a_id, _, a)) in members.iter().enumerate() {
+                for (b_id, _, b) in &members[i + 1..] {
+                    let distance = x.distance_to_segment(a, b);
+                    if distance > self.tolerance {
+                        continue;
+                    }
+                    *counts.entry(*a_id).or_default() += 1;
+                    *counts.entry(*b_id).or_default() += 1;
+                    if closest.as_ref().is_none_or(|c| distance < c.distance) {
+                        closest = Some(ConvexityViolation {
+                            intruder: *intruder,
+                            intruder_category: intruder_category.clone(),
+                            between: (*a_id, *b_id),
+                            distance,
+                        });
+                    }
+                }
+            }
+            violations.extend(closest);
+        }
+        violations.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+
+        let mut outliers: Vec<ConvexityOutlier> = counts
+            .into_iter()
+            .map(|(relationship_id, violations)| ConvexityOutlier {
+                relationship_id,
+                violations,
+            })
+            .collect();
+        outliers.sort_by(|a, b| {
+            b.violations
+                .cmp(&a.violations)
+                .then_with(|| a.relationship_id.as_uuid().cmp(&b.relationship_id.as_uuid()))
+        });
+
+        CategoryConvexity {
+            category: category.clone(),
+            members: members.len(),
+            violations,
+            outliers,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(strength: f64, trust: f64) -> QualityPoint {
+        QualityPoint::new(strength, trust, 0.5, 0.5, 0.5)
+    }
+
+    #[test]
+    fn test_separated_categories_are_convex() {
+        let points = vec![
+            (RelationshipId::new(), RelationshipCategory::Friendship, point(0.1, 0.8)),
+            (RelationshipId::new(), RelationshipCategory::Friendship, point(0.2, 0.9)),
+            (RelationshipId::new(), RelationshipCategory::Employment, point(0.8, 0.2)),
+            (RelationshipId::new(), RelationshipCategory::Employment, point(0.9, 0.1)),
+        ];
+        let reports = ConvexityValidator::default().validate(&points);
+        assert_eq!(reports.len(), 2);
+        assert!(reports.iter().all(|r| r.is_convex()));
+    }
+
+    #[test]
+    fn test_intruder_and_outlier() {
+        let (near, far, other, intruder) = (
+            RelationshipId::new(),
+            RelationshipId::new(),
+            RelationshipId::new(),
+            RelationshipId::new(),
+        );
+        let points = vec![
+            (near, RelationshipCategory::Friendship, point(0.1, 0.1)),
+            (other, RelationshipCategory::Friendship, point(0.1, 0.2)),
+            // A stray friendship far away stretches the region
+            (far, RelationshipCategory::Friendship, point(0.9, 0.1)),
+            (intruder, RelationshipCategory::Employment, point(0.5, 0.1)),
+        ];
+
+        let report = ConvexityValidator::default().check(&RelationshipCategory::Friendship, &points);
+        assert!(!report.is_convex());
+        assert_eq!(report.violations[0].intruder, intruder);
+        assert_eq!(report.violations[0].between, (near, far));
+        assert_eq!(report.outliers[0].relationship_id, far);
+    }
+}
@@ -0,0 +1,147 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Quality Covariance and Mahalanobis Distance
+//!
+//! Quality dimensions are not independent: strong relationships tend to be
+//! trusted ones. Euclidean distance counts such a co-varying pair twice.
+//! Mahalanobis distance measures through the inverse covariance of the
+//! relationships in a space, so correlated dimensions count once and
+//! dimensions that barely vary weigh more when they do.
+//!
+//! ## Estimate
+//!
+//! ```text
+//! points --> sample covariance + ridge * I --> precision (inverse)
+//!
+//! d(a, b) = sqrt((a - b)' precision (a - b))
+//! ```
+//!
+//! The ridge keeps the covariance invertible when a dimension is constant
+//! across the space (every relationship equally formal, say).
+
+use super::linalg::{covariance, invert};
+use super::QualityPoint;
+use crate::{RelationshipError, RelationshipResult};
+use serde::{Deserialize, Serialize};
+
+/// Default value added to the covariance diagonal
+pub const DEFAULT_RIDGE: f64 = 1e-3;
+
+/// Covariance of quality points, with its inverse for Mahalanobis distance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QualityCovariance {
+    /// Number of points estimated from
+    pub count: usize,
+    /// Mean of the points
+    pub mean: QualityPoint,
+    /// Sample covariance in `QualityPoint::DIMENSIONS` order (without ridge)
+    pub covariance: Vec<Vec<f64>>,
+    /// Value added to the diagonal before inverting
+    pub ridge: f64,
+    /// Inverse of the regularized covariance
+    pub precision: Vec<Vec<f64>>,
+}
+
+impl QualityCovariance {
+    /// Estimate from quality points with the default ridge
+    pub fn estimate(points: &[QualityPoint]) -> RelationshipResult<Self> {
+        Self::estimate_with_ridge(points, DEFAULT_RIDGE)
+    }
+
+    /// Estimate from quality points, adding `ridge` to the diagonal
+    pub fn estimate_with_ridge(points: &[QualityPoint], ridge: f64) -> RelationshipResult<Self> {
+        if points.len() < 2 {
+            return Err(RelationshipError::InvalidConfiguration(
+                "covariance needs at least two points".to_string(),
+            ));
+        }
+        if ridge.is_nan() || ridge < 0.0 {
+            return Err(RelationshipError::InvalidConfiguration(format!(
+                "ridge must be non-negative, got {ridge}"
+            )));
+        }
+
+        let rows: Vec<Vec<f64>> = points.iter().map(|p| p.to_array().to_vec()).collect();
+        let (mean, cov) = covariance(&rows);
+        let mut regularized = cov.clone();
+        for (i, row) in regularized.iter_mut().enumerate() {
+            row[i] += ridge;
+        }
+        let precision = invert(&regularized).ok_or_else(|| {
+            RelationshipError::InvalidConfiguration(
+                "covariance is singular; use a positive ridge".to_string(),
+            )
+        })?;
+
+        Ok(Self {
+            count: points.len(),
+            mean: QualityPoint::from_array([mean[0], mean[1], mean[2], mean[3], mean[4]]),
+            covariance: cov,
+            ridge,
+            precision,
+        })
+    }
+
+    /// Mahalanobis distance between two points
+    pub fn distance(&self, a: &QualityPoint, b: &QualityPoint) -> f64 {
+        let (a, b) = (a.to_array(), b.to_array());
+        let diff: Vec<f64> = a.iter().zip(&b).map(|(x, y)| x - y).collect();
+        let squared: f64 = self
+            .precision
+            .iter()
+            .zip(&diff)
+            .map(|(row, d)| d * row.iter().zip(&diff).map(|(p, e)| p * e).sum::<f64>())
+            .sum();
+        squared.max(0.0).sqrt()
+    }
+
+    /// Correlation between two dimensions, by name
+    pub fn correlation(&self, a: &str, b: &str) -> Option<f64> {
+        let i = QualityPoint::DIMENSIONS.iter().position(|d| *d == a)?;
+        let j = QualityPoint::DIMENSIONS.iter().position(|d| *d == b)?;
+        let scale = (self.covariance[i][i] * self.covariance[j][j]).sqrt();
+        Some(if scale > 0.0 {
+            self.covariance[i][j] / scale
+        } else {
+            0.0
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_correlated_dimensions_count_once() {
+        // Strength and trust move together; formality varies independently
+        let points: Vec<QualityPoint> = (0..20)
+            .map(|i| {
+                let t = (i % 10) as f64 / 10.0;
+                let f = if i < 10 { 0.3 } else { 0.7 };
+                QualityPoint::new(t, t, f, 0.5, 0.5)
+            })
+            .collect();
+        let cov = QualityCovariance::estimate(&points).unwrap();
+        assert!(cov.correlation("strength", "trust").unwrap() > 0.99);
+
+        let origin = QualityPoint::new(0.5, 0.5, 0.5, 0.5, 0.5);
+        // Same Euclidean step: along the correlated direction vs against it
+        let along = QualityPoint::new(0.6, 0.6, 0.5, 0.5, 0.5);
+        let against = QualityPoint::new(0.6, 0.4, 0.5, 0.5, 0.5);
+        assert!((origin.distance(&along) - origin.distance(&against)).abs() < 1e-12);
+        assert!(cov.distance(&origin, &along) < cov.distance(&origin, &against));
+        assert_eq!(cov.distance(&origin, &origin), 0.0);
+    }
+
+    #[test]
+    fn test_estimate_errors() {
+        let point = QualityPoint::origin();
+        assert!(QualityCovariance::estimate(&[point]).is_err());
+        // Identical points have zero covariance: singular without a ridge
+        assert!(QualityCovariance::estimate_with_ridge(&[point, point], 0.0).is_err());
+        assert!(QualityCovariance::estimate(&[point, point]).is_ok());
+    }
+}
@@ -0,0 +1,161 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Duration Normalization
+//!
+//! The duration dimension maps how long a relationship has lasted onto
+//! [0.0, 1.0]. What counts as "permanent" depends on the domain: a support
+//! ticket is old after a week, an employment after years.
+//!
+//! ## Curves
+//!
+//! ```text
+//! Linear       d / horizon                     (capped at 1.0)
+//! Logarithmic  ln(1 + d) / ln(1 + horizon)     (capped at 1.0)
+//! ```
+//!
+//! A logarithmic curve separates short durations more finely than long
+//! ones. Both reach 1.0 at the horizon.
+
+use crate::value_objects::{RelationshipCategory, ValidityPeriod};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Shape of the duration normalization
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DurationCurve {
+    /// Proportional to elapsed time
+    #[default]
+    Linear,
+    /// Proportional to the logarithm of elapsed time
+    Logarithmic,
+}
+
+/// Curve and horizon for normalizing a duration
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DurationScale {
+    /// Shape of the curve
+    pub curve: DurationCurve,
+    /// Days at which a relationship counts as permanent
+    pub horizon_days: f64,
+}
+
+impl DurationScale {
+    /// Create a scale
+    pub fn new(curve: DurationCurve, horizon_days: f64) -> Self {
+        Self { curve, horizon_days }
+    }
+
+    /// Map elapsed days onto [0.0, 1.0]
+    pub fn normalize(&self, days: f64) -> f64 {
+        if days <= 0.0 {
+            return 0.0;
+        }
+        if self.horizon_days <= 0.0 {
+            return 1.0;
+        }
+        let value = match self.curve {
+            DurationCurve::Linear => days / self.horizon_days,
+            DurationCurve::Logarithmic => days.ln_1p() / self.horizon_days.ln_1p(),
+        };
+        value.min(1.0)
+    }
+}
+
+impl Default for DurationScale {
+    fn default() -> Self {
+        Self::new(DurationCurve::Linear, 365.0)
+    }
+}
+
+/// Duration normalization, with optional per-category scales
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DurationModel {
+    /// Scale for categories without their own
+    pub default: DurationScale,
+    /// Per-category scales
+    #[serde(with = "crate::value_objects::category_map")]
+    pub categories: HashMap<RelationshipCategory, DurationScale>,
+}
+
+impl DurationModel {
+    /// Model with a single scale for every category
+    pub fn new(default: DurationScale) -> Self {
+        Self {
+            default,
+            categories: HashMap::new(),
+        }
+    }
+
+    /// Override the scale of a category
+    pub fn with_category(mut self, category: RelationshipCategory, scale: DurationScale) -> Self {
+        self.categories.insert(category, scale);
+        self
+    }
+
+    /// Scale used for a category
+    pub fn scale(&self, category: Option<&RelationshipCategory>) -> &DurationScale {
+        category
+            .and_then(|c| self.categories.get(c))
+            .unwrap_or(&self.default)
+    }
+
+    /// Normalize a validity period as of `now`
+    ///
+    /// Ended periods count their full length; ongoing ones count time since
+    /// they started.
+    pub fn normalize(
+        &self,
+        validity: &ValidityPeriod,
+        category: Option<&RelationshipCategory>,
+        now: DateTime<Utc>,
+    ) -> f64 {
+        let end = match validity.ends_at {
+            Some(ends_at) if validity.has_ended() => ends_at,
+            _ => now,
+        };
+        let days = (end - validity.starts_at).num_seconds() as f64 / 86_400.0;
+        self.scale(category).normalize(days)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_curves() {
+        let linear = DurationScale::default();
+        assert_eq!(linear.normalize(0.0), 0.0);
+        assert!((linear.normalize(182.5) - 0.5).abs() < 1e-12);
+        assert_eq!(linear.normalize(1000.0), 1.0);
+
+        let log = DurationScale::new(DurationCurve::Logarithmic, 7.0);
+        assert!((log.normalize(7.0) - 1.0).abs() < 1e-12);
+        // One day of a week-long horizon is a third of the way there
+        assert!((log.normalize(1.0) - 2f64.ln() / 8f64.ln()).abs() < 1e-12);
+        assert!(log.normalize(1.0) > 1.0 / 7.0);
+    }
+
+    #[test]
+    fn test_per_category_model() {
+        let now = Utc::now();
+        let model = DurationModel::default().with_category(
+            RelationshipCategory::Custom("SupportTicket".to_string()),
+            DurationScale::new(DurationCurve::Linear, 7.0),
+        );
+        let validity = ValidityPeriod::ongoing(now - Duration::days(7));
+
+        let ticket = RelationshipCategory::Custom("SupportTicket".to_string());
+        assert!((model.normalize(&validity, Some(&ticket), now) - 1.0).abs() < 1e-9);
+        let employment = model.normalize(&validity, Some(&RelationshipCategory::Employment), now);
+        assert!((employment - 7.0 / 365.0).abs() < 1e-9);
+
+        let json = serde_json::to_string(&model).unwrap();
+        assert_eq!(serde_json::from_str::<DurationModel>(&json).unwrap(), model);
+    }
+}
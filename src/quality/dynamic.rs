@@ -0,0 +1,303 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Extensible Quality Points
+//!
+//! `QualityPoint` fixes the five standard dimensions. A `DynamicQualityPoint`
+//! holds any set of named dimensions, described by a `QualitySchema`, so a
+//! domain can add dimensions such as "intensity" or "risk".
+//!
+//! ## Schema
+//!
+//! ```text
+//! QualitySchema
+//!     |
+//!     +-- RelationshipDimension { id, min_value, max_value, ... }
+//!     +-- ...
+//! ```
+//!
+//! Distances normalize each dimension by its range, so dimensions with
+//! different bounds contribute comparably. A dimension missing from a point
+//! reads as the midpoint of its range.
+//!
+//! ## Migration
+//!
+//! Every `QualityPoint` converts losslessly into a `DynamicQualityPoint`
+//! over `QualitySchema::standard()`, and back via `to_quality_point`.
+
+use super::{QualityPoint, RelationshipDimension};
+use crate::{RelationshipError, RelationshipResult};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Ordered set of dimensions making up a quality space
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QualitySchema {
+    dimensions: Vec<RelationshipDimension>,
+}
+
+impl Default for QualitySchema {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+impl QualitySchema {
+    /// Schema with no dimensions
+    pub fn empty() -> Self {
+        Self {
+            dimensions: Vec::new(),
+        }
+    }
+
+    /// The five standard relationship dimensions
+    pub fn standard() -> Self {
+        Self {
+            dimensions: RelationshipDimension::all_dimensions(),
+        }
+    }
+
+    /// Add a dimension, replacing any dimension with the same id
+    pub fn with_dimension(mut self, dimension: RelationshipDimension) -> Self {
+        match self.dimensions.iter_mut().find(|d| d.id == dimension.id) {
+            Some(existing) => *existing = dimension,
+            None => self.dimensions.push(dimension),
+        }
+        self
+    }
+
+    /// Get a dimension by id
+    pub fn dimension(&self, id: &str) -> Option<&RelationshipDimension> {
+        self.dimensions.iter().find(|d| d.id == id)
+    }
+
+    /// All dimensions, in schema order
+    pub fn dimensions(&self) -> &[RelationshipDimension] {
+        &self.dimensions
+    }
+
+    /// Dimension ids, in schema order
+    pub fn ids(&self) -> impl Iterator<Item = &str> {
+        self.dimensions.iter().map(|d| d.id.as_str())
+    }
+
+    /// Number of dimensions
+    pub fn len(&self) -> usize {
+        self.dimensions.len()
+    }
+
+    /// Check if the schema has no dimensions
+    pub fn is_empty(&self) -> bool {
+        self.dimensions.is_empty()
+    }
+}
+
+impl RelationshipDimension {
+    /// Create a custom dimension
+    pub fn new(
+        id: impl Into<String>,
+        name: impl Into<String>,
+        min_value: f64,
+        max_value: f64,
+        description: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            min_value,
+            max_value,
+            description: description.into(),
+            labels: Vec::new(),
+        }
+    }
+
+    /// Midpoint of the dimension's range
+    pub fn midpoint(&self) -> f64 {
+        (self.min_value + self.max_value) / 2.0
+    }
+
+    /// Map a value onto [0.0, 1.0] by the dimension's range
+    pub fn normalize(&self, value: f64) -> f64 {
+        let span = self.max_value - self.min_value;
+        if span <= 0.0 {
+            0.0
+        } else {
+            (value - self.min_value) / span
+        }
+    }
+
+    fn contains(&self, value: f64) -> bool {
+        value >= self.min_value && value <= self.max_value
+    }
+}
+
+/// Per-dimension weights for dynamic distances (missing = 1.0)
+pub type DimensionWeights = BTreeMap<String, f64>;
+
+/// Quality point over an arbitrary set of named dimensions
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct DynamicQualityPoint {
+    values: BTreeMap<String, f64>,
+}
+
+impl DynamicQualityPoint {
+    /// Create an empty point (every dimension at its midpoint)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a point from values, checking them against a schema
+    pub fn from_values<K: Into<String>>(
+        schema: &QualitySchema,
+        values: impl IntoIterator<Item = (K, f64)>,
+    ) -> RelationshipResult<Self> {
+        let mut point = Self::new();
+        for (id, value) in values {
+            point.set(schema, id, value)?;
+        }
+        Ok(point)
+    }
+
+    /// Set a dimension, checking it against a schema
+    pub fn set(&mut self, schema: &QualitySchema, id: impl Into<String>, value: f64) -> RelationshipResult<()> {
+        let id = id.into();
+        let dimension = schema.dimension(&id).ok_or_else(|| {
+            RelationshipError::InvalidConfiguration(format!("unknown quality dimension: {}", id))
+        })?;
+        if !dimension.contains(value) {
+            return Err(RelationshipError::QualityOutOfRange(format!("{} = {}", id, value)));
+        }
+        self.values.insert(id, value);
+        Ok(())
+    }
+
+    /// Get the explicit value of a dimension
+    pub fn get(&self, id: &str) -> Option<f64> {
+        self.values.get(id).copied()
+    }
+
+    /// Get a dimension's value, defaulting to the midpoint of its range
+    pub fn value(&self, dimension: &RelationshipDimension) -> f64 {
+        self.get(&dimension.id).unwrap_or_else(|| dimension.midpoint())
+    }
+
+    /// Explicit values, ordered by dimension id
+    pub fn values(&self) -> &BTreeMap<String, f64> {
+        &self.values
+    }
+
+    /// Check that every value names a schema dimension and lies in range
+    pub fn conforms_to(&self, schema: &QualitySchema) -> bool {
+        self.values
+            .iter()
+            .all(|(id, &v)| schema.dimension(id).is_some_and(|d| d.contains(v)))
+    }
+
+    /// Range-normalized Euclidean distance over the schema's dimensions
+    pub fn distance(&self, other: &Self, schema: &QualitySchema) -> f64 {
+        self.weighted_distance(other, schema, &DimensionWeights::new())
+    }
+
+    /// Range-normalized weighted distance over the schema's dimensions
+    pub fn weighted_distance(&self, other: &Self, schema: &QualitySchema, weights: &DimensionWeights) -> f64 {
+        schema
+            .dimensions()
+            .iter()
+            .map(|d| {
+                let delta = d.normalize(self.value(d)) - d.normalize(other.value(d));
+                let weight = weights.get(&d.id).copied().unwrap_or(1.0);
+                (delta * weight).powi(2)
+            })
+            .sum::<f64>()
+            .sqrt()
+    }
+
+    /// Linear interpolation toward another point over the schema's dimensions
+    pub fn lerp(&self, other: &Self, t: f64, schema: &QualitySchema) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        let values = schema
+            .dimensions()
+            .iter()
+            .filter(|d| self.values.contains_key(&d.id) || other.values.contains_key(&d.id))
+            .map(|d| {
+                let (a, b) = (self.value(d), other.value(d));
+                (d.id.clone(), a + (b - a) * t)
+            })
+            .collect();
+        Self { values }
+    }
+
+    /// Project onto the five standard dimensions
+    ///
+    /// Standard dimensions missing from the point read as 0.5.
+    pub fn to_quality_point(&self) -> QualityPoint {
+        let value = |id: &str| self.get(id).unwrap_or(0.5);
+        QualityPoint::from_array(QualityPoint::DIMENSIONS.map(value))
+    }
+}
+
+impl From<QualityPoint> for DynamicQualityPoint {
+    fn from(point: QualityPoint) -> Self {
+        let values = QualityPoint::DIMENSIONS
+            .iter()
+            .zip(point.to_array())
+            .map(|(id, v)| (id.to_string(), v))
+            .collect();
+        Self { values }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn risk_schema() -> QualitySchema {
+        QualitySchema::standard().with_dimension(RelationshipDimension::new(
+            "risk",
+            "Risk",
+            0.0,
+            10.0,
+            "Exposure carried by the relationship",
+        ))
+    }
+
+    #[test]
+    fn test_migration_round_trip() {
+        let fixed = QualityPoint::new(0.1, 0.2, 0.3, 0.4, 0.5);
+        let dynamic = DynamicQualityPoint::from(fixed);
+        assert!(dynamic.conforms_to(&QualitySchema::standard()));
+        assert_eq!(dynamic.to_quality_point(), fixed);
+
+        // Over the standard schema, distance agrees with the fixed struct
+        let other = QualityPoint::new(0.9, 0.8, 0.7, 0.6, 0.5);
+        let d = dynamic.distance(&other.into(), &QualitySchema::standard());
+        assert!((d - fixed.distance(&other)).abs() < 1e-12);
+
+        let json = serde_json::to_string(&dynamic).unwrap();
+        assert!(json.contains("\"trust\":0.2"));
+        assert_eq!(serde_json::from_str::<DynamicQualityPoint>(&json).unwrap(), dynamic);
+    }
+
+    #[test]
+    fn test_custom_dimension() {
+        let schema = risk_schema();
+        let low = DynamicQualityPoint::from_values(&schema, [("risk", 0.0)]).unwrap();
+        let high = DynamicQualityPoint::from_values(&schema, [("risk", 10.0)]).unwrap();
+
+        // Normalized by range: a full-range gap counts as 1.0
+        assert!((low.distance(&high, &schema) - 1.0).abs() < 1e-12);
+        assert_eq!(low.lerp(&high, 0.25, &schema).get("risk"), Some(2.5));
+
+        let mut weights = DimensionWeights::new();
+        weights.insert("risk".to_string(), 2.0);
+        assert!((low.weighted_distance(&high, &schema, &weights) - 2.0).abs() < 1e-12);
+
+        assert!(matches!(
+            DynamicQualityPoint::from_values(&schema, [("risk", 11.0)]),
+            Err(RelationshipError::QualityOutOfRange(_))
+        ));
+        assert!(DynamicQualityPoint::from_values(&schema, [("intensity", 0.5)]).is_err());
+    }
+}
@@ -0,0 +1,362 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! KD-Tree Index over Quality Space
+//!
+//! Spatial index for similarity queries over the full 5-dimensional
+//! quality space. Points are split on one dimension per level, cycling
+//! through the dimensions in `QualityPoint::DIMENSIONS` order.
+//!
+//! ## Maintenance
+//!
+//! ```text
+//! insert(id, point)  --> descend and attach as a leaf
+//! remove(id)         --> tombstone the node
+//! tombstones > live  --> rebuild balanced from the live points
+//! ```
+//!
+//! Re-inserting an id moves it: the old node is tombstoned first. The
+//! index serializes as its live entries and is rebuilt balanced on load.
+
+use super::QualityPoint;
+use crate::value_objects::RelationshipId;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+const DIMS: usize = 5;
+
+#[derive(Debug, Clone)]
+struct Node {
+    id: RelationshipId,
+    point: [f64; DIMS],
+    axis: usize,
+    left: Option<usize>,
+    right: Option<usize>,
+    removed: bool,
+}
+
+/// Indexed relationship and its position in quality space
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexEntry {
+    /// Relationship
+    pub id: RelationshipId,
+    /// Position in quality space
+    pub point: QualityPoint,
+}
+
+/// KD-tree over relationship quality points
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(from = "Vec<IndexEntry>", into = "Vec<IndexEntry>")]
+pub struct QualityIndex {
+    nodes: Vec<Node>,
+    root: Option<usize>,
+    positions: HashMap<RelationshipId, usize>,
+}
+
+/// Candidate in a k-nearest search, ordered by distance (max-heap)
+struct Candidate {
+    distance: f64,
+    node: usize,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.distance == other.distance
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance.total_cmp(&other.distance)
+    }
+}
+
+impl QualityIndex {
+    /// Create an empty index
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a balanced index from a set of points
+    pub fn build(entries: impl IntoIterator<Item = (RelationshipId, QualityPoint)>) -> Self {
+        let mut points: Vec<(RelationshipId, [f64; DIMS])> = entries
+            .into_iter()
+            .map(|(id, point)| (id, point.to_array()))
+            .collect::<HashMap<_, _>>()
+            .into_iter()
+            .collect();
+        let mut index = Self::new();
+        index.root = index.build_subtree(&mut points, 0);
+        index
+    }
+
+    fn build_subtree(&mut self, points: &mut [(RelationshipId, [f64; DIMS])], depth: usize) -> Option<usize> {
+        if points.is_empty() {
+            return None;
+        }
+        let axis = depth % DIMS;
+        let median = points.len() / 2;
+        points.select_nth_unstable_by(median, |a, b| a.1[axis].total_cmp(&b.1[axis]));
+
+        let (id, point) = points[median];
+        let idx = self.nodes.len();
+        self.nodes.push(Node {
+            id,
+            point,
+            axis,
+            left: None,
+            right: None,
+            removed: false,
+        });
+        self.positions.insert(id, idx);
+
+        let (left, rest) = points.split_at_mut(median);
+        let left = self.build_subtree(left, depth + 1);
+        let right = self.build_subtree(&mut rest[1..], depth + 1);
+        self.nodes[idx].left = left;
+        self.nodes[idx].right = right;
+        Some(idx)
+    }
+
+    /// Number of indexed relationships
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Check if the index is empty
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// Check if a relationship is indexed
+    pub fn contains(&self, id: &RelationshipId) -> bool {
+        self.positions.contains_key(id)
+    }
+
+    /// Insert a relationship, moving it if it is already indexed
+    pub fn insert(&mut self, id: RelationshipId, point: &QualityPoint) {
+        self.remove(&id);
+
+        let point = point.to_array();
+        let idx = self.nodes.len();
+        let mut axis = 0;
+        let mut cursor = self.root;
+        let mut parent = None;
+        while let Some(current) = cursor {
+            let node = &self.nodes[current];
+            let go_left = point[node.axis] < node.point[node.axis];
+            parent = Some((current, go_left));
+            axis = (node.axis + 1) % DIMS;
+            cursor = if go_left { node.left } else { node.right };
+        }
+
+        self.nodes.push(Node {
+            id,
+            point,
+            axis,
+            left: None,
+            right: None,
+            removed: false,
+        });
+        match parent {
+            Some((p, true)) => self.nodes[p].left = Some(idx),
+            Some((p, false)) => self.nodes[p].right = Some(idx),
+            None => self.root = Some(idx),
+        }
+        self.positions.insert(id, idx);
+    }
+
+    /// Remove a relationship, returning whether it was indexed
+    pub fn remove(&mut self, id: &RelationshipId) -> bool {
+        let Some(idx) = self.positions.remove(id) else {
+            return false;
+        };
+        self.nodes[idx].removed = true;
+        if self.nodes.len() > 2 * self.positions.len() {
+            *self = Self::build(self.entries().into_iter().map(|e| (e.id, e.point)));
+        }
+        true
+    }
+
+    /// All live entries
+    pub fn entries(&self) -> Vec<IndexEntry> {
+        self.nodes
+            .iter()
+            .filter(|n| !n.removed)
+            .map(|n| IndexEntry {
+                id: n.id,
+                point: QualityPoint::from_array(n.point),
+            })
+            .collect()
+    }
+
+    /// Relationships within Euclidean `radius` of a point
+    pub fn within(&self, point: &QualityPoint, radius: f64) -> Vec<RelationshipId> {
+        let target = point.to_array();
+        let mut found = Vec::new();
+        let mut stack: Vec<usize> = self.root.into_iter().collect();
+        while let Some(idx) = stack.pop() {
+            let node = &self.nodes[idx];
+            if !node.removed && distance(&node.point, &target) <= radius {
+                found.push(node.id);
+            }
+            let delta = target[node.axis] - node.point[node.axis];
+            if delta - radius < 0.0 {
+                stack.extend(node.left);
+            }
+            if delta + radius >= 0.0 {
+                stack.extend(node.right);
+            }
+        }
+        found
+    }
+
+    /// The `k` relationships nearest to a point, closest first
+    pub fn nearest(&self, point: &QualityPoint, k: usize) -> Vec<(RelationshipId, f64)> {
+        if k == 0 {
+            return Vec::new();
+        }
+        let target = point.to_array();
+        let mut best: BinaryHeap<Candidate> = BinaryHeap::new();
+        let mut stack: Vec<usize> = self.root.into_iter().collect();
+        while let Some(idx) = stack.pop() {
+            let node = &self.nodes[idx];
+            let bound = if best.len() < k {
+                f64::INFINITY
+            } else {
+                best.peek().map_or(f64::INFINITY, |c| c.distance)
+            };
+
+            if !node.removed {
+                let d = distance(&node.point, &target);
+                if d < bound {
+                    best.push(Candidate { distance: d, node: idx });
+                    if best.len() > k {
+                        best.pop();
+                    }
+                }
+            }
+
+            // Visit the far side first so the near side is popped next
+            let delta = target[node.axis] - node.point[node.axis];
+            let (near, far) = if delta < 0.0 {
+                (node.left, node.right)
+            } else {
+                (node.right, node.left)
+            };
+            let bound = if best.len() < k {
+                f64::INFINITY
+            } else {
+                best.peek().map_or(f64::INFINITY, |c| c.distance)
+            };
+            if delta.abs() <= bound {
+                stack.extend(far);
+            }
+            stack.extend(near);
+        }
+
+        best.into_sorted_vec()
+            .into_iter()
+            .map(|c| (self.nodes[c.node].id, c.distance))
+            .collect()
+    }
+}
+
+impl From<Vec<IndexEntry>> for QualityIndex {
+    fn from(entries: Vec<IndexEntry>) -> Self {
+        Self::build(entries.into_iter().map(|e| (e.id, e.point)))
+    }
+}
+
+impl From<QualityIndex> for Vec<IndexEntry> {
+    fn from(index: QualityIndex) -> Self {
+        index.entries()
+    }
+}
+
+fn distance(a: &[f64; DIMS], b: &[f64; DIMS]) -> f64 {
+    a.iter()
+        .zip(b)
+        .map(|(x, y)| (x - y) * (x - y))
+        .sum::<f64>()
+        .sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scatter(n: usize) -> Vec<(RelationshipId, QualityPoint)> {
+        // Deterministic spread over the unit hypercube
+        (0..n)
+            .map(|i| {
+                let f = |k: usize| ((i * (2 * k + 3) * 7919) % 1000) as f64 / 1000.0;
+                (RelationshipId::new(), QualityPoint::new(f(0), f(1), f(2), f(3), f(4)))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_within_matches_linear_scan() {
+        let points = scatter(200);
+        let index = QualityIndex::build(points.clone());
+        let query = QualityPoint::default();
+
+        let mut expected: Vec<RelationshipId> = points
+            .iter()
+            .filter(|(_, p)| p.distance(&query) <= 0.4)
+            .map(|(id, _)| *id)
+            .collect();
+        let mut found = index.within(&query, 0.4);
+        expected.sort_by_key(|id| id.as_uuid());
+        found.sort_by_key(|id| id.as_uuid());
+        assert_eq!(found, expected);
+
+        let nearest = index.nearest(&query, 5);
+        let mut distances: Vec<f64> = points.iter().map(|(_, p)| p.distance(&query)).collect();
+        distances.sort_by(f64::total_cmp);
+        assert_eq!(nearest.len(), 5);
+        for ((_, d), expected) in nearest.iter().zip(&distances) {
+            assert!((d - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_incremental_insert_and_remove() {
+        let mut index = QualityIndex::new();
+        let points = scatter(50);
+        for (id, point) in &points {
+            index.insert(*id, point);
+        }
+        assert_eq!(index.len(), 50);
+
+        // Moving a point leaves a single live entry for it
+        let (moved, _) = points[0];
+        let corner = QualityPoint::new(1.0, 1.0, 1.0, 1.0, 1.0);
+        index.insert(moved, &corner);
+        assert_eq!(index.len(), 50);
+        assert_eq!(index.nearest(&corner, 1)[0].0, moved);
+
+        for (id, _) in &points[..40] {
+            assert!(index.remove(id));
+        }
+        assert_eq!(index.len(), 10);
+        assert!(!index.contains(&moved));
+        assert_eq!(index.within(&QualityPoint::default(), 10.0).len(), 10);
+
+        // Round-trips through serde as its live entries
+        let json = serde_json::to_string(&index).unwrap();
+        let restored: QualityIndex = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.len(), 10);
+    }
+}
@@ -0,0 +1,160 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Small dense linear algebra for quality-space statistics
+//!
+//! Quality spaces have a handful of dimensions, so plain `Vec<Vec<f64>>`
+//! matrices and textbook algorithms are enough.
+
+/// Mean and sample covariance of a set of N-dimensional rows
+///
+/// With a single row the covariance is all zeros.
+pub(crate) fn covariance(rows: &[Vec<f64>]) -> (Vec<f64>, Vec<Vec<f64>>) {
+    let dims = rows.first().map_or(0, Vec::len);
+    let n = rows.len() as f64;
+    let mut mean = vec![0.0; dims];
+    for row in rows {
+        for (m, v) in mean.iter_mut().zip(row) {
+            *m += v / n;
+        }
+    }
+
+    let mut cov = vec![vec![0.0; dims]; dims];
+    if rows.len() > 1 {
+        for row in rows {
+            for i in 0..dims {
+                for j in i..dims {
+                    cov[i][j] += (row[i] - mean[i]) * (row[j] - mean[j]) / (n - 1.0);
+                }
+            }
+        }
+        for i in 1..dims {
+            let (upper, lower) = cov.split_at_mut(i);
+            for (j, row) in upper.iter().enumerate() {
+                lower[0][j] = row[i];
+            }
+        }
+    }
+    (mean, cov)
+}
+
+/// Eigenvalues and eigenvectors of a symmetric matrix (cyclic Jacobi),
+/// largest eigenvalue first
+///
+/// Each eigenvector is returned as a row, with its largest-magnitude
+/// entry made positive so results are deterministic.
+pub(crate) fn symmetric_eigen(matrix: &[Vec<f64>]) -> Vec<(f64, Vec<f64>)> {
+    let n = matrix.len();
+    let mut a: Vec<Vec<f64>> = matrix.to_vec();
+    let mut v: Vec<Vec<f64>> = (0..n)
+        .map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.0 }).collect())
+        .collect();
+
+    for _ in 0..100 {
+        let off: f64 = (0..n)
+            .flat_map(|i| (0..n).filter(move |&j| j != i).map(move |j| (i, j)))
+            .map(|(i, j)| a[i][j] * a[i][j])
+            .sum();
+        if off < 1e-22 {
+            break;
+        }
+        for p in 0..n {
+            for q in p + 1..n {
+                if a[p][q].abs() < 1e-300 {
+                    continue;
+                }
+                let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+                for row in a.iter_mut() {
+                    let (akp, akq) = (row[p], row[q]);
+                    row[p] = c * akp - s * akq;
+                    row[q] = s * akp + c * akq;
+                }
+                let (upper, lower) = a.split_at_mut(q);
+                for (apk, aqk) in upper[p].iter_mut().zip(lower[0].iter_mut()) {
+                    let (x, y) = (*apk, *aqk);
+                    *apk = c * x - s * y;
+                    *aqk = s * x + c * y;
+                }
+                for row in v.iter_mut() {
+                    let (vkp, vkq) = (row[p], row[q]);
+                    row[p] = c * vkp - s * vkq;
+                    row[q] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+
+    let mut pairs: Vec<(f64, Vec<f64>)> = (0..n)
+        .map(|i| {
+            let mut vector: Vec<f64> = v.iter().map(|row| row[i]).collect();
+            let dominant = vector.iter().copied().fold(0.0_f64, |m, x| if x.abs() > m.abs() { x } else { m });
+            if dominant < 0.0 {
+                vector.iter_mut().for_each(|x| *x = -*x);
+            }
+            (a[i][i], vector)
+        })
+        .collect();
+    pairs.sort_by(|x, y| y.0.total_cmp(&x.0));
+    pairs
+}
+
+/// Inverse of a square matrix (Gauss-Jordan with partial pivoting)
+///
+/// Returns `None` when the matrix is singular.
+pub(crate) fn invert(matrix: &[Vec<f64>]) -> Option<Vec<Vec<f64>>> {
+    let n = matrix.len();
+    let mut a: Vec<Vec<f64>> = matrix
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let mut row = row.clone();
+            row.extend((0..n).map(|j| if i == j { 1.0 } else { 0.0 }));
+            row
+        })
+        .collect();
+
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&x, &y| a[x][col].abs().total_cmp(&a[y][col].abs()))?;
+        if a[pivot][col].abs() < 1e-12 {
+            return None;
+        }
+        a.swap(col, pivot);
+        let scale = a[col][col];
+        a[col].iter_mut().for_each(|x| *x /= scale);
+        let pivot_row = a[col].clone();
+        for (i, row) in a.iter_mut().enumerate() {
+            if i == col {
+                continue;
+            }
+            let factor = row[col];
+            for (x, p) in row.iter_mut().zip(&pivot_row) {
+                *x -= factor * p;
+            }
+        }
+    }
+    Some(a.into_iter().map(|row| row[n..].to_vec()).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_symmetric_eigen() {
+        let matrix = vec![vec![2.0, 1.0], vec![1.0, 2.0]];
+        let eigen = symmetric_eigen(&matrix);
+        assert!((eigen[0].0 - 3.0).abs() < 1e-9);
+        assert!((eigen[1].0 - 1.0).abs() < 1e-9);
+        let s = std::f64::consts::FRAC_1_SQRT_2;
+        assert!((eigen[0].1[0] - s).abs() < 1e-9 && (eigen[0].1[1] - s).abs() < 1e-9);
+
+        let inverse = invert(&matrix).unwrap();
+        assert!((inverse[0][0] - 2.0 / 3.0).abs() < 1e-9);
+        assert!((inverse[0][1] + 1.0 / 3.0).abs() < 1e-9);
+        assert!(invert(&[vec![1.0, 2.0], vec![2.0, 4.0]]).is_none());
+    }
+}
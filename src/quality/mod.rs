@@ -24,7 +24,27 @@
 //! - Clustering ("group similar relationships")
 //! - Voronoi tessellation ("define relationship neighborhoods")
 
-use crate::value_objects::{Formality, ValidityPeriod};
+mod betweenness;
+mod convexity;
+mod covariance;
+mod duration;
+mod dynamic;
+mod index;
+mod linalg;
+mod projection;
+mod prototypes;
+mod registry;
+
+pub use convexity::{CategoryConvexity, ConvexityOutlier, ConvexityValidator, ConvexityViolation};
+pub use covariance::{QualityCovariance, DEFAULT_RIDGE};
+pub use duration::{DurationCurve, DurationModel, DurationScale};
+pub use dynamic::{DimensionWeights, DynamicQualityPoint, QualitySchema};
+pub use index::{IndexEntry, QualityIndex};
+pub use projection::PcaProjection;
+pub use prototypes::{CategoryDrift, CategoryPrototypes, Prototype};
+pub use registry::QualityWeightRegistry;
+
+use crate::value_objects::{Formality, RelationshipCategory, ValidityPeriod};
 use serde::{Deserialize, Serialize};
 
 /// Quality point in the 5-dimensional relationship space
@@ -97,6 +117,20 @@ impl QualityPoint {
         (ds * ds + dt * dt + df * df + dd * dd + dr * dr).sqrt()
     }
 
+    /// Distance to the closest point of the segment from `a` to `b`
+    pub fn distance_to_segment(&self, a: &Self, b: &Self) -> f64 {
+        let (p, a_arr, b_arr) = (self.to_array(), a.to_array(), b.to_array());
+        let ab: Vec<f64> = a_arr.iter().zip(&b_arr).map(|(x, y)| y - x).collect();
+        let ap: Vec<f64> = a_arr.iter().zip(&p).map(|(x, y)| y - x).collect();
+        let len_sq: f64 = ab.iter().map(|v| v * v).sum();
+        let t = if len_sq > 0.0 {
+            (ab.iter().zip(&ap).map(|(x, y)| x * y).sum::<f64>() / len_sq).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        self.distance(&a.lerp(b, t))
+    }
+
     /// Linear interpolation toward another point
     pub fn lerp(&self, other: &Self, t: f64) -> Self {
         let t = t.clamp(0.0, 1.0);
@@ -125,6 +159,18 @@ impl QualityPoint {
         Self::new(arr[0], arr[1], arr[2], arr[3], arr[4])
     }
 
+    /// Names of the dimensions, in array order
+    pub const DIMENSIONS: [&'static str; 5] =
+        ["strength", "trust", "formality", "duration", "reciprocity"];
+
+    /// Get a dimension value by name
+    pub fn get(&self, dimension: &str) -> Option<f64> {
+        Self::DIMENSIONS
+            .iter()
+            .position(|d| *d == dimension)
+            .map(|i| self.to_array()[i])
+    }
+
     /// Convert to cim-domain-spaces Point3 (using first 3 dimensions)
     /// Useful for visualization and Voronoi tessellation
     pub fn to_point3(&self) -> cim_domain_spaces::Point3<f64> {
@@ -199,7 +245,8 @@ impl QualityWeights {
 ///
 /// This is the high-level quality type that includes both normalized
 /// QualityPoint values and the original value objects.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct RelationshipQuality {
     /// Strength of the relationship (0.0 - 1.0)
     pub strength: f64,
@@ -236,25 +283,34 @@ impl RelationshipQuality {
     }
 
     /// Convert to normalized QualityPoint
+    ///
+    /// Duration is normalized linearly with one year counting as permanent.
     pub fn to_quality_point(&self) -> QualityPoint {
-        // Normalize duration based on whether it's ongoing and how long
-        let duration_normalized = if self.duration.has_ended() {
-            // Ended relationships: normalize by how long they lasted
-            self.duration
-                .duration_days()
-                .map(|days| (days as f64 / 365.0).min(1.0))
-                .unwrap_or(0.0)
-        } else {
-            // Ongoing relationships: normalize by time since start
-            let days = (chrono::Utc::now() - self.duration.starts_at).num_days();
-            ((days as f64) / 365.0).min(1.0)
-        };
-
+        self.to_quality_point_with(&DurationModel::default(), None)
+    }
+
+    /// Convert to normalized QualityPoint, normalizing duration with the
+    /// given model and the scale of `category`
+    pub fn to_quality_point_with(
+        &self,
+        model: &DurationModel,
+        category: Option<&RelationshipCategory>,
+    ) -> QualityPoint {
+        self.to_quality_point_at(model, category, chrono::Utc::now())
+    }
+
+    /// Convert to normalized QualityPoint as of a given time
+    pub fn to_quality_point_at(
+        &self,
+        model: &DurationModel,
+        category: Option<&RelationshipCategory>,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> QualityPoint {
         QualityPoint::new(
             self.strength,
             self.trust,
             self.formality.as_f64(),
-            duration_normalized,
+            model.normalize(&self.duration, category, at),
             self.reciprocity,
         )
     }
@@ -457,6 +513,14 @@ mod tests {
         assert!((point.formality - 0.75).abs() < 0.001); // Contractual
     }
 
+    #[test]
+    fn test_quality_point_get_by_name() {
+        let point = QualityPoint::new(0.1, 0.2, 0.3, 0.4, 0.5);
+        assert_eq!(point.get("trust"), Some(0.2));
+        assert_eq!(point.get("reciprocity"), Some(0.5));
+        assert_eq!(point.get("risk"), None);
+    }
+
     #[test]
     fn test_quality_clamping() {
         let point = QualityPoint::new(2.0, -1.0, 0.5, 0.5, 0.5);
@@ -0,0 +1,185 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Projection of Quality Space to 3-D
+//!
+//! `QualityPoint::to_point3` keeps strength, trust and formality and drops
+//! the rest. A `PcaProjection` instead keeps the three directions along
+//! which the relationships of a space actually vary (principal component
+//! analysis), and reports how much of the variance they retain.
+//!
+//! ## Fit
+//!
+//! ```text
+//! points --> mean, covariance --> eigenvectors, largest eigenvalue first
+//!                                      |
+//!                     project(p) = top-3 components . (p - mean)
+//! ```
+//!
+//! Works on any number of dimensions: the standard five, or the dimensions
+//! of a `QualitySchema`.
+
+use super::linalg::{covariance, symmetric_eigen};
+use super::{DynamicQualityPoint, QualityPoint, QualitySchema};
+use crate::{RelationshipError, RelationshipResult};
+use cim_domain_spaces::Point3;
+use serde::{Deserialize, Serialize};
+
+/// Principal component projection into three dimensions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PcaProjection {
+    /// Names of the input dimensions
+    pub dimensions: Vec<String>,
+    /// Mean of the fitted points
+    pub mean: Vec<f64>,
+    /// Up to three principal axes, each a unit vector over the inputs
+    pub components: Vec<Vec<f64>>,
+    /// Variance along each retained axis
+    pub explained_variance: Vec<f64>,
+    /// Total variance of the fitted points
+    pub total_variance: f64,
+}
+
+impl PcaProjection {
+    /// Fit a projection to rows of values over the named dimensions
+    pub fn fit(dimensions: Vec<String>, rows: &[Vec<f64>]) -> RelationshipResult<Self> {
+        if rows.is_empty() {
+            return Err(RelationshipError::InvalidConfiguration(
+                "projection needs at least one point".to_string(),
+            ));
+        }
+        if let Some(row) = rows.iter().find(|r| r.len() != dimensions.len()) {
+            return Err(RelationshipError::InvalidConfiguration(format!(
+                "expected {} values per point, got {}",
+                dimensions.len(),
+                row.len()
+            )));
+        }
+
+        let (mean, cov) = covariance(rows);
+        let total_variance = (0..dimensions.len()).map(|i| cov[i][i]).sum();
+        let (explained_variance, components) = symmetric_eigen(&cov)
+            .into_iter()
+            .take(3)
+            .map(|(value, vector)| (value.max(0.0), vector))
+            .unzip();
+
+        Ok(Self {
+            dimensions,
+            mean,
+            components,
+            explained_variance,
+            total_variance,
+        })
+    }
+
+    /// Fit a projection to standard quality points
+    pub fn fit_quality(points: &[QualityPoint]) -> RelationshipResult<Self> {
+        let rows: Vec<Vec<f64>> = points.iter().map(|p| p.to_array().to_vec()).collect();
+        let dimensions = QualityPoint::DIMENSIONS.iter().map(|d| d.to_string()).collect();
+        Self::fit(dimensions, &rows)
+    }
+
+    /// Fit a projection to dynamic quality points over a schema
+    pub fn fit_dynamic(points: &[DynamicQualityPoint], schema: &QualitySchema) -> RelationshipResult<Self> {
+        let rows: Vec<Vec<f64>> = points.iter().map(|p| Self::row(p, schema)).collect();
+        Self::fit(schema.ids().map(str::to_string).collect(), &rows)
+    }
+
+    fn row(point: &DynamicQualityPoint, schema: &QualitySchema) -> Vec<f64> {
+        schema.dimensions().iter().map(|d| point.value(d)).collect()
+    }
+
+    /// Project values over the fitted dimensions
+    ///
+    /// Axes beyond the number of input dimensions project to zero.
+    pub fn project(&self, values: &[f64]) -> Point3<f64> {
+        let coordinate = |axis: usize| {
+            self.components.get(axis).map_or(0.0, |component| {
+                component
+                    .iter()
+                    .zip(values.iter().zip(&self.mean))
+                    .map(|(c, (v, m))| c * (v - m))
+                    .sum()
+            })
+        };
+        Point3::new(coordinate(0), coordinate(1), coordinate(2))
+    }
+
+    /// Project a standard quality point
+    pub fn project_quality(&self, point: &QualityPoint) -> Point3<f64> {
+        self.project(&point.to_array())
+    }
+
+    /// Project a dynamic quality point over a schema
+    pub fn project_dynamic(&self, point: &DynamicQualityPoint, schema: &QualitySchema) -> Point3<f64> {
+        self.project(&Self::row(point, schema))
+    }
+
+    /// Fraction of the total variance along each retained axis
+    pub fn explained_variance_ratio(&self) -> Vec<f64> {
+        self.explained_variance
+            .iter()
+            .map(|v| {
+                if self.total_variance > 0.0 {
+                    v / self.total_variance
+                } else {
+                    0.0
+                }
+            })
+            .collect()
+    }
+
+    /// Fraction of the total variance the projection keeps
+    pub fn retained_variance(&self) -> f64 {
+        self.explained_variance_ratio().iter().sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pca_finds_varying_dimensions() {
+        // Points vary only along duration and reciprocity, which
+        // `to_point3` would drop
+        let points: Vec<QualityPoint> = (0..10)
+            .map(|i| {
+                let t = i as f64 / 10.0;
+                QualityPoint::new(0.5, 0.5, 0.5, t, 1.0 - t * 0.5)
+            })
+            .collect();
+
+        let pca = PcaProjection::fit_quality(&points).unwrap();
+        assert!((pca.retained_variance() - 1.0).abs() < 1e-9);
+        assert!(pca.explained_variance_ratio()[0] > 0.99);
+
+        // Structure survives: the spread of the first axis matches the data
+        let first = pca.project_quality(&points[0]);
+        let last = pca.project_quality(&points[9]);
+        let spread = (last.x - first.x).abs();
+        assert!((spread - points[0].distance(&points[9])).abs() < 1e-9);
+        assert!(points.iter().all(|p| p.to_point3().x == 0.5));
+    }
+
+    #[test]
+    fn test_pca_dynamic_and_errors() {
+        let schema = QualitySchema::empty()
+            .with_dimension(crate::quality::RelationshipDimension::new("risk", "Risk", 0.0, 10.0, "Exposure"));
+        let points: Vec<DynamicQualityPoint> = [1.0, 5.0, 9.0]
+            .iter()
+            .map(|r| DynamicQualityPoint::from_values(&schema, [("risk", *r)]).unwrap())
+            .collect();
+
+        // One input dimension: one meaningful axis, the others project to zero
+        let pca = PcaProjection::fit_dynamic(&points, &schema).unwrap();
+        let projected = pca.project_dynamic(&points[2], &schema);
+        assert!((projected.x - 4.0).abs() < 1e-9);
+        assert_eq!((projected.y, projected.z), (0.0, 0.0));
+
+        assert!(PcaProjection::fit_quality(&[]).is_err());
+        assert!(PcaProjection::fit(vec!["a".to_string()], &[vec![1.0, 2.0]]).is_err());
+    }
+}
@@ -0,0 +1,265 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Category Prototypes
+//!
+//! In a conceptual space, a category is represented by its prototype: the
+//! most typical point of the category. Any point can then be classified by
+//! the prototype it lies closest to.
+//!
+//! ## Maintenance
+//!
+//! ```text
+//! standard()          --> hand-set defaults (employment, friendship, membership)
+//! learn(points)       --> centroid of each category's observed points
+//! observe(cat, point) --> running mean, one point at a time
+//! ```
+//!
+//! Learned prototypes replace the defaults of the categories they cover.
+//!
+//! ## Drift
+//!
+//! When many relationships of one category sit closer to another
+//! category's prototype, the categories may be mislabeled or the
+//! prototypes may be out of date. `drift` reports those pairs.
+
+use super::QualityPoint;
+use crate::value_objects::RelationshipCategory;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Prototype of a category
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Prototype {
+    /// Most typical point of the category
+    pub point: QualityPoint,
+    /// Points the prototype was learned from (0 = hand-set)
+    pub support: usize,
+}
+
+/// Relationships of one category that sit nearer another prototype
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryDrift {
+    /// Category the relationships carry
+    pub category: RelationshipCategory,
+    /// Category whose prototype they are nearer to
+    pub nearer_to: RelationshipCategory,
+    /// Number of relationships nearer to the other prototype
+    pub count: usize,
+    /// Number of relationships of the category
+    pub total: usize,
+}
+
+impl CategoryDrift {
+    /// Fraction of the category nearer to the other prototype
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.count as f64 / self.total as f64
+        }
+    }
+}
+
+/// Prototype point per relationship category
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CategoryPrototypes {
+    #[serde(with = "crate::value_objects::category_map")]
+    prototypes: HashMap<RelationshipCategory, Prototype>,
+}
+
+impl CategoryPrototypes {
+    /// Create an empty set of prototypes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The hand-set default prototypes
+    pub fn standard() -> Self {
+        Self::new()
+            .with_prototype(RelationshipCategory::Employment, QualityPoint::default_for_employment())
+            .with_prototype(RelationshipCategory::Friendship, QualityPoint::default_for_friendship())
+            .with_prototype(RelationshipCategory::Membership, QualityPoint::default_for_membership())
+    }
+
+    /// Set a category's prototype by hand
+    pub fn with_prototype(mut self, category: RelationshipCategory, point: QualityPoint) -> Self {
+        self.prototypes.insert(category, Prototype { point, support: 0 });
+        self
+    }
+
+    /// Learn prototypes as category centroids, on top of the existing ones
+    pub fn learn(mut self, points: impl IntoIterator<Item = (RelationshipCategory, QualityPoint)>) -> Self {
+        let mut sums: HashMap<RelationshipCategory, ([f64; 5], usize)> = HashMap::new();
+        for (category, point) in points {
+            let (sum, count) = sums.entry(category).or_insert(([0.0; 5], 0));
+            for (s, v) in sum.iter_mut().zip(point.to_array()) {
+                *s += v;
+            }
+            *count += 1;
+        }
+        for (category, (sum, count)) in sums {
+            let point = QualityPoint::from_array(sum.map(|s| s / count as f64));
+            self.prototypes.insert(category, Prototype { point, support: count });
+        }
+        self
+    }
+
+    /// Fold one more observed point into its category's prototype
+    ///
+    /// A hand-set prototype is replaced by the first observation.
+    pub fn observe(&mut self, category: RelationshipCategory, point: &QualityPoint) {
+        let prototype = self.prototypes.entry(category).or_insert(Prototype {
+            point: *point,
+            support: 0,
+        });
+        if prototype.support == 0 {
+            prototype.point = *point;
+        } else {
+            let t = 1.0 / (prototype.support + 1) as f64;
+            prototype.point = prototype.point.lerp(point, t);
+        }
+        prototype.support += 1;
+    }
+
+    /// Get a category's prototype
+    pub fn prototype(&self, category: &RelationshipCategory) -> Option<&Prototype> {
+        self.prototypes.get(category)
+    }
+
+    /// All categories with a prototype
+    pub fn categories(&self) -> impl Iterator<Item = &RelationshipCategory> {
+        self.prototypes.keys()
+    }
+
+    /// Number of prototypes
+    pub fn len(&self) -> usize {
+        self.prototypes.len()
+    }
+
+    /// Check if there are no prototypes
+    pub fn is_empty(&self) -> bool {
+        self.prototypes.is_empty()
+    }
+
+    /// Classify a point by its nearest prototype
+    ///
+    /// Returns None only when there are no prototypes. Ties go to the
+    /// category whose display name sorts first.
+    pub fn classify(&self, point: &QualityPoint) -> Option<RelationshipCategory> {
+        self.prototypes
+            .iter()
+            .min_by(|a, b| {
+                a.1.point
+                    .distance(point)
+                    .total_cmp(&b.1.point.distance(point))
+                    .then_with(|| a.0.display_name().cmp(&b.0.display_name()))
+            })
+            .map(|(category, _)| category.clone())
+    }
+
+    /// Find categories whose relationships sit nearer another prototype
+    ///
+    /// Reports every (category, other) pair where at least `min_fraction`
+    /// of the category's points classify as `other`, most drifted first.
+    pub fn drift(
+        &self,
+        points: impl IntoIterator<Item = (RelationshipCategory, QualityPoint)>,
+        min_fraction: f64,
+    ) -> Vec<CategoryDrift> {
+        let mut totals: HashMap<RelationshipCategory, usize> = HashMap::new();
+        let mut strays: HashMap<(RelationshipCategory, RelationshipCategory), usize> = HashMap::new();
+        for (category, point) in points {
+            *totals.entry(category.clone()).or_default() += 1;
+            if let Some(nearest) = self.classify(&point) {
+                if nearest != category {
+                    *strays.entry((category, nearest)).or_default() += 1;
+                }
+            }
+        }
+
+        let mut drift: Vec<CategoryDrift> = strays
+            .into_iter()
+            .map(|((category, nearer_to), count)| CategoryDrift {
+                total: totals[&category],
+                category,
+                nearer_to,
+                count,
+            })
+            .filter(|d| d.fraction() >= min_fraction)
+            .collect();
+        drift.sort_by(|a, b| {
+            b.fraction()
+                .total_cmp(&a.fraction())
+                .then_with(|| a.category.display_name().cmp(&b.category.display_name()))
+        });
+        drift
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_by_nearest_prototype() {
+        let prototypes = CategoryPrototypes::standard();
+        assert_eq!(
+            prototypes.classify(&QualityPoint::default_for_friendship()),
+            Some(RelationshipCategory::Friendship)
+        );
+        assert_eq!(
+            prototypes.classify(&QualityPoint::new(0.7, 0.6, 0.8, 0.7, 0.6)),
+            Some(RelationshipCategory::Employment)
+        );
+        assert_eq!(CategoryPrototypes::new().classify(&QualityPoint::default()), None);
+
+        // Learned prototypes cover new categories
+        let corner = QualityPoint::origin();
+        let learned = prototypes.learn([(RelationshipCategory::References, corner)]);
+        assert_eq!(learned.classify(&corner), Some(RelationshipCategory::References));
+        assert_eq!(learned.prototype(&RelationshipCategory::References).unwrap().support, 1);
+
+        // Custom categories survive a JSON round trip
+        let custom = RelationshipCategory::Custom("Sponsorship".to_string());
+        let json = serde_json::to_string(&learned.with_prototype(custom.clone(), corner)).unwrap();
+        let restored: CategoryPrototypes = serde_json::from_str(&json).unwrap();
+        assert!(restored.prototype(&custom).is_some());
+    }
+
+    #[test]
+    fn test_observe_running_mean() {
+        let mut prototypes = CategoryPrototypes::standard();
+        prototypes.observe(RelationshipCategory::Friendship, &QualityPoint::origin());
+        prototypes.observe(RelationshipCategory::Friendship, &QualityPoint::new(1.0, 1.0, 1.0, 1.0, 1.0));
+
+        let prototype = prototypes.prototype(&RelationshipCategory::Friendship).unwrap();
+        assert_eq!(prototype.support, 2);
+        assert!((prototype.point.strength - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_drift_detection() {
+        let prototypes = CategoryPrototypes::standard();
+        let friendly = QualityPoint::default_for_friendship();
+        let formal = QualityPoint::default_for_employment();
+
+        // Three of four "employment" relationships look like friendships
+        let points = vec![
+            (RelationshipCategory::Employment, friendly),
+            (RelationshipCategory::Employment, friendly),
+            (RelationshipCategory::Employment, friendly),
+            (RelationshipCategory::Employment, formal),
+            (RelationshipCategory::Friendship, friendly),
+        ];
+
+        let drift = prototypes.drift(points.clone(), 0.5);
+        assert_eq!(drift.len(), 1);
+        assert_eq!(drift[0].category, RelationshipCategory::Employment);
+        assert_eq!(drift[0].nearer_to, RelationshipCategory::Friendship);
+        assert_eq!((drift[0].count, drift[0].total), (3, 4));
+
+        assert!(prototypes.drift(points, 0.8).is_empty());
+    }
+}
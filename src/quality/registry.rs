@@ -0,0 +1,122 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Per-Category Quality Weights
+//!
+//! Which dimensions matter when comparing two relationships depends on
+//! their category: trust and reciprocity dominate a friendship, formality
+//! and duration an employment. The registry binds weights to categories
+//! so similarity queries can pick them up without the caller choosing.
+//!
+//! ## Standard Bindings
+//!
+//! ```text
+//! business_focused  Employment, Membership, Ownership, Management
+//! social_focused    Friendship, ProfessionalContact
+//! trust_focused     Mentorship
+//! default (1.0)     everything else
+//! ```
+
+use super::QualityWeights;
+use crate::value_objects::RelationshipCategory;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Quality weights bound to relationship categories
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QualityWeightRegistry {
+    /// Weights for categories without a binding
+    pub default: QualityWeights,
+    /// Per-category weights
+    #[serde(with = "crate::value_objects::category_map")]
+    pub categories: HashMap<RelationshipCategory, QualityWeights>,
+}
+
+impl Default for QualityWeightRegistry {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+impl QualityWeightRegistry {
+    /// Registry with no bindings; every category uses `default`
+    pub fn uniform(default: QualityWeights) -> Self {
+        Self {
+            default,
+            categories: HashMap::new(),
+        }
+    }
+
+    /// Registry with the standard bindings
+    pub fn standard() -> Self {
+        let business = [
+            RelationshipCategory::Employment,
+            RelationshipCategory::Membership,
+            RelationshipCategory::Ownership,
+            RelationshipCategory::Management,
+        ]
+        .map(|c| (c, QualityWeights::business_focused()));
+        let social = [
+            RelationshipCategory::Friendship,
+            RelationshipCategory::ProfessionalContact,
+        ]
+        .map(|c| (c, QualityWeights::social_focused()));
+        let trust = [(RelationshipCategory::Mentorship, QualityWeights::trust_focused())];
+
+        Self {
+            default: QualityWeights::default(),
+            categories: business.into_iter().chain(social).chain(trust).collect(),
+        }
+    }
+
+    /// Bind weights to a category, replacing any existing binding
+    pub fn with_weights(mut self, category: RelationshipCategory, weights: QualityWeights) -> Self {
+        self.categories.insert(category, weights);
+        self
+    }
+
+    /// Weights for a category
+    pub fn weights_for(&self, category: &RelationshipCategory) -> &QualityWeights {
+        self.categories.get(category).unwrap_or(&self.default)
+    }
+}
+
+impl QualityWeights {
+    /// Smallest weight across the dimensions
+    pub fn min_weight(&self) -> f64 {
+        [
+            self.strength,
+            self.trust,
+            self.formality,
+            self.duration,
+            self.reciprocity,
+        ]
+        .into_iter()
+        .fold(f64::INFINITY, f64::min)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_standard_bindings_and_overrides() {
+        let registry = QualityWeightRegistry::standard();
+        assert_eq!(registry.weights_for(&RelationshipCategory::Friendship).reciprocity, 2.0);
+        assert_eq!(registry.weights_for(&RelationshipCategory::Employment).formality, 2.0);
+        assert_eq!(registry.weights_for(&RelationshipCategory::References).trust, 1.0);
+
+        let registry = registry.with_weights(
+            RelationshipCategory::References,
+            QualityWeights::trust_focused(),
+        );
+        assert_eq!(registry.weights_for(&RelationshipCategory::References).trust, 2.0);
+
+        let json = serde_json::to_string(&registry).unwrap();
+        let restored: QualityWeightRegistry = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.weights_for(&RelationshipCategory::References).trust, 2.0);
+    }
+}
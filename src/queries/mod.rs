@@ -0,0 +1,372 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Queries for the Relationship Domain
+//!
+//! Queries read relationship state without changing it. They are the
+//! payloads of `relationship.queries.{query_type}` requests.
+//!
+//! ## Query Families
+//!
+//! - **Analytics**: Graph measures over a RelationshipSpace (centrality,
+//!   communities, ego networks, k-cores, ...)
+//! - **Tags**: Relationships by tag, and tag usage
+//! - **System**: Operational metrics about the domain itself
+//!   (`relationship.queries.system.*`)
+
+use crate::aggregates::RelationshipSpace;
+use crate::graph::{
+    self, CentralityMeasure, CommunityAssignment, CoreDecomposition, EgoFilter, EgoNetwork,
+    EntityScore, RelationshipGraph, SiteEntry,
+};
+use crate::infrastructure::{DomainMetrics, MetricsCollector};
+use crate::value_objects::{EntityRef, RelationshipCategory, RelationshipId, TagMatch, TagStats};
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// Analytics Queries
+// ============================================================================
+
+/// Graph analytics queries over a RelationshipSpace
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum AnalyticsQuery {
+    Centrality(CentralityQuery),
+    Communities(CommunityQuery),
+    EgoNetwork(EgoNetworkQuery),
+    CoreNumbers(CoreQuery),
+    EntitiesAt(EntitiesAtQuery),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CentralityQuery {
+    pub measure: CentralityMeasure,
+    /// Restrict the graph to these categories (empty = all)
+    #[serde(default)]
+    pub categories: Vec<RelationshipCategory>,
+    /// Return only the top N entities
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CommunityQuery {
+    /// Restrict the graph to these categories (empty = all)
+    #[serde(default)]
+    pub categories: Vec<RelationshipCategory>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CoreQuery {
+    /// Restrict the graph to these categories (empty = all)
+    #[serde(default)]
+    pub categories: Vec<RelationshipCategory>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EgoNetworkQuery {
+    pub center: EntityRef,
+    pub radius: usize,
+    #[serde(default)]
+    pub filter: EgoFilter,
+}
+
+/// Everything contained in a site, through Contains and PartOf edges
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntitiesAtQuery {
+    pub site: EntityRef,
+    /// Containment steps to follow (`None` = unlimited)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_depth: Option<usize>,
+}
+
+/// Results of analytics queries
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum AnalyticsResult {
+    Centrality(Vec<EntityScore>),
+    Communities(CommunityAssignment),
+    EgoNetwork(EgoNetwork),
+    CoreNumbers(CoreDecomposition),
+    EntitiesAt(Vec<SiteEntry>),
+}
+
+impl AnalyticsQuery {
+    /// Get the query type name used in NATS subjects
+    pub fn query_type(&self) -> &'static str {
+        match self {
+            AnalyticsQuery::Centrality(_) => "analytics.centrality",
+            AnalyticsQuery::Communities(_) => "analytics.communities",
+            AnalyticsQuery::EgoNetwork(_) => "analytics.ego_network",
+            AnalyticsQuery::CoreNumbers(_) => "analytics.core_numbers",
+            AnalyticsQuery::EntitiesAt(_) => "analytics.entities_at",
+        }
+    }
+
+    /// Execute the query against the active relationships of a space
+    pub fn execute(&self, space: &RelationshipSpace) -> AnalyticsResult {
+        match self {
+            AnalyticsQuery::Centrality(q) => {
+                let graph = active_graph(space, &q.categories);
+                let mut scores = graph::centrality(&graph, q.measure);
+                if let Some(limit) = q.limit {
+                    scores.truncate(limit);
+                }
+                AnalyticsResult::Centrality(scores)
+            }
+            AnalyticsQuery::Communities(q) => {
+                let graph = active_graph(space, &q.categories);
+                AnalyticsResult::Communities(graph::label_propagation(&graph))
+            }
+            AnalyticsQuery::EgoNetwork(q) => {
+                AnalyticsResult::EgoNetwork(space.ego_network(&q.center, q.radius, &q.filter))
+            }
+            AnalyticsQuery::CoreNumbers(q) => {
+                let graph = active_graph(space, &q.categories);
+                AnalyticsResult::CoreNumbers(graph::k_core_decomposition(&graph))
+            }
+            AnalyticsQuery::EntitiesAt(q) => {
+                AnalyticsResult::EntitiesAt(graph::entities_at(space, &q.site, q.max_depth))
+            }
+        }
+    }
+}
+
+/// Graph of active edges, optionally restricted to some categories
+fn active_graph(space: &RelationshipSpace, categories: &[RelationshipCategory]) -> RelationshipGraph {
+    RelationshipGraph::from_space_filtered(space, |e| {
+        e.is_active() && (categories.is_empty() || categories.contains(&e.category))
+    })
+}
+
+// ============================================================================
+// Tag Queries
+// ============================================================================
+
+/// Queries over relationship tags
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum TagQuery {
+    Tagged(TaggedQuery),
+    Statistics,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaggedQuery {
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub mode: TagMatch,
+    /// Only active relationships
+    #[serde(default)]
+    pub active_only: bool,
+}
+
+/// Results of tag queries
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum TagResult {
+    Tagged {
+        edges: Vec<RelationshipId>,
+        hyperedges: Vec<RelationshipId>,
+    },
+    Statistics(Vec<TagStats>),
+}
+
+impl TagQuery {
+    /// Get the query type name used in NATS subjects
+    pub fn query_type(&self) -> &'static str {
+        match self {
+            TagQuery::Tagged(_) => "tags.tagged",
+            TagQuery::Statistics => "tags.statistics",
+        }
+    }
+
+    /// Execute the query against a space
+    pub fn execute(&self, space: &RelationshipSpace) -> TagResult {
+        match self {
+            TagQuery::Tagged(q) => {
+                let mut edges: Vec<_> = space
+                    .edges_tagged(&q.tags, q.mode)
+                    .into_iter()
+                    .filter(|e| !q.active_only || e.is_active())
+                    .map(|e| e.id)
+                    .collect();
+                let mut hyperedges: Vec<_> = space
+                    .hyperedges_tagged(&q.tags, q.mode)
+                    .into_iter()
+                    .filter(|h| !q.active_only || h.is_active())
+                    .map(|h| h.id)
+                    .collect();
+                edges.sort_by_key(|id| id.as_uuid());
+                hyperedges.sort_by_key(|id| id.as_uuid());
+                TagResult::Tagged { edges, hyperedges }
+            }
+            TagQuery::Statistics => TagResult::Statistics(space.tag_statistics()),
+        }
+    }
+}
+
+// ============================================================================
+// System Queries
+// ============================================================================
+
+/// Operational queries about the relationship domain itself
+///
+/// Answered from a MetricsCollector as well as a space, so they are kept
+/// apart from RelationshipQuery.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SystemQuery {
+    Metrics(MetricsQuery),
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MetricsQuery {
+    /// Return the full projection lag history instead of the latest sample
+    #[serde(default)]
+    pub include_lag_history: bool,
+}
+
+/// Results of system queries
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SystemResult {
+    Metrics(DomainMetrics),
+}
+
+impl SystemQuery {
+    /// Get the query type name used in NATS subjects
+    pub fn query_type(&self) -> &'static str {
+        match self {
+            SystemQuery::Metrics(_) => "system.metrics",
+        }
+    }
+
+    /// Execute the query
+    pub fn execute(&self, space: &RelationshipSpace, collector: &MetricsCollector) -> SystemResult {
+        match self {
+            SystemQuery::Metrics(q) => {
+                SystemResult::Metrics(collector.snapshot(space, q.include_lag_history))
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Unified Relationship Query
+// ============================================================================
+
+/// Unified query type for the relationship domain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum RelationshipQuery {
+    Analytics(AnalyticsQuery),
+    Tags(TagQuery),
+}
+
+/// Unified query result type for the relationship domain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum QueryResult {
+    Analytics(AnalyticsResult),
+    Tags(TagResult),
+}
+
+impl RelationshipQuery {
+    /// Get the query type name used in NATS subjects
+    pub fn query_type(&self) -> &'static str {
+        match self {
+            RelationshipQuery::Analytics(q) => q.query_type(),
+            RelationshipQuery::Tags(q) => q.query_type(),
+        }
+    }
+
+    /// Execute the query against a space
+    pub fn execute(&self, space: &RelationshipSpace) -> QueryResult {
+        match self {
+            RelationshipQuery::Analytics(q) => QueryResult::Analytics(q.execute(space)),
+            RelationshipQuery::Tags(q) => QueryResult::Tags(q.execute(space)),
+        }
+    }
+}
+
+impl From<AnalyticsQuery> for RelationshipQuery {
+    fn from(query: AnalyticsQuery) -> Self {
+        RelationshipQuery::Analytics(query)
+    }
+}
+
+impl From<TagQuery> for RelationshipQuery {
+    fn from(query: TagQuery) -> Self {
+        RelationshipQuery::Tags(query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregates::EdgeConcept;
+    use cim_domain_spaces::TopologicalSpaceId;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_centrality_query_limit() {
+        let mut space = RelationshipSpace::new("Org Chart", TopologicalSpaceId::new());
+        let manager = EntityRef::person(Uuid::now_v7());
+
+        for _ in 0..4 {
+            let mut edge = EdgeConcept::new(
+                "Manages",
+                manager.clone(),
+                EntityRef::person(Uuid::now_v7()),
+                RelationshipCategory::Management,
+            );
+            edge.activate().unwrap();
+            space.add_edge(edge).unwrap();
+        }
+
+        let query = AnalyticsQuery::Centrality(CentralityQuery {
+            measure: CentralityMeasure::Degree,
+            categories: vec![RelationshipCategory::Management],
+            limit: Some(2),
+        });
+
+        let AnalyticsResult::Centrality(scores) = query.execute(&space) else {
+            panic!("expected centrality result");
+        };
+        assert_eq!(scores.len(), 2);
+        assert_eq!(scores[0].entity, manager);
+    }
+
+    #[test]
+    fn test_tag_queries() {
+        let mut space = RelationshipSpace::new("Vendors", TopologicalSpaceId::new());
+        let acme = EntityRef::organization(Uuid::now_v7());
+        let vendor = |tags: &[&str]| {
+            let edge = EdgeConcept::new("Supplies", EntityRef::organization(Uuid::now_v7()), acme.clone(), RelationshipCategory::Custom("Supplier".to_string()));
+            tags.iter().fold(edge, |edge, tag| edge.with_tag(tag))
+        };
+        let mut critical = vendor(&["Critical ", "eu"]);
+        critical.activate().unwrap();
+        let critical_id = critical.id;
+        space.add_edge(critical).unwrap();
+        space.add_edge(vendor(&["eu"])).unwrap();
+        space.add_edge(vendor(&[])).unwrap();
+
+        let tagged = |tags: &[&str], mode| {
+            let query = TagQuery::Tagged(TaggedQuery {
+                tags: tags.iter().map(|t| t.to_string()).collect(),
+                mode,
+                active_only: false,
+            });
+            match RelationshipQuery::from(query).execute(&space) {
+                QueryResult::Tags(TagResult::Tagged { edges, .. }) => edges,
+                other => panic!("unexpected result {:?}", other),
+            }
+        };
+        assert_eq!(tagged(&["EU"], TagMatch::Any).len(), 2);
+        assert_eq!(tagged(&["eu", "critical"], TagMatch::All), vec![critical_id]);
+        assert!(tagged(&["apac"], TagMatch::Any).is_empty());
+        assert!(tagged(&[" "], TagMatch::All).is_empty());
+
+        let TagResult::Statistics(stats) = TagQuery::Statistics.execute(&space) else {
+            panic!("expected tag statistics");
+        };
+        assert_eq!(stats.len(), 2);
+        assert_eq!((stats[0].tag.as_str(), stats[0].edges, stats[0].active), ("eu", 2, 1));
+        assert_eq!(stats[1].tag, "critical");
+    }
+}
@@ -0,0 +1,465 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! JSON Schemas for the Relationship Domain (`schema` feature)
+//!
+//! Every event and command variant has a JSON Schema (draft 7) derived with
+//! schemars, so domains not written in Rust can validate the payloads they
+//! send and receive. A schema describes the message exactly as it appears
+//! on its subject, enum envelope included:
+//!
+//! ```text
+//! relationship.events.edge_created     {"Edge": {"EdgeCreated": {...}}}
+//! relationship.commands.create_edge    {"Edge": {"CreateEdge": {...}}}
+//! ```
+//!
+//! Schemas are served over request/reply:
+//!
+//! ```text
+//! relationship.schema.events.{event_type}       --> schema of that event
+//! relationship.schema.commands.{command_type}   --> schema of that command
+//! relationship.schema.catalog                   --> bundle of every schema
+//! ```
+//!
+//! The bundle keeps each schema under `definitions`, keyed
+//! `events/{event_type}` or `commands/{command_type}`, so consumers can
+//! embed it in their own contracts and `$ref` the messages they use.
+//!
+//! `MessageIdentity`, `ConceptId` and `KnowledgeLevel` belong to upstream
+//! crates; their schemas here are descriptive and do not constrain values.
+
+use crate::nats::{RelationshipSubjects, Transport};
+use crate::{commands, events};
+use crate::{RelationshipError, RelationshipResult};
+use bytes::Bytes;
+use futures::StreamExt;
+use schemars::gen::SchemaGenerator;
+use schemars::schema::{InstanceType, Metadata, Schema, SchemaObject};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+/// Kind of message a schema describes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageKind {
+    /// A relationship event
+    Event,
+    /// A relationship command
+    Command,
+}
+
+impl MessageKind {
+    /// Subject token of the kind: `events` or `commands`
+    pub fn token(&self) -> &'static str {
+        match self {
+            MessageKind::Event => "events",
+            MessageKind::Command => "commands",
+        }
+    }
+}
+
+/// JSON Schema of one event or command variant
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageSchema {
+    /// Event or command
+    pub kind: MessageKind,
+    /// `event_type` or `command_type`, as used in subjects
+    pub message_type: String,
+    /// Draft 7 schema of the message on its subject
+    pub schema: Value,
+}
+
+impl MessageSchema {
+    /// Subject the message itself is published on
+    pub fn message_subject(&self) -> String {
+        format!("{}.{}.{}", RelationshipSubjects::DOMAIN, self.kind.token(), self.message_type)
+    }
+
+    /// Subject the schema is served on
+    pub fn subject(&self) -> String {
+        RelationshipSubjects::schema(self.kind.token(), &self.message_type)
+    }
+
+    /// Key of the schema in the catalog bundle
+    pub fn bundle_key(&self) -> String {
+        format!("{}/{}", self.kind.token(), self.message_type)
+    }
+}
+
+/// Schemas of every relationship event and command
+#[derive(Debug, Clone)]
+pub struct SchemaCatalog {
+    schemas: Vec<MessageSchema>,
+}
+
+impl Default for SchemaCatalog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SchemaCatalog {
+    /// Derive every schema
+    pub fn new() -> Self {
+        Self { schemas: catalog() }
+    }
+
+    /// All schemas, events first, in declaration order
+    pub fn schemas(&self) -> &[MessageSchema] {
+        &self.schemas
+    }
+
+    /// Schema of one message type
+    pub fn get(&self, kind: MessageKind, message_type: &str) -> Option<&MessageSchema> {
+        self.schemas
+            .iter()
+            .find(|s| s.kind == kind && s.message_type == message_type)
+    }
+
+    /// Schema of the message published on a subject, e.g.
+    /// `relationship.events.edge_created`
+    pub fn for_subject(&self, subject: &str) -> Option<&MessageSchema> {
+        self.schemas.iter().find(|s| s.message_subject() == subject)
+    }
+
+    /// Every schema in one document, under `definitions`
+    pub fn bundle(&self) -> Value {
+        let definitions: Map<String, Value> = self
+            .schemas
+            .iter()
+            .map(|s| {
+                let mut schema = s.schema.clone();
+                if let Value::Object(map) = &mut schema {
+                    map.remove("$schema");
+                }
+                (s.bundle_key(), schema)
+            })
+            .collect();
+        json!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "title": format!("{} messages", RelationshipSubjects::DOMAIN),
+            "version": crate::VERSION,
+            "definitions": definitions,
+        })
+    }
+
+    /// Reply to a request on a schema subject
+    ///
+    /// `relationship.schema.catalog` gets the bundle; unknown subjects are
+    /// an error.
+    pub fn respond(&self, subject: &str) -> RelationshipResult<Bytes> {
+        let schema = if subject == RelationshipSubjects::schema_catalog() {
+            self.bundle()
+        } else {
+            self.schemas
+                .iter()
+                .find(|s| s.subject() == subject)
+                .map(|s| s.schema.clone())
+                .ok_or_else(|| RelationshipError::InvalidConfiguration(format!("no schema on {}", subject)))?
+        };
+        serde_json::to_vec(&schema)
+            .map(Bytes::from)
+            .map_err(|e| RelationshipError::SerializationError(e.to_string()))
+    }
+
+    /// Answer schema requests on `relationship.schema.>` until the
+    /// subscription ends
+    ///
+    /// Requests for unknown subjects are logged and left unanswered.
+    pub async fn serve<T: Transport>(&self, transport: &T) -> RelationshipResult<()> {
+        let mut requests = transport.subscribe(&RelationshipSubjects::all_schemas()).await?;
+        while let Some(request) = requests.next().await {
+            let Some(reply) = request.reply else {
+                continue;
+            };
+            match self.respond(&request.subject) {
+                Ok(payload) => transport.publish(&reply, payload).await?,
+                Err(e) => tracing::debug!("schema request on {} unanswered: {}", request.subject, e),
+            }
+        }
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Upstream Types
+// ============================================================================
+
+/// Schema for `cim_domain::MessageIdentity`
+pub fn message_identity(_: &mut SchemaGenerator) -> Schema {
+    described(
+        Some(InstanceType::Object),
+        "cim-domain MessageIdentity: message, correlation and causation ids",
+    )
+}
+
+/// Schema for `cim_domain_spaces::ConceptId`
+pub fn concept_id(_: &mut SchemaGenerator) -> Schema {
+    described(None, "cim-domain-spaces ConceptId")
+}
+
+/// Schema for `cim_domain_spaces::KnowledgeLevel`
+pub fn knowledge_level(_: &mut SchemaGenerator) -> Schema {
+    described(None, "cim-domain-spaces KnowledgeLevel")
+}
+
+fn described(instance_type: Option<InstanceType>, description: &str) -> Schema {
+    SchemaObject {
+        instance_type: instance_type.map(Into::into),
+        metadata: Some(Box::new(Metadata {
+            description: Some(description.to_string()),
+            ..Default::default()
+        })),
+        ..Default::default()
+    }
+    .into()
+}
+
+// ============================================================================
+// Catalog
+// ============================================================================
+
+fn catalog() -> Vec<MessageSchema> {
+    vec![
+        // Events
+        event::<events::EdgeCreated>("edge_created", &["Edge", "EdgeCreated"]),
+        event::<events::EdgeActivated>("edge_activated", &["Edge", "EdgeActivated"]),
+        event::<events::EdgeSuspended>("edge_suspended", &["Edge", "EdgeSuspended"]),
+        event::<events::EdgeTerminated>("edge_terminated", &["Edge", "EdgeTerminated"]),
+        event::<events::EdgeRejected>("edge_rejected", &["Edge", "EdgeRejected"]),
+        event::<events::EdgeReproposed>("edge_reproposed", &["Edge", "Reproposed"]),
+        event::<events::EdgeQualityUpdated>("edge_quality_updated", &["Edge", "QualityUpdated"]),
+        event::<events::EdgeEvidenceAdded>("edge_evidence_added", &["Edge", "EvidenceAdded"]),
+        event::<events::EdgeEvidenceRevoked>("edge_evidence_revoked", &["Edge", "EvidenceRevoked"]),
+        event::<events::EdgeKnowledgeProgressed>("edge_knowledge_progressed", &["Edge", "KnowledgeProgressed"]),
+        event::<events::EdgePropertyUpdated>("edge_property_updated", &["Edge", "PropertyUpdated"]),
+        event::<events::EdgePropertyRemoved>("edge_property_removed", &["Edge", "PropertyRemoved"]),
+        event::<events::EdgeEndpointsRewritten>("edge_endpoints_rewritten", &["Edge", "EndpointsRewritten"]),
+        event::<events::EdgeReversed>("edge_reversed", &["Edge", "Reversed"]),
+        event::<events::EdgeConsentRequested>("edge_consent_requested", &["Edge", "ConsentRequested"]),
+        event::<events::EdgeConsentGranted>("edge_consent_granted", &["Edge", "ConsentGranted"]),
+        event::<events::EdgeConsentDeclined>("edge_consent_declined", &["Edge", "ConsentDeclined"]),
+        event::<events::EdgeTemplateApplied>("edge_template_applied", &["Edge", "TemplateApplied"]),
+        event::<events::EdgeTagAdded>("edge_tag_added", &["Edge", "TagAdded"]),
+        event::<events::EdgeTagRemoved>("edge_tag_removed", &["Edge", "TagRemoved"]),
+        event::<events::EdgeArchived>("edge_archived", &["Edge", "Archived"]),
+        event::<events::EdgeRestored>("edge_restored", &["Edge", "Restored"]),
+        event::<events::EdgeRenamed>("edge_renamed", &["Edge", "Renamed"]),
+        event::<events::EdgeDescriptionUpdated>("edge_description_updated", &["Edge", "DescriptionUpdated"]),
+        event::<events::HyperEdgeCreated>("hyperedge_created", &["HyperEdge", "HyperEdgeCreated"]),
+        event::<events::HyperEdgeActivated>("hyperedge_activated", &["HyperEdge", "HyperEdgeActivated"]),
+        event::<events::ParticipantAdded>("participant_added", &["HyperEdge", "ParticipantAdded"]),
+        event::<events::ParticipantRemoved>("participant_removed", &["HyperEdge", "ParticipantRemoved"]),
+        event::<events::ParticipantRoleChanged>("participant_role_changed", &["HyperEdge", "ParticipantRoleChanged"]),
+        event::<events::ParticipantWeightChanged>("participant_weight_changed", &["HyperEdge", "ParticipantWeightChanged"]),
+        event::<events::ParticipantAcknowledged>("participant_acknowledged", &["HyperEdge", "ParticipantAcknowledged"]),
+        event::<events::HyperEdgeTerminated>("hyperedge_terminated", &["HyperEdge", "HyperEdgeTerminated"]),
+        event::<events::RestructuringBegun>("restructuring_begun", &["HyperEdge", "RestructuringBegun"]),
+        event::<events::RestructuringCompleted>("restructuring_completed", &["HyperEdge", "RestructuringCompleted"]),
+        event::<events::HyperEdgeQualityUpdated>("hyperedge_quality_updated", &["HyperEdge", "HyperEdgeQualityUpdated"]),
+        event::<events::HyperEdgePromotedFromEdge>("hyperedge_promoted_from_edge", &["HyperEdge", "PromotedFromEdge"]),
+        event::<events::HyperEdgeTemplateApplied>("hyperedge_template_applied", &["HyperEdge", "TemplateApplied"]),
+        event::<events::HyperEdgeTagAdded>("hyperedge_tag_added", &["HyperEdge", "TagAdded"]),
+        event::<events::HyperEdgeTagRemoved>("hyperedge_tag_removed", &["HyperEdge", "TagRemoved"]),
+        event::<events::ParticipantGroupAdded>("participant_group_added", &["HyperEdge", "GroupAdded"]),
+        event::<events::ParticipantGroupRemoved>("participant_group_removed", &["HyperEdge", "GroupRemoved"]),
+        event::<events::ParticipantAssignedToGroup>("participant_assigned_to_group", &["HyperEdge", "AssignedToGroup"]),
+        event::<events::ParticipantUnassignedFromGroup>("participant_unassigned_from_group", &["HyperEdge", "UnassignedFromGroup"]),
+        event::<events::HyperEdgeArchived>("hyperedge_archived", &["HyperEdge", "Archived"]),
+        event::<events::HyperEdgeRestored>("hyperedge_restored", &["HyperEdge", "Restored"]),
+        event::<events::HyperEdgeRenamed>("hyperedge_renamed", &["HyperEdge", "Renamed"]),
+        event::<events::HyperEdgeDescriptionUpdated>("hyperedge_description_updated", &["HyperEdge", "DescriptionUpdated"]),
+        event::<events::PolicyEnacted>("policy_enacted", &["Policy", "PolicyEnacted"]),
+        event::<events::PolicyRevoked>("policy_revoked", &["Policy", "PolicyRevoked"]),
+        // Commands
+        command::<commands::CreateEdge>("create_edge", &["Edge", "CreateEdge"]),
+        command::<commands::ActivateEdge>("activate_edge", &["Edge", "ActivateEdge"]),
+        command::<commands::SuspendEdge>("suspend_edge", &["Edge", "SuspendEdge"]),
+        command::<commands::ResumeEdge>("resume_edge", &["Edge", "ResumeEdge"]),
+        command::<commands::TerminateEdge>("terminate_edge", &["Edge", "TerminateEdge"]),
+        command::<commands::RejectEdge>("reject_edge", &["Edge", "RejectEdge"]),
+        command::<commands::ReproposeEdge>("repropose_edge", &["Edge", "ReproposeEdge"]),
+        command::<commands::ExpireEdge>("expire_edge", &["Edge", "ExpireEdge"]),
+        command::<commands::UpdateEdgeQuality>("update_edge_quality", &["Edge", "UpdateEdgeQuality"]),
+        command::<commands::AddEdgeEvidence>("add_edge_evidence", &["Edge", "AddEdgeEvidence"]),
+        command::<commands::RevokeEdgeEvidence>("revoke_edge_evidence", &["Edge", "RevokeEdgeEvidence"]),
+        command::<commands::ProgressKnowledge>("progress_knowledge", &["Edge", "ProgressKnowledge"]),
+        command::<commands::ReverseEdge>("reverse_edge", &["Edge", "ReverseEdge"]),
+        command::<commands::RedirectEdge>("redirect_edge", &["Edge", "RedirectEdge"]),
+        command::<commands::RequestConsent>("request_consent", &["Edge", "RequestConsent"]),
+        command::<commands::GrantConsent>("grant_consent", &["Edge", "GrantConsent"]),
+        command::<commands::DeclineConsent>("decline_consent", &["Edge", "DeclineConsent"]),
+        command::<commands::ExpireConsent>("expire_consent", &["Edge", "ExpireConsent"]),
+        command::<commands::SetEdgeProperty>("set_edge_property", &["Edge", "SetEdgeProperty"]),
+        command::<commands::RemoveEdgeProperty>("remove_edge_property", &["Edge", "RemoveEdgeProperty"]),
+        command::<commands::AddEdgeTag>("add_edge_tag", &["Edge", "AddEdgeTag"]),
+        command::<commands::RemoveEdgeTag>("remove_edge_tag", &["Edge", "RemoveEdgeTag"]),
+        command::<commands::ArchiveRelationship>("archive_edge", &["Edge", "ArchiveEdge"]),
+        command::<commands::RestoreRelationship>("restore_edge", &["Edge", "RestoreEdge"]),
+        command::<commands::RenameRelationship>("rename_edge", &["Edge", "RenameEdge"]),
+        command::<commands::UpdateDescription>("describe_edge", &["Edge", "DescribeEdge"]),
+        command::<commands::CreateHyperEdge>("create_hyperedge", &["HyperEdge", "CreateHyperEdge"]),
+        command::<commands::ActivateHyperEdge>("activate_hyperedge", &["HyperEdge", "ActivateHyperEdge"]),
+        command::<commands::AddParticipant>("add_participant", &["HyperEdge", "AddParticipant"]),
+        command::<commands::RemoveParticipant>("remove_participant", &["HyperEdge", "RemoveParticipant"]),
+        command::<commands::ChangeParticipantRole>("change_participant_role", &["HyperEdge", "ChangeParticipantRole"]),
+        command::<commands::ChangeParticipantWeight>("change_participant_weight", &["HyperEdge", "ChangeParticipantWeight"]),
+        command::<commands::AcknowledgeParticipation>("acknowledge_participation", &["HyperEdge", "AcknowledgeParticipation"]),
+        command::<commands::TerminateHyperEdge>("terminate_hyperedge", &["HyperEdge", "TerminateHyperEdge"]),
+        command::<commands::UpdateHyperEdgeQuality>("update_hyperedge_quality", &["HyperEdge", "UpdateHyperEdgeQuality"]),
+        command::<commands::BeginRestructuring>("begin_restructuring", &["HyperEdge", "BeginRestructuring"]),
+        command::<commands::CompleteRestructuring>("complete_restructuring", &["HyperEdge", "CompleteRestructuring"]),
+        command::<commands::AddHyperEdgeTag>("add_hyperedge_tag", &["HyperEdge", "AddHyperEdgeTag"]),
+        command::<commands::RemoveHyperEdgeTag>("remove_hyperedge_tag", &["HyperEdge", "RemoveHyperEdgeTag"]),
+        command::<commands::AddParticipantGroup>("add_participant_group", &["HyperEdge", "AddParticipantGroup"]),
+        command::<commands::RemoveParticipantGroup>("remove_participant_group", &["HyperEdge", "RemoveParticipantGroup"]),
+        command::<commands::AssignToGroup>("assign_to_group", &["HyperEdge", "AssignToGroup"]),
+        command::<commands::UnassignFromGroup>("unassign_from_group", &["HyperEdge", "UnassignFromGroup"]),
+        command::<commands::ArchiveRelationship>("archive_hyperedge", &["HyperEdge", "ArchiveHyperEdge"]),
+        command::<commands::RestoreRelationship>("restore_hyperedge", &["HyperEdge", "RestoreHyperEdge"]),
+        command::<commands::RenameRelationship>("rename_hyperedge", &["HyperEdge", "RenameHyperEdge"]),
+        command::<commands::UpdateDescription>("describe_hyperedge", &["HyperEdge", "DescribeHyperEdge"]),
+        command::<commands::PromoteEdgeToHyperEdge>("promote_edge_to_hyperedge", &["Restructure", "PromoteEdgeToHyperEdge"]),
+        command::<commands::MergeEdges>("merge_edges", &["Restructure", "MergeEdges"]),
+        command::<commands::DecomposeHyperEdge>("decompose_hyperedge", &["Restructure", "DecomposeHyperEdge"]),
+        command::<commands::CreateFromTemplate>("create_from_template", &["CreateFromTemplate"]),
+        command::<commands::CreateEdgesBatch>("create_edges_batch", &["CreateEdgesBatch"]),
+        command::<commands::AddParticipantsBatch>("add_participants_batch", &["AddParticipantsBatch"]),
+        command::<commands::ArchiveRelationship>("archive_relationship", &["ArchiveRelationship"]),
+        command::<commands::RestoreRelationship>("restore_relationship", &["RestoreRelationship"]),
+        command::<commands::RenameRelationship>("rename_relationship", &["RenameRelationship"]),
+        command::<commands::UpdateDescription>("update_description", &["UpdateDescription"]),
+        command::<commands::EnactPolicy>("enact_policy", &["EnactPolicy"]),
+        command::<commands::RevokePolicy>("revoke_policy", &["RevokePolicy"]),
+    ]
+}
+
+fn event<T: JsonSchema>(event_type: &str, envelope: &[&str]) -> MessageSchema {
+    message::<T>(MessageKind::Event, event_type, envelope)
+}
+
+fn command<T: JsonSchema>(command_type: &str, envelope: &[&str]) -> MessageSchema {
+    message::<T>(MessageKind::Command, command_type, envelope)
+}
+
+/// Schema of `T` wrapped in its enum envelope, one object per tag
+fn message<T: JsonSchema>(kind: MessageKind, message_type: &str, envelope: &[&str]) -> MessageSchema {
+    let root = schemars::schema_for!(T);
+    let mut schema = serde_json::to_value(&root.schema).expect("schemas serialize to JSON");
+    for tag in envelope.iter().rev() {
+        let mut properties = Map::new();
+        properties.insert(tag.to_string(), schema);
+        schema = json!({
+            "type": "object",
+            "required": [tag],
+            "properties": properties,
+            "additionalProperties": false,
+        });
+    }
+    if let Value::Object(map) = &mut schema {
+        map.insert("$schema".to_string(), json!(root.meta_schema));
+        map.insert("title".to_string(), json!(message_type));
+        if !root.definitions.is_empty() {
+            map.insert(
+                "definitions".to_string(),
+                serde_json::to_value(&root.definitions).expect("schemas serialize to JSON"),
+            );
+        }
+    }
+    MessageSchema {
+        kind,
+        message_type: message_type.to_string(),
+        schema,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{CreateEdge, EdgeCommand, HyperEdgeCommand, RelationshipCommand, RestructureCommand};
+    use crate::events::{EdgeEvent, HyperEdgeEvent, PolicyEvent};
+    use crate::nats::{decode, encode, MockTransport};
+    use crate::value_objects::{EntityRef, RelationshipCategory, RelationshipId};
+    use cim_domain::MessageIdentity;
+    use std::collections::HashSet;
+    use uuid::Uuid;
+
+    fn variants<T: JsonSchema>() -> usize {
+        let root = schemars::schema_for!(T);
+        root.schema.subschemas.and_then(|s| s.one_of).map_or(0, |v| v.len())
+    }
+
+    #[test]
+    fn test_catalog_covers_every_variant() {
+        let catalog = SchemaCatalog::new();
+        let events = catalog.schemas().iter().filter(|s| s.kind == MessageKind::Event).count();
+        let commands = catalog.schemas().len() - events;
+        assert_eq!(events, variants::<EdgeEvent>() + variants::<HyperEdgeEvent>() + variants::<PolicyEvent>());
+        // The three nested command enums stand in for their own variants
+        assert_eq!(
+            commands,
+            variants::<EdgeCommand>() + variants::<HyperEdgeCommand>() + variants::<RestructureCommand>()
+                + variants::<RelationshipCommand>()
+                - 3
+        );
+        let subjects: HashSet<String> = catalog.schemas().iter().map(MessageSchema::subject).collect();
+        assert_eq!(subjects.len(), catalog.schemas().len());
+    }
+
+    #[test]
+    fn test_schema_matches_wire_envelope() {
+        let catalog = SchemaCatalog::new();
+        let command = RelationshipCommand::Edge(EdgeCommand::CreateEdge(CreateEdge {
+            identity: MessageIdentity::new_root(),
+            edge_id: RelationshipId::new(),
+            source: EntityRef::person(Uuid::now_v7()),
+            target: EntityRef::organization(Uuid::now_v7()),
+            category: RelationshipCategory::Employment,
+            name: "Works at".to_string(),
+            quality: None,
+            created_by: "test".to_string(),
+        }));
+        let subject = RelationshipSubjects::command(&command);
+        let schema = &catalog.for_subject(&subject).unwrap().schema;
+        let wire: Value = serde_json::to_value(&command).unwrap();
+
+        let payload = &schema["properties"]["Edge"]["properties"]["CreateEdge"];
+        assert_eq!(schema["required"], json!(["Edge"]));
+        let required: Vec<&str> = payload["required"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v.as_str().unwrap())
+            .collect();
+        for field in required {
+            assert!(wire["Edge"]["CreateEdge"].get(field).is_some(), "missing {}", field);
+        }
+        assert!(schema["definitions"].get("EntityRef").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_schemas_served_on_request() {
+        let transport = MockTransport::new();
+        let catalog = SchemaCatalog::new();
+        let served = catalog.clone();
+        transport.on_request(RelationshipSubjects::all_schemas(), move |message| served.respond(&message.subject));
+
+        let reply = transport
+            .request(&RelationshipSubjects::schema("events", "edge_created"), encode(&json!({})).unwrap())
+            .await
+            .unwrap();
+        let schema: Value = decode(&reply).unwrap();
+        assert_eq!(schema["title"], "edge_created");
+
+        let reply = transport
+            .request(&RelationshipSubjects::schema_catalog(), Bytes::new())
+            .await
+            .unwrap();
+        let bundle: Value = decode(&reply).unwrap();
+        assert_eq!(bundle["definitions"].as_object().unwrap().len(), catalog.schemas().len());
+        assert!(bundle["definitions"]["commands/create_edge"].get("$schema").is_none());
+        assert!(catalog.respond("relationship.schema.events.unknown").is_err());
+    }
+}
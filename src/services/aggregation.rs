@@ -0,0 +1,303 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Hyperedge Quality Aggregation
+//!
+//! Derives a hyperedge's quality from its participants instead of leaving
+//! it at defaults.
+//!
+//! ## Contributions
+//!
+//! ```text
+//! participant --(active edges to other participants)--> mean quality
+//!     |
+//!     +-- weighted by participation weight
+//!     v
+//! QualityAggregation (per dimension: mean, weighted mean, min, max)
+//!     v
+//! hyperedge strength / trust / reciprocity
+//! ```
+//!
+//! Participants without edges to the rest of the group contribute nothing.
+//! Formality and duration are properties of the hyperedge itself and are
+//! never aggregated.
+
+use crate::aggregates::{HyperEdgeConcept, RelationshipSpace};
+use crate::events::{HyperEdgeEvent, HyperEdgeQualityUpdated, RelationshipEvent};
+use crate::quality::RelationshipQuality;
+use crate::value_objects::EntityRef;
+use chrono::Utc;
+use cim_domain::state_machine::State;
+use cim_domain::MessageIdentity;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Reason recorded on aggregation events
+pub const AGGREGATION_REASON: &str = "participant_aggregation";
+
+/// How one dimension is combined across participants
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Aggregator {
+    /// Plain mean
+    Mean,
+    /// Mean weighted by participation weight
+    WeightedMean,
+    /// Weakest participant
+    Min,
+    /// Strongest participant
+    Max,
+}
+
+impl Aggregator {
+    /// Combine `(weight, value)` pairs; None when there are none
+    pub fn apply(&self, values: &[(f64, f64)]) -> Option<f64> {
+        if values.is_empty() {
+            return None;
+        }
+        let result = match self {
+            Aggregator::Mean => values.iter().map(|(_, v)| v).sum::<f64>() / values.len() as f64,
+            Aggregator::WeightedMean => {
+                let total: f64 = values.iter().map(|(w, _)| w).sum();
+                if total <= 0.0 {
+                    return Aggregator::Mean.apply(values);
+                }
+                values.iter().map(|(w, v)| w * v).sum::<f64>() / total
+            }
+            Aggregator::Min => values.iter().map(|(_, v)| *v).fold(f64::INFINITY, f64::min),
+            Aggregator::Max => values.iter().map(|(_, v)| *v).fold(f64::NEG_INFINITY, f64::max),
+        };
+        Some(result)
+    }
+}
+
+/// Aggregation strategy, one aggregator per derived dimension
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QualityAggregation {
+    /// Strength (default: weakest link)
+    pub strength: Aggregator,
+    /// Trust (default: weighted by participation)
+    pub trust: Aggregator,
+    /// Reciprocity (default: plain mean)
+    pub reciprocity: Aggregator,
+}
+
+impl Default for QualityAggregation {
+    fn default() -> Self {
+        Self {
+            strength: Aggregator::Min,
+            trust: Aggregator::WeightedMean,
+            reciprocity: Aggregator::Mean,
+        }
+    }
+}
+
+/// Quality a participant brings to a hyperedge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParticipantContribution {
+    /// The participant
+    pub entity: EntityRef,
+    /// Participation weight
+    pub weight: f64,
+    /// Mean strength of its edges to other participants
+    pub strength: f64,
+    /// Mean trust of its edges to other participants
+    pub trust: f64,
+    /// Mean reciprocity of its edges to other participants
+    pub reciprocity: f64,
+}
+
+/// Derives hyperedge quality from participant relationships
+#[derive(Debug, Clone, Default)]
+pub struct HyperEdgeQualityAggregator {
+    strategy: QualityAggregation,
+}
+
+impl HyperEdgeQualityAggregator {
+    /// Create an aggregator with the given strategy
+    pub fn new(strategy: QualityAggregation) -> Self {
+        Self { strategy }
+    }
+
+    /// Get the strategy
+    pub fn strategy(&self) -> &QualityAggregation {
+        &self.strategy
+    }
+
+    /// What each participant contributes, from the active edges among
+    /// the participants
+    pub fn contributions(&self, space: &RelationshipSpace, hyperedge: &HyperEdgeConcept) -> Vec<ParticipantContribution> {
+        let members = &hyperedge.participants;
+        let internal: Vec<_> = space
+            .edges
+            .values()
+            .filter(|e| e.is_active() && members.contains(&e.source) && members.contains(&e.target))
+            .filter(|e| !e.source.same_entity(&e.target))
+            .collect();
+
+        members
+            .participants()
+            .filter_map(|p| {
+                let own: Vec<_> = internal
+                    .iter()
+                    .filter(|e| e.source.same_entity(&p.entity_ref) || e.target.same_entity(&p.entity_ref))
+                    .collect();
+                if own.is_empty() {
+                    return None;
+                }
+                let n = own.len() as f64;
+                Some(ParticipantContribution {
+                    entity: p.entity_ref.clone(),
+                    weight: p.weight,
+                    strength: own.iter().map(|e| e.quality.strength).sum::<f64>() / n,
+                    trust: own.iter().map(|e| e.quality.trust).sum::<f64>() / n,
+                    reciprocity: own.iter().map(|e| e.quality.reciprocity).sum::<f64>() / n,
+                })
+            })
+            .collect()
+    }
+
+    /// Aggregate participant contributions into a quality for the hyperedge
+    ///
+    /// Dimensions with no contributions keep the hyperedge's current value.
+    pub fn aggregate(&self, space: &RelationshipSpace, hyperedge: &HyperEdgeConcept) -> RelationshipQuality {
+        let contributions = self.contributions(space, hyperedge);
+        let column = |f: fn(&ParticipantContribution) -> f64| -> Vec<(f64, f64)> {
+            contributions.iter().map(|c| (c.weight, f(c))).collect()
+        };
+
+        let mut quality = hyperedge.quality.clone();
+        if let Some(v) = self.strategy.strength.apply(&column(|c| c.strength)) {
+            quality.strength = v.clamp(0.0, 1.0);
+        }
+        if let Some(v) = self.strategy.trust.apply(&column(|c| c.trust)) {
+            quality.trust = v.clamp(0.0, 1.0);
+        }
+        if let Some(v) = self.strategy.reciprocity.apply(&column(|c| c.reciprocity)) {
+            quality.reciprocity = v.clamp(0.0, 1.0);
+        }
+        quality
+    }
+
+    /// Recompute a hyperedge's quality, returning the update if it changed
+    pub fn recompute(&self, space: &RelationshipSpace, hyperedge: &HyperEdgeConcept) -> Option<HyperEdgeEvent> {
+        if hyperedge.state.is_terminal() {
+            return None;
+        }
+        let quality = self.aggregate(space, hyperedge);
+        let old = &hyperedge.quality;
+        let unchanged = (quality.strength - old.strength).abs() < 1e-9
+            && (quality.trust - old.trust).abs() < 1e-9
+            && (quality.reciprocity - old.reciprocity).abs() < 1e-9;
+        if unchanged {
+            return None;
+        }
+
+        Some(HyperEdgeEvent::HyperEdgeQualityUpdated(HyperEdgeQualityUpdated {
+            event_id: Uuid::now_v7(),
+            identity: MessageIdentity::new_root(),
+            hyperedge_id: hyperedge.id,
+            old_quality: old.clone(),
+            new_quality: quality,
+            reason: AGGREGATION_REASON.to_string(),
+            updated_at: Utc::now(),
+        }))
+    }
+
+    /// React to a hyperedge event already applied to the space
+    ///
+    /// Participant changes trigger a recompute; other events do not.
+    pub fn react(&self, space: &RelationshipSpace, event: &HyperEdgeEvent) -> Option<HyperEdgeEvent> {
+        let participants_changed = matches!(
+            event,
+            HyperEdgeEvent::HyperEdgeCreated(_)
+                | HyperEdgeEvent::ParticipantAdded(_)
+                | HyperEdgeEvent::ParticipantRemoved(_)
+                | HyperEdgeEvent::ParticipantWeightChanged(_)
+        );
+        if !participants_changed {
+            return None;
+        }
+        let hyperedge = space.get_hyperedge(&event.hyperedge_id())?;
+        self.recompute(space, hyperedge)
+    }
+
+    /// Recompute every live hyperedge, e.g. after participant edges changed
+    pub fn refresh(&self, space: &RelationshipSpace) -> Vec<RelationshipEvent> {
+        space
+            .hyperedges
+            .values()
+            .filter_map(|h| self.recompute(space, h))
+            .map(RelationshipEvent::HyperEdge)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregates::EdgeConcept;
+    use crate::value_objects::{ParticipantRole, RelationshipCategory};
+    use cim_domain_spaces::TopologicalSpaceId;
+
+    fn edge(a: &EntityRef, b: &EntityRef, strength: f64, trust: f64) -> EdgeConcept {
+        let quality = RelationshipQuality {
+            strength,
+            trust,
+            ..RelationshipQuality::default()
+        };
+        let mut edge = EdgeConcept::new("Colleagues", a.clone(), b.clone(), RelationshipCategory::Friendship)
+            .with_quality(quality);
+        edge.activate().unwrap();
+        edge
+    }
+
+    #[test]
+    fn test_aggregators() {
+        let values = [(1.0, 0.2), (3.0, 0.6)];
+        let close = |a: Option<f64>, b: f64| a.is_some_and(|a| (a - b).abs() < 1e-12);
+        assert!(close(Aggregator::Mean.apply(&values), 0.4));
+        assert!(close(Aggregator::WeightedMean.apply(&values), 0.5));
+        assert_eq!(Aggregator::Min.apply(&values), Some(0.2));
+        assert_eq!(Aggregator::Max.apply(&values), Some(0.6));
+        assert_eq!(Aggregator::Mean.apply(&[]), None);
+    }
+
+    #[test]
+    fn test_hyperedge_quality_from_participants() {
+        let mut space = RelationshipSpace::new("Team", TopologicalSpaceId::new());
+        let [a, b, c, d] = [(); 4].map(|_| EntityRef::person(Uuid::now_v7()));
+        space.add_edge(edge(&a, &b, 0.8, 0.9)).unwrap();
+        space.add_edge(edge(&b, &c, 0.4, 0.5)).unwrap();
+
+        let mut team = HyperEdgeConcept::new("Team", RelationshipCategory::Membership);
+        team.add_participant(a.clone(), ParticipantRole::Member, 1.0).unwrap();
+        team.add_participant(b.clone(), ParticipantRole::Member, 1.0).unwrap();
+        team.add_participant(c.clone(), ParticipantRole::Member, 1.0).unwrap();
+        // No edges to the group: contributes nothing
+        team.add_participant(d, ParticipantRole::Member, 1.0).unwrap();
+        space.add_hyperedge(team.clone());
+
+        let aggregator = HyperEdgeQualityAggregator::default();
+        assert_eq!(aggregator.contributions(&space, &team).len(), 3);
+
+        let quality = aggregator.aggregate(&space, &team);
+        // Weakest link: c's only edge has strength 0.4
+        assert!((quality.strength - 0.4).abs() < 1e-9);
+        // a = 0.9, b = (0.9 + 0.5) / 2, c = 0.5
+        assert!((quality.trust - 0.7).abs() < 1e-9);
+
+        let Some(HyperEdgeEvent::HyperEdgeQualityUpdated(update)) = aggregator.recompute(&space, &team) else {
+            panic!("expected a quality update");
+        };
+        assert_eq!(update.reason, AGGREGATION_REASON);
+
+        // Applying the update leaves nothing to recompute
+        let updated = team
+            .apply_event_pure(&HyperEdgeEvent::HyperEdgeQualityUpdated(update))
+            .unwrap();
+        space.add_hyperedge(updated);
+        assert!(aggregator.refresh(&space).is_empty());
+    }
+}
@@ -0,0 +1,208 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! CID Resolution
+//!
+//! Entity references can be pinned to a CID, and edges cite evidence by
+//! CID. The resolver fetches that content from the CIM object store and
+//! checks it against the hash in the CID before handing it out:
+//!
+//! ```text
+//! resolve(cid)
+//!     |
+//!     +-- cached?              --> content
+//!     +-- ObjectStore::get     --> content | not stored
+//!     +-- blake3(content) == multihash digest of cid
+//!     +-- cache                --> content
+//! ```
+//!
+//! Content addressed by a CID never changes, so cached content never goes
+//! stale. CIM content is addressed with BLAKE3; CIDs using other hash
+//! functions cannot be verified and fail to resolve.
+
+use crate::aggregates::EdgeConcept;
+use crate::nats::Transport;
+use crate::value_objects::EntityRef;
+use crate::{RelationshipError, RelationshipResult};
+use async_trait::async_trait;
+use bytes::Bytes;
+use cid::Cid;
+use multihash::Multihash;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Multihash code of BLAKE3-256
+pub const BLAKE3_MULTIHASH: u64 = 0x1e;
+
+/// Multicodec of raw bytes
+pub const RAW_CODEC: u64 = 0x55;
+
+/// Subject prefix of object store requests (`cim.objects.get.{cid}`)
+pub const OBJECT_STORE_SUBJECT: &str = "cim.objects.get";
+
+/// CID of raw content, as the CIM object store addresses it
+pub fn cid_of(content: &[u8]) -> String {
+    let digest = blake3::hash(content);
+    let hash = Multihash::<64>::wrap(BLAKE3_MULTIHASH, digest.as_bytes()).expect("BLAKE3 digests fit a multihash");
+    Cid::new_v1(RAW_CODEC, hash).to_string()
+}
+
+/// Where content is fetched from
+#[async_trait]
+pub trait ObjectStore: Send + Sync {
+    /// Content stored under a CID (`None` = not stored)
+    async fn get(&self, cid: &str) -> RelationshipResult<Option<Bytes>>;
+}
+
+/// Object store reached by request over a transport
+///
+/// An empty reply means the CID is not stored.
+#[derive(Debug, Clone)]
+pub struct TransportObjectStore<T: Transport> {
+    transport: T,
+}
+
+impl<T: Transport> TransportObjectStore<T> {
+    /// Request content over a transport
+    pub fn new(transport: T) -> Self {
+        Self { transport }
+    }
+}
+
+#[async_trait]
+impl<T: Transport> ObjectStore for TransportObjectStore<T> {
+    async fn get(&self, cid: &str) -> RelationshipResult<Option<Bytes>> {
+        let reply = self
+            .transport
+            .request(&format!("{}.{}", OBJECT_STORE_SUBJECT, cid), Bytes::new())
+            .await?;
+        Ok((!reply.is_empty()).then_some(reply))
+    }
+}
+
+/// Fetches, verifies and caches content by CID
+#[derive(Debug)]
+pub struct CidResolver<S: ObjectStore> {
+    store: S,
+    cache: Mutex<HashMap<String, Bytes>>,
+}
+
+impl<S: ObjectStore> CidResolver<S> {
+    /// Resolve CIDs from an object store
+    pub fn new(store: S) -> Self {
+        Self {
+            store,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Verified content of a CID
+    pub async fn resolve(&self, cid: &str) -> RelationshipResult<Bytes> {
+        if let Some(content) = self.lock().get(cid) {
+            return Ok(content.clone());
+        }
+        let parsed = Cid::try_from(cid).map_err(|e| failed(cid, e))?;
+        let content = self
+            .store
+            .get(cid)
+            .await?
+            .ok_or_else(|| failed(cid, "not in the object store"))?;
+        verify(&parsed, &content).map_err(|reason| failed(cid, reason))?;
+        self.lock().insert(cid.to_string(), content.clone());
+        Ok(content)
+    }
+
+    /// Content of the version an entity reference is pinned to
+    pub async fn resolve_entity(&self, entity: &EntityRef) -> RelationshipResult<Bytes> {
+        match &entity.cid {
+            Some(cid) => self.resolve(cid).await,
+            None => Err(RelationshipError::CidResolutionFailed(format!(
+                "{} is not pinned to a CID",
+                entity
+            ))),
+        }
+    }
+
+    /// Content of every piece of evidence an edge cites, in order
+    pub async fn resolve_evidence(&self, edge: &EdgeConcept) -> RelationshipResult<Vec<(String, Bytes)>> {
+        let mut evidence = Vec::with_capacity(edge.evidence_cids.len());
+        for cid in &edge.evidence_cids {
+            evidence.push((cid.clone(), self.resolve(cid).await?));
+        }
+        Ok(evidence)
+    }
+
+    /// Check if a CID's content is cached
+    pub fn is_cached(&self, cid: &str) -> bool {
+        self.lock().contains_key(cid)
+    }
+
+    /// Drop all cached content
+    pub fn clear_cache(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Bytes>> {
+        self.cache.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Check content against the digest in its CID
+fn verify(cid: &Cid, content: &[u8]) -> Result<(), String> {
+    let hash = cid.hash();
+    if hash.code() != BLAKE3_MULTIHASH {
+        return Err(format!("unsupported hash function 0x{:x}", hash.code()));
+    }
+    if blake3::hash(content).as_bytes().as_slice() != hash.digest() {
+        return Err("content does not match its hash".to_string());
+    }
+    Ok(())
+}
+
+fn failed(cid: &str, reason: impl std::fmt::Display) -> RelationshipError {
+    RelationshipError::CidResolutionFailed(format!("{}: {}", cid, reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nats::MockTransport;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_resolve_verifies_and_caches() {
+        let contract = Bytes::from_static(b"employment contract");
+        let good = cid_of(&contract);
+        let tampered = cid_of(b"original terms");
+        let missing = cid_of(b"never stored");
+
+        let transport = MockTransport::new();
+        let fetches = Arc::new(AtomicUsize::new(0));
+        let counter = fetches.clone();
+        let (good_subject, served) = (format!("{}.{}", OBJECT_STORE_SUBJECT, good), contract.clone());
+        transport.on_request(good_subject, move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Ok(served.clone())
+        });
+        transport.on_request(format!("{}.{}", OBJECT_STORE_SUBJECT, tampered), |_| {
+            Ok(Bytes::from_static(b"altered terms"))
+        });
+        transport.on_request(format!("{}.{}", OBJECT_STORE_SUBJECT, missing), |_| Ok(Bytes::new()));
+
+        let resolver = CidResolver::new(TransportObjectStore::new(transport));
+        assert_eq!(resolver.resolve(&good).await.unwrap(), contract);
+        assert_eq!(resolver.resolve(&good).await.unwrap(), contract);
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+        assert!(resolver.is_cached(&good));
+
+        for cid in [tampered.as_str(), missing.as_str(), "not-a-cid"] {
+            assert!(matches!(
+                resolver.resolve(cid).await,
+                Err(RelationshipError::CidResolutionFailed(_))
+            ));
+            assert!(!resolver.is_cached(cid));
+        }
+    }
+}
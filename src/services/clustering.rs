@@ -0,0 +1,411 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! K-Means Clustering in Quality Space
+//!
+//! Groups the live relationships of a space by their QualityPoints.
+//!
+//! ## Algorithm
+//!
+//! ```text
+//! seed:    point nearest the mean, then repeatedly the point farthest
+//!          from every chosen centroid (deterministic maximin)
+//! assign:  each relationship -> nearest centroid (weighted distance)
+//! update:  centroid = mean of its members
+//! repeat   until no centroid moves more than `tolerance`
+//!          or `max_iterations` is reached
+//! ```
+//!
+//! Centroids can seed the space's Voronoi tessellation, giving one cell per
+//! cluster.
+
+use crate::aggregates::RelationshipSpace;
+use crate::quality::{QualityPoint, QualityWeights};
+use crate::value_objects::{RelationshipCategory, RelationshipId};
+use crate::{RelationshipError, RelationshipResult};
+use cim_domain::state_machine::State;
+use cim_domain_spaces::VoronoiTessellation;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// K-means parameters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClusteringConfig {
+    /// Number of clusters (capped at the number of relationships)
+    pub k: usize,
+    /// Dimension weights for the distance metric
+    pub weights: QualityWeights,
+    /// Iteration limit
+    pub max_iterations: usize,
+    /// Largest centroid movement still counted as converged
+    pub tolerance: f64,
+    /// Cluster hyperedges as well as edges
+    pub include_hyperedges: bool,
+}
+
+impl Default for ClusteringConfig {
+    fn default() -> Self {
+        Self {
+            k: 4,
+            weights: QualityWeights::default(),
+            max_iterations: 100,
+            tolerance: 1e-6,
+            include_hyperedges: true,
+        }
+    }
+}
+
+/// Group of similar relationships
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelationshipCluster {
+    /// Cluster identifier, also the id of its tessellation cell
+    pub id: Uuid,
+    /// Position of the cluster in the result, 0-based
+    pub label: usize,
+    /// Mean quality point of the members
+    pub centroid: QualityPoint,
+    /// Member relationships
+    pub members: Vec<RelationshipId>,
+    /// Most common category among the members
+    pub dominant_category: Option<RelationshipCategory>,
+}
+
+impl RelationshipCluster {
+    /// Number of members
+    pub fn size(&self) -> usize {
+        self.members.len()
+    }
+}
+
+/// Result of a clustering run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Clustering {
+    /// Clusters, largest first
+    pub clusters: Vec<RelationshipCluster>,
+    /// Iterations performed
+    pub iterations: usize,
+    /// Whether the centroids settled within tolerance
+    pub converged: bool,
+    /// Sum of squared weighted distances from members to their centroid
+    pub inertia: f64,
+}
+
+impl Clustering {
+    /// Find the cluster a relationship belongs to
+    pub fn cluster_of(&self, id: &RelationshipId) -> Option<&RelationshipCluster> {
+        self.clusters.iter().find(|c| c.members.contains(id))
+    }
+
+    /// Tessellate the space around the cluster centroids
+    ///
+    /// Each cell carries the id of the cluster that generated it.
+    pub fn seed_tessellation<'a>(&self, space: &'a mut RelationshipSpace) -> &'a VoronoiTessellation {
+        space.install_tessellation(self.clusters.iter().map(|c| (c.id, c.centroid)))
+    }
+}
+
+/// K-means clustering of relationships
+#[derive(Debug, Clone, Default)]
+pub struct ClusteringService {
+    config: ClusteringConfig,
+}
+
+impl ClusteringService {
+    /// Create a service with the given parameters
+    pub fn new(config: ClusteringConfig) -> Self {
+        Self { config }
+    }
+
+    /// Get the parameters
+    pub fn config(&self) -> &ClusteringConfig {
+        &self.config
+    }
+
+    /// Cluster the live relationships of a space
+    pub fn cluster(&self, space: &RelationshipSpace) -> RelationshipResult<Clustering> {
+        if self.config.k == 0 {
+            return Err(RelationshipError::InvalidConfiguration(
+                "k-means requires k >= 1".to_string(),
+            ));
+        }
+
+        let mut items: Vec<(RelationshipId, QualityPoint, &RelationshipCategory)> = space
+            .edges
+            .values()
+            .filter(|e| !e.state.is_closed())
+            .map(|e| (e.id, e.quality_point(), &e.category))
+            .collect();
+        if self.config.include_hyperedges {
+            items.extend(
+                space
+                    .hyperedges
+                    .values()
+                    .filter(|h| !h.state.is_terminal())
+                    .map(|h| (h.id, h.quality_point(), &h.category)),
+            );
+        }
+        items.sort_by_key(|(id, _, _)| id.as_uuid());
+
+        let points: Vec<QualityPoint> = items.iter().map(|(_, p, _)| *p).collect();
+        let mut centroids = self.initial_centroids(&points);
+        let mut assignment = vec![0; points.len()];
+        let mut iterations = 0;
+        let mut converged = points.is_empty();
+
+        while !converged && iterations < self.config.max_iterations {
+            iterations += 1;
+            for (slot, point) in assignment.iter_mut().zip(&points) {
+                *slot = self.nearest(&centroids, point);
+            }
+
+            let mut moved: f64 = 0.0;
+            for (c, centroid) in centroids.iter_mut().enumerate() {
+                let members = points
+                    .iter()
+                    .zip(&assignment)
+                    .filter(|(_, &a)| a == c)
+                    .map(|(p, _)| p);
+                // An emptied cluster keeps its previous centroid
+                if let Some(mean) = mean(members) {
+                    moved = moved.max(centroid.weighted_distance(&mean, &self.config.weights));
+                    *centroid = mean;
+                }
+            }
+            converged = moved <= self.config.tolerance;
+        }
+
+        // Final assignment against the settled centroids
+        for (slot, point) in assignment.iter_mut().zip(&points) {
+            *slot = self.nearest(&centroids, point);
+        }
+        let inertia = points
+            .iter()
+            .zip(&assignment)
+            .map(|(p, &a)| p.weighted_distance(&centroids[a], &self.config.weights).powi(2))
+            .sum();
+
+        let mut clusters: Vec<RelationshipCluster> = centroids
+            .iter()
+            .enumerate()
+            .map(|(c, centroid)| {
+                let members: Vec<_> = items
+                    .iter()
+                    .zip(&assignment)
+                    .filter(|(_, &a)| a == c)
+                    .map(|(item, _)| item)
+                    .collect();
+                let mut counts: HashMap<&RelationshipCategory, usize> = HashMap::new();
+                for (_, _, category) in &members {
+                    *counts.entry(category).or_default() += 1;
+                }
+                let dominant_category = counts
+                    .into_iter()
+                    .max_by(|a, b| {
+                        a.1.cmp(&b.1)
+                            .then_with(|| format!("{:?}", b.0).cmp(&format!("{:?}", a.0)))
+                    })
+                    .map(|(category, _)| category.clone());
+                RelationshipCluster {
+                    id: Uuid::now_v7(),
+                    label: 0,
+                    centroid: *centroid,
+                    members: members.iter().map(|(id, _, _)| *id).collect(),
+                    dominant_category,
+                }
+            })
+            .filter(|c| !c.members.is_empty())
+            .collect();
+        clusters.sort_by_key(|c| std::cmp::Reverse(c.size()));
+        for (label, cluster) in clusters.iter_mut().enumerate() {
+            cluster.label = label;
+        }
+
+        Ok(Clustering {
+            clusters,
+            iterations,
+            converged,
+            inertia,
+        })
+    }
+
+    /// Deterministic maximin seeding
+    fn initial_centroids(&self, points: &[QualityPoint]) -> Vec<QualityPoint> {
+        let Some(center) = mean(points.iter()) else {
+            return Vec::new();
+        };
+        let weights = &self.config.weights;
+        let first = points[self.nearest(points, &center)];
+
+        let mut centroids = vec![first];
+        while centroids.len() < self.config.k.min(points.len()) {
+            let farthest = points
+                .iter()
+                .map(|p| {
+                    let d = centroids
+                        .iter()
+                        .map(|c| p.weighted_distance(c, weights))
+                        .fold(f64::INFINITY, f64::min);
+                    (p, d)
+                })
+                .max_by(|a, b| a.1.total_cmp(&b.1));
+            match farthest {
+                Some((point, d)) if d > 0.0 => centroids.push(*point),
+                // Fewer distinct points than k
+                _ => break,
+            }
+        }
+        centroids
+    }
+
+    /// Index of the centroid nearest to a point
+    fn nearest(&self, centroids: &[QualityPoint], point: &QualityPoint) -> usize {
+        let weights = &self.config.weights;
+        centroids
+            .iter()
+            .enumerate()
+            .min_by(|a, b| {
+                a.1.weighted_distance(point, weights)
+                    .total_cmp(&b.1.weighted_distance(point, weights))
+            })
+            .map_or(0, |(i, _)| i)
+    }
+}
+
+fn mean<'a>(points: impl IntoIterator<Item = &'a QualityPoint>) -> Option<QualityPoint> {
+    let mut sum = [0.0; 5];
+    let mut count = 0;
+    for point in points {
+        for (s, v) in sum.iter_mut().zip(point.to_array()) {
+            *s += v;
+        }
+        count += 1;
+    }
+    (count > 0).then(|| QualityPoint::from_array(sum.map(|s| s / count as f64)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregates::EdgeConcept;
+    use crate::quality::RelationshipQuality;
+    use crate::value_objects::EntityRef;
+    use cim_domain_spaces::TopologicalSpaceId;
+
+    fn edge_with_quality(category: RelationshipCategory, quality: RelationshipQuality) -> EdgeConcept {
+        EdgeConcept::new(
+            "Edge",
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::person(Uuid::now_v7()),
+            category,
+        )
+        .with_quality(quality)
+    }
+
+    fn two_group_space() -> RelationshipSpace {
+        let mut space = RelationshipSpace::new("Clusters", TopologicalSpaceId::new());
+        for _ in 0..4 {
+            space
+                .add_edge(edge_with_quality(
+                    RelationshipCategory::Employment,
+                    RelationshipQuality::default_employment(),
+                ))
+                .unwrap();
+            space
+                .add_edge(edge_with_quality(
+                    RelationshipCategory::Friendship,
+                    RelationshipQuality::default_friendship(),
+                ))
+                .unwrap();
+        }
+        space
+    }
+
+    #[test]
+    fn test_kmeans_separates_groups() {
+        let space = two_group_space();
+        let service = ClusteringService::new(ClusteringConfig {
+            k: 2,
+            ..ClusteringConfig::default()
+        });
+
+        let clustering = service.cluster(&space).unwrap();
+        assert!(clustering.converged);
+        assert_eq!(clustering.clusters.len(), 2);
+        for cluster in &clustering.clusters {
+            assert_eq!(cluster.size(), 4);
+            let category = cluster.dominant_category.clone().unwrap();
+            assert!(cluster.members.iter().all(|id| space.edges[id].category == category));
+        }
+        assert!(clustering.inertia < 1e-9);
+    }
+
+    #[test]
+    fn test_kmeans_rejects_zero_k_and_caps_k() {
+        let space = two_group_space();
+        let zero = ClusteringService::new(ClusteringConfig {
+            k: 0,
+            ..ClusteringConfig::default()
+        });
+        assert!(matches!(zero.cluster(&space), Err(RelationshipError::InvalidConfiguration(_))));
+
+        // Only two distinct points exist, so k = 10 yields two clusters
+        let many = ClusteringService::new(ClusteringConfig {
+            k: 10,
+            ..ClusteringConfig::default()
+        });
+        assert_eq!(many.cluster(&space).unwrap().clusters.len(), 2);
+
+        let empty = RelationshipSpace::new("Empty", TopologicalSpaceId::new());
+        assert!(many.cluster(&empty).unwrap().clusters.is_empty());
+    }
+
+    #[test]
+    fn test_centroids_seed_tessellation() {
+        let mut space = two_group_space();
+        let clustering = ClusteringService::new(ClusteringConfig {
+            k: 2,
+            ..ClusteringConfig::default()
+        })
+        .cluster(&space)
+        .unwrap();
+
+        let tessellation = clustering.seed_tessellation(&mut space);
+        assert_eq!(tessellation.cells.len(), 2);
+        assert!(!space.is_tessellation_stale());
+
+        let member = clustering.clusters[0].members[0];
+        let point = space.edges[&member].quality_point();
+        assert_eq!(space.nearest_cell(&point).unwrap().id, clustering.clusters[0].id);
+    }
+
+    #[test]
+    fn test_cells_located_in_all_dimensions() {
+        // Two groups that differ only in duration and reciprocity
+        let mut space = RelationshipSpace::new("Clusters", TopologicalSpaceId::new());
+        for reciprocity in [0.0, 1.0] {
+            for _ in 0..3 {
+                let quality = RelationshipQuality {
+                    reciprocity,
+                    ..RelationshipQuality::default()
+                };
+                space
+                    .add_edge(edge_with_quality(RelationshipCategory::Friendship, quality))
+                    .unwrap();
+            }
+        }
+        let clustering = ClusteringService::new(ClusteringConfig {
+            k: 2,
+            ..ClusteringConfig::default()
+        })
+        .cluster(&space)
+        .unwrap();
+        clustering.seed_tessellation(&mut space);
+
+        for cluster in &clustering.clusters {
+            let point = space.edges[&cluster.members[0]].quality_point();
+            assert_eq!(space.nearest_cell(&point).unwrap().id, cluster.id);
+        }
+    }
+}
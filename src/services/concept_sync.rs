@@ -0,0 +1,279 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Conceptual Space Sync
+//!
+//! Every edge and hyperedge is also a Concept in the shared ConceptualSpace
+//! owned by cim-domain-spaces. When a relationship is created or its
+//! quality changes, the sync tells the spaces domain where its concept now
+//! sits:
+//!
+//! ```text
+//! relationship.events.edge_created              --+
+//! relationship.events.edge_quality_updated        |    ConceptUpsert
+//! relationship.events.hyperedge_created           +-->  spaces.commands.upsert_concept
+//! relationship.events.hyperedge_quality_updated --+
+//! ```
+//!
+//! ## Idempotency
+//!
+//! An upsert is built from its event alone and carries that event's ID as
+//! `upsert_id`, so replaying an event produces the very same upsert and the
+//! spaces domain can discard it. Within a process, the sync also skips
+//! events not newer than the last upsert sent for the relationship (by
+//! time, then by the time-ordered event ID), so a replayed stream
+//! publishes nothing it has already published.
+
+use crate::aggregates::RelationshipSpace;
+use crate::events::{EdgeEvent, HyperEdgeEvent, RelationshipEvent};
+use crate::nats::{RelationshipBus, Transport};
+use crate::quality::RelationshipQuality;
+use crate::value_objects::{RelationshipCategory, RelationshipId, ValidityPeriod};
+use crate::{RelationshipError, RelationshipResult};
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use cim_domain_spaces::{ConceptId, ConceptualSpaceId, Point3};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+/// Subject concept upserts are sent on
+pub const CONCEPT_UPSERT_SUBJECT: &str = "spaces.commands.upsert_concept";
+
+/// What a concept stands for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConceptKind {
+    /// A binary edge
+    Edge,
+    /// A hyperedge
+    HyperEdge,
+}
+
+/// Create or move a relationship's concept in the shared ConceptualSpace
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConceptUpsert {
+    /// ID of the relationship event behind the upsert (same on replay)
+    pub upsert_id: Uuid,
+    /// The RelationshipSpace's conceptual space
+    pub space_id: ConceptualSpaceId,
+    /// The concept
+    pub concept_id: ConceptId,
+    /// The relationship it stands for
+    pub relationship_id: RelationshipId,
+    /// Edge or hyperedge
+    pub kind: ConceptKind,
+    /// Relationship category
+    pub category: RelationshipCategory,
+    /// Relationship name (set on creation only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Quality of the relationship
+    pub quality: RelationshipQuality,
+    /// Position in the conceptual space, derived from the quality
+    pub position: Point3<f64>,
+    /// When the relationship reached this state
+    pub as_of: DateTime<Utc>,
+}
+
+/// Keeps the shared ConceptualSpace aligned with a RelationshipSpace
+#[derive(Debug, Clone, Default)]
+pub struct ConceptSync {
+    /// Time and ID of the last upsert sent per relationship
+    synced: HashMap<RelationshipId, (DateTime<Utc>, Uuid)>,
+}
+
+impl ConceptSync {
+    /// Create a sync that has sent nothing yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The upsert an event calls for, if any
+    ///
+    /// Quality updates of relationships not in the space, and events not
+    /// newer than the last upsert for their relationship, call for none.
+    pub fn upsert_for(&mut self, space: &RelationshipSpace, event: &RelationshipEvent) -> Option<ConceptUpsert> {
+        let upsert = Self::build(space, event)?;
+        if self
+            .synced
+            .get(&upsert.relationship_id)
+            .is_some_and(|synced| *synced >= (upsert.as_of, upsert.upsert_id))
+        {
+            return None;
+        }
+        self.synced.insert(upsert.relationship_id, (upsert.as_of, upsert.upsert_id));
+        Some(upsert)
+    }
+
+    /// Send the upsert an event calls for, returning it
+    pub async fn publish(
+        &mut self,
+        transport: &impl Transport,
+        space: &RelationshipSpace,
+        event: &RelationshipEvent,
+    ) -> RelationshipResult<Option<ConceptUpsert>> {
+        let Some(upsert) = self.upsert_for(space, event) else {
+            return Ok(None);
+        };
+        let payload =
+            serde_json::to_vec(&upsert).map_err(|e| RelationshipError::SerializationError(e.to_string()))?;
+        if let Err(e) = transport.publish(CONCEPT_UPSERT_SUBJECT, Bytes::from(payload)).await {
+            // Not sent: a later replay of the event must try again
+            self.synced.remove(&upsert.relationship_id);
+            return Err(e);
+        }
+        Ok(Some(upsert))
+    }
+
+    fn build(space: &RelationshipSpace, event: &RelationshipEvent) -> Option<ConceptUpsert> {
+        let upsert = |event_id, concept_id, relationship_id, kind, category, name, quality: RelationshipQuality, as_of| {
+            ConceptUpsert {
+                upsert_id: event_id,
+                space_id: space.id,
+                concept_id,
+                relationship_id,
+                kind,
+                category,
+                name,
+                position: quality.to_quality_point().to_point3(),
+                quality,
+                as_of,
+            }
+        };
+        match event {
+            RelationshipEvent::Edge(EdgeEvent::EdgeCreated(e)) => Some(upsert(
+                e.event_id,
+                e.concept_id,
+                e.edge_id,
+                ConceptKind::Edge,
+                e.category.clone(),
+                Some(e.name.clone()),
+                initial_quality(e.created_at),
+                e.created_at,
+            )),
+            RelationshipEvent::Edge(EdgeEvent::QualityUpdated(e)) => {
+                let edge = space.get_edge(&e.edge_id)?;
+                Some(upsert(
+                    e.event_id,
+                    edge.concept_id,
+                    e.edge_id,
+                    ConceptKind::Edge,
+                    edge.category.clone(),
+                    None,
+                    e.new_quality.clone(),
+                    e.updated_at,
+                ))
+            }
+            RelationshipEvent::HyperEdge(HyperEdgeEvent::HyperEdgeCreated(e)) => Some(upsert(
+                e.event_id,
+                e.concept_id,
+                e.hyperedge_id,
+                ConceptKind::HyperEdge,
+                e.category.clone(),
+                Some(e.name.clone()),
+                initial_quality(e.created_at),
+                e.created_at,
+            )),
+            RelationshipEvent::HyperEdge(HyperEdgeEvent::HyperEdgeQualityUpdated(e)) => {
+                let hyperedge = space.get_hyperedge(&e.hyperedge_id)?;
+                Some(upsert(
+                    e.event_id,
+                    hyperedge.concept_id,
+                    e.hyperedge_id,
+                    ConceptKind::HyperEdge,
+                    hyperedge.category.clone(),
+                    None,
+                    e.new_quality.clone(),
+                    e.updated_at,
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    /// Follow the relationship event stream, sending upserts for a shared
+    /// space
+    ///
+    /// Runs until the stream ends. Publish failures are logged.
+    pub async fn run<T: Transport>(
+        mut self,
+        space: Arc<RwLock<RelationshipSpace>>,
+        bus: RelationshipBus<T>,
+    ) -> RelationshipResult<()> {
+        let mut events = bus.subscribe_events().await?;
+        while let Some(event) = events.next().await {
+            let space = space.read().await;
+            if let Err(e) = self.publish(bus.transport(), &space, &event).await {
+                tracing::warn!("failed to sync concept of {}: {}", event.relationship_id(), e);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Quality a relationship is created with, dated to its creation so that
+/// rebuilding the upsert yields the same message
+fn initial_quality(created_at: DateTime<Utc>) -> RelationshipQuality {
+    RelationshipQuality {
+        duration: ValidityPeriod::ongoing(created_at),
+        ..RelationshipQuality::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{CreateEdge, EdgeCommand, RelationshipCommand};
+    use crate::nats::MockTransport;
+    use crate::value_objects::EntityRef;
+    use cim_domain::MessageIdentity;
+    use cim_domain_spaces::TopologicalSpaceId;
+
+    #[tokio::test]
+    async fn test_concept_sync_is_idempotent() {
+        let mut space = RelationshipSpace::new("Synced", TopologicalSpaceId::new());
+        let events = space
+            .handle_command(RelationshipCommand::Edge(EdgeCommand::CreateEdge(CreateEdge {
+                identity: MessageIdentity::new_root(),
+                edge_id: RelationshipId::new(),
+                source: EntityRef::person(Uuid::now_v7()),
+                target: EntityRef::organization(Uuid::now_v7()),
+                category: RelationshipCategory::Employment,
+                name: "Employment".to_string(),
+                quality: Some(RelationshipQuality::default_employment()),
+                created_by: "test".to_string(),
+            })))
+            .unwrap();
+        for event in &events {
+            space.apply_event(event).unwrap();
+        }
+
+        let transport = MockTransport::new();
+        let mut sync = ConceptSync::new();
+        let mut sent = Vec::new();
+        for event in &events {
+            sent.extend(sync.publish(&transport, &space, event).await.unwrap());
+        }
+        let edge = space.edges.values().next().unwrap();
+        assert_eq!(sent.len(), events.len());
+        assert!(sent.iter().all(|u| u.concept_id == edge.concept_id && u.space_id == space.id));
+        assert_eq!(sent.last().unwrap().position, edge.position);
+        assert_eq!(transport.published_on(CONCEPT_UPSERT_SUBJECT).len(), events.len());
+
+        // Replaying the stream sends nothing; a fresh sync rebuilds the
+        // same upserts
+        for event in &events {
+            assert!(sync.publish(&transport, &space, event).await.unwrap().is_none());
+        }
+        let rebuilt: Vec<ConceptUpsert> = events
+            .iter()
+            .filter_map(|event| ConceptSync::new().upsert_for(&space, event))
+            .collect();
+        assert_eq!(rebuilt, sent);
+    }
+}
@@ -0,0 +1,334 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Cross-Domain Consistency
+//!
+//! Cascades only happen for upstream events the domain actually received.
+//! A lost event, a purge that bypassed the event stream or a late
+//! integration leaves active edges pointing at entities that are gone. The
+//! checker finds them by asking the owning domains directly:
+//!
+//! ```text
+//! tick
+//!  |
+//!  +-- next `sample_size` active edges (rotating through the space)
+//!  +-- request {type}.query.get.{id} once per endpoint
+//!  |       null / empty             --> Missing
+//!  |       {"status": "dissolved"}  --> Inactive("dissolved")
+//!  |       anything else            --> Live
+//!  +-- ConsistencyReport { dangling references }
+//!  +-- (remediation on) SuspendEdge for each dangling edge
+//! ```
+//!
+//! The sample window rotates on every tick, so every active edge is checked
+//! once per `edges / sample_size` ticks without loading the upstream
+//! domains with the whole space at once. Remediation suspends rather than
+//! terminates: a wrong answer upstream is undone by resuming the edge.
+
+use crate::aggregates::{EdgeConcept, RelationshipSpace};
+use crate::commands::{EdgeCommand, RelationshipCommand, SuspendEdge};
+use crate::events::RelationshipEvent;
+use crate::nats::{RelationshipBus, Transport};
+use crate::value_objects::{EntityKey, EntityRef, RelationshipId};
+use crate::{RelationshipError, RelationshipResult};
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use cim_domain::MessageIdentity;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Actor remediation commands are issued as
+pub const CONSISTENCY_ACTOR: &str = "relationship.consistency";
+
+/// An endpoint as its owning domain reports it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EndpointStatus {
+    /// Exists and is active
+    Live,
+    /// Exists with a status other than `active`
+    Inactive(String),
+    /// Unknown to its domain
+    Missing,
+}
+
+impl EndpointStatus {
+    /// Check if active edges may still point at the endpoint
+    pub fn is_live(&self) -> bool {
+        matches!(self, EndpointStatus::Live)
+    }
+}
+
+/// An active edge pointing at an entity that is gone
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DanglingReference {
+    /// The edge
+    pub edge_id: RelationshipId,
+    /// The endpoint that is gone
+    pub entity: EntityRef,
+    /// What its domain reported
+    pub status: EndpointStatus,
+}
+
+/// Outcome of one consistency check
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsistencyReport {
+    /// Edges checked
+    pub edges_checked: usize,
+    /// Distinct entities queried
+    pub entities_checked: usize,
+    /// Dangling references found
+    pub dangling: Vec<DanglingReference>,
+    /// Remediation commands (empty unless remediation is on)
+    pub remediation: Vec<RelationshipCommand>,
+    /// When the check ran
+    pub checked_at: DateTime<Utc>,
+}
+
+impl ConsistencyReport {
+    /// Check if no dangling reference was found
+    pub fn is_consistent(&self) -> bool {
+        self.dangling.is_empty()
+    }
+}
+
+/// Periodically checks edge endpoints against their owning domains
+#[derive(Debug, Clone)]
+pub struct ConsistencyChecker {
+    sample_size: usize,
+    remediate: bool,
+    /// Last edge checked by the previous tick
+    cursor: Option<RelationshipId>,
+}
+
+impl Default for ConsistencyChecker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConsistencyChecker {
+    /// Create a checker sampling 100 edges per tick, reporting only
+    pub fn new() -> Self {
+        Self {
+            sample_size: 100,
+            remediate: false,
+            cursor: None,
+        }
+    }
+
+    /// Set how many edges each tick checks
+    pub fn with_sample_size(mut self, sample_size: usize) -> Self {
+        self.sample_size = sample_size.max(1);
+        self
+    }
+
+    /// Suspend dangling edges instead of only reporting them
+    pub fn with_remediation(mut self, remediate: bool) -> Self {
+        self.remediate = remediate;
+        self
+    }
+
+    /// Ask an entity's domain about it
+    pub async fn status(&self, transport: &impl Transport, entity: &EntityRef) -> RelationshipResult<EndpointStatus> {
+        let reply = transport.request(&entity.to_nats_subject(), Bytes::new()).await?;
+        if reply.is_empty() {
+            return Ok(EndpointStatus::Missing);
+        }
+        let body: serde_json::Value =
+            serde_json::from_slice(&reply).map_err(|e| RelationshipError::SerializationError(e.to_string()))?;
+        Ok(match body.get("status").and_then(|s| s.as_str()) {
+            _ if body.is_null() => EndpointStatus::Missing,
+            Some(status) if !status.eq_ignore_ascii_case("active") => EndpointStatus::Inactive(status.to_string()),
+            _ => EndpointStatus::Live,
+        })
+    }
+
+    /// Check the next sample of active edges
+    ///
+    /// Every endpoint is queried once per tick however many sampled edges
+    /// share it. Transport failures abort the tick: an unreachable domain
+    /// is not a missing entity.
+    pub async fn tick(
+        &mut self,
+        transport: &impl Transport,
+        space: &RelationshipSpace,
+        now: DateTime<Utc>,
+    ) -> RelationshipResult<ConsistencyReport> {
+        let sample = self.sample(space);
+        let mut statuses: HashMap<EntityKey, EndpointStatus> = HashMap::new();
+        let mut dangling = Vec::new();
+
+        for edge in &sample {
+            for endpoint in [&edge.source, &edge.target] {
+                let status = match statuses.get(&endpoint.key()) {
+                    Some(status) => status.clone(),
+                    None => {
+                        let status = self.status(transport, endpoint).await?;
+                        statuses.insert(endpoint.key(), status.clone());
+                        status
+                    }
+                };
+                if !status.is_live() {
+                    dangling.push(DanglingReference {
+                        edge_id: edge.id,
+                        entity: endpoint.clone(),
+                        status,
+                    });
+                }
+            }
+        }
+
+        let mut remediation = Vec::new();
+        if self.remediate {
+            let mut suspended = HashSet::new();
+            for reference in dangling.iter().filter(|r| suspended.insert(r.edge_id)) {
+                remediation.push(RelationshipCommand::Edge(EdgeCommand::SuspendEdge(SuspendEdge {
+                    identity: MessageIdentity::new_root(),
+                    edge_id: reference.edge_id,
+                    reason: Some(format!("dangling reference to {} ({:?})", reference.entity, reference.status)),
+                    suspended_by: CONSISTENCY_ACTOR.to_string(),
+                })));
+            }
+        }
+
+        self.cursor = sample.last().map(|e| e.id).or(self.cursor);
+        Ok(ConsistencyReport {
+            edges_checked: sample.len(),
+            entities_checked: statuses.len(),
+            dangling,
+            remediation,
+            checked_at: now,
+        })
+    }
+
+    /// The next `sample_size` active edges after the cursor, wrapping
+    /// around to the start of the space
+    fn sample<'a>(&self, space: &'a RelationshipSpace) -> Vec<&'a EdgeConcept> {
+        let mut active: Vec<&EdgeConcept> = space.edges.values().filter(|e| e.is_active()).collect();
+        active.sort_by_key(|e| e.id.as_uuid());
+        let start = self
+            .cursor
+            .map(|cursor| active.partition_point(|e| e.id.as_uuid() <= cursor.as_uuid()))
+            .unwrap_or(0);
+        active
+            .iter()
+            .cycle()
+            .skip(start)
+            .take(self.sample_size.min(active.len()))
+            .copied()
+            .collect()
+    }
+
+    /// Check a shared space every `period`, deciding and publishing the
+    /// remediation
+    ///
+    /// Runs until the task is aborted. Failed checks, refused commands and
+    /// publish failures are logged.
+    pub async fn run<T: Transport>(
+        mut self,
+        space: Arc<RwLock<RelationshipSpace>>,
+        bus: RelationshipBus<T>,
+        period: std::time::Duration,
+    ) {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            let report = {
+                let space = space.read().await;
+                self.tick(bus.transport(), &space, Utc::now()).await
+            };
+            let report = match report {
+                Ok(report) => report,
+                Err(e) => {
+                    tracing::warn!("consistency check failed: {}", e);
+                    continue;
+                }
+            };
+            for reference in &report.dangling {
+                tracing::warn!(
+                    "edge {} references {} ({:?})",
+                    reference.edge_id,
+                    reference.entity,
+                    reference.status
+                );
+            }
+
+            let mut events: Vec<RelationshipEvent> = Vec::new();
+            {
+                let mut space = space.write().await;
+                for command in report.remediation {
+                    match space.handle_command(command) {
+                        Ok(decided) => {
+                            for event in decided {
+                                match space.apply_event(&event) {
+                                    Ok(()) => events.push(event),
+                                    Err(e) => tracing::warn!("failed to apply remediation event: {}", e),
+                                }
+                            }
+                        }
+                        Err(e) => tracing::warn!("remediation refused: {}", e),
+                    }
+                }
+            }
+            for event in &events {
+                if let Err(e) = bus.publish_event(event).await {
+                    tracing::warn!("failed to publish remediation event: {}", e);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nats::MockTransport;
+    use crate::value_objects::RelationshipCategory;
+    use cim_domain_spaces::TopologicalSpaceId;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_dangling_references_found_and_remediated() {
+        let transport = MockTransport::new();
+        let alice = EntityRef::person(Uuid::now_v7());
+        let acme = EntityRef::organization(Uuid::now_v7());
+        let gone = EntityRef::organization(Uuid::now_v7());
+        transport.on_request(alice.to_nats_subject(), |_| Ok(Bytes::from_static(br#"{"status":"active"}"#)));
+        transport.on_request(acme.to_nats_subject(), |_| Ok(Bytes::from_static(br#"{"status":"dissolved"}"#)));
+        transport.on_request(gone.to_nats_subject(), |_| Ok(Bytes::from_static(b"null")));
+
+        let mut space = RelationshipSpace::new("Checked", TopologicalSpaceId::new());
+        for org in [&acme, &gone] {
+            let mut edge = EdgeConcept::new("Employment", alice.clone(), org.clone(), RelationshipCategory::Employment);
+            edge.activate().unwrap();
+            space.add_edge(edge).unwrap();
+        }
+
+        let mut checker = ConsistencyChecker::new().with_sample_size(1).with_remediation(true);
+        let first = checker.tick(&transport, &space, Utc::now()).await.unwrap();
+        let second = checker.tick(&transport, &space, Utc::now()).await.unwrap();
+        assert_eq!((first.edges_checked, second.edges_checked), (1, 1));
+        assert_ne!(first.dangling[0].edge_id, second.dangling[0].edge_id);
+
+        let statuses: Vec<EndpointStatus> = [&first, &second]
+            .iter()
+            .map(|r| r.dangling[0].status.clone())
+            .collect();
+        assert!(statuses.contains(&EndpointStatus::Missing));
+        assert!(statuses.contains(&EndpointStatus::Inactive("dissolved".to_string())));
+
+        for command in first.remediation.into_iter().chain(second.remediation) {
+            for event in space.handle_command(command).unwrap() {
+                space.apply_event(&event).unwrap();
+            }
+        }
+        assert!(space.edges.values().all(|e| !e.is_active()));
+
+        // Reporting only: nothing to remediate
+        let report = ConsistencyChecker::new().tick(&transport, &space, Utc::now()).await.unwrap();
+        assert!(report.is_consistent());
+    }
+}
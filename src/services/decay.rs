@@ -0,0 +1,345 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Quality Decay
+//!
+//! Relationships that see no activity grow stale. The decay service lowers
+//! the strength and trust of idle active relationships and emits the
+//! change as ordinary quality-updated events.
+//!
+//! ## Model
+//!
+//! ```text
+//! idle        = now - last activity
+//! decayed     = baseline * 0.5 ^ ((idle - grace) / half_life)
+//!
+//! baseline    = strength and trust at the last activity
+//! half_life   = per category, falling back to the default
+//! ```
+//!
+//! Activity is any change to a relationship other than the service's own
+//! decay events. The service notices it by version: a relationship whose
+//! version moved past the one its last decay event produced was touched,
+//! and decays afresh from its new quality.
+
+use crate::aggregates::RelationshipSpace;
+use crate::events::{
+    EdgeEvent, EdgeQualityUpdated, HyperEdgeEvent, HyperEdgeQualityUpdated, RelationshipEvent,
+};
+use crate::nats::{RelationshipBus, Transport};
+use crate::quality::RelationshipQuality;
+use crate::value_objects::{RelationshipCategory, RelationshipId};
+use chrono::{DateTime, Utc};
+use cim_domain::MessageIdentity;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+/// Reason recorded on decay events
+pub const DECAY_REASON: &str = "decay";
+
+/// Tuning for quality decay
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DecayConfig {
+    /// Days without activity before decay starts
+    pub grace_days: f64,
+    /// Days for strength and trust to halve, unless overridden per category
+    pub half_life_days: f64,
+    /// Per-category half-lives in days
+    #[serde(with = "crate::value_objects::category_map")]
+    pub category_half_life_days: HashMap<RelationshipCategory, f64>,
+    /// Smallest change in strength or trust worth an event
+    pub min_change: f64,
+}
+
+impl Default for DecayConfig {
+    fn default() -> Self {
+        Self {
+            grace_days: 30.0,
+            half_life_days: 180.0,
+            category_half_life_days: HashMap::new(),
+            min_change: 0.01,
+        }
+    }
+}
+
+impl DecayConfig {
+    /// Override the half-life of a category
+    pub fn with_half_life(mut self, category: RelationshipCategory, days: f64) -> Self {
+        self.category_half_life_days.insert(category, days);
+        self
+    }
+
+    /// Half-life of a category in days
+    pub fn half_life(&self, category: &RelationshipCategory) -> f64 {
+        self.category_half_life_days
+            .get(category)
+            .copied()
+            .unwrap_or(self.half_life_days)
+    }
+
+    /// Fraction of the baseline left after `idle_days` without activity
+    pub fn factor(&self, category: &RelationshipCategory, idle_days: f64) -> f64 {
+        let decaying = idle_days - self.grace_days;
+        let half_life = self.half_life(category);
+        if decaying <= 0.0 || half_life <= 0.0 {
+            1.0
+        } else {
+            0.5_f64.powf(decaying / half_life)
+        }
+    }
+}
+
+/// Quality of a relationship when it was last active
+#[derive(Debug, Clone)]
+struct Baseline {
+    since: DateTime<Utc>,
+    strength: f64,
+    trust: f64,
+    /// Version the last decay event left the relationship at
+    decayed_version: u64,
+}
+
+/// Decays the quality of idle relationships
+#[derive(Debug, Clone, Default)]
+pub struct QualityDecayService {
+    config: DecayConfig,
+    baselines: HashMap<RelationshipId, Baseline>,
+}
+
+impl QualityDecayService {
+    /// Create a decay service
+    pub fn new(config: DecayConfig) -> Self {
+        Self {
+            config,
+            baselines: HashMap::new(),
+        }
+    }
+
+    /// Get the tuning
+    pub fn config(&self) -> &DecayConfig {
+        &self.config
+    }
+
+    /// Compute the decay events due at `now`
+    ///
+    /// The events are not applied; apply them to the space (or publish
+    /// them) for the next tick to see the decayed quality.
+    pub fn tick(&mut self, space: &RelationshipSpace, now: DateTime<Utc>) -> Vec<RelationshipEvent> {
+        let mut events = Vec::new();
+
+        for edge in space.edges.values().filter(|e| e.is_active()) {
+            if let Some(quality) =
+                self.decayed(edge.id, &edge.category, &edge.quality, edge.version, edge.updated_at, now)
+            {
+                events.push(RelationshipEvent::Edge(EdgeEvent::QualityUpdated(EdgeQualityUpdated {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity::new_root(),
+                    edge_id: edge.id,
+                    old_quality: edge.quality.clone(),
+                    new_quality: quality,
+                    reason: DECAY_REASON.to_string(),
+                    updated_at: now,
+                })));
+            }
+        }
+
+        for hyperedge in space.hyperedges.values().filter(|h| h.is_active()) {
+            if let Some(quality) = self.decayed(
+                hyperedge.id,
+                &hyperedge.category,
+                &hyperedge.quality,
+                hyperedge.version,
+                hyperedge.updated_at,
+                now,
+            ) {
+                events.push(RelationshipEvent::HyperEdge(HyperEdgeEvent::HyperEdgeQualityUpdated(
+                    HyperEdgeQualityUpdated {
+                        event_id: Uuid::now_v7(),
+                        identity: MessageIdentity::new_root(),
+                        hyperedge_id: hyperedge.id,
+                        old_quality: hyperedge.quality.clone(),
+                        new_quality: quality,
+                        reason: DECAY_REASON.to_string(),
+                        updated_at: now,
+                    },
+                )));
+            }
+        }
+
+        // Forget relationships that left the space or stopped being active
+        self.baselines.retain(|id, _| {
+            space.get_edge(id).is_some_and(|e| e.is_active())
+                || space.get_hyperedge(id).is_some_and(|h| h.is_active())
+        });
+        events
+    }
+
+    fn decayed(
+        &mut self,
+        id: RelationshipId,
+        category: &RelationshipCategory,
+        quality: &RelationshipQuality,
+        version: u64,
+        updated_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Option<RelationshipQuality> {
+        let baseline = self
+            .baselines
+            .entry(id)
+            .and_modify(|b| {
+                if b.decayed_version != version {
+                    // Touched since the last decay: start over from here
+                    *b = Baseline {
+                        since: updated_at,
+                        strength: quality.strength,
+                        trust: quality.trust,
+                        decayed_version: version,
+                    };
+                }
+            })
+            .or_insert_with(|| Baseline {
+                since: updated_at,
+                strength: quality.strength,
+                trust: quality.trust,
+                decayed_version: version,
+            });
+
+        let idle_days = (now - baseline.since).num_seconds() as f64 / 86_400.0;
+        let factor = self.config.factor(category, idle_days);
+        let strength = baseline.strength * factor;
+        let trust = baseline.trust * factor;
+        if (quality.strength - strength).max(quality.trust - trust) < self.config.min_change {
+            return None;
+        }
+
+        // Applying the event bumps the version by one
+        baseline.decayed_version = version + 1;
+        let mut next = quality.clone();
+        next.strength = strength;
+        next.trust = trust;
+        Some(next)
+    }
+
+    /// Decay a shared space every `period`, applying and publishing the
+    /// events
+    ///
+    /// Runs until the task is aborted. Publish failures are logged and the
+    /// event stays applied locally.
+    pub async fn run<T: Transport>(
+        mut self,
+        space: Arc<RwLock<RelationshipSpace>>,
+        bus: RelationshipBus<T>,
+        period: std::time::Duration,
+    ) {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            let events = {
+                let mut space = space.write().await;
+                let events = self.tick(&space, Utc::now());
+                for event in &events {
+                    if let Err(e) = space.apply_event(event) {
+                        tracing::warn!("failed to apply decay event: {}", e);
+                    }
+                }
+                events
+            };
+            for event in &events {
+                if let Err(e) = bus.publish_event(event).await {
+                    tracing::warn!("failed to publish decay event: {}", e);
+                }
+            }
+        }
+    }
+}
+
+/// Apply a decay event to the relationship it targets
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregates::EdgeConcept;
+    use crate::value_objects::EntityRef;
+    use chrono::Duration;
+    use cim_domain_spaces::TopologicalSpaceId;
+
+    fn space_with_edge(category: RelationshipCategory) -> (RelationshipSpace, RelationshipId) {
+        let mut space = RelationshipSpace::new("Decay", TopologicalSpaceId::new());
+        let mut edge = EdgeConcept::new(
+            "Edge",
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::person(Uuid::now_v7()),
+            category,
+        );
+        edge.activate().unwrap();
+        let id = edge.id;
+        space.add_edge(edge).unwrap();
+        (space, id)
+    }
+
+    #[test]
+    fn test_decay_halves_after_half_life() {
+        let (mut space, id) = space_with_edge(RelationshipCategory::Friendship);
+        let config = DecayConfig::default().with_half_life(RelationshipCategory::Friendship, 10.0);
+        let mut service = QualityDecayService::new(config);
+        let start = space.edges[&id].quality.strength;
+        let now = Utc::now();
+
+        // Within the grace period nothing decays
+        assert!(service.tick(&space, now + Duration::days(20)).is_empty());
+
+        let events = service.tick(&space, now + Duration::days(40));
+        assert_eq!(events.len(), 1);
+        space.apply_event(&events[0]).unwrap();
+        let strength = space.edges[&id].quality.strength;
+        assert!((strength - start / 2.0).abs() < 0.01);
+
+        // Decay continues from the original baseline, not the decayed value
+        let events = service.tick(&space, now + Duration::days(50));
+        space.apply_event(&events[0]).unwrap();
+        assert!((space.edges[&id].quality.strength - start / 4.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_activity_resets_decay() {
+        let (mut space, id) = space_with_edge(RelationshipCategory::Employment);
+        let mut service = QualityDecayService::new(DecayConfig::default());
+        let now = Utc::now();
+
+        let events = service.tick(&space, now + Duration::days(400));
+        space.apply_event(&events[0]).unwrap();
+
+        // An outside quality update counts as activity
+        let edge = &space.edges[&id];
+        let mut quality = edge.quality.clone();
+        quality.strength = 0.9;
+        let update = EdgeEvent::QualityUpdated(EdgeQualityUpdated {
+            event_id: Uuid::now_v7(),
+            identity: MessageIdentity::new_root(),
+            edge_id: id,
+            old_quality: edge.quality.clone(),
+            new_quality: quality,
+            reason: "reviewed".to_string(),
+            updated_at: Utc::now(),
+        });
+        let edge = edge.apply_event_pure(&update).unwrap();
+        let touched_at = edge.updated_at;
+        space.add_edge(edge).unwrap();
+
+        assert!(service.tick(&space, touched_at + Duration::days(1)).is_empty());
+        assert_eq!(space.edges[&id].quality.strength, 0.9);
+    }
+
+    #[test]
+    fn test_config_round_trips_custom_categories() {
+        let config = DecayConfig::default()
+            .with_half_life(RelationshipCategory::Custom("Alliance".to_string()), 90.0);
+        let json = serde_json::to_string(&config).unwrap();
+        let parsed: DecayConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.half_life(&RelationshipCategory::Custom("Alliance".to_string())), 90.0);
+    }
+}
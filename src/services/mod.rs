@@ -5,6 +5,48 @@
 //! Services for the Relationship Domain
 //!
 //! Application services and domain services.
+//!
+//! - **HyperEdgeQualityAggregator**: hyperedge quality derived from participants
+//! - **ClusteringService**: k-means grouping of relationships in quality space
+//! - **QualityDecayService**: strength and trust decay for idle relationships
+//! - **TransitionScheduler**: commands run at future times, persisted across restarts
+//! - **EntityVerifier**: edge endpoints checked with their owning domains before creation
+//! - **CidResolver**: content of pinned entities and evidence, fetched and verified by CID
+//! - **ConceptSync**: relationship concepts upserted into the shared ConceptualSpace
+//! - **ConsistencyChecker**: sampled edges checked for endpoints gone upstream, optionally suspended
+//! - **Neo4jSync**: relationship graph mirrored into Neo4j with Cypher MERGE statements
+//! - **WebhookDispatcher**: matching events POSTed to registered URLs, signed, retried, logged and dead-lettered
+//! - **WebhookManager**: webhook registrations, kept in a key-value bucket
+
+mod aggregation;
+mod cid;
+mod clustering;
+mod concept_sync;
+mod consistency;
+mod decay;
+mod neo4j_sync;
+mod schedule;
+mod verification;
+mod webhooks;
 
-// Placeholder for relationship services
-// TODO: Implement RelationshipService, SimilarityService
+pub use aggregation::{
+    Aggregator, HyperEdgeQualityAggregator, ParticipantContribution, QualityAggregation,
+    AGGREGATION_REASON,
+};
+pub use cid::{cid_of, CidResolver, ObjectStore, TransportObjectStore, BLAKE3_MULTIHASH, OBJECT_STORE_SUBJECT, RAW_CODEC};
+pub use clustering::{Clustering, ClusteringConfig, ClusteringService, RelationshipCluster};
+pub use concept_sync::{ConceptKind, ConceptSync, ConceptUpsert, CONCEPT_UPSERT_SUBJECT};
+pub use consistency::{
+    ConsistencyChecker, ConsistencyReport, DanglingReference, EndpointStatus, CONSISTENCY_ACTOR,
+};
+pub use decay::{DecayConfig, QualityDecayService, DECAY_REASON};
+pub use neo4j_sync::{CypherExecutor, Neo4jSync, TransportCypherExecutor, CYPHER_EXECUTE_SUBJECT};
+pub use schedule::{ScheduledTransition, TransitionScheduler};
+pub use verification::{EntityVerifier, VerificationMode, VERIFICATION_EVIDENCE};
+#[cfg(feature = "webhooks")]
+pub use webhooks::HttpWebhookSender;
+pub use webhooks::{
+    sign, verify_signature, DeadLetter, Delivery, DeliveryAttempt, DeliveryLog, KvWebhookStore, RetryPolicy, Webhook,
+    WebhookCommand, WebhookDispatcher, WebhookFilter, WebhookManager, WebhookRegistry, WebhookResponse, WebhookSender,
+    WebhookStore, DEFAULT_LOG_CAPACITY, DELIVERY_HEADER, EVENT_TYPE_HEADER, SIGNATURE_HEADER, WEBHOOK_HEADER,
+};
@@ -0,0 +1,187 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Neo4j Sync
+//!
+//! Mirrors the relationship graph into Neo4j for teams that query with
+//! Cypher. The sink follows the relationship event stream and executes the
+//! statements `cypher_for_event` generates, one transaction per event:
+//!
+//! ```text
+//! relationship.events.>  --> cypher_for_event(shared space, event)
+//!                                        |
+//!                        CypherExecutor::execute (one tx)
+//!                                        |
+//!                   graph.cypher.execute --> Neo4j bridge
+//! ```
+//!
+//! The crate has no Neo4j driver: statements reach the database through a
+//! `CypherExecutor`. `TransportCypherExecutor` sends them, in the body of a
+//! Neo4j HTTP transaction, to a bridge service over the transport; a
+//! deployment with a driver at hand implements the trait directly.
+//!
+//! Statements describe the relationship's current state, so a failed event
+//! is repaired by the next one touching the same relationship, or by
+//! `mirror` of the whole space.
+
+use crate::aggregates::RelationshipSpace;
+use crate::events::RelationshipEvent;
+use crate::interop::{cypher_for_event, cypher_for_space, CypherStatement};
+use crate::nats::{RelationshipBus, Transport};
+use crate::{RelationshipError, RelationshipResult};
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::StreamExt;
+use serde_json::json;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Subject Cypher transactions are requested on
+pub const CYPHER_EXECUTE_SUBJECT: &str = "graph.cypher.execute";
+
+/// Runs Cypher against a graph database
+#[async_trait]
+pub trait CypherExecutor: Send + Sync {
+    /// Execute statements in one transaction
+    async fn execute(&self, statements: &[CypherStatement]) -> RelationshipResult<()>;
+}
+
+/// Executor reached by request over a transport
+///
+/// The request body is a Neo4j HTTP transaction (`{"statements": [...]}`).
+/// An empty reply, or one with an empty `errors` list, means committed.
+#[derive(Debug, Clone)]
+pub struct TransportCypherExecutor<T: Transport> {
+    transport: T,
+}
+
+impl<T: Transport> TransportCypherExecutor<T> {
+    /// Request transactions over a transport
+    pub fn new(transport: T) -> Self {
+        Self { transport }
+    }
+}
+
+#[async_trait]
+impl<T: Transport> CypherExecutor for TransportCypherExecutor<T> {
+    async fn execute(&self, statements: &[CypherStatement]) -> RelationshipResult<()> {
+        let body = serde_json::to_vec(&json!({ "statements": statements }))
+            .map_err(|e| RelationshipError::SerializationError(e.to_string()))?;
+        let reply = self.transport.request(CYPHER_EXECUTE_SUBJECT, Bytes::from(body)).await?;
+        if reply.is_empty() {
+            return Ok(());
+        }
+        let reply: serde_json::Value =
+            serde_json::from_slice(&reply).map_err(|e| RelationshipError::SerializationError(e.to_string()))?;
+        match reply.get("errors").and_then(|e| e.as_array()) {
+            Some(errors) if !errors.is_empty() => Err(RelationshipError::TransportError(format!(
+                "cypher transaction failed: {}",
+                serde_json::Value::Array(errors.clone())
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Mirrors a RelationshipSpace into Neo4j
+#[derive(Debug)]
+pub struct Neo4jSync<E: CypherExecutor> {
+    executor: E,
+}
+
+impl<E: CypherExecutor> Neo4jSync<E> {
+    /// Mirror through an executor
+    pub fn new(executor: E) -> Self {
+        Self { executor }
+    }
+
+    /// Mirror every relationship of a space, in one transaction
+    ///
+    /// Used for the initial load and to repair drift.
+    pub async fn mirror(&self, space: &RelationshipSpace) -> RelationshipResult<usize> {
+        let statements = cypher_for_space(space);
+        if !statements.is_empty() {
+            self.executor.execute(&statements).await?;
+        }
+        Ok(statements.len())
+    }
+
+    /// Mirror the relationship an event touched, returning the statements
+    /// executed
+    ///
+    /// The space must already have the event applied.
+    pub async fn apply(
+        &self,
+        space: &RelationshipSpace,
+        event: &RelationshipEvent,
+    ) -> RelationshipResult<Vec<CypherStatement>> {
+        let statements = cypher_for_event(space, event);
+        if !statements.is_empty() {
+            self.executor.execute(&statements).await?;
+        }
+        Ok(statements)
+    }
+
+    /// Follow the relationship event stream, mirroring a shared space
+    ///
+    /// The space is kept current by whoever applies the events; the sync
+    /// only reads it. Runs until the stream ends. Failures are logged.
+    pub async fn run<T: Transport>(
+        self,
+        space: Arc<RwLock<RelationshipSpace>>,
+        bus: RelationshipBus<T>,
+    ) -> RelationshipResult<()> {
+        let mut events = bus.subscribe_events().await?;
+        while let Some(event) = events.next().await {
+            let space = space.read().await;
+            if let Err(e) = self.apply(&space, &event).await {
+                tracing::warn!("failed to mirror {} into neo4j: {}", event.relationship_id(), e);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregates::EdgeConcept;
+    use crate::nats::MockTransport;
+    use crate::value_objects::{EntityRef, RelationshipCategory};
+    use cim_domain_spaces::TopologicalSpaceId;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_neo4j_sync_executes_transactions() {
+        let transport = MockTransport::new();
+        transport.on_request(CYPHER_EXECUTE_SUBJECT, |message| {
+            let body: serde_json::Value = serde_json::from_slice(&message.payload).unwrap();
+            let count = body["statements"].as_array().unwrap().len();
+            Ok(Bytes::from(serde_json::to_vec(&json!({ "results": vec![json!({}); count], "errors": [] })).unwrap()))
+        });
+
+        let mut space = RelationshipSpace::new("Mirrored", TopologicalSpaceId::new());
+        let mut edge = EdgeConcept::new(
+            "Employment",
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::organization(Uuid::now_v7()),
+            RelationshipCategory::Employment,
+        );
+        edge.activate().unwrap();
+        space.add_edge(edge).unwrap();
+
+        let sync = Neo4jSync::new(TransportCypherExecutor::new(transport));
+        assert_eq!(sync.mirror(&space).await.unwrap(), 2);
+
+        let failing = MockTransport::new();
+        failing.on_request(CYPHER_EXECUTE_SUBJECT, |_| {
+            Ok(Bytes::from_static(br#"{"errors":[{"code":"Neo.ClientError"}]}"#))
+        });
+        let sync = Neo4jSync::new(TransportCypherExecutor::new(failing));
+        assert!(matches!(
+            sync.mirror(&space).await,
+            Err(RelationshipError::TransportError(_))
+        ));
+    }
+}
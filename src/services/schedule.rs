@@ -0,0 +1,445 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Scheduled Transitions
+//!
+//! Many transitions are known in advance: an employment starts on the
+//! contract date, a membership ends with its period. The scheduler holds
+//! commands until they fall due, then decides them against the space like
+//! any other command and emits the resulting events.
+//!
+//! ## Lifecycle
+//!
+//! ```text
+//! schedule(due_at, command)  -->  pending (sorted by due_at)
+//! tick(now)                  -->  due commands decided in order
+//!                                 events applied to a copy of the space
+//!                                 refused commands dropped with a warning
+//!                            -->  live edges past their validity expired
+//!                            -->  consent requests past their deadline expired
+//!                            -->  copy replaces the space once the schedule is saved
+//! ```
+//!
+//! The expiry sweeps catch edges whose end was never scheduled, so an
+//! edge's state never outlives its validity period, nor a consent request
+//! its deadline, by more than a tick.
+//!
+//! A scheduler opened on a file writes every change there, so schedules
+//! survive restarts. Commands overdue at startup run on the first tick.
+
+use crate::aggregates::{EdgeConcept, EdgeState, RelationshipSpace};
+use crate::commands::{ActivateEdge, EdgeCommand, ExpireConsent, ExpireEdge, RelationshipCommand};
+use crate::events::RelationshipEvent;
+use crate::nats::{RelationshipBus, Transport};
+use crate::{RelationshipError, RelationshipResult};
+use chrono::{DateTime, Utc};
+use cim_domain::MessageIdentity;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+/// A command waiting for its time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledTransition {
+    /// Schedule entry ID
+    pub id: Uuid,
+    /// When the command runs
+    pub due_at: DateTime<Utc>,
+    /// The command to decide
+    pub command: RelationshipCommand,
+    /// Who scheduled it
+    pub scheduled_by: String,
+    /// When it was scheduled
+    pub scheduled_at: DateTime<Utc>,
+}
+
+/// Persisted scheduler state
+#[derive(Default, Serialize, Deserialize)]
+struct ScheduleFile {
+    pending: Vec<ScheduledTransition>,
+}
+
+/// Runs relationship commands at future times
+#[derive(Debug, Clone, Default)]
+pub struct TransitionScheduler {
+    /// Pending entries, ordered by due time
+    pending: Vec<ScheduledTransition>,
+    /// File every change is written to
+    path: Option<PathBuf>,
+}
+
+impl TransitionScheduler {
+    /// Create an in-memory scheduler
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open a scheduler persisted at `path`, loading its pending entries
+    ///
+    /// A missing file starts an empty schedule.
+    pub fn open(path: impl AsRef<Path>) -> RelationshipResult<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = match std::fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str::<ScheduleFile>(&json)
+                .map_err(|e| RelationshipError::InvalidConfiguration(format!("{}: {}", path.display(), e)))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => ScheduleFile::default(),
+            Err(e) => return Err(RelationshipError::InvalidConfiguration(format!("{}: {}", path.display(), e))),
+        };
+        let mut scheduler = Self {
+            pending: file.pending,
+            path: Some(path),
+        };
+        scheduler.pending.sort_by_key(|t| t.due_at);
+        Ok(scheduler)
+    }
+
+    /// Pending entries, earliest first
+    pub fn pending(&self) -> &[ScheduledTransition] {
+        &self.pending
+    }
+
+    /// When the next entry falls due
+    pub fn next_due(&self) -> Option<DateTime<Utc>> {
+        self.pending.first().map(|t| t.due_at)
+    }
+
+    /// Run a command at `due_at`
+    pub fn schedule(
+        &mut self,
+        due_at: DateTime<Utc>,
+        command: RelationshipCommand,
+        scheduled_by: impl Into<String>,
+    ) -> RelationshipResult<Uuid> {
+        let entry = ScheduledTransition {
+            id: Uuid::now_v7(),
+            due_at,
+            command,
+            scheduled_by: scheduled_by.into(),
+            scheduled_at: Utc::now(),
+        };
+        let id = entry.id;
+        let idx = self.pending.partition_point(|t| t.due_at <= due_at);
+        self.pending.insert(idx, entry);
+        self.persist()?;
+        Ok(id)
+    }
+
+    /// Schedule an edge's activation at the start of its validity and its
+    /// expiry at the end
+    ///
+    /// Only transitions still ahead of the edge are scheduled: activation
+    /// for a proposed edge starting in the future, expiry for a live edge
+    /// whose validity ends.
+    pub fn schedule_validity(&mut self, edge: &EdgeConcept, scheduled_by: impl Into<String>) -> RelationshipResult<Vec<Uuid>> {
+        let scheduled_by = scheduled_by.into();
+        let mut ids = Vec::new();
+        if edge.state == EdgeState::Proposed && edge.validity.starts_at > Utc::now() {
+            let activate = EdgeCommand::ActivateEdge(ActivateEdge {
+                identity: MessageIdentity::new_root(),
+                edge_id: edge.id,
+                activated_by: scheduled_by.clone(),
+            });
+            ids.push(self.schedule(edge.validity.starts_at, activate.into(), scheduled_by.clone())?);
+        }
+        if let (Some(ends_at), false) = (edge.validity.ends_at, edge.state.is_closed()) {
+            let expire = EdgeCommand::ExpireEdge(ExpireEdge {
+                identity: MessageIdentity::new_root(),
+                edge_id: edge.id,
+                as_of: ends_at,
+            });
+            ids.push(self.schedule(ends_at, expire.into(), scheduled_by)?);
+        }
+        Ok(ids)
+    }
+
+    /// Remove a pending entry
+    pub fn cancel(&mut self, id: Uuid) -> RelationshipResult<Option<ScheduledTransition>> {
+        let Some(idx) = self.pending.iter().position(|t| t.id == id) else {
+            return Ok(None);
+        };
+        let entry = self.pending.remove(idx);
+        self.persist()?;
+        Ok(Some(entry))
+    }
+
+    /// Run every entry due at `now`, then expire live edges whose validity
+    /// has ended and consent requests whose deadline has passed, applying
+    /// the events to the space
+    ///
+    /// Entries run in due order, each against the space as the earlier
+    /// ones left it. An entry the space refuses (e.g. the edge was already
+    /// terminated by hand) is dropped with a warning. The tick works on a
+    /// copy of the space and of the schedule: on error neither changes, and
+    /// on success the space holds exactly the returned events.
+    pub fn tick(&mut self, space: &mut RelationshipSpace, now: DateTime<Utc>) -> RelationshipResult<Vec<RelationshipEvent>> {
+        let mut next = space.clone();
+        let mut pending = self.pending.clone();
+        let due = pending.partition_point(|t| t.due_at <= now);
+        let mut events = Vec::new();
+        for entry in pending.drain(..due) {
+            match Self::decide(&mut next, entry.command) {
+                Ok(decided) => events.extend(decided),
+                Err(e) => tracing::warn!("scheduled transition {} refused: {}", entry.id, e),
+            }
+        }
+        for edge_id in next.expired_edges(now) {
+            let expire = EdgeCommand::ExpireEdge(ExpireEdge {
+                identity: MessageIdentity::new_root(),
+                edge_id,
+                as_of: now,
+            });
+            match Self::decide(&mut next, expire.into()) {
+                Ok(decided) => events.extend(decided),
+                Err(e) => tracing::warn!("expiry of edge {} refused: {}", edge_id, e),
+            }
+        }
+        for edge_id in next.expired_consents(now) {
+            let expire = EdgeCommand::ExpireConsent(ExpireConsent {
+                identity: MessageIdentity::new_root(),
+                edge_id,
+            });
+            match Self::decide(&mut next, expire.into()) {
+                Ok(decided) => events.extend(decided),
+                Err(e) => tracing::warn!("consent expiry of edge {} refused: {}", edge_id, e),
+            }
+        }
+        if due > 0 {
+            let previous = std::mem::replace(&mut self.pending, pending);
+            if let Err(e) = self.persist() {
+                self.pending = previous;
+                return Err(e);
+            }
+        }
+        *space = next;
+        Ok(events)
+    }
+
+    /// Decide a command and apply its events, all or nothing
+    fn decide(space: &mut RelationshipSpace, command: RelationshipCommand) -> RelationshipResult<Vec<RelationshipEvent>> {
+        let decided = space.handle_command(command)?;
+        let mut next = space.clone();
+        decided.iter().try_for_each(|event| next.apply_event(event))?;
+        *space = next;
+        Ok(decided)
+    }
+
+    /// Run due entries against a shared space every `period`, publishing
+    /// the events
+    ///
+    /// Runs until the task is aborted. Entries can be scheduled through the
+    /// shared scheduler meanwhile.
+    pub async fn run<T: Transport>(
+        scheduler: Arc<RwLock<Self>>,
+        space: Arc<RwLock<RelationshipSpace>>,
+        bus: RelationshipBus<T>,
+        period: std::time::Duration,
+    ) {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            let events = {
+                let mut space = space.write().await;
+                match scheduler.write().await.tick(&mut space, Utc::now()) {
+                    Ok(events) => events,
+                    Err(e) => {
+                        tracing::warn!("failed to persist schedule: {}", e);
+                        continue;
+                    }
+                }
+            };
+            for event in &events {
+                if let Err(e) = bus.publish_event(event).await {
+                    tracing::warn!("failed to publish scheduled event: {}", e);
+                }
+            }
+        }
+    }
+
+    /// Write the pending entries to the scheduler's file, if any
+    ///
+    /// Writes a sibling file and renames it over the old one, so a crash
+    /// never leaves a truncated schedule.
+    fn persist(&self) -> RelationshipResult<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let json = serde_json::to_string_pretty(&ScheduleFile {
+            pending: self.pending.clone(),
+        })
+        .map_err(|e| RelationshipError::SerializationError(e.to_string()))?;
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, json)
+            .and_then(|()| std::fs::rename(&tmp, path))
+            .map_err(|e| RelationshipError::InvalidConfiguration(format!("{}: {}", path.display(), e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value_objects::{EntityRef, RelationshipCategory, ValidityPeriod};
+    use chrono::Duration;
+    use cim_domain_spaces::TopologicalSpaceId;
+
+    #[test]
+    fn test_scheduled_validity() {
+        let now = Utc::now();
+        let edge = EdgeConcept::new(
+            "Contract",
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::organization(Uuid::now_v7()),
+            RelationshipCategory::Employment,
+        )
+        .with_validity(ValidityPeriod {
+            starts_at: now + Duration::days(30),
+            ends_at: Some(now + Duration::days(395)),
+            end_reason: None,
+        });
+        let edge_id = edge.id;
+        let mut space = RelationshipSpace::new("HR", TopologicalSpaceId::new());
+        space.add_edge(edge.clone()).unwrap();
+
+        let path = std::env::temp_dir().join(format!("schedule-{}.json", Uuid::now_v7()));
+        let mut scheduler = TransitionScheduler::open(&path).unwrap();
+        assert_eq!(scheduler.schedule_validity(&edge, "hr").unwrap().len(), 2);
+
+        // Restarting keeps the schedule
+        let mut scheduler = TransitionScheduler::open(&path).unwrap();
+        assert_eq!(scheduler.pending().len(), 2);
+        assert_eq!(scheduler.next_due(), Some(now + Duration::days(30)));
+
+        assert!(scheduler.tick(&mut space, now).unwrap().is_empty());
+        scheduler.tick(&mut space, now + Duration::days(31)).unwrap();
+        assert_eq!(space.get_edge(&edge_id).unwrap().state, EdgeState::Active);
+
+        // Overdue entries run on the next tick, ending the edge at its
+        // validity end
+        let events = scheduler.tick(&mut space, now + Duration::days(500)).unwrap();
+        assert_eq!(events.len(), 1);
+        let edge = space.get_edge(&edge_id).unwrap();
+        assert_eq!(edge.state, EdgeState::Terminated);
+        assert_eq!(edge.validity.ends_at, Some(now + Duration::days(395)));
+        assert!(TransitionScheduler::open(&path).unwrap().pending().is_empty());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_unscheduled_expiry() {
+        let now = Utc::now();
+        let mut edge = EdgeConcept::new(
+            "Lease",
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::organization(Uuid::now_v7()),
+            RelationshipCategory::Custom("Lease".to_string()),
+        )
+        .with_validity(ValidityPeriod::fixed_term(now - Duration::days(10), now + Duration::days(10)));
+        edge.activate().unwrap();
+        let edge_id = edge.id;
+        let mut space = RelationshipSpace::new("Leases", TopologicalSpaceId::new());
+        space.add_edge(edge).unwrap();
+
+        // Nothing scheduled; the sweep still ends the edge
+        let mut scheduler = TransitionScheduler::new();
+        assert!(scheduler.tick(&mut space, now).unwrap().is_empty());
+        let events = scheduler.tick(&mut space, now + Duration::days(11)).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(space.get_edge(&edge_id).unwrap().state, EdgeState::Terminated);
+        assert!(space.expired_edges(now + Duration::days(11)).is_empty());
+        assert!(scheduler.tick(&mut space, now + Duration::days(12)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_expiry_passes_guards() {
+        use crate::aggregates::TransitionGuards;
+        use crate::commands::TerminateEdge;
+
+        let now = Utc::now();
+        let guards = TransitionGuards::new().with_edge_guard(
+            RelationshipCategory::Ownership,
+            EdgeState::Terminated,
+            "legal",
+            |_| Err("needs Legal formality".to_string()),
+        );
+        let mut edge = EdgeConcept::new(
+            "Stake",
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::organization(Uuid::now_v7()),
+            RelationshipCategory::Ownership,
+        )
+        .with_validity(ValidityPeriod::fixed_term(now - Duration::days(10), now + Duration::days(10)));
+        edge.activate().unwrap();
+        let edge_id = edge.id;
+        let mut space = RelationshipSpace::new("Cap Table", TopologicalSpaceId::new()).with_transition_guards(guards);
+        space.add_edge(edge).unwrap();
+
+        // Ending it by hand is guarded, the end of its validity is not
+        let terminate = EdgeCommand::TerminateEdge(TerminateEdge {
+            identity: MessageIdentity::new_root(),
+            edge_id,
+            reason: "sold".to_string(),
+            terminated_by: "test".to_string(),
+        });
+        assert!(space.handle_command(terminate.into()).is_err());
+        let events = TransitionScheduler::new().tick(&mut space, now + Duration::days(11)).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(space.get_edge(&edge_id).unwrap().state, EdgeState::Terminated);
+    }
+
+    #[test]
+    fn test_failed_tick_changes_nothing() {
+        let now = Utc::now();
+        let edge = EdgeConcept::new(
+            "Contract",
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::organization(Uuid::now_v7()),
+            RelationshipCategory::Employment,
+        )
+        .with_validity(ValidityPeriod::fixed_term(now + Duration::days(1), now + Duration::days(30)));
+        let edge_id = edge.id;
+        let mut space = RelationshipSpace::new("HR", TopologicalSpaceId::new());
+        space.add_edge(edge.clone()).unwrap();
+
+        let dir = std::env::temp_dir().join(format!("schedule-{}", Uuid::now_v7()));
+        std::fs::create_dir(&dir).unwrap();
+        let mut scheduler = TransitionScheduler::open(dir.join("schedule.json")).unwrap();
+        scheduler.schedule_validity(&edge, "hr").unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        // The schedule cannot be saved, so the activation is not kept
+        assert!(scheduler.tick(&mut space, now + Duration::days(2)).is_err());
+        assert_eq!(space.get_edge(&edge_id).unwrap().state, EdgeState::Proposed);
+        assert_eq!(scheduler.pending().len(), 2);
+    }
+
+    #[test]
+    fn test_consent_expiry_sweep() {
+        use crate::commands::RequestConsent;
+
+        let now = Utc::now();
+        let edge = EdgeConcept::new(
+            "Friends",
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::person(Uuid::now_v7()),
+            RelationshipCategory::Friendship,
+        );
+        let edge_id = edge.id;
+        let mut space = RelationshipSpace::new("Social", TopologicalSpaceId::new());
+        space.add_edge(edge).unwrap();
+        let request = EdgeCommand::RequestConsent(RequestConsent {
+            identity: MessageIdentity::new_root(),
+            edge_id,
+            expires_at: Some(now - Duration::minutes(1)),
+            requested_by: "test".to_string(),
+        });
+        for event in space.handle_command(request.into()).unwrap() {
+            space.apply_event(&event).unwrap();
+        }
+
+        let events = TransitionScheduler::new().tick(&mut space, now).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(space.get_edge(&edge_id).unwrap().state, EdgeState::Rejected);
+    }
+}
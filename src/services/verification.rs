@@ -0,0 +1,210 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Entity Verification
+//!
+//! Edges reference entities owned by other domains. Before a `CreateEdge`
+//! is decided, the verifier can ask those domains whether the endpoints
+//! exist, on each endpoint's `EntityRef::to_nats_subject`:
+//!
+//! ```text
+//! CreateEdge(alice -> acme)
+//!     |
+//!     +-- request person.query.get.{alice}          -> entity
+//!     +-- request organization.query.get.{acme}     -> null
+//!     |
+//!     Reject    ==>  EntityNotFound
+//!     Suspect   ==>  CreateEdge, AddEdgeEvidence(report), ProgressKnowledge(Suspected)
+//!     Skip      ==>  CreateEdge (no requests sent)
+//! ```
+//!
+//! A suspected edge cites the verification report (the missing endpoints)
+//! as evidence, by its CID, so it passes the aggregate's knowledge
+//! thresholds like any other progression.
+//!
+//! A reply of `null` (or an empty reply) means the entity does not exist;
+//! any other reply means it does. Transport failures are returned as
+//! errors in every mode: an unreachable domain is not a missing entity.
+
+use crate::aggregates::RelationshipSpace;
+use crate::commands::{AddEdgeEvidence, CreateEdge, EdgeCommand, ProgressKnowledge, RelationshipCommand};
+use crate::events::RelationshipEvent;
+use crate::nats::Transport;
+use crate::services::cid_of;
+use crate::value_objects::EntityRef;
+use crate::{RelationshipError, RelationshipResult};
+use bytes::Bytes;
+use cim_domain::MessageIdentity;
+use cim_domain_spaces::KnowledgeLevel;
+use serde::{Deserialize, Serialize};
+
+/// Evidence type of a verification report
+pub const VERIFICATION_EVIDENCE: &str = "endpoint_verification";
+
+/// What happens to an edge whose endpoints cannot be found
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VerificationMode {
+    /// Refuse the edge
+    Reject,
+    /// Create the edge, marked as only suspected
+    Suspect,
+    /// Do not verify
+    #[default]
+    Skip,
+}
+
+/// Verifies edge endpoints with their owning domains
+#[derive(Debug, Clone, Default)]
+pub struct EntityVerifier {
+    mode: VerificationMode,
+}
+
+impl EntityVerifier {
+    /// Create a verifier that skips verification
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the verification mode
+    pub fn with_mode(mut self, mode: VerificationMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// The verification mode
+    pub fn mode(&self) -> VerificationMode {
+        self.mode
+    }
+
+    /// Ask an entity's domain whether it exists
+    pub async fn exists(&self, transport: &impl Transport, entity: &EntityRef) -> RelationshipResult<bool> {
+        let reply = transport.request(&entity.to_nats_subject(), Bytes::new()).await?;
+        if reply.is_empty() {
+            return Ok(false);
+        }
+        let body: serde_json::Value =
+            serde_json::from_slice(&reply).map_err(|e| RelationshipError::SerializationError(e.to_string()))?;
+        Ok(!body.is_null())
+    }
+
+    /// Endpoints of an edge that their domains do not know
+    pub async fn missing_endpoints(
+        &self,
+        transport: &impl Transport,
+        create: &CreateEdge,
+    ) -> RelationshipResult<Vec<EntityRef>> {
+        let mut missing = Vec::new();
+        for endpoint in [&create.source, &create.target] {
+            if !self.exists(transport, endpoint).await? {
+                missing.push(endpoint.clone());
+            }
+        }
+        Ok(missing)
+    }
+
+    /// Decide a command against the space, verifying the endpoints of a
+    /// `CreateEdge` first
+    ///
+    /// Other commands are decided unchanged.
+    pub async fn decide(
+        &self,
+        transport: &impl Transport,
+        space: &RelationshipSpace,
+        cmd: RelationshipCommand,
+    ) -> RelationshipResult<Vec<RelationshipEvent>> {
+        let create = match &cmd {
+            RelationshipCommand::Edge(EdgeCommand::CreateEdge(c)) if self.mode != VerificationMode::Skip => c.clone(),
+            _ => return space.handle_command(cmd),
+        };
+
+        let missing = self.missing_endpoints(transport, &create).await?;
+        if missing.is_empty() {
+            return space.handle_command(cmd);
+        }
+        let names = missing.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ");
+        if self.mode == VerificationMode::Reject {
+            return Err(RelationshipError::EntityNotFound(names));
+        }
+
+        let report = serde_json::to_vec(&serde_json::json!({
+            "edge_id": create.edge_id,
+            "missing": missing,
+        }))
+        .map_err(|e| RelationshipError::SerializationError(e.to_string()))?;
+        let mut space = space.clone();
+        let mut events = Vec::new();
+        space.run_command(cmd, &mut events)?;
+        space.run_command(
+            EdgeCommand::AddEdgeEvidence(AddEdgeEvidence {
+                identity: MessageIdentity::new_caused_by(&create.identity),
+                edge_id: create.edge_id,
+                evidence_cid: cid_of(&report),
+                evidence_type: VERIFICATION_EVIDENCE.to_string(),
+            }),
+            &mut events,
+        )?;
+        space.run_command(
+            EdgeCommand::ProgressKnowledge(ProgressKnowledge {
+                identity: MessageIdentity::new_caused_by(&create.identity),
+                edge_id: create.edge_id,
+                to_level: KnowledgeLevel::Suspected,
+                confidence: 0.0,
+                reason: format!("unverified endpoints: {}", names),
+            }),
+            &mut events,
+        )?;
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nats::MockTransport;
+    use crate::value_objects::{RelationshipCategory, RelationshipId};
+    use cim_domain_spaces::TopologicalSpaceId;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_verify_endpoints() {
+        let transport = MockTransport::new();
+        let alice = EntityRef::person(Uuid::now_v7());
+        let acme = EntityRef::organization(Uuid::now_v7());
+        transport.on_request(alice.to_nats_subject(), |_| Ok(Bytes::from_static(br#"{"name":"Alice"}"#)));
+        transport.on_request(acme.to_nats_subject(), |_| Ok(Bytes::from_static(b"null")));
+
+        let space = RelationshipSpace::new("Verified", TopologicalSpaceId::new());
+        let create = || {
+            RelationshipCommand::Edge(EdgeCommand::CreateEdge(CreateEdge {
+                identity: MessageIdentity::new_root(),
+                edge_id: RelationshipId::new(),
+                source: alice.clone(),
+                target: acme.clone(),
+                category: RelationshipCategory::Employment,
+                name: "Employment".to_string(),
+                quality: None,
+                created_by: "test".to_string(),
+            }))
+        };
+
+        let rejecting = EntityVerifier::new().with_mode(VerificationMode::Reject);
+        let err = rejecting.decide(&transport, &space, create()).await.unwrap_err();
+        assert!(matches!(err, RelationshipError::EntityNotFound(_)));
+
+        let suspecting = EntityVerifier::new().with_mode(VerificationMode::Suspect);
+        let mut space = space;
+        for event in suspecting.decide(&transport, &space, create()).await.unwrap() {
+            space.apply_event(&event).unwrap();
+        }
+        let edge = space.edges.values().next().unwrap();
+        assert_eq!(edge.knowledge_level, KnowledgeLevel::Suspected);
+        assert_eq!(edge.evidence_cids.len(), 1);
+
+        // Skipping sends no requests
+        let sent = transport.published().len();
+        EntityVerifier::new().decide(&transport, &space, create()).await.unwrap();
+        assert_eq!(transport.published().len(), sent);
+    }
+}
@@ -0,0 +1,1222 @@
+/*
+ * Copyright (c) 2025 - Cowboy AI, LLC.
+ */
+
+//! Webhooks
+//!
+//! Delivers relationship events to external HTTP endpoints. A webhook is a
+//! URL, a filter and a shared secret; every matching event is POSTed to it
+//! as a structured-mode CloudEvent, signed with HMAC-SHA256:
+//!
+//! ```text
+//! relationship.webhooks.commands.*  --> WebhookManager --> WebhookStore (KV bucket)
+//!                                                      --> WebhookRegistry
+//!
+//! relationship.events.>  --> jetstream::WebhookFeed, applying each event to its own space
+//!                                        |
+//!                       WebhookDispatcher::dispatch: WebhookRegistry::matching(space, event)
+//!                                        |
+//!                       CloudEventEnvelope::wrap, sign(secret, t, body)
+//!                                        |
+//!                       WebhookSender::post, retried per RetryPolicy --> DeliveryLog
+//!                                        |
+//!                       gave up --> DeadLetter --> relationship.webhooks.dead_letters.{webhook_id}
+//! ```
+//!
+//! Webhooks are registered, removed, paused and resumed with
+//! `WebhookCommand`s, which the `WebhookManager` keeps in a `WebhookStore`
+//! before its registry takes them, so they survive restarts.
+//!
+//! Receivers recompute `HMAC-SHA256(secret, "{t}.{body}")` and compare it
+//! with the `v1` of `X-CIM-Signature: t={unix seconds},v1={hex}`, as
+//! `verify_signature` does. The `X-CIM-Delivery` id is the same on every
+//! attempt, so receivers can drop duplicates.
+//!
+//! A 2xx answer is a delivery. 408, 429, 5xx and failures to connect are
+//! retried with exponential backoff; any other status fails at once.
+//! Deliveries that give up become `DeadLetter`s, which
+//! `WebhookDispatcher::redeliver` sends again.
+//!
+//! Requests go out through a `WebhookSender`; `HttpWebhookSender` (feature
+//! `webhooks`) is a reqwest client.
+
+use crate::aggregates::RelationshipSpace;
+use crate::events::RelationshipEvent;
+use crate::nats::{
+    decode, encode, CloudEvent, CloudEventEnvelope, MessageHeaders, RelationshipSubjects, Shutdown, Transport,
+    TransportMessage,
+};
+use crate::value_objects::{EntityKey, EntityRef, RelationshipCategory};
+use crate::{RelationshipError, RelationshipResult};
+use async_nats::jetstream::{self, kv};
+use async_trait::async_trait;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+/// Header carrying `t={unix seconds},v1={hex HMAC-SHA256}`
+pub const SIGNATURE_HEADER: &str = "X-CIM-Signature";
+
+/// Header naming the relationship event type
+pub const EVENT_TYPE_HEADER: &str = "X-CIM-Event";
+
+/// Header carrying the delivery id, the same on every attempt
+pub const DELIVERY_HEADER: &str = "X-CIM-Delivery";
+
+/// Header carrying the webhook id
+pub const WEBHOOK_HEADER: &str = "X-CIM-Webhook";
+
+/// Deliveries kept by a default DeliveryLog
+pub const DEFAULT_LOG_CAPACITY: usize = 1000;
+
+// ============================================================================
+// Webhooks
+// ============================================================================
+
+/// Which events a webhook receives
+///
+/// Empty sets match everything. Categories and entities are looked up in
+/// the relationship space, which must already have the event applied.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WebhookFilter {
+    /// Event types, e.g. "edge_created"
+    #[serde(default)]
+    pub event_types: HashSet<String>,
+    /// Categories of the relationship
+    #[serde(default)]
+    pub categories: HashSet<RelationshipCategory>,
+    /// Entities taking part in the relationship
+    #[serde(default)]
+    pub entities: HashSet<EntityKey>,
+}
+
+impl WebhookFilter {
+    /// Filter matching every event
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    /// Also match an event type
+    pub fn with_event_type(mut self, event_type: impl Into<String>) -> Self {
+        self.event_types.insert(event_type.into());
+        self
+    }
+
+    /// Also match a category
+    pub fn with_category(mut self, category: RelationshipCategory) -> Self {
+        self.categories.insert(category);
+        self
+    }
+
+    /// Also match relationships an entity takes part in
+    pub fn with_entity(mut self, entity: &EntityRef) -> Self {
+        self.entities.insert(entity.key());
+        self
+    }
+
+    /// Whether an event passes the filter
+    pub fn matches(&self, space: &RelationshipSpace, event: &RelationshipEvent) -> bool {
+        if !self.event_types.is_empty() && !self.event_types.contains(event.event_type()) {
+            return false;
+        }
+        if self.categories.is_empty() && self.entities.is_empty() {
+            return true;
+        }
+
+        let id = event.relationship_id();
+        let (category, entities): (&RelationshipCategory, Vec<EntityKey>) =
+            if let Some(edge) = space.get_edge(&id) {
+                (&edge.category, vec![edge.source.key(), edge.target.key()])
+            } else if let Some(hyperedge) = space.get_hyperedge(&id) {
+                let entities = hyperedge
+                    .participants
+                    .participants()
+                    .map(|p| p.entity_ref.key())
+                    .collect();
+                (&hyperedge.category, entities)
+            } else {
+                return false;
+            };
+
+        (self.categories.is_empty() || self.categories.contains(category))
+            && (self.entities.is_empty() || entities.iter().any(|e| self.entities.contains(e)))
+    }
+}
+
+/// An endpoint receiving relationship events
+///
+/// Serialized with its secret, for a `WebhookStore`; never send one to a
+/// client.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Webhook {
+    pub id: Uuid,
+    pub url: String,
+    pub filter: WebhookFilter,
+    /// Paused webhooks receive nothing
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+    secret: String,
+}
+
+impl std::fmt::Debug for Webhook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Webhook")
+            .field("id", &self.id)
+            .field("url", &self.url)
+            .field("filter", &self.filter)
+            .field("active", &self.active)
+            .field("created_at", &self.created_at)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Webhook {
+    /// Webhook posting to an http(s) URL, signing with a non-empty secret
+    pub fn new(
+        url: impl Into<String>,
+        filter: WebhookFilter,
+        secret: impl Into<String>,
+    ) -> RelationshipResult<Self> {
+        let url = url.into();
+        let secret = secret.into();
+        if !(url.starts_with("https://") || url.starts_with("http://")) {
+            return Err(RelationshipError::InvalidConfiguration(format!(
+                "webhook url must be http(s): {}",
+                url
+            )));
+        }
+        if secret.is_empty() {
+            return Err(RelationshipError::InvalidConfiguration(
+                "webhook secret must not be empty".to_string(),
+            ));
+        }
+        Ok(Self {
+            id: Uuid::now_v7(),
+            url,
+            filter,
+            active: true,
+            created_at: Utc::now(),
+            secret,
+        })
+    }
+
+    /// Signature header value for a body sent at `timestamp`
+    pub fn sign(&self, timestamp: i64, body: &[u8]) -> String {
+        sign(&self.secret, timestamp, body)
+    }
+}
+
+/// Registered webhooks
+#[derive(Debug, Clone, Default)]
+pub struct WebhookRegistry {
+    webhooks: HashMap<Uuid, Webhook>,
+}
+
+impl WebhookRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a webhook
+    pub fn register(
+        &mut self,
+        url: impl Into<String>,
+        filter: WebhookFilter,
+        secret: impl Into<String>,
+    ) -> RelationshipResult<Webhook> {
+        let webhook = Webhook::new(url, filter, secret)?;
+        self.webhooks.insert(webhook.id, webhook.clone());
+        Ok(webhook)
+    }
+
+    /// Register an existing webhook, replacing one with its id
+    pub fn insert(&mut self, webhook: Webhook) {
+        self.webhooks.insert(webhook.id, webhook);
+    }
+
+    /// Remove a webhook
+    pub fn unregister(&mut self, id: &Uuid) -> Option<Webhook> {
+        self.webhooks.remove(id)
+    }
+
+    /// Pause or resume a webhook; false if unknown
+    pub fn set_active(&mut self, id: &Uuid, active: bool) -> bool {
+        self.webhooks
+            .get_mut(id)
+            .map(|w| w.active = active)
+            .is_some()
+    }
+
+    pub fn get(&self, id: &Uuid) -> Option<&Webhook> {
+        self.webhooks.get(id)
+    }
+
+    /// Webhooks, oldest first
+    pub fn list(&self) -> Vec<&Webhook> {
+        let mut webhooks: Vec<&Webhook> = self.webhooks.values().collect();
+        webhooks.sort_by_key(|w| w.id);
+        webhooks
+    }
+
+    /// Active webhooks whose filter passes an event
+    pub fn matching(&self, space: &RelationshipSpace, event: &RelationshipEvent) -> Vec<Webhook> {
+        self.list()
+            .into_iter()
+            .filter(|w| w.active && w.filter.matches(space, event))
+            .cloned()
+            .collect()
+    }
+}
+
+impl FromIterator<Webhook> for WebhookRegistry {
+    fn from_iter<I: IntoIterator<Item = Webhook>>(webhooks: I) -> Self {
+        let mut registry = Self::new();
+        webhooks.into_iter().for_each(|webhook| registry.insert(webhook));
+        registry
+    }
+}
+
+// ============================================================================
+// Management
+// ============================================================================
+
+/// A change to the registered webhooks, sent to
+/// `relationship.webhooks.commands.{command_type}`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum WebhookCommand {
+    /// Register a webhook
+    Register {
+        url: String,
+        #[serde(default)]
+        filter: WebhookFilter,
+        secret: String,
+    },
+    /// Remove a webhook
+    Unregister { id: Uuid },
+    /// Pause or resume a webhook
+    SetActive { id: Uuid, active: bool },
+}
+
+impl WebhookCommand {
+    pub fn command_type(&self) -> &'static str {
+        match self {
+            Self::Register { .. } => "register",
+            Self::Unregister { .. } => "unregister",
+            Self::SetActive { .. } => "set_active",
+        }
+    }
+
+    /// Subject the command is sent to
+    pub fn subject(&self) -> String {
+        RelationshipSubjects::webhook_command(self.command_type())
+    }
+}
+
+/// Reply to a webhook command
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WebhookResponse {
+    /// Whether the command was accepted
+    pub accepted: bool,
+    /// The webhook the command concerned
+    pub webhook_id: Option<Uuid>,
+    /// Rejection reason
+    pub error: Option<String>,
+}
+
+impl WebhookResponse {
+    /// Accepted command
+    pub fn accepted(webhook_id: Uuid) -> Self {
+        Self {
+            accepted: true,
+            webhook_id: Some(webhook_id),
+            error: None,
+        }
+    }
+
+    /// Rejected command
+    pub fn rejected(error: impl Into<String>) -> Self {
+        Self {
+            accepted: false,
+            webhook_id: None,
+            error: Some(error.into()),
+        }
+    }
+}
+
+/// Where registered webhooks are kept, secrets included
+#[async_trait]
+pub trait WebhookStore: Send + Sync {
+    /// Every webhook kept
+    async fn load(&self) -> RelationshipResult<Vec<Webhook>>;
+
+    /// Keep a webhook, replacing the one with its id
+    async fn save(&self, webhook: &Webhook) -> RelationshipResult<()>;
+
+    /// Forget a webhook
+    async fn remove(&self, id: &Uuid) -> RelationshipResult<()>;
+}
+
+/// Store over a JetStream key-value bucket, keyed by webhook id
+#[derive(Debug, Clone)]
+pub struct KvWebhookStore {
+    bucket: kv::Store,
+}
+
+impl KvWebhookStore {
+    pub fn new(bucket: kv::Store) -> Self {
+        Self { bucket }
+    }
+
+    /// Open a bucket, creating it if missing
+    pub async fn open(js: &jetstream::Context, bucket: &str) -> RelationshipResult<Self> {
+        match js.get_key_value(bucket).await {
+            Ok(store) => Ok(Self::new(store)),
+            Err(_) => {
+                tracing::info!("creating key-value bucket {}", bucket);
+                js.create_key_value(kv::Config {
+                    bucket: bucket.to_string(),
+                    ..Default::default()
+                })
+                .await
+                .map(Self::new)
+                .map_err(kv_error)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl WebhookStore for KvWebhookStore {
+    async fn load(&self) -> RelationshipResult<Vec<Webhook>> {
+        let mut keys = self.bucket.keys().await.map_err(kv_error)?;
+        let mut webhooks = Vec::new();
+        while let Some(key) = keys.next().await {
+            let key = key.map_err(kv_error)?;
+            // Removed since the keys were listed
+            let Some(value) = self.bucket.get(key.as_str()).await.map_err(kv_error)? else {
+                continue;
+            };
+            let webhook = serde_json::from_slice(&value)
+                .map_err(|e| RelationshipError::SerializationError(format!("webhook {}: {}", key, e)))?;
+            webhooks.push(webhook);
+        }
+        Ok(webhooks)
+    }
+
+    async fn save(&self, webhook: &Webhook) -> RelationshipResult<()> {
+        let value = serde_json::to_vec(webhook)
+            .map_err(|e| RelationshipError::SerializationError(format!("webhook {}: {}", webhook.id, e)))?;
+        self.bucket
+            .put(webhook.id.to_string(), value.into())
+            .await
+            .map_err(kv_error)?;
+        Ok(())
+    }
+
+    async fn remove(&self, id: &Uuid) -> RelationshipResult<()> {
+        self.bucket.delete(id.to_string()).await.map_err(kv_error)
+    }
+}
+
+fn kv_error(error: impl std::fmt::Display) -> RelationshipError {
+    RelationshipError::TransportError(error.to_string())
+}
+
+/// Answers webhook commands, keeping the webhooks in a `WebhookStore`
+///
+/// A change is kept in the store before the registry takes it, so one the
+/// store refuses changes neither.
+pub struct WebhookManager<K: WebhookStore> {
+    store: K,
+    registry: Arc<RwLock<WebhookRegistry>>,
+    shutdown: Shutdown,
+}
+
+impl<K: WebhookStore> WebhookManager<K> {
+    /// Manager of the webhooks kept in `store`
+    pub async fn load(store: K) -> RelationshipResult<Self> {
+        let registry = store.load().await?.into_iter().collect();
+        Ok(Self {
+            store,
+            registry: Arc::new(RwLock::new(registry)),
+            shutdown: Shutdown::new(),
+        })
+    }
+
+    /// Stop serving when `shutdown` is triggered
+    pub fn with_shutdown(mut self, shutdown: Shutdown) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+
+    /// The registry, for `WebhookDispatcher::with_registry`
+    pub fn registry(&self) -> &Arc<RwLock<WebhookRegistry>> {
+        &self.registry
+    }
+
+    /// Apply a command, returning the id of the webhook it concerned
+    pub async fn execute(&self, command: WebhookCommand) -> RelationshipResult<Uuid> {
+        let mut registry = self.registry.write().await;
+        let known = |id: Uuid| {
+            registry
+                .get(&id)
+                .cloned()
+                .ok_or_else(|| RelationshipError::EntityNotFound(format!("webhook {}", id)))
+        };
+        match command {
+            WebhookCommand::Register {
+                url,
+                filter,
+                secret,
+            } => {
+                let webhook = Webhook::new(url, filter, secret)?;
+                self.store.save(&webhook).await?;
+                let id = webhook.id;
+                registry.insert(webhook);
+                Ok(id)
+            }
+            WebhookCommand::Unregister { id } => {
+                known(id)?;
+                self.store.remove(&id).await?;
+                registry.unregister(&id);
+                Ok(id)
+            }
+            WebhookCommand::SetActive { id, active } => {
+                let mut webhook = known(id)?;
+                webhook.active = active;
+                self.store.save(&webhook).await?;
+                registry.insert(webhook);
+                Ok(id)
+            }
+        }
+    }
+
+    /// Reply to a command message with its `WebhookResponse`
+    pub async fn handle(&self, message: &TransportMessage) -> RelationshipResult<Bytes> {
+        let response = match decode::<WebhookCommand>(&message.payload) {
+            Ok(command) => match self.execute(command).await {
+                Ok(id) => WebhookResponse::accepted(id),
+                Err(e) => WebhookResponse::rejected(e.to_string()),
+            },
+            Err(e) => WebhookResponse::rejected(e.to_string()),
+        };
+        encode(&response)
+    }
+
+    /// Answer commands on `relationship.webhooks.commands.*`, one at a
+    /// time, until shutdown or the subscription ends
+    pub async fn serve<T: Transport>(&self, transport: &T) -> RelationshipResult<()> {
+        let mut requests = transport
+            .subscribe(&RelationshipSubjects::all_webhook_commands())
+            .await?;
+        while let Some(request) = self.shutdown.next(&mut requests).await {
+            let reply = self.handle(&request).await;
+            let Some(inbox) = &request.reply else {
+                continue;
+            };
+            let sent = match reply {
+                Ok(payload) => transport.publish(inbox, payload).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = sent {
+                tracing::warn!("failed to reply on {}: {}", request.subject, e);
+            }
+        }
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Signatures
+// ============================================================================
+
+fn mac(secret: &str, timestamp: i64, body: &[u8]) -> Hmac<Sha256> {
+    // HMAC takes keys of any length
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("hmac key");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    mac
+}
+
+/// `t={timestamp},v1={hex HMAC-SHA256(secret, "{timestamp}.{body}")}`
+pub fn sign(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let tag = mac(secret, timestamp, body).finalize().into_bytes();
+    let hex: String = tag.iter().map(|b| format!("{:02x}", b)).collect();
+    format!("t={},v1={}", timestamp, hex)
+}
+
+/// Whether a signature header matches a body and is no older than
+/// `tolerance` at `now`
+pub fn verify_signature(
+    secret: &str,
+    header: &str,
+    body: &[u8],
+    now: DateTime<Utc>,
+    tolerance: Duration,
+) -> bool {
+    let mut timestamp = None;
+    let mut signatures = Vec::new();
+    for part in header.split(',') {
+        match part.trim().split_once('=') {
+            Some(("t", t)) => timestamp = t.parse::<i64>().ok(),
+            Some(("v1", v1)) => signatures.extend(decode_hex(v1)),
+            _ => {}
+        }
+    }
+    let Some(timestamp) = timestamp else {
+        return false;
+    };
+    if now.timestamp().abs_diff(timestamp) > tolerance.as_secs() {
+        return false;
+    }
+    signatures
+        .iter()
+        .any(|tag| mac(secret, timestamp, body).verify_slice(tag).is_ok())
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+// ============================================================================
+// Delivery
+// ============================================================================
+
+/// Sends webhook requests
+#[async_trait]
+pub trait WebhookSender: Send + Sync {
+    /// POST a body, returning the response status
+    ///
+    /// Errors are failures to get a response at all.
+    async fn post(
+        &self,
+        url: &str,
+        headers: &MessageHeaders,
+        body: Bytes,
+    ) -> RelationshipResult<u16>;
+}
+
+/// Sender over a reqwest client
+#[cfg(feature = "webhooks")]
+#[derive(Debug, Clone)]
+pub struct HttpWebhookSender {
+    client: reqwest::Client,
+}
+
+#[cfg(feature = "webhooks")]
+impl HttpWebhookSender {
+    /// Sender giving up on a request after `timeout`
+    pub fn new(timeout: Duration) -> RelationshipResult<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .map_err(|e| RelationshipError::InvalidConfiguration(e.to_string()))?;
+        Ok(Self { client })
+    }
+}
+
+#[cfg(feature = "webhooks")]
+#[async_trait]
+impl WebhookSender for HttpWebhookSender {
+    async fn post(
+        &self,
+        url: &str,
+        headers: &MessageHeaders,
+        body: Bytes,
+    ) -> RelationshipResult<u16> {
+        let mut request = self.client.post(url).body(body);
+        for (name, value) in headers {
+            request = request.header(name, value);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| RelationshipError::TransportError(e.to_string()))?;
+        Ok(response.status().as_u16())
+    }
+}
+
+/// When failed deliveries are attempted again
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    /// Attempts in total, including the first
+    pub max_attempts: u32,
+    /// Wait before the second attempt; doubled for each one after
+    pub initial_backoff: Duration,
+    /// Longest wait between attempts
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Wait after the given failed attempt (1-based)
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+
+    /// Whether a status is worth another attempt
+    pub fn is_retryable(status: u16) -> bool {
+        status == 408 || status == 429 || status >= 500
+    }
+}
+
+/// One request of a delivery
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeliveryAttempt {
+    pub at: DateTime<Utc>,
+    /// Response status, if there was a response
+    pub status: Option<u16>,
+    pub error: Option<String>,
+}
+
+/// One event sent to one webhook
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Delivery {
+    pub id: Uuid,
+    pub webhook_id: Uuid,
+    /// The CloudEvent id
+    pub event_id: String,
+    pub event_type: String,
+    pub attempts: Vec<DeliveryAttempt>,
+    pub delivered: bool,
+}
+
+/// Most recent deliveries, oldest dropped first
+#[derive(Debug, Clone)]
+pub struct DeliveryLog {
+    capacity: usize,
+    deliveries: VecDeque<Delivery>,
+}
+
+impl Default for DeliveryLog {
+    fn default() -> Self {
+        Self::new(DEFAULT_LOG_CAPACITY)
+    }
+}
+
+impl DeliveryLog {
+    /// Log keeping at most `capacity` deliveries
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            deliveries: VecDeque::new(),
+        }
+    }
+
+    pub fn record(&mut self, delivery: Delivery) {
+        if self.deliveries.len() == self.capacity {
+            self.deliveries.pop_front();
+        }
+        if self.capacity > 0 {
+            self.deliveries.push_back(delivery);
+        }
+    }
+
+    /// Deliveries, oldest first
+    pub fn deliveries(&self) -> impl Iterator<Item = &Delivery> {
+        self.deliveries.iter()
+    }
+
+    /// Deliveries to one webhook, oldest first
+    pub fn for_webhook(&self, webhook_id: &Uuid) -> Vec<&Delivery> {
+        self.deliveries
+            .iter()
+            .filter(|d| &d.webhook_id == webhook_id)
+            .collect()
+    }
+
+    /// Deliveries that gave up
+    pub fn failed(&self) -> Vec<&Delivery> {
+        self.deliveries.iter().filter(|d| !d.delivered).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.deliveries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.deliveries.is_empty()
+    }
+}
+
+/// A delivery that gave up, with the event it carried, kept for
+/// `WebhookDispatcher::redeliver`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeadLetter {
+    pub delivery: Delivery,
+    /// The event as it was sent
+    pub event: CloudEvent,
+}
+
+impl DeadLetter {
+    /// `relationship.webhooks.dead_letters.{webhook_id}`
+    pub fn subject(&self) -> String {
+        RelationshipSubjects::webhook_dead_letter(&self.delivery.webhook_id)
+    }
+}
+
+/// Delivers relationship events to registered webhooks
+pub struct WebhookDispatcher<S: WebhookSender> {
+    sender: S,
+    registry: Arc<RwLock<WebhookRegistry>>,
+    log: Arc<RwLock<DeliveryLog>>,
+    envelope: CloudEventEnvelope,
+    retry: RetryPolicy,
+}
+
+impl<S: WebhookSender> WebhookDispatcher<S> {
+    /// Dispatcher with an empty registry and log
+    pub fn new(sender: S) -> Self {
+        Self {
+            sender,
+            registry: Arc::new(RwLock::new(WebhookRegistry::new())),
+            log: Arc::new(RwLock::new(DeliveryLog::default())),
+            envelope: CloudEventEnvelope::default(),
+            retry: RetryPolicy::default(),
+        }
+    }
+
+    /// Share an existing registry
+    pub fn with_registry(mut self, registry: Arc<RwLock<WebhookRegistry>>) -> Self {
+        self.registry = registry;
+        self
+    }
+
+    /// Share an existing log
+    pub fn with_log(mut self, log: Arc<RwLock<DeliveryLog>>) -> Self {
+        self.log = log;
+        self
+    }
+
+    /// Wrap events with another CloudEvents source
+    pub fn with_envelope(mut self, envelope: CloudEventEnvelope) -> Self {
+        self.envelope = envelope;
+        self
+    }
+
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    pub fn registry(&self) -> &Arc<RwLock<WebhookRegistry>> {
+        &self.registry
+    }
+
+    pub fn log(&self) -> &Arc<RwLock<DeliveryLog>> {
+        &self.log
+    }
+
+    /// Send an event to webhooks, concurrently, and log the deliveries
+    pub async fn deliver(
+        &self,
+        webhooks: &[Webhook],
+        event: &RelationshipEvent,
+    ) -> RelationshipResult<Vec<Delivery>> {
+        if webhooks.is_empty() {
+            return Ok(Vec::new());
+        }
+        let cloud_event = self.envelope.wrap(event)?;
+        self.send(webhooks, &cloud_event, event.event_type()).await
+    }
+
+    /// Send an event to the webhooks whose filters pass it in `space`,
+    /// which must already have it applied, returning a dead letter for
+    /// each delivery that gave up
+    pub async fn dispatch(
+        &self,
+        space: &RelationshipSpace,
+        event: &RelationshipEvent,
+    ) -> RelationshipResult<Vec<DeadLetter>> {
+        let webhooks = self.registry.read().await.matching(space, event);
+        if webhooks.is_empty() {
+            return Ok(Vec::new());
+        }
+        let cloud_event = self.envelope.wrap(event)?;
+        let deliveries = self.send(&webhooks, &cloud_event, event.event_type()).await?;
+        Ok(deliveries
+            .into_iter()
+            .filter(|delivery| !delivery.delivered)
+            .map(|delivery| DeadLetter {
+                delivery,
+                event: cloud_event.clone(),
+            })
+            .collect())
+    }
+
+    /// Send a dead letter's event to its webhook again, as a new delivery
+    ///
+    /// Fails if the webhook has since been removed or paused.
+    pub async fn redeliver(&self, dead_letter: &DeadLetter) -> RelationshipResult<Delivery> {
+        let webhook_id = dead_letter.delivery.webhook_id;
+        let webhook = self
+            .registry
+            .read()
+            .await
+            .get(&webhook_id)
+            .filter(|webhook| webhook.active)
+            .cloned()
+            .ok_or_else(|| RelationshipError::EntityNotFound(format!("active webhook {}", webhook_id)))?;
+        let mut deliveries = self
+            .send(&[webhook], &dead_letter.event, &dead_letter.delivery.event_type)
+            .await?;
+        Ok(deliveries.remove(0))
+    }
+
+    /// Send a CloudEvent to webhooks, concurrently, and log the deliveries
+    async fn send(
+        &self,
+        webhooks: &[Webhook],
+        cloud_event: &CloudEvent,
+        event_type: &str,
+    ) -> RelationshipResult<Vec<Delivery>> {
+        let body = cloud_event.encode()?;
+        let deliveries = futures::future::join_all(
+            webhooks
+                .iter()
+                .map(|webhook| self.deliver_to(webhook, cloud_event, event_type, body.clone())),
+        )
+        .await;
+
+        let mut log = self.log.write().await;
+        for delivery in &deliveries {
+            if !delivery.delivered {
+                tracing::warn!(
+                    "webhook {} gave up on {} after {} attempts",
+                    delivery.webhook_id,
+                    delivery.event_id,
+                    delivery.attempts.len()
+                );
+            }
+            log.record(delivery.clone());
+        }
+        Ok(deliveries)
+    }
+
+    async fn deliver_to(
+        &self,
+        webhook: &Webhook,
+        cloud_event: &CloudEvent,
+        event_type: &str,
+        body: Bytes,
+    ) -> Delivery {
+        let mut delivery = Delivery {
+            id: Uuid::now_v7(),
+            webhook_id: webhook.id,
+            event_id: cloud_event.id.clone(),
+            event_type: event_type.to_string(),
+            attempts: Vec::new(),
+            delivered: false,
+        };
+        let mut headers = CloudEvent::headers();
+        headers.insert(EVENT_TYPE_HEADER.to_string(), event_type.to_string());
+        headers.insert(DELIVERY_HEADER.to_string(), delivery.id.to_string());
+        headers.insert(WEBHOOK_HEADER.to_string(), webhook.id.to_string());
+
+        let attempts = self.retry.max_attempts.max(1);
+        for attempt in 1..=attempts {
+            let at = Utc::now();
+            headers.insert(
+                SIGNATURE_HEADER.to_string(),
+                webhook.sign(at.timestamp(), &body),
+            );
+            let (status, error, retry) =
+                match self.sender.post(&webhook.url, &headers, body.clone()).await {
+                    Ok(status) if (200..300).contains(&status) => (Some(status), None, false),
+                    Ok(status) => (
+                        Some(status),
+                        Some(format!("status {}", status)),
+                        RetryPolicy::is_retryable(status),
+                    ),
+                    Err(e) => (None, Some(e.to_string()), true),
+                };
+            delivery.delivered = error.is_none();
+            delivery
+                .attempts
+                .push(DeliveryAttempt { at, status, error });
+            if !retry || attempt == attempts {
+                break;
+            }
+            tokio::time::sleep(self.retry.backoff(attempt)).await;
+        }
+        delivery
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregates::EdgeConcept;
+    use crate::events::{EdgeActivated, EdgeEvent};
+    use cim_domain::MessageIdentity;
+    use cim_domain_spaces::TopologicalSpaceId;
+    use std::sync::Mutex;
+
+    /// Answers with queued statuses, recording requests
+    #[derive(Default)]
+    struct Scripted {
+        statuses: Mutex<VecDeque<u16>>,
+        requests: Mutex<Vec<(String, MessageHeaders, Bytes)>>,
+    }
+
+    #[async_trait]
+    impl WebhookSender for Scripted {
+        async fn post(
+            &self,
+            url: &str,
+            headers: &MessageHeaders,
+            body: Bytes,
+        ) -> RelationshipResult<u16> {
+            self.requests
+                .lock()
+                .unwrap()
+                .push((url.to_string(), headers.clone(), body));
+            Ok(self.statuses.lock().unwrap().pop_front().unwrap_or(200))
+        }
+    }
+
+    /// A space with Alice employed at Acme, and the activation of that edge
+    fn employment() -> (RelationshipSpace, RelationshipEvent, EntityRef) {
+        let alice = EntityRef::person(Uuid::now_v7());
+        let mut space = RelationshipSpace::new("Hooks", TopologicalSpaceId::new());
+        let edge = EdgeConcept::new(
+            "Alice at Acme",
+            alice.clone(),
+            EntityRef::organization(Uuid::now_v7()),
+            RelationshipCategory::Employment,
+        );
+        let edge_id = edge.id;
+        space.add_edge(edge).unwrap();
+        let event = RelationshipEvent::Edge(EdgeEvent::EdgeActivated(EdgeActivated {
+            event_id: Uuid::now_v7(),
+            identity: MessageIdentity::new_root(),
+            edge_id,
+            activated_by: "test".to_string(),
+            activated_at: Utc::now(),
+        }));
+        (space, event, alice)
+    }
+
+    #[tokio::test]
+    async fn test_signed_delivery_with_retries() {
+        let (space, event, alice) = employment();
+        let edge_id = event.relationship_id();
+
+        let sender = Scripted::default();
+        sender.statuses.lock().unwrap().extend([503, 200]);
+        let dispatcher = WebhookDispatcher::new(sender).with_retry(RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::ZERO,
+            max_backoff: Duration::ZERO,
+        });
+        let (hr, other) = {
+            let mut registry = dispatcher.registry().write().await;
+            let hr = registry
+                .register(
+                    "https://hr.example/hooks",
+                    WebhookFilter::all().with_entity(&alice),
+                    "s3cret",
+                )
+                .unwrap();
+            let other = registry
+                .register(
+                    "https://crm.example/hooks",
+                    WebhookFilter::all().with_category(RelationshipCategory::Membership),
+                    "other",
+                )
+                .unwrap();
+            (hr, other)
+        };
+        assert!(Webhook::new("ftp://x", WebhookFilter::all(), "s").is_err());
+
+        let webhooks = dispatcher.registry().read().await.matching(&space, &event);
+        assert_eq!(webhooks.len(), 1);
+        let deliveries = dispatcher.deliver(&webhooks, &event).await.unwrap();
+        assert!(deliveries[0].delivered);
+        assert_eq!(deliveries[0].attempts.len(), 2);
+        assert_eq!(deliveries[0].attempts[0].status, Some(503));
+
+        let requests = dispatcher.sender.requests.lock().unwrap().clone();
+        let (url, headers, body) = &requests[1];
+        assert_eq!(url, &hr.url);
+        assert_eq!(headers[DELIVERY_HEADER], requests[0].1[DELIVERY_HEADER]);
+        assert_eq!(headers[EVENT_TYPE_HEADER], "edge_activated");
+        assert!(verify_signature(
+            "s3cret",
+            &headers[SIGNATURE_HEADER],
+            body,
+            Utc::now(),
+            Duration::from_secs(300)
+        ));
+        assert!(!verify_signature(
+            "other",
+            &headers[SIGNATURE_HEADER],
+            body,
+            Utc::now(),
+            Duration::from_secs(300)
+        ));
+        assert_eq!(
+            CloudEvent::decode(body)
+                .unwrap()
+                .to_event()
+                .unwrap()
+                .relationship_id(),
+            edge_id
+        );
+
+        let log = dispatcher.log().read().await;
+        assert_eq!(log.for_webhook(&hr.id).len(), 1);
+        assert!(log.for_webhook(&other.id).is_empty());
+        assert!(log.failed().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_gave_up_deliveries_dead_lettered() {
+        let (space, event, alice) = employment();
+        let sender = Scripted::default();
+        sender.statuses.lock().unwrap().extend([500, 500]);
+        let dispatcher = WebhookDispatcher::new(sender).with_retry(RetryPolicy {
+            max_attempts: 2,
+            initial_backoff: Duration::ZERO,
+            max_backoff: Duration::ZERO,
+        });
+        let hr = dispatcher
+            .registry()
+            .write()
+            .await
+            .register("https://hr.example/hooks", WebhookFilter::all().with_entity(&alice), "s3cret")
+            .unwrap();
+
+        let dead_letters = dispatcher.dispatch(&space, &event).await.unwrap();
+        assert_eq!(dead_letters.len(), 1);
+        let dead_letter = &dead_letters[0];
+        assert_eq!(dead_letter.delivery.attempts.len(), 2);
+        assert_eq!(
+            dead_letter.subject(),
+            format!("relationship.webhooks.dead_letters.{}", hr.id)
+        );
+        let kept: DeadLetter = decode(&encode(dead_letter).unwrap()).unwrap();
+        assert_eq!(&kept, dead_letter);
+
+        // Sent again as a new delivery of the same event
+        let delivery = dispatcher.redeliver(&kept).await.unwrap();
+        assert!(delivery.delivered);
+        assert_ne!(delivery.id, dead_letter.delivery.id);
+        let requests = dispatcher.sender.requests.lock().unwrap().clone();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[2].2, requests[0].2);
+        assert_eq!(dispatcher.log().read().await.failed().len(), 1);
+
+        dispatcher.registry().write().await.set_active(&hr.id, false);
+        assert!(dispatcher.redeliver(&kept).await.is_err());
+    }
+
+    /// Keeps webhooks in memory
+    #[derive(Default)]
+    struct Kept(Mutex<HashMap<Uuid, Webhook>>);
+
+    #[async_trait]
+    impl WebhookStore for Arc<Kept> {
+        async fn load(&self) -> RelationshipResult<Vec<Webhook>> {
+            Ok(self.0.lock().unwrap().values().cloned().collect())
+        }
+
+        async fn save(&self, webhook: &Webhook) -> RelationshipResult<()> {
+            self.0.lock().unwrap().insert(webhook.id, webhook.clone());
+            Ok(())
+        }
+
+        async fn remove(&self, id: &Uuid) -> RelationshipResult<()> {
+            self.0.lock().unwrap().remove(id);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_webhook_commands_kept_in_store() {
+        use crate::nats::MockTransport;
+
+        let transport = MockTransport::new();
+        let store = Arc::new(Kept::default());
+        let manager = Arc::new(WebhookManager::load(store.clone()).await.unwrap());
+        let serving = tokio::spawn({
+            let (manager, transport) = (manager.clone(), transport.clone());
+            async move { manager.serve(&transport).await }
+        });
+        for _ in 0..10 {
+            tokio::task::yield_now().await;
+        }
+        let send = |command: WebhookCommand| {
+            let transport = transport.clone();
+            async move {
+                let reply = transport
+                    .request(&command.subject(), encode(&command).unwrap())
+                    .await
+                    .unwrap();
+                decode::<WebhookResponse>(&reply).unwrap()
+            }
+        };
+
+        let registered = send(WebhookCommand::Register {
+            url: "https://hr.example/hooks".to_string(),
+            filter: WebhookFilter::all().with_event_type("edge_activated"),
+            secret: "s3cret".to_string(),
+        })
+        .await;
+        assert!(registered.accepted, "{:?}", registered.error);
+        let id = registered.webhook_id.unwrap();
+        let paused = send(WebhookCommand::SetActive { id, active: false }).await;
+        assert!(paused.accepted);
+        let refused = send(WebhookCommand::Register {
+            url: "ftp://hr.example".to_string(),
+            filter: WebhookFilter::all(),
+            secret: "s3cret".to_string(),
+        })
+        .await;
+        assert!(!refused.accepted);
+
+        // A restarted manager finds the webhook as it was left, secret included
+        let restarted = WebhookManager::load(store.clone()).await.unwrap();
+        let webhook = restarted.registry().read().await.get(&id).cloned().unwrap();
+        assert!(!webhook.active);
+        assert_eq!(webhook.filter, WebhookFilter::all().with_event_type("edge_activated"));
+        assert_eq!(webhook.sign(1, b"body"), sign("s3cret", 1, b"body"));
+
+        let unknown = send(WebhookCommand::Unregister { id: Uuid::now_v7() }).await;
+        assert!(!unknown.accepted);
+        assert!(send(WebhookCommand::Unregister { id }).await.accepted);
+        assert!(store.0.lock().unwrap().is_empty());
+        assert!(manager.registry().read().await.list().is_empty());
+
+        manager.shutdown.trigger();
+        serving.await.unwrap().unwrap();
+    }
+}
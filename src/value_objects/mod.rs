@@ -6,16 +6,20 @@
 //!
 //! Core value types representing relationship concepts:
 //! - EntityRef: Content-addressed reference to any domain entity
+//! - EntityKey: Version-independent identity of a referenced entity
 //! - RelationshipId: Unique identifier for relationships
 //! - RelationshipCategory: Classification of relationship types
 //! - ValidityPeriod: Temporal bounds for relationships
 //! - IncidenceMatrix: Sparse representation for hyperedge membership
 //! - ParticipantRole: Role assignment for hyperedge participants
+//! - ParticipantGroup: Named, nestable sub-group of hyperedge participants
+//! - ActivationMode: Whether a hyperedge activates on command or by quorum
 
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::{HashMap, HashSet};
 use std::marker::PhantomData;
+use std::str::FromStr;
 use uuid::Uuid;
 
 // ============================================================================
@@ -82,12 +86,30 @@ impl<S, T> std::fmt::Display for RelationshipId<S, T> {
     }
 }
 
+/// Same schema for every phantom type: `{"id": uuid}`
+#[cfg(feature = "schema")]
+impl<S, T> schemars::JsonSchema for RelationshipId<S, T> {
+    fn schema_name() -> String {
+        "RelationshipId".to_string()
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        #[derive(schemars::JsonSchema)]
+        #[allow(dead_code)]
+        struct RelationshipId {
+            id: Uuid,
+        }
+        RelationshipId::json_schema(gen)
+    }
+}
+
 // ============================================================================
 // Entity Reference (CID-Addressed)
 // ============================================================================
 
 /// Type of entity being referenced
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum EntityType {
     /// Person entity from cim-domain-person
     Person,
@@ -121,6 +143,22 @@ impl EntityType {
             EntityType::Custom(_) => "custom",
         }
     }
+
+    /// Parse a NATS subject prefix back into a built-in entity type
+    ///
+    /// Custom types cannot be recovered from their prefix alone.
+    pub fn from_subject_prefix(prefix: &str) -> Option<Self> {
+        match prefix {
+            "person" => Some(EntityType::Person),
+            "organization" => Some(EntityType::Organization),
+            "location" => Some(EntityType::Location),
+            "agent" => Some(EntityType::Agent),
+            "policy" => Some(EntityType::Policy),
+            "concept" => Some(EntityType::Concept),
+            "relationship" => Some(EntityType::Relationship),
+            _ => None,
+        }
+    }
 }
 
 /// Content-addressed reference to any domain entity
@@ -138,6 +176,7 @@ impl EntityType {
 ///     .with_version(3);
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct EntityRef {
     /// Type of entity being referenced
     pub entity_type: EntityType,
@@ -221,6 +260,19 @@ impl EntityRef {
     pub fn is_pinned(&self) -> bool {
         self.cid.is_some() || self.version.is_some()
     }
+
+    /// Get the version-independent identity of the referenced entity
+    pub fn key(&self) -> EntityKey {
+        EntityKey {
+            entity_type: self.entity_type.clone(),
+            entity_id: self.entity_id,
+        }
+    }
+
+    /// Check if two references point at the same entity, ignoring pinning
+    pub fn same_entity(&self, other: &EntityRef) -> bool {
+        self.entity_type == other.entity_type && self.entity_id == other.entity_id
+    }
 }
 
 impl std::fmt::Display for EntityRef {
@@ -235,6 +287,80 @@ impl std::fmt::Display for EntityRef {
     }
 }
 
+/// Version-independent identity of a referenced entity
+///
+/// Two EntityRefs to the same entity pinned at different CIDs or versions
+/// share one EntityKey. Serializes as `"{type}:{uuid}"`, with custom types
+/// written as `"custom/{name}:{uuid}"`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EntityKey {
+    /// Type of entity
+    pub entity_type: EntityType,
+    /// Entity's unique identifier
+    pub entity_id: Uuid,
+}
+
+impl std::fmt::Display for EntityKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.entity_type {
+            EntityType::Custom(name) => write!(f, "custom/{}:{}", name, self.entity_id),
+            other => write!(f, "{}:{}", other.nats_subject_prefix(), self.entity_id),
+        }
+    }
+}
+
+impl FromStr for EntityKey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (prefix, id) = s
+            .rsplit_once(':')
+            .ok_or_else(|| format!("Invalid entity key: {}", s))?;
+        let entity_id = Uuid::parse_str(id).map_err(|e| format!("Invalid entity key {}: {}", s, e))?;
+        let entity_type = match prefix.strip_prefix("custom/") {
+            Some(name) => EntityType::Custom(name.to_string()),
+            None => EntityType::from_subject_prefix(prefix)
+                .ok_or_else(|| format!("Unknown entity type in key: {}", s))?,
+        };
+        Ok(Self {
+            entity_type,
+            entity_id,
+        })
+    }
+}
+
+impl Serialize for EntityKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for EntityKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for EntityKey {
+    fn schema_name() -> String {
+        "EntityKey".to_string()
+    }
+
+    fn json_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        schemars::schema::SchemaObject {
+            instance_type: Some(schemars::schema::InstanceType::String.into()),
+            string: Some(Box::new(schemars::schema::StringValidation {
+                pattern: Some(r"^(custom/)?[^:]+:[0-9a-fA-F-]{36}$".to_string()),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
 // ============================================================================
 // Relationship Categories
 // ============================================================================
@@ -244,6 +370,7 @@ impl std::fmt::Display for EntityRef {
 /// Categories define the semantic meaning of relationships in the domain.
 /// Each category has associated quality dimension defaults.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum RelationshipCategory {
     // ---- Organizational Relationships ----
     /// Employment relationship (Person -> Organization)
@@ -291,6 +418,25 @@ pub enum RelationshipCategory {
 }
 
 impl RelationshipCategory {
+    /// Every category except `Custom`
+    pub const STANDARD: [RelationshipCategory; 15] = [
+        RelationshipCategory::Employment,
+        RelationshipCategory::Membership,
+        RelationshipCategory::Ownership,
+        RelationshipCategory::Management,
+        RelationshipCategory::Friendship,
+        RelationshipCategory::ProfessionalContact,
+        RelationshipCategory::Mentorship,
+        RelationshipCategory::PartOf,
+        RelationshipCategory::Contains,
+        RelationshipCategory::DependsOn,
+        RelationshipCategory::Implements,
+        RelationshipCategory::Precedes,
+        RelationshipCategory::Triggers,
+        RelationshipCategory::References,
+        RelationshipCategory::DerivesFrom,
+    ];
+
     /// Get the default formality for this category
     pub fn default_formality(&self) -> Formality {
         match self {
@@ -311,6 +457,31 @@ impl RelationshipCategory {
         )
     }
 
+    /// Category of the same relationship read in the other direction
+    ///
+    /// Symmetric categories are their own inverse; categories without a
+    /// known inverse return `None`.
+    pub fn inverse(&self) -> Option<RelationshipCategory> {
+        match self {
+            RelationshipCategory::PartOf => Some(RelationshipCategory::Contains),
+            RelationshipCategory::Contains => Some(RelationshipCategory::PartOf),
+            c if c.is_symmetric() => Some(c.clone()),
+            _ => None,
+        }
+    }
+
+    /// Category of a name, ignoring case and separators: "Part_Of" ==
+    /// "part of" == "PartOf"
+    ///
+    /// Names that are not standard categories become `Custom`.
+    pub fn from_name(name: &str) -> RelationshipCategory {
+        RelationshipCategory::STANDARD
+            .iter()
+            .find(|c| normalize_name(&c.display_name()) == normalize_name(name))
+            .cloned()
+            .unwrap_or_else(|| RelationshipCategory::Custom(name.to_string()))
+    }
+
     /// Get human-readable name
     pub fn display_name(&self) -> String {
         match self {
@@ -334,6 +505,34 @@ impl RelationshipCategory {
     }
 }
 
+/// Serde adapter for category-keyed maps
+///
+/// Serializes as a list of `[category, value]` pairs: `Custom` categories
+/// cannot be JSON object keys.
+pub(crate) mod category_map {
+    use super::RelationshipCategory;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::collections::HashMap;
+
+    pub fn serialize<V, S>(map: &HashMap<RelationshipCategory, V>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        V: Serialize,
+        S: Serializer,
+    {
+        serializer.collect_seq(map.iter())
+    }
+
+    pub fn deserialize<'de, V, D>(deserializer: D) -> Result<HashMap<RelationshipCategory, V>, D::Error>
+    where
+        V: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Ok(Vec::<(RelationshipCategory, V)>::deserialize(deserializer)?
+            .into_iter()
+            .collect())
+    }
+}
+
 // ============================================================================
 // Validity Period
 // ============================================================================
@@ -345,6 +544,7 @@ impl RelationshipCategory {
 /// - Fixed-term relationships
 /// - Historical (ended) relationships
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ValidityPeriod {
     /// When the relationship started
     pub starts_at: DateTime<Utc>,
@@ -422,6 +622,7 @@ impl Default for ValidityPeriod {
 
 /// Level of formality for a relationship
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum Formality {
     /// Casual, no documentation
     Informal,
@@ -447,6 +648,22 @@ impl Formality {
         }
     }
 
+    /// Every formality, least formal first
+    pub const ALL: [Formality; 5] = [
+        Formality::Informal,
+        Formality::SemiFormal,
+        Formality::Formal,
+        Formality::Contractual,
+        Formality::Legal,
+    ];
+
+    /// Formality of a name, ignoring case and separators ("semi-formal")
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|f| normalize_name(&format!("{:?}", f)) == normalize_name(name))
+    }
+
     /// Create from numeric value
     pub fn from_f64(value: f64) -> Self {
         if value < 0.125 {
@@ -463,6 +680,14 @@ impl Formality {
     }
 }
 
+/// Letters and digits only, lowercased
+fn normalize_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
 // ============================================================================
 // Incidence Matrix (for HyperEdges)
 // ============================================================================
@@ -471,14 +696,63 @@ impl Formality {
 ///
 /// Maps entity references to their participation in the hyperedge.
 /// Each participant has a role assignment.
+///
+/// Participants are keyed by EntityKey, so re-pinning a participant's
+/// reference to a new CID or version never duplicates it. Matrices
+/// serialized before this keying (keyed by the EntityRef display string)
+/// are upcast on deserialization: entries are re-keyed from their own
+/// `entity_ref`, and duplicates of one entity are merged.
+///
+/// Participants can be organized into named groups, which may nest, so a
+/// collective inside the hyperedge (a squad inside a team) needs no
+/// hyperedge of its own:
+///
+/// ```text
+/// Project Team
+///   +-- engineering (Contributor)
+///   |     +-- frontend squad (Contributor)   {Ann, Bo}
+///   |     +-- backend squad  (Contributor)   {Cy}
+///   +-- steering (Stakeholder)               {Dee}
+/// ```
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct IncidenceMatrix {
     /// Participants and their roles
-    participants: HashMap<String, ParticipantEntry>,
+    #[serde(deserialize_with = "deserialize_participants")]
+    participants: HashMap<EntityKey, ParticipantEntry>,
+    /// Participant groups by name
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    groups: HashMap<String, ParticipantGroup>,
+}
+
+/// Deserialize participants under any historical key format
+///
+/// When one entity appears more than once, the most recently joined entry
+/// wins but keeps the earliest join time.
+fn deserialize_participants<'de, D>(
+    deserializer: D,
+) -> Result<HashMap<EntityKey, ParticipantEntry>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let raw: HashMap<String, ParticipantEntry> = HashMap::deserialize(deserializer)?;
+    let mut entries: Vec<ParticipantEntry> = raw.into_values().collect();
+    entries.sort_by_key(|entry| entry.joined_at);
+
+    let mut participants: HashMap<EntityKey, ParticipantEntry> = HashMap::new();
+    for mut entry in entries {
+        let key = entry.entity_ref.key();
+        if let Some(earlier) = participants.get(&key) {
+            entry.joined_at = earlier.joined_at;
+        }
+        participants.insert(key, entry);
+    }
+    Ok(participants)
 }
 
 /// Entry in the incidence matrix
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ParticipantEntry {
     /// Reference to the participating entity
     pub entity_ref: EntityRef,
@@ -490,12 +764,43 @@ pub struct ParticipantEntry {
     pub joined_at: DateTime<Utc>,
 }
 
+/// A named group of participants within a hyperedge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ParticipantGroup {
+    /// Name, unique within the hyperedge
+    pub name: String,
+    /// Role of the group as a whole
+    pub role: ParticipantRole,
+    /// Enclosing group, if nested
+    #[serde(default)]
+    pub parent: Option<String>,
+    /// Direct members
+    #[serde(default)]
+    pub members: HashSet<EntityKey>,
+}
+
 impl IncidenceMatrix {
     /// Create an empty incidence matrix
     pub fn new() -> Self {
         Self::default()
     }
 
+    /// Rebuild a matrix from its entries and groups, e.g. when decoding
+    #[cfg(any(feature = "protobuf", feature = "avro"))]
+    pub(crate) fn from_parts(
+        participants: impl IntoIterator<Item = ParticipantEntry>,
+        groups: impl IntoIterator<Item = ParticipantGroup>,
+    ) -> Self {
+        Self {
+            participants: participants
+                .into_iter()
+                .map(|entry| (entry.entity_ref.key(), entry))
+                .collect(),
+            groups: groups.into_iter().map(|group| (group.name.clone(), group)).collect(),
+        }
+    }
+
     /// Add a participant
     pub fn add_participant(
         &mut self,
@@ -503,7 +808,7 @@ impl IncidenceMatrix {
         role: ParticipantRole,
         weight: f64,
     ) {
-        let key = entity_ref.to_string();
+        let key = entity_ref.key();
         self.participants.insert(
             key,
             ParticipantEntry {
@@ -515,9 +820,36 @@ impl IncidenceMatrix {
         );
     }
 
-    /// Remove a participant
+    /// Remove a participant, and its group memberships
     pub fn remove_participant(&mut self, entity_ref: &EntityRef) -> Option<ParticipantEntry> {
-        self.participants.remove(&entity_ref.to_string())
+        let key = entity_ref.key();
+        for group in self.groups.values_mut() {
+            group.members.remove(&key);
+        }
+        self.participants.remove(&key)
+    }
+
+    /// Change a participant's role in place
+    ///
+    /// Returns the previous role, or None if the entity is not a participant.
+    pub fn set_role(&mut self, entity_ref: &EntityRef, role: ParticipantRole) -> Option<ParticipantRole> {
+        self.participants
+            .get_mut(&entity_ref.key())
+            .map(|entry| std::mem::replace(&mut entry.role, role))
+    }
+
+    /// Change a participant's weight in place
+    ///
+    /// Returns the previous weight, or None if the entity is not a participant.
+    pub fn set_weight(&mut self, entity_ref: &EntityRef, weight: f64) -> Option<f64> {
+        self.participants
+            .get_mut(&entity_ref.key())
+            .map(|entry| std::mem::replace(&mut entry.weight, weight.clamp(0.0, 1.0)))
+    }
+
+    /// Get a participant's entry
+    pub fn get(&self, entity_ref: &EntityRef) -> Option<&ParticipantEntry> {
+        self.participants.get(&entity_ref.key())
     }
 
     /// Get participant count
@@ -540,7 +872,107 @@ impl IncidenceMatrix {
 
     /// Check if entity is a participant
     pub fn contains(&self, entity_ref: &EntityRef) -> bool {
-        self.participants.contains_key(&entity_ref.to_string())
+        self.participants.contains_key(&entity_ref.key())
+    }
+
+    // ---- Groups ----
+
+    /// Add an empty group, optionally nested in an existing one
+    ///
+    /// Returns false if the name is taken or the parent does not exist.
+    pub fn add_group(&mut self, name: impl Into<String>, role: ParticipantRole, parent: Option<&str>) -> bool {
+        let name = name.into();
+        if self.groups.contains_key(&name) || parent.is_some_and(|p| !self.groups.contains_key(p)) {
+            return false;
+        }
+        self.groups.insert(
+            name.clone(),
+            ParticipantGroup {
+                name,
+                role,
+                parent: parent.map(str::to_string),
+                members: HashSet::new(),
+            },
+        );
+        true
+    }
+
+    /// Remove a group; its subgroups move up to its parent
+    pub fn remove_group(&mut self, name: &str) -> Option<ParticipantGroup> {
+        let group = self.groups.remove(name)?;
+        for child in self.groups.values_mut() {
+            if child.parent.as_deref() == Some(name) {
+                child.parent = group.parent.clone();
+            }
+        }
+        Some(group)
+    }
+
+    /// Put a participant in a group
+    ///
+    /// Returns false if the entity is not a participant or the group does
+    /// not exist. A participant may belong to several groups.
+    pub fn assign_to_group(&mut self, entity_ref: &EntityRef, group: &str) -> bool {
+        let key = entity_ref.key();
+        match self.groups.get_mut(group) {
+            Some(group) if self.participants.contains_key(&key) => {
+                group.members.insert(key);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Take a participant out of a group
+    pub fn unassign_from_group(&mut self, entity_ref: &EntityRef, group: &str) -> bool {
+        self.groups
+            .get_mut(group)
+            .is_some_and(|group| group.members.remove(&entity_ref.key()))
+    }
+
+    /// Get a group
+    pub fn group(&self, name: &str) -> Option<&ParticipantGroup> {
+        self.groups.get(name)
+    }
+
+    /// Get all groups
+    pub fn groups(&self) -> impl Iterator<Item = &ParticipantGroup> {
+        self.groups.values()
+    }
+
+    /// Groups directly nested in a group
+    pub fn subgroups(&self, name: &str) -> Vec<&ParticipantGroup> {
+        self.groups
+            .values()
+            .filter(|g| g.parent.as_deref() == Some(name))
+            .collect()
+    }
+
+    /// Groups with a role
+    pub fn groups_with_role(&self, role: &ParticipantRole) -> Vec<&ParticipantGroup> {
+        self.groups.values().filter(|g| &g.role == role).collect()
+    }
+
+    /// Groups a participant directly belongs to
+    pub fn groups_of(&self, entity_ref: &EntityRef) -> Vec<&ParticipantGroup> {
+        let key = entity_ref.key();
+        self.groups.values().filter(|g| g.members.contains(&key)).collect()
+    }
+
+    /// Members of a group and of every group nested in it
+    pub fn group_members(&self, name: &str) -> Vec<&ParticipantEntry> {
+        let mut visited = HashSet::new();
+        let mut pending = vec![name];
+        let mut members = HashSet::new();
+        while let Some(name) = pending.pop() {
+            let Some(group) = self.groups.get(name) else { continue };
+            if !visited.insert(name) {
+                continue;
+            }
+            members.extend(&group.members);
+            pending.extend(self.subgroups(name).into_iter().map(|g| g.name.as_str()));
+        }
+        members.into_iter().filter_map(|key| self.participants.get(key)).collect()
     }
 }
 
@@ -550,6 +982,7 @@ impl IncidenceMatrix {
 
 /// Role of a participant in a hyperedge
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum ParticipantRole {
     // ---- Generic Roles ----
     /// Primary entity in the relationship
@@ -604,6 +1037,192 @@ impl ParticipantRole {
     }
 }
 
+// ============================================================================
+// Activation Modes
+// ============================================================================
+
+/// How a hyperedge becomes Active
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum ActivationMode {
+    /// On an explicit activation command
+    #[default]
+    Immediate,
+    /// Once participants holding `threshold` of the total participation
+    /// weight have acknowledged
+    Quorum {
+        /// Fraction of the total weight (0.0 - 1.0)
+        threshold: f64,
+    },
+}
+
+impl ActivationMode {
+    /// Weighted quorum mode
+    pub fn quorum(threshold: f64) -> Self {
+        ActivationMode::Quorum {
+            threshold: threshold.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Check if acknowledgements allow activation
+    ///
+    /// Always true in `Immediate` mode.
+    pub fn is_met(&self, participants: &IncidenceMatrix, acknowledged: &HashSet<EntityKey>) -> bool {
+        match self {
+            ActivationMode::Immediate => true,
+            ActivationMode::Quorum { threshold } => {
+                let (total, acked) = participants.participants().fold((0.0, 0.0), |(total, acked), p| {
+                    let ack = if acknowledged.contains(&p.entity_ref.key()) { p.weight } else { 0.0 };
+                    (total + p.weight, acked + ack)
+                });
+                total > 0.0 && acked >= threshold * total
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Tags
+// ============================================================================
+
+/// Normalize a tag: trimmed and lowercase, so `Urgent ` and `urgent` are
+/// the same tag
+pub fn normalize_tag(tag: &str) -> Option<String> {
+    let tag = tag.trim().to_lowercase();
+    (!tag.is_empty()).then_some(tag)
+}
+
+/// How a tag query combines its tags
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TagMatch {
+    /// Relationships with at least one of the tags
+    #[default]
+    Any,
+    /// Relationships with every tag
+    All,
+}
+
+impl TagMatch {
+    /// Check a tag set against the queried tags
+    ///
+    /// A query without tags, once blank ones are dropped, matches nothing
+    /// in either mode.
+    pub fn matches(&self, tags: &std::collections::BTreeSet<String>, queried: &[String]) -> bool {
+        let queried: Vec<String> = queried.iter().filter_map(|t| normalize_tag(t)).collect();
+        match self {
+            TagMatch::Any => queried.iter().any(|t| tags.contains(t)),
+            TagMatch::All => !queried.is_empty() && queried.iter().all(|t| tags.contains(t)),
+        }
+    }
+}
+
+/// Usage of one tag across a space
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TagStats {
+    /// The tag
+    pub tag: String,
+    /// Edges carrying it
+    pub edges: usize,
+    /// Hyperedges carrying it
+    pub hyperedges: usize,
+    /// Active relationships carrying it
+    pub active: usize,
+}
+
+// ============================================================================
+// Property History
+// ============================================================================
+
+/// One change to a relationship property
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PropertyChange {
+    /// Version of the property after the change, counting from 1
+    pub version: u64,
+    /// New value (`None` = removed)
+    pub value: Option<serde_json::Value>,
+    /// Who made the change, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub changed_by: Option<String>,
+    /// When the change was made
+    pub changed_at: DateTime<Utc>,
+}
+
+/// One rejection of an edge, kept when it is re-proposed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rejection {
+    /// Why it was rejected
+    pub reason: Option<String>,
+    /// Who rejected it
+    pub rejected_by: String,
+    /// When it was rejected
+    pub rejected_at: DateTime<Utc>,
+}
+
+/// One change of an edge's endpoints, kept on the edge
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Redirect {
+    /// Source before the change
+    pub old_source: EntityRef,
+    /// Target before the change
+    pub old_target: EntityRef,
+    /// Why the endpoints changed
+    pub reason: String,
+    /// Who changed them
+    pub redirected_by: String,
+    /// When they changed
+    pub redirected_at: DateTime<Utc>,
+}
+
+/// Limits on re-proposing rejected edges
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReproposalPolicy {
+    /// Times an edge may be re-proposed
+    pub max_reproposals: u32,
+    /// Days after a rejection before the edge may be re-proposed
+    pub cooling_off_days: u32,
+}
+
+impl Default for ReproposalPolicy {
+    fn default() -> Self {
+        Self {
+            max_reproposals: 2,
+            cooling_off_days: 7,
+        }
+    }
+}
+
+impl ReproposalPolicy {
+    /// Allow `max` re-proposals
+    pub fn with_max_reproposals(mut self, max: u32) -> Self {
+        self.max_reproposals = max;
+        self
+    }
+
+    /// Wait `days` after a rejection
+    pub fn with_cooling_off_days(mut self, days: u32) -> Self {
+        self.cooling_off_days = days;
+        self
+    }
+
+    /// Earliest time an edge rejected at `rejected_at` may be re-proposed
+    pub fn eligible_at(&self, rejected_at: DateTime<Utc>) -> DateTime<Utc> {
+        rejected_at + chrono::Duration::days(self.cooling_off_days.into())
+    }
+}
+
+/// One weight a participant held in a hyperedge
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WeightChange {
+    /// Participation weight from this point on
+    pub weight: f64,
+    /// Who set it
+    pub changed_by: String,
+    /// When it was set
+    pub changed_at: DateTime<Utc>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -658,4 +1277,128 @@ mod tests {
         assert!(matrix.contains(&person));
         assert!(matrix.contains(&org));
     }
+
+    #[test]
+    fn test_incidence_matrix_set_weight() {
+        let mut matrix = IncidenceMatrix::new();
+        let person = EntityRef::person(Uuid::now_v7());
+
+        matrix.add_participant(person.clone(), ParticipantRole::Member, 0.5);
+        let joined_at = matrix.get(&person).unwrap().joined_at;
+
+        assert_eq!(matrix.set_weight(&person, 1.5), Some(0.5));
+        let entry = matrix.get(&person).unwrap();
+        assert_eq!(entry.weight, 1.0);
+        assert_eq!(entry.joined_at, joined_at);
+
+        assert_eq!(matrix.set_weight(&EntityRef::person(Uuid::now_v7()), 0.3), None);
+    }
+
+    #[test]
+    fn test_entity_key_roundtrip() {
+        let id = Uuid::now_v7();
+        let key = EntityRef::person(id).with_version(2).key();
+        assert_eq!(key, EntityRef::person(id).with_cid("bafyreigdmqpykrgxyaxtlafqpqhzrb7qy2rh75nldvfd4tucqmqqme5yje").key());
+        assert_eq!(key.to_string().parse::<EntityKey>().unwrap(), key);
+
+        let custom = EntityRef::new(EntityType::Custom("ticket".to_string()), id).key();
+        assert_eq!(custom.to_string(), format!("custom/ticket:{}", id));
+        assert_eq!(custom.to_string().parse::<EntityKey>().unwrap(), custom);
+    }
+
+    #[test]
+    fn test_incidence_matrix_pinning_does_not_duplicate() {
+        let mut matrix = IncidenceMatrix::new();
+        let id = Uuid::now_v7();
+
+        matrix.add_participant(EntityRef::person(id), ParticipantRole::Member, 1.0);
+        matrix.add_participant(EntityRef::person(id).with_version(4), ParticipantRole::Member, 1.0);
+
+        assert_eq!(matrix.participant_count(), 1);
+        assert!(matrix.contains(&EntityRef::person(id)));
+    }
+
+    #[test]
+    fn test_incidence_matrix_groups() {
+        let mut matrix = IncidenceMatrix::new();
+        let (ann, bo, cy, dee) = (
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::person(Uuid::now_v7()),
+            EntityRef::person(Uuid::now_v7()),
+        );
+        for person in [&ann, &bo, &cy] {
+            matrix.add_participant(person.clone(), ParticipantRole::Contributor, 1.0);
+        }
+
+        assert!(matrix.add_group("engineering", ParticipantRole::Contributor, None));
+        assert!(matrix.add_group("frontend", ParticipantRole::Contributor, Some("engineering")));
+        assert!(matrix.add_group("backend", ParticipantRole::Leader, Some("engineering")));
+        assert!(!matrix.add_group("frontend", ParticipantRole::Member, None));
+        assert!(!matrix.add_group("qa", ParticipantRole::Member, Some("missing")));
+
+        assert!(matrix.assign_to_group(&ann, "frontend"));
+        assert!(matrix.assign_to_group(&bo, "frontend"));
+        assert!(matrix.assign_to_group(&cy, "backend"));
+        assert!(!matrix.assign_to_group(&dee, "backend"));
+
+        assert_eq!(matrix.group_members("frontend").len(), 2);
+        assert_eq!(matrix.group_members("engineering").len(), 3);
+        assert_eq!(matrix.subgroups("engineering").len(), 2);
+        assert_eq!(matrix.groups_with_role(&ParticipantRole::Leader)[0].name, "backend");
+        assert_eq!(matrix.groups_of(&ann)[0].name, "frontend");
+
+        // Role changes keep memberships; leaving the hyperedge drops them
+        matrix.set_role(&ann, ParticipantRole::Leader);
+        assert_eq!(matrix.groups_of(&ann).len(), 1);
+        matrix.remove_participant(&bo);
+        assert_eq!(matrix.group_members("frontend").len(), 1);
+
+        // Removing a group lifts its subgroups
+        matrix.remove_group("engineering");
+        assert_eq!(matrix.group("frontend").unwrap().parent, None);
+
+        let reloaded: IncidenceMatrix = serde_json::from_value(serde_json::to_value(&matrix).unwrap()).unwrap();
+        assert_eq!(reloaded.group_members("frontend")[0].entity_ref, ann);
+    }
+
+    #[test]
+    fn test_incidence_matrix_upcasts_display_keys() {
+        let id = Uuid::now_v7();
+        let unpinned = EntityRef::person(id);
+        let pinned = EntityRef::person(id).with_version(4);
+        let earlier = Utc::now() - chrono::Duration::days(7);
+
+        // Legacy format: keyed by EntityRef display, same entity twice
+        let legacy = serde_json::json!({
+            "participants": {
+                unpinned.to_string(): {
+                    "entity_ref": unpinned,
+                    "role": "Member",
+                    "weight": 0.5,
+                    "joined_at": earlier,
+                },
+                pinned.to_string(): {
+                    "entity_ref": pinned,
+                    "role": "Leader",
+                    "weight": 0.9,
+                    "joined_at": Utc::now(),
+                },
+            }
+        });
+
+        let matrix: IncidenceMatrix = serde_json::from_value(legacy).unwrap();
+        assert_eq!(matrix.participant_count(), 1);
+
+        let entry = matrix.get(&unpinned).unwrap();
+        assert_eq!(entry.role, ParticipantRole::Leader);
+        assert_eq!(entry.joined_at, earlier);
+
+        // Round-trips under the new keys
+        let json = serde_json::to_value(&matrix).unwrap();
+        assert!(json["participants"].get(id.to_string()).is_none());
+        assert!(json["participants"].get(format!("person:{}", id)).is_some());
+        let reloaded: IncidenceMatrix = serde_json::from_value(json).unwrap();
+        assert_eq!(reloaded.participant_count(), 1);
+    }
 }